@@ -17,6 +17,8 @@ pub struct Model {
     pub country: String,
     pub customer_account_id: Option<i32>,
     pub supplier_account_id: Option<i32>,
+    pub vat_id: String,
+    pub currency: String,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -37,6 +39,8 @@ pub enum Relation {
         on_delete = "Restrict"
     )]
     LedgerAccounts1,
+    #[sea_orm(has_many = "super::price_list_customers::Entity")]
+    PriceListCustomers,
     #[sea_orm(has_many = "super::purchase_invoices::Entity")]
     PurchaseInvoices,
     #[sea_orm(has_many = "super::purchase_orders::Entity")]
@@ -47,6 +51,14 @@ pub enum Relation {
     SaleOrders,
     #[sea_orm(has_many = "super::stocks::Entity")]
     Stocks,
+    #[sea_orm(has_many = "super::supplier_prices::Entity")]
+    SupplierPrices,
+}
+
+impl Related<super::price_list_customers::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PriceListCustomers.def()
+    }
 }
 
 impl Related<super::purchase_invoices::Entity> for Entity {
@@ -79,4 +91,10 @@ impl Related<super::stocks::Entity> for Entity {
     }
 }
 
+impl Related<super::supplier_prices::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SupplierPrices.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -3,20 +3,49 @@
 pub mod prelude;
 
 pub mod contacts;
+pub mod inventory_count_entries;
+pub mod inventory_count_lines;
+pub mod inventory_counts;
 pub mod ledger_accounts;
 pub mod ledger_transactions;
+pub mod lots;
+pub mod number_sequences;
+pub mod price_list_customers;
+pub mod price_list_items;
+pub mod price_lists;
+pub mod product_attribute_values;
+pub mod product_attributes;
+pub mod product_categories;
 pub mod products;
+pub mod purchase_credit_note_line_taxes;
+pub mod purchase_credit_note_lines;
+pub mod purchase_credit_notes;
+pub mod purchase_invoice_line_taxes;
 pub mod purchase_invoice_lines;
 pub mod purchase_invoices;
 pub mod purchase_order_lines;
 pub mod purchase_orders;
+pub mod reorder_rules;
+pub mod sale_credit_note_line_taxes;
+pub mod sale_credit_note_lines;
+pub mod sale_credit_notes;
+pub mod sale_invoice_line_taxes;
 pub mod sale_invoice_lines;
 pub mod sale_invoices;
 pub mod sale_order_lines;
 pub mod sale_orders;
 pub mod sea_orm_active_enums;
+pub mod stock_move_lots;
 pub mod stock_moves;
+pub mod stock_reservations;
+pub mod stock_valuation_layers;
 pub mod stocks;
+pub mod supplier_prices;
+pub mod tax_components;
+pub mod tax_rates;
+pub mod tax_rules;
 pub mod taxes;
+pub mod units;
 pub mod users;
+pub mod variant_attribute_values;
 pub mod variants;
@@ -1,19 +1,48 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
 
 pub use super::contacts::Entity as Contacts;
+pub use super::inventory_count_entries::Entity as InventoryCountEntries;
+pub use super::inventory_count_lines::Entity as InventoryCountLines;
+pub use super::inventory_counts::Entity as InventoryCounts;
 pub use super::ledger_accounts::Entity as LedgerAccounts;
 pub use super::ledger_transactions::Entity as LedgerTransactions;
+pub use super::lots::Entity as Lots;
+pub use super::number_sequences::Entity as NumberSequences;
+pub use super::price_list_customers::Entity as PriceListCustomers;
+pub use super::price_list_items::Entity as PriceListItems;
+pub use super::price_lists::Entity as PriceLists;
+pub use super::product_attribute_values::Entity as ProductAttributeValues;
+pub use super::product_attributes::Entity as ProductAttributes;
+pub use super::product_categories::Entity as ProductCategories;
 pub use super::products::Entity as Products;
+pub use super::purchase_credit_note_line_taxes::Entity as PurchaseCreditNoteLineTaxes;
+pub use super::purchase_credit_note_lines::Entity as PurchaseCreditNoteLines;
+pub use super::purchase_credit_notes::Entity as PurchaseCreditNotes;
+pub use super::purchase_invoice_line_taxes::Entity as PurchaseInvoiceLineTaxes;
 pub use super::purchase_invoice_lines::Entity as PurchaseInvoiceLines;
 pub use super::purchase_invoices::Entity as PurchaseInvoices;
 pub use super::purchase_order_lines::Entity as PurchaseOrderLines;
 pub use super::purchase_orders::Entity as PurchaseOrders;
+pub use super::reorder_rules::Entity as ReorderRules;
+pub use super::sale_credit_note_line_taxes::Entity as SaleCreditNoteLineTaxes;
+pub use super::sale_credit_note_lines::Entity as SaleCreditNoteLines;
+pub use super::sale_credit_notes::Entity as SaleCreditNotes;
+pub use super::sale_invoice_line_taxes::Entity as SaleInvoiceLineTaxes;
 pub use super::sale_invoice_lines::Entity as SaleInvoiceLines;
 pub use super::sale_invoices::Entity as SaleInvoices;
 pub use super::sale_order_lines::Entity as SaleOrderLines;
 pub use super::sale_orders::Entity as SaleOrders;
+pub use super::stock_move_lots::Entity as StockMoveLots;
 pub use super::stock_moves::Entity as StockMoves;
+pub use super::stock_reservations::Entity as StockReservations;
+pub use super::stock_valuation_layers::Entity as StockValuationLayers;
 pub use super::stocks::Entity as Stocks;
+pub use super::supplier_prices::Entity as SupplierPrices;
+pub use super::tax_components::Entity as TaxComponents;
+pub use super::tax_rates::Entity as TaxRates;
+pub use super::tax_rules::Entity as TaxRules;
 pub use super::taxes::Entity as Taxes;
+pub use super::units::Entity as Units;
 pub use super::users::Entity as Users;
+pub use super::variant_attribute_values::Entity as VariantAttributeValues;
 pub use super::variants::Entity as Variants;
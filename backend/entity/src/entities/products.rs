@@ -1,5 +1,6 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
 
+use super::sea_orm_active_enums::{CostingMethod, ProductType, Tracking};
 use sea_orm::entity::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
@@ -8,14 +9,41 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub name: String,
+    pub tax_category: String,
+    pub category_id: Option<i32>,
+    pub product_type: ProductType,
+    pub costing_method: CostingMethod,
+    pub tracking: Tracking,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::product_categories::Entity",
+        from = "Column::CategoryId",
+        to = "super::product_categories::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Restrict"
+    )]
+    ProductCategories,
+    #[sea_orm(has_many = "super::product_attributes::Entity")]
+    ProductAttributes,
     #[sea_orm(has_many = "super::variants::Entity")]
     Variants,
 }
 
+impl Related<super::product_attributes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProductAttributes.def()
+    }
+}
+
+impl Related<super::product_categories::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProductCategories.def()
+    }
+}
+
 impl Related<super::variants::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Variants.def()
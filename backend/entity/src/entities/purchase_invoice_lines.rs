@@ -17,10 +17,22 @@ pub struct Model {
     pub transaction_id: Option<i32>,
     pub tax_id: Option<i32>,
     pub tax_transaction_id: Option<i32>,
+    #[sea_orm(column_type = "Double")]
+    pub discount_percent: f64,
+    pub discount_amount: i32,
+    pub discount_transaction_id: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::ledger_transactions::Entity",
+        from = "Column::DiscountTransactionId",
+        to = "super::ledger_transactions::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Restrict"
+    )]
+    LedgerTransactions3,
     #[sea_orm(
         belongs_to = "super::ledger_transactions::Entity",
         from = "Column::TaxTransactionId",
@@ -3,7 +3,7 @@
 use super::sea_orm_active_enums::PurchaseInvoiceStatus;
 use sea_orm::entity::prelude::*;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
 #[sea_orm(table_name = "purchase_invoices")]
 pub struct Model {
     #[sea_orm(primary_key)]
@@ -11,8 +11,15 @@ pub struct Model {
     pub reference: String,
     pub date: Date,
     pub status: PurchaseInvoiceStatus,
-    pub order_id: i32,
-    pub supplier_id: i32,
+    pub order_id: Option<i32>,
+    pub supplier_id: Option<i32>,
+    pub needs_review: bool,
+    #[sea_orm(column_type = "Text")]
+    pub review_note: String,
+    pub supplier_reference: String,
+    #[sea_orm(column_type = "Double")]
+    pub discount_percent: f64,
+    pub discount_amount: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -25,6 +32,8 @@ pub enum Relation {
         on_delete = "Restrict"
     )]
     Contacts,
+    #[sea_orm(has_many = "super::purchase_credit_notes::Entity")]
+    PurchaseCreditNotes,
     #[sea_orm(has_many = "super::purchase_invoice_lines::Entity")]
     PurchaseInvoiceLines,
     #[sea_orm(
@@ -43,6 +52,12 @@ impl Related<super::contacts::Entity> for Entity {
     }
 }
 
+impl Related<super::purchase_credit_notes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PurchaseCreditNotes.def()
+    }
+}
+
 impl Related<super::purchase_invoice_lines::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::PurchaseInvoiceLines.def()
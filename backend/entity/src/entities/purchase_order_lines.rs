@@ -15,6 +15,13 @@ pub struct Model {
     pub order_id: i32,
     pub variant_id: Option<i32>,
     pub move_id: Option<i32>,
+    #[sea_orm(column_type = "Double")]
+    pub discount_percent: f64,
+    pub discount_amount: i32,
+    pub unit_id: Option<i32>,
+    #[sea_orm(unique)]
+    pub sale_line_id: Option<i32>,
+    pub tax_id: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -27,6 +34,14 @@ pub enum Relation {
         on_delete = "Restrict"
     )]
     PurchaseOrders,
+    #[sea_orm(
+        belongs_to = "super::sale_order_lines::Entity",
+        from = "Column::SaleLineId",
+        to = "super::sale_order_lines::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    SaleOrderLines,
     #[sea_orm(
         belongs_to = "super::stock_moves::Entity",
         from = "Column::MoveId",
@@ -35,6 +50,22 @@ pub enum Relation {
         on_delete = "Restrict"
     )]
     StockMoves,
+    #[sea_orm(
+        belongs_to = "super::taxes::Entity",
+        from = "Column::TaxId",
+        to = "super::taxes::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Restrict"
+    )]
+    Taxes,
+    #[sea_orm(
+        belongs_to = "super::units::Entity",
+        from = "Column::UnitId",
+        to = "super::units::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Restrict"
+    )]
+    Units,
     #[sea_orm(
         belongs_to = "super::variants::Entity",
         from = "Column::VariantId",
@@ -51,12 +82,30 @@ impl Related<super::purchase_orders::Entity> for Entity {
     }
 }
 
+impl Related<super::sale_order_lines::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SaleOrderLines.def()
+    }
+}
+
 impl Related<super::stock_moves::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::StockMoves.def()
     }
 }
 
+impl Related<super::taxes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Taxes.def()
+    }
+}
+
+impl Related<super::units::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Units.def()
+    }
+}
+
 impl Related<super::variants::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Variants.def()
@@ -3,7 +3,7 @@
 use super::sea_orm_active_enums::SaleInvoiceStatus;
 use sea_orm::entity::prelude::*;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
 #[sea_orm(table_name = "sale_invoices")]
 pub struct Model {
     #[sea_orm(primary_key)]
@@ -13,6 +13,9 @@ pub struct Model {
     pub status: SaleInvoiceStatus,
     pub order_id: i32,
     pub customer_id: i32,
+    #[sea_orm(column_type = "Double")]
+    pub discount_percent: f64,
+    pub discount_amount: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -25,6 +28,8 @@ pub enum Relation {
         on_delete = "Restrict"
     )]
     Contacts,
+    #[sea_orm(has_many = "super::sale_credit_notes::Entity")]
+    SaleCreditNotes,
     #[sea_orm(has_many = "super::sale_invoice_lines::Entity")]
     SaleInvoiceLines,
     #[sea_orm(
@@ -43,6 +48,12 @@ impl Related<super::contacts::Entity> for Entity {
     }
 }
 
+impl Related<super::sale_credit_notes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SaleCreditNotes.def()
+    }
+}
+
 impl Related<super::sale_invoice_lines::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::SaleInvoiceLines.def()
@@ -1,5 +1,6 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
 
+use super::sea_orm_active_enums::SaleOrderLineRoute;
 use sea_orm::entity::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
@@ -15,10 +16,18 @@ pub struct Model {
     pub order_id: i32,
     pub variant_id: Option<i32>,
     pub move_id: Option<i32>,
+    #[sea_orm(column_type = "Double")]
+    pub discount_percent: f64,
+    pub discount_amount: i32,
+    pub unit_id: Option<i32>,
+    pub route: SaleOrderLineRoute,
+    pub tax_id: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_one = "super::purchase_order_lines::Entity")]
+    PurchaseOrderLines,
     #[sea_orm(
         belongs_to = "super::sale_orders::Entity",
         from = "Column::OrderId",
@@ -35,6 +44,24 @@ pub enum Relation {
         on_delete = "Restrict"
     )]
     StockMoves,
+    #[sea_orm(has_one = "super::stock_reservations::Entity")]
+    StockReservations,
+    #[sea_orm(
+        belongs_to = "super::taxes::Entity",
+        from = "Column::TaxId",
+        to = "super::taxes::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Restrict"
+    )]
+    Taxes,
+    #[sea_orm(
+        belongs_to = "super::units::Entity",
+        from = "Column::UnitId",
+        to = "super::units::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Restrict"
+    )]
+    Units,
     #[sea_orm(
         belongs_to = "super::variants::Entity",
         from = "Column::VariantId",
@@ -45,6 +72,12 @@ pub enum Relation {
     Variants,
 }
 
+impl Related<super::purchase_order_lines::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PurchaseOrderLines.def()
+    }
+}
+
 impl Related<super::sale_orders::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::SaleOrders.def()
@@ -57,6 +90,24 @@ impl Related<super::stock_moves::Entity> for Entity {
     }
 }
 
+impl Related<super::stock_reservations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::StockReservations.def()
+    }
+}
+
+impl Related<super::taxes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Taxes.def()
+    }
+}
+
+impl Related<super::units::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Units.def()
+    }
+}
+
 impl Related<super::variants::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Variants.def()
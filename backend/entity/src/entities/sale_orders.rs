@@ -3,7 +3,7 @@
 use super::sea_orm_active_enums::SaleOrderStatus;
 use sea_orm::entity::prelude::*;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
 #[sea_orm(table_name = "sale_orders")]
 pub struct Model {
     #[sea_orm(primary_key)]
@@ -14,6 +14,9 @@ pub struct Model {
     pub customer_id: i32,
     pub source_stock_id: i32,
     pub target_stock_id: i32,
+    #[sea_orm(column_type = "Double")]
+    pub discount_percent: f64,
+    pub discount_amount: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -2,6 +2,52 @@
 
 use sea_orm::entity::prelude::*;
 
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "costing_method")]
+pub enum CostingMethod {
+    #[sea_orm(string_value = "fifo")]
+    Fifo,
+    #[sea_orm(string_value = "average")]
+    Average,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum DocumentType {
+    #[sea_orm(string_value = "sale_order")]
+    SaleOrder,
+    #[sea_orm(string_value = "sale_invoice")]
+    SaleInvoice,
+    #[sea_orm(string_value = "sale_credit_note")]
+    SaleCreditNote,
+    #[sea_orm(string_value = "purchase_order")]
+    PurchaseOrder,
+    #[sea_orm(string_value = "purchase_invoice")]
+    PurchaseInvoice,
+    #[sea_orm(string_value = "purchase_credit_note")]
+    PurchaseCreditNote,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "inventory_count_status"
+)]
+pub enum InventoryCountStatus {
+    #[sea_orm(string_value = "open")]
+    Open,
+    #[sea_orm(string_value = "approved")]
+    Approved,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "product_type")]
+pub enum ProductType {
+    #[sea_orm(string_value = "stockable")]
+    Stockable,
+    #[sea_orm(string_value = "consumable")]
+    Consumable,
+    #[sea_orm(string_value = "service")]
+    Service,
+}
 #[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
 #[sea_orm(
     rs_type = "String",
@@ -23,6 +69,8 @@ pub enum PurchaseInvoiceStatus {
 pub enum PurchaseOrderStatus {
     #[sea_orm(string_value = "draft")]
     Draft,
+    #[sea_orm(string_value = "order")]
+    Order,
     #[sea_orm(string_value = "finished")]
     Finished,
 }
@@ -43,6 +91,18 @@ pub enum SaleInvoiceStatus {
     Finished,
 }
 #[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "sale_order_line_route"
+)]
+pub enum SaleOrderLineRoute {
+    #[sea_orm(string_value = "stock")]
+    Stock,
+    #[sea_orm(string_value = "drop_ship")]
+    DropShip,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "sale_order_status")]
 pub enum SaleOrderStatus {
     #[sea_orm(string_value = "draft")]
@@ -53,4 +113,48 @@ pub enum SaleOrderStatus {
     Order,
     #[sea_orm(string_value = "finished")]
     Finished,
+    #[sea_orm(string_value = "cancelled")]
+    Cancelled,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "stock_kind")]
+pub enum StockKind {
+    #[sea_orm(string_value = "internal")]
+    Internal,
+    #[sea_orm(string_value = "supplier")]
+    Supplier,
+    #[sea_orm(string_value = "customer")]
+    Customer,
+    #[sea_orm(string_value = "inventory_loss")]
+    InventoryLoss,
+    #[sea_orm(string_value = "production")]
+    Production,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "tax_direction")]
+pub enum TaxDirection {
+    #[sea_orm(string_value = "sale")]
+    Sale,
+    #[sea_orm(string_value = "purchase")]
+    Purchase,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "tax_region")]
+pub enum TaxRegion {
+    #[sea_orm(string_value = "domestic")]
+    Domestic,
+    #[sea_orm(string_value = "eu")]
+    Eu,
+    #[sea_orm(string_value = "non_eu")]
+    NonEu,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "tracking")]
+pub enum Tracking {
+    #[sea_orm(string_value = "none")]
+    None,
+    #[sea_orm(string_value = "lot")]
+    Lot,
+    #[sea_orm(string_value = "serial")]
+    Serial,
 }
@@ -16,10 +16,16 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::inventory_count_lines::Entity")]
+    InventoryCountLines,
     #[sea_orm(has_many = "super::purchase_order_lines::Entity")]
     PurchaseOrderLines,
     #[sea_orm(has_many = "super::sale_order_lines::Entity")]
     SaleOrderLines,
+    #[sea_orm(has_many = "super::stock_move_lots::Entity")]
+    StockMoveLots,
+    #[sea_orm(has_one = "super::stock_valuation_layers::Entity")]
+    StockValuationLayers,
     #[sea_orm(
         belongs_to = "super::stocks::Entity",
         from = "Column::SourceStockId",
@@ -46,6 +52,12 @@ pub enum Relation {
     Variants,
 }
 
+impl Related<super::inventory_count_lines::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::InventoryCountLines.def()
+    }
+}
+
 impl Related<super::purchase_order_lines::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::PurchaseOrderLines.def()
@@ -58,6 +70,18 @@ impl Related<super::sale_order_lines::Entity> for Entity {
     }
 }
 
+impl Related<super::stock_move_lots::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::StockMoveLots.def()
+    }
+}
+
+impl Related<super::stock_valuation_layers::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::StockValuationLayers.def()
+    }
+}
+
 impl Related<super::variants::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Variants.def()
@@ -1,5 +1,6 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
 
+use super::sea_orm_active_enums::StockKind;
 use sea_orm::entity::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
@@ -10,6 +11,9 @@ pub struct Model {
     pub name: String,
     pub is_own: bool,
     pub owner_id: Option<i32>,
+    pub kind: StockKind,
+    pub parent_id: Option<i32>,
+    pub consignment: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -22,6 +26,20 @@ pub enum Relation {
         on_delete = "SetNull"
     )]
     Contacts,
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::ParentId",
+        to = "Column::Id",
+        on_update = "NoAction",
+        on_delete = "Restrict"
+    )]
+    SelfRef,
+    #[sea_orm(has_many = "super::inventory_counts::Entity")]
+    InventoryCounts,
+    #[sea_orm(has_many = "super::reorder_rules::Entity")]
+    ReorderRules,
+    #[sea_orm(has_many = "super::stock_reservations::Entity")]
+    StockReservations,
 }
 
 impl Related<super::contacts::Entity> for Entity {
@@ -30,4 +48,22 @@ impl Related<super::contacts::Entity> for Entity {
     }
 }
 
+impl Related<super::inventory_counts::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::InventoryCounts.def()
+    }
+}
+
+impl Related<super::reorder_rules::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ReorderRules.def()
+    }
+}
+
+impl Related<super::stock_reservations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::StockReservations.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
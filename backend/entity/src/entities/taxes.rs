@@ -12,6 +12,9 @@ pub struct Model {
     #[sea_orm(column_type = "Double")]
     pub rate: f64,
     pub account_id: i32,
+    pub category: String,
+    #[sea_orm(column_type = "Text")]
+    pub note: String,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -24,10 +27,20 @@ pub enum Relation {
         on_delete = "Restrict"
     )]
     LedgerAccounts,
+    #[sea_orm(has_many = "super::product_categories::Entity")]
+    ProductCategories,
     #[sea_orm(has_many = "super::purchase_invoice_lines::Entity")]
     PurchaseInvoiceLines,
+    #[sea_orm(has_many = "super::purchase_order_lines::Entity")]
+    PurchaseOrderLines,
     #[sea_orm(has_many = "super::sale_invoice_lines::Entity")]
     SaleInvoiceLines,
+    #[sea_orm(has_many = "super::sale_order_lines::Entity")]
+    SaleOrderLines,
+    #[sea_orm(has_many = "super::tax_rates::Entity")]
+    TaxRates,
+    #[sea_orm(has_many = "super::tax_rules::Entity")]
+    TaxRules,
 }
 
 impl Related<super::ledger_accounts::Entity> for Entity {
@@ -36,16 +49,46 @@ impl Related<super::ledger_accounts::Entity> for Entity {
     }
 }
 
+impl Related<super::product_categories::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProductCategories.def()
+    }
+}
+
 impl Related<super::purchase_invoice_lines::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::PurchaseInvoiceLines.def()
     }
 }
 
+impl Related<super::purchase_order_lines::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PurchaseOrderLines.def()
+    }
+}
+
 impl Related<super::sale_invoice_lines::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::SaleInvoiceLines.def()
     }
 }
 
+impl Related<super::sale_order_lines::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SaleOrderLines.def()
+    }
+}
+
+impl Related<super::tax_rates::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TaxRates.def()
+    }
+}
+
+impl Related<super::tax_rules::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TaxRules.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
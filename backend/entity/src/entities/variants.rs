@@ -11,6 +11,11 @@ pub struct Model {
     pub sale_price: i32,
     pub purchase_price: i32,
     pub product_id: i32,
+    #[sea_orm(unique)]
+    pub sku: Option<String>,
+    #[sea_orm(unique)]
+    pub gtin: Option<String>,
+    pub unit_id: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -23,16 +28,40 @@ pub enum Relation {
         on_delete = "Restrict"
     )]
     Products,
+    #[sea_orm(has_many = "super::inventory_count_lines::Entity")]
+    InventoryCountLines,
+    #[sea_orm(has_many = "super::lots::Entity")]
+    Lots,
+    #[sea_orm(has_many = "super::price_list_items::Entity")]
+    PriceListItems,
     #[sea_orm(has_many = "super::purchase_invoice_lines::Entity")]
     PurchaseInvoiceLines,
     #[sea_orm(has_many = "super::purchase_order_lines::Entity")]
     PurchaseOrderLines,
+    #[sea_orm(has_many = "super::reorder_rules::Entity")]
+    ReorderRules,
     #[sea_orm(has_many = "super::sale_invoice_lines::Entity")]
     SaleInvoiceLines,
     #[sea_orm(has_many = "super::sale_order_lines::Entity")]
     SaleOrderLines,
     #[sea_orm(has_many = "super::stock_moves::Entity")]
     StockMoves,
+    #[sea_orm(has_many = "super::stock_reservations::Entity")]
+    StockReservations,
+    #[sea_orm(has_many = "super::stock_valuation_layers::Entity")]
+    StockValuationLayers,
+    #[sea_orm(has_many = "super::supplier_prices::Entity")]
+    SupplierPrices,
+    #[sea_orm(
+        belongs_to = "super::units::Entity",
+        from = "Column::UnitId",
+        to = "super::units::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Restrict"
+    )]
+    Units,
+    #[sea_orm(has_many = "super::variant_attribute_values::Entity")]
+    VariantAttributeValues,
 }
 
 impl Related<super::products::Entity> for Entity {
@@ -41,6 +70,24 @@ impl Related<super::products::Entity> for Entity {
     }
 }
 
+impl Related<super::inventory_count_lines::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::InventoryCountLines.def()
+    }
+}
+
+impl Related<super::lots::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Lots.def()
+    }
+}
+
+impl Related<super::price_list_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PriceListItems.def()
+    }
+}
+
 impl Related<super::purchase_invoice_lines::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::PurchaseInvoiceLines.def()
@@ -53,6 +100,12 @@ impl Related<super::purchase_order_lines::Entity> for Entity {
     }
 }
 
+impl Related<super::reorder_rules::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ReorderRules.def()
+    }
+}
+
 impl Related<super::sale_invoice_lines::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::SaleInvoiceLines.def()
@@ -71,4 +124,34 @@ impl Related<super::stock_moves::Entity> for Entity {
     }
 }
 
+impl Related<super::stock_reservations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::StockReservations.def()
+    }
+}
+
+impl Related<super::stock_valuation_layers::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::StockValuationLayers.def()
+    }
+}
+
+impl Related<super::supplier_prices::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SupplierPrices.def()
+    }
+}
+
+impl Related<super::units::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Units.def()
+    }
+}
+
+impl Related<super::variant_attribute_values::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::VariantAttributeValues.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
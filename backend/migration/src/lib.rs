@@ -1,12 +1,64 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20220101_000001_init;
+mod m20261019_000001_einvoice_import;
+mod m20261020_000001_number_sequences;
+mod m20261021_000001_credit_notes;
+mod m20261022_000001_tax_rules;
+mod m20261023_000001_tax_rates;
+mod m20261024_000001_tax_groups;
+mod m20261025_000001_discounts;
+mod m20261026_000001_price_lists;
+mod m20261027_000001_supplier_prices;
+mod m20261028_000001_attributes;
+mod m20261029_000001_units;
+mod m20261030_000001_product_categories;
+mod m20261031_000001_product_types;
+mod m20261101_000001_inventory_valuation;
+mod m20261102_000001_stock_reservations;
+mod m20261103_000001_lots;
+mod m20261104_000001_stock_locations;
+mod m20261105_000001_inventory_counts;
+mod m20261106_000001_reorder_rules;
+mod m20261107_000001_drop_shipping;
+mod m20261108_000001_consignment;
+mod m20261109_000001_order_line_taxes;
+mod m20261110_000001_discount_accounts;
+mod m20261111_000001_inventory_count_lots;
+mod m20261112_000001_purchase_order_line_taxes;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20220101_000001_init::Migration)]
+        vec![
+            Box::new(m20220101_000001_init::Migration),
+            Box::new(m20261019_000001_einvoice_import::Migration),
+            Box::new(m20261020_000001_number_sequences::Migration),
+            Box::new(m20261021_000001_credit_notes::Migration),
+            Box::new(m20261022_000001_tax_rules::Migration),
+            Box::new(m20261023_000001_tax_rates::Migration),
+            Box::new(m20261024_000001_tax_groups::Migration),
+            Box::new(m20261025_000001_discounts::Migration),
+            Box::new(m20261026_000001_price_lists::Migration),
+            Box::new(m20261027_000001_supplier_prices::Migration),
+            Box::new(m20261028_000001_attributes::Migration),
+            Box::new(m20261029_000001_units::Migration),
+            Box::new(m20261030_000001_product_categories::Migration),
+            Box::new(m20261031_000001_product_types::Migration),
+            Box::new(m20261101_000001_inventory_valuation::Migration),
+            Box::new(m20261102_000001_stock_reservations::Migration),
+            Box::new(m20261103_000001_lots::Migration),
+            Box::new(m20261104_000001_stock_locations::Migration),
+            Box::new(m20261105_000001_inventory_counts::Migration),
+            Box::new(m20261106_000001_reorder_rules::Migration),
+            Box::new(m20261107_000001_drop_shipping::Migration),
+            Box::new(m20261108_000001_consignment::Migration),
+            Box::new(m20261109_000001_order_line_taxes::Migration),
+            Box::new(m20261110_000001_discount_accounts::Migration),
+            Box::new(m20261111_000001_inventory_count_lots::Migration),
+            Box::new(m20261112_000001_purchase_order_line_taxes::Migration),
+        ]
     }
 }
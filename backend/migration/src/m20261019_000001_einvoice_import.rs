@@ -0,0 +1,74 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Contacts::Table)
+                    .add_column(string(Contacts::VatId).default(""))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Imported invoices are stored even if their supplier or order could
+        // not be matched, so both references have to be optional.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PurchaseInvoices::Table)
+                    .modify_column(integer_null(PurchaseInvoices::OrderId))
+                    .modify_column(integer_null(PurchaseInvoices::SupplierId))
+                    .add_column(boolean(PurchaseInvoices::NeedsReview).default(false))
+                    .add_column(text(PurchaseInvoices::ReviewNote).default(""))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PurchaseInvoices::Table)
+                    .drop_column(PurchaseInvoices::ReviewNote)
+                    .drop_column(PurchaseInvoices::NeedsReview)
+                    .modify_column(integer(PurchaseInvoices::SupplierId))
+                    .modify_column(integer(PurchaseInvoices::OrderId))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Contacts::Table)
+                    .drop_column(Contacts::VatId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Contacts {
+    Table,
+    VatId,
+}
+
+#[derive(DeriveIden)]
+enum PurchaseInvoices {
+    Table,
+    OrderId,
+    SupplierId,
+    NeedsReview,
+    ReviewNote,
+}
@@ -0,0 +1,132 @@
+use sea_orm_migration::{
+    prelude::{extension::postgres::Type, *},
+    schema::*,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum("document_type")
+                    .values(vec![
+                        "sale_order",
+                        "sale_invoice",
+                        "purchase_order",
+                        "purchase_invoice",
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(NumberSequences::Table)
+                    .if_not_exists()
+                    .col(pk_auto(NumberSequences::Id))
+                    .col(custom(NumberSequences::DocumentType, "document_type").unique_key())
+                    .col(string(NumberSequences::Pattern))
+                    .col(boolean(NumberSequences::Gapless))
+                    .col(string(NumberSequences::Period).default(""))
+                    .col(integer(NumberSequences::NextNumber).default(1))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Orders may skip numbers when a create fails, invoices must not.
+        let sequences = [
+            ("sale_order", "SO-{YYYY}-{00000}", false),
+            ("sale_invoice", "INV-{YYYY}-{00000}", true),
+            ("purchase_order", "PO-{YYYY}-{00000}", false),
+            ("purchase_invoice", "PI-{YYYY}-{00000}", true),
+        ];
+        for (document_type, pattern, gapless) in sequences {
+            manager
+                .exec_stmt(
+                    Query::insert()
+                        .into_table(NumberSequences::Table)
+                        .columns([
+                            NumberSequences::DocumentType,
+                            NumberSequences::Pattern,
+                            NumberSequences::Gapless,
+                        ])
+                        .values_panic([
+                            Expr::val(document_type).as_enum(Alias::new("document_type")),
+                            pattern.into(),
+                            gapless.into(),
+                        ])
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // The reference of purchase invoices becomes our own number, the one
+        // printed by the supplier moves to its own column.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PurchaseInvoices::Table)
+                    .add_column(string(PurchaseInvoices::SupplierReference).default(""))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(PurchaseInvoices::Table)
+                    .value(
+                        PurchaseInvoices::SupplierReference,
+                        Expr::col(PurchaseInvoices::Reference),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PurchaseInvoices::Table)
+                    .drop_column(PurchaseInvoices::SupplierReference)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(NumberSequences::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(Alias::new("document_type")).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum NumberSequences {
+    Table,
+    Id,
+    DocumentType,
+    Pattern,
+    Gapless,
+    Period,
+    NextNumber,
+}
+
+#[derive(DeriveIden)]
+enum PurchaseInvoices {
+    Table,
+    Reference,
+    SupplierReference,
+}
@@ -0,0 +1,267 @@
+use sea_orm_migration::{
+    prelude::{extension::postgres::Type, *},
+    schema::*,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Postgres can't use an enum value in the transaction that added it,
+        // and all pending migrations run in one transaction. With a plain
+        // string column new document types can be seeded right away.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(NumberSequences::Table)
+                    .modify_column(string(NumberSequences::DocumentType))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(Alias::new("document_type")).to_owned())
+            .await?;
+
+        let sequences = [
+            ("sale_credit_note", "CN-{YYYY}-{00000}"),
+            ("purchase_credit_note", "PCN-{YYYY}-{00000}"),
+        ];
+        for (document_type, pattern) in sequences {
+            manager
+                .exec_stmt(
+                    Query::insert()
+                        .into_table(NumberSequences::Table)
+                        .columns([
+                            NumberSequences::DocumentType,
+                            NumberSequences::Pattern,
+                            NumberSequences::Gapless,
+                        ])
+                        .values_panic([document_type.into(), pattern.into(), true.into()])
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(SaleCreditNotes::Table)
+                    .if_not_exists()
+                    .col(pk_auto(SaleCreditNotes::Id))
+                    .col(string(SaleCreditNotes::Reference))
+                    .col(date(SaleCreditNotes::Date))
+                    .col(integer(SaleCreditNotes::InvoiceId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_sale_credit_notes_invoice_id")
+                            .from("sale_credit_notes", "invoice_id")
+                            .to("sale_invoices", "id")
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(text(SaleCreditNotes::Reason).default(""))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(credit_note_lines(
+                "sale_credit_note_lines",
+                "sale_credit_notes",
+                "sale_invoice_lines",
+            ))
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PurchaseCreditNotes::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PurchaseCreditNotes::Id))
+                    .col(string(PurchaseCreditNotes::Reference))
+                    .col(date(PurchaseCreditNotes::Date))
+                    .col(integer(PurchaseCreditNotes::InvoiceId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_purchase_credit_notes_invoice_id")
+                            .from("purchase_credit_notes", "invoice_id")
+                            .to("purchase_invoices", "id")
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(text(PurchaseCreditNotes::Reason).default(""))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(credit_note_lines(
+                "purchase_credit_note_lines",
+                "purchase_credit_notes",
+                "purchase_invoice_lines",
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in [
+            "purchase_credit_note_lines",
+            "purchase_credit_notes",
+            "sale_credit_note_lines",
+            "sale_credit_notes",
+        ] {
+            manager
+                .drop_table(Table::drop().table(Alias::new(table)).to_owned())
+                .await?;
+        }
+
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(NumberSequences::Table)
+                    .and_where(
+                        Expr::col(NumberSequences::DocumentType)
+                            .is_in(["sale_credit_note", "purchase_credit_note"]),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum("document_type")
+                    .values(vec![
+                        "sale_order",
+                        "sale_invoice",
+                        "purchase_order",
+                        "purchase_invoice",
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        // There is no implicit cast from text to an enum.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE number_sequences ALTER COLUMN document_type \
+                 TYPE document_type USING document_type::document_type",
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// The lines of sale and purchase credit notes only differ in the tables
+/// they point to.
+fn credit_note_lines(table: &str, credit_notes: &str, invoice_lines: &str) -> TableCreateStatement {
+    let foreign_key = |column: &str, target: &str, action: ForeignKeyAction| {
+        ForeignKey::create()
+            .name(format!("rel_{table}_{column}"))
+            .from(Alias::new(table), Alias::new(column))
+            .to(Alias::new(target), Alias::new("id"))
+            .on_delete(action)
+            .to_owned()
+    };
+
+    Table::create()
+        .table(Alias::new(table))
+        .if_not_exists()
+        .col(pk_auto(CreditNoteLines::Id))
+        .col(integer(CreditNoteLines::CreditNoteId))
+        .foreign_key(&mut foreign_key(
+            "credit_note_id",
+            credit_notes,
+            ForeignKeyAction::Cascade,
+        ))
+        .col(integer(CreditNoteLines::InvoiceLineId))
+        .foreign_key(&mut foreign_key(
+            "invoice_line_id",
+            invoice_lines,
+            ForeignKeyAction::Restrict,
+        ))
+        .col(string(CreditNoteLines::Description))
+        .col(double(CreditNoteLines::Quantity))
+        .col(integer(CreditNoteLines::Price))
+        .col(integer(CreditNoteLines::Tax))
+        .col(integer_null(CreditNoteLines::TaxId))
+        .foreign_key(&mut foreign_key(
+            "tax_id",
+            "taxes",
+            ForeignKeyAction::Restrict,
+        ))
+        .col(integer_null(CreditNoteLines::VariantId))
+        .foreign_key(&mut foreign_key(
+            "variant_id",
+            "variants",
+            ForeignKeyAction::Restrict,
+        ))
+        .col(integer_null(CreditNoteLines::TransactionId))
+        .foreign_key(&mut foreign_key(
+            "transaction_id",
+            "ledger_transactions",
+            ForeignKeyAction::Restrict,
+        ))
+        .col(integer_null(CreditNoteLines::TaxTransactionId))
+        .foreign_key(&mut foreign_key(
+            "tax_transaction_id",
+            "ledger_transactions",
+            ForeignKeyAction::Restrict,
+        ))
+        .col(integer_null(CreditNoteLines::MoveId))
+        .foreign_key(&mut foreign_key(
+            "move_id",
+            "stock_moves",
+            ForeignKeyAction::Restrict,
+        ))
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+enum NumberSequences {
+    Table,
+    DocumentType,
+    Pattern,
+    Gapless,
+}
+
+#[derive(DeriveIden)]
+enum SaleCreditNotes {
+    Table,
+    Id,
+    Reference,
+    Date,
+    InvoiceId,
+    Reason,
+}
+
+#[derive(DeriveIden)]
+enum PurchaseCreditNotes {
+    Table,
+    Id,
+    Reference,
+    Date,
+    InvoiceId,
+    Reason,
+}
+
+#[derive(DeriveIden)]
+enum CreditNoteLines {
+    Id,
+    CreditNoteId,
+    InvoiceLineId,
+    Description,
+    Quantity,
+    Price,
+    Tax,
+    TaxId,
+    VariantId,
+    TransactionId,
+    TaxTransactionId,
+    MoveId,
+}
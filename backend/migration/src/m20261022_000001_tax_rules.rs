@@ -0,0 +1,143 @@
+use sea_orm_migration::{
+    prelude::{extension::postgres::Type, *},
+    schema::*,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // The category is the UNTDID 5305 code used by e-invoices, the note
+        // is printed on every invoice with a line of this tax.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Taxes::Table)
+                    .add_column(string(Taxes::Category).default("S"))
+                    .add_column(text(Taxes::Note).default(""))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(Taxes::Table)
+                    .value(Taxes::Category, "Z")
+                    .and_where(Expr::col(Taxes::Rate).eq(0.0))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Products::Table)
+                    .add_column(string(Products::TaxCategory).default("standard"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum("tax_direction")
+                    .values(vec!["sale", "purchase"])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum("tax_region")
+                    .values(vec!["domestic", "eu", "non_eu"])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaxRules::Table)
+                    .if_not_exists()
+                    .col(pk_auto(TaxRules::Id))
+                    .col(custom(TaxRules::Direction, "tax_direction"))
+                    .col(custom(TaxRules::Region, "tax_region"))
+                    .col(boolean_null(TaxRules::VatId))
+                    .col(string_null(TaxRules::ProductTaxCategory))
+                    .col(integer(TaxRules::TaxId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_tax_rules_tax_id")
+                            .from("tax_rules", "tax_id")
+                            .to("taxes", "id")
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TaxRules::Table).to_owned())
+            .await?;
+
+        for name in ["tax_region", "tax_direction"] {
+            manager
+                .drop_type(Type::drop().name(Alias::new(name)).to_owned())
+                .await?;
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Products::Table)
+                    .drop_column(Products::TaxCategory)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Taxes::Table)
+                    .drop_column(Taxes::Note)
+                    .drop_column(Taxes::Category)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Taxes {
+    Table,
+    Rate,
+    Category,
+    Note,
+}
+
+#[derive(DeriveIden)]
+enum Products {
+    Table,
+    TaxCategory,
+}
+
+#[derive(DeriveIden)]
+enum TaxRules {
+    Table,
+    Id,
+    Direction,
+    Region,
+    VatId,
+    ProductTaxCategory,
+    TaxId,
+}
@@ -0,0 +1,64 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // A version overrides the rate of its tax from `valid_from` up to and
+        // including `valid_to`. Outside of all versions `taxes.rate` applies.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaxRates::Table)
+                    .if_not_exists()
+                    .col(pk_auto(TaxRates::Id))
+                    .col(integer(TaxRates::TaxId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_tax_rates_tax_id")
+                            .from("tax_rates", "tax_id")
+                            .to("taxes", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(double(TaxRates::Rate))
+                    .col(date(TaxRates::ValidFrom))
+                    .col(date_null(TaxRates::ValidTo))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_tax_rates_tax_id_valid_from")
+                    .table(TaxRates::Table)
+                    .col(TaxRates::TaxId)
+                    .col(TaxRates::ValidFrom)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TaxRates::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum TaxRates {
+    Table,
+    Id,
+    TaxId,
+    Rate,
+    ValidFrom,
+    ValidTo,
+}
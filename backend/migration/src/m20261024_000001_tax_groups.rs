@@ -0,0 +1,141 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // A tax with components is a tax group. Its components are levied in
+        // the order of their sequence, a compound one on the net amount plus
+        // the tax of the components before it.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaxComponents::Table)
+                    .if_not_exists()
+                    .col(pk_auto(TaxComponents::Id))
+                    .col(integer(TaxComponents::GroupId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_tax_components_group_id")
+                            .from("tax_components", "group_id")
+                            .to("taxes", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer(TaxComponents::TaxId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_tax_components_tax_id")
+                            .from("tax_components", "tax_id")
+                            .to("taxes", "id")
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer(TaxComponents::Sequence))
+                    .col(boolean(TaxComponents::Compound).default(false))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_tax_components_group_id_tax_id")
+                    .table(TaxComponents::Table)
+                    .col(TaxComponents::GroupId)
+                    .col(TaxComponents::TaxId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Lines with a tax group are posted once per component instead of
+        // through `tax_transaction_id`.
+        for (table, lines) in [
+            ("sale_invoice_line_taxes", "sale_invoice_lines"),
+            ("purchase_invoice_line_taxes", "purchase_invoice_lines"),
+            ("sale_credit_note_line_taxes", "sale_credit_note_lines"),
+            (
+                "purchase_credit_note_line_taxes",
+                "purchase_credit_note_lines",
+            ),
+        ] {
+            manager.create_table(line_taxes(table, lines)).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in [
+            "purchase_credit_note_line_taxes",
+            "sale_credit_note_line_taxes",
+            "purchase_invoice_line_taxes",
+            "sale_invoice_line_taxes",
+            "tax_components",
+        ] {
+            manager
+                .drop_table(Table::drop().table(Alias::new(table)).to_owned())
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The component postings of invoice and credit note lines only differ in
+/// the lines they belong to.
+fn line_taxes(table: &str, lines: &str) -> TableCreateStatement {
+    let foreign_key = |column: &str, target: &str, action: ForeignKeyAction| {
+        ForeignKey::create()
+            .name(format!("rel_{table}_{column}"))
+            .from(Alias::new(table), Alias::new(column))
+            .to(Alias::new(target), Alias::new("id"))
+            .on_delete(action)
+            .to_owned()
+    };
+
+    Table::create()
+        .table(Alias::new(table))
+        .if_not_exists()
+        .col(pk_auto(LineTaxes::Id))
+        .col(integer(LineTaxes::LineId))
+        .foreign_key(&mut foreign_key(
+            "line_id",
+            lines,
+            ForeignKeyAction::Cascade,
+        ))
+        .col(integer(LineTaxes::TaxId))
+        .foreign_key(&mut foreign_key(
+            "tax_id",
+            "taxes",
+            ForeignKeyAction::Restrict,
+        ))
+        .col(integer(LineTaxes::Amount))
+        .col(integer_null(LineTaxes::TransactionId))
+        .foreign_key(&mut foreign_key(
+            "transaction_id",
+            "ledger_transactions",
+            ForeignKeyAction::Restrict,
+        ))
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+enum TaxComponents {
+    Table,
+    Id,
+    GroupId,
+    TaxId,
+    Sequence,
+    Compound,
+}
+
+#[derive(DeriveIden)]
+enum LineTaxes {
+    Id,
+    LineId,
+    TaxId,
+    Amount,
+    TransactionId,
+}
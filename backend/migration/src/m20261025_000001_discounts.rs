@@ -0,0 +1,129 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Documents and lines take a percentage off first and then an
+        // absolute amount in cents.
+        for table in [
+            "sale_orders",
+            "sale_order_lines",
+            "sale_invoices",
+            "sale_invoice_lines",
+            "purchase_orders",
+            "purchase_order_lines",
+            "purchase_invoices",
+            "purchase_invoice_lines",
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .add_column(double(Alias::new("discount_percent")).default(0.0))
+                        .add_column(integer(Alias::new("discount_amount")).default(0))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // A credit note line keeps the credited share of the invoice line's
+        // discounts, including its share of the invoice discount.
+        for table in ["sale_credit_note_lines", "purchase_credit_note_lines"] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .add_column(integer(Alias::new("discount_amount")).default(0))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // Lines whose discount is posted to a sales discount account of its
+        // own keep the undiscounted amount on `transaction_id`.
+        for table in [
+            "sale_invoice_lines",
+            "purchase_invoice_lines",
+            "sale_credit_note_lines",
+            "purchase_credit_note_lines",
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .add_column(integer_null(Alias::new("discount_transaction_id")))
+                        .add_foreign_key(
+                            TableForeignKey::new()
+                                .name(format!("rel_{table}_discount_transaction_id"))
+                                .from_tbl(Alias::new(table))
+                                .from_col(Alias::new("discount_transaction_id"))
+                                .to_tbl(Alias::new("ledger_transactions"))
+                                .to_col(Alias::new("id"))
+                                .on_delete(ForeignKeyAction::Restrict),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in [
+            "purchase_credit_note_lines",
+            "sale_credit_note_lines",
+            "purchase_invoice_lines",
+            "sale_invoice_lines",
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .drop_foreign_key(Alias::new(format!(
+                            "rel_{table}_discount_transaction_id"
+                        )))
+                        .drop_column(Alias::new("discount_transaction_id"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        for table in ["purchase_credit_note_lines", "sale_credit_note_lines"] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .drop_column(Alias::new("discount_amount"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        for table in [
+            "purchase_invoice_lines",
+            "purchase_invoices",
+            "purchase_order_lines",
+            "purchase_orders",
+            "sale_invoice_lines",
+            "sale_invoices",
+            "sale_order_lines",
+            "sale_orders",
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .drop_column(Alias::new("discount_amount"))
+                        .drop_column(Alias::new("discount_percent"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
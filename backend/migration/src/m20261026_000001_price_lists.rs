@@ -0,0 +1,178 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Customers are quoted in their currency, which selects the price
+        // lists their orders are priced from.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Contacts::Table)
+                    .add_column(string_len(Contacts::Currency, 3).default("EUR"))
+                    .to_owned(),
+            )
+            .await?;
+
+        // A list applies from `valid_from` up to and including `valid_to`,
+        // an open end applies without limit.
+        manager
+            .create_table(
+                Table::create()
+                    .table(PriceLists::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PriceLists::Id))
+                    .col(string(PriceLists::Name))
+                    .col(string_len(PriceLists::Currency, 3))
+                    .col(date_null(PriceLists::ValidFrom))
+                    .col(date_null(PriceLists::ValidTo))
+                    .to_owned(),
+            )
+            .await?;
+
+        // An item is the price of a variant from a minimum quantity on, so
+        // several items of a variant make up its quantity breaks.
+        manager
+            .create_table(
+                Table::create()
+                    .table(PriceListItems::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PriceListItems::Id))
+                    .col(integer(PriceListItems::PriceListId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_price_list_items_price_list_id")
+                            .from("price_list_items", "price_list_id")
+                            .to("price_lists", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer(PriceListItems::VariantId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_price_list_items_variant_id")
+                            .from("price_list_items", "variant_id")
+                            .to("variants", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(double(PriceListItems::MinQuantity).default(0.0))
+                    .col(integer(PriceListItems::Price))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_price_list_items_price_list_id_variant_id_min_quantity")
+                    .table(PriceListItems::Table)
+                    .col(PriceListItems::PriceListId)
+                    .col(PriceListItems::VariantId)
+                    .col(PriceListItems::MinQuantity)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PriceListCustomers::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PriceListCustomers::Id))
+                    .col(integer(PriceListCustomers::PriceListId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_price_list_customers_price_list_id")
+                            .from("price_list_customers", "price_list_id")
+                            .to("price_lists", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer(PriceListCustomers::ContactId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_price_list_customers_contact_id")
+                            .from("price_list_customers", "contact_id")
+                            .to("contacts", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_price_list_customers_price_list_id_contact_id")
+                    .table(PriceListCustomers::Table)
+                    .col(PriceListCustomers::PriceListId)
+                    .col(PriceListCustomers::ContactId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PriceListCustomers::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(PriceListItems::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(PriceLists::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Contacts::Table)
+                    .drop_column(Contacts::Currency)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Contacts {
+    Table,
+    Currency,
+}
+
+#[derive(DeriveIden)]
+enum PriceLists {
+    Table,
+    Id,
+    Name,
+    Currency,
+    ValidFrom,
+    ValidTo,
+}
+
+#[derive(DeriveIden)]
+enum PriceListItems {
+    Table,
+    Id,
+    PriceListId,
+    VariantId,
+    MinQuantity,
+    Price,
+}
+
+#[derive(DeriveIden)]
+enum PriceListCustomers {
+    Table,
+    Id,
+    PriceListId,
+    ContactId,
+}
@@ -0,0 +1,81 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // A supplier's catalog entry for a variant. The minimum quantity and
+        // pack size are in the variant's unit, the lead time in days.
+        manager
+            .create_table(
+                Table::create()
+                    .table(SupplierPrices::Table)
+                    .if_not_exists()
+                    .col(pk_auto(SupplierPrices::Id))
+                    .col(integer(SupplierPrices::VariantId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_supplier_prices_variant_id")
+                            .from("supplier_prices", "variant_id")
+                            .to("variants", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer(SupplierPrices::SupplierId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_supplier_prices_supplier_id")
+                            .from("supplier_prices", "supplier_id")
+                            .to("contacts", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(string(SupplierPrices::SupplierSku).default(""))
+                    .col(string(SupplierPrices::Description).default(""))
+                    .col(integer(SupplierPrices::Price))
+                    .col(double(SupplierPrices::MinQuantity).default(0.0))
+                    .col(double(SupplierPrices::PackSize).default(1.0))
+                    .col(integer(SupplierPrices::LeadTimeDays).default(0))
+                    .col(boolean(SupplierPrices::Preferred).default(false))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_supplier_prices_variant_id_supplier_id")
+                    .table(SupplierPrices::Table)
+                    .col(SupplierPrices::VariantId)
+                    .col(SupplierPrices::SupplierId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SupplierPrices::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SupplierPrices {
+    Table,
+    Id,
+    VariantId,
+    SupplierId,
+    SupplierSku,
+    Description,
+    Price,
+    MinQuantity,
+    PackSize,
+    LeadTimeDays,
+    Preferred,
+}
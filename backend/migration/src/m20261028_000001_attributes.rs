@@ -0,0 +1,155 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // An attribute like size or color along which the variants of a
+        // product differ. The position orders attributes in variant names.
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProductAttributes::Table)
+                    .if_not_exists()
+                    .col(pk_auto(ProductAttributes::Id))
+                    .col(integer(ProductAttributes::ProductId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_product_attributes_product_id")
+                            .from("product_attributes", "product_id")
+                            .to("products", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(string(ProductAttributes::Name))
+                    .col(integer(ProductAttributes::Position))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_product_attributes_product_id_name")
+                    .table(ProductAttributes::Table)
+                    .col(ProductAttributes::ProductId)
+                    .col(ProductAttributes::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProductAttributeValues::Table)
+                    .if_not_exists()
+                    .col(pk_auto(ProductAttributeValues::Id))
+                    .col(integer(ProductAttributeValues::AttributeId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_product_attribute_values_attribute_id")
+                            .from("product_attribute_values", "attribute_id")
+                            .to("product_attributes", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(string(ProductAttributeValues::Name))
+                    .col(integer(ProductAttributeValues::Position))
+                    .to_owned(),
+            )
+            .await?;
+
+        // The attribute values a variant was generated from, which tells
+        // which combinations of a product already exist.
+        manager
+            .create_table(
+                Table::create()
+                    .table(VariantAttributeValues::Table)
+                    .if_not_exists()
+                    .col(pk_auto(VariantAttributeValues::Id))
+                    .col(integer(VariantAttributeValues::VariantId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_variant_attribute_values_variant_id")
+                            .from("variant_attribute_values", "variant_id")
+                            .to("variants", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer(VariantAttributeValues::ValueId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_variant_attribute_values_value_id")
+                            .from("variant_attribute_values", "value_id")
+                            .to("product_attribute_values", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_variant_attribute_values_variant_id_value_id")
+                    .table(VariantAttributeValues::Table)
+                    .col(VariantAttributeValues::VariantId)
+                    .col(VariantAttributeValues::ValueId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(VariantAttributeValues::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ProductAttributeValues::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ProductAttributes::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ProductAttributes {
+    Table,
+    Id,
+    ProductId,
+    Name,
+    Position,
+}
+
+#[derive(DeriveIden)]
+enum ProductAttributeValues {
+    Table,
+    Id,
+    AttributeId,
+    Name,
+    Position,
+}
+
+#[derive(DeriveIden)]
+enum VariantAttributeValues {
+    Table,
+    Id,
+    VariantId,
+    ValueId,
+}
@@ -0,0 +1,171 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const LINE_TABLES: [&str; 2] = ["sale_order_lines", "purchase_order_lines"];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // A unit is either a base unit like piece or kg, or a multiple of
+        // one: a box of 12 is 12 pieces, a gram 0.001 kg. Quantities only
+        // convert between units with the same base.
+        manager
+            .create_table(
+                Table::create()
+                    .table(Units::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Units::Id))
+                    .col(string_uniq(Units::Name))
+                    .col(string(Units::Symbol))
+                    .col(integer_null(Units::BaseUnitId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_units_base_unit_id")
+                            .from("units", "base_unit_id")
+                            .to("units", "id")
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(double(Units::Factor).default(1.0))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Stock of a variant is kept in its unit, so the quantities of its
+        // stock moves are too.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Variants::Table)
+                    .add_column(string_null(Variants::Sku))
+                    .add_column(string_null(Variants::Gtin))
+                    .add_column(integer_null(Variants::UnitId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("rel_variants_unit_id")
+                            .from_tbl(Variants::Table)
+                            .from_col(Variants::UnitId)
+                            .to_tbl(Units::Table)
+                            .to_col(Units::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_variants_sku")
+                    .table(Variants::Table)
+                    .col(Variants::Sku)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_variants_gtin")
+                    .table(Variants::Table)
+                    .col(Variants::Gtin)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // The unit an order line's quantity and price are in, the variant's
+        // unit if none.
+        for table in LINE_TABLES {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .add_column(integer_null(Alias::new("unit_id")))
+                        .add_foreign_key(
+                            TableForeignKey::new()
+                                .name(format!("rel_{table}_unit_id"))
+                                .from_tbl(Alias::new(table))
+                                .from_col(Alias::new("unit_id"))
+                                .to_tbl(Units::Table)
+                                .to_col(Units::Id)
+                                .on_delete(ForeignKeyAction::Restrict),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in LINE_TABLES {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .drop_foreign_key(Alias::new(format!("rel_{table}_unit_id")))
+                        .drop_column(Alias::new("unit_id"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_variants_gtin")
+                    .table(Variants::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_variants_sku")
+                    .table(Variants::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Variants::Table)
+                    .drop_foreign_key(Alias::new("rel_variants_unit_id"))
+                    .drop_column(Variants::UnitId)
+                    .drop_column(Variants::Gtin)
+                    .drop_column(Variants::Sku)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Units::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Units {
+    Table,
+    Id,
+    Name,
+    Symbol,
+    BaseUnitId,
+    Factor,
+}
+
+#[derive(DeriveIden)]
+enum Variants {
+    Table,
+    Sku,
+    Gtin,
+    UnitId,
+}
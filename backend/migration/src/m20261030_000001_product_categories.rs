@@ -0,0 +1,104 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const ACCOUNT_COLUMNS: [&str; 3] = [
+    "income_account_id",
+    "expense_account_id",
+    "inventory_account_id",
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Categories form a tree. A category without an account or tax of
+        // its own takes the one of its nearest ancestor that has one.
+        let mut table = Table::create()
+            .table(ProductCategories::Table)
+            .if_not_exists()
+            .col(pk_auto(ProductCategories::Id))
+            .col(string(ProductCategories::Name))
+            .col(integer_null(ProductCategories::ParentId))
+            .foreign_key(
+                ForeignKey::create()
+                    .name("rel_product_categories_parent_id")
+                    .from("product_categories", "parent_id")
+                    .to("product_categories", "id")
+                    .on_delete(ForeignKeyAction::Restrict),
+            )
+            .to_owned();
+        for column in ACCOUNT_COLUMNS {
+            table.col(integer_null(Alias::new(column))).foreign_key(
+                ForeignKey::create()
+                    .name(format!("rel_product_categories_{column}"))
+                    .from("product_categories", column)
+                    .to("ledger_accounts", "id")
+                    .on_delete(ForeignKeyAction::Restrict),
+            );
+        }
+        table
+            .col(integer_null(ProductCategories::TaxId))
+            .foreign_key(
+                ForeignKey::create()
+                    .name("rel_product_categories_tax_id")
+                    .from("product_categories", "tax_id")
+                    .to("taxes", "id")
+                    .on_delete(ForeignKeyAction::Restrict),
+            );
+        manager.create_table(table).await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Products::Table)
+                    .add_column(integer_null(Products::CategoryId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("rel_products_category_id")
+                            .from_tbl(Products::Table)
+                            .from_col(Products::CategoryId)
+                            .to_tbl(ProductCategories::Table)
+                            .to_col(ProductCategories::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Products::Table)
+                    .drop_foreign_key(Alias::new("rel_products_category_id"))
+                    .drop_column(Products::CategoryId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ProductCategories::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ProductCategories {
+    Table,
+    Id,
+    Name,
+    ParentId,
+    TaxId,
+}
+
+#[derive(DeriveIden)]
+enum Products {
+    Table,
+    CategoryId,
+}
@@ -0,0 +1,115 @@
+use sea_orm_migration::{
+    prelude::{extension::postgres::Type, *},
+    schema::*,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Issues either consume the oldest receipts first or take the
+        // average cost of everything in stock.
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum("costing_method")
+                    .values(vec!["fifo", "average"])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Products::Table)
+                    .add_column(custom(Products::CostingMethod, "costing_method").default("fifo"))
+                    .to_owned(),
+            )
+            .await?;
+
+        // One layer per move into or out of the own stocks. Receipts are
+        // positive and keep track of what is left of them, issues are
+        // negative.
+        manager
+            .create_table(
+                Table::create()
+                    .table(StockValuationLayers::Table)
+                    .if_not_exists()
+                    .col(pk_auto(StockValuationLayers::Id))
+                    .col(integer_uniq(StockValuationLayers::MoveId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_stock_valuation_layers_move_id")
+                            .from("stock_valuation_layers", "move_id")
+                            .to("stock_moves", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer(StockValuationLayers::VariantId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_stock_valuation_layers_variant_id")
+                            .from("stock_valuation_layers", "variant_id")
+                            .to("variants", "id")
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(double(StockValuationLayers::Quantity))
+                    .col(integer(StockValuationLayers::Value))
+                    .col(double(StockValuationLayers::RemainingQuantity).default(0.0))
+                    .col(integer(StockValuationLayers::RemainingValue).default(0))
+                    .col(integer_null(StockValuationLayers::TransactionId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_stock_valuation_layers_transaction_id")
+                            .from("stock_valuation_layers", "transaction_id")
+                            .to("ledger_transactions", "id")
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(StockValuationLayers::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Products::Table)
+                    .drop_column(Products::CostingMethod)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(Alias::new("costing_method")).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Products {
+    Table,
+    CostingMethod,
+}
+
+#[derive(DeriveIden)]
+enum StockValuationLayers {
+    Table,
+    Id,
+    MoveId,
+    VariantId,
+    Quantity,
+    Value,
+    RemainingQuantity,
+    RemainingValue,
+    TransactionId,
+}
@@ -0,0 +1,81 @@
+use sea_orm_migration::{
+    prelude::{extension::postgres::Type, *},
+    schema::*,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_type(
+                Type::alter()
+                    .name(Alias::new("sale_order_status"))
+                    .add_value(Alias::new("cancelled"))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Confirmed sale orders reserve the quantity of their lines in the
+        // order's source stock until the goods are delivered or the order
+        // is cancelled.
+        manager
+            .create_table(
+                Table::create()
+                    .table(StockReservations::Table)
+                    .if_not_exists()
+                    .col(pk_auto(StockReservations::Id))
+                    .col(integer_uniq(StockReservations::LineId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_stock_reservations_line_id")
+                            .from("stock_reservations", "line_id")
+                            .to("sale_order_lines", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer(StockReservations::VariantId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_stock_reservations_variant_id")
+                            .from("stock_reservations", "variant_id")
+                            .to("variants", "id")
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer(StockReservations::StockId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_stock_reservations_stock_id")
+                            .from("stock_reservations", "stock_id")
+                            .to("stocks", "id")
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(double(StockReservations::Quantity))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Postgres can't drop a value from an enum, so cancelled orders
+        // keep their status.
+        manager
+            .drop_table(Table::drop().table(StockReservations::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum StockReservations {
+    Table,
+    Id,
+    LineId,
+    VariantId,
+    StockId,
+    Quantity,
+}
@@ -0,0 +1,139 @@
+use sea_orm_migration::{
+    prelude::{extension::postgres::Type, *},
+    schema::*,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Lot tracked goods are moved in batches sharing a number and an
+        // expiry date, serial tracked goods each have a number of their own.
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum("tracking")
+                    .values(vec!["none", "lot", "serial"])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Products::Table)
+                    .add_column(custom(Products::Tracking, "tracking").default("none"))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Serial numbers are lots of a single piece.
+        manager
+            .create_table(
+                Table::create()
+                    .table(Lots::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Lots::Id))
+                    .col(integer(Lots::VariantId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_lots_variant_id")
+                            .from("lots", "variant_id")
+                            .to("variants", "id")
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(string(Lots::Number))
+                    .col(date_null(Lots::ExpiryDate))
+                    .index(
+                        Index::create()
+                            .name("idx_lots_variant_id_number")
+                            .col(Lots::VariantId)
+                            .col(Lots::Number)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // A move of tracked goods is split over the lots it moves.
+        manager
+            .create_table(
+                Table::create()
+                    .table(StockMoveLots::Table)
+                    .if_not_exists()
+                    .col(pk_auto(StockMoveLots::Id))
+                    .col(integer(StockMoveLots::MoveId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_stock_move_lots_move_id")
+                            .from("stock_move_lots", "move_id")
+                            .to("stock_moves", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer(StockMoveLots::LotId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_stock_move_lots_lot_id")
+                            .from("stock_move_lots", "lot_id")
+                            .to("lots", "id")
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(double(StockMoveLots::Quantity))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(StockMoveLots::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Lots::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Products::Table)
+                    .drop_column(Products::Tracking)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(Alias::new("tracking")).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Products {
+    Table,
+    Tracking,
+}
+
+#[derive(DeriveIden)]
+enum Lots {
+    Table,
+    Id,
+    VariantId,
+    Number,
+    ExpiryDate,
+}
+
+#[derive(DeriveIden)]
+enum StockMoveLots {
+    Table,
+    Id,
+    MoveId,
+    LotId,
+    Quantity,
+}
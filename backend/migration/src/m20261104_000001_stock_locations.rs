@@ -0,0 +1,156 @@
+use sea_orm_migration::{
+    prelude::{extension::postgres::Type, *},
+    schema::*,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Internal locations hold the company's goods and nest as warehouse,
+        // zone and bin. The others are virtual counterparts of the moves
+        // that bring goods in or take them out.
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum("stock_kind")
+                    .values(vec![
+                        "internal",
+                        "supplier",
+                        "customer",
+                        "inventory_loss",
+                        "production",
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Stocks::Table)
+                    .add_column(custom(Stocks::Kind, "stock_kind").default("internal"))
+                    .add_column(integer_null(Stocks::ParentId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("rel_stocks_parent_id")
+                            .from_tbl(Stocks::Table)
+                            .from_col(Stocks::ParentId)
+                            .to_tbl(Stocks::Table)
+                            .to_col(Stocks::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Stocks of contacts are customer locations, unless the contact
+        // only supplies.
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(Stocks::Table)
+                    .value(
+                        Stocks::Kind,
+                        Expr::val("customer").as_enum(Alias::new("stock_kind")),
+                    )
+                    .and_where(Expr::col(Stocks::IsOwn).eq(false))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(Stocks::Table)
+                    .value(
+                        Stocks::Kind,
+                        Expr::val("supplier").as_enum(Alias::new("stock_kind")),
+                    )
+                    .and_where(
+                        Expr::col(Stocks::OwnerId).in_subquery(
+                            Query::select()
+                                .column(Contacts::Id)
+                                .from(Contacts::Table)
+                                .and_where(Expr::col(Contacts::IsSupplier).eq(true))
+                                .and_where(Expr::col(Contacts::IsCustomer).eq(false))
+                                .to_owned(),
+                        ),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        for (name, kind) in [
+            ("Suppliers", "supplier"),
+            ("Customers", "customer"),
+            ("Inventory loss", "inventory_loss"),
+            ("Production", "production"),
+        ] {
+            manager
+                .exec_stmt(
+                    Query::insert()
+                        .into_table(Stocks::Table)
+                        .columns([Stocks::Name, Stocks::IsOwn, Stocks::Kind])
+                        .values_panic([
+                            name.into(),
+                            false.into(),
+                            Expr::val(kind).as_enum(Alias::new("stock_kind")),
+                        ])
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(Stocks::Table)
+                    .and_where(Expr::col(Stocks::OwnerId).is_null())
+                    .and_where(Expr::col(Stocks::IsOwn).eq(false))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Stocks::Table)
+                    .drop_foreign_key(Alias::new("rel_stocks_parent_id"))
+                    .drop_column(Stocks::ParentId)
+                    .drop_column(Stocks::Kind)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(Alias::new("stock_kind")).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Stocks {
+    Table,
+    Id,
+    Name,
+    IsOwn,
+    OwnerId,
+    Kind,
+    ParentId,
+}
+
+#[derive(DeriveIden)]
+enum Contacts {
+    Table,
+    Id,
+    IsCustomer,
+    IsSupplier,
+}
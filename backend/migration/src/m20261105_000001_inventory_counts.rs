@@ -0,0 +1,169 @@
+use sea_orm_migration::{
+    prelude::{extension::postgres::Type, *},
+    schema::*,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum("inventory_count_status")
+                    .values(vec!["open", "approved"])
+                    .to_owned(),
+            )
+            .await?;
+
+        // A count of what is physically in a location, started with a
+        // snapshot of what should be there.
+        manager
+            .create_table(
+                Table::create()
+                    .table(InventoryCounts::Table)
+                    .if_not_exists()
+                    .col(pk_auto(InventoryCounts::Id))
+                    .col(integer(InventoryCounts::StockId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_inventory_counts_stock_id")
+                            .from("inventory_counts", "stock_id")
+                            .to("stocks", "id")
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(custom(InventoryCounts::Status, "inventory_count_status").default("open"))
+                    .col(date(InventoryCounts::Date))
+                    .to_owned(),
+            )
+            .await?;
+
+        // The expected quantity of a variant, and the move that adjusted the
+        // stock to what was counted once the count is approved.
+        manager
+            .create_table(
+                Table::create()
+                    .table(InventoryCountLines::Table)
+                    .if_not_exists()
+                    .col(pk_auto(InventoryCountLines::Id))
+                    .col(integer(InventoryCountLines::CountId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_inventory_count_lines_count_id")
+                            .from("inventory_count_lines", "count_id")
+                            .to("inventory_counts", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer(InventoryCountLines::VariantId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_inventory_count_lines_variant_id")
+                            .from("inventory_count_lines", "variant_id")
+                            .to("variants", "id")
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(double(InventoryCountLines::ExpectedQuantity))
+                    .col(integer_null(InventoryCountLines::MoveId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_inventory_count_lines_move_id")
+                            .from("inventory_count_lines", "move_id")
+                            .to("stock_moves", "id")
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_inventory_count_lines_count_id_variant_id")
+                            .col(InventoryCountLines::CountId)
+                            .col(InventoryCountLines::VariantId)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // What each counter found of a line's variant.
+        manager
+            .create_table(
+                Table::create()
+                    .table(InventoryCountEntries::Table)
+                    .if_not_exists()
+                    .col(pk_auto(InventoryCountEntries::Id))
+                    .col(integer(InventoryCountEntries::LineId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_inventory_count_entries_line_id")
+                            .from("inventory_count_entries", "line_id")
+                            .to("inventory_count_lines", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(string(InventoryCountEntries::Counter))
+                    .col(double(InventoryCountEntries::Quantity))
+                    .index(
+                        Index::create()
+                            .name("idx_inventory_count_entries_line_id_counter")
+                            .col(InventoryCountEntries::LineId)
+                            .col(InventoryCountEntries::Counter)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(InventoryCountEntries::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(InventoryCountLines::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(InventoryCounts::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("inventory_count_status"))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum InventoryCounts {
+    Table,
+    Id,
+    StockId,
+    Status,
+    Date,
+}
+
+#[derive(DeriveIden)]
+enum InventoryCountLines {
+    Table,
+    Id,
+    CountId,
+    VariantId,
+    ExpectedQuantity,
+    MoveId,
+}
+
+#[derive(DeriveIden)]
+enum InventoryCountEntries {
+    Table,
+    Id,
+    LineId,
+    Counter,
+    Quantity,
+}
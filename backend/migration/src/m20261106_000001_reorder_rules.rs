@@ -0,0 +1,82 @@
+use sea_orm_migration::{
+    prelude::{extension::postgres::Type, *},
+    schema::*,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Drafts proposed by the replenishment are confirmed into orders
+        // by a buyer.
+        manager
+            .alter_type(
+                Type::alter()
+                    .name(Alias::new("purchase_order_status"))
+                    .add_value(Alias::new("order"))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Once what is expected of a variant in a stock falls below the
+        // minimum, it is replenished up to the maximum.
+        manager
+            .create_table(
+                Table::create()
+                    .table(ReorderRules::Table)
+                    .if_not_exists()
+                    .col(pk_auto(ReorderRules::Id))
+                    .col(integer(ReorderRules::VariantId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_reorder_rules_variant_id")
+                            .from("reorder_rules", "variant_id")
+                            .to("variants", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer(ReorderRules::StockId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rel_reorder_rules_stock_id")
+                            .from("reorder_rules", "stock_id")
+                            .to("stocks", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(double(ReorderRules::MinQuantity))
+                    .col(double(ReorderRules::MaxQuantity))
+                    .index(
+                        Index::create()
+                            .name("idx_reorder_rules_variant_id_stock_id")
+                            .col(ReorderRules::VariantId)
+                            .col(ReorderRules::StockId)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Postgres can't drop a value from an enum, so confirmed orders
+        // keep their status.
+        manager
+            .drop_table(Table::drop().table(ReorderRules::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ReorderRules {
+    Table,
+    Id,
+    VariantId,
+    StockId,
+    MinQuantity,
+    MaxQuantity,
+}
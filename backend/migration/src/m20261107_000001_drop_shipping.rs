@@ -0,0 +1,109 @@
+use sea_orm_migration::{
+    prelude::{extension::postgres::Type, *},
+    schema::*,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Lines are delivered from the own stock, or drop-shipped by a
+        // supplier straight to the customer.
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum("sale_order_line_route")
+                    .values(vec!["stock", "drop_ship"])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SaleOrderLines::Table)
+                    .add_column(
+                        custom(SaleOrderLines::Route, "sale_order_line_route").default("stock"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // The purchase order line that drop-ships a sale order line.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PurchaseOrderLines::Table)
+                    .add_column(integer_null(PurchaseOrderLines::SaleLineId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("rel_purchase_order_lines_sale_line_id")
+                            .from_tbl(PurchaseOrderLines::Table)
+                            .from_col(PurchaseOrderLines::SaleLineId)
+                            .to_tbl(SaleOrderLines::Table)
+                            .to_col(SaleOrderLines::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_purchase_order_lines_sale_line_id")
+                    .table(PurchaseOrderLines::Table)
+                    .col(PurchaseOrderLines::SaleLineId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PurchaseOrderLines::Table)
+                    .drop_foreign_key(Alias::new("rel_purchase_order_lines_sale_line_id"))
+                    .drop_column(PurchaseOrderLines::SaleLineId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SaleOrderLines::Table)
+                    .drop_column(SaleOrderLines::Route)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("sale_order_line_route"))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SaleOrderLines {
+    Table,
+    Id,
+    Route,
+}
+
+#[derive(DeriveIden)]
+enum PurchaseOrderLines {
+    Table,
+    SaleLineId,
+}
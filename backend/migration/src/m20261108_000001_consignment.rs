@@ -0,0 +1,42 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Consignment stocks hold goods of one party on the premises of the
+        // other: the company's goods at a customer, or a supplier's goods at
+        // the company, until their consumption is reported.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Stocks::Table)
+                    .add_column(boolean(Stocks::Consignment).default(false))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Stocks::Table)
+                    .drop_column(Stocks::Consignment)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Stocks {
+    Table,
+    Consignment,
+}
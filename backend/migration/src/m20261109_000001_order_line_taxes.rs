@@ -0,0 +1,57 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // The tax of a sale order line, resolved with the rate that applies
+        // on the order date. Lines without one keep their plain percentage.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SaleOrderLines::Table)
+                    .add_column(integer_null(SaleOrderLines::TaxId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("rel_sale_order_lines_tax_id")
+                            .from_tbl(SaleOrderLines::Table)
+                            .from_col(SaleOrderLines::TaxId)
+                            .to_tbl(Taxes::Table)
+                            .to_col(Taxes::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SaleOrderLines::Table)
+                    .drop_foreign_key(Alias::new("rel_sale_order_lines_tax_id"))
+                    .drop_column(SaleOrderLines::TaxId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SaleOrderLines {
+    Table,
+    TaxId,
+}
+
+#[derive(DeriveIden)]
+enum Taxes {
+    Table,
+    Id,
+}
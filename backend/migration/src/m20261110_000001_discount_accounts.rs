@@ -0,0 +1,62 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const ACCOUNT_COLUMNS: [&str; 2] = ["sale_discount_account_id", "purchase_discount_account_id"];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Invoice lines of a category with a discount account are posted
+        // undiscounted, their discount is posted to that account on its own.
+        for column in ACCOUNT_COLUMNS {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(ProductCategories::Table)
+                        .add_column(integer_null(Alias::new(column)))
+                        .add_foreign_key(
+                            TableForeignKey::new()
+                                .name(format!("rel_product_categories_{column}"))
+                                .from_tbl(ProductCategories::Table)
+                                .from_col(Alias::new(column))
+                                .to_tbl(LedgerAccounts::Table)
+                                .to_col(LedgerAccounts::Id)
+                                .on_delete(ForeignKeyAction::Restrict),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in ACCOUNT_COLUMNS {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(ProductCategories::Table)
+                        .drop_foreign_key(Alias::new(format!("rel_product_categories_{column}")))
+                        .drop_column(Alias::new(column))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ProductCategories {
+    Table,
+}
+
+#[derive(DeriveIden)]
+enum LedgerAccounts {
+    Table,
+    Id,
+}
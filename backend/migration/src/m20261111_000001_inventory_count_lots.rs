@@ -0,0 +1,84 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Tracked variants are counted per lot or serial number, with a line
+        // for each. Lines of untracked variants keep an empty number.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(InventoryCountLines::Table)
+                    .add_column(string(InventoryCountLines::LotNumber).default(""))
+                    .to_owned(),
+            )
+            .await?;
+
+        // The old unique index was created with the table, which makes it a
+        // constraint.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE inventory_count_lines \
+                 DROP CONSTRAINT idx_inventory_count_lines_count_id_variant_id",
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_inventory_count_lines_count_id_variant_id_lot_number")
+                    .table(InventoryCountLines::Table)
+                    .col(InventoryCountLines::CountId)
+                    .col(InventoryCountLines::VariantId)
+                    .col(InventoryCountLines::LotNumber)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_inventory_count_lines_count_id_variant_id_lot_number")
+                    .table(InventoryCountLines::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(InventoryCountLines::Table)
+                    .drop_column(InventoryCountLines::LotNumber)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE inventory_count_lines \
+                 ADD CONSTRAINT idx_inventory_count_lines_count_id_variant_id \
+                 UNIQUE (count_id, variant_id)",
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum InventoryCountLines {
+    Table,
+    CountId,
+    VariantId,
+    LotNumber,
+}
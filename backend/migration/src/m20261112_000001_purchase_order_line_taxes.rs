@@ -0,0 +1,57 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // The tax of a purchase order line, resolved with the rate that applies
+        // on the order date. Lines without one keep their plain percentage.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PurchaseOrderLines::Table)
+                    .add_column(integer_null(PurchaseOrderLines::TaxId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("rel_purchase_order_lines_tax_id")
+                            .from_tbl(PurchaseOrderLines::Table)
+                            .from_col(PurchaseOrderLines::TaxId)
+                            .to_tbl(Taxes::Table)
+                            .to_col(Taxes::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PurchaseOrderLines::Table)
+                    .drop_foreign_key(Alias::new("rel_purchase_order_lines_tax_id"))
+                    .drop_column(PurchaseOrderLines::TaxId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum PurchaseOrderLines {
+    Table,
+    TaxId,
+}
+
+#[derive(DeriveIden)]
+enum Taxes {
+    Table,
+    Id,
+}
@@ -0,0 +1,320 @@
+use std::io;
+
+use quick_xml::events::{BytesDecl, BytesText, Event};
+
+use super::{
+    ALLOWANCE_REASON, ALLOWANCE_REASON_CODE, EInvoice, Party, XmlWriter, amount, money, percent,
+    quantity, text, xml_writer,
+};
+
+/// The EN 16931 ("COMFORT") profile of Factur-X / ZUGFeRD.
+const GUIDELINE_ID: &str = "urn:cen.eu:en16931:2017";
+
+/// Credit transfer (UNTDID 4461).
+const PAYMENT_MEANS_CODE: &str = "58";
+
+/// Writes the invoice as UN/CEFACT `CrossIndustryInvoice`. The element order
+/// is fixed by the CII schema and must not be changed.
+pub fn write(invoice: &EInvoice) -> io::Result<Vec<u8>> {
+    let mut writer = xml_writer();
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+
+    writer
+        .create_element("rsm:CrossIndustryInvoice")
+        .with_attribute((
+            "xmlns:rsm",
+            "urn:un:unece:uncefact:data:standard:CrossIndustryInvoice:100",
+        ))
+        .with_attribute((
+            "xmlns:ram",
+            "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:100",
+        ))
+        .with_attribute((
+            "xmlns:udt",
+            "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:100",
+        ))
+        .write_inner_content(|writer| {
+            writer
+                .create_element("rsm:ExchangedDocumentContext")
+                .write_inner_content(|writer| {
+                    writer
+                        .create_element("ram:GuidelineSpecifiedDocumentContextParameter")
+                        .write_inner_content(|writer| text(writer, "ram:ID", GUIDELINE_ID))?;
+                    Ok(())
+                })?;
+
+            writer
+                .create_element("rsm:ExchangedDocument")
+                .write_inner_content(|writer| {
+                    text(writer, "ram:ID", &invoice.number)?;
+                    text(writer, "ram:TypeCode", &invoice.type_code)?;
+                    writer
+                        .create_element("ram:IssueDateTime")
+                        .write_inner_content(|writer| {
+                            writer
+                                .create_element("udt:DateTimeString")
+                                .with_attribute(("format", "102"))
+                                .write_text_content(BytesText::new(
+                                    &invoice.issue_date.replace('-', ""),
+                                ))?;
+                            Ok(())
+                        })?;
+                    Ok(())
+                })?;
+
+            writer
+                .create_element("rsm:SupplyChainTradeTransaction")
+                .write_inner_content(|writer| {
+                    lines(writer, invoice)?;
+                    agreement(writer, invoice)?;
+                    writer
+                        .create_element("ram:ApplicableHeaderTradeDelivery")
+                        .write_empty()?;
+                    settlement(writer, invoice)
+                })?;
+
+            Ok(())
+        })?;
+
+    Ok(writer.into_inner().into_inner())
+}
+
+fn lines(writer: &mut XmlWriter, invoice: &EInvoice) -> io::Result<()> {
+    for line in invoice.lines.iter() {
+        writer
+            .create_element("ram:IncludedSupplyChainTradeLineItem")
+            .write_inner_content(|writer| {
+                writer
+                    .create_element("ram:AssociatedDocumentLineDocument")
+                    .write_inner_content(|writer| text(writer, "ram:LineID", &line.id))?;
+                writer
+                    .create_element("ram:SpecifiedTradeProduct")
+                    .write_inner_content(|writer| text(writer, "ram:Name", &line.name))?;
+                writer
+                    .create_element("ram:SpecifiedLineTradeAgreement")
+                    .write_inner_content(|writer| {
+                        writer
+                            .create_element("ram:NetPriceProductTradePrice")
+                            .write_inner_content(|writer| {
+                                text(writer, "ram:ChargeAmount", &money(line.price))
+                            })?;
+                        Ok(())
+                    })?;
+                writer
+                    .create_element("ram:SpecifiedLineTradeDelivery")
+                    .write_inner_content(|writer| {
+                        writer
+                            .create_element("ram:BilledQuantity")
+                            .with_attribute(("unitCode", line.unit_code.as_str()))
+                            .write_text_content(BytesText::new(&quantity(line.quantity)))?;
+                        Ok(())
+                    })?;
+                writer
+                    .create_element("ram:SpecifiedLineTradeSettlement")
+                    .write_inner_content(|writer| {
+                        writer
+                            .create_element("ram:ApplicableTradeTax")
+                            .write_inner_content(|writer| {
+                                text(writer, "ram:TypeCode", "VAT")?;
+                                text(writer, "ram:CategoryCode", line.tax_category.code())?;
+                                text(writer, "ram:RateApplicablePercent", &percent(line.tax_rate))
+                            })?;
+                        if line.allowance != 0 {
+                            writer
+                                .create_element("ram:SpecifiedTradeAllowanceCharge")
+                                .write_inner_content(|writer| {
+                                    allowance_charge(writer, line.allowance)
+                                })?;
+                        }
+                        writer
+                            .create_element("ram:SpecifiedTradeSettlementLineMonetarySummation")
+                            .write_inner_content(|writer| {
+                                text(writer, "ram:LineTotalAmount", &money(line.net))
+                            })?;
+                        Ok(())
+                    })?;
+                Ok(())
+            })?;
+    }
+    Ok(())
+}
+
+fn agreement(writer: &mut XmlWriter, invoice: &EInvoice) -> io::Result<()> {
+    writer
+        .create_element("ram:ApplicableHeaderTradeAgreement")
+        .write_inner_content(|writer| {
+            text(writer, "ram:BuyerReference", &invoice.buyer_reference)?;
+            writer
+                .create_element("ram:SellerTradeParty")
+                .write_inner_content(|writer| party(writer, &invoice.seller))?;
+            writer
+                .create_element("ram:BuyerTradeParty")
+                .write_inner_content(|writer| party(writer, &invoice.buyer))?;
+            if !invoice.order_reference.is_empty() {
+                writer
+                    .create_element("ram:BuyerOrderReferencedDocument")
+                    .write_inner_content(|writer| {
+                        text(writer, "ram:IssuerAssignedID", &invoice.order_reference)
+                    })?;
+            }
+            Ok(())
+        })?;
+    Ok(())
+}
+
+fn settlement(writer: &mut XmlWriter, invoice: &EInvoice) -> io::Result<()> {
+    writer
+        .create_element("ram:ApplicableHeaderTradeSettlement")
+        .write_inner_content(|writer| {
+            text(writer, "ram:InvoiceCurrencyCode", &invoice.currency)?;
+
+            if let Some(payment) = &invoice.payment {
+                writer
+                    .create_element("ram:SpecifiedTradeSettlementPaymentMeans")
+                    .write_inner_content(|writer| {
+                        text(writer, "ram:TypeCode", PAYMENT_MEANS_CODE)?;
+                        writer
+                            .create_element("ram:PayeePartyCreditorFinancialAccount")
+                            .write_inner_content(|writer| {
+                                text(writer, "ram:IBANID", &payment.iban)?;
+                                text(writer, "ram:AccountName", &payment.name)
+                            })?;
+                        if !payment.bic.is_empty() {
+                            writer
+                                .create_element("ram:PayeeSpecifiedCreditorFinancialInstitution")
+                                .write_inner_content(|writer| {
+                                    text(writer, "ram:BICID", &payment.bic)
+                                })?;
+                        }
+                        Ok(())
+                    })?;
+            }
+
+            for tax in invoice.taxes.iter() {
+                writer
+                    .create_element("ram:ApplicableTradeTax")
+                    .write_inner_content(|writer| {
+                        text(writer, "ram:CalculatedAmount", &money(tax.amount))?;
+                        text(writer, "ram:TypeCode", "VAT")?;
+                        text(writer, "ram:ExemptionReason", &tax.exemption_reason)?;
+                        text(writer, "ram:BasisAmount", &money(tax.base))?;
+                        text(writer, "ram:CategoryCode", tax.category.code())?;
+                        text(writer, "ram:RateApplicablePercent", &percent(tax.rate))
+                    })?;
+            }
+
+            for allowance in invoice.allowances.iter() {
+                writer
+                    .create_element("ram:SpecifiedTradeAllowanceCharge")
+                    .write_inner_content(|writer| {
+                        allowance_charge(writer, allowance.amount)?;
+                        writer
+                            .create_element("ram:CategoryTradeTax")
+                            .write_inner_content(|writer| {
+                                text(writer, "ram:TypeCode", "VAT")?;
+                                text(writer, "ram:CategoryCode", allowance.category.code())?;
+                                text(
+                                    writer,
+                                    "ram:RateApplicablePercent",
+                                    &percent(allowance.rate),
+                                )
+                            })?;
+                        Ok(())
+                    })?;
+            }
+
+            writer
+                .create_element("ram:SpecifiedTradeSettlementHeaderMonetarySummation")
+                .write_inner_content(|writer| {
+                    text(writer, "ram:LineTotalAmount", &money(invoice.line_total))?;
+                    if invoice.allowance_total != 0 {
+                        text(
+                            writer,
+                            "ram:AllowanceTotalAmount",
+                            &money(invoice.allowance_total),
+                        )?;
+                    }
+                    text(
+                        writer,
+                        "ram:TaxBasisTotalAmount",
+                        &money(invoice.tax_exclusive_total()),
+                    )?;
+                    amount(
+                        writer,
+                        "ram:TaxTotalAmount",
+                        &invoice.currency,
+                        invoice.tax_total,
+                    )?;
+                    text(writer, "ram:GrandTotalAmount", &money(invoice.grand_total))?;
+                    text(writer, "ram:DuePayableAmount", &money(invoice.payable))
+                })?;
+            Ok(())
+        })?;
+    Ok(())
+}
+
+/// Writes the elements an allowance on document and line level share.
+fn allowance_charge(writer: &mut XmlWriter, cents: i64) -> io::Result<()> {
+    writer
+        .create_element("ram:ChargeIndicator")
+        .write_inner_content(|writer| text(writer, "udt:Indicator", "false"))?;
+    text(writer, "ram:ActualAmount", &money(cents))?;
+    text(writer, "ram:ReasonCode", ALLOWANCE_REASON_CODE)?;
+    text(writer, "ram:Reason", ALLOWANCE_REASON)
+}
+
+fn party(writer: &mut XmlWriter, party: &Party) -> io::Result<()> {
+    text(writer, "ram:Name", &party.name)?;
+    if !party.phone.is_empty() || !party.email.is_empty() {
+        writer
+            .create_element("ram:DefinedTradeContact")
+            .write_inner_content(|writer| {
+                text(writer, "ram:PersonName", &party.name)?;
+                if !party.phone.is_empty() {
+                    writer
+                        .create_element("ram:TelephoneUniversalCommunication")
+                        .write_inner_content(|writer| {
+                            text(writer, "ram:CompleteNumber", &party.phone)
+                        })?;
+                }
+                if !party.email.is_empty() {
+                    writer
+                        .create_element("ram:EmailURIUniversalCommunication")
+                        .write_inner_content(|writer| text(writer, "ram:URIID", &party.email))?;
+                }
+                Ok(())
+            })?;
+    }
+    writer
+        .create_element("ram:PostalTradeAddress")
+        .write_inner_content(|writer| {
+            text(writer, "ram:PostcodeCode", &party.postal_code)?;
+            text(writer, "ram:LineOne", &party.street1)?;
+            text(writer, "ram:LineTwo", &party.street2)?;
+            text(writer, "ram:CityName", &party.city)?;
+            text(writer, "ram:CountryID", &party.country_code)
+        })?;
+    if !party.email.is_empty() {
+        writer
+            .create_element("ram:URIUniversalCommunication")
+            .write_inner_content(|writer| {
+                writer
+                    .create_element("ram:URIID")
+                    .with_attribute(("schemeID", "EM"))
+                    .write_text_content(BytesText::new(&party.email))?;
+                Ok(())
+            })?;
+    }
+    if !party.vat_id.is_empty() {
+        writer
+            .create_element("ram:SpecifiedTaxRegistration")
+            .write_inner_content(|writer| {
+                writer
+                    .create_element("ram:ID")
+                    .with_attribute(("schemeID", "VA"))
+                    .write_text_content(BytesText::new(&party.vat_id))?;
+                Ok(())
+            })?;
+    }
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use chrono::{DateTime, Utc};
+use printpdf::lopdf::{Document, Error, Object, Stream, dictionary};
+
+/// The file name mandated by Factur-X 1.0 / ZUGFeRD 2.x.
+pub const FILE_NAME: &str = "factur-x.xml";
+
+/// Names of the embedded invoice in older ZUGFeRD versions and in XRechnung
+/// hybrids, which are accepted on import.
+const LEGACY_FILE_NAMES: [&str; 3] = [
+    "zugferd-invoice.xml",
+    "ZUGFeRD-invoice.xml",
+    "xrechnung.xml",
+];
+
+/// Conformance level written to the XMP metadata for the CII we generate.
+pub const CONFORMANCE_LEVEL: &str = "EN 16931";
+
+/// Turns a PDF/A-3 document into a Factur-X invoice: the XML is embedded as
+/// associated file, the XMP metadata is replaced by the given packet which
+/// carries the Factur-X extension schema, and the output intent written by
+/// printpdf (which targets PDF/X) is relabelled for PDF/A.
+pub fn attach_factur_x(
+    pdf: Vec<u8>,
+    xml: &[u8],
+    metadata: &[u8],
+    date: DateTime<Utc>,
+) -> Result<Vec<u8>, Error> {
+    let mut doc = Document::load_mem(&pdf)?;
+    let pdf_date = Object::string_literal(date.format("D:%Y%m%d%H%M%S+00'00'").to_string());
+
+    let embedded_id = doc.add_object(Stream::new(
+        dictionary! {
+            "Type" => "EmbeddedFile",
+            "Subtype" => Object::Name(b"text/xml".to_vec()),
+            "Params" => dictionary! {
+                "Size" => xml.len() as i64,
+                "ModDate" => pdf_date.clone(),
+            },
+        },
+        xml.to_vec(),
+    ));
+    let filespec_id = doc.add_object(dictionary! {
+        "Type" => "Filespec",
+        "F" => Object::string_literal(FILE_NAME),
+        "UF" => Object::string_literal(FILE_NAME),
+        "Desc" => Object::string_literal("Factur-X invoice"),
+        "AFRelationship" => "Data",
+        "EF" => dictionary! {
+            "F" => embedded_id,
+            "UF" => embedded_id,
+        },
+    });
+    let metadata_id = doc.add_object(Stream::new(
+        dictionary! {
+            "Type" => "Metadata",
+            "Subtype" => "XML",
+        },
+        metadata.to_vec(),
+    ));
+
+    if let Ok(info_id) = doc.trailer.get(b"Info").and_then(Object::as_reference) {
+        let info = doc.get_dictionary_mut(info_id)?;
+        info.remove(b"GTS_PDFXVersion");
+        info.set("CreationDate", pdf_date.clone());
+        info.set("ModDate", pdf_date);
+    }
+
+    let catalog = doc.catalog_mut()?;
+    catalog.set(
+        "Names",
+        dictionary! {
+            "EmbeddedFiles" => dictionary! {
+                "Names" => vec![Object::string_literal(FILE_NAME), filespec_id.into()],
+            },
+        },
+    );
+    catalog.set("AF", vec![Object::Reference(filespec_id)]);
+    catalog.set("Metadata", metadata_id);
+    if let Ok(intents) = catalog
+        .get_mut(b"OutputIntents")
+        .and_then(Object::as_array_mut)
+    {
+        for intent in intents.iter_mut() {
+            if let Ok(intent) = intent.as_dict_mut() {
+                intent.set("S", "GTS_PDFA1");
+            }
+        }
+    }
+
+    doc.version = "1.7".to_string();
+    let mut output = Vec::new();
+    doc.save_to(&mut output)?;
+    Ok(output)
+}
+
+/// Returns the invoice XML embedded in a Factur-X / ZUGFeRD PDF, or `None` if
+/// the PDF can't be read or has no such attachment.
+pub fn extract_xml(pdf: &[u8]) -> Option<Vec<u8>> {
+    let doc = Document::load_mem(pdf).ok()?;
+    let names = doc
+        .catalog()
+        .ok()?
+        .get_deref(b"Names", &doc)
+        .and_then(Object::as_dict)
+        .ok()?
+        .get_deref(b"EmbeddedFiles", &doc)
+        .and_then(Object::as_dict)
+        .ok()?
+        .get_deref(b"Names", &doc)
+        .and_then(Object::as_array)
+        .ok()?;
+
+    for pair in names.chunks(2) {
+        let [name, filespec] = pair else {
+            continue;
+        };
+        let Ok(name) = name.as_str() else {
+            continue;
+        };
+        if name != FILE_NAME.as_bytes() && !LEGACY_FILE_NAMES.iter().any(|n| name == n.as_bytes()) {
+            continue;
+        }
+
+        let stream = doc
+            .dereference(filespec)
+            .and_then(|(_, filespec)| filespec.as_dict())
+            .and_then(|filespec| filespec.get_deref(b"EF", &doc))
+            .and_then(Object::as_dict)
+            .and_then(|files| files.get_deref(b"F", &doc))
+            .and_then(Object::as_stream)
+            .ok()?;
+
+        let mut stream = stream.clone();
+        stream.decompress();
+        return Some(stream.content);
+    }
+
+    None
+}
@@ -0,0 +1,348 @@
+mod cii;
+mod factur_x;
+mod parse;
+mod rules;
+mod ubl;
+
+pub use factur_x::{CONFORMANCE_LEVEL, FILE_NAME, attach_factur_x, extract_xml};
+pub use parse::ParseError;
+pub use rules::RuleViolation;
+
+use std::io::{self, Cursor};
+
+use quick_xml::{Writer, events::BytesText};
+
+use super::{PrintableDocument, money};
+
+pub const CURRENCY: &str = "EUR";
+
+/// UN/ECE rec 20 code for "one", used for all quantities until variants
+/// carry their own unit.
+const UNIT_CODE: &str = "C62";
+
+/// Discount (UNTDID 5189), the reason of all allowances we write.
+const ALLOWANCE_REASON_CODE: &str = "95";
+const ALLOWANCE_REASON: &str = "Discount";
+
+/// Commercial invoice (UNTDID 1001).
+pub const TYPE_CODE_INVOICE: &str = "380";
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Syntax {
+    /// UBL 2.1 following the XRechnung CIUS.
+    XRechnung,
+    /// UN/CEFACT CII following the Factur-X EN 16931 profile.
+    FacturX,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TaxCategory {
+    Standard,
+    ZeroRated,
+    Exempt,
+    ReverseCharge,
+    IntraCommunity,
+    Export,
+    OutOfScope,
+}
+
+impl TaxCategory {
+    /// UNTDID 5305 code of the category.
+    pub fn code(&self) -> &'static str {
+        match self {
+            TaxCategory::Standard => "S",
+            TaxCategory::ZeroRated => "Z",
+            TaxCategory::Exempt => "E",
+            TaxCategory::ReverseCharge => "AE",
+            TaxCategory::IntraCommunity => "K",
+            TaxCategory::Export => "G",
+            TaxCategory::OutOfScope => "O",
+        }
+    }
+
+    /// The category of a line whose tax only is known by its rate.
+    pub fn for_rate(rate: f64) -> Self {
+        if rate > 0.0 {
+            TaxCategory::Standard
+        } else {
+            TaxCategory::ZeroRated
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "S" => Some(TaxCategory::Standard),
+            "Z" => Some(TaxCategory::ZeroRated),
+            "E" => Some(TaxCategory::Exempt),
+            "AE" => Some(TaxCategory::ReverseCharge),
+            "K" => Some(TaxCategory::IntraCommunity),
+            "G" => Some(TaxCategory::Export),
+            "O" => Some(TaxCategory::OutOfScope),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Party {
+    pub name: String,
+    pub street1: String,
+    pub street2: String,
+    pub postal_code: String,
+    pub city: String,
+    pub country_code: String,
+    pub vat_id: String,
+    pub email: String,
+    pub phone: String,
+}
+
+pub struct PaymentAccount {
+    pub iban: String,
+    pub bic: String,
+    pub name: String,
+}
+
+pub struct EInvoiceLine {
+    pub id: String,
+    pub name: String,
+    pub quantity: f64,
+    pub unit_code: String,
+    /// The net price of one unit, whatever base quantity (BT-149) the
+    /// invoice gives it for.
+    pub price: i64,
+    /// The discount of the line (BT-136), already taken off the net.
+    pub allowance: i64,
+    pub net: i64,
+    pub tax_category: TaxCategory,
+    pub tax_rate: f64,
+}
+
+/// A discount on document level (BG-20). It reduces the taxable amount of
+/// one tax category and rate, so a discount spread over lines of different
+/// rates is given once per rate.
+pub struct EInvoiceAllowance {
+    pub amount: i64,
+    pub category: TaxCategory,
+    pub rate: f64,
+}
+
+pub struct EInvoiceTax {
+    pub category: TaxCategory,
+    pub rate: f64,
+    pub base: i64,
+    pub amount: i64,
+    /// Why the amount is not taxed at the standard rate (BT-120).
+    pub exemption_reason: String,
+}
+
+/// The EN 16931 semantic model of an invoice, independent of the syntax it is
+/// written in. Amounts are in cents and rates are fractions like on `taxes`.
+pub struct EInvoice {
+    pub number: String,
+    pub issue_date: String,
+    pub type_code: String,
+    pub currency: String,
+    pub buyer_reference: String,
+    pub order_reference: String,
+    pub seller: Party,
+    pub buyer: Party,
+    pub payment: Option<PaymentAccount>,
+    pub lines: Vec<EInvoiceLine>,
+    pub allowances: Vec<EInvoiceAllowance>,
+    pub taxes: Vec<EInvoiceTax>,
+    pub line_total: i64,
+    pub allowance_total: i64,
+    pub tax_total: i64,
+    pub grand_total: i64,
+    pub payable: i64,
+}
+
+impl EInvoice {
+    /// Builds the e-invoice from a rendered invoice. The order reference is
+    /// used as buyer reference, because contacts don't store one yet.
+    pub fn from_document(document: &PrintableDocument, order_reference: &str) -> Self {
+        let letterhead = &document.letterhead;
+        let recipient = &document.recipient;
+
+        let lines: Vec<EInvoiceLine> = document
+            .lines
+            .iter()
+            .map(|line| EInvoiceLine {
+                id: line.position.to_string(),
+                name: line.description.clone(),
+                quantity: line.quantity,
+                unit_code: UNIT_CODE.to_string(),
+                price: line.price,
+                allowance: line.discount,
+                net: line.net,
+                tax_category: line.tax_category,
+                tax_rate: line.tax_rate,
+            })
+            .collect();
+
+        let mut allowances: Vec<EInvoiceAllowance> = Vec::new();
+        for line in document
+            .lines
+            .iter()
+            .filter(|line| line.document_discount != 0)
+        {
+            let existing = allowances.iter_mut().find(|allowance| {
+                allowance.category == line.tax_category && allowance.rate == line.tax_rate
+            });
+            match existing {
+                Some(allowance) => allowance.amount += line.document_discount,
+                None => allowances.push(EInvoiceAllowance {
+                    amount: line.document_discount,
+                    category: line.tax_category,
+                    rate: line.tax_rate,
+                }),
+            }
+        }
+
+        let mut taxes: Vec<EInvoiceTax> = Vec::new();
+        for line in document.lines.iter() {
+            let base = line.net - line.document_discount;
+            let existing = taxes
+                .iter_mut()
+                .find(|tax| tax.category == line.tax_category && tax.rate == line.tax_rate);
+            match existing {
+                Some(tax) => tax.base += base,
+                None => taxes.push(EInvoiceTax {
+                    category: line.tax_category,
+                    rate: line.tax_rate,
+                    base,
+                    amount: 0,
+                    exemption_reason: String::new(),
+                }),
+            }
+        }
+        for tax in taxes.iter_mut() {
+            tax.amount = (tax.base as f64 * tax.rate).round() as i64;
+            tax.exemption_reason = document
+                .taxes
+                .iter()
+                .find(|other| other.category == tax.category && other.rate == tax.rate)
+                .map(|other| other.note.clone())
+                .unwrap_or_default();
+        }
+
+        let line_total = lines.iter().map(|line| line.net).sum();
+        let allowance_total = allowances.iter().map(|allowance| allowance.amount).sum();
+        let tax_total = taxes.iter().map(|tax| tax.amount).sum();
+        let grand_total = line_total - allowance_total + tax_total;
+
+        let payment = match letterhead.iban.is_empty() {
+            true => None,
+            false => Some(PaymentAccount {
+                iban: letterhead.iban.clone(),
+                bic: letterhead.bic.clone(),
+                name: letterhead.name.clone(),
+            }),
+        };
+
+        Self {
+            number: document.reference.clone(),
+            issue_date: document.date.clone(),
+            type_code: TYPE_CODE_INVOICE.to_string(),
+            currency: CURRENCY.to_string(),
+            buyer_reference: order_reference.to_string(),
+            order_reference: order_reference.to_string(),
+            seller: Party {
+                name: letterhead.name.clone(),
+                street1: letterhead.street1.clone(),
+                street2: letterhead.street2.clone(),
+                postal_code: letterhead.postal_code.clone(),
+                city: letterhead.city.clone(),
+                country_code: letterhead.country.clone(),
+                vat_id: letterhead.vat_id.clone(),
+                email: letterhead.email.clone(),
+                phone: letterhead.phone.clone(),
+            },
+            buyer: Party {
+                name: recipient.name.clone(),
+                street1: recipient.street1.clone(),
+                street2: recipient.street2.clone(),
+                postal_code: recipient.postal_code.clone(),
+                city: recipient.city.clone(),
+                country_code: recipient.country.clone(),
+                vat_id: recipient.vat_id.clone(),
+                email: String::new(),
+                phone: String::new(),
+            },
+            payment,
+            lines,
+            allowances,
+            taxes,
+            line_total,
+            allowance_total,
+            tax_total,
+            grand_total,
+            payable: grand_total,
+        }
+    }
+
+    /// The invoice total without VAT (BT-109).
+    pub fn tax_exclusive_total(&self) -> i64 {
+        self.line_total - self.allowance_total
+    }
+
+    /// Reads a UBL or CII invoice as sent by a supplier.
+    pub fn parse(xml: &[u8]) -> Result<Self, ParseError> {
+        parse::parse(xml)
+    }
+
+    /// Checks the EN 16931 business rules, and the XRechnung rules on top if
+    /// that syntax is requested.
+    pub fn validate(&self, syntax: Syntax) -> Vec<RuleViolation> {
+        rules::validate(self, syntax)
+    }
+
+    pub fn to_xml(&self, syntax: Syntax) -> io::Result<Vec<u8>> {
+        match syntax {
+            Syntax::XRechnung => ubl::write(self),
+            Syntax::FacturX => cii::write(self),
+        }
+    }
+}
+
+/// Formats a rate fraction as percentage with at most two decimals.
+fn percent(rate: f64) -> String {
+    let value = (rate * 10000.0).round() / 100.0;
+    trim_decimals(format!("{value:.2}"))
+}
+
+fn quantity(value: f64) -> String {
+    trim_decimals(format!("{value:.4}"))
+}
+
+fn trim_decimals(value: String) -> String {
+    value
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+type XmlWriter = Writer<Cursor<Vec<u8>>>;
+
+fn xml_writer() -> XmlWriter {
+    Writer::new_with_indent(Cursor::new(Vec::new()), b' ', 2)
+}
+
+/// Writes `<name>value</name>`, or nothing if the value is empty, because
+/// empty elements are rejected by the schematron of both syntaxes.
+fn text(writer: &mut XmlWriter, name: &str, value: &str) -> io::Result<()> {
+    if !value.is_empty() {
+        writer
+            .create_element(name)
+            .write_text_content(BytesText::new(value))?;
+    }
+    Ok(())
+}
+
+fn amount(writer: &mut XmlWriter, name: &str, currency: &str, cents: i64) -> io::Result<()> {
+    writer
+        .create_element(name)
+        .with_attribute(("currencyID", currency))
+        .write_text_content(BytesText::new(&money(cents)))?;
+    Ok(())
+}
@@ -0,0 +1,462 @@
+use chrono::NaiveDate;
+use roxmltree::{Document, Node};
+
+use super::{
+    EInvoice, EInvoiceAllowance, EInvoiceLine, EInvoiceTax, Party, PaymentAccount, TaxCategory,
+};
+
+const UBL_INVOICE_NAMESPACE: &str = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2";
+const CII_NAMESPACE: &str = "urn:un:unece:uncefact:data:standard:CrossIndustryInvoice:100";
+
+#[derive(Debug)]
+pub enum ParseError {
+    Xml(roxmltree::Error),
+    /// The XML is neither a UBL invoice nor a CII invoice.
+    UnsupportedDocument,
+    /// A mandatory element is missing, named by its EN 16931 business term.
+    Missing(&'static str),
+    /// An element has a value that can't be interpreted.
+    Invalid(&'static str),
+}
+
+/// Reads a UBL 2.1 or CII invoice into the semantic model. Only what we can
+/// store is read, everything else in the document is ignored.
+pub fn parse(xml: &[u8]) -> Result<EInvoice, ParseError> {
+    let xml = std::str::from_utf8(xml).map_err(|_| ParseError::Invalid("encoding"))?;
+    let xml = xml.trim_start_matches('\u{feff}');
+    let doc = Document::parse(xml).map_err(ParseError::Xml)?;
+    let root = doc.root_element();
+
+    match (root.tag_name().namespace(), root.tag_name().name()) {
+        (Some(UBL_INVOICE_NAMESPACE), "Invoice") => parse_ubl(root),
+        (Some(CII_NAMESPACE), "CrossIndustryInvoice") => parse_cii(root),
+        _ => Err(ParseError::UnsupportedDocument),
+    }
+}
+
+fn parse_ubl(root: Node) -> Result<EInvoice, ParseError> {
+    let currency = text(root, &["DocumentCurrencyCode"]);
+
+    let mut lines = Vec::new();
+    for line in children(root, "InvoiceLine") {
+        let category = node(line, &["Item", "ClassifiedTaxCategory"]);
+        lines.push(EInvoiceLine {
+            id: text(line, &["ID"]),
+            name: text(line, &["Item", "Name"]),
+            quantity: number(line, &["InvoicedQuantity"], "BT-129")?,
+            unit_code: attribute(line, &["InvoicedQuantity"], "unitCode"),
+            price: unit_price(line, &["Price", "PriceAmount"], &["Price", "BaseQuantity"])?,
+            allowance: ubl_allowances(line)
+                .map(|allowance| cents(allowance, &["Amount"], "BT-136"))
+                .sum::<Result<i64, ParseError>>()?,
+            net: cents(line, &["LineExtensionAmount"], "BT-131")?,
+            tax_category: tax_category(category, &["ID"])?,
+            tax_rate: rate(category, &["Percent"])?,
+        });
+    }
+
+    let mut allowances = Vec::new();
+    for allowance in ubl_allowances(root) {
+        let category = node(allowance, &["TaxCategory"]);
+        allowances.push(EInvoiceAllowance {
+            amount: cents(allowance, &["Amount"], "BT-92")?,
+            category: tax_category(category, &["ID"])?,
+            rate: rate(category, &["Percent"])?,
+        });
+    }
+
+    let mut taxes = Vec::new();
+    for total in children(root, "TaxTotal") {
+        for subtotal in children(total, "TaxSubtotal") {
+            let category = node(subtotal, &["TaxCategory"]);
+            taxes.push(EInvoiceTax {
+                category: tax_category(category, &["ID"])?,
+                rate: rate(category, &["Percent"])?,
+                base: cents(subtotal, &["TaxableAmount"], "BT-116")?,
+                amount: cents(subtotal, &["TaxAmount"], "BT-117")?,
+                exemption_reason: category
+                    .map(|category| text(category, &["TaxExemptionReason"]))
+                    .unwrap_or_default(),
+            });
+        }
+    }
+
+    let account = node(root, &["PaymentMeans", "PayeeFinancialAccount"]);
+    let payment = account.map(|account| PaymentAccount {
+        iban: text(account, &["ID"]),
+        bic: text(account, &["FinancialInstitutionBranch", "ID"]),
+        name: text(account, &["Name"]),
+    });
+
+    // Only the tax total in document currency has subtotals, a second one
+    // may carry the amount in accounting currency.
+    let tax_total = children(root, "TaxTotal")
+        .find(|total| children(*total, "TaxSubtotal").next().is_some())
+        .map(|total| cents(total, &["TaxAmount"], "BT-110"))
+        .transpose()?
+        .unwrap_or(0);
+
+    Ok(EInvoice {
+        number: required(text(root, &["ID"]), "BT-1")?,
+        issue_date: required(text(root, &["IssueDate"]), "BT-2")?,
+        type_code: text(root, &["InvoiceTypeCode"]),
+        currency,
+        buyer_reference: text(root, &["BuyerReference"]),
+        order_reference: text(root, &["OrderReference", "ID"]),
+        seller: ubl_party(node(root, &["AccountingSupplierParty", "Party"])),
+        buyer: ubl_party(node(root, &["AccountingCustomerParty", "Party"])),
+        payment,
+        lines,
+        allowances,
+        taxes,
+        line_total: cents(
+            root,
+            &["LegalMonetaryTotal", "LineExtensionAmount"],
+            "BT-106",
+        )?,
+        allowance_total: optional_cents(
+            root,
+            &["LegalMonetaryTotal", "AllowanceTotalAmount"],
+            "BT-107",
+        )?,
+        tax_total,
+        grand_total: cents(
+            root,
+            &["LegalMonetaryTotal", "TaxInclusiveAmount"],
+            "BT-112",
+        )?,
+        payable: cents(root, &["LegalMonetaryTotal", "PayableAmount"], "BT-115")?,
+    })
+}
+
+/// The allowances among the allowances and charges of a document or line.
+/// Charges aren't stored and therefore not read.
+fn ubl_allowances<'a, 'input>(parent: Node<'a, 'input>) -> impl Iterator<Item = Node<'a, 'input>> {
+    children(parent, "AllowanceCharge")
+        .filter(|allowance| text(*allowance, &["ChargeIndicator"]) == "false")
+}
+
+fn ubl_party(party: Option<Node>) -> Party {
+    let Some(party) = party else {
+        return Party::default();
+    };
+
+    let mut name = text(party, &["PartyLegalEntity", "RegistrationName"]);
+    if name.is_empty() {
+        name = text(party, &["PartyName", "Name"]);
+    }
+
+    let vat_id = children(party, "PartyTaxScheme")
+        .find(|scheme| text(*scheme, &["TaxScheme", "ID"]) == "VAT")
+        .map(|scheme| text(scheme, &["CompanyID"]))
+        .unwrap_or_default();
+
+    Party {
+        name,
+        street1: text(party, &["PostalAddress", "StreetName"]),
+        street2: text(party, &["PostalAddress", "AdditionalStreetName"]),
+        postal_code: text(party, &["PostalAddress", "PostalZone"]),
+        city: text(party, &["PostalAddress", "CityName"]),
+        country_code: text(party, &["PostalAddress", "Country", "IdentificationCode"]),
+        vat_id,
+        email: text(party, &["Contact", "ElectronicMail"]),
+        phone: text(party, &["Contact", "Telephone"]),
+    }
+}
+
+fn parse_cii(root: Node) -> Result<EInvoice, ParseError> {
+    let transaction =
+        node(root, &["SupplyChainTradeTransaction"]).ok_or(ParseError::Missing("BG-25"))?;
+    let agreement = node(transaction, &["ApplicableHeaderTradeAgreement"]);
+    let settlement = node(transaction, &["ApplicableHeaderTradeSettlement"])
+        .ok_or(ParseError::Missing("BG-22"))?;
+    let summation = node(
+        settlement,
+        &["SpecifiedTradeSettlementHeaderMonetarySummation"],
+    )
+    .ok_or(ParseError::Missing("BG-22"))?;
+
+    let mut lines = Vec::new();
+    for line in children(transaction, "IncludedSupplyChainTradeLineItem") {
+        let tax = node(
+            line,
+            &["SpecifiedLineTradeSettlement", "ApplicableTradeTax"],
+        );
+        lines.push(EInvoiceLine {
+            id: text(line, &["AssociatedDocumentLineDocument", "LineID"]),
+            name: text(line, &["SpecifiedTradeProduct", "Name"]),
+            quantity: number(
+                line,
+                &["SpecifiedLineTradeDelivery", "BilledQuantity"],
+                "BT-129",
+            )?,
+            unit_code: attribute(
+                line,
+                &["SpecifiedLineTradeDelivery", "BilledQuantity"],
+                "unitCode",
+            ),
+            price: unit_price(
+                line,
+                &[
+                    "SpecifiedLineTradeAgreement",
+                    "NetPriceProductTradePrice",
+                    "ChargeAmount",
+                ],
+                &[
+                    "SpecifiedLineTradeAgreement",
+                    "NetPriceProductTradePrice",
+                    "BasisQuantity",
+                ],
+            )?,
+            allowance: node(line, &["SpecifiedLineTradeSettlement"])
+                .map(|settlement| {
+                    cii_allowances(settlement)
+                        .map(|allowance| cents(allowance, &["ActualAmount"], "BT-136"))
+                        .sum::<Result<i64, ParseError>>()
+                })
+                .transpose()?
+                .unwrap_or(0),
+            net: cents(
+                line,
+                &[
+                    "SpecifiedLineTradeSettlement",
+                    "SpecifiedTradeSettlementLineMonetarySummation",
+                    "LineTotalAmount",
+                ],
+                "BT-131",
+            )?,
+            tax_category: tax_category(tax, &["CategoryCode"])?,
+            tax_rate: rate(tax, &["RateApplicablePercent"])?,
+        });
+    }
+
+    let mut allowances = Vec::new();
+    for allowance in cii_allowances(settlement) {
+        let category = node(allowance, &["CategoryTradeTax"]);
+        allowances.push(EInvoiceAllowance {
+            amount: cents(allowance, &["ActualAmount"], "BT-92")?,
+            category: tax_category(category, &["CategoryCode"])?,
+            rate: rate(category, &["RateApplicablePercent"])?,
+        });
+    }
+
+    let mut taxes = Vec::new();
+    for tax in children(settlement, "ApplicableTradeTax") {
+        taxes.push(EInvoiceTax {
+            category: tax_category(Some(tax), &["CategoryCode"])?,
+            rate: rate(Some(tax), &["RateApplicablePercent"])?,
+            base: cents(tax, &["BasisAmount"], "BT-116")?,
+            amount: cents(tax, &["CalculatedAmount"], "BT-117")?,
+            exemption_reason: text(tax, &["ExemptionReason"]),
+        });
+    }
+
+    let payment = node(
+        settlement,
+        &[
+            "SpecifiedTradeSettlementPaymentMeans",
+            "PayeePartyCreditorFinancialAccount",
+        ],
+    )
+    .map(|account| PaymentAccount {
+        iban: text(account, &["IBANID"]),
+        bic: text(
+            settlement,
+            &[
+                "SpecifiedTradeSettlementPaymentMeans",
+                "PayeeSpecifiedCreditorFinancialInstitution",
+                "BICID",
+            ],
+        ),
+        name: text(account, &["AccountName"]),
+    });
+
+    let issue_date = text(
+        root,
+        &["ExchangedDocument", "IssueDateTime", "DateTimeString"],
+    );
+    let issue_date = NaiveDate::parse_from_str(&required(issue_date, "BT-2")?, "%Y%m%d")
+        .map_err(|_| ParseError::Invalid("BT-2"))?
+        .format("%Y-%m-%d")
+        .to_string();
+
+    let tax_total = children(summation, "TaxTotalAmount")
+        .next()
+        .map(|total| cents(total, &[], "BT-110"))
+        .transpose()?
+        .unwrap_or(0);
+
+    Ok(EInvoice {
+        number: required(text(root, &["ExchangedDocument", "ID"]), "BT-1")?,
+        issue_date,
+        type_code: text(root, &["ExchangedDocument", "TypeCode"]),
+        currency: text(settlement, &["InvoiceCurrencyCode"]),
+        buyer_reference: agreement
+            .map(|agreement| text(agreement, &["BuyerReference"]))
+            .unwrap_or_default(),
+        order_reference: agreement
+            .map(|agreement| {
+                text(
+                    agreement,
+                    &["BuyerOrderReferencedDocument", "IssuerAssignedID"],
+                )
+            })
+            .unwrap_or_default(),
+        seller: cii_party(agreement.and_then(|agreement| node(agreement, &["SellerTradeParty"]))),
+        buyer: cii_party(agreement.and_then(|agreement| node(agreement, &["BuyerTradeParty"]))),
+        payment,
+        lines,
+        allowances,
+        taxes,
+        line_total: cents(summation, &["LineTotalAmount"], "BT-106")?,
+        allowance_total: optional_cents(summation, &["AllowanceTotalAmount"], "BT-107")?,
+        tax_total,
+        grand_total: cents(summation, &["GrandTotalAmount"], "BT-112")?,
+        payable: cents(summation, &["DuePayableAmount"], "BT-115")?,
+    })
+}
+
+/// The allowances among the allowances and charges of the settlement of a
+/// document or line.
+fn cii_allowances<'a, 'input>(
+    settlement: Node<'a, 'input>,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    children(settlement, "SpecifiedTradeAllowanceCharge")
+        .filter(|allowance| text(*allowance, &["ChargeIndicator", "Indicator"]) == "false")
+}
+
+fn cii_party(party: Option<Node>) -> Party {
+    let Some(party) = party else {
+        return Party::default();
+    };
+
+    let vat_id = children(party, "SpecifiedTaxRegistration")
+        .filter_map(|registration| node(registration, &["ID"]))
+        .find(|id| id.attribute("schemeID") == Some("VA"))
+        .and_then(|id| id.text())
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+
+    Party {
+        name: text(party, &["Name"]),
+        street1: text(party, &["PostalTradeAddress", "LineOne"]),
+        street2: text(party, &["PostalTradeAddress", "LineTwo"]),
+        postal_code: text(party, &["PostalTradeAddress", "PostcodeCode"]),
+        city: text(party, &["PostalTradeAddress", "CityName"]),
+        country_code: text(party, &["PostalTradeAddress", "CountryID"]),
+        vat_id,
+        email: text(
+            party,
+            &[
+                "DefinedTradeContact",
+                "EmailURIUniversalCommunication",
+                "URIID",
+            ],
+        ),
+        phone: text(
+            party,
+            &[
+                "DefinedTradeContact",
+                "TelephoneUniversalCommunication",
+                "CompleteNumber",
+            ],
+        ),
+    }
+}
+
+fn children<'a, 'input>(
+    node: Node<'a, 'input>,
+    name: &'static str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children()
+        .filter(move |child| child.is_element() && child.tag_name().name() == name)
+}
+
+/// Follows a path of element names, ignoring namespaces because both syntaxes
+/// use prefixes inconsistently in the wild.
+fn node<'a, 'input>(node: Node<'a, 'input>, path: &[&'static str]) -> Option<Node<'a, 'input>> {
+    path.iter()
+        .try_fold(node, |node, name| children(node, name).next())
+}
+
+fn text(parent: Node, path: &[&'static str]) -> String {
+    node(parent, path)
+        .and_then(|node| node.text())
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+fn attribute(parent: Node, path: &[&'static str], name: &str) -> String {
+    node(parent, path)
+        .and_then(|node| node.attribute(name))
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn required(value: String, term: &'static str) -> Result<String, ParseError> {
+    match value.is_empty() {
+        true => Err(ParseError::Missing(term)),
+        false => Ok(value),
+    }
+}
+
+fn number(parent: Node, path: &[&'static str], term: &'static str) -> Result<f64, ParseError> {
+    required(text(parent, path), term)?
+        .parse()
+        .map_err(|_| ParseError::Invalid(term))
+}
+
+/// Reads an amount into cents. More than two decimals are rounded, which only
+/// happens for unit prices.
+fn cents(parent: Node, path: &[&'static str], term: &'static str) -> Result<i64, ParseError> {
+    Ok((number(parent, path, term)? * 100.0).round() as i64)
+}
+
+/// Reads an amount that may be left out when it is zero.
+/// The net price (BT-146) of one unit in cents. Prices may be given for a
+/// base quantity (BT-149) of several units, e.g. per 100 pieces.
+fn unit_price(
+    parent: Node,
+    price_path: &[&'static str],
+    base_path: &[&'static str],
+) -> Result<i64, ParseError> {
+    let price = number(parent, price_path, "BT-146")?;
+    let base = match text(parent, base_path).is_empty() {
+        true => 1.0,
+        false => number(parent, base_path, "BT-149")?,
+    };
+    if base <= 0.0 {
+        return Err(ParseError::Invalid("BT-149"));
+    }
+    Ok((price * 100.0 / base).round() as i64)
+}
+
+fn optional_cents(
+    parent: Node,
+    path: &[&'static str],
+    term: &'static str,
+) -> Result<i64, ParseError> {
+    match text(parent, path).is_empty() {
+        true => Ok(0),
+        false => cents(parent, path, term),
+    }
+}
+
+fn tax_category(parent: Option<Node>, path: &[&'static str]) -> Result<TaxCategory, ParseError> {
+    let code = parent.map(|parent| text(parent, path)).unwrap_or_default();
+    TaxCategory::from_code(&required(code, "BT-151")?).ok_or(ParseError::Invalid("BT-151"))
+}
+
+/// Reads a percentage as fraction like on `taxes`. The rate may be omitted
+/// for categories which are never taxed.
+fn rate(parent: Option<Node>, path: &[&'static str]) -> Result<f64, ParseError> {
+    let value = parent.map(|parent| text(parent, path)).unwrap_or_default();
+    if value.is_empty() {
+        return Ok(0.0);
+    }
+
+    let percent: f64 = value.parse().map_err(|_| ParseError::Invalid("BT-152"))?;
+    Ok(percent / 100.0)
+}
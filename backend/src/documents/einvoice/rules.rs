@@ -0,0 +1,230 @@
+use serde::Serialize;
+
+use super::{EInvoice, Syntax, TaxCategory};
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct RuleViolation {
+    pub rule: &'static str,
+    pub message: &'static str,
+}
+
+/// Checks the subset of the EN 16931 business rules (and the German CIUS
+/// rules for XRechnung) that can be violated by the data we store. Rules
+/// which our writers guarantee by construction are not repeated here.
+pub fn validate(invoice: &EInvoice, syntax: Syntax) -> Vec<RuleViolation> {
+    let mut violations = Vec::new();
+    let mut check = |ok: bool, rule: &'static str, message: &'static str| {
+        if !ok {
+            violations.push(RuleViolation { rule, message });
+        }
+    };
+
+    check(
+        !invoice.number.trim().is_empty(),
+        "BR-02",
+        "An Invoice shall have an Invoice number",
+    );
+    check(
+        !invoice.issue_date.trim().is_empty(),
+        "BR-03",
+        "An Invoice shall have an Invoice issue date",
+    );
+    check(
+        !invoice.currency.is_empty(),
+        "BR-05",
+        "An Invoice shall have an Invoice currency code",
+    );
+    check(
+        !invoice.seller.name.trim().is_empty(),
+        "BR-06",
+        "An Invoice shall contain the Seller name",
+    );
+    check(
+        !invoice.buyer.name.trim().is_empty(),
+        "BR-07",
+        "An Invoice shall contain the Buyer name",
+    );
+    check(
+        !invoice.seller.country_code.trim().is_empty(),
+        "BR-09",
+        "The Seller postal address shall contain a Seller country code",
+    );
+    check(
+        !invoice.buyer.country_code.trim().is_empty(),
+        "BR-11",
+        "The Buyer postal address shall contain a Buyer country code",
+    );
+    check(
+        !invoice.lines.is_empty(),
+        "BR-16",
+        "An Invoice shall have at least one Invoice line",
+    );
+
+    for line in invoice.lines.iter() {
+        check(
+            !line.id.is_empty(),
+            "BR-21",
+            "Each Invoice line shall have an Invoice line identifier",
+        );
+        check(
+            line.quantity != 0.0,
+            "BR-22",
+            "Each Invoice line shall have an Invoiced quantity",
+        );
+        check(
+            !line.unit_code.is_empty(),
+            "BR-23",
+            "An Invoice line shall have an Invoiced quantity unit of measure code",
+        );
+        check(
+            !line.name.trim().is_empty(),
+            "BR-25",
+            "Each Invoice line shall contain the Item name",
+        );
+        check(
+            line.price >= 0,
+            "BR-27",
+            "The Item net price shall NOT be negative",
+        );
+    }
+
+    let line_total: i64 = invoice.lines.iter().map(|line| line.net).sum();
+    let tax_total: i64 = invoice.taxes.iter().map(|tax| tax.amount).sum();
+    check(
+        invoice.line_total == line_total,
+        "BR-CO-10",
+        "Sum of Invoice line net amount = Σ Invoice line net amount",
+    );
+    let allowance_total: i64 = invoice
+        .allowances
+        .iter()
+        .map(|allowance| allowance.amount)
+        .sum();
+    check(
+        invoice.allowance_total == allowance_total,
+        "BR-CO-11",
+        "Sum of allowances on document level = Σ Document level allowance amount",
+    );
+    check(
+        invoice.tax_total == tax_total,
+        "BR-CO-14",
+        "Invoice total VAT amount = Σ VAT category tax amount",
+    );
+    check(
+        invoice.grand_total == invoice.tax_exclusive_total() + invoice.tax_total,
+        "BR-CO-15",
+        "Invoice total amount with VAT = Invoice total amount without VAT + Invoice total VAT amount",
+    );
+
+    let has_standard = invoice
+        .lines
+        .iter()
+        .any(|line| line.tax_category == TaxCategory::Standard);
+    let has_vat_breakdown = !invoice.taxes.is_empty();
+    check(
+        has_vat_breakdown || invoice.lines.is_empty(),
+        "BR-CO-18",
+        "An Invoice shall at least have one VAT breakdown group",
+    );
+    check(
+        !has_standard || !invoice.seller.vat_id.trim().is_empty(),
+        "BR-S-02",
+        "An Invoice that contains a line where the VAT category is \"Standard rated\" shall contain the Seller VAT identifier",
+    );
+    for (category, rule, message) in [
+        (
+            TaxCategory::ReverseCharge,
+            "BR-AE-02",
+            "An Invoice that contains a line where the VAT category is \"Reverse charge\" shall contain the Seller VAT identifier and the Buyer VAT identifier",
+        ),
+        (
+            TaxCategory::IntraCommunity,
+            "BR-IC-02",
+            "An Invoice that contains a line where the VAT category is \"Intra-community supply\" shall contain the Seller VAT identifier and the Buyer VAT identifier",
+        ),
+    ] {
+        let has_category = invoice
+            .lines
+            .iter()
+            .any(|line| line.tax_category == category);
+        check(
+            !has_category
+                || (!invoice.seller.vat_id.trim().is_empty()
+                    && !invoice.buyer.vat_id.trim().is_empty()),
+            rule,
+            message,
+        );
+    }
+    for tax in invoice.taxes.iter() {
+        let rule = match tax.category {
+            TaxCategory::Exempt => Some("BR-E-10"),
+            TaxCategory::ReverseCharge => Some("BR-AE-10"),
+            TaxCategory::IntraCommunity => Some("BR-IC-10"),
+            TaxCategory::Export => Some("BR-G-10"),
+            _ => None,
+        };
+        if let Some(rule) = rule {
+            check(
+                !tax.exemption_reason.trim().is_empty(),
+                rule,
+                "A VAT breakdown without VAT shall have a VAT exemption reason text",
+            );
+        }
+    }
+    for tax in invoice.taxes.iter() {
+        let base: i64 = invoice
+            .lines
+            .iter()
+            .filter(|line| line.tax_category == tax.category && line.tax_rate == tax.rate)
+            .map(|line| line.net)
+            .sum::<i64>()
+            - invoice
+                .allowances
+                .iter()
+                .filter(|allowance| {
+                    allowance.category == tax.category && allowance.rate == tax.rate
+                })
+                .map(|allowance| allowance.amount)
+                .sum::<i64>();
+        check(
+            tax.base == base,
+            "BR-S-08",
+            "The VAT category taxable amount shall equal the sum of the line net amounts of that category and rate minus the document level allowances",
+        );
+    }
+
+    if syntax == Syntax::XRechnung {
+        check(
+            !invoice.buyer_reference.trim().is_empty(),
+            "BR-DE-15",
+            "The element \"Buyer reference\" (BT-10) shall be provided",
+        );
+        check(
+            !invoice.seller.city.trim().is_empty(),
+            "BR-DE-3",
+            "The element \"Seller city\" (BT-37) shall be provided",
+        );
+        check(
+            !invoice.seller.postal_code.trim().is_empty(),
+            "BR-DE-4",
+            "The element \"Seller post code\" (BT-38) shall be provided",
+        );
+        check(
+            !invoice.seller.phone.trim().is_empty(),
+            "BR-DE-6",
+            "The element \"Seller contact telephone number\" (BT-42) shall be provided",
+        );
+        check(
+            !invoice.seller.email.trim().is_empty(),
+            "BR-DE-7",
+            "The element \"Seller contact email address\" (BT-43) shall be provided",
+        );
+        check(
+            invoice.payment.is_some(),
+            "BR-DE-1",
+            "An Invoice shall contain information on \"PAYMENT INSTRUCTIONS\" (BG-16)",
+        );
+    }
+
+    violations
+}
@@ -0,0 +1,295 @@
+use std::io;
+
+use quick_xml::events::{BytesDecl, BytesText, Event};
+
+use super::{
+    ALLOWANCE_REASON, ALLOWANCE_REASON_CODE, EInvoice, Party, XmlWriter, amount, percent, quantity,
+    text, xml_writer,
+};
+
+const CUSTOMIZATION_ID: &str =
+    "urn:cen.eu:en16931:2017#compliant#urn:xeinkauf.de:kosit:xrechnung_3.0";
+const PROFILE_ID: &str = "urn:fdc:peppol.eu:2017:poacc:billing:01:1.0";
+
+/// Credit transfer (UNTDID 4461).
+const PAYMENT_MEANS_CODE: &str = "58";
+
+/// Writes the invoice as UBL 2.1 `Invoice` document. The element order is
+/// fixed by the UBL schema and must not be changed.
+pub fn write(invoice: &EInvoice) -> io::Result<Vec<u8>> {
+    let mut writer = xml_writer();
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+
+    writer
+        .create_element("ubl:Invoice")
+        .with_attribute((
+            "xmlns:ubl",
+            "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
+        ))
+        .with_attribute((
+            "xmlns:cac",
+            "urn:oasis:names:specification:ubl:schema:xsd:CommonAggregateComponents-2",
+        ))
+        .with_attribute((
+            "xmlns:cbc",
+            "urn:oasis:names:specification:ubl:schema:xsd:CommonBasicComponents-2",
+        ))
+        .write_inner_content(|writer| {
+            text(writer, "cbc:CustomizationID", CUSTOMIZATION_ID)?;
+            text(writer, "cbc:ProfileID", PROFILE_ID)?;
+            text(writer, "cbc:ID", &invoice.number)?;
+            text(writer, "cbc:IssueDate", &invoice.issue_date)?;
+            text(writer, "cbc:InvoiceTypeCode", &invoice.type_code)?;
+            text(writer, "cbc:DocumentCurrencyCode", &invoice.currency)?;
+            text(writer, "cbc:BuyerReference", &invoice.buyer_reference)?;
+            if !invoice.order_reference.is_empty() {
+                writer
+                    .create_element("cac:OrderReference")
+                    .write_inner_content(|writer| {
+                        text(writer, "cbc:ID", &invoice.order_reference)
+                    })?;
+            }
+
+            writer
+                .create_element("cac:AccountingSupplierParty")
+                .write_inner_content(|writer| party(writer, &invoice.seller))?;
+            writer
+                .create_element("cac:AccountingCustomerParty")
+                .write_inner_content(|writer| party(writer, &invoice.buyer))?;
+
+            if let Some(payment) = &invoice.payment {
+                writer
+                    .create_element("cac:PaymentMeans")
+                    .write_inner_content(|writer| {
+                        text(writer, "cbc:PaymentMeansCode", PAYMENT_MEANS_CODE)?;
+                        writer
+                            .create_element("cac:PayeeFinancialAccount")
+                            .write_inner_content(|writer| {
+                                text(writer, "cbc:ID", &payment.iban)?;
+                                text(writer, "cbc:Name", &payment.name)?;
+                                if !payment.bic.is_empty() {
+                                    writer
+                                        .create_element("cac:FinancialInstitutionBranch")
+                                        .write_inner_content(|writer| {
+                                            text(writer, "cbc:ID", &payment.bic)
+                                        })?;
+                                }
+                                Ok(())
+                            })?;
+                        Ok(())
+                    })?;
+            }
+
+            for allowance in invoice.allowances.iter() {
+                writer
+                    .create_element("cac:AllowanceCharge")
+                    .write_inner_content(|writer| {
+                        allowance_charge(writer, &invoice.currency, allowance.amount)?;
+                        tax_category(
+                            writer,
+                            "cac:TaxCategory",
+                            allowance.category.code(),
+                            allowance.rate,
+                            "",
+                        )
+                    })?;
+            }
+
+            writer
+                .create_element("cac:TaxTotal")
+                .write_inner_content(|writer| {
+                    amount(
+                        writer,
+                        "cbc:TaxAmount",
+                        &invoice.currency,
+                        invoice.tax_total,
+                    )?;
+                    for tax in invoice.taxes.iter() {
+                        writer
+                            .create_element("cac:TaxSubtotal")
+                            .write_inner_content(|writer| {
+                                amount(writer, "cbc:TaxableAmount", &invoice.currency, tax.base)?;
+                                amount(writer, "cbc:TaxAmount", &invoice.currency, tax.amount)?;
+                                tax_category(
+                                    writer,
+                                    "cac:TaxCategory",
+                                    tax.category.code(),
+                                    tax.rate,
+                                    &tax.exemption_reason,
+                                )
+                            })?;
+                    }
+                    Ok(())
+                })?;
+
+            writer
+                .create_element("cac:LegalMonetaryTotal")
+                .write_inner_content(|writer| {
+                    amount(
+                        writer,
+                        "cbc:LineExtensionAmount",
+                        &invoice.currency,
+                        invoice.line_total,
+                    )?;
+                    amount(
+                        writer,
+                        "cbc:TaxExclusiveAmount",
+                        &invoice.currency,
+                        invoice.tax_exclusive_total(),
+                    )?;
+                    amount(
+                        writer,
+                        "cbc:TaxInclusiveAmount",
+                        &invoice.currency,
+                        invoice.grand_total,
+                    )?;
+                    if invoice.allowance_total != 0 {
+                        amount(
+                            writer,
+                            "cbc:AllowanceTotalAmount",
+                            &invoice.currency,
+                            invoice.allowance_total,
+                        )?;
+                    }
+                    amount(
+                        writer,
+                        "cbc:PayableAmount",
+                        &invoice.currency,
+                        invoice.payable,
+                    )
+                })?;
+
+            for line in invoice.lines.iter() {
+                writer
+                    .create_element("cac:InvoiceLine")
+                    .write_inner_content(|writer| {
+                        text(writer, "cbc:ID", &line.id)?;
+                        writer
+                            .create_element("cbc:InvoicedQuantity")
+                            .with_attribute(("unitCode", line.unit_code.as_str()))
+                            .write_text_content(BytesText::new(&quantity(line.quantity)))?;
+                        amount(
+                            writer,
+                            "cbc:LineExtensionAmount",
+                            &invoice.currency,
+                            line.net,
+                        )?;
+                        if line.allowance != 0 {
+                            writer
+                                .create_element("cac:AllowanceCharge")
+                                .write_inner_content(|writer| {
+                                    allowance_charge(writer, &invoice.currency, line.allowance)
+                                })?;
+                        }
+                        writer
+                            .create_element("cac:Item")
+                            .write_inner_content(|writer| {
+                                text(writer, "cbc:Name", &line.name)?;
+                                tax_category(
+                                    writer,
+                                    "cac:ClassifiedTaxCategory",
+                                    line.tax_category.code(),
+                                    line.tax_rate,
+                                    "",
+                                )
+                            })?;
+                        writer
+                            .create_element("cac:Price")
+                            .write_inner_content(|writer| {
+                                amount(writer, "cbc:PriceAmount", &invoice.currency, line.price)
+                            })?;
+                        Ok(())
+                    })?;
+            }
+
+            Ok(())
+        })?;
+
+    Ok(writer.into_inner().into_inner())
+}
+
+fn party(writer: &mut XmlWriter, party: &Party) -> io::Result<()> {
+    writer
+        .create_element("cac:Party")
+        .write_inner_content(|writer| {
+            if !party.email.is_empty() {
+                writer
+                    .create_element("cbc:EndpointID")
+                    .with_attribute(("schemeID", "EM"))
+                    .write_text_content(BytesText::new(&party.email))?;
+            }
+            writer
+                .create_element("cac:PostalAddress")
+                .write_inner_content(|writer| {
+                    text(writer, "cbc:StreetName", &party.street1)?;
+                    text(writer, "cbc:AdditionalStreetName", &party.street2)?;
+                    text(writer, "cbc:CityName", &party.city)?;
+                    text(writer, "cbc:PostalZone", &party.postal_code)?;
+                    writer
+                        .create_element("cac:Country")
+                        .write_inner_content(|writer| {
+                            text(writer, "cbc:IdentificationCode", &party.country_code)
+                        })?;
+                    Ok(())
+                })?;
+            if !party.vat_id.is_empty() {
+                writer
+                    .create_element("cac:PartyTaxScheme")
+                    .write_inner_content(|writer| {
+                        text(writer, "cbc:CompanyID", &party.vat_id)?;
+                        tax_scheme(writer)
+                    })?;
+            }
+            writer
+                .create_element("cac:PartyLegalEntity")
+                .write_inner_content(|writer| text(writer, "cbc:RegistrationName", &party.name))?;
+            if !party.phone.is_empty() || !party.email.is_empty() {
+                writer
+                    .create_element("cac:Contact")
+                    .write_inner_content(|writer| {
+                        text(writer, "cbc:Name", &party.name)?;
+                        text(writer, "cbc:Telephone", &party.phone)?;
+                        text(writer, "cbc:ElectronicMail", &party.email)
+                    })?;
+            }
+            Ok(())
+        })?;
+    Ok(())
+}
+
+/// Writes the elements an allowance on document and line level share.
+fn allowance_charge(writer: &mut XmlWriter, currency: &str, cents: i64) -> io::Result<()> {
+    text(writer, "cbc:ChargeIndicator", "false")?;
+    text(
+        writer,
+        "cbc:AllowanceChargeReasonCode",
+        ALLOWANCE_REASON_CODE,
+    )?;
+    text(writer, "cbc:AllowanceChargeReason", ALLOWANCE_REASON)?;
+    amount(writer, "cbc:Amount", currency, cents)
+}
+
+/// Writes a tax category. The exemption reason is only given in the VAT
+/// breakdown, line categories leave it empty.
+fn tax_category(
+    writer: &mut XmlWriter,
+    name: &str,
+    code: &str,
+    rate: f64,
+    exemption_reason: &str,
+) -> io::Result<()> {
+    writer.create_element(name).write_inner_content(|writer| {
+        text(writer, "cbc:ID", code)?;
+        text(writer, "cbc:Percent", &percent(rate))?;
+        text(writer, "cbc:TaxExemptionReason", exemption_reason)?;
+        tax_scheme(writer)
+    })?;
+    Ok(())
+}
+
+fn tax_scheme(writer: &mut XmlWriter) -> io::Result<()> {
+    writer
+        .create_element("cac:TaxScheme")
+        .write_inner_content(|writer| text(writer, "cbc:ID", "VAT"))?;
+    Ok(())
+}
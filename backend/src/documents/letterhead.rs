@@ -0,0 +1,40 @@
+use serde::Serialize;
+
+/// Company details printed at the top and bottom of every customer-facing
+/// document. Every field is read from a `COMPANY_*` environment variable.
+#[derive(Clone, Default, Serialize)]
+pub struct Letterhead {
+    pub name: String,
+    pub street1: String,
+    pub street2: String,
+    pub postal_code: String,
+    pub city: String,
+    pub country: String,
+    pub vat_id: String,
+    pub email: String,
+    pub phone: String,
+    pub bank_name: String,
+    pub iban: String,
+    pub bic: String,
+}
+
+impl Letterhead {
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).unwrap_or_default();
+
+        Self {
+            name: var("COMPANY_NAME"),
+            street1: var("COMPANY_STREET1"),
+            street2: var("COMPANY_STREET2"),
+            postal_code: var("COMPANY_POSTAL_CODE"),
+            city: var("COMPANY_CITY"),
+            country: var("COMPANY_COUNTRY"),
+            vat_id: var("COMPANY_VAT_ID"),
+            email: var("COMPANY_EMAIL"),
+            phone: var("COMPANY_PHONE"),
+            bank_name: var("COMPANY_BANK_NAME"),
+            iban: var("COMPANY_IBAN"),
+            bic: var("COMPANY_BIC"),
+        }
+    }
+}
@@ -0,0 +1,456 @@
+pub mod einvoice;
+mod letterhead;
+mod pdf;
+
+pub use letterhead::*;
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use entity::sea_orm_active_enums::{SaleInvoiceStatus, SaleOrderStatus};
+use minijinja::{Environment, context};
+use serde::Serialize;
+
+use crate::{
+    repositories::ResolvedTax,
+    utils::{
+        tax_groups::component_bases,
+        totals::{Discount, allocate, line_net},
+    },
+};
+use einvoice::TaxCategory;
+
+const LAYOUT_TEMPLATE: &str = include_str!("templates/layout.txt");
+const SALE_INVOICE_TEMPLATE: &str = include_str!("templates/sale_invoice.txt");
+const SALE_ORDER_TEMPLATE: &str = include_str!("templates/sale_order.txt");
+const FACTUR_X_XMP_TEMPLATE: &str = include_str!("templates/factur_x_xmp.xml");
+
+const SALE_INVOICE_TEMPLATE_NAME: &str = "sale_invoice.txt";
+const SALE_ORDER_TEMPLATE_NAME: &str = "sale_order.txt";
+const FACTUR_X_XMP_TEMPLATE_NAME: &str = "factur_x_xmp.xml";
+
+#[derive(Debug)]
+pub enum RenderError {
+    Template(minijinja::Error),
+    Pdf(printpdf::Error),
+    Attachment(printpdf::lopdf::Error),
+}
+
+#[derive(Serialize)]
+pub struct DocumentAddress {
+    pub name: String,
+    pub street1: String,
+    pub street2: String,
+    pub postal_code: String,
+    pub city: String,
+    pub country: String,
+    pub vat_id: String,
+}
+
+impl From<&entity::contacts::Model> for DocumentAddress {
+    fn from(entity: &entity::contacts::Model) -> Self {
+        Self {
+            name: entity.name.clone(),
+            street1: entity.street1.clone(),
+            street2: entity.street2.clone(),
+            postal_code: entity.postal_code.clone(),
+            city: entity.city.clone(),
+            country: entity.country.clone(),
+            vat_id: entity.vat_id.clone(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct DocumentLine {
+    pub position: usize,
+    pub description: String,
+    pub quantity: f64,
+    pub price: i64,
+    pub tax: String,
+    #[serde(skip)]
+    pub tax_rate: f64,
+    #[serde(skip)]
+    pub tax_category: TaxCategory,
+    /// More than one for lines with a tax group.
+    #[serde(skip)]
+    pub tax_components: usize,
+    /// Quantity times price, before the discount of the line.
+    pub amount: i64,
+    pub discount: i64,
+    pub discount_label: String,
+    pub net: i64,
+    /// The line's share of the document discount, which is only shown as a
+    /// whole.
+    #[serde(skip)]
+    pub document_discount: i64,
+}
+
+#[derive(Serialize)]
+pub struct DocumentTax {
+    pub label: String,
+    #[serde(skip)]
+    pub rate: f64,
+    #[serde(skip)]
+    pub category: TaxCategory,
+    /// Legal note of the tax, e.g. why no tax is charged.
+    pub note: String,
+    pub base: i64,
+    pub amount: i64,
+}
+
+/// Everything a customer-facing document shows, already resolved from the
+/// database rows. Amounts are in cents like the prices on the lines.
+#[derive(Serialize)]
+pub struct PrintableDocument {
+    #[serde(skip)]
+    template: &'static str,
+    pub title: String,
+    pub status: String,
+    pub reference: String,
+    pub date: String,
+    pub letterhead: Letterhead,
+    pub recipient: DocumentAddress,
+    pub lines: Vec<DocumentLine>,
+    pub taxes: Vec<DocumentTax>,
+    /// The sum of the line nets, before the document discount.
+    pub line_total: i64,
+    pub discount: i64,
+    pub discount_label: String,
+    pub net_total: i64,
+    pub tax_total: i64,
+    pub gross_total: i64,
+    /// The notes of the taxes used, each printed once.
+    pub notes: Vec<String>,
+}
+
+struct LineInput {
+    description: String,
+    quantity: f64,
+    price: i32,
+    discount: Discount,
+    tax_label: String,
+    taxes: Vec<LineTax>,
+}
+
+/// A component of a line's tax, in the order the components are levied.
+struct LineTax {
+    label: String,
+    rate: f64,
+    category: TaxCategory,
+    note: String,
+    compound: bool,
+}
+
+impl LineTax {
+    /// A tax only known by the percentage stored on the line.
+    fn percentage(tax: i32) -> Self {
+        let rate = tax as f64 / 100.0;
+        Self {
+            label: format!("{tax}%"),
+            rate,
+            category: TaxCategory::for_rate(rate),
+            note: String::new(),
+            compound: false,
+        }
+    }
+}
+
+impl PrintableDocument {
+    pub fn sale_invoice(
+        invoice: &entity::sale_invoices::Model,
+        lines: &[entity::sale_invoice_lines::Model],
+        customer: &entity::contacts::Model,
+        taxes: &HashMap<i32, ResolvedTax>,
+        letterhead: &Letterhead,
+    ) -> Self {
+        let (title, status) = match invoice.status {
+            SaleInvoiceStatus::Draft => ("Draft invoice", "draft"),
+            SaleInvoiceStatus::Proforma => ("Proforma invoice", "proforma"),
+            SaleInvoiceStatus::Invoice => ("Invoice", "invoice"),
+            SaleInvoiceStatus::Finished => ("Invoice", "finished"),
+        };
+
+        let lines = lines
+            .iter()
+            .map(|line| {
+                let tax = line.tax_id.and_then(|id| taxes.get(&id));
+                let (tax_label, taxes) = match tax {
+                    Some(resolved) => (
+                        resolved.tax.name_short.clone(),
+                        resolved
+                            .components
+                            .iter()
+                            .map(|component| LineTax {
+                                label: component.tax.name_short.clone(),
+                                rate: component.tax.rate,
+                                category: TaxCategory::from_code(&component.tax.category)
+                                    .unwrap_or(TaxCategory::for_rate(component.tax.rate)),
+                                note: component.tax.note.clone(),
+                                compound: component.compound,
+                            })
+                            .collect(),
+                    ),
+                    None => (
+                        format!("{}%", line.tax),
+                        vec![LineTax::percentage(line.tax)],
+                    ),
+                };
+
+                LineInput {
+                    description: line.description.clone(),
+                    quantity: line.quantity,
+                    price: line.price,
+                    discount: Discount::new(line.discount_percent, line.discount_amount),
+                    tax_label,
+                    taxes,
+                }
+            })
+            .collect();
+
+        Self::build(
+            SALE_INVOICE_TEMPLATE_NAME,
+            title,
+            status,
+            &invoice.reference,
+            invoice.date.format("%Y-%m-%d").to_string(),
+            customer,
+            letterhead,
+            lines,
+            Discount::new(invoice.discount_percent, invoice.discount_amount),
+        )
+    }
+
+    pub fn sale_order(
+        order: &entity::sale_orders::Model,
+        lines: &[entity::sale_order_lines::Model],
+        customer: &entity::contacts::Model,
+        letterhead: &Letterhead,
+    ) -> Self {
+        let (title, status) = match order.status {
+            SaleOrderStatus::Draft => ("Quotation", "draft"),
+            SaleOrderStatus::Quotation => ("Quotation", "quotation"),
+            SaleOrderStatus::Order => ("Order confirmation", "order"),
+            SaleOrderStatus::Finished => ("Order confirmation", "finished"),
+            SaleOrderStatus::Cancelled => ("Order confirmation", "cancelled"),
+        };
+
+        let lines = lines
+            .iter()
+            .map(|line| LineInput {
+                description: line.description.clone(),
+                quantity: line.quantity,
+                price: line.price,
+                discount: Discount::new(line.discount_percent, line.discount_amount),
+                tax_label: format!("{}%", line.tax),
+                taxes: vec![LineTax::percentage(line.tax)],
+            })
+            .collect();
+
+        Self::build(
+            SALE_ORDER_TEMPLATE_NAME,
+            title,
+            status,
+            &order.reference,
+            order.date.format("%Y-%m-%d").to_string(),
+            customer,
+            letterhead,
+            lines,
+            Discount::new(order.discount_percent, order.discount_amount),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn build(
+        template: &'static str,
+        title: &str,
+        status: &str,
+        reference: &str,
+        date: String,
+        customer: &entity::contacts::Model,
+        letterhead: &Letterhead,
+        inputs: Vec<LineInput>,
+        discount: Discount,
+    ) -> Self {
+        let mut lines = Vec::with_capacity(inputs.len());
+        let mut taxes: Vec<DocumentTax> = Vec::new();
+
+        let nets: Vec<i64> = inputs
+            .iter()
+            .map(|input| input.discount.apply(line_net(input.quantity, input.price)))
+            .collect();
+        let line_total: i64 = nets.iter().sum();
+        let document_discount = discount.of(line_total);
+        let shares = allocate(&nets, document_discount);
+
+        for (index, ((input, net), share)) in inputs.into_iter().zip(nets).zip(shares).enumerate() {
+            let amount = line_net(input.quantity, input.price);
+
+            // The tax is levied on what is left after both discounts.
+            let components: Vec<(f64, bool)> = input
+                .taxes
+                .iter()
+                .map(|tax| (tax.rate, tax.compound))
+                .collect();
+            let bases = component_bases(net - share, &components);
+            for (line_tax, base) in input.taxes.iter().zip(bases) {
+                match taxes.iter_mut().find(|tax| tax.label == line_tax.label) {
+                    Some(tax) => tax.base += base,
+                    None => taxes.push(DocumentTax {
+                        label: line_tax.label.clone(),
+                        rate: line_tax.rate,
+                        category: line_tax.category,
+                        note: line_tax.note.clone(),
+                        base,
+                        amount: 0,
+                    }),
+                }
+            }
+
+            // Only the first component has a say on the line, e.g. in
+            // e-invoices, which allow a single tax per line.
+            let first = &input.taxes[0];
+            lines.push(DocumentLine {
+                position: index + 1,
+                description: input.description,
+                quantity: input.quantity,
+                price: input.price as i64,
+                tax: input.tax_label,
+                tax_rate: first.rate,
+                tax_category: first.category,
+                tax_components: input.taxes.len(),
+                amount,
+                discount: amount - net,
+                discount_label: discount_label(input.discount),
+                net,
+                document_discount: share,
+            });
+        }
+
+        // The tax is computed once per group and not per line, so rounding
+        // differences of the lines don't add up.
+        for tax in taxes.iter_mut() {
+            tax.amount = (tax.base as f64 * tax.rate).round() as i64;
+        }
+
+        let net_total = line_total - document_discount;
+        let tax_total = taxes.iter().map(|tax| tax.amount).sum();
+
+        let mut notes: Vec<String> = Vec::new();
+        for tax in taxes.iter() {
+            if !tax.note.is_empty() && !notes.contains(&tax.note) {
+                notes.push(tax.note.clone());
+            }
+        }
+
+        Self {
+            template,
+            title: title.to_string(),
+            status: status.to_string(),
+            reference: reference.to_string(),
+            date,
+            letterhead: letterhead.clone(),
+            recipient: DocumentAddress::from(customer),
+            lines,
+            taxes,
+            line_total,
+            discount: document_discount,
+            discount_label: discount_label(discount),
+            net_total,
+            tax_total,
+            gross_total: net_total + tax_total,
+            notes,
+        }
+    }
+
+    pub fn render_text(&self) -> Result<String, minijinja::Error> {
+        let env = environment()?;
+        let template = env.get_template(self.template)?;
+        template.render(self)
+    }
+
+    pub fn render_pdf(&self) -> Result<Vec<u8>, RenderError> {
+        let text = self.render_text().map_err(RenderError::Template)?;
+        pdf::render(&self.pdf_title(), &text, false).map_err(RenderError::Pdf)
+    }
+
+    /// Renders the document as PDF/A-3 with the Factur-X XML attached.
+    pub fn render_factur_x(&self, xml: &[u8]) -> Result<Vec<u8>, RenderError> {
+        let text = self.render_text().map_err(RenderError::Template)?;
+        let title = self.pdf_title();
+        let date = Utc::now();
+
+        let metadata = environment()
+            .and_then(|env| {
+                env.get_template(FACTUR_X_XMP_TEMPLATE_NAME)?
+                    .render(context! {
+                        title => title,
+                        date => date.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+                        producer => pdf::PRODUCER,
+                        file_name => einvoice::FILE_NAME,
+                        conformance_level => einvoice::CONFORMANCE_LEVEL,
+                    })
+            })
+            .map_err(RenderError::Template)?;
+
+        let pdf = pdf::render(&title, &text, true).map_err(RenderError::Pdf)?;
+        einvoice::attach_factur_x(pdf, xml, metadata.as_bytes(), date)
+            .map_err(RenderError::Attachment)
+    }
+
+    fn pdf_title(&self) -> String {
+        format!("{} {}", self.title, self.reference)
+    }
+}
+
+fn environment() -> Result<Environment<'static>, minijinja::Error> {
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    env.add_filter("money", money);
+    env.add_filter("quantity", quantity);
+    env.add_filter("ljust", ljust);
+    env.add_filter("rjust", rjust);
+    env.add_template("layout.txt", LAYOUT_TEMPLATE)?;
+    env.add_template(SALE_INVOICE_TEMPLATE_NAME, SALE_INVOICE_TEMPLATE)?;
+    env.add_template(SALE_ORDER_TEMPLATE_NAME, SALE_ORDER_TEMPLATE)?;
+    env.add_template(FACTUR_X_XMP_TEMPLATE_NAME, FACTUR_X_XMP_TEMPLATE)?;
+    Ok(env)
+}
+
+/// Describes a discount as printed next to the amount it takes off, e.g.
+/// `10% + 5.00`.
+fn discount_label(discount: Discount) -> String {
+    let mut parts = Vec::new();
+    if discount.percent > 0.0 {
+        parts.push(format!("{}%", quantity(discount.percent)));
+    }
+    if discount.amount > 0 {
+        parts.push(money(discount.amount));
+    }
+    parts.join(" + ")
+}
+
+/// Formats an amount in cents as `1234.56`.
+fn money(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    let cents = cents.abs();
+    format!("{sign}{}.{:02}", cents / 100, cents % 100)
+}
+
+fn quantity(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value as i64)
+    } else {
+        format!("{value:.3}").trim_end_matches('0').to_string()
+    }
+}
+
+fn ljust(value: String, width: usize) -> String {
+    let value: String = value.chars().take(width).collect();
+    format!("{value:<width$}")
+}
+
+fn rjust(value: String, width: usize) -> String {
+    format!("{value:>width$}")
+}
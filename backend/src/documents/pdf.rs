@@ -0,0 +1,49 @@
+use std::io::Cursor;
+
+use printpdf::{Mm, PdfConformance, PdfDocument};
+
+/// Written to the PDF info dictionary and the XMP metadata.
+pub const PRODUCER: &str = "liro";
+
+const FONT: &[u8] = include_bytes!("fonts/DejaVuSansMono.ttf");
+
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN_LEFT: f32 = 25.0;
+const MARGIN_TOP: f32 = 20.0;
+const MARGIN_BOTTOM: f32 = 20.0;
+const FONT_SIZE: f32 = 9.0;
+const LINE_HEIGHT: f32 = 4.2;
+
+/// Lays out pre-rendered text on A4 pages using an embedded monospaced font,
+/// so the column alignment of the templates is kept in the PDF. Archival
+/// documents are written as PDF/A-3, which allows attachments like the
+/// Factur-X XML.
+pub fn render(title: &str, text: &str, archival: bool) -> Result<Vec<u8>, printpdf::Error> {
+    let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Content");
+    let doc = doc.with_producer(PRODUCER);
+    let doc = if archival {
+        doc.with_conformance(PdfConformance::A3_2012_PDF_1_7)
+    } else {
+        doc
+    };
+    let font = doc.add_external_font(Cursor::new(FONT))?;
+
+    let mut layer = doc.get_page(page).get_layer(layer);
+    let mut y = PAGE_HEIGHT - MARGIN_TOP;
+
+    for line in text.lines() {
+        if y < MARGIN_BOTTOM {
+            let (page, new_layer) = doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Content");
+            layer = doc.get_page(page).get_layer(new_layer);
+            y = PAGE_HEIGHT - MARGIN_TOP;
+        }
+
+        if !line.trim().is_empty() {
+            layer.use_text(line, FONT_SIZE, Mm(MARGIN_LEFT), Mm(y), &font);
+        }
+        y -= LINE_HEIGHT;
+    }
+
+    doc.save_to_bytes()
+}
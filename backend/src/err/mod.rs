@@ -2,59 +2,775 @@ use actix_web::{HttpResponse, HttpResponseBuilder, http::StatusCode};
 use serde_json::json;
 
 pub const MESSAGE_ACCOUNT_NOT_FOUND: &str = "Ledger account could not be found";
+pub const MESSAGE_CONSUMPTION_EXCEEDS_STOCK: &str =
+    "The reported consumption exceeds what is on consignment in the stock";
+pub const MESSAGE_CONTACT_ACCOUNT_MISSING: &str =
+    "The contact of the invoice has no ledger account";
+pub const MESSAGE_CONTACT_NOT_CUSTOMER: &str = "The contact is not a customer";
+pub const MESSAGE_CONTACT_NOT_FOUND: &str = "Contact could not be found";
+pub const MESSAGE_CONTACT_NOT_SUPPLIER: &str = "The contact is not a supplier";
+pub const MESSAGE_CREDIT_NOTE_EXCEEDS_INVOICE: &str =
+    "The credit note exceeds what is left of the invoice";
+pub const MESSAGE_CUSTOMER_LOCATION_MISSING: &str =
+    "There is no customer location to take consumed goods to";
+pub const MESSAGE_CUSTOMER_STOCK_MISSING: &str = "The customer has no stock to drop-ship to";
 pub const MESSAGE_DATABASE_UNREACHABLE: &str = "The database is currently unreachable";
+pub const MESSAGE_DOCUMENT_RENDERING_FAILED: &str = "The document could not be rendered";
+pub const MESSAGE_DROP_SHIP_SUPPLIER_MISSING: &str =
+    "No supplier sells the variant of a drop-shipped line";
+pub const MESSAGE_DROP_SHIP_VARIANT_MISSING: &str = "Drop-shipped lines need a variant";
+pub const MESSAGE_DUPLICATE_LOT: &str = "The same lot or serial number was given twice for a move";
+pub const MESSAGE_EINVOICE_TAX_GROUP: &str =
+    "E-invoices can only carry one tax per line, but a line has a tax group";
+pub const MESSAGE_EINVOICE_TOO_LARGE: &str = "The file exceeds the size limit for e-invoices";
+pub const MESSAGE_EINVOICE_UNREADABLE: &str = "The file is not a readable UBL or CII invoice";
+pub const MESSAGE_EINVOICE_VALIDATION_FAILED: &str =
+    "The invoice violates the EN 16931 business rules";
+pub const MESSAGE_INCOMPATIBLE_UNIT: &str = "The unit can't be converted into the variant's unit";
+pub const MESSAGE_INSUFFICIENT_STOCK: &str =
+    "The order asks for more than is available in its source stock";
 pub const MESSAGE_INTERNAL_SERVER_ERROR: &str = "Internal server error";
-pub const MESSAGE_INVALID_ACCOUNT_TYPE: &str = "Invalid account type. Must be asset, liability, income or expense";
+pub const MESSAGE_INVALID_ACCOUNT_TYPE: &str =
+    "Invalid account type. Must be asset, liability, income or expense";
+pub const MESSAGE_INVALID_BASE_UNIT: &str = "The base unit is itself a multiple of another unit";
+pub const MESSAGE_INVALID_CONSUMPTION_TARGET: &str =
+    "Goods consumed from a supplier's stock must go to an internal location";
+pub const MESSAGE_INVALID_CONTACT_STOCK_KIND: &str = "The kind of a contact's stock must be customer or supplier";
+pub const MESSAGE_INVALID_COSTING_METHOD: &str = "Invalid costing method. Must be fifo or average";
+pub const MESSAGE_INVALID_COUNT_STOCK: &str = "Only internal locations can be counted";
+pub const MESSAGE_INVALID_CURRENCY: &str =
+    "The currency must be a three letter ISO 4217 code like EUR";
+pub const MESSAGE_INVALID_DATE: &str = "The date must have the format YYYY-MM-DD";
+pub const MESSAGE_INVALID_DATE_RANGE: &str =
+    "The end of the date range must not lie before its start";
+pub const MESSAGE_INVALID_GTIN: &str = "The GTIN has an invalid length or check digit";
+pub const MESSAGE_INVALID_NUMBER_PATTERN: &str = "The pattern must contain exactly one counter like {00000} and only the placeholders {YYYY}, {YY} and {MM}";
+pub const MESSAGE_INVALID_PARENT_STOCK: &str = "Only internal locations can hold other locations";
+pub const MESSAGE_INVALID_PRODUCT_TYPE: &str =
+    "Invalid product type. Must be stockable, consumable or service";
+pub const MESSAGE_INVALID_REORDER_QUANTITIES: &str =
+    "The maximum quantity can't be below the minimum quantity";
+pub const MESSAGE_INVALID_REORDER_STOCK: &str = "Reorder rules only apply to internal locations";
+pub const MESSAGE_INVALID_ROUTE: &str = "The route is not valid for a sale order line";
+pub const MESSAGE_INVALID_STATUS: &str = "The status is not valid for this document";
+pub const MESSAGE_INVALID_TAX_CATEGORY: &str = "The tax category is not a known UNTDID 5305 code";
+pub const MESSAGE_INVALID_TAX_COMPONENTS: &str =
+    "The components of a tax group must be distinct taxes other than the group";
+pub const MESSAGE_INVALID_TAX_DIRECTION: &str = "The tax direction must be sale or purchase";
+pub const MESSAGE_INVALID_TAX_REGION: &str = "The tax region must be domestic, eu or non_eu";
+pub const MESSAGE_INVALID_TRACKING: &str = "Invalid tracking. Must be none, lot or serial";
+pub const MESSAGE_INVALID_VARIANT_OVERRIDE: &str =
+    "The price override doesn't name one value of each attribute of the product";
+pub const MESSAGE_INVENTORY_COUNT_APPROVED: &str = "The inventory count has been approved already";
+pub const MESSAGE_INVENTORY_COUNT_NOT_FOUND: &str = "Inventory count could not be found";
+pub const MESSAGE_INVENTORY_LOSS_LOCATION_MISSING: &str =
+    "There is no inventory loss location to adjust the stock against";
+pub const MESSAGE_INVOICE_ALREADY_POSTED: &str =
+    "All lines of the invoice have been posted already";
+pub const MESSAGE_INVOICE_FULLY_CREDITED: &str = "The invoice has already been credited in full";
+pub const MESSAGE_INVOICE_LINE_NOT_FOUND: &str = "Invoice line could not be found";
+pub const MESSAGE_LOTS_MISSING: &str =
+    "The variant is tracked and must be moved in lots or serial numbers";
+pub const MESSAGE_LOTS_NOT_TRACKED: &str = "Lots were given for a variant that isn't tracked";
+pub const MESSAGE_LOT_LINE_NOT_FOUND: &str =
+    "The order has no line whose goods are still to be moved with this id";
+pub const MESSAGE_LOT_NOT_FOUND: &str = "Lot or serial number could not be found";
+pub const MESSAGE_LOT_NOT_IN_STOCK: &str = "The source stock doesn't hold enough of the lot";
+pub const MESSAGE_LOT_QUANTITY_MISMATCH: &str =
+    "The quantities of the lots don't add up to the moved quantity";
+pub const MESSAGE_MISSING_PRICE: &str = "A line without a variant needs a price";
+pub const MESSAGE_MISSING_TAX: &str = "A line without a variant needs a tax";
+pub const MESSAGE_NESTED_TAX_GROUP: &str =
+    "Tax groups can't contain other groups or be components themselves";
+pub const MESSAGE_NO_MATCHING_TAX_RULE: &str = "No tax rule matches the contact and product";
+pub const MESSAGE_NUMBER_SEQUENCE_NOT_FOUND: &str = "Number sequence could not be found";
+pub const MESSAGE_NUMBER_SEQUENCE_PERIOD_CLOSED: &str =
+    "The date lies in a period whose numbers have already been passed";
+pub const MESSAGE_POSTING_ACCOUNT_MISSING: &str = "No ledger account could be determined for an invoice line. Assign its product a category with one";
+pub const MESSAGE_PRICE_LIST_ITEM_EXISTS: &str =
+    "The price list already has a price for the variant at this minimum quantity";
+pub const MESSAGE_PRICE_LIST_NOT_FOUND: &str = "Price list could not be found";
+pub const MESSAGE_PRODUCT_ATTRIBUTE_EXISTS: &str =
+    "The product already has an attribute with this name";
+pub const MESSAGE_PRODUCT_ATTRIBUTE_NOT_FOUND: &str = "Product attribute could not be found";
+pub const MESSAGE_PRODUCT_CATEGORY_IN_USE: &str =
+    "The product category still has subcategories or products";
+pub const MESSAGE_PRODUCT_CATEGORY_NOT_FOUND: &str = "Product category could not be found";
+pub const MESSAGE_PRODUCT_NOT_FOUND: &str = "Product could not be found";
+pub const MESSAGE_PURCHASE_INVOICE_ALREADY_IMPORTED: &str =
+    "The supplier's invoice has already been imported";
+pub const MESSAGE_PURCHASE_INVOICE_NOT_FOUND: &str = "Purchase invoice could not be found";
+pub const MESSAGE_PURCHASE_INVOICE_NOT_POSTED: &str =
+    "The purchase invoice has not been posted yet";
+pub const MESSAGE_PURCHASE_ORDER_ALREADY_CONFIRMED: &str =
+    "The purchase order has been confirmed already";
+pub const MESSAGE_PURCHASE_ORDER_ALREADY_RECEIVED: &str =
+    "The purchase order has been received already";
+pub const MESSAGE_PURCHASE_ORDER_NOT_FOUND: &str = "Purchase order could not be found";
+pub const MESSAGE_REORDER_RULE_EXISTS: &str =
+    "The variant already has a reorder rule for the location";
+pub const MESSAGE_REORDER_RULE_NOT_FOUND: &str = "Reorder rule could not be found";
+pub const MESSAGE_SALE_INVOICE_NOT_FOUND: &str = "Sale invoice could not be found";
+pub const MESSAGE_SALE_INVOICE_NOT_POSTED: &str = "The sale invoice has not been posted yet";
+pub const MESSAGE_SALE_ORDER_ALREADY_CONFIRMED: &str = "The sale order has been confirmed already";
+pub const MESSAGE_SALE_ORDER_ALREADY_DELIVERED: &str = "The sale order has been delivered already";
+pub const MESSAGE_SALE_ORDER_CANCELLED: &str = "The sale order has been cancelled";
+pub const MESSAGE_SALE_ORDER_DROP_SHIPPED: &str = "The sale order has drop-ship purchase orders and can't be cancelled";
+pub const MESSAGE_SALE_ORDER_NOT_CONFIRMED: &str =
+    "Sale orders with drop-shipped lines have to be confirmed before delivery";
+pub const MESSAGE_SALE_ORDER_NOT_FOUND: &str = "Sale order could not be found";
+pub const MESSAGE_SERIAL_IN_STOCK: &str = "The serial number is already in the own stocks";
+pub const MESSAGE_SERIAL_QUANTITY_NOT_ONE: &str =
+    "A serial number stands for a single piece and must be moved with quantity 1";
+pub const MESSAGE_STOCK_NOT_CONSIGNMENT: &str = "The stock holds no goods on consignment";
+pub const MESSAGE_STOCK_NOT_FOUND: &str = "Stock could not be found";
+pub const MESSAGE_STOCK_RETURN_WITHOUT_ORDER: &str =
+    "Goods can only be returned for invoices of an order";
+pub const MESSAGE_SUPPLIER_LOCATION_MISSING: &str =
+    "There is no supplier location to order goods from";
+pub const MESSAGE_SUPPLIER_PRICE_EXISTS: &str = "The supplier already has a price for the variant";
+pub const MESSAGE_SUPPLIER_PRICE_NOT_FOUND: &str = "Supplier price could not be found";
+pub const MESSAGE_TAX_NOT_FOUND: &str = "Tax could not be found";
+pub const MESSAGE_TAX_RATE_OVERLAP: &str =
+    "The validity of the rate overlaps an existing rate of the tax";
+pub const MESSAGE_TAX_RULE_NOT_FOUND: &str = "Tax rule could not be found";
+pub const MESSAGE_UNIT_EXISTS: &str = "A unit with this name already exists";
+pub const MESSAGE_UNIT_NOT_FOUND: &str = "Unit could not be found";
+pub const MESSAGE_VARIANT_CODE_EXISTS: &str = "Another variant already has this SKU or GTIN";
 pub const MESSAGE_VARIANT_NOT_FOUND: &str = "Variant not found";
+pub const MESSAGE_VARIANT_NOT_STOCKABLE: &str =
+    "Only variants of stockable products can be moved between stocks";
 
 pub const CODE_ACCOUNT_NOT_FOUND: &str = "ACCOUNT_NOT_FOUND";
+pub const CODE_CONSUMPTION_EXCEEDS_STOCK: &str = "CONSUMPTION_EXCEEDS_STOCK";
+pub const CODE_CONTACT_ACCOUNT_MISSING: &str = "CONTACT_ACCOUNT_MISSING";
+pub const CODE_CONTACT_NOT_CUSTOMER: &str = "CONTACT_NOT_CUSTOMER";
+pub const CODE_CONTACT_NOT_FOUND: &str = "CONTACT_NOT_FOUND";
+pub const CODE_CONTACT_NOT_SUPPLIER: &str = "CONTACT_NOT_SUPPLIER";
+pub const CODE_CREDIT_NOTE_EXCEEDS_INVOICE: &str = "CREDIT_NOTE_EXCEEDS_INVOICE";
+pub const CODE_CUSTOMER_LOCATION_MISSING: &str = "CUSTOMER_LOCATION_MISSING";
+pub const CODE_CUSTOMER_STOCK_MISSING: &str = "CUSTOMER_STOCK_MISSING";
 pub const CODE_DATABASE_UNREACHABLE: &str = "DATABASE_UNREACHABLE";
+pub const CODE_DOCUMENT_RENDERING_FAILED: &str = "DOCUMENT_RENDERING_FAILED";
+pub const CODE_DROP_SHIP_SUPPLIER_MISSING: &str = "DROP_SHIP_SUPPLIER_MISSING";
+pub const CODE_DROP_SHIP_VARIANT_MISSING: &str = "DROP_SHIP_VARIANT_MISSING";
+pub const CODE_DUPLICATE_LOT: &str = "DUPLICATE_LOT";
+pub const CODE_EINVOICE_TAX_GROUP: &str = "EINVOICE_TAX_GROUP";
+pub const CODE_EINVOICE_TOO_LARGE: &str = "EINVOICE_TOO_LARGE";
+pub const CODE_EINVOICE_UNREADABLE: &str = "EINVOICE_UNREADABLE";
+pub const CODE_EINVOICE_VALIDATION_FAILED: &str = "EINVOICE_VALIDATION_FAILED";
+pub const CODE_INCOMPATIBLE_UNIT: &str = "INCOMPATIBLE_UNIT";
+pub const CODE_INSUFFICIENT_STOCK: &str = "INSUFFICIENT_STOCK";
 pub const CODE_INTERNAL_SERVER_ERROR: &str = "INTERNAL_SERVER_ERROR";
 pub const CODE_INVALID_ACCOUNT_TYPE: &str = "INVALID_ACCOUNT_TYPE";
+pub const CODE_INVALID_BASE_UNIT: &str = "INVALID_BASE_UNIT";
+pub const CODE_INVALID_CONSUMPTION_TARGET: &str = "INVALID_CONSUMPTION_TARGET";
+pub const CODE_INVALID_CONTACT_STOCK_KIND: &str = "INVALID_CONTACT_STOCK_KIND";
+pub const CODE_INVALID_COSTING_METHOD: &str = "INVALID_COSTING_METHOD";
+pub const CODE_INVALID_COUNT_STOCK: &str = "INVALID_COUNT_STOCK";
+pub const CODE_INVALID_CURRENCY: &str = "INVALID_CURRENCY";
+pub const CODE_INVALID_DATE: &str = "INVALID_DATE";
+pub const CODE_INVALID_DATE_RANGE: &str = "INVALID_DATE_RANGE";
+pub const CODE_INVALID_GTIN: &str = "INVALID_GTIN";
+pub const CODE_INVALID_NUMBER_PATTERN: &str = "INVALID_NUMBER_PATTERN";
+pub const CODE_INVALID_PARENT_STOCK: &str = "INVALID_PARENT_STOCK";
+pub const CODE_INVALID_PRODUCT_TYPE: &str = "INVALID_PRODUCT_TYPE";
+pub const CODE_INVALID_REORDER_QUANTITIES: &str = "INVALID_REORDER_QUANTITIES";
+pub const CODE_INVALID_REORDER_STOCK: &str = "INVALID_REORDER_STOCK";
+pub const CODE_INVALID_ROUTE: &str = "INVALID_ROUTE";
+pub const CODE_INVALID_STATUS: &str = "INVALID_STATUS";
+pub const CODE_INVALID_TAX_CATEGORY: &str = "INVALID_TAX_CATEGORY";
+pub const CODE_INVALID_TAX_COMPONENTS: &str = "INVALID_TAX_COMPONENTS";
+pub const CODE_INVALID_TAX_DIRECTION: &str = "INVALID_TAX_DIRECTION";
+pub const CODE_INVALID_TAX_REGION: &str = "INVALID_TAX_REGION";
+pub const CODE_INVALID_TRACKING: &str = "INVALID_TRACKING";
+pub const CODE_INVALID_VARIANT_OVERRIDE: &str = "INVALID_VARIANT_OVERRIDE";
+pub const CODE_INVENTORY_COUNT_APPROVED: &str = "INVENTORY_COUNT_APPROVED";
+pub const CODE_INVENTORY_COUNT_NOT_FOUND: &str = "INVENTORY_COUNT_NOT_FOUND";
+pub const CODE_INVENTORY_LOSS_LOCATION_MISSING: &str = "INVENTORY_LOSS_LOCATION_MISSING";
+pub const CODE_INVOICE_ALREADY_POSTED: &str = "INVOICE_ALREADY_POSTED";
+pub const CODE_INVOICE_FULLY_CREDITED: &str = "INVOICE_FULLY_CREDITED";
+pub const CODE_INVOICE_LINE_NOT_FOUND: &str = "INVOICE_LINE_NOT_FOUND";
+pub const CODE_LOTS_MISSING: &str = "LOTS_MISSING";
+pub const CODE_LOTS_NOT_TRACKED: &str = "LOTS_NOT_TRACKED";
+pub const CODE_LOT_LINE_NOT_FOUND: &str = "LOT_LINE_NOT_FOUND";
+pub const CODE_LOT_NOT_FOUND: &str = "LOT_NOT_FOUND";
+pub const CODE_LOT_NOT_IN_STOCK: &str = "LOT_NOT_IN_STOCK";
+pub const CODE_LOT_QUANTITY_MISMATCH: &str = "LOT_QUANTITY_MISMATCH";
+pub const CODE_MISSING_PRICE: &str = "MISSING_PRICE";
+pub const CODE_MISSING_TAX: &str = "MISSING_TAX";
+pub const CODE_NESTED_TAX_GROUP: &str = "NESTED_TAX_GROUP";
+pub const CODE_NO_MATCHING_TAX_RULE: &str = "NO_MATCHING_TAX_RULE";
+pub const CODE_NUMBER_SEQUENCE_NOT_FOUND: &str = "NUMBER_SEQUENCE_NOT_FOUND";
+pub const CODE_NUMBER_SEQUENCE_PERIOD_CLOSED: &str = "NUMBER_SEQUENCE_PERIOD_CLOSED";
+pub const CODE_POSTING_ACCOUNT_MISSING: &str = "POSTING_ACCOUNT_MISSING";
+pub const CODE_PRICE_LIST_ITEM_EXISTS: &str = "PRICE_LIST_ITEM_EXISTS";
+pub const CODE_PRICE_LIST_NOT_FOUND: &str = "PRICE_LIST_NOT_FOUND";
+pub const CODE_PRODUCT_ATTRIBUTE_EXISTS: &str = "PRODUCT_ATTRIBUTE_EXISTS";
+pub const CODE_PRODUCT_ATTRIBUTE_NOT_FOUND: &str = "PRODUCT_ATTRIBUTE_NOT_FOUND";
+pub const CODE_PRODUCT_CATEGORY_IN_USE: &str = "PRODUCT_CATEGORY_IN_USE";
+pub const CODE_PRODUCT_CATEGORY_NOT_FOUND: &str = "PRODUCT_CATEGORY_NOT_FOUND";
+pub const CODE_PRODUCT_NOT_FOUND: &str = "PRODUCT_NOT_FOUND";
+pub const CODE_PURCHASE_INVOICE_ALREADY_IMPORTED: &str = "PURCHASE_INVOICE_ALREADY_IMPORTED";
+pub const CODE_PURCHASE_INVOICE_NOT_FOUND: &str = "PURCHASE_INVOICE_NOT_FOUND";
+pub const CODE_PURCHASE_INVOICE_NOT_POSTED: &str = "PURCHASE_INVOICE_NOT_POSTED";
+pub const CODE_PURCHASE_ORDER_ALREADY_CONFIRMED: &str = "PURCHASE_ORDER_ALREADY_CONFIRMED";
+pub const CODE_PURCHASE_ORDER_ALREADY_RECEIVED: &str = "PURCHASE_ORDER_ALREADY_RECEIVED";
+pub const CODE_PURCHASE_ORDER_NOT_FOUND: &str = "PURCHASE_ORDER_NOT_FOUND";
+pub const CODE_REORDER_RULE_EXISTS: &str = "REORDER_RULE_EXISTS";
+pub const CODE_REORDER_RULE_NOT_FOUND: &str = "REORDER_RULE_NOT_FOUND";
+pub const CODE_SALE_INVOICE_NOT_FOUND: &str = "SALE_INVOICE_NOT_FOUND";
+pub const CODE_SALE_INVOICE_NOT_POSTED: &str = "SALE_INVOICE_NOT_POSTED";
+pub const CODE_SALE_ORDER_ALREADY_CONFIRMED: &str = "SALE_ORDER_ALREADY_CONFIRMED";
+pub const CODE_SALE_ORDER_ALREADY_DELIVERED: &str = "SALE_ORDER_ALREADY_DELIVERED";
+pub const CODE_SALE_ORDER_CANCELLED: &str = "SALE_ORDER_CANCELLED";
+pub const CODE_SALE_ORDER_DROP_SHIPPED: &str = "SALE_ORDER_DROP_SHIPPED";
+pub const CODE_SALE_ORDER_NOT_CONFIRMED: &str = "SALE_ORDER_NOT_CONFIRMED";
+pub const CODE_SALE_ORDER_NOT_FOUND: &str = "SALE_ORDER_NOT_FOUND";
+pub const CODE_SERIAL_IN_STOCK: &str = "SERIAL_IN_STOCK";
+pub const CODE_SERIAL_QUANTITY_NOT_ONE: &str = "SERIAL_QUANTITY_NOT_ONE";
+pub const CODE_STOCK_NOT_CONSIGNMENT: &str = "STOCK_NOT_CONSIGNMENT";
+pub const CODE_STOCK_NOT_FOUND: &str = "STOCK_NOT_FOUND";
+pub const CODE_STOCK_RETURN_WITHOUT_ORDER: &str = "STOCK_RETURN_WITHOUT_ORDER";
+pub const CODE_SUPPLIER_LOCATION_MISSING: &str = "SUPPLIER_LOCATION_MISSING";
+pub const CODE_SUPPLIER_PRICE_EXISTS: &str = "SUPPLIER_PRICE_EXISTS";
+pub const CODE_SUPPLIER_PRICE_NOT_FOUND: &str = "SUPPLIER_PRICE_NOT_FOUND";
+pub const CODE_TAX_NOT_FOUND: &str = "TAX_NOT_FOUND";
+pub const CODE_TAX_RATE_OVERLAP: &str = "TAX_RATE_OVERLAP";
+pub const CODE_TAX_RULE_NOT_FOUND: &str = "TAX_RULE_NOT_FOUND";
+pub const CODE_UNIT_EXISTS: &str = "UNIT_EXISTS";
+pub const CODE_UNIT_NOT_FOUND: &str = "UNIT_NOT_FOUND";
+pub const CODE_VARIANT_CODE_EXISTS: &str = "VARIANT_CODE_EXISTS";
 pub const CODE_VARIANT_NOT_FOUND: &str = "VARIANT_NOT_FOUND";
+pub const CODE_VARIANT_NOT_STOCKABLE: &str = "VARIANT_NOT_STOCKABLE";
 
 pub const STATUS_ACCOUNT_NOT_FOUND: StatusCode = StatusCode::NOT_FOUND;
+pub const STATUS_CONSUMPTION_EXCEEDS_STOCK: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_CONTACT_ACCOUNT_MISSING: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_CONTACT_NOT_CUSTOMER: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_CONTACT_NOT_FOUND: StatusCode = StatusCode::NOT_FOUND;
+pub const STATUS_CONTACT_NOT_SUPPLIER: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_CREDIT_NOTE_EXCEEDS_INVOICE: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_CUSTOMER_LOCATION_MISSING: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_CUSTOMER_STOCK_MISSING: StatusCode = StatusCode::CONFLICT;
 pub const STATUS_DATABASE_UNREACHABLE: StatusCode = StatusCode::SERVICE_UNAVAILABLE;
+pub const STATUS_DOCUMENT_RENDERING_FAILED: StatusCode = StatusCode::INTERNAL_SERVER_ERROR;
+pub const STATUS_DROP_SHIP_SUPPLIER_MISSING: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_DROP_SHIP_VARIANT_MISSING: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_DUPLICATE_LOT: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_EINVOICE_TAX_GROUP: StatusCode = StatusCode::UNPROCESSABLE_ENTITY;
+pub const STATUS_EINVOICE_TOO_LARGE: StatusCode = StatusCode::PAYLOAD_TOO_LARGE;
+pub const STATUS_EINVOICE_UNREADABLE: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_EINVOICE_VALIDATION_FAILED: StatusCode = StatusCode::UNPROCESSABLE_ENTITY;
+pub const STATUS_INCOMPATIBLE_UNIT: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INSUFFICIENT_STOCK: StatusCode = StatusCode::CONFLICT;
 pub const STATUS_INTERNAL_SERVER_ERROR: StatusCode = StatusCode::INTERNAL_SERVER_ERROR;
 pub const STATUS_INVALID_ACCOUNT_TYPE: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVALID_BASE_UNIT: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVALID_CONSUMPTION_TARGET: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVALID_CONTACT_STOCK_KIND: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVALID_COSTING_METHOD: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVALID_COUNT_STOCK: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVALID_CURRENCY: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVALID_DATE: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVALID_DATE_RANGE: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVALID_GTIN: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVALID_NUMBER_PATTERN: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVALID_PARENT_STOCK: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVALID_PRODUCT_TYPE: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVALID_REORDER_QUANTITIES: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVALID_REORDER_STOCK: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVALID_ROUTE: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVALID_STATUS: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVALID_TAX_CATEGORY: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVALID_TAX_COMPONENTS: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVALID_TAX_DIRECTION: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVALID_TAX_REGION: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVALID_TRACKING: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVALID_VARIANT_OVERRIDE: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_INVENTORY_COUNT_APPROVED: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_INVENTORY_COUNT_NOT_FOUND: StatusCode = StatusCode::NOT_FOUND;
+pub const STATUS_INVENTORY_LOSS_LOCATION_MISSING: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_INVOICE_ALREADY_POSTED: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_INVOICE_FULLY_CREDITED: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_INVOICE_LINE_NOT_FOUND: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_LOTS_MISSING: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_LOTS_NOT_TRACKED: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_LOT_LINE_NOT_FOUND: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_LOT_NOT_FOUND: StatusCode = StatusCode::NOT_FOUND;
+pub const STATUS_LOT_NOT_IN_STOCK: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_LOT_QUANTITY_MISMATCH: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_MISSING_PRICE: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_MISSING_TAX: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_NESTED_TAX_GROUP: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_NO_MATCHING_TAX_RULE: StatusCode = StatusCode::UNPROCESSABLE_ENTITY;
+pub const STATUS_NUMBER_SEQUENCE_NOT_FOUND: StatusCode = StatusCode::NOT_FOUND;
+pub const STATUS_NUMBER_SEQUENCE_PERIOD_CLOSED: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_POSTING_ACCOUNT_MISSING: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_PRICE_LIST_ITEM_EXISTS: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_PRICE_LIST_NOT_FOUND: StatusCode = StatusCode::NOT_FOUND;
+pub const STATUS_PRODUCT_ATTRIBUTE_EXISTS: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_PRODUCT_ATTRIBUTE_NOT_FOUND: StatusCode = StatusCode::NOT_FOUND;
+pub const STATUS_PRODUCT_CATEGORY_IN_USE: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_PRODUCT_CATEGORY_NOT_FOUND: StatusCode = StatusCode::NOT_FOUND;
+pub const STATUS_PRODUCT_NOT_FOUND: StatusCode = StatusCode::NOT_FOUND;
+pub const STATUS_PURCHASE_INVOICE_ALREADY_IMPORTED: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_PURCHASE_INVOICE_NOT_FOUND: StatusCode = StatusCode::NOT_FOUND;
+pub const STATUS_PURCHASE_INVOICE_NOT_POSTED: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_PURCHASE_ORDER_ALREADY_CONFIRMED: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_PURCHASE_ORDER_ALREADY_RECEIVED: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_PURCHASE_ORDER_NOT_FOUND: StatusCode = StatusCode::NOT_FOUND;
+pub const STATUS_REORDER_RULE_EXISTS: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_REORDER_RULE_NOT_FOUND: StatusCode = StatusCode::NOT_FOUND;
+pub const STATUS_SALE_INVOICE_NOT_FOUND: StatusCode = StatusCode::NOT_FOUND;
+pub const STATUS_SALE_INVOICE_NOT_POSTED: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_SALE_ORDER_ALREADY_CONFIRMED: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_SALE_ORDER_ALREADY_DELIVERED: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_SALE_ORDER_CANCELLED: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_SALE_ORDER_DROP_SHIPPED: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_SALE_ORDER_NOT_CONFIRMED: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_SALE_ORDER_NOT_FOUND: StatusCode = StatusCode::NOT_FOUND;
+pub const STATUS_SERIAL_IN_STOCK: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_SERIAL_QUANTITY_NOT_ONE: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_STOCK_NOT_CONSIGNMENT: StatusCode = StatusCode::BAD_REQUEST;
+pub const STATUS_STOCK_NOT_FOUND: StatusCode = StatusCode::NOT_FOUND;
+pub const STATUS_STOCK_RETURN_WITHOUT_ORDER: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_SUPPLIER_LOCATION_MISSING: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_SUPPLIER_PRICE_EXISTS: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_SUPPLIER_PRICE_NOT_FOUND: StatusCode = StatusCode::NOT_FOUND;
+pub const STATUS_TAX_NOT_FOUND: StatusCode = StatusCode::NOT_FOUND;
+pub const STATUS_TAX_RATE_OVERLAP: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_TAX_RULE_NOT_FOUND: StatusCode = StatusCode::NOT_FOUND;
+pub const STATUS_UNIT_EXISTS: StatusCode = StatusCode::CONFLICT;
+pub const STATUS_UNIT_NOT_FOUND: StatusCode = StatusCode::NOT_FOUND;
+pub const STATUS_VARIANT_CODE_EXISTS: StatusCode = StatusCode::CONFLICT;
 pub const STATUS_VARIANT_NOT_FOUND: StatusCode = StatusCode::NOT_FOUND;
+pub const STATUS_VARIANT_NOT_STOCKABLE: StatusCode = StatusCode::BAD_REQUEST;
 
 pub enum ApiErrors {
     AccountNotFound,
+    ConsumptionExceedsStock,
+    ContactAccountMissing,
+    ContactNotCustomer,
+    ContactNotFound,
+    ContactNotSupplier,
+    CreditNoteExceedsInvoice,
+    CustomerLocationMissing,
+    CustomerStockMissing,
     DatabaseUnreachable,
+    DocumentRenderingFailed,
+    DropShipSupplierMissing,
+    DropShipVariantMissing,
+    DuplicateLot,
+    EinvoiceTaxGroup,
+    EinvoiceTooLarge,
+    EinvoiceUnreadable,
+    EinvoiceValidationFailed,
+    IncompatibleUnit,
+    InsufficientStock,
     InternalServerError,
     InvalidAccountType,
+    InvalidBaseUnit,
+    InvalidConsumptionTarget,
+    InvalidContactStockKind,
+    InvalidCostingMethod,
+    InvalidCountStock,
+    InvalidCurrency,
+    InvalidDate,
+    InvalidDateRange,
+    InvalidGtin,
+    InvalidNumberPattern,
+    InvalidParentStock,
+    InvalidProductType,
+    InvalidReorderQuantities,
+    InvalidReorderStock,
+    InvalidRoute,
+    InvalidStatus,
+    InvalidTaxCategory,
+    InvalidTaxComponents,
+    InvalidTaxDirection,
+    InvalidTaxRegion,
+    InvalidTracking,
+    InvalidVariantOverride,
+    InventoryCountApproved,
+    InventoryCountNotFound,
+    InventoryLossLocationMissing,
+    InvoiceAlreadyPosted,
+    InvoiceFullyCredited,
+    InvoiceLineNotFound,
+    LotLineNotFound,
+    LotNotFound,
+    LotNotInStock,
+    LotQuantityMismatch,
+    LotsMissing,
+    LotsNotTracked,
+    MissingPrice,
+    MissingTax,
+    NestedTaxGroup,
+    NoMatchingTaxRule,
+    NumberSequenceNotFound,
+    NumberSequencePeriodClosed,
+    PostingAccountMissing,
+    PriceListItemExists,
+    PriceListNotFound,
+    ProductAttributeExists,
+    ProductAttributeNotFound,
+    ProductCategoryInUse,
+    ProductCategoryNotFound,
+    ProductNotFound,
+    PurchaseInvoiceAlreadyImported,
+    PurchaseInvoiceNotFound,
+    PurchaseInvoiceNotPosted,
+    PurchaseOrderAlreadyConfirmed,
+    PurchaseOrderAlreadyReceived,
+    PurchaseOrderNotFound,
+    ReorderRuleExists,
+    ReorderRuleNotFound,
+    SaleInvoiceNotFound,
+    SaleInvoiceNotPosted,
+    SaleOrderAlreadyConfirmed,
+    SaleOrderAlreadyDelivered,
+    SaleOrderCancelled,
+    SaleOrderDropShipped,
+    SaleOrderNotConfirmed,
+    SaleOrderNotFound,
+    SerialInStock,
+    SerialQuantityNotOne,
+    StockNotConsignment,
+    StockNotFound,
+    StockReturnWithoutOrder,
+    SupplierLocationMissing,
+    SupplierPriceExists,
+    SupplierPriceNotFound,
+    TaxNotFound,
+    TaxRateOverlap,
+    TaxRuleNotFound,
+    UnitExists,
+    UnitNotFound,
+    VariantCodeExists,
     VariantNotFound,
+    VariantNotStockable,
 }
 
 impl ApiErrors {
     pub fn status(&self) -> StatusCode {
         match self {
             ApiErrors::AccountNotFound => STATUS_ACCOUNT_NOT_FOUND,
+            ApiErrors::ConsumptionExceedsStock => STATUS_CONSUMPTION_EXCEEDS_STOCK,
+            ApiErrors::ContactAccountMissing => STATUS_CONTACT_ACCOUNT_MISSING,
+            ApiErrors::ContactNotCustomer => STATUS_CONTACT_NOT_CUSTOMER,
+            ApiErrors::ContactNotFound => STATUS_CONTACT_NOT_FOUND,
+            ApiErrors::ContactNotSupplier => STATUS_CONTACT_NOT_SUPPLIER,
+            ApiErrors::CreditNoteExceedsInvoice => STATUS_CREDIT_NOTE_EXCEEDS_INVOICE,
+            ApiErrors::CustomerLocationMissing => STATUS_CUSTOMER_LOCATION_MISSING,
+            ApiErrors::CustomerStockMissing => STATUS_CUSTOMER_STOCK_MISSING,
             ApiErrors::DatabaseUnreachable => STATUS_DATABASE_UNREACHABLE,
+            ApiErrors::DocumentRenderingFailed => STATUS_DOCUMENT_RENDERING_FAILED,
+            ApiErrors::DropShipSupplierMissing => STATUS_DROP_SHIP_SUPPLIER_MISSING,
+            ApiErrors::DropShipVariantMissing => STATUS_DROP_SHIP_VARIANT_MISSING,
+            ApiErrors::DuplicateLot => STATUS_DUPLICATE_LOT,
+            ApiErrors::EinvoiceTaxGroup => STATUS_EINVOICE_TAX_GROUP,
+            ApiErrors::EinvoiceTooLarge => STATUS_EINVOICE_TOO_LARGE,
+            ApiErrors::EinvoiceUnreadable => STATUS_EINVOICE_UNREADABLE,
+            ApiErrors::EinvoiceValidationFailed => STATUS_EINVOICE_VALIDATION_FAILED,
+            ApiErrors::IncompatibleUnit => STATUS_INCOMPATIBLE_UNIT,
+            ApiErrors::InsufficientStock => STATUS_INSUFFICIENT_STOCK,
             ApiErrors::InternalServerError => STATUS_INTERNAL_SERVER_ERROR,
             ApiErrors::InvalidAccountType => STATUS_INVALID_ACCOUNT_TYPE,
+            ApiErrors::InvalidBaseUnit => STATUS_INVALID_BASE_UNIT,
+            ApiErrors::InvalidConsumptionTarget => STATUS_INVALID_CONSUMPTION_TARGET,
+            ApiErrors::InvalidContactStockKind => STATUS_INVALID_CONTACT_STOCK_KIND,
+            ApiErrors::InvalidCostingMethod => STATUS_INVALID_COSTING_METHOD,
+            ApiErrors::InvalidCountStock => STATUS_INVALID_COUNT_STOCK,
+            ApiErrors::InvalidCurrency => STATUS_INVALID_CURRENCY,
+            ApiErrors::InvalidDate => STATUS_INVALID_DATE,
+            ApiErrors::InvalidDateRange => STATUS_INVALID_DATE_RANGE,
+            ApiErrors::InvalidGtin => STATUS_INVALID_GTIN,
+            ApiErrors::InvalidNumberPattern => STATUS_INVALID_NUMBER_PATTERN,
+            ApiErrors::InvalidParentStock => STATUS_INVALID_PARENT_STOCK,
+            ApiErrors::InvalidProductType => STATUS_INVALID_PRODUCT_TYPE,
+            ApiErrors::InvalidReorderQuantities => STATUS_INVALID_REORDER_QUANTITIES,
+            ApiErrors::InvalidReorderStock => STATUS_INVALID_REORDER_STOCK,
+            ApiErrors::InvalidRoute => STATUS_INVALID_ROUTE,
+            ApiErrors::InvalidStatus => STATUS_INVALID_STATUS,
+            ApiErrors::InvalidTaxCategory => STATUS_INVALID_TAX_CATEGORY,
+            ApiErrors::InvalidTaxComponents => STATUS_INVALID_TAX_COMPONENTS,
+            ApiErrors::InvalidTaxDirection => STATUS_INVALID_TAX_DIRECTION,
+            ApiErrors::InvalidTaxRegion => STATUS_INVALID_TAX_REGION,
+            ApiErrors::InvalidTracking => STATUS_INVALID_TRACKING,
+            ApiErrors::InvalidVariantOverride => STATUS_INVALID_VARIANT_OVERRIDE,
+            ApiErrors::InventoryCountApproved => STATUS_INVENTORY_COUNT_APPROVED,
+            ApiErrors::InventoryCountNotFound => STATUS_INVENTORY_COUNT_NOT_FOUND,
+            ApiErrors::InventoryLossLocationMissing => STATUS_INVENTORY_LOSS_LOCATION_MISSING,
+            ApiErrors::InvoiceAlreadyPosted => STATUS_INVOICE_ALREADY_POSTED,
+            ApiErrors::InvoiceFullyCredited => STATUS_INVOICE_FULLY_CREDITED,
+            ApiErrors::InvoiceLineNotFound => STATUS_INVOICE_LINE_NOT_FOUND,
+            ApiErrors::LotLineNotFound => STATUS_LOT_LINE_NOT_FOUND,
+            ApiErrors::LotNotFound => STATUS_LOT_NOT_FOUND,
+            ApiErrors::LotNotInStock => STATUS_LOT_NOT_IN_STOCK,
+            ApiErrors::LotQuantityMismatch => STATUS_LOT_QUANTITY_MISMATCH,
+            ApiErrors::LotsMissing => STATUS_LOTS_MISSING,
+            ApiErrors::LotsNotTracked => STATUS_LOTS_NOT_TRACKED,
+            ApiErrors::MissingPrice => STATUS_MISSING_PRICE,
+            ApiErrors::MissingTax => STATUS_MISSING_TAX,
+            ApiErrors::NestedTaxGroup => STATUS_NESTED_TAX_GROUP,
+            ApiErrors::NoMatchingTaxRule => STATUS_NO_MATCHING_TAX_RULE,
+            ApiErrors::NumberSequenceNotFound => STATUS_NUMBER_SEQUENCE_NOT_FOUND,
+            ApiErrors::NumberSequencePeriodClosed => STATUS_NUMBER_SEQUENCE_PERIOD_CLOSED,
+            ApiErrors::PostingAccountMissing => STATUS_POSTING_ACCOUNT_MISSING,
+            ApiErrors::PriceListItemExists => STATUS_PRICE_LIST_ITEM_EXISTS,
+            ApiErrors::PriceListNotFound => STATUS_PRICE_LIST_NOT_FOUND,
+            ApiErrors::ProductAttributeExists => STATUS_PRODUCT_ATTRIBUTE_EXISTS,
+            ApiErrors::ProductAttributeNotFound => STATUS_PRODUCT_ATTRIBUTE_NOT_FOUND,
+            ApiErrors::ProductCategoryInUse => STATUS_PRODUCT_CATEGORY_IN_USE,
+            ApiErrors::ProductCategoryNotFound => STATUS_PRODUCT_CATEGORY_NOT_FOUND,
+            ApiErrors::ProductNotFound => STATUS_PRODUCT_NOT_FOUND,
+            ApiErrors::PurchaseInvoiceAlreadyImported => STATUS_PURCHASE_INVOICE_ALREADY_IMPORTED,
+            ApiErrors::PurchaseInvoiceNotFound => STATUS_PURCHASE_INVOICE_NOT_FOUND,
+            ApiErrors::PurchaseInvoiceNotPosted => STATUS_PURCHASE_INVOICE_NOT_POSTED,
+            ApiErrors::PurchaseOrderAlreadyConfirmed => STATUS_PURCHASE_ORDER_ALREADY_CONFIRMED,
+            ApiErrors::PurchaseOrderAlreadyReceived => STATUS_PURCHASE_ORDER_ALREADY_RECEIVED,
+            ApiErrors::PurchaseOrderNotFound => STATUS_PURCHASE_ORDER_NOT_FOUND,
+            ApiErrors::ReorderRuleExists => STATUS_REORDER_RULE_EXISTS,
+            ApiErrors::ReorderRuleNotFound => STATUS_REORDER_RULE_NOT_FOUND,
+            ApiErrors::SaleInvoiceNotFound => STATUS_SALE_INVOICE_NOT_FOUND,
+            ApiErrors::SaleInvoiceNotPosted => STATUS_SALE_INVOICE_NOT_POSTED,
+            ApiErrors::SaleOrderAlreadyConfirmed => STATUS_SALE_ORDER_ALREADY_CONFIRMED,
+            ApiErrors::SaleOrderAlreadyDelivered => STATUS_SALE_ORDER_ALREADY_DELIVERED,
+            ApiErrors::SaleOrderCancelled => STATUS_SALE_ORDER_CANCELLED,
+            ApiErrors::SaleOrderDropShipped => STATUS_SALE_ORDER_DROP_SHIPPED,
+            ApiErrors::SaleOrderNotConfirmed => STATUS_SALE_ORDER_NOT_CONFIRMED,
+            ApiErrors::SaleOrderNotFound => STATUS_SALE_ORDER_NOT_FOUND,
+            ApiErrors::SerialInStock => STATUS_SERIAL_IN_STOCK,
+            ApiErrors::SerialQuantityNotOne => STATUS_SERIAL_QUANTITY_NOT_ONE,
+            ApiErrors::StockNotConsignment => STATUS_STOCK_NOT_CONSIGNMENT,
+            ApiErrors::StockNotFound => STATUS_STOCK_NOT_FOUND,
+            ApiErrors::StockReturnWithoutOrder => STATUS_STOCK_RETURN_WITHOUT_ORDER,
+            ApiErrors::SupplierLocationMissing => STATUS_SUPPLIER_LOCATION_MISSING,
+            ApiErrors::SupplierPriceExists => STATUS_SUPPLIER_PRICE_EXISTS,
+            ApiErrors::SupplierPriceNotFound => STATUS_SUPPLIER_PRICE_NOT_FOUND,
+            ApiErrors::TaxNotFound => STATUS_TAX_NOT_FOUND,
+            ApiErrors::TaxRateOverlap => STATUS_TAX_RATE_OVERLAP,
+            ApiErrors::TaxRuleNotFound => STATUS_TAX_RULE_NOT_FOUND,
+            ApiErrors::UnitExists => STATUS_UNIT_EXISTS,
+            ApiErrors::UnitNotFound => STATUS_UNIT_NOT_FOUND,
+            ApiErrors::VariantCodeExists => STATUS_VARIANT_CODE_EXISTS,
             ApiErrors::VariantNotFound => STATUS_VARIANT_NOT_FOUND,
+            ApiErrors::VariantNotStockable => STATUS_VARIANT_NOT_STOCKABLE,
         }
     }
 
     pub fn message(&self) -> &'static str {
         match self {
             ApiErrors::AccountNotFound => MESSAGE_ACCOUNT_NOT_FOUND,
+            ApiErrors::ConsumptionExceedsStock => MESSAGE_CONSUMPTION_EXCEEDS_STOCK,
+            ApiErrors::ContactAccountMissing => MESSAGE_CONTACT_ACCOUNT_MISSING,
+            ApiErrors::ContactNotCustomer => MESSAGE_CONTACT_NOT_CUSTOMER,
+            ApiErrors::ContactNotFound => MESSAGE_CONTACT_NOT_FOUND,
+            ApiErrors::ContactNotSupplier => MESSAGE_CONTACT_NOT_SUPPLIER,
+            ApiErrors::CreditNoteExceedsInvoice => MESSAGE_CREDIT_NOTE_EXCEEDS_INVOICE,
+            ApiErrors::CustomerLocationMissing => MESSAGE_CUSTOMER_LOCATION_MISSING,
+            ApiErrors::CustomerStockMissing => MESSAGE_CUSTOMER_STOCK_MISSING,
             ApiErrors::DatabaseUnreachable => MESSAGE_DATABASE_UNREACHABLE,
+            ApiErrors::DocumentRenderingFailed => MESSAGE_DOCUMENT_RENDERING_FAILED,
+            ApiErrors::DropShipSupplierMissing => MESSAGE_DROP_SHIP_SUPPLIER_MISSING,
+            ApiErrors::DropShipVariantMissing => MESSAGE_DROP_SHIP_VARIANT_MISSING,
+            ApiErrors::DuplicateLot => MESSAGE_DUPLICATE_LOT,
+            ApiErrors::EinvoiceTaxGroup => MESSAGE_EINVOICE_TAX_GROUP,
+            ApiErrors::EinvoiceTooLarge => MESSAGE_EINVOICE_TOO_LARGE,
+            ApiErrors::EinvoiceUnreadable => MESSAGE_EINVOICE_UNREADABLE,
+            ApiErrors::EinvoiceValidationFailed => MESSAGE_EINVOICE_VALIDATION_FAILED,
+            ApiErrors::IncompatibleUnit => MESSAGE_INCOMPATIBLE_UNIT,
+            ApiErrors::InsufficientStock => MESSAGE_INSUFFICIENT_STOCK,
             ApiErrors::InternalServerError => MESSAGE_INTERNAL_SERVER_ERROR,
             ApiErrors::InvalidAccountType => MESSAGE_INVALID_ACCOUNT_TYPE,
+            ApiErrors::InvalidBaseUnit => MESSAGE_INVALID_BASE_UNIT,
+            ApiErrors::InvalidConsumptionTarget => MESSAGE_INVALID_CONSUMPTION_TARGET,
+            ApiErrors::InvalidContactStockKind => MESSAGE_INVALID_CONTACT_STOCK_KIND,
+            ApiErrors::InvalidCostingMethod => MESSAGE_INVALID_COSTING_METHOD,
+            ApiErrors::InvalidCountStock => MESSAGE_INVALID_COUNT_STOCK,
+            ApiErrors::InvalidCurrency => MESSAGE_INVALID_CURRENCY,
+            ApiErrors::InvalidDate => MESSAGE_INVALID_DATE,
+            ApiErrors::InvalidDateRange => MESSAGE_INVALID_DATE_RANGE,
+            ApiErrors::InvalidGtin => MESSAGE_INVALID_GTIN,
+            ApiErrors::InvalidNumberPattern => MESSAGE_INVALID_NUMBER_PATTERN,
+            ApiErrors::InvalidParentStock => MESSAGE_INVALID_PARENT_STOCK,
+            ApiErrors::InvalidProductType => MESSAGE_INVALID_PRODUCT_TYPE,
+            ApiErrors::InvalidReorderQuantities => MESSAGE_INVALID_REORDER_QUANTITIES,
+            ApiErrors::InvalidReorderStock => MESSAGE_INVALID_REORDER_STOCK,
+            ApiErrors::InvalidRoute => MESSAGE_INVALID_ROUTE,
+            ApiErrors::InvalidStatus => MESSAGE_INVALID_STATUS,
+            ApiErrors::InvalidTaxCategory => MESSAGE_INVALID_TAX_CATEGORY,
+            ApiErrors::InvalidTaxComponents => MESSAGE_INVALID_TAX_COMPONENTS,
+            ApiErrors::InvalidTaxDirection => MESSAGE_INVALID_TAX_DIRECTION,
+            ApiErrors::InvalidTaxRegion => MESSAGE_INVALID_TAX_REGION,
+            ApiErrors::InvalidTracking => MESSAGE_INVALID_TRACKING,
+            ApiErrors::InvalidVariantOverride => MESSAGE_INVALID_VARIANT_OVERRIDE,
+            ApiErrors::InventoryCountApproved => MESSAGE_INVENTORY_COUNT_APPROVED,
+            ApiErrors::InventoryCountNotFound => MESSAGE_INVENTORY_COUNT_NOT_FOUND,
+            ApiErrors::InventoryLossLocationMissing => MESSAGE_INVENTORY_LOSS_LOCATION_MISSING,
+            ApiErrors::InvoiceAlreadyPosted => MESSAGE_INVOICE_ALREADY_POSTED,
+            ApiErrors::InvoiceFullyCredited => MESSAGE_INVOICE_FULLY_CREDITED,
+            ApiErrors::InvoiceLineNotFound => MESSAGE_INVOICE_LINE_NOT_FOUND,
+            ApiErrors::LotLineNotFound => MESSAGE_LOT_LINE_NOT_FOUND,
+            ApiErrors::LotNotFound => MESSAGE_LOT_NOT_FOUND,
+            ApiErrors::LotNotInStock => MESSAGE_LOT_NOT_IN_STOCK,
+            ApiErrors::LotQuantityMismatch => MESSAGE_LOT_QUANTITY_MISMATCH,
+            ApiErrors::LotsMissing => MESSAGE_LOTS_MISSING,
+            ApiErrors::LotsNotTracked => MESSAGE_LOTS_NOT_TRACKED,
+            ApiErrors::MissingPrice => MESSAGE_MISSING_PRICE,
+            ApiErrors::MissingTax => MESSAGE_MISSING_TAX,
+            ApiErrors::NestedTaxGroup => MESSAGE_NESTED_TAX_GROUP,
+            ApiErrors::NoMatchingTaxRule => MESSAGE_NO_MATCHING_TAX_RULE,
+            ApiErrors::NumberSequenceNotFound => MESSAGE_NUMBER_SEQUENCE_NOT_FOUND,
+            ApiErrors::NumberSequencePeriodClosed => MESSAGE_NUMBER_SEQUENCE_PERIOD_CLOSED,
+            ApiErrors::PostingAccountMissing => MESSAGE_POSTING_ACCOUNT_MISSING,
+            ApiErrors::PriceListItemExists => MESSAGE_PRICE_LIST_ITEM_EXISTS,
+            ApiErrors::PriceListNotFound => MESSAGE_PRICE_LIST_NOT_FOUND,
+            ApiErrors::ProductAttributeExists => MESSAGE_PRODUCT_ATTRIBUTE_EXISTS,
+            ApiErrors::ProductAttributeNotFound => MESSAGE_PRODUCT_ATTRIBUTE_NOT_FOUND,
+            ApiErrors::ProductCategoryInUse => MESSAGE_PRODUCT_CATEGORY_IN_USE,
+            ApiErrors::ProductCategoryNotFound => MESSAGE_PRODUCT_CATEGORY_NOT_FOUND,
+            ApiErrors::ProductNotFound => MESSAGE_PRODUCT_NOT_FOUND,
+            ApiErrors::PurchaseInvoiceAlreadyImported => MESSAGE_PURCHASE_INVOICE_ALREADY_IMPORTED,
+            ApiErrors::PurchaseInvoiceNotFound => MESSAGE_PURCHASE_INVOICE_NOT_FOUND,
+            ApiErrors::PurchaseInvoiceNotPosted => MESSAGE_PURCHASE_INVOICE_NOT_POSTED,
+            ApiErrors::PurchaseOrderAlreadyConfirmed => MESSAGE_PURCHASE_ORDER_ALREADY_CONFIRMED,
+            ApiErrors::PurchaseOrderAlreadyReceived => MESSAGE_PURCHASE_ORDER_ALREADY_RECEIVED,
+            ApiErrors::PurchaseOrderNotFound => MESSAGE_PURCHASE_ORDER_NOT_FOUND,
+            ApiErrors::ReorderRuleExists => MESSAGE_REORDER_RULE_EXISTS,
+            ApiErrors::ReorderRuleNotFound => MESSAGE_REORDER_RULE_NOT_FOUND,
+            ApiErrors::SaleInvoiceNotFound => MESSAGE_SALE_INVOICE_NOT_FOUND,
+            ApiErrors::SaleInvoiceNotPosted => MESSAGE_SALE_INVOICE_NOT_POSTED,
+            ApiErrors::SaleOrderAlreadyConfirmed => MESSAGE_SALE_ORDER_ALREADY_CONFIRMED,
+            ApiErrors::SaleOrderAlreadyDelivered => MESSAGE_SALE_ORDER_ALREADY_DELIVERED,
+            ApiErrors::SaleOrderCancelled => MESSAGE_SALE_ORDER_CANCELLED,
+            ApiErrors::SaleOrderDropShipped => MESSAGE_SALE_ORDER_DROP_SHIPPED,
+            ApiErrors::SaleOrderNotConfirmed => MESSAGE_SALE_ORDER_NOT_CONFIRMED,
+            ApiErrors::SaleOrderNotFound => MESSAGE_SALE_ORDER_NOT_FOUND,
+            ApiErrors::SerialInStock => MESSAGE_SERIAL_IN_STOCK,
+            ApiErrors::SerialQuantityNotOne => MESSAGE_SERIAL_QUANTITY_NOT_ONE,
+            ApiErrors::StockNotConsignment => MESSAGE_STOCK_NOT_CONSIGNMENT,
+            ApiErrors::StockNotFound => MESSAGE_STOCK_NOT_FOUND,
+            ApiErrors::StockReturnWithoutOrder => MESSAGE_STOCK_RETURN_WITHOUT_ORDER,
+            ApiErrors::SupplierLocationMissing => MESSAGE_SUPPLIER_LOCATION_MISSING,
+            ApiErrors::SupplierPriceExists => MESSAGE_SUPPLIER_PRICE_EXISTS,
+            ApiErrors::SupplierPriceNotFound => MESSAGE_SUPPLIER_PRICE_NOT_FOUND,
+            ApiErrors::TaxNotFound => MESSAGE_TAX_NOT_FOUND,
+            ApiErrors::TaxRateOverlap => MESSAGE_TAX_RATE_OVERLAP,
+            ApiErrors::TaxRuleNotFound => MESSAGE_TAX_RULE_NOT_FOUND,
+            ApiErrors::UnitExists => MESSAGE_UNIT_EXISTS,
+            ApiErrors::UnitNotFound => MESSAGE_UNIT_NOT_FOUND,
+            ApiErrors::VariantCodeExists => MESSAGE_VARIANT_CODE_EXISTS,
             ApiErrors::VariantNotFound => MESSAGE_VARIANT_NOT_FOUND,
+            ApiErrors::VariantNotStockable => MESSAGE_VARIANT_NOT_STOCKABLE,
         }
     }
 
     pub fn code(&self) -> &'static str {
         match self {
             ApiErrors::AccountNotFound => CODE_ACCOUNT_NOT_FOUND,
+            ApiErrors::ConsumptionExceedsStock => CODE_CONSUMPTION_EXCEEDS_STOCK,
+            ApiErrors::ContactAccountMissing => CODE_CONTACT_ACCOUNT_MISSING,
+            ApiErrors::ContactNotCustomer => CODE_CONTACT_NOT_CUSTOMER,
+            ApiErrors::ContactNotFound => CODE_CONTACT_NOT_FOUND,
+            ApiErrors::ContactNotSupplier => CODE_CONTACT_NOT_SUPPLIER,
+            ApiErrors::CreditNoteExceedsInvoice => CODE_CREDIT_NOTE_EXCEEDS_INVOICE,
+            ApiErrors::CustomerLocationMissing => CODE_CUSTOMER_LOCATION_MISSING,
+            ApiErrors::CustomerStockMissing => CODE_CUSTOMER_STOCK_MISSING,
             ApiErrors::DatabaseUnreachable => CODE_DATABASE_UNREACHABLE,
+            ApiErrors::DocumentRenderingFailed => CODE_DOCUMENT_RENDERING_FAILED,
+            ApiErrors::DropShipSupplierMissing => CODE_DROP_SHIP_SUPPLIER_MISSING,
+            ApiErrors::DropShipVariantMissing => CODE_DROP_SHIP_VARIANT_MISSING,
+            ApiErrors::DuplicateLot => CODE_DUPLICATE_LOT,
+            ApiErrors::EinvoiceTaxGroup => CODE_EINVOICE_TAX_GROUP,
+            ApiErrors::EinvoiceTooLarge => CODE_EINVOICE_TOO_LARGE,
+            ApiErrors::EinvoiceUnreadable => CODE_EINVOICE_UNREADABLE,
+            ApiErrors::EinvoiceValidationFailed => CODE_EINVOICE_VALIDATION_FAILED,
+            ApiErrors::IncompatibleUnit => CODE_INCOMPATIBLE_UNIT,
+            ApiErrors::InsufficientStock => CODE_INSUFFICIENT_STOCK,
             ApiErrors::InternalServerError => CODE_INTERNAL_SERVER_ERROR,
             ApiErrors::InvalidAccountType => CODE_INVALID_ACCOUNT_TYPE,
+            ApiErrors::InvalidBaseUnit => CODE_INVALID_BASE_UNIT,
+            ApiErrors::InvalidConsumptionTarget => CODE_INVALID_CONSUMPTION_TARGET,
+            ApiErrors::InvalidContactStockKind => CODE_INVALID_CONTACT_STOCK_KIND,
+            ApiErrors::InvalidCostingMethod => CODE_INVALID_COSTING_METHOD,
+            ApiErrors::InvalidCountStock => CODE_INVALID_COUNT_STOCK,
+            ApiErrors::InvalidCurrency => CODE_INVALID_CURRENCY,
+            ApiErrors::InvalidDate => CODE_INVALID_DATE,
+            ApiErrors::InvalidDateRange => CODE_INVALID_DATE_RANGE,
+            ApiErrors::InvalidGtin => CODE_INVALID_GTIN,
+            ApiErrors::InvalidNumberPattern => CODE_INVALID_NUMBER_PATTERN,
+            ApiErrors::InvalidParentStock => CODE_INVALID_PARENT_STOCK,
+            ApiErrors::InvalidProductType => CODE_INVALID_PRODUCT_TYPE,
+            ApiErrors::InvalidReorderQuantities => CODE_INVALID_REORDER_QUANTITIES,
+            ApiErrors::InvalidReorderStock => CODE_INVALID_REORDER_STOCK,
+            ApiErrors::InvalidRoute => CODE_INVALID_ROUTE,
+            ApiErrors::InvalidStatus => CODE_INVALID_STATUS,
+            ApiErrors::InvalidTaxCategory => CODE_INVALID_TAX_CATEGORY,
+            ApiErrors::InvalidTaxComponents => CODE_INVALID_TAX_COMPONENTS,
+            ApiErrors::InvalidTaxDirection => CODE_INVALID_TAX_DIRECTION,
+            ApiErrors::InvalidTaxRegion => CODE_INVALID_TAX_REGION,
+            ApiErrors::InvalidTracking => CODE_INVALID_TRACKING,
+            ApiErrors::InvalidVariantOverride => CODE_INVALID_VARIANT_OVERRIDE,
+            ApiErrors::InventoryCountApproved => CODE_INVENTORY_COUNT_APPROVED,
+            ApiErrors::InventoryCountNotFound => CODE_INVENTORY_COUNT_NOT_FOUND,
+            ApiErrors::InventoryLossLocationMissing => CODE_INVENTORY_LOSS_LOCATION_MISSING,
+            ApiErrors::InvoiceAlreadyPosted => CODE_INVOICE_ALREADY_POSTED,
+            ApiErrors::InvoiceFullyCredited => CODE_INVOICE_FULLY_CREDITED,
+            ApiErrors::InvoiceLineNotFound => CODE_INVOICE_LINE_NOT_FOUND,
+            ApiErrors::LotLineNotFound => CODE_LOT_LINE_NOT_FOUND,
+            ApiErrors::LotNotFound => CODE_LOT_NOT_FOUND,
+            ApiErrors::LotNotInStock => CODE_LOT_NOT_IN_STOCK,
+            ApiErrors::LotQuantityMismatch => CODE_LOT_QUANTITY_MISMATCH,
+            ApiErrors::LotsMissing => CODE_LOTS_MISSING,
+            ApiErrors::LotsNotTracked => CODE_LOTS_NOT_TRACKED,
+            ApiErrors::MissingPrice => CODE_MISSING_PRICE,
+            ApiErrors::MissingTax => CODE_MISSING_TAX,
+            ApiErrors::NestedTaxGroup => CODE_NESTED_TAX_GROUP,
+            ApiErrors::NoMatchingTaxRule => CODE_NO_MATCHING_TAX_RULE,
+            ApiErrors::NumberSequenceNotFound => CODE_NUMBER_SEQUENCE_NOT_FOUND,
+            ApiErrors::NumberSequencePeriodClosed => CODE_NUMBER_SEQUENCE_PERIOD_CLOSED,
+            ApiErrors::PostingAccountMissing => CODE_POSTING_ACCOUNT_MISSING,
+            ApiErrors::PriceListItemExists => CODE_PRICE_LIST_ITEM_EXISTS,
+            ApiErrors::PriceListNotFound => CODE_PRICE_LIST_NOT_FOUND,
+            ApiErrors::ProductAttributeExists => CODE_PRODUCT_ATTRIBUTE_EXISTS,
+            ApiErrors::ProductAttributeNotFound => CODE_PRODUCT_ATTRIBUTE_NOT_FOUND,
+            ApiErrors::ProductCategoryInUse => CODE_PRODUCT_CATEGORY_IN_USE,
+            ApiErrors::ProductCategoryNotFound => CODE_PRODUCT_CATEGORY_NOT_FOUND,
+            ApiErrors::ProductNotFound => CODE_PRODUCT_NOT_FOUND,
+            ApiErrors::PurchaseInvoiceAlreadyImported => CODE_PURCHASE_INVOICE_ALREADY_IMPORTED,
+            ApiErrors::PurchaseInvoiceNotFound => CODE_PURCHASE_INVOICE_NOT_FOUND,
+            ApiErrors::PurchaseInvoiceNotPosted => CODE_PURCHASE_INVOICE_NOT_POSTED,
+            ApiErrors::PurchaseOrderAlreadyConfirmed => CODE_PURCHASE_ORDER_ALREADY_CONFIRMED,
+            ApiErrors::PurchaseOrderAlreadyReceived => CODE_PURCHASE_ORDER_ALREADY_RECEIVED,
+            ApiErrors::PurchaseOrderNotFound => CODE_PURCHASE_ORDER_NOT_FOUND,
+            ApiErrors::ReorderRuleExists => CODE_REORDER_RULE_EXISTS,
+            ApiErrors::ReorderRuleNotFound => CODE_REORDER_RULE_NOT_FOUND,
+            ApiErrors::SaleInvoiceNotFound => CODE_SALE_INVOICE_NOT_FOUND,
+            ApiErrors::SaleInvoiceNotPosted => CODE_SALE_INVOICE_NOT_POSTED,
+            ApiErrors::SaleOrderAlreadyConfirmed => CODE_SALE_ORDER_ALREADY_CONFIRMED,
+            ApiErrors::SaleOrderAlreadyDelivered => CODE_SALE_ORDER_ALREADY_DELIVERED,
+            ApiErrors::SaleOrderCancelled => CODE_SALE_ORDER_CANCELLED,
+            ApiErrors::SaleOrderDropShipped => CODE_SALE_ORDER_DROP_SHIPPED,
+            ApiErrors::SaleOrderNotConfirmed => CODE_SALE_ORDER_NOT_CONFIRMED,
+            ApiErrors::SaleOrderNotFound => CODE_SALE_ORDER_NOT_FOUND,
+            ApiErrors::SerialInStock => CODE_SERIAL_IN_STOCK,
+            ApiErrors::SerialQuantityNotOne => CODE_SERIAL_QUANTITY_NOT_ONE,
+            ApiErrors::StockNotConsignment => CODE_STOCK_NOT_CONSIGNMENT,
+            ApiErrors::StockNotFound => CODE_STOCK_NOT_FOUND,
+            ApiErrors::StockReturnWithoutOrder => CODE_STOCK_RETURN_WITHOUT_ORDER,
+            ApiErrors::SupplierLocationMissing => CODE_SUPPLIER_LOCATION_MISSING,
+            ApiErrors::SupplierPriceExists => CODE_SUPPLIER_PRICE_EXISTS,
+            ApiErrors::SupplierPriceNotFound => CODE_SUPPLIER_PRICE_NOT_FOUND,
+            ApiErrors::TaxNotFound => CODE_TAX_NOT_FOUND,
+            ApiErrors::TaxRateOverlap => CODE_TAX_RATE_OVERLAP,
+            ApiErrors::TaxRuleNotFound => CODE_TAX_RULE_NOT_FOUND,
+            ApiErrors::UnitExists => CODE_UNIT_EXISTS,
+            ApiErrors::UnitNotFound => CODE_UNIT_NOT_FOUND,
+            ApiErrors::VariantCodeExists => CODE_VARIANT_CODE_EXISTS,
             ApiErrors::VariantNotFound => CODE_VARIANT_NOT_FOUND,
+            ApiErrors::VariantNotStockable => CODE_VARIANT_NOT_STOCKABLE,
         }
     }
 
@@ -4,7 +4,9 @@ use actix_web_validator::{Json, Query};
 use crate::{
     err::{self, ApiErrors},
     models::{ContactModel, CreateContactInput, ListContactsInput, Page},
+    repositories::ContactDraft,
     state::AppState,
+    utils::currency::normalize_currency,
 };
 
 #[utoipa::path(
@@ -13,6 +15,12 @@ use crate::{
     description = "Create a customer, connect it with an existing account and return the new created customer",
     responses(
         (status = 200, description = "Returns the created customer contact", body = ContactModel),
+        (status = 400, description = err::MESSAGE_INVALID_CURRENCY, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_CURRENCY,
+                "errorCode": err::CODE_INVALID_CURRENCY,
+            })),
+        )),
         (status = 400, description = err::MESSAGE_ACCOUNT_NOT_FOUND, content(
             (String = "application/json", example = json!({
                 "error": err::MESSAGE_ACCOUNT_NOT_FOUND,
@@ -40,6 +48,10 @@ async fn create_customer(
 ) -> HttpResponse {
     let payload = payload.into_inner();
 
+    let Some(currency) = normalize_currency(&payload.currency) else {
+        return ApiErrors::InvalidCurrency.into();
+    };
+
     let query = state.ledger_accounts.get(payload.account_id);
 
     match query.await {
@@ -48,15 +60,16 @@ async fn create_customer(
         Err(_) => return ApiErrors::InternalServerError.into(),
     };
 
-    let query = state.contacts.create_customer(
-        payload.name,
-        payload.account_id,
-        payload.street1,
-        payload.street2,
-        payload.postal_code,
-        payload.city,
-        payload.country,
-    );
+    let query = state.contacts.create_customer(ContactDraft {
+        name: payload.name,
+        account_id: payload.account_id,
+        street1: payload.street1,
+        street2: payload.street2,
+        postal_code: payload.postal_code,
+        city: payload.city,
+        country: payload.country,
+        currency,
+    });
 
     let entity = match query.await {
         Ok(entity) => entity,
@@ -73,6 +86,12 @@ async fn create_customer(
     description = "Create a supplier, connect it with an existing account and return the new created supplier",
     responses(
         (status = 200, description = "Returns the created supplier contact", body = ContactModel),
+        (status = 400, description = err::MESSAGE_INVALID_CURRENCY, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_CURRENCY,
+                "errorCode": err::CODE_INVALID_CURRENCY,
+            })),
+        )),
         (status = 404, description = "Ledger account could not be found")
     )
 )]
@@ -83,6 +102,10 @@ async fn create_supplier(
 ) -> HttpResponse {
     let payload = payload.into_inner();
 
+    let Some(currency) = normalize_currency(&payload.currency) else {
+        return ApiErrors::InvalidCurrency.into();
+    };
+
     let query = state.ledger_accounts.get(payload.account_id);
 
     match query.await {
@@ -91,15 +114,16 @@ async fn create_supplier(
         Err(_) => return HttpResponse::InternalServerError().finish(),
     };
 
-    let query = state.contacts.create_supplier(
-        payload.name,
-        payload.account_id,
-        payload.street1,
-        payload.street2,
-        payload.postal_code,
-        payload.city,
-        payload.country,
-    );
+    let query = state.contacts.create_supplier(ContactDraft {
+        name: payload.name,
+        account_id: payload.account_id,
+        street1: payload.street1,
+        street2: payload.street2,
+        postal_code: payload.postal_code,
+        city: payload.city,
+        country: payload.country,
+        currency,
+    });
 
     let entity = match query.await {
         Ok(entity) => entity,
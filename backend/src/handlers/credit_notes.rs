@@ -0,0 +1,960 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpResponse, get, post, web};
+use actix_web_validator::Json;
+use chrono::NaiveDate;
+use entity::sea_orm_active_enums::{PurchaseInvoiceStatus, SaleInvoiceStatus, Tracking};
+use sea_orm::DbErr;
+
+use crate::{
+    err::{self, ApiErrors},
+    handlers::{
+        stock_moves::{lot_draft, lot_error, lot_problem},
+        taxes::resolve_taxes,
+    },
+    models::{
+        CreateCreditNoteInput, CreditNoteLineInput, CreditNoteLineModel, CreditNoteModel,
+        InvoiceCreditNotesModel, LineLotInput,
+    },
+    repositories::{
+        CreditNoteLineDraft, ERR_CREDIT_EXCEEDS_INVOICE, ERR_PERIOD_CLOSED, QUANTITY_TOLERANCE,
+        ResolvedTax, TaxPostingDraft,
+    },
+    state::AppState,
+    utils::{
+        lots::check_lots,
+        tax_groups::component_bases,
+        totals::{Discount, line_net, tax_total, taxable_nets},
+    },
+};
+
+/// The fields of sale and purchase invoice lines credit notes and postings
+/// work with.
+pub(super) struct InvoiceLine {
+    pub(super) id: i32,
+    pub(super) description: String,
+    pub(super) quantity: f64,
+    pub(super) price: i32,
+    pub(super) tax: i32,
+    pub(super) tax_id: Option<i32>,
+    pub(super) variant_id: Option<i32>,
+    pub(super) transaction_id: Option<i32>,
+    pub(super) tax_transaction_id: Option<i32>,
+    /// The postings of the tax group components as tax id and transaction.
+    pub(super) tax_postings: Vec<(i32, Option<i32>)>,
+    pub(super) discount: Discount,
+    pub(super) discount_transaction_id: Option<i32>,
+}
+
+impl InvoiceLine {
+    /// Attaches the component postings, which are stored apart from the
+    /// lines.
+    pub(super) fn with_tax_postings(mut self, postings: &[(i32, i32, Option<i32>)]) -> Self {
+        self.tax_postings = postings
+            .iter()
+            .filter(|(line_id, _, _)| *line_id == self.id)
+            .map(|(_, tax_id, transaction_id)| (*tax_id, *transaction_id))
+            .collect();
+        self
+    }
+}
+
+impl From<&entity::sale_invoice_lines::Model> for InvoiceLine {
+    fn from(entity: &entity::sale_invoice_lines::Model) -> Self {
+        Self {
+            id: entity.id,
+            description: entity.description.clone(),
+            quantity: entity.quantity,
+            price: entity.price,
+            tax: entity.tax,
+            tax_id: entity.tax_id,
+            variant_id: entity.variant_id,
+            transaction_id: entity.transaction_id,
+            tax_transaction_id: entity.tax_transaction_id,
+            tax_postings: Vec::new(),
+            discount: Discount::new(entity.discount_percent, entity.discount_amount),
+            discount_transaction_id: entity.discount_transaction_id,
+        }
+    }
+}
+
+impl From<&entity::purchase_invoice_lines::Model> for InvoiceLine {
+    fn from(entity: &entity::purchase_invoice_lines::Model) -> Self {
+        Self {
+            id: entity.id,
+            description: entity.description.clone(),
+            quantity: entity.quantity,
+            price: entity.price,
+            tax: entity.tax,
+            tax_id: entity.tax_id,
+            variant_id: entity.variant_id,
+            transaction_id: entity.transaction_id,
+            tax_transaction_id: entity.tax_transaction_id,
+            tax_postings: Vec::new(),
+            discount: Discount::new(entity.discount_percent, entity.discount_amount),
+            discount_transaction_id: entity.discount_transaction_id,
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Sale invoices",
+    summary = "Credit a sale invoice",
+    description = "Create a credit note for selected lines and quantities of a posted sale invoice, or for everything that is left of it when no lines are given. \
+        The revenue and tax postings of the lines are reversed and with `returnStock` the goods are moved back from the customer. \
+        Returned goods of lot or serial tracked variants are moved in the lots given for their lines. \
+        The reference is the next number of the gapless sale credit note sequence.",
+    responses(
+        (status = 200, description = "Returns the created credit note", body = CreditNoteModel),
+        (status = 400, description = err::MESSAGE_INVALID_DATE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_DATE,
+                "errorCode": err::CODE_INVALID_DATE,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_DUPLICATE_LOT, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_DUPLICATE_LOT,
+                "errorCode": err::CODE_DUPLICATE_LOT,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INVOICE_LINE_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVOICE_LINE_NOT_FOUND,
+                "errorCode": err::CODE_INVOICE_LINE_NOT_FOUND,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_LOT_LINE_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOT_LINE_NOT_FOUND,
+                "errorCode": err::CODE_LOT_LINE_NOT_FOUND,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_LOT_QUANTITY_MISMATCH, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOT_QUANTITY_MISMATCH,
+                "errorCode": err::CODE_LOT_QUANTITY_MISMATCH,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_LOTS_MISSING, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOTS_MISSING,
+                "errorCode": err::CODE_LOTS_MISSING,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_LOTS_NOT_TRACKED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOTS_NOT_TRACKED,
+                "errorCode": err::CODE_LOTS_NOT_TRACKED,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_SERIAL_QUANTITY_NOT_ONE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SERIAL_QUANTITY_NOT_ONE,
+                "errorCode": err::CODE_SERIAL_QUANTITY_NOT_ONE,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_SALE_INVOICE_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SALE_INVOICE_NOT_FOUND,
+                "errorCode": err::CODE_SALE_INVOICE_NOT_FOUND,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_SALE_INVOICE_NOT_POSTED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SALE_INVOICE_NOT_POSTED,
+                "errorCode": err::CODE_SALE_INVOICE_NOT_POSTED,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_CREDIT_NOTE_EXCEEDS_INVOICE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_CREDIT_NOTE_EXCEEDS_INVOICE,
+                "errorCode": err::CODE_CREDIT_NOTE_EXCEEDS_INVOICE,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_INVOICE_FULLY_CREDITED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVOICE_FULLY_CREDITED,
+                "errorCode": err::CODE_INVOICE_FULLY_CREDITED,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_NUMBER_SEQUENCE_PERIOD_CLOSED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_NUMBER_SEQUENCE_PERIOD_CLOSED,
+                "errorCode": err::CODE_NUMBER_SEQUENCE_PERIOD_CLOSED,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_SERIAL_IN_STOCK, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SERIAL_IN_STOCK,
+                "errorCode": err::CODE_SERIAL_IN_STOCK,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/sale-invoices/{id}/credit-notes")]
+async fn create_sale(
+    path: web::Path<i32>,
+    payload: Json<CreateCreditNoteInput>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let payload = payload.into_inner();
+
+    let date = match NaiveDate::parse_from_str(&payload.date, "%Y-%m-%d") {
+        Ok(date) => date,
+        Err(_) => return ApiErrors::InvalidDate.into(),
+    };
+
+    let query = state.sale_invoices.get(path.into_inner());
+    let invoice = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::SaleInvoiceNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    match invoice.status {
+        SaleInvoiceStatus::Invoice | SaleInvoiceStatus::Finished => {}
+        SaleInvoiceStatus::Draft | SaleInvoiceStatus::Proforma => {
+            return ApiErrors::SaleInvoiceNotPosted.into();
+        }
+    }
+
+    let query = state.sale_invoice_lines.list_for_invoice(invoice.id);
+    let lines: Vec<InvoiceLine> = match query.await {
+        Ok(lines) => lines.iter().map(InvoiceLine::from).collect(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state.sale_invoice_lines.list_taxes_for_invoice(invoice.id);
+    let postings: Vec<(i32, i32, Option<i32>)> = match query.await {
+        Ok(postings) => postings
+            .iter()
+            .map(|posting| (posting.line_id, posting.tax_id, posting.transaction_id))
+            .collect(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+    let lines: Vec<InvoiceLine> = lines
+        .into_iter()
+        .map(|line| line.with_tax_postings(&postings))
+        .collect();
+
+    let query = state.sale_credit_notes.list_lines_for_invoice(invoice.id);
+    let credited: Vec<CreditNoteLineModel> = match query.await {
+        Ok(lines) => lines.iter().map(CreditNoteLineModel::from).collect(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    // The goods went from our stock to the customer's stock of the order.
+    let stock_return = match payload.return_stock {
+        true => match state.sale_orders.get(invoice.order_id).await {
+            Ok(Some(order)) => Some((order.target_stock_id, order.source_stock_id)),
+            Ok(None) => return ApiErrors::SaleOrderNotFound.into(),
+            Err(_) => return ApiErrors::InternalServerError.into(),
+        },
+        false => None,
+    };
+
+    let taxes = match resolve_taxes(
+        lines.iter().filter_map(|line| line.tax_id),
+        invoice.date,
+        &state,
+    )
+    .await
+    {
+        Ok(taxes) => taxes,
+        Err(response) => return response,
+    };
+
+    let stockable = match stockable_variants(&lines, stock_return, &state).await {
+        Ok(stockable) => stockable,
+        Err(response) => return response,
+    };
+
+    let mut drafts = match drafts(
+        &lines,
+        Discount::new(invoice.discount_percent, invoice.discount_amount),
+        &credited,
+        payload.lines.as_deref(),
+        &taxes,
+        stock_return,
+        &stockable,
+    ) {
+        Ok(drafts) => drafts,
+        Err(error) => return error.into(),
+    };
+    if let Err(response) = return_lots(&mut drafts, payload.lots, &state).await {
+        return response;
+    }
+
+    let query = state
+        .sale_credit_notes
+        .create(invoice.id, date, payload.reason, drafts);
+    let (credit_note, lines) = match query.await {
+        Ok(result) => result,
+        Err(error) => return create_error(error).into(),
+    };
+
+    let lines: Vec<CreditNoteLineModel> = lines.iter().map(CreditNoteLineModel::from).collect();
+    let total = credited_total(&lines, &taxes);
+    let model = CreditNoteModel::sale(&credit_note, lines, total);
+    HttpResponse::Ok().json(model)
+}
+
+#[utoipa::path(
+    tag = "Sale invoices",
+    summary = "List the credit notes of a sale invoice",
+    description = "List the credit notes of a sale invoice together with the invoice total, the credited total and the open amount that is left",
+    responses(
+        (status = 200, description = "Returns the credit notes and totals", body = InvoiceCreditNotesModel),
+        (status = 404, description = err::MESSAGE_SALE_INVOICE_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SALE_INVOICE_NOT_FOUND,
+                "errorCode": err::CODE_SALE_INVOICE_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/sale-invoices/{id}/credit-notes")]
+async fn list_sale(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let query = state.sale_invoices.get(path.into_inner());
+    let invoice = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::SaleInvoiceNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state.sale_invoice_lines.list_for_invoice(invoice.id);
+    let lines: Vec<InvoiceLine> = match query.await {
+        Ok(lines) => lines.iter().map(InvoiceLine::from).collect(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state.sale_credit_notes.list_for_invoice(invoice.id);
+    let credit_notes = match query.await {
+        Ok(credit_notes) => credit_notes,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state.sale_credit_notes.list_lines_for_invoice(invoice.id);
+    let credited = match query.await {
+        Ok(lines) => lines,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let taxes = match resolve_taxes(
+        lines.iter().filter_map(|line| line.tax_id),
+        invoice.date,
+        &state,
+    )
+    .await
+    {
+        Ok(taxes) => taxes,
+        Err(response) => return response,
+    };
+
+    let credit_notes = credit_notes
+        .iter()
+        .map(|credit_note| {
+            let lines: Vec<CreditNoteLineModel> = credited
+                .iter()
+                .filter(|line| line.credit_note_id == credit_note.id)
+                .map(CreditNoteLineModel::from)
+                .collect();
+            let total = credited_total(&lines, &taxes);
+            CreditNoteModel::sale(credit_note, lines, total)
+        })
+        .collect();
+
+    let discount = Discount::new(invoice.discount_percent, invoice.discount_amount);
+    HttpResponse::Ok().json(summary(&lines, discount, &taxes, credit_notes))
+}
+
+#[utoipa::path(
+    tag = "Purchase invoices",
+    summary = "Credit a purchase invoice",
+    description = "Record a credit note of the supplier for selected lines and quantities of a finished purchase invoice, or for everything that is left of it when no lines are given. \
+        The expense and input tax postings of the lines are reversed and with `returnStock` the goods are moved back to the supplier. \
+        Returned goods of lot or serial tracked variants are moved in the lots given for their lines, which the stock must hold. \
+        The reference is the next number of the gapless purchase credit note sequence.",
+    responses(
+        (status = 200, description = "Returns the created credit note", body = CreditNoteModel),
+        (status = 400, description = err::MESSAGE_INVALID_DATE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_DATE,
+                "errorCode": err::CODE_INVALID_DATE,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_DUPLICATE_LOT, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_DUPLICATE_LOT,
+                "errorCode": err::CODE_DUPLICATE_LOT,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INVOICE_LINE_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVOICE_LINE_NOT_FOUND,
+                "errorCode": err::CODE_INVOICE_LINE_NOT_FOUND,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_LOT_LINE_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOT_LINE_NOT_FOUND,
+                "errorCode": err::CODE_LOT_LINE_NOT_FOUND,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_LOT_QUANTITY_MISMATCH, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOT_QUANTITY_MISMATCH,
+                "errorCode": err::CODE_LOT_QUANTITY_MISMATCH,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_LOTS_MISSING, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOTS_MISSING,
+                "errorCode": err::CODE_LOTS_MISSING,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_LOTS_NOT_TRACKED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOTS_NOT_TRACKED,
+                "errorCode": err::CODE_LOTS_NOT_TRACKED,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_SERIAL_QUANTITY_NOT_ONE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SERIAL_QUANTITY_NOT_ONE,
+                "errorCode": err::CODE_SERIAL_QUANTITY_NOT_ONE,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_LOT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOT_NOT_FOUND,
+                "errorCode": err::CODE_LOT_NOT_FOUND,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_PURCHASE_INVOICE_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PURCHASE_INVOICE_NOT_FOUND,
+                "errorCode": err::CODE_PURCHASE_INVOICE_NOT_FOUND,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_PURCHASE_INVOICE_NOT_POSTED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PURCHASE_INVOICE_NOT_POSTED,
+                "errorCode": err::CODE_PURCHASE_INVOICE_NOT_POSTED,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_STOCK_RETURN_WITHOUT_ORDER, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_STOCK_RETURN_WITHOUT_ORDER,
+                "errorCode": err::CODE_STOCK_RETURN_WITHOUT_ORDER,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_CREDIT_NOTE_EXCEEDS_INVOICE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_CREDIT_NOTE_EXCEEDS_INVOICE,
+                "errorCode": err::CODE_CREDIT_NOTE_EXCEEDS_INVOICE,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_INVOICE_FULLY_CREDITED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVOICE_FULLY_CREDITED,
+                "errorCode": err::CODE_INVOICE_FULLY_CREDITED,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_LOT_NOT_IN_STOCK, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOT_NOT_IN_STOCK,
+                "errorCode": err::CODE_LOT_NOT_IN_STOCK,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_NUMBER_SEQUENCE_PERIOD_CLOSED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_NUMBER_SEQUENCE_PERIOD_CLOSED,
+                "errorCode": err::CODE_NUMBER_SEQUENCE_PERIOD_CLOSED,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/purchase-invoices/{id}/credit-notes")]
+async fn create_purchase(
+    path: web::Path<i32>,
+    payload: Json<CreateCreditNoteInput>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let payload = payload.into_inner();
+
+    let date = match NaiveDate::parse_from_str(&payload.date, "%Y-%m-%d") {
+        Ok(date) => date,
+        Err(_) => return ApiErrors::InvalidDate.into(),
+    };
+
+    let query = state.purchase_invoices.get(path.into_inner());
+    let invoice = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::PurchaseInvoiceNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    match invoice.status {
+        PurchaseInvoiceStatus::Finished => {}
+        PurchaseInvoiceStatus::Draft => return ApiErrors::PurchaseInvoiceNotPosted.into(),
+    }
+
+    let query = state.purchase_invoice_lines.list_for_invoice(invoice.id);
+    let lines: Vec<InvoiceLine> = match query.await {
+        Ok(lines) => lines.iter().map(InvoiceLine::from).collect(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state
+        .purchase_invoice_lines
+        .list_taxes_for_invoice(invoice.id);
+    let postings: Vec<(i32, i32, Option<i32>)> = match query.await {
+        Ok(postings) => postings
+            .iter()
+            .map(|posting| (posting.line_id, posting.tax_id, posting.transaction_id))
+            .collect(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+    let lines: Vec<InvoiceLine> = lines
+        .into_iter()
+        .map(|line| line.with_tax_postings(&postings))
+        .collect();
+
+    let query = state
+        .purchase_credit_notes
+        .list_lines_for_invoice(invoice.id);
+    let credited: Vec<CreditNoteLineModel> = match query.await {
+        Ok(lines) => lines.iter().map(CreditNoteLineModel::from).collect(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    // The goods went from the supplier's stock to our stock of the order.
+    // Imported invoices may not belong to any order.
+    let stock_return = match (payload.return_stock, invoice.order_id) {
+        (true, Some(order_id)) => match state.purchase_orders.get(order_id).await {
+            Ok(Some(order)) => Some((order.target_stock_id, order.source_stock_id)),
+            Ok(None) => return ApiErrors::PurchaseOrderNotFound.into(),
+            Err(_) => return ApiErrors::InternalServerError.into(),
+        },
+        (true, None) => return ApiErrors::StockReturnWithoutOrder.into(),
+        (false, _) => None,
+    };
+
+    let taxes = match resolve_taxes(
+        lines.iter().filter_map(|line| line.tax_id),
+        invoice.date,
+        &state,
+    )
+    .await
+    {
+        Ok(taxes) => taxes,
+        Err(response) => return response,
+    };
+
+    let stockable = match stockable_variants(&lines, stock_return, &state).await {
+        Ok(stockable) => stockable,
+        Err(response) => return response,
+    };
+
+    let mut drafts = match drafts(
+        &lines,
+        Discount::new(invoice.discount_percent, invoice.discount_amount),
+        &credited,
+        payload.lines.as_deref(),
+        &taxes,
+        stock_return,
+        &stockable,
+    ) {
+        Ok(drafts) => drafts,
+        Err(error) => return error.into(),
+    };
+    if let Err(response) = return_lots(&mut drafts, payload.lots, &state).await {
+        return response;
+    }
+
+    let query = state
+        .purchase_credit_notes
+        .create(invoice.id, date, payload.reason, drafts);
+    let (credit_note, lines) = match query.await {
+        Ok(result) => result,
+        Err(error) => return create_error(error).into(),
+    };
+
+    let lines: Vec<CreditNoteLineModel> = lines.iter().map(CreditNoteLineModel::from).collect();
+    let total = credited_total(&lines, &taxes);
+    let model = CreditNoteModel::purchase(&credit_note, lines, total);
+    HttpResponse::Ok().json(model)
+}
+
+#[utoipa::path(
+    tag = "Purchase invoices",
+    summary = "List the credit notes of a purchase invoice",
+    description = "List the credit notes of a purchase invoice together with the invoice total, the credited total and the open amount that is left",
+    responses(
+        (status = 200, description = "Returns the credit notes and totals", body = InvoiceCreditNotesModel),
+        (status = 404, description = err::MESSAGE_PURCHASE_INVOICE_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PURCHASE_INVOICE_NOT_FOUND,
+                "errorCode": err::CODE_PURCHASE_INVOICE_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/purchase-invoices/{id}/credit-notes")]
+async fn list_purchase(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let query = state.purchase_invoices.get(path.into_inner());
+    let invoice = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::PurchaseInvoiceNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state.purchase_invoice_lines.list_for_invoice(invoice.id);
+    let lines: Vec<InvoiceLine> = match query.await {
+        Ok(lines) => lines.iter().map(InvoiceLine::from).collect(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state.purchase_credit_notes.list_for_invoice(invoice.id);
+    let credit_notes = match query.await {
+        Ok(credit_notes) => credit_notes,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state
+        .purchase_credit_notes
+        .list_lines_for_invoice(invoice.id);
+    let credited = match query.await {
+        Ok(lines) => lines,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let taxes = match resolve_taxes(
+        lines.iter().filter_map(|line| line.tax_id),
+        invoice.date,
+        &state,
+    )
+    .await
+    {
+        Ok(taxes) => taxes,
+        Err(response) => return response,
+    };
+
+    let credit_notes = credit_notes
+        .iter()
+        .map(|credit_note| {
+            let lines: Vec<CreditNoteLineModel> = credited
+                .iter()
+                .filter(|line| line.credit_note_id == credit_note.id)
+                .map(CreditNoteLineModel::from)
+                .collect();
+            let total = credited_total(&lines, &taxes);
+            CreditNoteModel::purchase(credit_note, lines, total)
+        })
+        .collect();
+
+    let discount = Discount::new(invoice.discount_percent, invoice.discount_amount);
+    HttpResponse::Ok().json(summary(&lines, discount, &taxes, credit_notes))
+}
+
+/// Splits a line's net amount into one base per tax component, given as
+/// component tax id, base and rate. Lines without a known tax fall back to
+/// their percentage, like on the printed documents.
+pub(super) fn tax_bases(
+    net: i64,
+    tax_id: Option<i32>,
+    tax: i32,
+    taxes: &HashMap<i32, ResolvedTax>,
+) -> Vec<(Option<i32>, i64, f64)> {
+    let Some(resolved) = tax_id.and_then(|tax_id| taxes.get(&tax_id)) else {
+        return vec![(None, net, tax as f64 / 100.0)];
+    };
+
+    let components: Vec<(f64, bool)> = resolved
+        .components
+        .iter()
+        .map(|component| (component.tax.rate, component.compound))
+        .collect();
+    resolved
+        .components
+        .iter()
+        .zip(component_bases(net, &components))
+        .map(|(component, base)| (Some(component.tax.id), base, component.tax.rate))
+        .collect()
+}
+
+/// Sums lines given as net amount, tax id and percentage up to the gross
+/// total, with the tax rounded once per rate.
+fn gross_total(
+    lines: impl IntoIterator<Item = (i64, Option<i32>, i32)>,
+    taxes: &HashMap<i32, ResolvedTax>,
+) -> i64 {
+    let mut net_total = 0;
+    let mut bases = Vec::new();
+    for (net, tax_id, tax) in lines {
+        net_total += net;
+        bases.extend(
+            tax_bases(net, tax_id, tax, taxes)
+                .into_iter()
+                .map(|(_, base, rate)| (base, rate)),
+        );
+    }
+    net_total + tax_total(bases)
+}
+
+/// The taxable amounts of the invoice lines after the line discounts and
+/// their shares of the invoice discount.
+pub(super) fn taxable(lines: &[InvoiceLine], discount: Discount) -> Vec<i64> {
+    let nets: Vec<i64> = lines
+        .iter()
+        .map(|line| line.discount.apply(line_net(line.quantity, line.price)))
+        .collect();
+    taxable_nets(&nets, discount)
+}
+
+/// Picks the variants of the lines whose goods go back into stock when the
+/// goods are returned at all. Consumables and services aren't kept in stock.
+async fn stockable_variants(
+    lines: &[InvoiceLine],
+    stock_return: Option<(i32, i32)>,
+    state: &AppState,
+) -> Result<Vec<i32>, HttpResponse> {
+    if stock_return.is_none() {
+        return Ok(Vec::new());
+    }
+
+    let variant_ids = lines.iter().filter_map(|line| line.variant_id).collect();
+    match state.variants.stockable_ids(variant_ids).await {
+        Ok(stockable) => Ok(stockable),
+        Err(_) => Err(ApiErrors::InternalServerError.into()),
+    }
+}
+
+/// Resolves the requested lines and quantities against what is left of the
+/// invoice lines after the earlier credit notes.
+fn drafts(
+    lines: &[InvoiceLine],
+    discount: Discount,
+    credited: &[CreditNoteLineModel],
+    requested: Option<&[CreditNoteLineInput]>,
+    taxes: &HashMap<i32, ResolvedTax>,
+    stock_return: Option<(i32, i32)>,
+    stockable: &[i32],
+) -> Result<Vec<CreditNoteLineDraft>, ApiErrors> {
+    let mut remaining: HashMap<i32, f64> =
+        lines.iter().map(|line| (line.id, line.quantity)).collect();
+    for line in credited {
+        if let Some(left) = remaining.get_mut(&line.invoice_line_id) {
+            *left -= line.quantity;
+        }
+    }
+
+    let requested: Vec<(i32, f64)> = match requested {
+        Some(requested) => requested
+            .iter()
+            .map(|line| (line.invoice_line_id, line.quantity))
+            .collect(),
+        None => lines
+            .iter()
+            .map(|line| (line.id, remaining[&line.id]))
+            .filter(|(_, quantity)| *quantity > QUANTITY_TOLERANCE)
+            .collect(),
+    };
+    if requested.is_empty() {
+        return Err(ApiErrors::InvoiceFullyCredited);
+    }
+
+    let taxable = taxable(lines, discount);
+    let mut drafts = Vec::with_capacity(requested.len());
+    for (invoice_line_id, quantity) in requested {
+        let (index, line) = lines
+            .iter()
+            .enumerate()
+            .find(|(_, line)| line.id == invoice_line_id)
+            .ok_or(ApiErrors::InvoiceLineNotFound)?;
+
+        let left = remaining.entry(line.id).or_default();
+        if quantity > *left + QUANTITY_TOLERANCE {
+            return Err(ApiErrors::CreditNoteExceedsInvoice);
+        }
+        *left -= quantity;
+
+        // The discounts are credited in proportion to the quantity.
+        let net = match line.quantity {
+            invoiced if invoiced > 0.0 => {
+                (taxable[index] as f64 * quantity / invoiced).round() as i64
+            }
+            _ => 0,
+        };
+        let discount = line_net(quantity, line.price) - net;
+        let bases = tax_bases(net, line.tax_id, line.tax, taxes);
+        let tax_amount: i64 = bases
+            .iter()
+            .map(|(_, base, rate)| (*base as f64 * rate).round() as i64)
+            .sum();
+
+        // Plain taxes are posted on the line itself, groups per component.
+        let tax_postings = match bases.len() {
+            1 => Vec::new(),
+            _ => bases
+                .iter()
+                .filter_map(|(tax_id, base, rate)| {
+                    let tax_id = (*tax_id)?;
+                    let transaction_id = line
+                        .tax_postings
+                        .iter()
+                        .find(|(posted_tax_id, _)| *posted_tax_id == tax_id)
+                        .and_then(|(_, transaction_id)| *transaction_id);
+                    Some(TaxPostingDraft {
+                        tax_id,
+                        amount: (*base as f64 * rate).round() as i32,
+                        transaction_id,
+                    })
+                })
+                .collect(),
+        };
+
+        drafts.push(CreditNoteLineDraft {
+            invoice_line_id: line.id,
+            description: line.description.clone(),
+            quantity,
+            price: line.price,
+            tax: line.tax,
+            tax_id: line.tax_id,
+            variant_id: line.variant_id,
+            net: net as i32,
+            tax_amount: tax_amount as i32,
+            transaction_id: line.transaction_id,
+            tax_transaction_id: line.tax_transaction_id,
+            tax_postings,
+            discount: discount as i32,
+            discount_transaction_id: line.discount_transaction_id,
+            stock_return: stock_return.filter(|_| {
+                line.variant_id
+                    .is_some_and(|variant_id| stockable.contains(&variant_id))
+            }),
+            lots: Vec::new(),
+        });
+    }
+    Ok(drafts)
+}
+
+/// Splits the returned goods of the drafts over the lots given for their
+/// invoice lines and checks them against the tracking of the variants.
+async fn return_lots(
+    drafts: &mut [CreditNoteLineDraft],
+    lots: Vec<LineLotInput>,
+    state: &AppState,
+) -> Result<(), HttpResponse> {
+    // Only stockable goods are returned, and those always have a variant.
+    let variant_ids: Vec<i32> = drafts
+        .iter()
+        .filter(|draft| draft.stock_return.is_some())
+        .filter_map(|draft| draft.variant_id)
+        .collect();
+    if variant_ids.is_empty() && lots.is_empty() {
+        return Ok(());
+    }
+    let tracking = match state.variants.tracking(variant_ids).await {
+        Ok(tracking) => tracking,
+        Err(_) => return Err(ApiErrors::InternalServerError.into()),
+    };
+
+    for lot in lots {
+        let Some(draft) = drafts
+            .iter_mut()
+            .find(|draft| draft.invoice_line_id == lot.line_id && draft.stock_return.is_some())
+        else {
+            return Err(ApiErrors::LotLineNotFound.into());
+        };
+        let lot = lot_draft(lot.number, lot.quantity, lot.expiry_date.as_deref())?;
+        draft.lots.push(lot);
+    }
+
+    for draft in drafts.iter().filter(|draft| draft.stock_return.is_some()) {
+        let tracking = draft
+            .variant_id
+            .and_then(|variant_id| tracking.get(&variant_id))
+            .unwrap_or(&Tracking::None);
+        if let Some(problem) = check_lots(tracking, draft.quantity, &draft.lots) {
+            return Err(lot_problem(problem).into());
+        }
+    }
+    Ok(())
+}
+
+fn credited_total(lines: &[CreditNoteLineModel], taxes: &HashMap<i32, ResolvedTax>) -> i64 {
+    gross_total(
+        lines.iter().map(|line| {
+            let net = line_net(line.quantity, line.price) - line.discount_amount as i64;
+            (net, line.tax_id, line.tax)
+        }),
+        taxes,
+    )
+}
+
+/// There are no payments yet, so the open amount is what the credit notes
+/// leave of the invoice.
+fn summary(
+    lines: &[InvoiceLine],
+    discount: Discount,
+    taxes: &HashMap<i32, ResolvedTax>,
+    credit_notes: Vec<CreditNoteModel>,
+) -> InvoiceCreditNotesModel {
+    let invoice_total = gross_total(
+        lines
+            .iter()
+            .zip(taxable(lines, discount))
+            .map(|(line, net)| (net, line.tax_id, line.tax)),
+        taxes,
+    );
+    let credited_total = credit_notes
+        .iter()
+        .map(|credit_note| credit_note.gross_total)
+        .sum();
+
+    InvoiceCreditNotesModel {
+        invoice_total,
+        credited_total,
+        open_amount: invoice_total - credited_total,
+        credit_notes,
+    }
+}
+
+fn create_error(error: DbErr) -> ApiErrors {
+    match error {
+        DbErr::Custom(message) if message == ERR_PERIOD_CLOSED => {
+            ApiErrors::NumberSequencePeriodClosed
+        }
+        DbErr::Custom(message) if message == ERR_CREDIT_EXCEEDS_INVOICE => {
+            ApiErrors::CreditNoteExceedsInvoice
+        }
+        error => lot_error(error),
+    }
+}
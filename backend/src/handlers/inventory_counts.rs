@@ -0,0 +1,435 @@
+use actix_web::{HttpResponse, get, post, web};
+use actix_web_validator::Json;
+use chrono::Utc;
+use entity::sea_orm_active_enums::{InventoryCountStatus, StockKind, Tracking};
+
+use crate::{
+    err::{self, ApiErrors},
+    handlers::{stock_moves::lot_error, units::resolve_line_unit},
+    models::{
+        ApproveInventoryCountInput, CreateInventoryCountInput, InventoryCountModel,
+        RecordInventoryCountInput,
+    },
+    repositories::{AdjustmentDraft, CostAccounts, LineMoveDraft, LotDraft, QUANTITY_TOLERANCE},
+    state::AppState,
+    utils::inventory_counts::differences,
+};
+
+#[utoipa::path(
+    tag = "Inventory counts",
+    summary = "Start an inventory count",
+    description = "Start counting an internal location. The count expects what the moves into and out of the location add up to for every variant, \
+        so the counted quantities can be compared with it.",
+    responses(
+        (status = 200, description = "Returns the count with the expected quantities", body = InventoryCountModel),
+        (status = 400, description = err::MESSAGE_INVALID_COUNT_STOCK, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_COUNT_STOCK,
+                "errorCode": err::CODE_INVALID_COUNT_STOCK,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_STOCK_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_STOCK_NOT_FOUND,
+                "errorCode": err::CODE_STOCK_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/inventory-counts")]
+async fn create(
+    payload: Json<CreateInventoryCountInput>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let payload = payload.into_inner();
+
+    match state.stocks.get(payload.stock_id).await {
+        Ok(Some(stock)) if stock.kind == StockKind::Internal => {}
+        Ok(Some(_)) => return ApiErrors::InvalidCountStock.into(),
+        Ok(None) => return ApiErrors::StockNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    }
+
+    let query = state
+        .inventory_counts
+        .create(payload.stock_id, Utc::now().date_naive());
+    let count = match query.await {
+        Ok(entity) => entity,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    match count_model(&count, &state).await {
+        Ok(model) => HttpResponse::Ok().json(model),
+        Err(response) => response,
+    }
+}
+
+#[utoipa::path(
+    tag = "Inventory counts",
+    summary = "Get an inventory count",
+    description = "Get the count with the expected quantity of every variant, what each counter found and the moves that adjusted the stock once it was approved",
+    responses(
+        (status = 200, description = "Returns the count", body = InventoryCountModel),
+        (status = 404, description = err::MESSAGE_INVENTORY_COUNT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVENTORY_COUNT_NOT_FOUND,
+                "errorCode": err::CODE_INVENTORY_COUNT_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/inventory-counts/{id}")]
+async fn get(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let query = state.inventory_counts.get(path.into_inner());
+    let count = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::InventoryCountNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    match count_model(&count, &state).await {
+        Ok(model) => HttpResponse::Ok().json(model),
+        Err(response) => response,
+    }
+}
+
+#[utoipa::path(
+    tag = "Inventory counts",
+    summary = "Record a counted quantity",
+    description = "Record what a counter found of a variant in the location. Several counters can count a location, each a part of it, and their quantities add up. \
+        A counter recording a variant again replaces their earlier quantity. Variants that weren't expected in the location are added to the count. \
+        Tracked variants are counted per lot or serial number.",
+    responses(
+        (status = 200, description = "Returns the count with the recorded quantity", body = InventoryCountModel),
+        (status = 400, description = err::MESSAGE_INCOMPATIBLE_UNIT, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INCOMPATIBLE_UNIT,
+                "errorCode": err::CODE_INCOMPATIBLE_UNIT,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_LOTS_MISSING, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOTS_MISSING,
+                "errorCode": err::CODE_LOTS_MISSING,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_LOTS_NOT_TRACKED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOTS_NOT_TRACKED,
+                "errorCode": err::CODE_LOTS_NOT_TRACKED,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_SERIAL_QUANTITY_NOT_ONE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SERIAL_QUANTITY_NOT_ONE,
+                "errorCode": err::CODE_SERIAL_QUANTITY_NOT_ONE,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_VARIANT_NOT_STOCKABLE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_VARIANT_NOT_STOCKABLE,
+                "errorCode": err::CODE_VARIANT_NOT_STOCKABLE,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_INVENTORY_COUNT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVENTORY_COUNT_NOT_FOUND,
+                "errorCode": err::CODE_INVENTORY_COUNT_NOT_FOUND,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_VARIANT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_VARIANT_NOT_FOUND,
+                "errorCode": err::CODE_VARIANT_NOT_FOUND,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_INVENTORY_COUNT_APPROVED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVENTORY_COUNT_APPROVED,
+                "errorCode": err::CODE_INVENTORY_COUNT_APPROVED,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/inventory-counts/{id}/entries")]
+async fn record(
+    path: web::Path<i32>,
+    payload: Json<RecordInventoryCountInput>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let payload = payload.into_inner();
+
+    let count = match open_count(path.into_inner(), &state).await {
+        Ok(count) => count,
+        Err(response) => return response,
+    };
+
+    let query = state.variants.get(payload.variant_id);
+    let variant = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::VariantNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    match state.variants.stockable_ids(vec![variant.id]).await {
+        Ok(ids) if ids.contains(&variant.id) => {}
+        Ok(_) => return ApiErrors::VariantNotStockable.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    }
+
+    let factor = match resolve_line_unit(payload.unit_id, Some(&variant), &state).await {
+        Ok((_, factor)) => factor,
+        Err(response) => return response,
+    };
+    let quantity = payload.quantity * factor;
+
+    let tracking = match state.variants.tracking(vec![variant.id]).await {
+        Ok(tracking) => tracking.get(&variant.id).cloned().unwrap_or(Tracking::None),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+    let lot_number = payload
+        .lot_number
+        .map(|number| number.trim().to_string())
+        .unwrap_or_default();
+    match (tracking, lot_number.is_empty()) {
+        (Tracking::None, false) => return ApiErrors::LotsNotTracked.into(),
+        (Tracking::Lot | Tracking::Serial, true) => return ApiErrors::LotsMissing.into(),
+        // A serial number is either there or not.
+        (Tracking::Serial, false) if quantity > 1.0 + QUANTITY_TOLERANCE => {
+            return ApiErrors::SerialQuantityNotOne.into();
+        }
+        _ => {}
+    }
+
+    let query =
+        state
+            .inventory_counts
+            .record(count.id, variant.id, lot_number, payload.counter, quantity);
+    if query.await.is_err() {
+        return ApiErrors::InternalServerError.into();
+    }
+
+    match count_model(&count, &state).await {
+        Ok(model) => HttpResponse::Ok().json(model),
+        Err(response) => response,
+    }
+}
+
+#[utoipa::path(
+    tag = "Inventory counts",
+    summary = "Approve an inventory count",
+    description = "Adjust the stock of the location to what was counted. Differences are moved against the inventory loss location: \
+        missing goods out of the location, found goods into it. Variants nobody counted are left as they are. \
+        Given an account, the value of the differences is posted against the inventory account of each product's category. \
+        Tracked variants are adjusted per lot: missing goods leave the location in their lot, found ones enter it in theirs.",
+    request_body(content = Option<ApproveInventoryCountInput>),
+    responses(
+        (status = 200, description = "Returns the approved count with the adjusting moves", body = InventoryCountModel),
+        (status = 404, description = err::MESSAGE_ACCOUNT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_ACCOUNT_NOT_FOUND,
+                "errorCode": err::CODE_ACCOUNT_NOT_FOUND,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_INVENTORY_COUNT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVENTORY_COUNT_NOT_FOUND,
+                "errorCode": err::CODE_INVENTORY_COUNT_NOT_FOUND,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_INVENTORY_COUNT_APPROVED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVENTORY_COUNT_APPROVED,
+                "errorCode": err::CODE_INVENTORY_COUNT_APPROVED,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_INVENTORY_LOSS_LOCATION_MISSING, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVENTORY_LOSS_LOCATION_MISSING,
+                "errorCode": err::CODE_INVENTORY_LOSS_LOCATION_MISSING,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_LOT_NOT_IN_STOCK, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOT_NOT_IN_STOCK,
+                "errorCode": err::CODE_LOT_NOT_IN_STOCK,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_POSTING_ACCOUNT_MISSING, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_POSTING_ACCOUNT_MISSING,
+                "errorCode": err::CODE_POSTING_ACCOUNT_MISSING,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_SERIAL_IN_STOCK, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SERIAL_IN_STOCK,
+                "errorCode": err::CODE_SERIAL_IN_STOCK,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/inventory-counts/{id}/approve")]
+async fn approve(
+    path: web::Path<i32>,
+    payload: Option<Json<ApproveInventoryCountInput>>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let count = match open_count(path.into_inner(), &state).await {
+        Ok(count) => count,
+        Err(response) => return response,
+    };
+
+    let loss = match state.stocks.list_virtual().await {
+        Ok(stocks) => stocks
+            .into_iter()
+            .find(|stock| stock.kind == StockKind::InventoryLoss),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+    let Some(loss) = loss else {
+        return ApiErrors::InventoryLossLocationMissing.into();
+    };
+
+    let lines = match state.inventory_counts.list_lines(count.id).await {
+        Ok(lines) => lines,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+    let entries = match state.inventory_counts.list_entries(count.id).await {
+        Ok(entries) => entries,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+    let differences = differences(&lines, &entries);
+
+    let account_id = payload.and_then(|payload| payload.into_inner().account_id);
+    // Without an account the differences are moved but not posted.
+    let mut cost_accounts = vec![None; differences.len()];
+    if let Some(account_id) = account_id {
+        match state.ledger_accounts.get(account_id).await {
+            Ok(Some(_)) => {}
+            Ok(None) => return ApiErrors::AccountNotFound.into(),
+            Err(_) => return ApiErrors::InternalServerError.into(),
+        }
+
+        let variant_ids = differences
+            .iter()
+            .map(|difference| difference.variant_id)
+            .collect();
+        let defaults = match state
+            .product_categories
+            .defaults_for_variants(variant_ids)
+            .await
+        {
+            Ok(defaults) => defaults,
+            Err(_) => return ApiErrors::InternalServerError.into(),
+        };
+        for (difference, accounts) in differences.iter().zip(cost_accounts.iter_mut()) {
+            let category = defaults.get(&difference.variant_id);
+            let Some(inventory_account_id) =
+                category.and_then(|category| category.inventory_account_id)
+            else {
+                return ApiErrors::PostingAccountMissing.into();
+            };
+            *accounts = Some(CostAccounts {
+                expense_account_id: account_id,
+                inventory_account_id,
+            });
+        }
+    }
+
+    let adjustments = differences
+        .into_iter()
+        .zip(cost_accounts)
+        .map(|(difference, cost_accounts)| {
+            let quantity = difference.difference.abs();
+            let lots = match difference.lot_number.is_empty() {
+                true => Vec::new(),
+                false => vec![LotDraft {
+                    number: difference.lot_number,
+                    quantity,
+                    expiry_date: None,
+                }],
+            };
+            AdjustmentDraft {
+                found: difference.difference > 0.0,
+                line: LineMoveDraft {
+                    line_id: difference.line_id,
+                    variant_id: difference.variant_id,
+                    quantity,
+                    unit_cost: None,
+                    cost_accounts,
+                    lots,
+                },
+            }
+        })
+        .collect();
+
+    let date = Utc::now().date_naive();
+    let query = state
+        .inventory_counts
+        .approve(count, date, loss.id, adjustments);
+    let count = match query.await {
+        Ok(entity) => entity,
+        Err(error) => return lot_error(error).into(),
+    };
+
+    match count_model(&count, &state).await {
+        Ok(model) => HttpResponse::Ok().json(model),
+        Err(response) => response,
+    }
+}
+
+/// Loads the count, which must still be open.
+async fn open_count(
+    id: i32,
+    state: &AppState,
+) -> Result<entity::inventory_counts::Model, HttpResponse> {
+    let count = match state.inventory_counts.get(id).await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return Err(ApiErrors::InventoryCountNotFound.into()),
+        Err(_) => return Err(ApiErrors::InternalServerError.into()),
+    };
+    match count.status {
+        InventoryCountStatus::Open => Ok(count),
+        InventoryCountStatus::Approved => Err(ApiErrors::InventoryCountApproved.into()),
+    }
+}
+
+/// Loads the lines and entries of the count into its model.
+async fn count_model(
+    count: &entity::inventory_counts::Model,
+    state: &AppState,
+) -> Result<InventoryCountModel, HttpResponse> {
+    let lines = match state.inventory_counts.list_lines(count.id).await {
+        Ok(lines) => lines,
+        Err(_) => return Err(ApiErrors::InternalServerError.into()),
+    };
+    let entries = match state.inventory_counts.list_entries(count.id).await {
+        Ok(entries) => entries,
+        Err(_) => return Err(ApiErrors::InternalServerError.into()),
+    };
+    Ok(InventoryCountModel::new(count, &lines, &entries))
+}
@@ -0,0 +1,469 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpResponse, post, web};
+use chrono::NaiveDate;
+use entity::sea_orm_active_enums::{PurchaseInvoiceStatus, SaleInvoiceStatus, TaxDirection};
+use sea_orm::DbErr;
+
+use crate::{
+    err::{self, ApiErrors},
+    handlers::{
+        credit_notes::{InvoiceLine, tax_bases, taxable},
+        tax_rules::line_tax_ids,
+        taxes::resolve_taxes,
+    },
+    models::{PurchaseInvoiceModel, SaleInvoiceModel},
+    repositories::{
+        ERR_PURCHASE_INVOICE_POSTED, ERR_SALE_INVOICE_POSTED, LinePostingDraft, PostingDraft,
+        ResolvedTax,
+    },
+    state::AppState,
+    utils::{
+        product_categories::CategoryDefaults,
+        totals::{Discount, line_net},
+    },
+};
+
+/// Whether the lines are booked as revenue against the customer's account
+/// or as expense against the supplier's.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Side {
+    Sale,
+    Purchase,
+}
+
+#[utoipa::path(
+    tag = "Sale invoices",
+    summary = "Post a sale invoice",
+    description = "Book the lines of a sale invoice which haven't been posted yet. \
+        The net amount of a line is credited to the income account and its tax to the tax's account, both debited to the customer's account. \
+        The income account comes from the category of the line's product or its nearest ancestor that sets it. \
+        Lines without a tax get the one the tax rules select for the customer, or else their category's default tax. \
+        Drafts and proforma invoices become invoices.",
+    responses(
+        (status = 200, description = "Returns the posted sale invoice", body = SaleInvoiceModel),
+        (status = 404, description = err::MESSAGE_SALE_INVOICE_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SALE_INVOICE_NOT_FOUND,
+                "errorCode": err::CODE_SALE_INVOICE_NOT_FOUND,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_INVOICE_ALREADY_POSTED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVOICE_ALREADY_POSTED,
+                "errorCode": err::CODE_INVOICE_ALREADY_POSTED,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_CONTACT_ACCOUNT_MISSING, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_CONTACT_ACCOUNT_MISSING,
+                "errorCode": err::CODE_CONTACT_ACCOUNT_MISSING,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_POSTING_ACCOUNT_MISSING, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_POSTING_ACCOUNT_MISSING,
+                "errorCode": err::CODE_POSTING_ACCOUNT_MISSING,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/sale-invoices/{id}/post")]
+async fn post_sale(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let query = state.sale_invoices.get(path.into_inner());
+    let invoice = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::SaleInvoiceNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state.sale_invoice_lines.list_for_invoice(invoice.id);
+    let lines: Vec<InvoiceLine> = match query.await {
+        Ok(lines) => lines.iter().map(InvoiceLine::from).collect(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let posted = matches!(
+        invoice.status,
+        SaleInvoiceStatus::Invoice | SaleInvoiceStatus::Finished
+    );
+    if posted && lines.iter().all(|line| line.transaction_id.is_some()) {
+        return ApiErrors::InvoiceAlreadyPosted.into();
+    }
+
+    let customer = match state.contacts.get(invoice.customer_id).await {
+        Ok(Some(contact)) => contact,
+        Ok(None) => return ApiErrors::ContactNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let discount = Discount::new(invoice.discount_percent, invoice.discount_amount);
+    let drafts = match line_postings(
+        lines,
+        discount,
+        invoice.date,
+        &customer,
+        Side::Sale,
+        &[],
+        &state,
+    )
+    .await
+    {
+        Ok(drafts) => drafts,
+        Err(response) => return response,
+    };
+
+    let invoice = match state.sale_invoices.post(invoice, drafts).await {
+        Ok(entity) => entity,
+        Err(DbErr::Custom(message)) if message == ERR_SALE_INVOICE_POSTED => {
+            return ApiErrors::InvoiceAlreadyPosted.into();
+        }
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let model = SaleInvoiceModel::from(&invoice);
+    HttpResponse::Ok().json(model)
+}
+
+#[utoipa::path(
+    tag = "Purchase invoices",
+    summary = "Post a purchase invoice",
+    description = "Book the lines of a purchase invoice which haven't been posted yet and finish the invoice. \
+        The net amount of a line is debited to the expense account and its tax to the tax's account, both credited to the supplier's account. \
+        The expense account comes from the category of the line's product or its nearest ancestor that sets it. \
+        Lines without a tax get the one the tax rules select for the supplier, or else their category's default tax. \
+        Stockable goods are debited to the category's inventory account instead if it has one, their cost is expensed when they are delivered. \
+        Goods the order drop-ships to a customer never enter the stock and are expensed right away.",
+    responses(
+        (status = 200, description = "Returns the posted purchase invoice", body = PurchaseInvoiceModel),
+        (status = 404, description = err::MESSAGE_PURCHASE_INVOICE_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PURCHASE_INVOICE_NOT_FOUND,
+                "errorCode": err::CODE_PURCHASE_INVOICE_NOT_FOUND,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_INVOICE_ALREADY_POSTED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVOICE_ALREADY_POSTED,
+                "errorCode": err::CODE_INVOICE_ALREADY_POSTED,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_CONTACT_ACCOUNT_MISSING, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_CONTACT_ACCOUNT_MISSING,
+                "errorCode": err::CODE_CONTACT_ACCOUNT_MISSING,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_POSTING_ACCOUNT_MISSING, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_POSTING_ACCOUNT_MISSING,
+                "errorCode": err::CODE_POSTING_ACCOUNT_MISSING,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/purchase-invoices/{id}/post")]
+async fn post_purchase(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let query = state.purchase_invoices.get(path.into_inner());
+    let invoice = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::PurchaseInvoiceNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state.purchase_invoice_lines.list_for_invoice(invoice.id);
+    let lines: Vec<InvoiceLine> = match query.await {
+        Ok(lines) => lines.iter().map(InvoiceLine::from).collect(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let posted = invoice.status == PurchaseInvoiceStatus::Finished;
+    if posted && lines.iter().all(|line| line.transaction_id.is_some()) {
+        return ApiErrors::InvoiceAlreadyPosted.into();
+    }
+
+    // Imported invoices may not have been matched to a supplier yet.
+    let Some(supplier_id) = invoice.supplier_id else {
+        return ApiErrors::ContactAccountMissing.into();
+    };
+    let supplier = match state.contacts.get(supplier_id).await {
+        Ok(Some(contact)) => contact,
+        Ok(None) => return ApiErrors::ContactNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    // Drop-shipped goods go from the supplier straight to the customer and
+    // never become inventory.
+    let drop_shipped: Vec<i32> = match invoice.order_id {
+        Some(order_id) => match state.purchase_order_lines.list_for_order(order_id).await {
+            Ok(lines) => lines
+                .iter()
+                .filter(|line| line.sale_line_id.is_some())
+                .filter_map(|line| line.variant_id)
+                .collect(),
+            Err(_) => return ApiErrors::InternalServerError.into(),
+        },
+        None => Vec::new(),
+    };
+
+    let discount = Discount::new(invoice.discount_percent, invoice.discount_amount);
+    let drafts = match line_postings(
+        lines,
+        discount,
+        invoice.date,
+        &supplier,
+        Side::Purchase,
+        &drop_shipped,
+        &state,
+    )
+    .await
+    {
+        Ok(drafts) => drafts,
+        Err(response) => return response,
+    };
+
+    let invoice = match state.purchase_invoices.post(invoice, drafts).await {
+        Ok(entity) => entity,
+        Err(DbErr::Custom(message)) if message == ERR_PURCHASE_INVOICE_POSTED => {
+            return ApiErrors::InvoiceAlreadyPosted.into();
+        }
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let model = PurchaseInvoiceModel::from(&invoice);
+    HttpResponse::Ok().json(model)
+}
+
+/// Loads the category defaults and taxes of the lines which haven't been
+/// posted yet and drafts their postings against the contact's account. Lines
+/// without a tax are taxed as the tax rules determine for the contact. The
+/// drop-shipped variants are expensed instead of taken into inventory.
+async fn line_postings(
+    mut lines: Vec<InvoiceLine>,
+    discount: Discount,
+    date: NaiveDate,
+    contact: &entity::contacts::Model,
+    side: Side,
+    drop_shipped: &[i32],
+    state: &AppState,
+) -> Result<Vec<LinePostingDraft>, HttpResponse> {
+    let contact_account_id = match side {
+        Side::Sale => contact.customer_account_id,
+        Side::Purchase => contact.supplier_account_id,
+    };
+    let Some(contact_account_id) = contact_account_id else {
+        return Err(ApiErrors::ContactAccountMissing.into());
+    };
+
+    let variant_ids: Vec<i32> = lines
+        .iter()
+        .filter(|line| line.transaction_id.is_none())
+        .filter_map(|line| line.variant_id)
+        .collect();
+    let defaults = match state
+        .product_categories
+        .defaults_for_variants(variant_ids.clone())
+        .await
+    {
+        Ok(defaults) => defaults,
+        Err(_) => return Err(ApiErrors::InternalServerError.into()),
+    };
+    // Purchased goods kept in stock are inventory until they are sold.
+    let stockable = match side {
+        Side::Sale => Vec::new(),
+        Side::Purchase => match state.variants.stockable_ids(variant_ids).await {
+            Ok(ids) => ids
+                .into_iter()
+                .filter(|id| !drop_shipped.contains(id))
+                .collect(),
+            Err(_) => return Err(ApiErrors::InternalServerError.into()),
+        },
+    };
+
+    let mut untaxed: Vec<entity::variants::Model> = Vec::new();
+    for line in &lines {
+        let Some(variant_id) = line.variant_id else {
+            continue;
+        };
+        if line.transaction_id.is_some() || line.tax_id.is_some() {
+            continue;
+        }
+        if untaxed.iter().any(|variant| variant.id == variant_id) {
+            continue;
+        }
+        // Lines can't outlive their variant.
+        match state.variants.get(variant_id).await {
+            Ok(Some(variant)) => untaxed.push(variant),
+            Ok(None) | Err(_) => return Err(ApiErrors::InternalServerError.into()),
+        }
+    }
+    let determined = if untaxed.is_empty() {
+        HashMap::new()
+    } else {
+        let direction = match side {
+            Side::Sale => TaxDirection::Sale,
+            Side::Purchase => TaxDirection::Purchase,
+        };
+        let variants: Vec<&entity::variants::Model> = untaxed.iter().collect();
+        line_tax_ids(direction, contact, &variants, &defaults, state).await?
+    };
+
+    for line in &mut lines {
+        line.tax_id = line_tax_id(line, &determined);
+    }
+    let tax_ids = lines.iter().filter_map(|line| line.tax_id);
+    let taxes = resolve_taxes(tax_ids, date, state).await?;
+
+    drafts(
+        &lines,
+        discount,
+        contact_account_id,
+        side,
+        &defaults,
+        &stockable,
+        &taxes,
+    )
+    .map_err(|error| error.into())
+}
+
+/// The line's own tax, or the one determined for its variant.
+fn line_tax_id(line: &InvoiceLine, determined: &HashMap<i32, i32>) -> Option<i32> {
+    line.tax_id.or_else(|| {
+        line.variant_id
+            .and_then(|variant_id| determined.get(&variant_id))
+            .copied()
+    })
+}
+
+/// The ledger account a component of a resolved tax is posted to.
+fn tax_account_id(
+    taxes: &HashMap<i32, ResolvedTax>,
+    tax_id: Option<i32>,
+    component_id: i32,
+) -> Option<i32> {
+    taxes
+        .get(&tax_id?)?
+        .components
+        .iter()
+        .find(|component| component.tax.id == component_id)
+        .map(|component| component.tax.account_id)
+}
+
+/// Drafts the postings of the lines which haven't been posted yet. Their
+/// nets take the invoice discount into account, which is spread over all
+/// lines of the invoice. Purchased goods are debited to the inventory
+/// account if they are stockable and their category has one. Lines of a
+/// category with a discount account are posted undiscounted and their
+/// discount is posted back to that account, except for goods taken into
+/// inventory, which is valued at the discounted cost.
+fn drafts(
+    lines: &[InvoiceLine],
+    discount: Discount,
+    contact_account_id: i32,
+    side: Side,
+    defaults: &HashMap<i32, CategoryDefaults>,
+    stockable: &[i32],
+    taxes: &HashMap<i32, ResolvedTax>,
+) -> Result<Vec<LinePostingDraft>, ApiErrors> {
+    let posting = |amount: i64, account_id: i32| match side {
+        Side::Sale => PostingDraft {
+            amount: amount as i32,
+            debit_account_id: contact_account_id,
+            credit_account_id: account_id,
+        },
+        Side::Purchase => PostingDraft {
+            amount: amount as i32,
+            debit_account_id: account_id,
+            credit_account_id: contact_account_id,
+        },
+    };
+
+    let taxable = taxable(lines, discount);
+    let mut drafts = Vec::new();
+    for (line, net) in lines.iter().zip(taxable) {
+        if line.transaction_id.is_some() {
+            continue;
+        }
+
+        let category = line
+            .variant_id
+            .and_then(|variant_id| defaults.get(&variant_id));
+        let in_stock = line
+            .variant_id
+            .is_some_and(|variant_id| stockable.contains(&variant_id));
+        let to_inventory =
+            in_stock && category.is_some_and(|category| category.inventory_account_id.is_some());
+        let account_id = category
+            .and_then(|category| match side {
+                Side::Sale => category.income_account_id,
+                Side::Purchase if to_inventory => category.inventory_account_id,
+                Side::Purchase => category.expense_account_id,
+            })
+            .ok_or(ApiErrors::PostingAccountMissing)?;
+        let discount_account_id = category.and_then(|category| match side {
+            Side::Sale => category.sale_discount_account_id,
+            Side::Purchase if to_inventory => None,
+            Side::Purchase => category.purchase_discount_account_id,
+        });
+        let undiscounted = line_net(line.quantity, line.price);
+        let (booked, discount) = match discount_account_id {
+            Some(discount_account_id) if undiscounted != net => {
+                // The discount goes the other way round than the line.
+                let reversed = posting(undiscounted - net, discount_account_id);
+                let discount = PostingDraft {
+                    amount: reversed.amount,
+                    debit_account_id: reversed.credit_account_id,
+                    credit_account_id: reversed.debit_account_id,
+                };
+                (undiscounted, Some(discount))
+            }
+            _ => (net, None),
+        };
+
+        let tax_id = line.tax_id;
+        let bases = tax_bases(net, tax_id, line.tax, taxes);
+        let mut components = Vec::with_capacity(bases.len());
+        for (component_id, base, rate) in &bases {
+            let amount = (*base as f64 * rate).round() as i64;
+            if amount == 0 {
+                continue;
+            }
+            // A percentage without a tax has no account to go to.
+            let account_id = component_id
+                .and_then(|component_id| tax_account_id(taxes, tax_id, component_id))
+                .ok_or(ApiErrors::PostingAccountMissing)?;
+            components.push((
+                component_id.unwrap_or_default(),
+                posting(amount, account_id),
+            ));
+        }
+
+        // Plain taxes are posted on the line itself, groups per component.
+        let (tax, tax_postings) = match bases.len() {
+            1 => (components.pop().map(|(_, posting)| posting), Vec::new()),
+            _ => (None, components),
+        };
+
+        drafts.push(LinePostingDraft {
+            line_id: line.id,
+            tax_id,
+            net: posting(booked, account_id),
+            discount,
+            tax,
+            tax_postings,
+        });
+    }
+    Ok(drafts)
+}
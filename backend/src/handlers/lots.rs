@@ -0,0 +1,48 @@
+use actix_web::{HttpResponse, get, web};
+
+use crate::{
+    err::{self, ApiErrors},
+    models::{LotModel, LotTraceModel, LotTraceMoveModel},
+    state::AppState,
+};
+
+#[utoipa::path(
+    tag = "Lots",
+    summary = "Trace a lot or serial number",
+    description = "Follow a lot or serial number through every move it was part of, in the order they were made. \
+        Moves of purchase and sale orders name the order and the supplier or customer it was received from or delivered to.",
+    responses(
+        (status = 200, description = "Returns the lot and its moves", body = LotTraceModel),
+        (status = 404, description = err::MESSAGE_LOT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOT_NOT_FOUND,
+                "errorCode": err::CODE_LOT_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/lots/{id}/trace")]
+async fn trace(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let query = state.lots.get(path.into_inner());
+    let lot = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::LotNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let moves = match state.lots.trace(lot.id).await {
+        Ok(moves) => moves,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    HttpResponse::Ok().json(LotTraceModel {
+        lot: LotModel::from(&lot),
+        moves: moves.iter().map(LotTraceMoveModel::from).collect(),
+    })
+}
@@ -1,19 +1,32 @@
 pub mod contacts;
+pub mod credit_notes;
 pub mod docs;
 pub mod info;
+pub mod inventory_counts;
+pub mod invoice_postings;
 pub mod ledger_accounts;
 pub mod ledger_transactions;
+pub mod lots;
+pub mod number_sequences;
+pub mod price_lists;
+pub mod product_attributes;
+pub mod product_categories;
 pub mod products;
 pub mod purchase_invoice_lines;
 pub mod purchase_invoices;
 pub mod purchase_order_lines;
 pub mod purchase_orders;
+pub mod reorder_rules;
+pub mod reports;
 pub mod sale_invoice_lines;
 pub mod sale_invoices;
 pub mod sale_order_lines;
 pub mod sale_orders;
 pub mod stock_moves;
 pub mod stocks;
+pub mod supplier_prices;
+pub mod tax_rules;
 pub mod taxes;
+pub mod units;
 pub mod users;
 pub mod variants;
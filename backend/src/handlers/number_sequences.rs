@@ -0,0 +1,93 @@
+use actix_web::{HttpResponse, get, put, web};
+use actix_web_validator::Json;
+
+use crate::{
+    err::{self, ApiErrors},
+    models::{NumberSequenceModel, UpdateNumberSequenceInput, document_type_from_name},
+    state::AppState,
+    utils::number_pattern::valid_pattern,
+};
+
+#[utoipa::path(
+    tag = "Number sequences",
+    summary = "List the number sequences",
+    description = "List the number sequences of all document types with their pattern and the next number to be allocated",
+    responses(
+        (status = 200, description = "Returns the number sequences", body = Vec<NumberSequenceModel>),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/number-sequences")]
+async fn list(state: web::Data<AppState>) -> HttpResponse {
+    let query = state.number_sequences.list();
+    let entities = match query.await {
+        Ok(entities) => entities,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let models: Vec<NumberSequenceModel> = entities.iter().map(NumberSequenceModel::from).collect();
+    HttpResponse::Ok().json(models)
+}
+
+#[utoipa::path(
+    tag = "Number sequences",
+    summary = "Change the pattern of a number sequence",
+    description = "Change the pattern of the document type's number sequence, e.g. to `INV-{YYYY}-{00000}`. \
+        The counter continues and starts over with the next period of the pattern.",
+    params(
+        ("documentType" = String, Path, description = "sale_order, sale_invoice, purchase_order or purchase_invoice"),
+    ),
+    responses(
+        (status = 200, description = "Returns the changed number sequence", body = NumberSequenceModel),
+        (status = 400, description = err::MESSAGE_INVALID_NUMBER_PATTERN, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_NUMBER_PATTERN,
+                "errorCode": err::CODE_INVALID_NUMBER_PATTERN,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_NUMBER_SEQUENCE_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_NUMBER_SEQUENCE_NOT_FOUND,
+                "errorCode": err::CODE_NUMBER_SEQUENCE_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[put("/api/number-sequences/{documentType}")]
+async fn update(
+    path: web::Path<String>,
+    payload: Json<UpdateNumberSequenceInput>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let Some(document_type) = document_type_from_name(&path.into_inner()) else {
+        return ApiErrors::NumberSequenceNotFound.into();
+    };
+
+    let payload = payload.into_inner();
+    if !valid_pattern(&payload.pattern) {
+        return ApiErrors::InvalidNumberPattern.into();
+    }
+
+    let query = state
+        .number_sequences
+        .update_pattern(document_type, payload.pattern);
+    let entity = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::NumberSequenceNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let model = NumberSequenceModel::from(&entity);
+    HttpResponse::Ok().json(model)
+}
@@ -0,0 +1,321 @@
+use actix_web::{HttpResponse, delete, get, post, put, web};
+use actix_web_validator::{Json, Query};
+use chrono::NaiveDate;
+use sea_orm::DbErr;
+
+use crate::{
+    err::{self, ApiErrors},
+    models::{
+        CreatePriceListInput, CreatePriceListItemInput, ListPriceListsInput, Page,
+        PriceListDetailModel, PriceListItemModel, PriceListModel, SetPriceListCustomersInput,
+    },
+    repositories::ERR_PRICE_LIST_ITEM_EXISTS,
+    state::AppState,
+    utils::currency::normalize_currency,
+};
+
+#[utoipa::path(
+    tag = "Price lists",
+    summary = "Create a price list",
+    description = "Create a list of variant prices in one currency, optionally limited to a period. \
+        Once assigned to customers, the list prices the lines of their orders which are given without a price.",
+    responses(
+        (status = 200, description = "Returns the created price list", body = PriceListModel),
+        (status = 400, description = err::MESSAGE_INVALID_CURRENCY, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_CURRENCY,
+                "errorCode": err::CODE_INVALID_CURRENCY,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INVALID_DATE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_DATE,
+                "errorCode": err::CODE_INVALID_DATE,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INVALID_DATE_RANGE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_DATE_RANGE,
+                "errorCode": err::CODE_INVALID_DATE_RANGE,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/price-lists")]
+async fn create(payload: Json<CreatePriceListInput>, state: web::Data<AppState>) -> HttpResponse {
+    let payload = payload.into_inner();
+
+    let Some(currency) = normalize_currency(&payload.currency) else {
+        return ApiErrors::InvalidCurrency.into();
+    };
+    let valid_from = match payload.valid_from {
+        Some(date) => match NaiveDate::parse_from_str(&date, "%Y-%m-%d") {
+            Ok(date) => Some(date),
+            Err(_) => return ApiErrors::InvalidDate.into(),
+        },
+        None => None,
+    };
+    let valid_to = match payload.valid_to {
+        Some(date) => match NaiveDate::parse_from_str(&date, "%Y-%m-%d") {
+            Ok(date) => Some(date),
+            Err(_) => return ApiErrors::InvalidDate.into(),
+        },
+        None => None,
+    };
+    if valid_from
+        .zip(valid_to)
+        .is_some_and(|(valid_from, valid_to)| valid_to < valid_from)
+    {
+        return ApiErrors::InvalidDateRange.into();
+    }
+
+    let query = state
+        .price_lists
+        .create(payload.name, currency, valid_from, valid_to);
+    let entity = match query.await {
+        Ok(entity) => entity,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let model = PriceListModel::from(&entity);
+    HttpResponse::Ok().json(model)
+}
+
+#[utoipa::path(
+    tag = "Price lists",
+    summary = "List price lists",
+    params(
+        ("limit" = Option<u64>, Query, description = "Maximum number of price lists, 100 by default"),
+        ("offset" = Option<u64>, Query, description = "Number of price lists to skip"),
+    ),
+    responses(
+        (status = 200, description = "Returns a page of price lists"),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/price-lists")]
+async fn list(query: Query<ListPriceListsInput>, state: web::Data<AppState>) -> HttpResponse {
+    let limit = query.limit.unwrap_or(100);
+    let offset = query.offset.unwrap_or(0);
+
+    let query = state.price_lists.list(limit, offset);
+    let result = match query.await {
+        Ok(result) => result,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let models: Vec<PriceListModel> = result.data.iter().map(PriceListModel::from).collect();
+    HttpResponse::Ok().json(Page {
+        limit,
+        offset,
+        total: result.total,
+        data: models,
+    })
+}
+
+#[utoipa::path(
+    tag = "Price lists",
+    summary = "Get a price list",
+    responses(
+        (status = 200, description = "Returns the price list with its prices and customers", body = PriceListDetailModel),
+        (status = 404, description = err::MESSAGE_PRICE_LIST_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PRICE_LIST_NOT_FOUND,
+                "errorCode": err::CODE_PRICE_LIST_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/price-lists/{id}")]
+async fn get(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let query = state.price_lists.get(path.into_inner());
+    let price_list = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::PriceListNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let items = match state.price_lists.list_items(price_list.id).await {
+        Ok(items) => items,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+    let customer_ids = match state.price_lists.list_customers(price_list.id).await {
+        Ok(customer_ids) => customer_ids,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    HttpResponse::Ok().json(PriceListDetailModel {
+        price_list: PriceListModel::from(&price_list),
+        items: items.iter().map(PriceListItemModel::from).collect(),
+        customer_ids,
+    })
+}
+
+#[utoipa::path(
+    tag = "Price lists",
+    summary = "Delete a price list",
+    description = "Delete the price list with its prices. Lines priced from it keep their price.",
+    responses(
+        (status = 204, description = "The price list was deleted"),
+        (status = 404, description = err::MESSAGE_PRICE_LIST_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PRICE_LIST_NOT_FOUND,
+                "errorCode": err::CODE_PRICE_LIST_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[delete("/api/price-lists/{id}")]
+async fn delete(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let query = state.price_lists.delete(path.into_inner());
+    match query.await {
+        Ok(true) => HttpResponse::NoContent().finish(),
+        Ok(false) => ApiErrors::PriceListNotFound.into(),
+        Err(_) => ApiErrors::InternalServerError.into(),
+    }
+}
+
+#[utoipa::path(
+    tag = "Price lists",
+    summary = "Add a price to a price list",
+    description = "Add the price of a variant from a minimum quantity on. \
+        Several prices of one variant make up its quantity breaks; a line gets the price of the highest minimum its quantity reaches.",
+    responses(
+        (status = 200, description = "Returns the created price", body = PriceListItemModel),
+        (status = 404, description = err::MESSAGE_PRICE_LIST_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PRICE_LIST_NOT_FOUND,
+                "errorCode": err::CODE_PRICE_LIST_NOT_FOUND,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_VARIANT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_VARIANT_NOT_FOUND,
+                "errorCode": err::CODE_VARIANT_NOT_FOUND,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_PRICE_LIST_ITEM_EXISTS, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PRICE_LIST_ITEM_EXISTS,
+                "errorCode": err::CODE_PRICE_LIST_ITEM_EXISTS,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/price-lists/{id}/items")]
+async fn create_item(
+    path: web::Path<i32>,
+    payload: Json<CreatePriceListItemInput>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let payload = payload.into_inner();
+
+    let query = state.variants.get(payload.variant_id);
+    let variant = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::VariantNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state.price_lists.create_item(
+        path.into_inner(),
+        variant.id,
+        payload.min_quantity,
+        payload.price,
+    );
+    let item = match query.await {
+        Ok(entity) => entity,
+        Err(DbErr::RecordNotFound(_)) => return ApiErrors::PriceListNotFound.into(),
+        Err(DbErr::Custom(message)) if message == ERR_PRICE_LIST_ITEM_EXISTS => {
+            return ApiErrors::PriceListItemExists.into();
+        }
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let model = PriceListItemModel::from(&item);
+    HttpResponse::Ok().json(model)
+}
+
+#[utoipa::path(
+    tag = "Price lists",
+    summary = "Assign a price list to customers",
+    description = "Replace the customers the price list is assigned to. \
+        A customer can have several lists, e.g. a yearly one and one for a promotion; of those valid on the order date and in the customer's currency, the one starting last applies.",
+    responses(
+        (status = 200, description = "Returns the ids of the customers the list is assigned to", body = Vec<i32>),
+        (status = 404, description = err::MESSAGE_CONTACT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_CONTACT_NOT_FOUND,
+                "errorCode": err::CODE_CONTACT_NOT_FOUND,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_PRICE_LIST_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PRICE_LIST_NOT_FOUND,
+                "errorCode": err::CODE_PRICE_LIST_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[put("/api/price-lists/{id}/customers")]
+async fn set_customers(
+    path: web::Path<i32>,
+    payload: Json<SetPriceListCustomersInput>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let mut customer_ids = payload.into_inner().customer_ids;
+    customer_ids.sort_unstable();
+    customer_ids.dedup();
+
+    for customer_id in &customer_ids {
+        match state.contacts.get(*customer_id).await {
+            Ok(Some(contact)) if contact.is_customer => {}
+            Ok(_) => return ApiErrors::ContactNotFound.into(),
+            Err(_) => return ApiErrors::InternalServerError.into(),
+        }
+    }
+
+    let query = state
+        .price_lists
+        .set_customers(path.into_inner(), customer_ids);
+    match query.await {
+        Ok(customer_ids) => HttpResponse::Ok().json(customer_ids),
+        Err(DbErr::RecordNotFound(_)) => ApiErrors::PriceListNotFound.into(),
+        Err(_) => ApiErrors::InternalServerError.into(),
+    }
+}
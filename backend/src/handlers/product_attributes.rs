@@ -0,0 +1,140 @@
+use actix_web::{HttpResponse, delete, get, post, web};
+use actix_web_validator::Json;
+use sea_orm::DbErr;
+
+use crate::{
+    err::{self, ApiErrors},
+    models::{CreateProductAttributeInput, ProductAttributeModel},
+    repositories::ERR_PRODUCT_ATTRIBUTE_EXISTS,
+    state::AppState,
+};
+
+#[utoipa::path(
+    tag = "Products",
+    summary = "Add an attribute to a product",
+    description = "Add an attribute like size or color with its values to a product. \
+        The product's variants can then be generated from the combinations of the values of its attributes.",
+    responses(
+        (status = 200, description = "Returns the created attribute with its values", body = ProductAttributeModel),
+        (status = 404, description = err::MESSAGE_PRODUCT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PRODUCT_NOT_FOUND,
+                "errorCode": err::CODE_PRODUCT_NOT_FOUND,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_PRODUCT_ATTRIBUTE_EXISTS, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PRODUCT_ATTRIBUTE_EXISTS,
+                "errorCode": err::CODE_PRODUCT_ATTRIBUTE_EXISTS,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/products/{id}/attributes")]
+async fn create(
+    path: web::Path<i32>,
+    payload: Json<CreateProductAttributeInput>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let payload = payload.into_inner();
+
+    let mut values: Vec<String> = Vec::with_capacity(payload.values.len());
+    for value in payload.values {
+        let value = value.trim().to_string();
+        if !value.is_empty() && !values.contains(&value) {
+            values.push(value);
+        }
+    }
+
+    let query =
+        state
+            .product_attributes
+            .create(path.into_inner(), payload.name.trim().to_string(), values);
+    let attribute = match query.await {
+        Ok(attribute) => attribute,
+        Err(DbErr::RecordNotFound(_)) => return ApiErrors::ProductNotFound.into(),
+        Err(DbErr::Custom(message)) if message == ERR_PRODUCT_ATTRIBUTE_EXISTS => {
+            return ApiErrors::ProductAttributeExists.into();
+        }
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let model = ProductAttributeModel::from(&attribute);
+    HttpResponse::Ok().json(model)
+}
+
+#[utoipa::path(
+    tag = "Products",
+    summary = "List the attributes of a product",
+    description = "List the attributes of a product with their values, in the order they appear in variant names",
+    responses(
+        (status = 200, description = "Returns the attributes", body = Vec<ProductAttributeModel>),
+        (status = 404, description = err::MESSAGE_PRODUCT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PRODUCT_NOT_FOUND,
+                "errorCode": err::CODE_PRODUCT_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/products/{id}/attributes")]
+async fn list(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let query = state.products.get(path.into_inner());
+    let product = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::ProductNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state.product_attributes.list_for_product(product.id);
+    let attributes = match query.await {
+        Ok(attributes) => attributes,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let models: Vec<ProductAttributeModel> =
+        attributes.iter().map(ProductAttributeModel::from).collect();
+    HttpResponse::Ok().json(models)
+}
+
+#[utoipa::path(
+    tag = "Products",
+    summary = "Delete a product attribute",
+    description = "Delete an attribute with its values. Variants generated from them are kept.",
+    responses(
+        (status = 204, description = "Attribute has been deleted"),
+        (status = 404, description = err::MESSAGE_PRODUCT_ATTRIBUTE_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PRODUCT_ATTRIBUTE_NOT_FOUND,
+                "errorCode": err::CODE_PRODUCT_ATTRIBUTE_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[delete("/api/product-attributes/{id}")]
+async fn delete(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let query = state.product_attributes.delete(path.into_inner());
+    match query.await {
+        Ok(true) => HttpResponse::NoContent().finish(),
+        Ok(false) => ApiErrors::ProductAttributeNotFound.into(),
+        Err(_) => ApiErrors::InternalServerError.into(),
+    }
+}
@@ -0,0 +1,199 @@
+use actix_web::{HttpResponse, delete, get, post, web};
+use actix_web_validator::Json;
+use sea_orm::DbErr;
+
+use crate::{
+    err::{self, ApiErrors},
+    models::{CreateProductCategoryInput, ProductCategoryModel},
+    repositories::{ERR_PRODUCT_CATEGORY_IN_USE, ProductCategoryDraft},
+    state::AppState,
+};
+
+#[utoipa::path(
+    tag = "Products",
+    summary = "Create a product category",
+    description = "Create a product category, optionally below a parent category. \
+        Invoice lines of the category's products are posted to its income or expense account. \
+        With a sale or purchase discount account, their discounts are posted to it on their own. \
+        Accounts and tax left unset are taken from the nearest ancestor that sets them.",
+    responses(
+        (status = 200, description = "Returns the created category", body = ProductCategoryModel),
+        (status = 404, description = err::MESSAGE_PRODUCT_CATEGORY_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PRODUCT_CATEGORY_NOT_FOUND,
+                "errorCode": err::CODE_PRODUCT_CATEGORY_NOT_FOUND,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_ACCOUNT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_ACCOUNT_NOT_FOUND,
+                "errorCode": err::CODE_ACCOUNT_NOT_FOUND,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_TAX_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_TAX_NOT_FOUND,
+                "errorCode": err::CODE_TAX_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/product-categories")]
+async fn create(
+    payload: Json<CreateProductCategoryInput>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let payload = payload.into_inner();
+
+    if let Some(parent_id) = payload.parent_id {
+        match state.product_categories.get(parent_id).await {
+            Ok(Some(_)) => {}
+            Ok(None) => return ApiErrors::ProductCategoryNotFound.into(),
+            Err(_) => return ApiErrors::InternalServerError.into(),
+        }
+    }
+
+    let account_ids = [
+        payload.income_account_id,
+        payload.expense_account_id,
+        payload.inventory_account_id,
+        payload.sale_discount_account_id,
+        payload.purchase_discount_account_id,
+    ];
+    for account_id in account_ids.into_iter().flatten() {
+        match state.ledger_accounts.get(account_id).await {
+            Ok(Some(_)) => {}
+            Ok(None) => return ApiErrors::AccountNotFound.into(),
+            Err(_) => return ApiErrors::InternalServerError.into(),
+        }
+    }
+
+    if let Some(tax_id) = payload.tax_id {
+        match state.taxes.get(tax_id).await {
+            Ok(Some(_)) => {}
+            Ok(None) => return ApiErrors::TaxNotFound.into(),
+            Err(_) => return ApiErrors::InternalServerError.into(),
+        }
+    }
+
+    let query = state.product_categories.create(ProductCategoryDraft {
+        name: payload.name.trim().to_string(),
+        parent_id: payload.parent_id,
+        income_account_id: payload.income_account_id,
+        expense_account_id: payload.expense_account_id,
+        inventory_account_id: payload.inventory_account_id,
+        tax_id: payload.tax_id,
+        sale_discount_account_id: payload.sale_discount_account_id,
+        purchase_discount_account_id: payload.purchase_discount_account_id,
+    });
+    let entity = match query.await {
+        Ok(entity) => entity,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let model = ProductCategoryModel::from(&entity);
+    HttpResponse::Ok().json(model)
+}
+
+#[utoipa::path(
+    tag = "Products",
+    summary = "List product categories",
+    description = "List all product categories by name",
+    responses(
+        (status = 200, description = "Returns the categories", body = Vec<ProductCategoryModel>),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/product-categories")]
+async fn list(state: web::Data<AppState>) -> HttpResponse {
+    let entities = match state.product_categories.list().await {
+        Ok(entities) => entities,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let models: Vec<ProductCategoryModel> =
+        entities.iter().map(ProductCategoryModel::from).collect();
+    HttpResponse::Ok().json(models)
+}
+
+#[utoipa::path(
+    tag = "Products",
+    summary = "Get a product category",
+    responses(
+        (status = 200, description = "Returns the category", body = ProductCategoryModel),
+        (status = 404, description = err::MESSAGE_PRODUCT_CATEGORY_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PRODUCT_CATEGORY_NOT_FOUND,
+                "errorCode": err::CODE_PRODUCT_CATEGORY_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/product-categories/{id}")]
+async fn get(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let query = state.product_categories.get(path.into_inner());
+    let entity = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::ProductCategoryNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let model = ProductCategoryModel::from(&entity);
+    HttpResponse::Ok().json(model)
+}
+
+#[utoipa::path(
+    tag = "Products",
+    summary = "Delete a product category",
+    description = "Delete a product category without subcategories or products",
+    responses(
+        (status = 204, description = "Category has been deleted"),
+        (status = 404, description = err::MESSAGE_PRODUCT_CATEGORY_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PRODUCT_CATEGORY_NOT_FOUND,
+                "errorCode": err::CODE_PRODUCT_CATEGORY_NOT_FOUND,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_PRODUCT_CATEGORY_IN_USE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PRODUCT_CATEGORY_IN_USE,
+                "errorCode": err::CODE_PRODUCT_CATEGORY_IN_USE,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[delete("/api/product-categories/{id}")]
+async fn delete(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let query = state.product_categories.delete(path.into_inner());
+    match query.await {
+        Ok(true) => HttpResponse::NoContent().finish(),
+        Ok(false) => ApiErrors::ProductCategoryNotFound.into(),
+        Err(DbErr::Custom(message)) if message == ERR_PRODUCT_CATEGORY_IN_USE => {
+            ApiErrors::ProductCategoryInUse.into()
+        }
+        Err(_) => ApiErrors::InternalServerError.into(),
+    }
+}
@@ -1,9 +1,13 @@
 use actix_web::{HttpResponse, delete, get, post, web};
 use actix_web_validator::{Json, Query};
+use entity::sea_orm_active_enums::{CostingMethod, ProductType, Tracking};
 
 use crate::{
     err::ApiErrors,
-    models::{CreateProductInput, ListProductsInput, Page, ProductModel},
+    models::{
+        CreateProductInput, DEFAULT_TAX_CATEGORY, ListProductsInput, Page, ProductModel,
+        costing_method_from_name, product_type_from_name, tracking_from_name,
+    },
     state::AppState,
 };
 
@@ -12,7 +16,47 @@ use crate::{
 pub async fn create(payload: Json<CreateProductInput>, state: web::Data<AppState>) -> HttpResponse {
     let payload = payload.into_inner();
 
-    let query = state.products.create(payload.name);
+    let tax_category = payload
+        .tax_category
+        .unwrap_or_else(|| DEFAULT_TAX_CATEGORY.to_string());
+    let product_type = match payload.product_type.as_deref() {
+        Some(name) => match product_type_from_name(name) {
+            Some(product_type) => product_type,
+            None => return ApiErrors::InvalidProductType.into(),
+        },
+        None => ProductType::Stockable,
+    };
+    let costing_method = match payload.costing_method.as_deref() {
+        Some(name) => match costing_method_from_name(name) {
+            Some(costing_method) => costing_method,
+            None => return ApiErrors::InvalidCostingMethod.into(),
+        },
+        None => CostingMethod::Fifo,
+    };
+    let tracking = match payload.tracking.as_deref() {
+        Some(name) => match tracking_from_name(name) {
+            Some(tracking) => tracking,
+            None => return ApiErrors::InvalidTracking.into(),
+        },
+        None => Tracking::None,
+    };
+
+    if let Some(category_id) = payload.category_id {
+        match state.product_categories.get(category_id).await {
+            Ok(Some(_)) => {}
+            Ok(None) => return ApiErrors::ProductCategoryNotFound.into(),
+            Err(_) => return ApiErrors::InternalServerError.into(),
+        }
+    }
+
+    let query = state.products.create(
+        payload.name,
+        tax_category,
+        payload.category_id,
+        product_type,
+        costing_method,
+        tracking,
+    );
 
     let entity = match query.await {
         Ok(entity) => entity,
@@ -1,9 +1,100 @@
-use actix_web::{HttpResponse, delete, get, post};
+use std::collections::{HashMap, hash_map::Entry};
 
-#[utoipa::path()]
+use actix_web::{HttpResponse, delete, get, post, web};
+use actix_web_validator::Json;
+use chrono::NaiveDate;
+use entity::sea_orm_active_enums::PurchaseInvoiceStatus;
+use sea_orm::{
+    ActiveValue::{NotSet, Set},
+    DbErr,
+};
+
+use crate::{
+    documents::einvoice::{self, EInvoice, EInvoiceLine, Party, Syntax},
+    err::{self, ApiErrors},
+    handlers::taxes::resolve_taxes,
+    models::{
+        CreatePurchaseInvoiceInput, DocumentTotalsModel, ImportedPurchaseInvoiceModel,
+        PurchaseInvoiceDetailModel, PurchaseInvoiceLineModel, PurchaseInvoiceModel,
+        ReviewIssueModel, purchase_invoice_status,
+    },
+    repositories::ERR_PERIOD_CLOSED,
+    state::AppState,
+    utils::{
+        totals::{Discount, DocumentTotals, TotalsLine, line_components, line_net},
+        vat_id::normalize_vat_id,
+    },
+};
+
+/// Hybrid PDFs carry fonts and images, so they are far larger than the XML.
+const IMPORT_SIZE_LIMIT: usize = 16 * 1024 * 1024;
+
+#[utoipa::path(
+    tag = "Purchase invoices",
+    summary = "Create a purchase invoice",
+    description = "Create a purchase invoice received from a supplier. The reference is the next number of the gapless purchase invoice sequence, the supplier's number is kept as supplier reference.",
+    responses(
+        (status = 200, description = "Returns the created purchase invoice", body = PurchaseInvoiceModel),
+        (status = 400, description = err::MESSAGE_INVALID_DATE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_DATE,
+                "errorCode": err::CODE_INVALID_DATE,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INVALID_STATUS, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_STATUS,
+                "errorCode": err::CODE_INVALID_STATUS,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_NUMBER_SEQUENCE_PERIOD_CLOSED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_NUMBER_SEQUENCE_PERIOD_CLOSED,
+                "errorCode": err::CODE_NUMBER_SEQUENCE_PERIOD_CLOSED,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
 #[post("/api/purchase-invoices")]
-async fn create() -> HttpResponse {
-    todo!();
+async fn create(
+    payload: Json<CreatePurchaseInvoiceInput>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let payload = payload.into_inner();
+
+    let date = match NaiveDate::parse_from_str(&payload.date, "%Y-%m-%d") {
+        Ok(date) => date,
+        Err(_) => return ApiErrors::InvalidDate.into(),
+    };
+    let status = match purchase_invoice_status(&payload.status) {
+        Some(status) => status,
+        None => return ApiErrors::InvalidStatus.into(),
+    };
+
+    let query = state.purchase_invoices.create(
+        payload.supplier_reference,
+        date,
+        status,
+        payload.order_id,
+        payload.supplier_id,
+        Discount::new(payload.discount_percent, payload.discount_amount),
+    );
+    let entity = match query.await {
+        Ok(entity) => entity,
+        Err(DbErr::Custom(message)) if message == ERR_PERIOD_CLOSED => {
+            return ApiErrors::NumberSequencePeriodClosed.into();
+        }
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let model = PurchaseInvoiceModel::from(&entity);
+    HttpResponse::Ok().json(model)
 }
 
 #[utoipa::path()]
@@ -12,10 +103,69 @@ async fn list() -> HttpResponse {
     todo!();
 }
 
-#[utoipa::path()]
+#[utoipa::path(
+    tag = "Purchase invoices",
+    summary = "Get a purchase invoice",
+    description = "Get a purchase invoice with its lines and the net, tax and gross amounts per line, per tax rate and in total. \
+        Taxes are applied with the rates of the invoice date and tax groups contribute one rate per component. \
+        The tax is rounded once per rate, so the line taxes can differ from the tax total by a few cents.",
+    responses(
+        (status = 200, description = "Returns the purchase invoice with its lines and totals", body = PurchaseInvoiceDetailModel),
+        (status = 404, description = err::MESSAGE_PURCHASE_INVOICE_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PURCHASE_INVOICE_NOT_FOUND,
+                "errorCode": err::CODE_PURCHASE_INVOICE_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
 #[get("/api/purchase-invoices/{id}")]
-async fn get() -> HttpResponse {
-    todo!();
+async fn get(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let id = path.into_inner();
+
+    let query = state.purchase_invoices.get(id);
+    let invoice = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::PurchaseInvoiceNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state.purchase_invoice_lines.list_for_invoice(invoice.id);
+    let lines = match query.await {
+        Ok(lines) => lines,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let tax_ids = lines.iter().filter_map(|line| line.tax_id);
+    let taxes = match resolve_taxes(tax_ids, invoice.date, &state).await {
+        Ok(taxes) => taxes,
+        Err(response) => return response,
+    };
+
+    let totals = DocumentTotals::compute(
+        lines.iter().map(|line| {
+            let resolved = line.tax_id.and_then(|tax_id| taxes.get(&tax_id));
+            TotalsLine {
+                quantity: line.quantity,
+                price: line.price,
+                discount: Discount::new(line.discount_percent, line.discount_amount),
+                components: line_components(line.tax, resolved),
+            }
+        }),
+        Discount::new(invoice.discount_percent, invoice.discount_amount),
+    );
+
+    HttpResponse::Ok().json(PurchaseInvoiceDetailModel {
+        invoice: PurchaseInvoiceModel::from(&invoice),
+        lines: lines.iter().map(PurchaseInvoiceLineModel::from).collect(),
+        totals: DocumentTotalsModel::new(lines.iter().map(|line| line.id), totals),
+    })
 }
 
 #[utoipa::path()]
@@ -23,3 +173,322 @@ async fn get() -> HttpResponse {
 async fn delete() -> HttpResponse {
     todo!();
 }
+
+#[utoipa::path(
+    tag = "Purchase invoices",
+    summary = "Import a purchase invoice from an e-invoice",
+    description = "Create a draft purchase invoice from a UBL or CII invoice (XRechnung, Factur-X, ZUGFeRD), sent either as XML or as hybrid PDF. \
+        The supplier is matched by VAT ID or name, the order by its reference and the line taxes by rate. \
+        Everything that could not be matched unambiguously is returned as issue and the invoice is flagged for manual review.",
+    request_body(content = String, content_type = "application/xml"),
+    responses(
+        (status = 200, description = "Returns the created draft invoice with its lines and the issues to review"),
+        (status = 400, description = err::MESSAGE_EINVOICE_UNREADABLE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_EINVOICE_UNREADABLE,
+                "errorCode": err::CODE_EINVOICE_UNREADABLE,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_PURCHASE_INVOICE_ALREADY_IMPORTED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PURCHASE_INVOICE_ALREADY_IMPORTED,
+                "errorCode": err::CODE_PURCHASE_INVOICE_ALREADY_IMPORTED,
+            })),
+        )),
+        (status = 413, description = err::MESSAGE_EINVOICE_TOO_LARGE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_EINVOICE_TOO_LARGE,
+                "errorCode": err::CODE_EINVOICE_TOO_LARGE,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/purchase-invoices/import")]
+async fn import(payload: web::Payload, state: web::Data<AppState>) -> HttpResponse {
+    let body = match payload.to_bytes_limited(IMPORT_SIZE_LIMIT).await {
+        Ok(Ok(body)) => body,
+        Ok(Err(_)) => return ApiErrors::EinvoiceUnreadable.into(),
+        Err(_) => return ApiErrors::EinvoiceTooLarge.into(),
+    };
+
+    let xml = match body.starts_with(b"%PDF") {
+        true => match einvoice::extract_xml(&body) {
+            Some(xml) => xml,
+            None => return ApiErrors::EinvoiceUnreadable.into(),
+        },
+        false => body.to_vec(),
+    };
+
+    let invoice = match EInvoice::parse(&xml) {
+        Ok(invoice) => invoice,
+        Err(_) => return ApiErrors::EinvoiceUnreadable.into(),
+    };
+    let date = match NaiveDate::parse_from_str(&invoice.issue_date, "%Y-%m-%d") {
+        Ok(date) => date,
+        Err(_) => return ApiErrors::EinvoiceUnreadable.into(),
+    };
+
+    let mut issues: Vec<ReviewIssueModel> = invoice
+        .validate(Syntax::FacturX)
+        .into_iter()
+        .map(|violation| ReviewIssueModel {
+            code: violation.rule.to_string(),
+            message: violation.message.to_string(),
+        })
+        .collect();
+
+    if invoice.currency != einvoice::CURRENCY {
+        issues.push(issue(
+            "CURRENCY_UNSUPPORTED",
+            format!(
+                "The invoice is in {}, the amounts were taken over unconverted",
+                invoice.currency
+            ),
+        ));
+    }
+
+    if invoice.type_code != einvoice::TYPE_CODE_INVOICE {
+        issues.push(issue(
+            "TYPE_CODE_UNSUPPORTED",
+            format!(
+                "The document type {} is imported as commercial invoice",
+                invoice.type_code
+            ),
+        ));
+    }
+
+    let supplier_id = match match_supplier(&state, &invoice.seller, &mut issues).await {
+        Ok(supplier_id) => supplier_id,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    if let Some(supplier_id) = supplier_id {
+        let query = state
+            .purchase_invoices
+            .find_by_supplier_reference(supplier_id, invoice.number.clone());
+        match query.await {
+            Ok(Some(_)) => return ApiErrors::PurchaseInvoiceAlreadyImported.into(),
+            Ok(None) => {}
+            Err(_) => return ApiErrors::InternalServerError.into(),
+        }
+    }
+
+    let order_id = match match_order(&state, &invoice, supplier_id, &mut issues).await {
+        Ok(order_id) => order_id,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let mut lines = Vec::with_capacity(invoice.lines.len());
+    let mut taxes = HashMap::new();
+    for line in invoice.lines.iter() {
+        let tax_id = match match_tax(&state, line, &mut taxes, &mut issues).await {
+            Ok(tax_id) => tax_id,
+            Err(_) => return ApiErrors::InternalServerError.into(),
+        };
+
+        // The price per unit may be rounded from a price per base quantity.
+        let net = line_net(line.quantity, line.price as i32) - line.allowance;
+        if net != line.net {
+            issues.push(issue(
+                "LINE_NET_MISMATCH",
+                format!(
+                    "Line {}: quantity times price less the discount is {}, the invoice states {}",
+                    line.id,
+                    decimal(net),
+                    decimal(line.net)
+                ),
+            ));
+        }
+
+        lines.push(entity::purchase_invoice_lines::ActiveModel {
+            id: NotSet,
+            description: Set(line.name.clone()),
+            quantity: Set(line.quantity),
+            price: Set(line.price as i32),
+            tax: Set((line.tax_rate * 100.0).round() as i32),
+            invoice_id: NotSet,
+            variant_id: Set(None),
+            transaction_id: Set(None),
+            tax_id: Set(tax_id),
+            tax_transaction_id: Set(None),
+            discount_percent: Set(0.0),
+            discount_amount: Set(line.allowance as i32),
+            discount_transaction_id: Set(None),
+        });
+    }
+
+    let review_note = issues
+        .iter()
+        .map(|issue| format!("{}: {}", issue.code, issue.message))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let entity = entity::purchase_invoices::ActiveModel {
+        id: NotSet,
+        reference: NotSet,
+        date: Set(date),
+        status: Set(PurchaseInvoiceStatus::Draft),
+        order_id: Set(order_id),
+        supplier_id: Set(supplier_id),
+        needs_review: Set(!issues.is_empty()),
+        review_note: Set(review_note),
+        supplier_reference: Set(invoice.number.clone()),
+        discount_percent: Set(0.0),
+        discount_amount: Set(invoice.allowance_total as i32),
+    };
+
+    let query = state.purchase_invoices.create_with_lines(entity, lines);
+    let (entity, lines) = match query.await {
+        Ok(result) => result,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    HttpResponse::Ok().json(ImportedPurchaseInvoiceModel {
+        invoice: PurchaseInvoiceModel::from(&entity),
+        lines: lines.iter().map(PurchaseInvoiceLineModel::from).collect(),
+        issues,
+    })
+}
+
+/// Formats an amount in cents like it is written in the invoice.
+fn decimal(cents: i64) -> String {
+    format!("{:.2}", cents as f64 / 100.0)
+}
+
+fn issue(code: &str, message: String) -> ReviewIssueModel {
+    ReviewIssueModel {
+        code: code.to_string(),
+        message,
+    }
+}
+
+/// Finds the supplier contact by VAT ID and falls back to the exact name, so
+/// suppliers without a stored VAT ID are still found.
+async fn match_supplier(
+    state: &AppState,
+    seller: &Party,
+    issues: &mut Vec<ReviewIssueModel>,
+) -> Result<Option<i32>, sea_orm::DbErr> {
+    let vat_id = normalize_vat_id(&seller.vat_id);
+    let mut candidates = Vec::new();
+    if !vat_id.is_empty() {
+        candidates = state.contacts.find_suppliers_by_vat_id(vat_id).await?;
+    }
+    if candidates.is_empty() && !seller.name.is_empty() {
+        candidates = state
+            .contacts
+            .find_suppliers_by_name(seller.name.clone())
+            .await?;
+    }
+
+    match candidates.as_slice() {
+        [supplier] => Ok(Some(supplier.id)),
+        [] => {
+            issues.push(issue(
+                "SUPPLIER_NOT_FOUND",
+                format!("No supplier matches \"{}\"", seller.name),
+            ));
+            Ok(None)
+        }
+        _ => {
+            issues.push(issue(
+                "SUPPLIER_AMBIGUOUS",
+                format!("{} suppliers match \"{}\"", candidates.len(), seller.name),
+            ));
+            Ok(None)
+        }
+    }
+}
+
+async fn match_order(
+    state: &AppState,
+    invoice: &EInvoice,
+    supplier_id: Option<i32>,
+    issues: &mut Vec<ReviewIssueModel>,
+) -> Result<Option<i32>, sea_orm::DbErr> {
+    if invoice.order_reference.is_empty() {
+        issues.push(issue(
+            "ORDER_MISSING",
+            "The invoice doesn't reference a purchase order".to_string(),
+        ));
+        return Ok(None);
+    }
+
+    let query = state
+        .purchase_orders
+        .find_by_reference(invoice.order_reference.clone());
+    let orders: Vec<_> = query
+        .await?
+        .into_iter()
+        .filter(|order| supplier_id.is_none_or(|id| order.supplier_id == id))
+        .collect();
+
+    match orders.as_slice() {
+        [order] => Ok(Some(order.id)),
+        [] => {
+            issues.push(issue(
+                "ORDER_NOT_FOUND",
+                format!(
+                    "No purchase order of the supplier has the reference \"{}\"",
+                    invoice.order_reference
+                ),
+            ));
+            Ok(None)
+        }
+        _ => {
+            issues.push(issue(
+                "ORDER_AMBIGUOUS",
+                format!(
+                    "{} purchase orders have the reference \"{}\"",
+                    orders.len(),
+                    invoice.order_reference
+                ),
+            ));
+            Ok(None)
+        }
+    }
+}
+
+/// Finds the tax with the line's rate. The candidates are cached by rate,
+/// because most invoices only use one or two rates.
+async fn match_tax(
+    state: &AppState,
+    line: &EInvoiceLine,
+    cache: &mut HashMap<u64, Vec<entity::taxes::Model>>,
+    issues: &mut Vec<ReviewIssueModel>,
+) -> Result<Option<i32>, sea_orm::DbErr> {
+    let taxes = match cache.entry(line.tax_rate.to_bits()) {
+        Entry::Occupied(entry) => entry.into_mut(),
+        Entry::Vacant(entry) => entry.insert(state.taxes.find_by_rate(line.tax_rate).await?),
+    };
+
+    let percent = line.tax_rate * 100.0;
+    match taxes.as_slice() {
+        [tax] => Ok(Some(tax.id)),
+        [] => {
+            issues.push(issue(
+                "TAX_NOT_FOUND",
+                format!("Line {}: no tax has the rate {percent}%", line.id),
+            ));
+            Ok(None)
+        }
+        taxes => {
+            issues.push(issue(
+                "TAX_AMBIGUOUS",
+                format!(
+                    "Line {}: {} taxes have the rate {percent}% (category {})",
+                    line.id,
+                    taxes.len(),
+                    line.tax_category.code()
+                ),
+            ));
+            Ok(None)
+        }
+    }
+}
@@ -1,9 +1,136 @@
-use actix_web::{HttpResponse, delete, get, post};
+use actix_web::{HttpResponse, delete, get, post, web};
+use actix_web_validator::Json;
 
-#[utoipa::path()]
+use crate::{
+    err::{self, ApiErrors},
+    handlers::units::resolve_line_unit,
+    models::{CreatePurchaseOrderLineInput, PurchaseOrderLineModel},
+    repositories::PurchaseOrderLineDraft,
+    state::AppState,
+    utils::totals::Discount,
+};
+
+#[utoipa::path(
+    tag = "Purchase orders",
+    summary = "Add a line to a purchase order",
+    description = "Add a line to a purchase order. A line with a variant is filled from the order supplier's catalog entry for it: \
+        without a price it gets the entry's price, and without a description the entry's description, prefixed by the supplier's SKU. \
+        Variants the supplier has no entry for get their purchase price and name. \
+        A line in another unit than its variant's, e.g. a box of 12 for a variant bought in pieces, is priced per its unit.",
+    responses(
+        (status = 200, description = "Returns the created line", body = PurchaseOrderLineModel),
+        (status = 400, description = err::MESSAGE_MISSING_PRICE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_MISSING_PRICE,
+                "errorCode": err::CODE_MISSING_PRICE,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INCOMPATIBLE_UNIT, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INCOMPATIBLE_UNIT,
+                "errorCode": err::CODE_INCOMPATIBLE_UNIT,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_PURCHASE_ORDER_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PURCHASE_ORDER_NOT_FOUND,
+                "errorCode": err::CODE_PURCHASE_ORDER_NOT_FOUND,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_UNIT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_UNIT_NOT_FOUND,
+                "errorCode": err::CODE_UNIT_NOT_FOUND,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_VARIANT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_VARIANT_NOT_FOUND,
+                "errorCode": err::CODE_VARIANT_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
 #[post("/api/purchase-order-lines")]
-async fn create() -> HttpResponse {
-    todo!();
+async fn create(
+    payload: Json<CreatePurchaseOrderLineInput>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let payload = payload.into_inner();
+
+    let query = state.purchase_orders.get(payload.order_id);
+    let order = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::PurchaseOrderNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let variant = match payload.variant_id {
+        Some(variant_id) => match state.variants.get(variant_id).await {
+            Ok(Some(entity)) => Some(entity),
+            Ok(None) => return ApiErrors::VariantNotFound.into(),
+            Err(_) => return ApiErrors::InternalServerError.into(),
+        },
+        None => None,
+    };
+
+    let (unit_id, factor) = match resolve_line_unit(payload.unit_id, variant.as_ref(), &state).await
+    {
+        Ok(unit) => unit,
+        Err(response) => return response,
+    };
+
+    let entry = match &variant {
+        Some(variant) => {
+            let query = state
+                .supplier_prices
+                .get_for_supplier(variant.id, order.supplier_id);
+            match query.await {
+                Ok(entry) => entry,
+                Err(_) => return ApiErrors::InternalServerError.into(),
+            }
+        }
+        None => None,
+    };
+
+    // Catalog and variant prices are per unit of the variant.
+    let price = match (payload.price, &entry, &variant) {
+        (Some(price), _, _) => price,
+        (None, Some(entry), _) => (entry.price as f64 * factor).round() as i32,
+        (None, None, Some(variant)) => (variant.purchase_price as f64 * factor).round() as i32,
+        (None, None, None) => return ApiErrors::MissingPrice.into(),
+    };
+
+    let description = match (&entry, &variant) {
+        _ if !payload.description.trim().is_empty() => payload.description,
+        (Some(entry), Some(variant)) => catalog_description(entry, variant),
+        (None, Some(variant)) => variant.name.clone(),
+        _ => payload.description,
+    };
+
+    let line = PurchaseOrderLineDraft {
+        description,
+        quantity: payload.quantity,
+        price,
+        tax: payload.tax,
+        tax_id: None,
+        variant_id: variant.map(|variant| variant.id),
+        discount: Discount::new(payload.discount_percent, payload.discount_amount),
+        unit_id,
+    };
+    let entity = match state.purchase_order_lines.create(order.id, line).await {
+        Ok(entity) => entity,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let model = PurchaseOrderLineModel::from(&entity);
+    HttpResponse::Ok().json(model)
 }
 
 #[utoipa::path()]
@@ -23,3 +150,19 @@ async fn get() -> HttpResponse {
 async fn delete() -> HttpResponse {
     todo!();
 }
+
+/// Describes a variant the way the supplier's catalog does: the supplier's
+/// description, or else the variant's name, prefixed by the supplier's SKU.
+pub(super) fn catalog_description(
+    entry: &entity::supplier_prices::Model,
+    variant: &entity::variants::Model,
+) -> String {
+    let name = match entry.description.trim() {
+        "" => variant.name.as_str(),
+        description => description,
+    };
+    match entry.supplier_sku.trim() {
+        "" => name.to_string(),
+        sku => format!("{sku} {name}"),
+    }
+}
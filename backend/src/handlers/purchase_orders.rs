@@ -1,9 +1,87 @@
-use actix_web::{HttpResponse, delete, get, post};
+use actix_web::{HttpResponse, delete, get, post, web};
+use actix_web_validator::Json;
+use chrono::{NaiveDate, Utc};
+use entity::sea_orm_active_enums::PurchaseOrderStatus;
+use sea_orm::DbErr;
 
-#[utoipa::path()]
+use crate::{
+    err::{self, ApiErrors},
+    handlers::stock_moves::{OrderLine, attach_lots, line_moves, lot_error},
+    models::{
+        CreatePurchaseOrderInput, DocumentTotalsModel, OrderLotsInput, PurchaseOrderDetailModel,
+        PurchaseOrderLineModel, PurchaseOrderModel, purchase_order_status,
+    },
+    repositories::{ERR_PERIOD_CLOSED, ERR_PURCHASE_ORDER_RECEIVED},
+    state::AppState,
+    utils::totals::{Discount, DocumentTotals, TotalsLine, line_components},
+};
+
+#[utoipa::path(
+    tag = "Purchase orders",
+    summary = "Create a purchase order",
+    description = "Create a purchase order at a supplier. The reference is the next number of the purchase order sequence.",
+    responses(
+        (status = 200, description = "Returns the created purchase order", body = PurchaseOrderModel),
+        (status = 400, description = err::MESSAGE_INVALID_DATE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_DATE,
+                "errorCode": err::CODE_INVALID_DATE,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INVALID_STATUS, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_STATUS,
+                "errorCode": err::CODE_INVALID_STATUS,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_NUMBER_SEQUENCE_PERIOD_CLOSED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_NUMBER_SEQUENCE_PERIOD_CLOSED,
+                "errorCode": err::CODE_NUMBER_SEQUENCE_PERIOD_CLOSED,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
 #[post("/api/purchase-orders")]
-async fn create() -> HttpResponse {
-    todo!();
+async fn create(
+    payload: Json<CreatePurchaseOrderInput>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let payload = payload.into_inner();
+
+    let date = match NaiveDate::parse_from_str(&payload.date, "%Y-%m-%d") {
+        Ok(date) => date,
+        Err(_) => return ApiErrors::InvalidDate.into(),
+    };
+    let status = match purchase_order_status(&payload.status) {
+        Some(status) => status,
+        None => return ApiErrors::InvalidStatus.into(),
+    };
+
+    let query = state.purchase_orders.create(
+        date,
+        status,
+        payload.supplier_id,
+        payload.source_stock_id,
+        payload.target_stock_id,
+        Discount::new(payload.discount_percent, payload.discount_amount),
+    );
+    let entity = match query.await {
+        Ok(entity) => entity,
+        Err(DbErr::Custom(message)) if message == ERR_PERIOD_CLOSED => {
+            return ApiErrors::NumberSequencePeriodClosed.into();
+        }
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let model = PurchaseOrderModel::from(&entity);
+    HttpResponse::Ok().json(model)
 }
 
 #[utoipa::path()]
@@ -12,10 +90,246 @@ async fn list() -> HttpResponse {
     todo!();
 }
 
-#[utoipa::path()]
+#[utoipa::path(
+    tag = "Purchase orders",
+    summary = "Get a purchase order",
+    description = "Get a purchase order with its lines and the net, tax and gross amounts per line, per tax rate and in total. \
+        The tax is rounded once per rate, so the line taxes can differ from the tax total by a few cents.",
+    responses(
+        (status = 200, description = "Returns the purchase order with its lines and totals", body = PurchaseOrderDetailModel),
+        (status = 404, description = err::MESSAGE_PURCHASE_ORDER_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PURCHASE_ORDER_NOT_FOUND,
+                "errorCode": err::CODE_PURCHASE_ORDER_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
 #[get("/api/purchase-orders/{id}")]
-async fn get() -> HttpResponse {
-    todo!();
+async fn get(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let id = path.into_inner();
+
+    let query = state.purchase_orders.get(id);
+    let order = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::PurchaseOrderNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state.purchase_order_lines.list_for_order(order.id);
+    let lines = match query.await {
+        Ok(lines) => lines,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    // Order lines only know the percentage of their tax.
+    let totals = DocumentTotals::compute(
+        lines.iter().map(|line| TotalsLine {
+            quantity: line.quantity,
+            price: line.price,
+            discount: Discount::new(line.discount_percent, line.discount_amount),
+            components: line_components(line.tax, None),
+        }),
+        Discount::new(order.discount_percent, order.discount_amount),
+    );
+
+    HttpResponse::Ok().json(PurchaseOrderDetailModel {
+        order: PurchaseOrderModel::from(&order),
+        lines: lines.iter().map(PurchaseOrderLineModel::from).collect(),
+        totals: DocumentTotalsModel::new(lines.iter().map(|line| line.id), totals),
+    })
+}
+
+#[utoipa::path(
+    tag = "Purchase orders",
+    summary = "Confirm a purchase order",
+    description = "Confirm a draft purchase order, e.g. one proposed by the replenishment, to place it at the supplier.",
+    responses(
+        (status = 200, description = "Returns the confirmed purchase order", body = PurchaseOrderModel),
+        (status = 404, description = err::MESSAGE_PURCHASE_ORDER_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PURCHASE_ORDER_NOT_FOUND,
+                "errorCode": err::CODE_PURCHASE_ORDER_NOT_FOUND,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_PURCHASE_ORDER_ALREADY_CONFIRMED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PURCHASE_ORDER_ALREADY_CONFIRMED,
+                "errorCode": err::CODE_PURCHASE_ORDER_ALREADY_CONFIRMED,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_PURCHASE_ORDER_ALREADY_RECEIVED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PURCHASE_ORDER_ALREADY_RECEIVED,
+                "errorCode": err::CODE_PURCHASE_ORDER_ALREADY_RECEIVED,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/purchase-orders/{id}/confirm")]
+async fn confirm(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let query = state.purchase_orders.get(path.into_inner());
+    let order = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::PurchaseOrderNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+    match order.status {
+        PurchaseOrderStatus::Draft => {}
+        PurchaseOrderStatus::Order => return ApiErrors::PurchaseOrderAlreadyConfirmed.into(),
+        PurchaseOrderStatus::Finished => return ApiErrors::PurchaseOrderAlreadyReceived.into(),
+    }
+
+    let order = match state.purchase_orders.confirm(order).await {
+        Ok(entity) => entity,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let model = PurchaseOrderModel::from(&order);
+    HttpResponse::Ok().json(model)
+}
+
+#[utoipa::path(
+    tag = "Purchase orders",
+    summary = "Receive a purchase order",
+    description = "Move the goods of the order lines with a stockable variant from the order's source stock into its target stock and finish the order. \
+        Quantities are converted into the variant's unit. The goods are valued at the line's net after its discount and its share of the order discount. \
+        Lines of lot or serial tracked variants are received in the lots given for them, which are created on their first receipt. \
+        Drop-shipped sale order lines are delivered by the move of their purchase order line, and their sale order is finished once all its goods are delivered.",
+    request_body(content = Option<OrderLotsInput>),
+    responses(
+        (status = 200, description = "Returns the received purchase order", body = PurchaseOrderModel),
+        (status = 400, description = err::MESSAGE_DUPLICATE_LOT, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_DUPLICATE_LOT,
+                "errorCode": err::CODE_DUPLICATE_LOT,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INCOMPATIBLE_UNIT, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INCOMPATIBLE_UNIT,
+                "errorCode": err::CODE_INCOMPATIBLE_UNIT,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INVALID_DATE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_DATE,
+                "errorCode": err::CODE_INVALID_DATE,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_LOT_LINE_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOT_LINE_NOT_FOUND,
+                "errorCode": err::CODE_LOT_LINE_NOT_FOUND,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_LOT_QUANTITY_MISMATCH, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOT_QUANTITY_MISMATCH,
+                "errorCode": err::CODE_LOT_QUANTITY_MISMATCH,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_LOTS_MISSING, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOTS_MISSING,
+                "errorCode": err::CODE_LOTS_MISSING,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_LOTS_NOT_TRACKED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOTS_NOT_TRACKED,
+                "errorCode": err::CODE_LOTS_NOT_TRACKED,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_SERIAL_QUANTITY_NOT_ONE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SERIAL_QUANTITY_NOT_ONE,
+                "errorCode": err::CODE_SERIAL_QUANTITY_NOT_ONE,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_PURCHASE_ORDER_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PURCHASE_ORDER_NOT_FOUND,
+                "errorCode": err::CODE_PURCHASE_ORDER_NOT_FOUND,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_PURCHASE_ORDER_ALREADY_RECEIVED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PURCHASE_ORDER_ALREADY_RECEIVED,
+                "errorCode": err::CODE_PURCHASE_ORDER_ALREADY_RECEIVED,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_SERIAL_IN_STOCK, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SERIAL_IN_STOCK,
+                "errorCode": err::CODE_SERIAL_IN_STOCK,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/purchase-orders/{id}/receive")]
+async fn receive(
+    path: web::Path<i32>,
+    payload: Option<Json<OrderLotsInput>>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let query = state.purchase_orders.get(path.into_inner());
+    let order = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::PurchaseOrderNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+    if order.status == PurchaseOrderStatus::Finished {
+        return ApiErrors::PurchaseOrderAlreadyReceived.into();
+    }
+
+    let query = state.purchase_order_lines.list_for_order(order.id);
+    let lines: Vec<OrderLine> = match query.await {
+        Ok(lines) => lines.iter().map(OrderLine::from).collect(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let discount = Discount::new(order.discount_percent, order.discount_amount);
+    let mut drafts = match line_moves(&lines, discount, &state).await {
+        Ok(drafts) => drafts,
+        Err(response) => return response,
+    };
+    let lots = payload.map(|payload| payload.into_inner().lots);
+    if let Err(response) = attach_lots(&mut drafts, lots.unwrap_or_default(), &state).await {
+        return response;
+    }
+
+    let query = state
+        .purchase_orders
+        .receive(order, Utc::now().date_naive(), drafts);
+    let order = match query.await {
+        Ok(entity) => entity,
+        Err(DbErr::Custom(message)) if message == ERR_PURCHASE_ORDER_RECEIVED => {
+            return ApiErrors::PurchaseOrderAlreadyReceived.into();
+        }
+        Err(error) => return lot_error(error).into(),
+    };
+
+    let model = PurchaseOrderModel::from(&order);
+    HttpResponse::Ok().json(model)
 }
 
 #[utoipa::path()]
@@ -0,0 +1,334 @@
+use std::collections::BTreeMap;
+
+use actix_web::{HttpResponse, delete, get, post, web};
+use actix_web_validator::Json;
+use chrono::Utc;
+use entity::sea_orm_active_enums::{StockKind, TaxDirection};
+use sea_orm::DbErr;
+
+use crate::{
+    err::{self, ApiErrors},
+    handlers::{
+        purchase_order_lines::catalog_description,
+        tax_rules::{line_taxes, tax_percent},
+    },
+    models::{
+        CreateReorderRuleInput, PurchaseOrderModel, ReorderRuleModel, ReplenishmentModel,
+        ReplenishmentShortfallModel,
+    },
+    repositories::{
+        ERR_PERIOD_CLOSED, ERR_REORDER_RULE_EXISTS, PurchaseOrderLineDraft, PurchaseProposalDraft,
+    },
+    state::AppState,
+    utils::{
+        replenishment::{order_quantity, shortfall},
+        totals::Discount,
+    },
+};
+
+#[utoipa::path(
+    tag = "Reorder rules",
+    summary = "Create a reorder rule",
+    description = "Keep a stockable variant in an internal location between a minimum and a maximum quantity in the variant's unit. \
+        The location counts the locations below it.",
+    responses(
+        (status = 200, description = "Returns the created rule", body = ReorderRuleModel),
+        (status = 400, description = err::MESSAGE_INVALID_REORDER_QUANTITIES, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_REORDER_QUANTITIES,
+                "errorCode": err::CODE_INVALID_REORDER_QUANTITIES,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INVALID_REORDER_STOCK, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_REORDER_STOCK,
+                "errorCode": err::CODE_INVALID_REORDER_STOCK,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_VARIANT_NOT_STOCKABLE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_VARIANT_NOT_STOCKABLE,
+                "errorCode": err::CODE_VARIANT_NOT_STOCKABLE,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_STOCK_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_STOCK_NOT_FOUND,
+                "errorCode": err::CODE_STOCK_NOT_FOUND,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_VARIANT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_VARIANT_NOT_FOUND,
+                "errorCode": err::CODE_VARIANT_NOT_FOUND,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_REORDER_RULE_EXISTS, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_REORDER_RULE_EXISTS,
+                "errorCode": err::CODE_REORDER_RULE_EXISTS,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/reorder-rules")]
+async fn create(payload: Json<CreateReorderRuleInput>, state: web::Data<AppState>) -> HttpResponse {
+    let payload = payload.into_inner();
+
+    if payload.max_quantity < payload.min_quantity {
+        return ApiErrors::InvalidReorderQuantities.into();
+    }
+
+    let query = state.variants.get(payload.variant_id);
+    let variant = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::VariantNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    match state.variants.stockable_ids(vec![variant.id]).await {
+        Ok(ids) if ids.contains(&variant.id) => {}
+        Ok(_) => return ApiErrors::VariantNotStockable.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    }
+
+    match state.stocks.get(payload.stock_id).await {
+        Ok(Some(stock)) if stock.kind == StockKind::Internal => {}
+        Ok(Some(_)) => return ApiErrors::InvalidReorderStock.into(),
+        Ok(None) => return ApiErrors::StockNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    }
+
+    let query = state.reorder_rules.create(
+        variant.id,
+        payload.stock_id,
+        payload.min_quantity,
+        payload.max_quantity,
+    );
+    let entity = match query.await {
+        Ok(entity) => entity,
+        Err(DbErr::Custom(message)) if message == ERR_REORDER_RULE_EXISTS => {
+            return ApiErrors::ReorderRuleExists.into();
+        }
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let model = ReorderRuleModel::from(&entity);
+    HttpResponse::Ok().json(model)
+}
+
+#[utoipa::path(
+    tag = "Reorder rules",
+    summary = "List the reorder rules",
+    description = "List the reorder rules of all variants and locations",
+    responses(
+        (status = 200, description = "Returns the rules", body = Vec<ReorderRuleModel>),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/reorder-rules")]
+async fn list(state: web::Data<AppState>) -> HttpResponse {
+    let entities = match state.reorder_rules.list().await {
+        Ok(entities) => entities,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let models: Vec<ReorderRuleModel> = entities.iter().map(ReorderRuleModel::from).collect();
+    HttpResponse::Ok().json(models)
+}
+
+#[utoipa::path(
+    tag = "Reorder rules",
+    summary = "Delete a reorder rule",
+    description = "Delete a reorder rule. Orders it has proposed stay as they are.",
+    responses(
+        (status = 204, description = "Rule has been deleted"),
+        (status = 404, description = err::MESSAGE_REORDER_RULE_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_REORDER_RULE_NOT_FOUND,
+                "errorCode": err::CODE_REORDER_RULE_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[delete("/api/reorder-rules/{id}")]
+async fn delete(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let query = state.reorder_rules.delete(path.into_inner());
+    match query.await {
+        Ok(true) => HttpResponse::NoContent().finish(),
+        Ok(false) => ApiErrors::ReorderRuleNotFound.into(),
+        Err(_) => ApiErrors::InternalServerError.into(),
+    }
+}
+
+#[utoipa::path(
+    tag = "Reorder rules",
+    summary = "Propose purchase orders",
+    description = "Run the replenishment, e.g. nightly from a scheduler. For every rule, what the location will hold is what is on hand, \
+        less what confirmed sale orders have reserved, plus what open purchase orders, drafts included, bring in. \
+        Variants expected below their minimum are ordered up to their maximum, rounded up to the supplier's minimum quantity and pack size, \
+        from their preferred supplier or else the cheapest one. The lines are grouped into a draft purchase order per supplier and location, \
+        which a buyer confirms. The lines are taxed by the purchase tax rules of the supplier's region, or else the product category's tax, \
+        at the rate valid on the order date. As drafts count as incoming, running the replenishment again doesn't propose the same goods twice.",
+    responses(
+        (status = 200, description = "Returns the proposed orders and the shortfalls no supplier covers", body = ReplenishmentModel),
+        (status = 409, description = err::MESSAGE_NUMBER_SEQUENCE_PERIOD_CLOSED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_NUMBER_SEQUENCE_PERIOD_CLOSED,
+                "errorCode": err::CODE_NUMBER_SEQUENCE_PERIOD_CLOSED,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_SUPPLIER_LOCATION_MISSING, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SUPPLIER_LOCATION_MISSING,
+                "errorCode": err::CODE_SUPPLIER_LOCATION_MISSING,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/reorder-rules/replenish")]
+async fn replenish(state: web::Data<AppState>) -> HttpResponse {
+    let forecasts = match state.reorder_rules.forecast().await {
+        Ok(forecasts) => forecasts,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+    let shortfalls: Vec<ReplenishmentShortfallModel> = forecasts
+        .iter()
+        .filter_map(|forecast| {
+            let rule = &forecast.rule;
+            let quantity = shortfall(forecast.forecast(), rule.min_quantity, rule.max_quantity)?;
+            Some(ReplenishmentShortfallModel {
+                rule_id: rule.id,
+                variant_id: rule.variant_id,
+                stock_id: rule.stock_id,
+                quantity,
+            })
+        })
+        .collect();
+    if shortfalls.is_empty() {
+        return HttpResponse::Ok().json(ReplenishmentModel {
+            orders: Vec::new(),
+            without_supplier: Vec::new(),
+        });
+    }
+
+    let suppliers = match state.stocks.list_virtual().await {
+        Ok(stocks) => stocks
+            .into_iter()
+            .find(|stock| stock.kind == StockKind::Supplier),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+    let Some(suppliers) = suppliers else {
+        return ApiErrors::SupplierLocationMissing.into();
+    };
+
+    let mut picks: BTreeMap<i32, Vec<_>> = BTreeMap::new();
+    let mut without_supplier = Vec::new();
+    for shortfall in shortfalls {
+        let query = state.supplier_prices.list_for_variant(shortfall.variant_id);
+        // The preferred entry comes first, then the cheapest.
+        let entry = match query.await {
+            Ok(entries) => entries.into_iter().next(),
+            Err(_) => return ApiErrors::InternalServerError.into(),
+        };
+        let Some(entry) = entry else {
+            without_supplier.push(shortfall);
+            continue;
+        };
+
+        // Rules can't outlive their variant.
+        let variant = match state.variants.get(shortfall.variant_id).await {
+            Ok(Some(entity)) => entity,
+            Ok(None) | Err(_) => return ApiErrors::InternalServerError.into(),
+        };
+        picks
+            .entry(entry.supplier_id)
+            .or_default()
+            .push((shortfall, entry, variant));
+    }
+
+    let date = Utc::now().date_naive();
+    let mut lines: BTreeMap<(i32, i32), Vec<PurchaseOrderLineDraft>> = BTreeMap::new();
+    for (supplier_id, picks) in picks {
+        // Catalog entries can't outlive their supplier.
+        let supplier = match state.contacts.get(supplier_id).await {
+            Ok(Some(entity)) => entity,
+            Ok(None) | Err(_) => return ApiErrors::InternalServerError.into(),
+        };
+        let variants: Vec<&entity::variants::Model> =
+            picks.iter().map(|(_, _, variant)| variant).collect();
+        let query = line_taxes(TaxDirection::Purchase, &supplier, &variants, date, &state);
+        let taxes = match query.await {
+            Ok(taxes) => taxes,
+            Err(response) => return response,
+        };
+
+        // Catalog prices and rule quantities are per unit of the variant.
+        for (shortfall, entry, variant) in picks {
+            let tax = taxes.get(&variant.id);
+            lines
+                .entry((supplier_id, shortfall.stock_id))
+                .or_default()
+                .push(PurchaseOrderLineDraft {
+                    description: catalog_description(&entry, &variant),
+                    quantity: order_quantity(
+                        shortfall.quantity,
+                        entry.min_quantity,
+                        entry.pack_size,
+                    ),
+                    price: entry.price,
+                    tax: tax.map(tax_percent).unwrap_or_default(),
+                    tax_id: tax.map(|tax| tax.id),
+                    variant_id: Some(variant.id),
+                    discount: Discount::default(),
+                    unit_id: variant.unit_id,
+                });
+        }
+    }
+
+    let proposals = lines
+        .into_iter()
+        .map(|((supplier_id, stock_id), lines)| PurchaseProposalDraft {
+            supplier_id,
+            source_stock_id: suppliers.id,
+            target_stock_id: stock_id,
+            lines,
+        })
+        .collect();
+    let query = state.purchase_orders.propose(date, proposals);
+    let orders = match query.await {
+        Ok(orders) => orders,
+        Err(DbErr::Custom(message)) if message == ERR_PERIOD_CLOSED => {
+            return ApiErrors::NumberSequencePeriodClosed.into();
+        }
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    HttpResponse::Ok().json(ReplenishmentModel {
+        orders: orders.iter().map(PurchaseOrderModel::from).collect(),
+        without_supplier,
+    })
+}
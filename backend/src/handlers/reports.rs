@@ -0,0 +1,568 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpResponse, get, web};
+use actix_web_validator::Query;
+use chrono::NaiveDate;
+use entity::sea_orm_active_enums::TaxRegion;
+
+use crate::{
+    err::{self, ApiErrors},
+    models::{
+        InventoryValuationLineModel, InventoryValuationModel, UstvaFieldModel, UstvaModel,
+        VatReportInput, VatReportLineModel, VatReportModel, tax_region_from_name, tax_region_name,
+    },
+    repositories::ResolvedTax,
+    state::AppState,
+    utils::{
+        tax_groups::component_bases,
+        tax_rules::tax_region,
+        totals::{Discount, line_net, taxable_nets},
+    },
+};
+
+/// The tax id, the tax percentage and the net amount of an invoice or
+/// credit note line.
+type TaxableLine = (Option<i32>, i32, i64);
+
+/// The sign, the date the tax rates apply at, the region of the customer or
+/// supplier and the lines of an invoice or credit note.
+type TaxableDocument = (i64, NaiveDate, TaxRegion, Vec<TaxableLine>);
+
+/// The standard rate the tax of reverse charge purchases is declared at.
+const STANDARD_RATE: f64 = 0.19;
+
+/// The UNTDID 5305 category of taxes the recipient owes.
+const REVERSE_CHARGE: &str = "AE";
+
+#[utoipa::path(
+    tag = "Reports",
+    summary = "VAT report",
+    description = "Sum up the taxable base and the tax per tax code of the posted sale and purchase invoices dated within the period. \
+        Credit notes of the period are subtracted. The balance is the output tax minus the input tax, negative if it is refunded.",
+    params(
+        ("from" = String, Query, description = "First day of the period as YYYY-MM-DD"),
+        ("to" = String, Query, description = "Last day of the period as YYYY-MM-DD"),
+    ),
+    responses(
+        (status = 200, description = "Returns the VAT report", body = VatReportModel),
+        (status = 400, description = err::MESSAGE_INVALID_DATE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_DATE,
+                "errorCode": err::CODE_INVALID_DATE,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INVALID_DATE_RANGE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_DATE_RANGE,
+                "errorCode": err::CODE_INVALID_DATE_RANGE,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/reports/vat")]
+async fn vat(query: Query<VatReportInput>, state: web::Data<AppState>) -> HttpResponse {
+    match vat_report(&query, &state).await {
+        Ok(report) => HttpResponse::Ok().json(report),
+        Err(response) => response,
+    }
+}
+
+#[utoipa::path(
+    tag = "Reports",
+    summary = "VAT report as UStVA",
+    description = "Map the VAT report of the period onto the fields of the German advance VAT return (Umsatzsteuer-Voranmeldung). \
+        Bases are given in full euros and taxes in euros and cents, like they are entered in ELSTER. \
+        Tax-free sales to the EU and other countries, reverse charge sales and purchases are mapped by the region of the customer or supplier. \
+        Other tax-free domestic sales are left out, because the reason of the exemption decides their field.",
+    params(
+        ("from" = String, Query, description = "First day of the period as YYYY-MM-DD"),
+        ("to" = String, Query, description = "Last day of the period as YYYY-MM-DD"),
+    ),
+    responses(
+        (status = 200, description = "Returns the UStVA fields", body = UstvaModel),
+        (status = 400, description = err::MESSAGE_INVALID_DATE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_DATE,
+                "errorCode": err::CODE_INVALID_DATE,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INVALID_DATE_RANGE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_DATE_RANGE,
+                "errorCode": err::CODE_INVALID_DATE_RANGE,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/reports/vat/ustva")]
+async fn ustva(query: Query<VatReportInput>, state: web::Data<AppState>) -> HttpResponse {
+    let report = match vat_report(&query, &state).await {
+        Ok(report) => report,
+        Err(response) => return response,
+    };
+
+    let model = UstvaModel {
+        from: report.from.clone(),
+        to: report.to.clone(),
+        fields: ustva_fields(&report),
+    };
+    HttpResponse::Ok().json(model)
+}
+
+#[utoipa::path(
+    tag = "Reports",
+    summary = "Inventory valuation",
+    description = "Sum up the quantity and value of every variant in the own stocks from what is left of the goods received. \
+        Goods are valued at the cost they were received at and leave the stock by the costing method of their product.",
+    responses(
+        (status = 200, description = "Returns the inventory valuation", body = InventoryValuationModel),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/reports/inventory-valuation")]
+async fn inventory_valuation(state: web::Data<AppState>) -> HttpResponse {
+    let valuations = match state.stock_valuation_layers.summary().await {
+        Ok(valuations) => valuations,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let lines: Vec<InventoryValuationLineModel> = valuations
+        .into_iter()
+        .map(|valuation| InventoryValuationLineModel {
+            variant_id: valuation.variant_id,
+            name: valuation.name,
+            quantity: valuation.quantity,
+            value: valuation.value,
+            unit_cost: valuation.value as f64 / valuation.quantity,
+        })
+        .collect();
+    let total = lines.iter().map(|line| line.value).sum();
+    HttpResponse::Ok().json(InventoryValuationModel { lines, total })
+}
+
+/// Builds the VAT report of the period.
+async fn vat_report(
+    query: &VatReportInput,
+    state: &AppState,
+) -> Result<VatReportModel, HttpResponse> {
+    let from = match NaiveDate::parse_from_str(&query.from, "%Y-%m-%d") {
+        Ok(date) => date,
+        Err(_) => return Err(ApiErrors::InvalidDate.into()),
+    };
+    let to = match NaiveDate::parse_from_str(&query.to, "%Y-%m-%d") {
+        Ok(date) => date,
+        Err(_) => return Err(ApiErrors::InvalidDate.into()),
+    };
+    if to < from {
+        return Err(ApiErrors::InvalidDateRange.into());
+    }
+
+    let query = state.sale_invoices.list_posted_with_lines(from, to);
+    let sale_invoices = match query.await {
+        Ok(invoices) => invoices,
+        Err(_) => return Err(ApiErrors::InternalServerError.into()),
+    };
+
+    let query = state.sale_credit_notes.list_with_lines(from, to);
+    let sale_credit_notes = match query.await {
+        Ok(credit_notes) => credit_notes,
+        Err(_) => return Err(ApiErrors::InternalServerError.into()),
+    };
+
+    let query = state.purchase_invoices.list_posted_with_lines(from, to);
+    let purchase_invoices = match query.await {
+        Ok(invoices) => invoices,
+        Err(_) => return Err(ApiErrors::InternalServerError.into()),
+    };
+
+    let query = state.purchase_credit_notes.list_with_lines(from, to);
+    let purchase_credit_notes = match query.await {
+        Ok(credit_notes) => credit_notes,
+        Err(_) => return Err(ApiErrors::InternalServerError.into()),
+    };
+
+    let contact_ids = sale_invoices
+        .iter()
+        .map(|(invoice, _)| Some(invoice.customer_id))
+        .chain(
+            sale_credit_notes
+                .iter()
+                .map(|(_, _, invoice)| Some(invoice.customer_id)),
+        )
+        .chain(
+            purchase_invoices
+                .iter()
+                .map(|(invoice, _)| invoice.supplier_id),
+        )
+        .chain(
+            purchase_credit_notes
+                .iter()
+                .map(|(_, _, invoice)| invoice.supplier_id),
+        )
+        .flatten();
+    let mut regions: HashMap<i32, TaxRegion> = HashMap::new();
+    for contact_id in contact_ids {
+        if regions.contains_key(&contact_id) {
+            continue;
+        }
+
+        // Contacts with invoices can't be deleted.
+        let contact = match state.contacts.get(contact_id).await {
+            Ok(Some(contact)) => contact,
+            Ok(None) | Err(_) => return Err(ApiErrors::InternalServerError.into()),
+        };
+        let region = tax_region(&state.letterhead.country, &contact.country);
+        regions.insert(contact_id, region);
+    }
+    // Purchases without a known supplier are taken as domestic.
+    let region = |contact_id: Option<i32>| {
+        contact_id
+            .and_then(|id| regions.get(&id).cloned())
+            .unwrap_or(TaxRegion::Domestic)
+    };
+
+    // Invoices count positive and credit notes negative. Credit notes are
+    // taxed at the rates of the invoice they correct. Discounts reduce the
+    // taxable amounts.
+    let mut sales: Vec<TaxableDocument> = Vec::new();
+    for (invoice, lines) in &sale_invoices {
+        let nets: Vec<i64> = lines
+            .iter()
+            .map(|line| {
+                let discount = Discount::new(line.discount_percent, line.discount_amount);
+                discount.apply(line_net(line.quantity, line.price))
+            })
+            .collect();
+        let discount = Discount::new(invoice.discount_percent, invoice.discount_amount);
+        let lines = lines
+            .iter()
+            .zip(taxable_nets(&nets, discount))
+            .map(|(line, net)| (line.tax_id, line.tax, net))
+            .collect();
+        let region = region(Some(invoice.customer_id));
+        sales.push((1, invoice.date, region, lines));
+    }
+    for (_, lines, invoice) in &sale_credit_notes {
+        let lines = lines
+            .iter()
+            .map(|line| {
+                let net = line_net(line.quantity, line.price) - line.discount_amount as i64;
+                (line.tax_id, line.tax, net)
+            })
+            .collect();
+        let region = region(Some(invoice.customer_id));
+        sales.push((-1, invoice.date, region, lines));
+    }
+
+    let mut purchases: Vec<TaxableDocument> = Vec::new();
+    for (invoice, lines) in &purchase_invoices {
+        let nets: Vec<i64> = lines
+            .iter()
+            .map(|line| {
+                let discount = Discount::new(line.discount_percent, line.discount_amount);
+                discount.apply(line_net(line.quantity, line.price))
+            })
+            .collect();
+        let discount = Discount::new(invoice.discount_percent, invoice.discount_amount);
+        let lines = lines
+            .iter()
+            .zip(taxable_nets(&nets, discount))
+            .map(|(line, net)| (line.tax_id, line.tax, net))
+            .collect();
+        purchases.push((1, invoice.date, region(invoice.supplier_id), lines));
+    }
+    for (_, lines, invoice) in &purchase_credit_notes {
+        let lines = lines
+            .iter()
+            .map(|line| {
+                let net = line_net(line.quantity, line.price) - line.discount_amount as i64;
+                (line.tax_id, line.tax, net)
+            })
+            .collect();
+        purchases.push((-1, invoice.date, region(invoice.supplier_id), lines));
+    }
+
+    let mut taxes = HashMap::new();
+    let tax_dates = sales
+        .iter()
+        .chain(purchases.iter())
+        .flat_map(|(_, date, _, lines)| {
+            lines
+                .iter()
+                .filter_map(move |(tax_id, _, _)| tax_id.map(|tax_id| (tax_id, *date)))
+        });
+    for (tax_id, date) in tax_dates {
+        if taxes.contains_key(&(tax_id, date)) {
+            continue;
+        }
+
+        match state.taxes.resolve_at(tax_id, date).await {
+            Ok(Some(tax)) => taxes.insert((tax_id, date), tax),
+            Ok(None) => continue,
+            Err(_) => return Err(ApiErrors::InternalServerError.into()),
+        };
+    }
+
+    let sales = tax_groups(sales, &taxes);
+    let purchases = tax_groups(purchases, &taxes);
+    let output_tax: i64 = sales.iter().map(|group| group.tax).sum();
+    let input_tax: i64 = purchases.iter().map(|group| group.tax).sum();
+
+    Ok(VatReportModel {
+        from: from.to_string(),
+        to: to.to_string(),
+        sales,
+        purchases,
+        output_tax,
+        input_tax,
+        balance: output_tax - input_tax,
+    })
+}
+
+/// Sums the lines up per tax, rate and region, so a rate change within the
+/// period yields one group per rate. Lines with a tax group count towards each of
+/// its components. Like on the documents themselves, the tax is rounded once
+/// per tax and document, so the report matches the invoices.
+fn tax_groups(
+    documents: Vec<TaxableDocument>,
+    taxes: &HashMap<(i32, NaiveDate), ResolvedTax>,
+) -> Vec<VatReportLineModel> {
+    let mut groups: Vec<VatReportLineModel> = Vec::new();
+
+    for (sign, date, region, lines) in documents {
+        let mut bases: Vec<(usize, i64)> = Vec::new();
+        let components = lines.into_iter().flat_map(|(tax_id, tax, net)| {
+            // Lines without a known tax are grouped by their percentage.
+            let Some(resolved) = tax_id.and_then(|id| taxes.get(&(id, date))) else {
+                return vec![(None, format!("{tax}%"), tax as f64 / 100.0, None, net)];
+            };
+
+            let rates: Vec<(f64, bool)> = resolved
+                .components
+                .iter()
+                .map(|component| (component.tax.rate, component.compound))
+                .collect();
+            resolved
+                .components
+                .iter()
+                .zip(component_bases(net, &rates))
+                .map(|(component, base)| {
+                    let tax = &component.tax;
+                    let category = Some(tax.category.clone());
+                    (
+                        Some(tax.id),
+                        tax.name_short.clone(),
+                        tax.rate,
+                        category,
+                        base,
+                    )
+                })
+                .collect()
+        });
+        let region = tax_region_name(&region).to_string();
+        for (tax_id, label, rate, category, amount) in components {
+            let index = match groups.iter().position(|group| {
+                group.tax_id == tax_id
+                    && group.label == label
+                    && group.rate == rate
+                    && group.region == region
+            }) {
+                Some(index) => index,
+                None => {
+                    groups.push(VatReportLineModel {
+                        tax_id,
+                        label,
+                        rate,
+                        category,
+                        region: region.clone(),
+                        base: 0,
+                        tax: 0,
+                    });
+                    groups.len() - 1
+                }
+            };
+
+            match bases.iter_mut().find(|(group, _)| *group == index) {
+                Some((_, base)) => *base += amount,
+                None => bases.push((index, amount)),
+            }
+        }
+
+        for (index, base) in bases {
+            let group = &mut groups[index];
+            group.base += sign * base;
+            group.tax += sign * (base as f64 * group.rate).round() as i64;
+        }
+    }
+
+    groups.sort_by(|a, b| {
+        b.rate
+            .total_cmp(&a.rate)
+            .then_with(|| a.label.cmp(&b.label))
+            .then_with(|| a.region.cmp(&b.region))
+    });
+    groups
+}
+
+/// Maps the report onto the UStVA fields. Domestic sales at the standard
+/// and reduced rate go to 81 and 86, whose tax ELSTER computes itself,
+/// sales at other rates to 35 and 36. Of the tax-free sales, reverse charge
+/// ones go to 60, the others to 41 if the customer is in the EU and to 43
+/// if not. Reverse charge purchases from the EU go to 46 and 47, the others
+/// to 84 and 85, and their tax is deducted again in 67.
+fn ustva_fields(report: &VatReportModel) -> Vec<UstvaFieldModel> {
+    let mut standard = 0;
+    let mut reduced = 0;
+    let mut other_base = 0;
+    let mut other_tax = 0;
+    let mut intra_eu = 0;
+    let mut export = 0;
+    let mut reverse_charge = 0;
+    for group in &report.sales {
+        let region = tax_region_from_name(&group.region);
+        if group.category.as_deref() == Some(REVERSE_CHARGE) {
+            reverse_charge += group.base;
+        } else if (group.rate - 0.19).abs() < 0.00001 {
+            standard += group.base;
+        } else if (group.rate - 0.07).abs() < 0.00001 {
+            reduced += group.base;
+        } else if group.rate > 0.0 {
+            other_base += group.base;
+            other_tax += group.tax;
+        } else if region == Some(TaxRegion::Eu) {
+            intra_eu += group.base;
+        } else if region == Some(TaxRegion::NonEu) {
+            export += group.base;
+        }
+    }
+
+    let mut eu_services = 0;
+    let mut other_services = 0;
+    for group in &report.purchases {
+        if group.category.as_deref() != Some(REVERSE_CHARGE) {
+            continue;
+        }
+        match tax_region_from_name(&group.region) {
+            Some(TaxRegion::Eu) => eu_services += group.base,
+            _ => other_services += group.base,
+        }
+    }
+    let reverse_charge_tax = |base: i64| (base as f64 * STANDARD_RATE).round() as i64;
+    let eu_services_tax = reverse_charge_tax(eu_services);
+    let other_services_tax = reverse_charge_tax(other_services);
+
+    let mut fields = Vec::new();
+    if intra_eu != 0 {
+        fields.push(UstvaFieldModel {
+            field: "41".to_string(),
+            label: "Innergemeinschaftliche Lieferungen an Abnehmer mit USt-IdNr.".to_string(),
+            value: full_euros(intra_eu),
+        });
+    }
+    if export != 0 {
+        fields.push(UstvaFieldModel {
+            field: "43".to_string(),
+            label: "Weitere steuerfreie Umsätze mit Vorsteuerabzug".to_string(),
+            value: full_euros(export),
+        });
+    }
+    fields.push(UstvaFieldModel {
+        field: "81".to_string(),
+        label: "Steuerpflichtige Umsätze zum Steuersatz von 19 Prozent".to_string(),
+        value: full_euros(standard),
+    });
+    fields.push(UstvaFieldModel {
+        field: "86".to_string(),
+        label: "Steuerpflichtige Umsätze zum Steuersatz von 7 Prozent".to_string(),
+        value: full_euros(reduced),
+    });
+    if other_base != 0 {
+        fields.push(UstvaFieldModel {
+            field: "35".to_string(),
+            label: "Umsätze, die anderen Steuersätzen unterliegen".to_string(),
+            value: full_euros(other_base),
+        });
+        fields.push(UstvaFieldModel {
+            field: "36".to_string(),
+            label: "Steuer auf Umsätze, die anderen Steuersätzen unterliegen".to_string(),
+            value: euros(other_tax),
+        });
+    }
+    if eu_services != 0 {
+        fields.push(UstvaFieldModel {
+            field: "46".to_string(),
+            label: "Sonstige Leistungen eines im übrigen Gemeinschaftsgebiet ansässigen Unternehmers (§ 13b Abs. 1 UStG)".to_string(),
+            value: full_euros(eu_services),
+        });
+        fields.push(UstvaFieldModel {
+            field: "47".to_string(),
+            label: "Steuer auf sonstige Leistungen nach § 13b Abs. 1 UStG".to_string(),
+            value: euros(eu_services_tax),
+        });
+    }
+    if other_services != 0 {
+        fields.push(UstvaFieldModel {
+            field: "84".to_string(),
+            label: "Andere Leistungen (§ 13b Abs. 2 Nr. 1, 2, 4 bis 12 UStG)".to_string(),
+            value: full_euros(other_services),
+        });
+        fields.push(UstvaFieldModel {
+            field: "85".to_string(),
+            label: "Steuer auf andere Leistungen nach § 13b UStG".to_string(),
+            value: euros(other_services_tax),
+        });
+    }
+    if reverse_charge != 0 {
+        fields.push(UstvaFieldModel {
+            field: "60".to_string(),
+            label: "Steuerpflichtige Umsätze, für die der Leistungsempfänger die Steuer nach § 13b UStG schuldet".to_string(),
+            value: full_euros(reverse_charge),
+        });
+    }
+    fields.push(UstvaFieldModel {
+        field: "66".to_string(),
+        label: "Vorsteuerbeträge aus Rechnungen von anderen Unternehmern".to_string(),
+        value: euros(report.input_tax),
+    });
+    if eu_services != 0 || other_services != 0 {
+        fields.push(UstvaFieldModel {
+            field: "67".to_string(),
+            label: "Vorsteuerbeträge aus Leistungen im Sinne des § 13b UStG".to_string(),
+            value: euros(eu_services_tax + other_services_tax),
+        });
+    }
+    fields.push(UstvaFieldModel {
+        field: "83".to_string(),
+        label: "Verbleibende Umsatzsteuer-Vorauszahlung bzw. verbleibender Überschuss".to_string(),
+        value: euros(report.balance),
+    });
+    fields
+}
+
+/// Bases are declared in full euros, the cents are dropped.
+fn full_euros(cents: i64) -> String {
+    (cents / 100).to_string()
+}
+
+fn euros(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    let cents = cents.abs();
+    format!("{sign}{},{:02}", cents / 100, cents % 100)
+}
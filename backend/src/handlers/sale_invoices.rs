@@ -1,9 +1,88 @@
-use actix_web::{HttpResponse, delete, get, post};
+use actix_web::{HttpResponse, delete, get, post, web};
+use actix_web_validator::{Json, Query};
+use chrono::NaiveDate;
+use entity::sea_orm_active_enums::SaleInvoiceStatus;
+use sea_orm::DbErr;
+use serde_json::json;
 
-#[utoipa::path()]
+use crate::{
+    documents::{
+        PrintableDocument,
+        einvoice::{EInvoice, Syntax},
+    },
+    err::{self, ApiErrors},
+    handlers::taxes::resolve_taxes,
+    models::{
+        CreateSaleInvoiceInput, DocumentTotalsModel, EInvoiceFormat, ExportEInvoiceInput,
+        SaleInvoiceDetailModel, SaleInvoiceLineModel, SaleInvoiceModel, sale_invoice_status,
+    },
+    repositories::ERR_PERIOD_CLOSED,
+    state::AppState,
+    utils::totals::{Discount, DocumentTotals, TotalsLine, line_components},
+};
+
+#[utoipa::path(
+    tag = "Sale invoices",
+    summary = "Create a sale invoice",
+    description = "Create a sale invoice for an order. The reference is the next number of the gapless sale invoice sequence.",
+    responses(
+        (status = 200, description = "Returns the created sale invoice", body = SaleInvoiceModel),
+        (status = 400, description = err::MESSAGE_INVALID_DATE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_DATE,
+                "errorCode": err::CODE_INVALID_DATE,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INVALID_STATUS, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_STATUS,
+                "errorCode": err::CODE_INVALID_STATUS,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_NUMBER_SEQUENCE_PERIOD_CLOSED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_NUMBER_SEQUENCE_PERIOD_CLOSED,
+                "errorCode": err::CODE_NUMBER_SEQUENCE_PERIOD_CLOSED,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
 #[post("/api/sale-invoices")]
-async fn create() -> HttpResponse {
-    todo!();
+async fn create(payload: Json<CreateSaleInvoiceInput>, state: web::Data<AppState>) -> HttpResponse {
+    let payload = payload.into_inner();
+
+    let date = match NaiveDate::parse_from_str(&payload.date, "%Y-%m-%d") {
+        Ok(date) => date,
+        Err(_) => return ApiErrors::InvalidDate.into(),
+    };
+    let status = match sale_invoice_status(&payload.status) {
+        Some(status) => status,
+        None => return ApiErrors::InvalidStatus.into(),
+    };
+
+    let query = state.sale_invoices.create(
+        date,
+        status,
+        payload.order_id,
+        payload.customer_id,
+        Discount::new(payload.discount_percent, payload.discount_amount),
+    );
+    let entity = match query.await {
+        Ok(entity) => entity,
+        Err(DbErr::Custom(message)) if message == ERR_PERIOD_CLOSED => {
+            return ApiErrors::NumberSequencePeriodClosed.into();
+        }
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let model = SaleInvoiceModel::from(&entity);
+    HttpResponse::Ok().json(model)
 }
 
 #[utoipa::path()]
@@ -12,10 +91,69 @@ async fn list() -> HttpResponse {
     todo!();
 }
 
-#[utoipa::path()]
+#[utoipa::path(
+    tag = "Sale invoices",
+    summary = "Get a sale invoice",
+    description = "Get a sale invoice with its lines and the net, tax and gross amounts per line, per tax rate and in total. \
+        Taxes are applied with the rates of the invoice date and tax groups contribute one rate per component. \
+        The tax is rounded once per rate, so the line taxes can differ from the tax total by a few cents.",
+    responses(
+        (status = 200, description = "Returns the sale invoice with its lines and totals", body = SaleInvoiceDetailModel),
+        (status = 404, description = err::MESSAGE_SALE_INVOICE_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SALE_INVOICE_NOT_FOUND,
+                "errorCode": err::CODE_SALE_INVOICE_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
 #[get("/api/sale-invoices/{id}")]
-async fn get() -> HttpResponse {
-    todo!();
+async fn get(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let id = path.into_inner();
+
+    let query = state.sale_invoices.get(id);
+    let invoice = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::SaleInvoiceNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state.sale_invoice_lines.list_for_invoice(invoice.id);
+    let lines = match query.await {
+        Ok(lines) => lines,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let tax_ids = lines.iter().filter_map(|line| line.tax_id);
+    let taxes = match resolve_taxes(tax_ids, invoice.date, &state).await {
+        Ok(taxes) => taxes,
+        Err(response) => return response,
+    };
+
+    let totals = DocumentTotals::compute(
+        lines.iter().map(|line| {
+            let resolved = line.tax_id.and_then(|tax_id| taxes.get(&tax_id));
+            TotalsLine {
+                quantity: line.quantity,
+                price: line.price,
+                discount: Discount::new(line.discount_percent, line.discount_amount),
+                components: line_components(line.tax, resolved),
+            }
+        }),
+        Discount::new(invoice.discount_percent, invoice.discount_amount),
+    );
+
+    HttpResponse::Ok().json(SaleInvoiceDetailModel {
+        invoice: SaleInvoiceModel::from(&invoice),
+        lines: lines.iter().map(SaleInvoiceLineModel::from).collect(),
+        totals: DocumentTotalsModel::new(lines.iter().map(|line| line.id), totals),
+    })
 }
 
 #[utoipa::path()]
@@ -23,3 +161,194 @@ async fn get() -> HttpResponse {
 async fn delete() -> HttpResponse {
     todo!();
 }
+
+#[utoipa::path(
+    tag = "Sale invoices",
+    summary = "Render a sale invoice as PDF",
+    description = "Render the invoice with its lines, taxes, totals and the customer address on the company letterhead",
+    responses(
+        (status = 200, description = "Returns the rendered invoice", content_type = "application/pdf"),
+        (status = 404, description = err::MESSAGE_SALE_INVOICE_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SALE_INVOICE_NOT_FOUND,
+                "errorCode": err::CODE_SALE_INVOICE_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/sale-invoices/{id}/pdf")]
+async fn pdf(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let (invoice, document) = match load_document(path.into_inner(), &state).await {
+        Ok(result) => result,
+        Err(response) => return response,
+    };
+
+    let bytes = match document.render_pdf() {
+        Ok(bytes) => bytes,
+        Err(_) => return ApiErrors::DocumentRenderingFailed.into(),
+    };
+
+    HttpResponse::Ok()
+        .content_type("application/pdf")
+        .insert_header((
+            "Content-Disposition",
+            format!("inline; filename=\"{}.pdf\"", invoice.reference),
+        ))
+        .body(bytes)
+}
+
+#[utoipa::path(
+    tag = "Sale invoices",
+    summary = "Export a sale invoice as e-invoice",
+    description = "Export a posted invoice as XRechnung (UBL 2.1), as CII XML or as Factur-X hybrid PDF/A-3 with the CII XML embedded. \
+        The invoice is checked against the EN 16931 business rules first, the XRechnung rules are checked on top for that format.",
+    params(
+        ("format" = String, Query, description = "One of `xrechnung`, `cii` or `facturx`"),
+    ),
+    responses(
+        (status = 200, description = "Returns the e-invoice", content(
+            (String = "application/xml"),
+            (String = "application/pdf"),
+        )),
+        (status = 404, description = err::MESSAGE_SALE_INVOICE_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SALE_INVOICE_NOT_FOUND,
+                "errorCode": err::CODE_SALE_INVOICE_NOT_FOUND,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_SALE_INVOICE_NOT_POSTED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SALE_INVOICE_NOT_POSTED,
+                "errorCode": err::CODE_SALE_INVOICE_NOT_POSTED,
+            })),
+        )),
+        (status = 422, description = err::MESSAGE_EINVOICE_VALIDATION_FAILED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_EINVOICE_VALIDATION_FAILED,
+                "errorCode": err::CODE_EINVOICE_VALIDATION_FAILED,
+                "violations": [{
+                    "rule": "BR-DE-15",
+                    "message": "The element \"Buyer reference\" (BT-10) shall be provided",
+                }],
+            })),
+        )),
+        (status = 422, description = err::MESSAGE_EINVOICE_TAX_GROUP, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_EINVOICE_TAX_GROUP,
+                "errorCode": err::CODE_EINVOICE_TAX_GROUP,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/sale-invoices/{id}/einvoice")]
+async fn einvoice(
+    path: web::Path<i32>,
+    query: Query<ExportEInvoiceInput>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let (invoice, document) = match load_document(path.into_inner(), &state).await {
+        Ok(result) => result,
+        Err(response) => return response,
+    };
+    let format = query.format;
+
+    // Drafts and proforma invoices are no invoices in the legal sense, so
+    // they must never end up in the recipient's accounting.
+    match invoice.status {
+        SaleInvoiceStatus::Invoice | SaleInvoiceStatus::Finished => {}
+        SaleInvoiceStatus::Draft | SaleInvoiceStatus::Proforma => {
+            return ApiErrors::SaleInvoiceNotPosted.into();
+        }
+    }
+
+    // EN 16931 knows a single VAT category per line.
+    if document.lines.iter().any(|line| line.tax_components > 1) {
+        return ApiErrors::EinvoiceTaxGroup.into();
+    }
+
+    let query = state.sale_orders.get(invoice.order_id);
+    let order = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::SaleOrderNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let syntax = match format {
+        EInvoiceFormat::XRechnung => Syntax::XRechnung,
+        EInvoiceFormat::Cii | EInvoiceFormat::FacturX => Syntax::FacturX,
+    };
+
+    let einvoice = EInvoice::from_document(&document, &order.reference);
+    let violations = einvoice.validate(syntax);
+    if !violations.is_empty() {
+        let mut body = ApiErrors::EinvoiceValidationFailed.json();
+        body["violations"] = json!(violations);
+        return HttpResponse::UnprocessableEntity().json(body);
+    }
+
+    let xml = match einvoice.to_xml(syntax) {
+        Ok(xml) => xml,
+        Err(_) => return ApiErrors::DocumentRenderingFailed.into(),
+    };
+
+    let (content_type, extension, bytes) = match format {
+        EInvoiceFormat::XRechnung | EInvoiceFormat::Cii => ("application/xml", "xml", xml),
+        EInvoiceFormat::FacturX => match document.render_factur_x(&xml) {
+            Ok(bytes) => ("application/pdf", "pdf", bytes),
+            Err(_) => return ApiErrors::DocumentRenderingFailed.into(),
+        },
+    };
+
+    HttpResponse::Ok()
+        .content_type(content_type)
+        .insert_header((
+            "Content-Disposition",
+            format!("attachment; filename=\"{}.{extension}\"", invoice.reference),
+        ))
+        .body(bytes)
+}
+
+/// Loads an invoice with everything needed to print it.
+async fn load_document(
+    id: i32,
+    state: &AppState,
+) -> Result<(entity::sale_invoices::Model, PrintableDocument), HttpResponse> {
+    let query = state.sale_invoices.get(id);
+    let invoice = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return Err(ApiErrors::SaleInvoiceNotFound.into()),
+        Err(_) => return Err(ApiErrors::InternalServerError.into()),
+    };
+
+    let query = state.sale_invoice_lines.list_for_invoice(invoice.id);
+    let lines = match query.await {
+        Ok(lines) => lines,
+        Err(_) => return Err(ApiErrors::InternalServerError.into()),
+    };
+
+    let query = state.contacts.get(invoice.customer_id);
+    let customer = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return Err(ApiErrors::ContactNotFound.into()),
+        Err(_) => return Err(ApiErrors::InternalServerError.into()),
+    };
+
+    let tax_ids = lines.iter().filter_map(|line| line.tax_id);
+    let taxes = resolve_taxes(tax_ids, invoice.date, state).await?;
+
+    let document =
+        PrintableDocument::sale_invoice(&invoice, &lines, &customer, &taxes, &state.letterhead);
+    Ok((invoice, document))
+}
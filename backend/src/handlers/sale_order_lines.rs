@@ -1,9 +1,228 @@
-use actix_web::{HttpResponse, delete, get, post};
+use actix_web::{HttpResponse, delete, get, post, web};
+use actix_web_validator::Json;
+use entity::sea_orm_active_enums::{SaleOrderLineRoute, TaxDirection};
 
-#[utoipa::path()]
+use crate::{
+    err::{self, ApiErrors},
+    handlers::{
+        tax_rules::{line_taxes, tax_percent},
+        units::resolve_line_unit,
+    },
+    models::{CreateSaleOrderLineInput, SaleOrderLineModel, sale_order_line_route},
+    repositories::SaleOrderLineDraft,
+    state::AppState,
+    utils::{price_lists::select_price, totals::Discount},
+};
+
+#[utoipa::path(
+    tag = "Sale orders",
+    summary = "Add a line to a sale order",
+    description = "Add a line to a sale order. A line with a variant but without a price is priced from the price lists assigned to the customer: \
+        of the lists in the customer's currency and valid on the order date, the one starting last applies, and within it the highest quantity break the line reaches. \
+        Variants without such a price get their sale price. \
+        A line in another unit than its variant's, e.g. a box of 12 for a variant sold in pieces, is priced per its unit. \
+        A line with a variant but without a tax is taxed as the tax rules select for the customer, or else with the default tax of the product's category. \
+        A line with a tax is totalled with the tax's rate on the order date instead of its percentage. \
+        Lines of stockable variants can be drop-shipped, in which case the supplier delivers them straight to the customer.",
+    responses(
+        (status = 200, description = "Returns the created line", body = SaleOrderLineModel),
+        (status = 400, description = err::MESSAGE_DROP_SHIP_VARIANT_MISSING, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_DROP_SHIP_VARIANT_MISSING,
+                "errorCode": err::CODE_DROP_SHIP_VARIANT_MISSING,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INVALID_ROUTE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_ROUTE,
+                "errorCode": err::CODE_INVALID_ROUTE,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_MISSING_PRICE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_MISSING_PRICE,
+                "errorCode": err::CODE_MISSING_PRICE,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_MISSING_TAX, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_MISSING_TAX,
+                "errorCode": err::CODE_MISSING_TAX,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INCOMPATIBLE_UNIT, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INCOMPATIBLE_UNIT,
+                "errorCode": err::CODE_INCOMPATIBLE_UNIT,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_VARIANT_NOT_STOCKABLE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_VARIANT_NOT_STOCKABLE,
+                "errorCode": err::CODE_VARIANT_NOT_STOCKABLE,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_SALE_ORDER_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SALE_ORDER_NOT_FOUND,
+                "errorCode": err::CODE_SALE_ORDER_NOT_FOUND,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_TAX_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_TAX_NOT_FOUND,
+                "errorCode": err::CODE_TAX_NOT_FOUND,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_UNIT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_UNIT_NOT_FOUND,
+                "errorCode": err::CODE_UNIT_NOT_FOUND,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_VARIANT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_VARIANT_NOT_FOUND,
+                "errorCode": err::CODE_VARIANT_NOT_FOUND,
+            })),
+        )),
+        (status = 422, description = err::MESSAGE_NO_MATCHING_TAX_RULE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_NO_MATCHING_TAX_RULE,
+                "errorCode": err::CODE_NO_MATCHING_TAX_RULE,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
 #[post("/api/sale-order-lines")]
-async fn create() -> HttpResponse {
-    todo!();
+async fn create(
+    payload: Json<CreateSaleOrderLineInput>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let payload = payload.into_inner();
+
+    let Some(route) = sale_order_line_route(&payload.route) else {
+        return ApiErrors::InvalidRoute.into();
+    };
+
+    let query = state.sale_orders.get(payload.order_id);
+    let order = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::SaleOrderNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let variant = match payload.variant_id {
+        Some(variant_id) => match state.variants.get(variant_id).await {
+            Ok(Some(entity)) => Some(entity),
+            Ok(None) => return ApiErrors::VariantNotFound.into(),
+            Err(_) => return ApiErrors::InternalServerError.into(),
+        },
+        None => None,
+    };
+
+    // Drop-shipped lines are delivered by the move receiving their purchase
+    // order line, and only stockable variants are moved.
+    if route == SaleOrderLineRoute::DropShip {
+        let Some(variant) = &variant else {
+            return ApiErrors::DropShipVariantMissing.into();
+        };
+        match state.variants.stockable_ids(vec![variant.id]).await {
+            Ok(ids) if ids.contains(&variant.id) => {}
+            Ok(_) => return ApiErrors::VariantNotStockable.into(),
+            Err(_) => return ApiErrors::InternalServerError.into(),
+        }
+    }
+
+    let (unit_id, factor) = match resolve_line_unit(payload.unit_id, variant.as_ref(), &state).await
+    {
+        Ok(unit) => unit,
+        Err(response) => return response,
+    };
+
+    // Orders can't outlive their customer. Only lines priced or taxed for
+    // the customer need it.
+    let untaxed = payload.tax.is_none() && payload.tax_id.is_none();
+    let customer = match &variant {
+        Some(_) if payload.price.is_none() || untaxed => {
+            match state.contacts.get(order.customer_id).await {
+                Ok(Some(entity)) => Some(entity),
+                Ok(None) | Err(_) => return ApiErrors::InternalServerError.into(),
+            }
+        }
+        _ => None,
+    };
+
+    let (tax, tax_id) = match (payload.tax_id, payload.tax, &variant, &customer) {
+        (Some(tax_id), tax, _, _) => match state.taxes.get(tax_id).await {
+            Ok(Some(entity)) => (tax.unwrap_or_else(|| tax_percent(&entity)), Some(entity.id)),
+            Ok(None) => return ApiErrors::TaxNotFound.into(),
+            Err(_) => return ApiErrors::InternalServerError.into(),
+        },
+        (None, Some(tax), _, _) => (tax, None),
+        (None, None, Some(variant), Some(customer)) => {
+            let variants = [variant];
+            let query = line_taxes(TaxDirection::Sale, customer, &variants, order.date, &state);
+            match query.await {
+                Ok(taxes) => match taxes.get(&variant.id) {
+                    Some(tax) => (tax_percent(tax), Some(tax.id)),
+                    None => return ApiErrors::NoMatchingTaxRule.into(),
+                },
+                Err(response) => return response,
+            }
+        }
+        (None, None, _, _) => return ApiErrors::MissingTax.into(),
+    };
+
+    let price = match (payload.price, &variant, &customer) {
+        (Some(price), _, _) => price,
+        (None, Some(variant), Some(customer)) => {
+            let query = state
+                .price_lists
+                .list_prices_for_customer(customer.id, variant.id);
+            let prices = match query.await {
+                Ok(prices) => prices,
+                Err(_) => return ApiErrors::InternalServerError.into(),
+            };
+
+            // Prices are per unit of the variant.
+            let quantity = payload.quantity * factor;
+            let price = select_price(&prices, &customer.currency, order.date, quantity)
+                .unwrap_or(variant.sale_price);
+            (price as f64 * factor).round() as i32
+        }
+        (None, _, _) => return ApiErrors::MissingPrice.into(),
+    };
+
+    let description = match &variant {
+        Some(variant) if payload.description.trim().is_empty() => variant.name.clone(),
+        _ => payload.description,
+    };
+
+    let line = SaleOrderLineDraft {
+        description,
+        quantity: payload.quantity,
+        price,
+        tax,
+        tax_id,
+        variant_id: variant.map(|variant| variant.id),
+        discount: Discount::new(payload.discount_percent, payload.discount_amount),
+        unit_id,
+        route,
+    };
+    let entity = match state.sale_order_lines.create(order.id, line).await {
+        Ok(entity) => entity,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let model = SaleOrderLineModel::from(&entity);
+    HttpResponse::Ok().json(model)
 }
 
 #[utoipa::path()]
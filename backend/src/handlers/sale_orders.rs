@@ -1,9 +1,97 @@
-use actix_web::{HttpResponse, delete, get, post};
+use std::collections::BTreeMap;
 
-#[utoipa::path()]
+use actix_web::{HttpResponse, delete, get, post, web};
+use actix_web_validator::{Json, Query};
+use chrono::{NaiveDate, Utc};
+use entity::sea_orm_active_enums::{SaleOrderLineRoute, SaleOrderStatus, StockKind};
+use sea_orm::DbErr;
+
+use crate::{
+    documents::PrintableDocument,
+    err::{self, ApiErrors},
+    handlers::{
+        purchase_order_lines::catalog_description,
+        stock_moves::{OrderLine, attach_lots, line_moves, lot_error},
+        taxes::resolve_taxes,
+        units::resolve_line_unit,
+    },
+    models::{
+        ConfirmSaleOrderInput, CreateSaleOrderInput, DocumentTotalsModel, DropShipmentModel,
+        OrderLotsInput, PurchaseOrderModel, SaleOrderConfirmationModel, SaleOrderDetailModel,
+        SaleOrderLineModel, SaleOrderModel, StockShortageModel, sale_order_status,
+    },
+    repositories::{
+        CostAccounts, DropShipDraft, DropShipLineDraft, ERR_INSUFFICIENT_STOCK, ERR_PERIOD_CLOSED,
+        ERR_SALE_ORDER_CANCELLED, ERR_SALE_ORDER_DELIVERED, ERR_SALE_ORDER_DROP_SHIPPED,
+        ERR_SALE_ORDER_NOT_DRAFT, LineMoveDraft, PurchaseOrderLineDraft, ReservationDraft,
+    },
+    state::AppState,
+    utils::totals::{Discount, DocumentTotals, TotalsLine, line_components},
+};
+
+#[utoipa::path(
+    tag = "Sale orders",
+    summary = "Create a sale order",
+    description = "Create a sale order for a customer. The reference is the next number of the sale order sequence.",
+    responses(
+        (status = 200, description = "Returns the created sale order", body = SaleOrderModel),
+        (status = 400, description = err::MESSAGE_INVALID_DATE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_DATE,
+                "errorCode": err::CODE_INVALID_DATE,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INVALID_STATUS, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_STATUS,
+                "errorCode": err::CODE_INVALID_STATUS,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_NUMBER_SEQUENCE_PERIOD_CLOSED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_NUMBER_SEQUENCE_PERIOD_CLOSED,
+                "errorCode": err::CODE_NUMBER_SEQUENCE_PERIOD_CLOSED,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
 #[post("/api/sale-orders")]
-async fn create() -> HttpResponse {
-    todo!();
+async fn create(payload: Json<CreateSaleOrderInput>, state: web::Data<AppState>) -> HttpResponse {
+    let payload = payload.into_inner();
+
+    let date = match NaiveDate::parse_from_str(&payload.date, "%Y-%m-%d") {
+        Ok(date) => date,
+        Err(_) => return ApiErrors::InvalidDate.into(),
+    };
+    let status = match sale_order_status(&payload.status) {
+        Some(status) => status,
+        None => return ApiErrors::InvalidStatus.into(),
+    };
+
+    let query = state.sale_orders.create(
+        date,
+        status,
+        payload.customer_id,
+        payload.source_stock_id,
+        payload.target_stock_id,
+        Discount::new(payload.discount_percent, payload.discount_amount),
+    );
+    let entity = match query.await {
+        Ok(entity) => entity,
+        Err(DbErr::Custom(message)) if message == ERR_PERIOD_CLOSED => {
+            return ApiErrors::NumberSequencePeriodClosed.into();
+        }
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let model = SaleOrderModel::from(&entity);
+    HttpResponse::Ok().json(model)
 }
 
 #[utoipa::path()]
@@ -12,10 +100,509 @@ async fn list() -> HttpResponse {
     todo!();
 }
 
-#[utoipa::path()]
+#[utoipa::path(
+    tag = "Sale orders",
+    summary = "Get a sale order",
+    description = "Get a sale order with its lines and the net, tax and gross amounts per line, per tax rate and in total. \
+        Lines with a tax are taxed at its rate on the order date. \
+        The tax is rounded once per rate, so the line taxes can differ from the tax total by a few cents. \
+        Drop-shipped lines come with the purchase orders their supplier delivers them by.",
+    responses(
+        (status = 200, description = "Returns the sale order with its lines and totals", body = SaleOrderDetailModel),
+        (status = 404, description = err::MESSAGE_SALE_ORDER_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SALE_ORDER_NOT_FOUND,
+                "errorCode": err::CODE_SALE_ORDER_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
 #[get("/api/sale-orders/{id}")]
-async fn get() -> HttpResponse {
-    todo!();
+async fn get(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let id = path.into_inner();
+
+    let query = state.sale_orders.get(id);
+    let order = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::SaleOrderNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state.sale_order_lines.list_for_order(order.id);
+    let lines = match query.await {
+        Ok(lines) => lines,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    // Lines without a tax only know its percentage.
+    let tax_ids = lines.iter().filter_map(|line| line.tax_id);
+    let taxes = match resolve_taxes(tax_ids, order.date, &state).await {
+        Ok(taxes) => taxes,
+        Err(response) => return response,
+    };
+
+    let totals = DocumentTotals::compute(
+        lines.iter().map(|line| {
+            let resolved = line.tax_id.and_then(|tax_id| taxes.get(&tax_id));
+            TotalsLine {
+                quantity: line.quantity,
+                price: line.price,
+                discount: Discount::new(line.discount_percent, line.discount_amount),
+                components: line_components(line.tax, resolved),
+            }
+        }),
+        Discount::new(order.discount_percent, order.discount_amount),
+    );
+
+    let drop_shipped: Vec<i32> = lines
+        .iter()
+        .filter(|line| line.route == SaleOrderLineRoute::DropShip)
+        .map(|line| line.id)
+        .collect();
+    let mut drop_shipments = Vec::new();
+    if !drop_shipped.is_empty() {
+        let query = state.purchase_order_lines.list_for_sale_lines(drop_shipped);
+        let purchase_lines = match query.await {
+            Ok(lines) => lines,
+            Err(_) => return ApiErrors::InternalServerError.into(),
+        };
+        for (purchase_line, purchase_order) in purchase_lines {
+            let Some(line_id) = purchase_line.sale_line_id else {
+                continue;
+            };
+            drop_shipments.push(DropShipmentModel {
+                line_id,
+                purchase_order_line_id: purchase_line.id,
+                purchase_order: PurchaseOrderModel::from(&purchase_order),
+            });
+        }
+    }
+
+    HttpResponse::Ok().json(SaleOrderDetailModel {
+        order: SaleOrderModel::from(&order),
+        lines: lines.iter().map(SaleOrderLineModel::from).collect(),
+        totals: DocumentTotalsModel::new(lines.iter().map(|line| line.id), totals),
+        drop_shipments,
+    })
+}
+
+#[utoipa::path(
+    tag = "Sale orders",
+    summary = "Confirm a sale order",
+    description = "Turn a draft or quotation into an order and reserve the quantities of its lines with a stockable variant in the order's source stock. \
+        The order is rejected if it asks for more than is on hand in the stock and the locations below it minus what other orders have reserved there, unless `allowOversell` is set. \
+        Then it is confirmed anyway and the shortages are returned as a warning. \
+        Drop-shipped lines aren't reserved. Instead a purchase order is placed per supplier, delivering from the supplier's stock, or else the supplier location, straight to the customer's stock. \
+        Each line goes to the variant's preferred supplier, or else the cheapest one, at the supplier's price. \
+        Once the purchase order is received, its lines are delivered too.",
+    params(
+        ("allowOversell" = Option<bool>, Query, description = "Confirm the order even if the stock doesn't cover it"),
+    ),
+    responses(
+        (status = 200, description = "Returns the confirmed sale order with its shortages and purchase orders", body = SaleOrderConfirmationModel),
+        (status = 400, description = err::MESSAGE_INCOMPATIBLE_UNIT, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INCOMPATIBLE_UNIT,
+                "errorCode": err::CODE_INCOMPATIBLE_UNIT,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_SALE_ORDER_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SALE_ORDER_NOT_FOUND,
+                "errorCode": err::CODE_SALE_ORDER_NOT_FOUND,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_SALE_ORDER_ALREADY_CONFIRMED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SALE_ORDER_ALREADY_CONFIRMED,
+                "errorCode": err::CODE_SALE_ORDER_ALREADY_CONFIRMED,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_SALE_ORDER_CANCELLED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SALE_ORDER_CANCELLED,
+                "errorCode": err::CODE_SALE_ORDER_CANCELLED,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_INSUFFICIENT_STOCK, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INSUFFICIENT_STOCK,
+                "errorCode": err::CODE_INSUFFICIENT_STOCK,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_CUSTOMER_STOCK_MISSING, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_CUSTOMER_STOCK_MISSING,
+                "errorCode": err::CODE_CUSTOMER_STOCK_MISSING,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_DROP_SHIP_SUPPLIER_MISSING, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_DROP_SHIP_SUPPLIER_MISSING,
+                "errorCode": err::CODE_DROP_SHIP_SUPPLIER_MISSING,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_NUMBER_SEQUENCE_PERIOD_CLOSED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_NUMBER_SEQUENCE_PERIOD_CLOSED,
+                "errorCode": err::CODE_NUMBER_SEQUENCE_PERIOD_CLOSED,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_SUPPLIER_LOCATION_MISSING, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SUPPLIER_LOCATION_MISSING,
+                "errorCode": err::CODE_SUPPLIER_LOCATION_MISSING,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/sale-orders/{id}/confirm")]
+async fn confirm(
+    path: web::Path<i32>,
+    query: Query<ConfirmSaleOrderInput>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let order = match state.sale_orders.get(path.into_inner()).await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::SaleOrderNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+    match order.status {
+        SaleOrderStatus::Draft | SaleOrderStatus::Quotation => {}
+        SaleOrderStatus::Order => return ApiErrors::SaleOrderAlreadyConfirmed.into(),
+        SaleOrderStatus::Finished => return ApiErrors::SaleOrderAlreadyDelivered.into(),
+        SaleOrderStatus::Cancelled => return ApiErrors::SaleOrderCancelled.into(),
+    }
+
+    let lines = match state.sale_order_lines.list_for_order(order.id).await {
+        Ok(lines) => lines,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+    let drafts = match pending_moves(&order, &lines, &state).await {
+        Ok(drafts) => drafts,
+        Err(response) => return response,
+    };
+
+    let reservations = drafts
+        .iter()
+        .map(|draft| ReservationDraft {
+            line_id: draft.line_id,
+            variant_id: draft.variant_id,
+            stock_id: order.source_stock_id,
+            quantity: draft.quantity,
+        })
+        .collect();
+    let drop_ships = match drop_ships(&order, &lines, &state).await {
+        Ok(drop_ships) => drop_ships,
+        Err(response) => return response,
+    };
+
+    let confirmation = state.sale_orders.confirm(
+        order.id,
+        Utc::now().date_naive(),
+        reservations,
+        drop_ships,
+        query.allow_oversell,
+    );
+    let confirmation = match confirmation.await {
+        Ok(confirmation) => confirmation,
+        // Confirmed or cancelled since it was read.
+        Err(DbErr::Custom(message)) if message == ERR_SALE_ORDER_NOT_DRAFT => {
+            return ApiErrors::SaleOrderAlreadyConfirmed.into();
+        }
+        Err(DbErr::Custom(message)) if message == ERR_INSUFFICIENT_STOCK => {
+            return ApiErrors::InsufficientStock.into();
+        }
+        Err(DbErr::Custom(message)) if message == ERR_PERIOD_CLOSED => {
+            return ApiErrors::NumberSequencePeriodClosed.into();
+        }
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    HttpResponse::Ok().json(SaleOrderConfirmationModel {
+        order: SaleOrderModel::from(&confirmation.order),
+        shortages: confirmation
+            .shortages
+            .iter()
+            .map(StockShortageModel::from)
+            .collect(),
+        purchase_orders: confirmation
+            .purchase_orders
+            .iter()
+            .map(PurchaseOrderModel::from)
+            .collect(),
+    })
+}
+
+#[utoipa::path(
+    tag = "Sale orders",
+    summary = "Cancel a sale order",
+    description = "Cancel a sale order that hasn't been delivered and release what it has reserved. \
+        Orders with drop-shipped lines can't be cancelled once their purchase orders are placed.",
+    responses(
+        (status = 200, description = "Returns the cancelled sale order", body = SaleOrderModel),
+        (status = 404, description = err::MESSAGE_SALE_ORDER_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SALE_ORDER_NOT_FOUND,
+                "errorCode": err::CODE_SALE_ORDER_NOT_FOUND,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_SALE_ORDER_ALREADY_DELIVERED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SALE_ORDER_ALREADY_DELIVERED,
+                "errorCode": err::CODE_SALE_ORDER_ALREADY_DELIVERED,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_SALE_ORDER_DROP_SHIPPED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SALE_ORDER_DROP_SHIPPED,
+                "errorCode": err::CODE_SALE_ORDER_DROP_SHIPPED,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/sale-orders/{id}/cancel")]
+async fn cancel(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let order = match state.sale_orders.get(path.into_inner()).await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::SaleOrderNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+    if order.status == SaleOrderStatus::Finished {
+        return ApiErrors::SaleOrderAlreadyDelivered.into();
+    }
+
+    let order = match state.sale_orders.cancel(order).await {
+        Ok(entity) => entity,
+        Err(DbErr::Custom(message)) if message == ERR_SALE_ORDER_DROP_SHIPPED => {
+            return ApiErrors::SaleOrderDropShipped.into();
+        }
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let model = SaleOrderModel::from(&order);
+    HttpResponse::Ok().json(model)
+}
+
+#[utoipa::path(
+    tag = "Sale orders",
+    summary = "Deliver a sale order",
+    description = "Move the goods of the order lines with a stockable variant from the order's source stock to its target stock and finish the order. \
+        Drop-shipped lines are delivered by their supplier, so an order with such lines has to be confirmed first and is only finished once its purchase orders are received. \
+        Quantities are converted into the variant's unit. The goods are valued by the costing method of their product, first in first out or at the average cost of the stock. \
+        Their cost is debited to the expense account and credited to the inventory account of the product's category or its nearest ancestor that sets them. \
+        What the order has reserved is released. Lines of lot or serial tracked variants are delivered in the lots given for them, which the source stock must hold.",
+    request_body(content = Option<OrderLotsInput>),
+    responses(
+        (status = 200, description = "Returns the delivered sale order", body = SaleOrderModel),
+        (status = 400, description = err::MESSAGE_DUPLICATE_LOT, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_DUPLICATE_LOT,
+                "errorCode": err::CODE_DUPLICATE_LOT,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INCOMPATIBLE_UNIT, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INCOMPATIBLE_UNIT,
+                "errorCode": err::CODE_INCOMPATIBLE_UNIT,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INVALID_DATE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_DATE,
+                "errorCode": err::CODE_INVALID_DATE,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_LOT_LINE_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOT_LINE_NOT_FOUND,
+                "errorCode": err::CODE_LOT_LINE_NOT_FOUND,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_LOT_QUANTITY_MISMATCH, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOT_QUANTITY_MISMATCH,
+                "errorCode": err::CODE_LOT_QUANTITY_MISMATCH,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_LOTS_MISSING, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOTS_MISSING,
+                "errorCode": err::CODE_LOTS_MISSING,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_LOTS_NOT_TRACKED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOTS_NOT_TRACKED,
+                "errorCode": err::CODE_LOTS_NOT_TRACKED,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_SERIAL_QUANTITY_NOT_ONE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SERIAL_QUANTITY_NOT_ONE,
+                "errorCode": err::CODE_SERIAL_QUANTITY_NOT_ONE,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_LOT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOT_NOT_FOUND,
+                "errorCode": err::CODE_LOT_NOT_FOUND,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_SALE_ORDER_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SALE_ORDER_NOT_FOUND,
+                "errorCode": err::CODE_SALE_ORDER_NOT_FOUND,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_LOT_NOT_IN_STOCK, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOT_NOT_IN_STOCK,
+                "errorCode": err::CODE_LOT_NOT_IN_STOCK,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_SALE_ORDER_ALREADY_DELIVERED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SALE_ORDER_ALREADY_DELIVERED,
+                "errorCode": err::CODE_SALE_ORDER_ALREADY_DELIVERED,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_SALE_ORDER_CANCELLED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SALE_ORDER_CANCELLED,
+                "errorCode": err::CODE_SALE_ORDER_CANCELLED,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_SALE_ORDER_NOT_CONFIRMED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SALE_ORDER_NOT_CONFIRMED,
+                "errorCode": err::CODE_SALE_ORDER_NOT_CONFIRMED,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_POSTING_ACCOUNT_MISSING, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_POSTING_ACCOUNT_MISSING,
+                "errorCode": err::CODE_POSTING_ACCOUNT_MISSING,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/sale-orders/{id}/deliver")]
+async fn deliver(
+    path: web::Path<i32>,
+    payload: Option<Json<OrderLotsInput>>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let query = state.sale_orders.get(path.into_inner());
+    let order = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::SaleOrderNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+    match order.status {
+        SaleOrderStatus::Finished => return ApiErrors::SaleOrderAlreadyDelivered.into(),
+        SaleOrderStatus::Cancelled => return ApiErrors::SaleOrderCancelled.into(),
+        _ => {}
+    }
+
+    let query = state.sale_order_lines.list_for_order(order.id);
+    let lines = match query.await {
+        Ok(lines) => lines,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+    // Drop-shipped lines are ordered from their supplier on confirmation.
+    let unconfirmed = matches!(
+        order.status,
+        SaleOrderStatus::Draft | SaleOrderStatus::Quotation
+    );
+    if unconfirmed
+        && lines
+            .iter()
+            .any(|line| line.route == SaleOrderLineRoute::DropShip)
+    {
+        return ApiErrors::SaleOrderNotConfirmed.into();
+    }
+
+    let mut drafts = match pending_moves(&order, &lines, &state).await {
+        Ok(drafts) => drafts,
+        Err(response) => return response,
+    };
+    let lots = payload.map(|payload| payload.into_inner().lots);
+    if let Err(response) = attach_lots(&mut drafts, lots.unwrap_or_default(), &state).await {
+        return response;
+    }
+
+    let variant_ids = drafts.iter().map(|draft| draft.variant_id).collect();
+    let defaults = match state
+        .product_categories
+        .defaults_for_variants(variant_ids)
+        .await
+    {
+        Ok(defaults) => defaults,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    // The goods leave at the cost they are valued at in stock, not at the
+    // price of the line.
+    let mut costed = Vec::with_capacity(drafts.len());
+    for draft in drafts {
+        let category = defaults.get(&draft.variant_id);
+        let accounts = category.and_then(|category| {
+            Some(CostAccounts {
+                expense_account_id: category.expense_account_id?,
+                inventory_account_id: category.inventory_account_id?,
+            })
+        });
+        let Some(accounts) = accounts else {
+            return ApiErrors::PostingAccountMissing.into();
+        };
+        costed.push(LineMoveDraft {
+            unit_cost: None,
+            cost_accounts: Some(accounts),
+            ..draft
+        });
+    }
+
+    let query = state
+        .sale_orders
+        .deliver(order, Utc::now().date_naive(), costed);
+    let order = match query.await {
+        Ok(entity) => entity,
+        Err(DbErr::Custom(message)) if message == ERR_SALE_ORDER_DELIVERED => {
+            return ApiErrors::SaleOrderAlreadyDelivered.into();
+        }
+        Err(DbErr::Custom(message)) if message == ERR_SALE_ORDER_CANCELLED => {
+            return ApiErrors::SaleOrderCancelled.into();
+        }
+        Err(error) => return lot_error(error).into(),
+    };
+
+    let model = SaleOrderModel::from(&order);
+    HttpResponse::Ok().json(model)
 }
 
 #[utoipa::path()]
@@ -23,3 +610,169 @@ async fn get() -> HttpResponse {
 async fn delete() -> HttpResponse {
     todo!();
 }
+
+#[utoipa::path(
+    tag = "Sale orders",
+    summary = "Render a sale order as PDF",
+    description = "Render a quotation or order confirmation with its lines, taxes, totals and the customer address on the company letterhead",
+    responses(
+        (status = 200, description = "Returns the rendered quotation or order confirmation", content_type = "application/pdf"),
+        (status = 404, description = err::MESSAGE_SALE_ORDER_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SALE_ORDER_NOT_FOUND,
+                "errorCode": err::CODE_SALE_ORDER_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/sale-orders/{id}/pdf")]
+async fn pdf(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let id = path.into_inner();
+
+    let query = state.sale_orders.get(id);
+    let order = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::SaleOrderNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state.sale_order_lines.list_for_order(order.id);
+    let lines = match query.await {
+        Ok(lines) => lines,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state.contacts.get(order.customer_id);
+    let customer = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::ContactNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let document = PrintableDocument::sale_order(&order, &lines, &customer, &state.letterhead);
+    let bytes = match document.render_pdf() {
+        Ok(bytes) => bytes,
+        Err(_) => return ApiErrors::DocumentRenderingFailed.into(),
+    };
+
+    HttpResponse::Ok()
+        .content_type("application/pdf")
+        .insert_header((
+            "Content-Disposition",
+            format!("inline; filename=\"{}.pdf\"", order.reference),
+        ))
+        .body(bytes)
+}
+
+/// Drafts the moves of the order's lines whose goods haven't left the stock
+/// yet. Drop-shipped lines are left to their supplier.
+async fn pending_moves(
+    order: &entity::sale_orders::Model,
+    lines: &[entity::sale_order_lines::Model],
+    state: &AppState,
+) -> Result<Vec<LineMoveDraft>, HttpResponse> {
+    let lines: Vec<OrderLine> = lines
+        .iter()
+        .filter(|line| line.route == SaleOrderLineRoute::Stock)
+        .map(OrderLine::from)
+        .collect();
+
+    let discount = Discount::new(order.discount_percent, order.discount_amount);
+    line_moves(&lines, discount, state).await
+}
+
+/// Drafts a purchase order per supplier for the order's drop-shipped lines
+/// that haven't been delivered, from the supplier's stock, or else the
+/// supplier location, to the customer's stock.
+async fn drop_ships(
+    order: &entity::sale_orders::Model,
+    lines: &[entity::sale_order_lines::Model],
+    state: &AppState,
+) -> Result<Vec<DropShipDraft>, HttpResponse> {
+    let lines: Vec<&entity::sale_order_lines::Model> = lines
+        .iter()
+        .filter(|line| line.route == SaleOrderLineRoute::DropShip && line.move_id.is_none())
+        .collect();
+    if lines.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let customer_stock = match state.stocks.find_for_contact(order.customer_id).await {
+        Ok(Some(stock)) => stock,
+        Ok(None) => return Err(ApiErrors::CustomerStockMissing.into()),
+        Err(_) => return Err(ApiErrors::InternalServerError.into()),
+    };
+
+    let mut suppliers: BTreeMap<i32, Vec<DropShipLineDraft>> = BTreeMap::new();
+    for line in lines {
+        let Some(variant_id) = line.variant_id else {
+            return Err(ApiErrors::DropShipVariantMissing.into());
+        };
+        // Lines can't outlive their variant.
+        let variant = match state.variants.get(variant_id).await {
+            Ok(Some(entity)) => entity,
+            Ok(None) | Err(_) => return Err(ApiErrors::InternalServerError.into()),
+        };
+
+        // The preferred entry comes first, then the cheapest.
+        let entry = match state.supplier_prices.list_for_variant(variant.id).await {
+            Ok(entries) => entries.into_iter().next(),
+            Err(_) => return Err(ApiErrors::InternalServerError.into()),
+        };
+        let Some(entry) = entry else {
+            return Err(ApiErrors::DropShipSupplierMissing.into());
+        };
+
+        // Catalog prices are per unit of the variant.
+        let (_, factor) = resolve_line_unit(line.unit_id, Some(&variant), state).await?;
+        suppliers
+            .entry(entry.supplier_id)
+            .or_default()
+            .push(DropShipLineDraft {
+                sale_line_id: line.id,
+                line: PurchaseOrderLineDraft {
+                    description: catalog_description(&entry, &variant),
+                    quantity: line.quantity,
+                    price: (entry.price as f64 * factor).round() as i32,
+                    // The goods are taxed at the rate the customer is
+                    // charged.
+                    tax: line.tax,
+                    tax_id: line.tax_id,
+                    variant_id: Some(variant.id),
+                    discount: Discount::default(),
+                    unit_id: line.unit_id,
+                },
+            });
+    }
+
+    let mut drafts = Vec::with_capacity(suppliers.len());
+    for (supplier_id, lines) in suppliers {
+        let source_stock_id = match state.stocks.find_for_contact(supplier_id).await {
+            Ok(Some(stock)) => stock.id,
+            Ok(None) => match state.stocks.list_virtual().await {
+                Ok(stocks) => match stocks
+                    .into_iter()
+                    .find(|stock| stock.kind == StockKind::Supplier)
+                {
+                    Some(stock) => stock.id,
+                    None => return Err(ApiErrors::SupplierLocationMissing.into()),
+                },
+                Err(_) => return Err(ApiErrors::InternalServerError.into()),
+            },
+            Err(_) => return Err(ApiErrors::InternalServerError.into()),
+        };
+        drafts.push(DropShipDraft {
+            supplier_id,
+            source_stock_id,
+            target_stock_id: customer_stock.id,
+            lines,
+        });
+    }
+    Ok(drafts)
+}
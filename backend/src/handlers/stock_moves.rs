@@ -1,9 +1,209 @@
-use actix_web::{HttpResponse, delete, get, post};
+use actix_web::{HttpResponse, delete, get, post, web};
+use actix_web_validator::Json;
+use chrono::NaiveDate;
+use entity::sea_orm_active_enums::Tracking;
+use sea_orm::DbErr;
 
-#[utoipa::path()]
+use crate::{
+    err::{self, ApiErrors},
+    handlers::units::resolve_line_unit,
+    models::{CreateStockMoveInput, LineLotInput, StockMoveModel},
+    repositories::{
+        ERR_LOT_NOT_FOUND, ERR_LOT_NOT_IN_STOCK, ERR_SERIAL_IN_STOCK, LineMoveDraft, LotDraft,
+    },
+    state::AppState,
+    utils::{
+        lots::{LotProblem, check_lots},
+        totals::{Discount, line_net, taxable_nets},
+    },
+};
+
+/// The fields of sale and purchase order lines their goods are moved by.
+pub(super) struct OrderLine {
+    pub(super) id: i32,
+    pub(super) quantity: f64,
+    pub(super) price: i32,
+    pub(super) variant_id: Option<i32>,
+    pub(super) unit_id: Option<i32>,
+    pub(super) move_id: Option<i32>,
+    pub(super) discount: Discount,
+}
+
+impl From<&entity::sale_order_lines::Model> for OrderLine {
+    fn from(entity: &entity::sale_order_lines::Model) -> Self {
+        Self {
+            id: entity.id,
+            quantity: entity.quantity,
+            price: entity.price,
+            variant_id: entity.variant_id,
+            unit_id: entity.unit_id,
+            move_id: entity.move_id,
+            discount: Discount::new(entity.discount_percent, entity.discount_amount),
+        }
+    }
+}
+
+impl From<&entity::purchase_order_lines::Model> for OrderLine {
+    fn from(entity: &entity::purchase_order_lines::Model) -> Self {
+        Self {
+            id: entity.id,
+            quantity: entity.quantity,
+            price: entity.price,
+            variant_id: entity.variant_id,
+            unit_id: entity.unit_id,
+            move_id: entity.move_id,
+            discount: Discount::new(entity.discount_percent, entity.discount_amount),
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Stock moves",
+    summary = "Move goods between stocks",
+    description = "Move a quantity of a variant from one stock to another. Only variants of stockable products are kept in stock. \
+        A quantity in another unit, e.g. boxes of 12 of a variant stocked in pieces, is converted into the variant's unit, which stock is kept in. \
+        Variants of lot or serial tracked products are moved in lots, whose quantities are in the variant's unit and add up to the moved quantity. \
+        Lots coming from outside the own stocks are created on their first receipt.",
+    responses(
+        (status = 200, description = "Returns the created move", body = StockMoveModel),
+        (status = 400, description = err::MESSAGE_DUPLICATE_LOT, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_DUPLICATE_LOT,
+                "errorCode": err::CODE_DUPLICATE_LOT,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INCOMPATIBLE_UNIT, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INCOMPATIBLE_UNIT,
+                "errorCode": err::CODE_INCOMPATIBLE_UNIT,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INVALID_DATE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_DATE,
+                "errorCode": err::CODE_INVALID_DATE,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_LOT_QUANTITY_MISMATCH, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOT_QUANTITY_MISMATCH,
+                "errorCode": err::CODE_LOT_QUANTITY_MISMATCH,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_LOTS_MISSING, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOTS_MISSING,
+                "errorCode": err::CODE_LOTS_MISSING,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_LOTS_NOT_TRACKED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOTS_NOT_TRACKED,
+                "errorCode": err::CODE_LOTS_NOT_TRACKED,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_SERIAL_QUANTITY_NOT_ONE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SERIAL_QUANTITY_NOT_ONE,
+                "errorCode": err::CODE_SERIAL_QUANTITY_NOT_ONE,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_VARIANT_NOT_STOCKABLE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_VARIANT_NOT_STOCKABLE,
+                "errorCode": err::CODE_VARIANT_NOT_STOCKABLE,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_LOT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOT_NOT_FOUND,
+                "errorCode": err::CODE_LOT_NOT_FOUND,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_UNIT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_UNIT_NOT_FOUND,
+                "errorCode": err::CODE_UNIT_NOT_FOUND,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_VARIANT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_VARIANT_NOT_FOUND,
+                "errorCode": err::CODE_VARIANT_NOT_FOUND,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_LOT_NOT_IN_STOCK, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOT_NOT_IN_STOCK,
+                "errorCode": err::CODE_LOT_NOT_IN_STOCK,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_SERIAL_IN_STOCK, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SERIAL_IN_STOCK,
+                "errorCode": err::CODE_SERIAL_IN_STOCK,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
 #[post("/api/stock-moves")]
-async fn create() -> HttpResponse {
-    todo!();
+async fn create(payload: Json<CreateStockMoveInput>, state: web::Data<AppState>) -> HttpResponse {
+    let payload = payload.into_inner();
+
+    let query = state.variants.get(payload.variant_id);
+    let variant = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::VariantNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    match state.variants.stockable_ids(vec![variant.id]).await {
+        Ok(ids) if ids.contains(&variant.id) => {}
+        Ok(_) => return ApiErrors::VariantNotStockable.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    }
+
+    let factor = match resolve_line_unit(payload.unit_id, Some(&variant), &state).await {
+        Ok((_, factor)) => factor,
+        Err(response) => return response,
+    };
+    let quantity = payload.quantity * factor;
+
+    let tracking = match state.variants.tracking(vec![variant.id]).await {
+        Ok(tracking) => tracking.get(&variant.id).cloned().unwrap_or(Tracking::None),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+    let mut lots = Vec::with_capacity(payload.lots.len());
+    for lot in payload.lots {
+        match lot_draft(lot.number, lot.quantity, lot.expiry_date.as_deref()) {
+            Ok(draft) => lots.push(draft),
+            Err(response) => return response,
+        }
+    }
+    if let Some(problem) = check_lots(&tracking, quantity, &lots) {
+        return lot_problem(problem).into();
+    }
+
+    let query = state.stock_moves.create(
+        quantity,
+        variant.id,
+        payload.source_stock_id,
+        payload.target_stock_id,
+        lots,
+    );
+    let entity = match query.await {
+        Ok(entity) => entity,
+        Err(error) => return lot_error(error).into(),
+    };
+
+    let model = StockMoveModel::from(&entity);
+    HttpResponse::Ok().json(model)
 }
 
 #[utoipa::path()]
@@ -23,3 +223,127 @@ async fn get() -> HttpResponse {
 async fn delete() -> HttpResponse {
     todo!();
 }
+
+/// Drafts the moves of the order lines with a stockable variant whose goods
+/// haven't been moved yet. Quantities are converted into the variant's unit
+/// and the unit cost is the line's net after its discount and its share of
+/// the order discount.
+pub(super) async fn line_moves(
+    lines: &[OrderLine],
+    discount: Discount,
+    state: &AppState,
+) -> Result<Vec<LineMoveDraft>, HttpResponse> {
+    let variant_ids: Vec<i32> = lines
+        .iter()
+        .filter(|line| line.move_id.is_none())
+        .filter_map(|line| line.variant_id)
+        .collect();
+    let stockable = match state.variants.stockable_ids(variant_ids).await {
+        Ok(ids) => ids,
+        Err(_) => return Err(ApiErrors::InternalServerError.into()),
+    };
+
+    let nets: Vec<i64> = lines
+        .iter()
+        .map(|line| line.discount.apply(line_net(line.quantity, line.price)))
+        .collect();
+    let mut drafts = Vec::new();
+    for (line, net) in lines.iter().zip(taxable_nets(&nets, discount)) {
+        let Some(variant_id) = line.variant_id else {
+            continue;
+        };
+        if line.move_id.is_some() || !stockable.contains(&variant_id) {
+            continue;
+        }
+
+        // Lines can't outlive their variant.
+        let variant = match state.variants.get(variant_id).await {
+            Ok(Some(entity)) => entity,
+            Ok(None) | Err(_) => return Err(ApiErrors::InternalServerError.into()),
+        };
+        let (_, factor) = resolve_line_unit(line.unit_id, Some(&variant), state).await?;
+
+        let quantity = line.quantity * factor;
+        drafts.push(LineMoveDraft {
+            line_id: line.id,
+            variant_id,
+            quantity,
+            unit_cost: (quantity > 0.0).then(|| net as f64 / quantity),
+            cost_accounts: None,
+            lots: Vec::new(),
+        });
+    }
+    Ok(drafts)
+}
+
+/// Splits the drafted moves over the lots given for their lines and checks
+/// them against the tracking of the variants.
+pub(super) async fn attach_lots(
+    drafts: &mut [LineMoveDraft],
+    lots: Vec<LineLotInput>,
+    state: &AppState,
+) -> Result<(), HttpResponse> {
+    if drafts.is_empty() && lots.is_empty() {
+        return Ok(());
+    }
+
+    let variant_ids = drafts.iter().map(|draft| draft.variant_id).collect();
+    let tracking = match state.variants.tracking(variant_ids).await {
+        Ok(tracking) => tracking,
+        Err(_) => return Err(ApiErrors::InternalServerError.into()),
+    };
+
+    for lot in lots {
+        let Some(draft) = drafts.iter_mut().find(|draft| draft.line_id == lot.line_id) else {
+            return Err(ApiErrors::LotLineNotFound.into());
+        };
+        let lot = lot_draft(lot.number, lot.quantity, lot.expiry_date.as_deref())?;
+        draft.lots.push(lot);
+    }
+
+    for draft in drafts.iter() {
+        let tracking = tracking.get(&draft.variant_id).unwrap_or(&Tracking::None);
+        if let Some(problem) = check_lots(tracking, draft.quantity, &draft.lots) {
+            return Err(lot_problem(problem).into());
+        }
+    }
+    Ok(())
+}
+
+/// Maps the errors of moving goods in lots, anything else is an internal
+/// error.
+pub(super) fn lot_error(error: DbErr) -> ApiErrors {
+    match error {
+        DbErr::Custom(message) if message == ERR_LOT_NOT_FOUND => ApiErrors::LotNotFound,
+        DbErr::Custom(message) if message == ERR_LOT_NOT_IN_STOCK => ApiErrors::LotNotInStock,
+        DbErr::Custom(message) if message == ERR_SERIAL_IN_STOCK => ApiErrors::SerialInStock,
+        _ => ApiErrors::InternalServerError,
+    }
+}
+
+pub(super) fn lot_draft(
+    number: String,
+    quantity: f64,
+    expiry_date: Option<&str>,
+) -> Result<LotDraft, HttpResponse> {
+    let expiry_date = match expiry_date.map(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d")) {
+        Some(Ok(date)) => Some(date),
+        Some(Err(_)) => return Err(ApiErrors::InvalidDate.into()),
+        None => None,
+    };
+    Ok(LotDraft {
+        number: number.trim().to_string(),
+        quantity,
+        expiry_date,
+    })
+}
+
+pub(super) fn lot_problem(problem: LotProblem) -> ApiErrors {
+    match problem {
+        LotProblem::NotTracked => ApiErrors::LotsNotTracked,
+        LotProblem::Missing => ApiErrors::LotsMissing,
+        LotProblem::Duplicate => ApiErrors::DuplicateLot,
+        LotProblem::SerialQuantity => ApiErrors::SerialQuantityNotOne,
+        LotProblem::QuantityMismatch => ApiErrors::LotQuantityMismatch,
+    }
+}
@@ -1,19 +1,76 @@
+use std::collections::HashMap;
+
 use actix_web::{HttpResponse, delete, get, post, web};
 use actix_web_validator::Json;
+use chrono::NaiveDate;
+use entity::sea_orm_active_enums::{SaleOrderLineRoute, StockKind, TaxDirection, Tracking};
+use sea_orm::DbErr;
 
 use crate::{
-    models::{CreateStockInput, StockModel},
+    err::{self, ApiErrors},
+    handlers::{
+        purchase_order_lines::catalog_description,
+        stock_moves::{lot_draft, lot_error, lot_problem},
+        tax_rules::{line_taxes, tax_percent},
+        units::resolve_line_unit,
+    },
+    models::{
+        ConsumptionLineInput, ConsumptionModel, CreateContactStockInput, CreateStockInput,
+        PurchaseOrderModel, ReportConsumptionInput, SaleOrderModel, StockModel, StockQuantityModel,
+        contact_stock_kind_from_name,
+    },
+    repositories::{
+        ConsumedPurchaseLineDraft, ConsumedSaleLineDraft, CostAccounts, ERR_EXCEEDS_STOCK,
+        ERR_PERIOD_CLOSED, LineMoveDraft, LotDraft, PurchaseOrderLineDraft, SaleOrderLineDraft,
+    },
     state::AppState,
+    utils::{lots::check_lots, price_lists::select_price, totals::Discount},
 };
 
-#[utoipa::path()]
+#[utoipa::path(
+    tag = "Stocks",
+    summary = "Create a location",
+    description = "Create an internal location of the company, optionally below another one, e.g. a warehouse, a zone in it or a bin in the zone. \
+        Goods can be moved between locations on any level.",
+    responses(
+        (status = 200, description = "Returns the created location", body = StockModel),
+        (status = 400, description = err::MESSAGE_INVALID_PARENT_STOCK, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_PARENT_STOCK,
+                "errorCode": err::CODE_INVALID_PARENT_STOCK,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_STOCK_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_STOCK_NOT_FOUND,
+                "errorCode": err::CODE_STOCK_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
 #[post("/api/stocks")]
 pub async fn create_own(
     payload: Json<CreateStockInput>,
     state: web::Data<AppState>,
 ) -> HttpResponse {
     let payload = payload.into_inner();
-    let query = state.stocks.create_own(payload.name);
+
+    if let Some(parent_id) = payload.parent_id {
+        match state.stocks.get(parent_id).await {
+            Ok(Some(parent)) if parent.kind == StockKind::Internal => {}
+            Ok(Some(_)) => return ApiErrors::InvalidParentStock.into(),
+            Ok(None) => return ApiErrors::StockNotFound.into(),
+            Err(_) => return ApiErrors::InternalServerError.into(),
+        }
+    }
+
+    let query = state.stocks.create_own(payload.name, payload.parent_id);
 
     match query.await {
         Ok(entity) => {
@@ -24,27 +81,79 @@ pub async fn create_own(
     }
 }
 
-#[utoipa::path()]
+#[utoipa::path(
+    tag = "Stocks",
+    summary = "Create a stock of a contact",
+    description = "Create a customer location for a customer or a supplier location for a supplier. \
+        A consignment stock holds the company's goods at a customer, which stay in the own stock until the customer reports consuming them, \
+        or a supplier's goods at the company, which stay the supplier's until the company reports consuming them.",
+    responses(
+        (status = 200, description = "Returns the created stock", body = StockModel),
+        (status = 400, description = err::MESSAGE_INVALID_CONTACT_STOCK_KIND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_CONTACT_STOCK_KIND,
+                "errorCode": err::CODE_INVALID_CONTACT_STOCK_KIND,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_CONTACT_NOT_CUSTOMER, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_CONTACT_NOT_CUSTOMER,
+                "errorCode": err::CODE_CONTACT_NOT_CUSTOMER,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_CONTACT_NOT_SUPPLIER, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_CONTACT_NOT_SUPPLIER,
+                "errorCode": err::CODE_CONTACT_NOT_SUPPLIER,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_CONTACT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_CONTACT_NOT_FOUND,
+                "errorCode": err::CODE_CONTACT_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
 #[post("/api/contacts/{contact}/stocks")]
 pub async fn create_contact(
     path: web::Path<i32>,
-    payload: Json<CreateStockInput>,
+    payload: Json<CreateContactStockInput>,
     state: web::Data<AppState>,
 ) -> HttpResponse {
     let contact = path.into_inner();
     let payload = payload.into_inner();
 
+    let kind = match contact_stock_kind_from_name(&payload.kind) {
+        Some(kind) => kind,
+        None => return ApiErrors::InvalidContactStockKind.into(),
+    };
+
     let query = state.contacts.get(contact);
     let contact = match query.await {
         Ok(Some(entity)) => entity,
-        Ok(None) => return HttpResponse::NotFound().finish(),
-        Err(_) => return HttpResponse::InternalServerError().finish(),
+        Ok(None) => return ApiErrors::ContactNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
     };
 
-    let query = state.stocks.create_for_contact(payload.name, contact.id);
+    match kind {
+        StockKind::Customer if !contact.is_customer => return ApiErrors::ContactNotCustomer.into(),
+        StockKind::Supplier if !contact.is_supplier => return ApiErrors::ContactNotSupplier.into(),
+        _ => {}
+    }
+    let query =
+        state
+            .stocks
+            .create_for_contact(payload.name, contact.id, kind, payload.consignment);
     let stock = match query.await {
         Ok(entity) => entity,
-        Err(_) => return HttpResponse::InternalServerError().finish(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
     };
 
     let model = StockModel::from(&stock);
@@ -63,14 +172,473 @@ pub async fn list_contract() -> HttpResponse {
     todo!();
 }
 
+#[utoipa::path(
+    tag = "Stocks",
+    summary = "List the virtual locations",
+    description = "List the locations of suppliers, customers, inventory losses and production that goods come from or go to when they enter or leave the company",
+    responses(
+        (status = 200, description = "Returns the virtual locations", body = Vec<StockModel>),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/stocks/virtual")]
+pub async fn list_virtual(state: web::Data<AppState>) -> HttpResponse {
+    let stocks = match state.stocks.list_virtual().await {
+        Ok(stocks) => stocks,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let models: Vec<StockModel> = stocks.iter().map(StockModel::from).collect();
+    HttpResponse::Ok().json(models)
+}
+
 #[utoipa::path()]
 #[get("/api/stocks/{id}")]
 pub async fn get() -> HttpResponse {
     todo!();
 }
 
+#[utoipa::path(
+    tag = "Stocks",
+    summary = "Quantities in a location",
+    description = "Get what is in the location and all locations below it per variant, e.g. everything in the bins of a warehouse",
+    responses(
+        (status = 200, description = "Returns the quantity per variant", body = Vec<StockQuantityModel>),
+        (status = 404, description = err::MESSAGE_STOCK_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_STOCK_NOT_FOUND,
+                "errorCode": err::CODE_STOCK_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/stocks/{id}/quantities")]
+pub async fn quantities(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let stock = match state.stocks.get(path.into_inner()).await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::StockNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let quantities = match state.stocks.quantities(stock.id).await {
+        Ok(quantities) => quantities,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let models: Vec<StockQuantityModel> = quantities.iter().map(StockQuantityModel::from).collect();
+    HttpResponse::Ok().json(models)
+}
+
+#[utoipa::path(
+    tag = "Stocks",
+    summary = "Report consumption from a consignment stock",
+    description = "Report the goods consumed from a consignment stock. \
+        Goods a customer consumed are moved to the customer location, which posts their cost, and recorded on a finished sale order priced like its lines. \
+        Goods consumed from a supplier's stock are moved into the given internal location, which values them at the supplier's price, and recorded on a finished purchase order. \
+        The lines are taxed by the tax rules of the contact's region. The order is then invoiced like any other.",
+    responses(
+        (status = 200, description = "Returns the order the consumption is recorded on", body = ConsumptionModel),
+        (status = 400, description = err::MESSAGE_INCOMPATIBLE_UNIT, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INCOMPATIBLE_UNIT,
+                "errorCode": err::CODE_INCOMPATIBLE_UNIT,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INVALID_CONSUMPTION_TARGET, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_CONSUMPTION_TARGET,
+                "errorCode": err::CODE_INVALID_CONSUMPTION_TARGET,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INVALID_DATE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_DATE,
+                "errorCode": err::CODE_INVALID_DATE,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_LOTS_MISSING, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOTS_MISSING,
+                "errorCode": err::CODE_LOTS_MISSING,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_STOCK_NOT_CONSIGNMENT, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_STOCK_NOT_CONSIGNMENT,
+                "errorCode": err::CODE_STOCK_NOT_CONSIGNMENT,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_VARIANT_NOT_STOCKABLE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_VARIANT_NOT_STOCKABLE,
+                "errorCode": err::CODE_VARIANT_NOT_STOCKABLE,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_STOCK_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_STOCK_NOT_FOUND,
+                "errorCode": err::CODE_STOCK_NOT_FOUND,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_VARIANT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_VARIANT_NOT_FOUND,
+                "errorCode": err::CODE_VARIANT_NOT_FOUND,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_CONSUMPTION_EXCEEDS_STOCK, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_CONSUMPTION_EXCEEDS_STOCK,
+                "errorCode": err::CODE_CONSUMPTION_EXCEEDS_STOCK,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_CUSTOMER_LOCATION_MISSING, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_CUSTOMER_LOCATION_MISSING,
+                "errorCode": err::CODE_CUSTOMER_LOCATION_MISSING,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_LOT_NOT_IN_STOCK, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_LOT_NOT_IN_STOCK,
+                "errorCode": err::CODE_LOT_NOT_IN_STOCK,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_NUMBER_SEQUENCE_PERIOD_CLOSED, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_NUMBER_SEQUENCE_PERIOD_CLOSED,
+                "errorCode": err::CODE_NUMBER_SEQUENCE_PERIOD_CLOSED,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_POSTING_ACCOUNT_MISSING, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_POSTING_ACCOUNT_MISSING,
+                "errorCode": err::CODE_POSTING_ACCOUNT_MISSING,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/stocks/{id}/consumption")]
+pub async fn report_consumption(
+    path: web::Path<i32>,
+    payload: Json<ReportConsumptionInput>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let payload = payload.into_inner();
+
+    let date = match NaiveDate::parse_from_str(&payload.date, "%Y-%m-%d") {
+        Ok(date) => date,
+        Err(_) => return ApiErrors::InvalidDate.into(),
+    };
+
+    let stock = match state.stocks.get(path.into_inner()).await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::StockNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+    if !stock.consignment {
+        return ApiErrors::StockNotConsignment.into();
+    }
+    // Consignment stocks are only created for contacts and go with them.
+    let contact = match stock.owner_id {
+        Some(owner_id) => match state.contacts.get(owner_id).await {
+            Ok(Some(entity)) => entity,
+            Ok(None) | Err(_) => return ApiErrors::InternalServerError.into(),
+        },
+        None => return ApiErrors::InternalServerError.into(),
+    };
+
+    let target = match (&stock.kind, payload.target_stock_id) {
+        (StockKind::Customer, _) => match state.stocks.list_virtual().await {
+            Ok(stocks) => match stocks
+                .into_iter()
+                .find(|stock| stock.kind == StockKind::Customer)
+            {
+                Some(stock) => stock,
+                None => return ApiErrors::CustomerLocationMissing.into(),
+            },
+            Err(_) => return ApiErrors::InternalServerError.into(),
+        },
+        (_, Some(target_stock_id)) => match state.stocks.get(target_stock_id).await {
+            Ok(Some(stock)) if stock.kind == StockKind::Internal => stock,
+            Ok(Some(_)) => return ApiErrors::InvalidConsumptionTarget.into(),
+            Ok(None) => return ApiErrors::StockNotFound.into(),
+            Err(_) => return ApiErrors::InternalServerError.into(),
+        },
+        (_, None) => return ApiErrors::InvalidConsumptionTarget.into(),
+    };
+
+    let lines = match consumed_lines(payload.lines, &state).await {
+        Ok(lines) => lines,
+        Err(response) => return response,
+    };
+
+    let direction = match stock.kind {
+        StockKind::Customer => TaxDirection::Sale,
+        _ => TaxDirection::Purchase,
+    };
+    let variants: Vec<&entity::variants::Model> = lines.iter().map(|line| &line.variant).collect();
+    let taxes = match line_taxes(direction, &contact, &variants, date, &state).await {
+        Ok(taxes) => taxes,
+        Err(response) => return response,
+    };
+
+    let model = if stock.kind == StockKind::Customer {
+        let drafts = match sale_lines(&contact, date, lines, &taxes, &state).await {
+            Ok(drafts) => drafts,
+            Err(response) => return response,
+        };
+        let query = state
+            .sale_orders
+            .consume(date, contact.id, stock.id, target.id, drafts);
+        match query.await {
+            Ok(order) => ConsumptionModel {
+                sale_order: Some(SaleOrderModel::from(&order)),
+                purchase_order: None,
+            },
+            Err(DbErr::Custom(message)) if message == ERR_PERIOD_CLOSED => {
+                return ApiErrors::NumberSequencePeriodClosed.into();
+            }
+            Err(DbErr::Custom(message)) if message == ERR_EXCEEDS_STOCK => {
+                return ApiErrors::ConsumptionExceedsStock.into();
+            }
+            Err(error) => return lot_error(error).into(),
+        }
+    } else {
+        let drafts = match purchase_lines(&contact, lines, &taxes, &state).await {
+            Ok(drafts) => drafts,
+            Err(response) => return response,
+        };
+        let query = state
+            .purchase_orders
+            .consume(date, contact.id, stock.id, target.id, drafts);
+        match query.await {
+            Ok(order) => ConsumptionModel {
+                sale_order: None,
+                purchase_order: Some(PurchaseOrderModel::from(&order)),
+            },
+            Err(DbErr::Custom(message)) if message == ERR_PERIOD_CLOSED => {
+                return ApiErrors::NumberSequencePeriodClosed.into();
+            }
+            Err(DbErr::Custom(message)) if message == ERR_EXCEEDS_STOCK => {
+                return ApiErrors::ConsumptionExceedsStock.into();
+            }
+            Err(error) => return lot_error(error).into(),
+        }
+    };
+
+    HttpResponse::Ok().json(model)
+}
+
 #[utoipa::path()]
 #[delete("/api/stocks/{id}")]
 pub async fn delete() -> HttpResponse {
     todo!();
 }
+
+/// A line of a consumption report with its variant and lots resolved.
+struct ConsumedLine {
+    variant: entity::variants::Model,
+    /// In the unit of the line.
+    quantity: f64,
+    unit_id: Option<i32>,
+    /// The variant's units per unit of the line.
+    factor: f64,
+    lots: Vec<LotDraft>,
+}
+
+/// Resolves the variants, units and lots of the reported lines. Only
+/// stockable variants are on consignment.
+async fn consumed_lines(
+    lines: Vec<ConsumptionLineInput>,
+    state: &AppState,
+) -> Result<Vec<ConsumedLine>, HttpResponse> {
+    let variant_ids: Vec<i32> = lines.iter().map(|line| line.variant_id).collect();
+    let stockable = match state.variants.stockable_ids(variant_ids.clone()).await {
+        Ok(ids) => ids,
+        Err(_) => return Err(ApiErrors::InternalServerError.into()),
+    };
+    let tracking = match state.variants.tracking(variant_ids).await {
+        Ok(tracking) => tracking,
+        Err(_) => return Err(ApiErrors::InternalServerError.into()),
+    };
+
+    let mut consumed = Vec::with_capacity(lines.len());
+    for line in lines {
+        let variant = match state.variants.get(line.variant_id).await {
+            Ok(Some(entity)) => entity,
+            Ok(None) => return Err(ApiErrors::VariantNotFound.into()),
+            Err(_) => return Err(ApiErrors::InternalServerError.into()),
+        };
+        if !stockable.contains(&variant.id) {
+            return Err(ApiErrors::VariantNotStockable.into());
+        }
+        let (unit_id, factor) = resolve_line_unit(line.unit_id, Some(&variant), state).await?;
+
+        let mut lots = Vec::with_capacity(line.lots.len());
+        for lot in line.lots {
+            lots.push(lot_draft(
+                lot.number,
+                lot.quantity,
+                lot.expiry_date.as_deref(),
+            )?);
+        }
+        let tracking = tracking.get(&variant.id).unwrap_or(&Tracking::None);
+        if let Some(problem) = check_lots(tracking, line.quantity * factor, &lots) {
+            return Err(lot_problem(problem).into());
+        }
+
+        consumed.push(ConsumedLine {
+            variant,
+            quantity: line.quantity,
+            unit_id,
+            factor,
+            lots,
+        });
+    }
+    Ok(consumed)
+}
+
+/// Drafts the sale order lines of the goods a customer consumed, priced from
+/// the customer's price lists or else the variant's sale price, and taxed at
+/// the tax determined for the customer. Their cost is posted to the
+/// category's accounts as they leave the own stock.
+async fn sale_lines(
+    customer: &entity::contacts::Model,
+    date: NaiveDate,
+    lines: Vec<ConsumedLine>,
+    taxes: &HashMap<i32, entity::taxes::Model>,
+    state: &AppState,
+) -> Result<Vec<ConsumedSaleLineDraft>, HttpResponse> {
+    let variant_ids = lines.iter().map(|line| line.variant.id).collect();
+    let defaults = match state
+        .product_categories
+        .defaults_for_variants(variant_ids)
+        .await
+    {
+        Ok(defaults) => defaults,
+        Err(_) => return Err(ApiErrors::InternalServerError.into()),
+    };
+
+    let mut drafts = Vec::with_capacity(lines.len());
+    for line in lines {
+        let category = defaults.get(&line.variant.id);
+        let accounts = category.and_then(|category| {
+            Some(CostAccounts {
+                expense_account_id: category.expense_account_id?,
+                inventory_account_id: category.inventory_account_id?,
+            })
+        });
+        let Some(accounts) = accounts else {
+            return Err(ApiErrors::PostingAccountMissing.into());
+        };
+
+        let query = state
+            .price_lists
+            .list_prices_for_customer(customer.id, line.variant.id);
+        let prices = match query.await {
+            Ok(prices) => prices,
+            Err(_) => return Err(ApiErrors::InternalServerError.into()),
+        };
+        // Prices are per unit of the variant.
+        let quantity = line.quantity * line.factor;
+        let price = select_price(&prices, &customer.currency, date, quantity)
+            .unwrap_or(line.variant.sale_price);
+
+        let tax = taxes.get(&line.variant.id);
+        drafts.push(ConsumedSaleLineDraft {
+            line: SaleOrderLineDraft {
+                description: line.variant.name.clone(),
+                quantity: line.quantity,
+                price: (price as f64 * line.factor).round() as i32,
+                tax: tax.map(tax_percent).unwrap_or_default(),
+                tax_id: tax.map(|tax| tax.id),
+                variant_id: Some(line.variant.id),
+                discount: Discount::default(),
+                unit_id: line.unit_id,
+                route: SaleOrderLineRoute::Stock,
+            },
+            // The goods leave at the cost they are valued at in stock.
+            stock_move: LineMoveDraft {
+                line_id: 0,
+                variant_id: line.variant.id,
+                quantity,
+                unit_cost: None,
+                cost_accounts: Some(accounts),
+                lots: line.lots,
+            },
+        });
+    }
+    Ok(drafts)
+}
+
+/// Drafts the purchase order lines of the goods consumed from a supplier's
+/// stock, priced from the supplier's catalog or else the variant's purchase
+/// price, and taxed at the tax determined for the supplier. The goods come
+/// into the own stock at that price.
+async fn purchase_lines(
+    supplier: &entity::contacts::Model,
+    lines: Vec<ConsumedLine>,
+    taxes: &HashMap<i32, entity::taxes::Model>,
+    state: &AppState,
+) -> Result<Vec<ConsumedPurchaseLineDraft>, HttpResponse> {
+    let mut drafts = Vec::with_capacity(lines.len());
+    for line in lines {
+        // The preferred entry comes first, then the cheapest.
+        let entry = match state
+            .supplier_prices
+            .list_for_variant(line.variant.id)
+            .await
+        {
+            Ok(entries) => entries
+                .into_iter()
+                .find(|entry| entry.supplier_id == supplier.id),
+            Err(_) => return Err(ApiErrors::InternalServerError.into()),
+        };
+        // Catalog prices are per unit of the variant.
+        let (description, price) = match &entry {
+            Some(entry) => (catalog_description(entry, &line.variant), entry.price),
+            None => (line.variant.name.clone(), line.variant.purchase_price),
+        };
+        let tax = taxes.get(&line.variant.id);
+
+        drafts.push(ConsumedPurchaseLineDraft {
+            line: PurchaseOrderLineDraft {
+                description,
+                quantity: line.quantity,
+                price: (price as f64 * line.factor).round() as i32,
+                tax: tax.map(tax_percent).unwrap_or_default(),
+                tax_id: tax.map(|tax| tax.id),
+                variant_id: Some(line.variant.id),
+                discount: Discount::default(),
+                unit_id: line.unit_id,
+            },
+            stock_move: LineMoveDraft {
+                line_id: 0,
+                variant_id: line.variant.id,
+                quantity: line.quantity * line.factor,
+                unit_cost: Some(price as f64),
+                cost_accounts: None,
+                lots: line.lots,
+            },
+        });
+    }
+    Ok(drafts)
+}
@@ -0,0 +1,156 @@
+use actix_web::{HttpResponse, delete, get, post, web};
+use actix_web_validator::Json;
+use sea_orm::DbErr;
+
+use crate::{
+    err::{self, ApiErrors},
+    models::{CreateSupplierPriceInput, SupplierPriceModel},
+    repositories::{ERR_SUPPLIER_PRICE_EXISTS, SupplierPriceDraft},
+    state::AppState,
+};
+
+#[utoipa::path(
+    tag = "Variants",
+    summary = "Add a supplier's price for a variant",
+    description = "Add a supplier's catalog entry for a variant, with the supplier's SKU, minimum order quantity, pack size and lead time. \
+        Lines of purchase orders from the supplier are filled from the entry. A preferred entry takes the preference from the other suppliers of the variant.",
+    responses(
+        (status = 200, description = "Returns the created entry", body = SupplierPriceModel),
+        (status = 404, description = err::MESSAGE_CONTACT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_CONTACT_NOT_FOUND,
+                "errorCode": err::CODE_CONTACT_NOT_FOUND,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_VARIANT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_VARIANT_NOT_FOUND,
+                "errorCode": err::CODE_VARIANT_NOT_FOUND,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_SUPPLIER_PRICE_EXISTS, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SUPPLIER_PRICE_EXISTS,
+                "errorCode": err::CODE_SUPPLIER_PRICE_EXISTS,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/variants/{id}/supplier-prices")]
+async fn create(
+    path: web::Path<i32>,
+    payload: Json<CreateSupplierPriceInput>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let payload = payload.into_inner();
+
+    let query = state.variants.get(path.into_inner());
+    let variant = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::VariantNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state.contacts.get(payload.supplier_id);
+    let supplier = match query.await {
+        Ok(Some(contact)) if contact.is_supplier => contact,
+        Ok(_) => return ApiErrors::ContactNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let entry = SupplierPriceDraft {
+        supplier_sku: payload.supplier_sku.trim().to_string(),
+        description: payload.description.trim().to_string(),
+        price: payload.price,
+        min_quantity: payload.min_quantity,
+        pack_size: payload.pack_size,
+        lead_time_days: payload.lead_time_days,
+        preferred: payload.preferred,
+    };
+    let query = state.supplier_prices.create(variant.id, supplier.id, entry);
+    let entity = match query.await {
+        Ok(entity) => entity,
+        Err(DbErr::Custom(message)) if message == ERR_SUPPLIER_PRICE_EXISTS => {
+            return ApiErrors::SupplierPriceExists.into();
+        }
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let model = SupplierPriceModel::from(&entity);
+    HttpResponse::Ok().json(model)
+}
+
+#[utoipa::path(
+    tag = "Variants",
+    summary = "List the supplier prices of a variant",
+    description = "List the suppliers' catalog entries for a variant, the preferred one first and then the cheapest.",
+    responses(
+        (status = 200, description = "Returns the entries", body = Vec<SupplierPriceModel>),
+        (status = 404, description = err::MESSAGE_VARIANT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_VARIANT_NOT_FOUND,
+                "errorCode": err::CODE_VARIANT_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/variants/{id}/supplier-prices")]
+async fn list(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let query = state.variants.get(path.into_inner());
+    let variant = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::VariantNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state.supplier_prices.list_for_variant(variant.id);
+    let entities = match query.await {
+        Ok(entities) => entities,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let models: Vec<SupplierPriceModel> = entities.iter().map(SupplierPriceModel::from).collect();
+    HttpResponse::Ok().json(models)
+}
+
+#[utoipa::path(
+    tag = "Variants",
+    summary = "Delete a supplier price",
+    description = "Delete a supplier's catalog entry for a variant",
+    responses(
+        (status = 204, description = "Entry has been deleted"),
+        (status = 404, description = err::MESSAGE_SUPPLIER_PRICE_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_SUPPLIER_PRICE_NOT_FOUND,
+                "errorCode": err::CODE_SUPPLIER_PRICE_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[delete("/api/supplier-prices/{id}")]
+async fn delete(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let query = state.supplier_prices.delete(path.into_inner());
+    match query.await {
+        Ok(true) => HttpResponse::NoContent().finish(),
+        Ok(false) => ApiErrors::SupplierPriceNotFound.into(),
+        Err(_) => ApiErrors::InternalServerError.into(),
+    }
+}
@@ -0,0 +1,355 @@
+use std::collections::{HashMap, hash_map::Entry};
+
+use actix_web::{HttpResponse, delete, get, post, web};
+use actix_web_validator::{Json, Query};
+use chrono::{NaiveDate, Utc};
+use entity::sea_orm_active_enums::TaxDirection;
+
+use crate::{
+    err::{self, ApiErrors},
+    models::{
+        CreateTaxRuleInput, DetermineTaxInput, ListTaxRulesInput, Page, TaxDeterminationModel,
+        TaxModel, TaxRuleModel, tax_direction_from_name, tax_region_from_name, tax_region_name,
+    },
+    state::AppState,
+    utils::{
+        product_categories::CategoryDefaults,
+        tax_rules::{select_tax_rule, tax_region},
+    },
+};
+
+#[utoipa::path(
+    tag = "Taxes",
+    summary = "Create a tax rule",
+    description = "Create a rule which selects a tax for sale or purchase lines. \
+        A rule applies to contacts of one region and can be limited to contacts with or without a VAT ID and to one product tax category.",
+    responses(
+        (status = 200, description = "Returns the created tax rule", body = TaxRuleModel),
+        (status = 400, description = err::MESSAGE_INVALID_TAX_DIRECTION, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_TAX_DIRECTION,
+                "errorCode": err::CODE_INVALID_TAX_DIRECTION,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INVALID_TAX_REGION, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_TAX_REGION,
+                "errorCode": err::CODE_INVALID_TAX_REGION,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_TAX_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_TAX_NOT_FOUND,
+                "errorCode": err::CODE_TAX_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/tax-rules")]
+async fn create(payload: Json<CreateTaxRuleInput>, state: web::Data<AppState>) -> HttpResponse {
+    let payload = payload.into_inner();
+
+    let direction = match tax_direction_from_name(&payload.direction) {
+        Some(direction) => direction,
+        None => return ApiErrors::InvalidTaxDirection.into(),
+    };
+    let region = match tax_region_from_name(&payload.region) {
+        Some(region) => region,
+        None => return ApiErrors::InvalidTaxRegion.into(),
+    };
+
+    let query = state.taxes.get(payload.tax_id);
+    let tax = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::TaxNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state.tax_rules.create(
+        direction,
+        region,
+        payload.vat_id,
+        payload.product_tax_category,
+        tax.id,
+    );
+    let entity = match query.await {
+        Ok(entity) => entity,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let model = TaxRuleModel::from(&entity);
+    HttpResponse::Ok().json(model)
+}
+
+#[utoipa::path(
+    tag = "Taxes",
+    summary = "List tax rules",
+    params(
+        ("limit" = Option<u64>, Query, description = "Maximum number of rules, 100 by default"),
+        ("offset" = Option<u64>, Query, description = "Number of rules to skip"),
+    ),
+    responses(
+        (status = 200, description = "Returns a page of tax rules"),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/tax-rules")]
+async fn list(query: Query<ListTaxRulesInput>, state: web::Data<AppState>) -> HttpResponse {
+    let limit = query.limit.unwrap_or(100);
+    let offset = query.offset.unwrap_or(0);
+
+    let query = state.tax_rules.list(limit, offset);
+    let result = match query.await {
+        Ok(result) => result,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let models: Vec<TaxRuleModel> = result.data.iter().map(TaxRuleModel::from).collect();
+    HttpResponse::Ok().json(Page {
+        limit,
+        offset,
+        total: result.total,
+        data: models,
+    })
+}
+
+#[utoipa::path(
+    tag = "Taxes",
+    summary = "Delete a tax rule",
+    responses(
+        (status = 204, description = "The tax rule was deleted"),
+        (status = 404, description = err::MESSAGE_TAX_RULE_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_TAX_RULE_NOT_FOUND,
+                "errorCode": err::CODE_TAX_RULE_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[delete("/api/tax-rules/{id}")]
+async fn delete(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let query = state.tax_rules.delete(path.into_inner());
+    match query.await {
+        Ok(true) => HttpResponse::NoContent().finish(),
+        Ok(false) => ApiErrors::TaxRuleNotFound.into(),
+        Err(_) => ApiErrors::InternalServerError.into(),
+    }
+}
+
+#[utoipa::path(
+    tag = "Taxes",
+    summary = "Determine the tax of a line",
+    description = "Select the tax for a sale or purchase line from the tax rules. \
+        The region follows from the contact's country compared to the company's, e.g. an EU customer with a VAT ID gets the reverse charge rule and a customer outside the EU the export rule. \
+        The product tax category is read from the variant's product; lines without a variant only match rules for all categories. \
+        The returned tax's note is the text an invoice with this tax has to carry, its rate the one valid on the document date.",
+    params(
+        ("direction" = String, Query, description = "`sale` or `purchase`"),
+        ("contactId" = i32, Query, description = "Customer or supplier of the document"),
+        ("variantId" = Option<i32>, Query, description = "Variant sold or bought on the line"),
+        ("date" = Option<String>, Query, description = "Date of the document, today by default"),
+    ),
+    responses(
+        (status = 200, description = "Returns the tax and the rule it was selected by", body = TaxDeterminationModel),
+        (status = 400, description = err::MESSAGE_INVALID_TAX_DIRECTION, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_TAX_DIRECTION,
+                "errorCode": err::CODE_INVALID_TAX_DIRECTION,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INVALID_DATE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_DATE,
+                "errorCode": err::CODE_INVALID_DATE,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_CONTACT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_CONTACT_NOT_FOUND,
+                "errorCode": err::CODE_CONTACT_NOT_FOUND,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_VARIANT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_VARIANT_NOT_FOUND,
+                "errorCode": err::CODE_VARIANT_NOT_FOUND,
+            })),
+        )),
+        (status = 422, description = err::MESSAGE_NO_MATCHING_TAX_RULE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_NO_MATCHING_TAX_RULE,
+                "errorCode": err::CODE_NO_MATCHING_TAX_RULE,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/tax-rules/determine")]
+async fn determine(query: Query<DetermineTaxInput>, state: web::Data<AppState>) -> HttpResponse {
+    let direction = match tax_direction_from_name(&query.direction) {
+        Some(direction) => direction,
+        None => return ApiErrors::InvalidTaxDirection.into(),
+    };
+    let date = match &query.date {
+        Some(date) => match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+            Ok(date) => date,
+            Err(_) => return ApiErrors::InvalidDate.into(),
+        },
+        None => Utc::now().date_naive(),
+    };
+
+    let contact = match state.contacts.get(query.contact_id).await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::ContactNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let product_tax_category = match query.variant_id {
+        Some(variant_id) => {
+            let variant = match state.variants.get(variant_id).await {
+                Ok(Some(entity)) => entity,
+                Ok(None) => return ApiErrors::VariantNotFound.into(),
+                Err(_) => return ApiErrors::InternalServerError.into(),
+            };
+
+            // Variants can't outlive their product.
+            match state.products.get(variant.product_id).await {
+                Ok(Some(product)) => Some(product.tax_category),
+                Ok(None) | Err(_) => return ApiErrors::InternalServerError.into(),
+            }
+        }
+        None => None,
+    };
+
+    let region = tax_region(&state.letterhead.country, &contact.country);
+    let query = state.tax_rules.list_for_region(direction, region.clone());
+    let rules = match query.await {
+        Ok(rules) => rules,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let has_vat_id = !contact.vat_id.trim().is_empty();
+    let rule = match select_tax_rule(&rules, &region, has_vat_id, product_tax_category.as_deref()) {
+        Some(rule) => rule,
+        None => return ApiErrors::NoMatchingTaxRule.into(),
+    };
+
+    // Rules can't outlive their tax.
+    let tax = match state.taxes.get_at(rule.tax_id, date).await {
+        Ok(Some(entity)) => entity,
+        Ok(None) | Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    HttpResponse::Ok().json(TaxDeterminationModel {
+        region: tax_region_name(&region).to_string(),
+        rule_id: rule.id,
+        tax: TaxModel::from(&tax),
+    })
+}
+
+/// The percentage order lines keep of a tax.
+pub(super) fn tax_percent(tax: &entity::taxes::Model) -> i32 {
+    (tax.rate * 100.0).round() as i32
+}
+
+/// Determines the tax of lines of the variants for the contact, with the
+/// rates of the date. The tax rules of the contact's region decide, and
+/// variants no rule matches get their product category's default tax.
+/// Variants with neither are left out.
+pub(super) async fn line_taxes(
+    direction: TaxDirection,
+    contact: &entity::contacts::Model,
+    variants: &[&entity::variants::Model],
+    date: NaiveDate,
+    state: &AppState,
+) -> Result<HashMap<i32, entity::taxes::Model>, HttpResponse> {
+    let variant_ids = variants.iter().map(|variant| variant.id).collect();
+    let query = state.product_categories.defaults_for_variants(variant_ids);
+    let defaults = match query.await {
+        Ok(defaults) => defaults,
+        Err(_) => return Err(ApiErrors::InternalServerError.into()),
+    };
+    let tax_ids = line_tax_ids(direction, contact, variants, &defaults, state).await?;
+
+    let mut taxes: HashMap<i32, entity::taxes::Model> = HashMap::new();
+    let mut line_taxes = HashMap::new();
+    for (variant_id, tax_id) in tax_ids {
+        if let Entry::Vacant(entry) = taxes.entry(tax_id) {
+            // Rules and categories can't outlive their tax.
+            match state.taxes.get_at(tax_id, date).await {
+                Ok(Some(tax)) => entry.insert(tax),
+                Ok(None) | Err(_) => return Err(ApiErrors::InternalServerError.into()),
+            };
+        }
+        line_taxes.insert(variant_id, taxes[&tax_id].clone());
+    }
+    Ok(line_taxes)
+}
+
+/// Determines the ids of the taxes of lines of the variants for the contact
+/// like `line_taxes`, with the category defaults already loaded.
+pub(super) async fn line_tax_ids(
+    direction: TaxDirection,
+    contact: &entity::contacts::Model,
+    variants: &[&entity::variants::Model],
+    defaults: &HashMap<i32, CategoryDefaults>,
+    state: &AppState,
+) -> Result<HashMap<i32, i32>, HttpResponse> {
+    let region = tax_region(&state.letterhead.country, &contact.country);
+    let rules = match state
+        .tax_rules
+        .list_for_region(direction, region.clone())
+        .await
+    {
+        Ok(rules) => rules,
+        Err(_) => return Err(ApiErrors::InternalServerError.into()),
+    };
+
+    let has_vat_id = !contact.vat_id.trim().is_empty();
+    let mut categories: HashMap<i32, String> = HashMap::new();
+    let mut tax_ids = HashMap::new();
+    for variant in variants {
+        if let Entry::Vacant(entry) = categories.entry(variant.product_id) {
+            // Variants can't outlive their product.
+            match state.products.get(variant.product_id).await {
+                Ok(Some(product)) => entry.insert(product.tax_category),
+                Ok(None) | Err(_) => return Err(ApiErrors::InternalServerError.into()),
+            };
+        }
+        let category = categories[&variant.product_id].as_str();
+        let tax_id = match select_tax_rule(&rules, &region, has_vat_id, Some(category)) {
+            Some(rule) => rule.tax_id,
+            None => match defaults
+                .get(&variant.id)
+                .and_then(|defaults| defaults.tax_id)
+            {
+                Some(tax_id) => tax_id,
+                None => continue,
+            },
+        };
+        tax_ids.insert(variant.id, tax_id);
+    }
+    Ok(tax_ids)
+}
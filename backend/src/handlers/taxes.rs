@@ -1,9 +1,18 @@
-use actix_web::{HttpResponse, delete, get, post, web};
+use std::collections::HashMap;
+
+use actix_web::{HttpResponse, delete, get, post, put, web};
 use actix_web_validator::{Json, Query};
+use chrono::NaiveDate;
+use sea_orm::DbErr;
 
 use crate::{
-    err::ApiErrors,
-    models::{CreateTaxInput, ListTaxesInput, Page, TaxModel},
+    documents::einvoice::TaxCategory,
+    err::{self, ApiErrors},
+    models::{
+        CreateTaxInput, CreateTaxRateInput, ListTaxesInput, Page, SetTaxComponentsInput,
+        TaxComponentModel, TaxModel, TaxRateModel,
+    },
+    repositories::{ERR_NESTED_TAX_GROUP, ERR_TAX_RATE_OVERLAP, ResolvedTax},
     state::AppState,
 };
 
@@ -12,6 +21,15 @@ use crate::{
 async fn create(payload: Json<CreateTaxInput>, state: web::Data<AppState>) -> HttpResponse {
     let payload = payload.into_inner();
 
+    let category = match payload.category {
+        Some(code) => match TaxCategory::from_code(&code) {
+            Some(category) => category,
+            None => return ApiErrors::InvalidTaxCategory.into(),
+        },
+        None if payload.rate == 0.0 => TaxCategory::ZeroRated,
+        None => TaxCategory::Standard,
+    };
+
     let query = state.ledger_accounts.get(payload.account_id);
     let account = match query.await {
         Ok(Some(entity)) => entity,
@@ -19,9 +37,14 @@ async fn create(payload: Json<CreateTaxInput>, state: web::Data<AppState>) -> Ht
         Err(_) => return ApiErrors::InternalServerError.into(),
     };
 
-    let query = state
-        .taxes
-        .create(payload.name, payload.name_short, payload.rate, account.id);
+    let query = state.taxes.create(
+        payload.name,
+        payload.name_short,
+        payload.rate,
+        account.id,
+        category.code().to_string(),
+        payload.note,
+    );
     let tax = match query.await {
         Ok(entity) => entity,
         Err(_) => return ApiErrors::InternalServerError.into(),
@@ -73,3 +96,255 @@ async fn get(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
 async fn delete() -> HttpResponse {
     todo!();
 }
+
+#[utoipa::path(
+    tag = "Taxes",
+    summary = "Add a rate version",
+    description = "Limit a rate of the tax to a period, e.g. for a temporary rate cut. \
+        Documents dated within the period are computed with this rate, all others keep the rate that applied on their date. \
+        An open-ended version that starts earlier is ended the day before, other versions of the tax must not overlap.",
+    responses(
+        (status = 200, description = "Returns the created rate version", body = TaxRateModel),
+        (status = 400, description = err::MESSAGE_INVALID_DATE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_DATE,
+                "errorCode": err::CODE_INVALID_DATE,
+            })),
+        )),
+        (status = 400, description = err::MESSAGE_INVALID_DATE_RANGE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_DATE_RANGE,
+                "errorCode": err::CODE_INVALID_DATE_RANGE,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_TAX_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_TAX_NOT_FOUND,
+                "errorCode": err::CODE_TAX_NOT_FOUND,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_TAX_RATE_OVERLAP, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_TAX_RATE_OVERLAP,
+                "errorCode": err::CODE_TAX_RATE_OVERLAP,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/taxes/{id}/rates")]
+async fn create_rate(
+    path: web::Path<i32>,
+    payload: Json<CreateTaxRateInput>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let payload = payload.into_inner();
+
+    let valid_from = match NaiveDate::parse_from_str(&payload.valid_from, "%Y-%m-%d") {
+        Ok(date) => date,
+        Err(_) => return ApiErrors::InvalidDate.into(),
+    };
+    let valid_to = match payload.valid_to {
+        Some(date) => match NaiveDate::parse_from_str(&date, "%Y-%m-%d") {
+            Ok(date) => Some(date),
+            Err(_) => return ApiErrors::InvalidDate.into(),
+        },
+        None => None,
+    };
+    if valid_to.is_some_and(|valid_to| valid_to < valid_from) {
+        return ApiErrors::InvalidDateRange.into();
+    }
+
+    let query = state.taxes.get(path.into_inner());
+    let tax = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::TaxNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state
+        .taxes
+        .create_rate(tax.id, payload.rate, valid_from, valid_to);
+    let version = match query.await {
+        Ok(entity) => entity,
+        Err(DbErr::Custom(message)) if message == ERR_TAX_RATE_OVERLAP => {
+            return ApiErrors::TaxRateOverlap.into();
+        }
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let model = TaxRateModel::from(&version);
+    HttpResponse::Ok().json(model)
+}
+
+#[utoipa::path(
+    tag = "Taxes",
+    summary = "List the rate versions of a tax",
+    responses(
+        (status = 200, description = "Returns the rate versions ordered by their start", body = Vec<TaxRateModel>),
+        (status = 404, description = err::MESSAGE_TAX_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_TAX_NOT_FOUND,
+                "errorCode": err::CODE_TAX_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/taxes/{id}/rates")]
+async fn list_rates(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let query = state.taxes.get(path.into_inner());
+    let tax = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::TaxNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state.taxes.list_rates(tax.id);
+    let versions = match query.await {
+        Ok(versions) => versions,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let models: Vec<TaxRateModel> = versions.iter().map(TaxRateModel::from).collect();
+    HttpResponse::Ok().json(models)
+}
+
+#[utoipa::path(
+    tag = "Taxes",
+    summary = "List the components of a tax group",
+    responses(
+        (status = 200, description = "Returns the components in the order they are levied, none for a plain tax", body = Vec<TaxComponentModel>),
+        (status = 404, description = err::MESSAGE_TAX_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_TAX_NOT_FOUND,
+                "errorCode": err::CODE_TAX_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/taxes/{id}/components")]
+async fn list_components(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let query = state.taxes.get(path.into_inner());
+    let tax = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::TaxNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state.taxes.list_components(tax.id);
+    let components = match query.await {
+        Ok(components) => components,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let models: Vec<TaxComponentModel> = components.iter().map(TaxComponentModel::from).collect();
+    HttpResponse::Ok().json(models)
+}
+
+#[utoipa::path(
+    tag = "Taxes",
+    summary = "Set the components of a tax group",
+    description = "Turn the tax into a group of other taxes, e.g. a federal and a provincial tax or an eco-fee with VAT on top. \
+        Lines with the group are taxed with each component in the given order, a compound component on the net amount plus the tax of the components before it. \
+        The group's own rate is not used anymore; its lines are posted once per component on the component's account. \
+        Groups can't contain other groups.",
+    responses(
+        (status = 200, description = "Returns the components in the order they are levied", body = Vec<TaxComponentModel>),
+        (status = 400, description = err::MESSAGE_INVALID_TAX_COMPONENTS, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_TAX_COMPONENTS,
+                "errorCode": err::CODE_INVALID_TAX_COMPONENTS,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_TAX_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_TAX_NOT_FOUND,
+                "errorCode": err::CODE_TAX_NOT_FOUND,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_NESTED_TAX_GROUP, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_NESTED_TAX_GROUP,
+                "errorCode": err::CODE_NESTED_TAX_GROUP,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[put("/api/taxes/{id}/components")]
+async fn set_components(
+    path: web::Path<i32>,
+    payload: Json<SetTaxComponentsInput>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let group_id = path.into_inner();
+    let components: Vec<(i32, bool)> = payload
+        .components
+        .iter()
+        .map(|component| (component.tax_id, component.compound))
+        .collect();
+
+    for (index, (tax_id, _)) in components.iter().enumerate() {
+        let repeated = components[..index].iter().any(|(other, _)| other == tax_id);
+        if *tax_id == group_id || repeated {
+            return ApiErrors::InvalidTaxComponents.into();
+        }
+    }
+
+    let query = state.taxes.set_components(group_id, components);
+    let components = match query.await {
+        Ok(components) => components,
+        Err(DbErr::RecordNotFound(_)) => return ApiErrors::TaxNotFound.into(),
+        Err(DbErr::Custom(message)) if message == ERR_NESTED_TAX_GROUP => {
+            return ApiErrors::NestedTaxGroup.into();
+        }
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let models: Vec<TaxComponentModel> = components.iter().map(TaxComponentModel::from).collect();
+    HttpResponse::Ok().json(models)
+}
+
+/// Loads the taxes of document lines with the rates they had on the document
+/// date, which the document keeps even if a rate changed since.
+pub(crate) async fn resolve_taxes(
+    tax_ids: impl IntoIterator<Item = i32>,
+    date: NaiveDate,
+    state: &AppState,
+) -> Result<HashMap<i32, ResolvedTax>, HttpResponse> {
+    let mut taxes = HashMap::new();
+    for tax_id in tax_ids {
+        if taxes.contains_key(&tax_id) {
+            continue;
+        }
+
+        match state.taxes.resolve_at(tax_id, date).await {
+            Ok(Some(tax)) => taxes.insert(tax_id, tax),
+            Ok(None) => continue,
+            Err(_) => return Err(ApiErrors::InternalServerError.into()),
+        };
+    }
+    Ok(taxes)
+}
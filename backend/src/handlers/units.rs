@@ -0,0 +1,173 @@
+use actix_web::{HttpResponse, delete, get, post, web};
+use actix_web_validator::Json;
+use sea_orm::DbErr;
+
+use crate::{
+    err::{self, ApiErrors},
+    models::{CreateUnitInput, UnitModel},
+    repositories::ERR_UNIT_EXISTS,
+    state::AppState,
+    utils::units::conversion_factor,
+};
+
+#[utoipa::path(
+    tag = "Units",
+    summary = "Create a unit of measure",
+    description = "Create a base unit like piece or kg, or a multiple of one like a box of 12 pieces. \
+        Quantities convert between units with the same base unit.",
+    responses(
+        (status = 200, description = "Returns the created unit", body = UnitModel),
+        (status = 400, description = err::MESSAGE_INVALID_BASE_UNIT, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_BASE_UNIT,
+                "errorCode": err::CODE_INVALID_BASE_UNIT,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_UNIT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_UNIT_NOT_FOUND,
+                "errorCode": err::CODE_UNIT_NOT_FOUND,
+            })),
+        )),
+        (status = 409, description = err::MESSAGE_UNIT_EXISTS, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_UNIT_EXISTS,
+                "errorCode": err::CODE_UNIT_EXISTS,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/units")]
+async fn create(payload: Json<CreateUnitInput>, state: web::Data<AppState>) -> HttpResponse {
+    let payload = payload.into_inner();
+
+    let factor = match payload.base_unit_id {
+        Some(base_unit_id) => {
+            let base = match state.units.get(base_unit_id).await {
+                Ok(Some(entity)) => entity,
+                Ok(None) => return ApiErrors::UnitNotFound.into(),
+                Err(_) => return ApiErrors::InternalServerError.into(),
+            };
+            if base.base_unit_id.is_some() {
+                return ApiErrors::InvalidBaseUnit.into();
+            }
+            payload.factor
+        }
+        None => 1.0,
+    };
+
+    let query = state.units.create(
+        payload.name.trim().to_string(),
+        payload.symbol.trim().to_string(),
+        payload.base_unit_id,
+        factor,
+    );
+    let entity = match query.await {
+        Ok(entity) => entity,
+        Err(DbErr::Custom(message)) if message == ERR_UNIT_EXISTS => {
+            return ApiErrors::UnitExists.into();
+        }
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let model = UnitModel::from(&entity);
+    HttpResponse::Ok().json(model)
+}
+
+#[utoipa::path(
+    tag = "Units",
+    summary = "List units of measure",
+    description = "List all units of measure by name",
+    responses(
+        (status = 200, description = "Returns the units", body = Vec<UnitModel>),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/units")]
+async fn list(state: web::Data<AppState>) -> HttpResponse {
+    let entities = match state.units.list().await {
+        Ok(entities) => entities,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let models: Vec<UnitModel> = entities.iter().map(UnitModel::from).collect();
+    HttpResponse::Ok().json(models)
+}
+
+#[utoipa::path(
+    tag = "Units",
+    summary = "Delete a unit of measure",
+    description = "Delete a unit which no variant, order line or other unit refers to",
+    responses(
+        (status = 204, description = "Unit has been deleted"),
+        (status = 404, description = err::MESSAGE_UNIT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_UNIT_NOT_FOUND,
+                "errorCode": err::CODE_UNIT_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[delete("/api/units/{id}")]
+async fn delete(path: web::Path<i32>, state: web::Data<AppState>) -> HttpResponse {
+    let query = state.units.delete(path.into_inner());
+    match query.await {
+        Ok(true) => HttpResponse::NoContent().finish(),
+        Ok(false) => ApiErrors::UnitNotFound.into(),
+        Err(_) => ApiErrors::InternalServerError.into(),
+    }
+}
+
+/// Resolves the unit of an order line or stock move, the variant's unit if
+/// none is given, and the factor converting its quantity into the variant's
+/// unit. Another unit than the variant's needs the same base unit.
+pub(crate) async fn resolve_line_unit(
+    unit_id: Option<i32>,
+    variant: Option<&entity::variants::Model>,
+    state: &AppState,
+) -> Result<(Option<i32>, f64), HttpResponse> {
+    let variant_unit_id = variant.and_then(|variant| variant.unit_id);
+    let Some(unit_id) = unit_id else {
+        return Ok((variant_unit_id, 1.0));
+    };
+
+    let unit = match state.units.get(unit_id).await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return Err(ApiErrors::UnitNotFound.into()),
+        Err(_) => return Err(ApiErrors::InternalServerError.into()),
+    };
+
+    match (variant, variant_unit_id) {
+        (None, _) => Ok((Some(unit.id), 1.0)),
+        (Some(_), Some(variant_unit_id)) if variant_unit_id == unit.id => Ok((Some(unit.id), 1.0)),
+        (Some(_), Some(variant_unit_id)) => {
+            // Variants can't outlive their unit.
+            let variant_unit = match state.units.get(variant_unit_id).await {
+                Ok(Some(entity)) => entity,
+                Ok(None) | Err(_) => return Err(ApiErrors::InternalServerError.into()),
+            };
+            match conversion_factor(&unit, &variant_unit) {
+                Some(factor) => Ok((Some(unit.id), factor)),
+                None => Err(ApiErrors::IncompatibleUnit.into()),
+            }
+        }
+        (Some(_), None) => Err(ApiErrors::IncompatibleUnit.into()),
+    }
+}
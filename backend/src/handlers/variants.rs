@@ -1,10 +1,19 @@
 use actix_web::{HttpResponse, delete, get, post, web};
 use actix_web_validator::{Json, Path};
+use sea_orm::DbErr;
 
 use crate::{
     err::{self, ApiErrors},
-    models::{CreateVariantInput, ListVariantsInput, Page, VariantModel, VariantsPath},
+    models::{
+        CreateVariantInput, DEFAULT_VARIANT_NAME_TEMPLATE, GenerateVariantsInput,
+        ListVariantsInput, LotModel, Page, StockAvailabilityModel, VariantModel, VariantsPath,
+    },
+    repositories::{ERR_VARIANT_CODE_EXISTS, GeneratedVariant, VariantDraft},
     state::AppState,
+    utils::{
+        gtin::normalize_gtin,
+        variants::{combinations, variant_name},
+    },
 };
 
 #[utoipa::path()]
@@ -12,6 +21,12 @@ use crate::{
 async fn create(payload: Json<CreateVariantInput>, state: web::Data<AppState>) -> HttpResponse {
     let payload = payload.into_inner();
 
+    let gtin = match payload.gtin.as_deref().map(normalize_gtin) {
+        Some(Some(gtin)) => Some(gtin),
+        Some(None) => return ApiErrors::InvalidGtin.into(),
+        None => None,
+    };
+
     let query = state.products.get(payload.product_id);
     let product_exists = match query.await {
         Ok(Some(_)) => true,
@@ -22,14 +37,28 @@ async fn create(payload: Json<CreateVariantInput>, state: web::Data<AppState>) -
         return HttpResponse::NotFound().finish();
     }
 
-    let query = state.variants.create(
-        payload.product_id,
-        payload.name,
-        payload.sale_price,
-        payload.purchase_price,
-    );
+    if let Some(unit_id) = payload.unit_id {
+        match state.units.get(unit_id).await {
+            Ok(Some(_)) => {}
+            Ok(None) => return ApiErrors::UnitNotFound.into(),
+            Err(_) => return ApiErrors::InternalServerError.into(),
+        }
+    }
+
+    let variant = VariantDraft {
+        name: payload.name,
+        sale_price: payload.sale_price,
+        purchase_price: payload.purchase_price,
+        sku: payload.sku.map(|sku| sku.trim().to_string()),
+        gtin,
+        unit_id: payload.unit_id,
+    };
+    let query = state.variants.create(payload.product_id, variant);
     let entity = match query.await {
         Ok(entity) => entity,
+        Err(DbErr::Custom(message)) if message == ERR_VARIANT_CODE_EXISTS => {
+            return ApiErrors::VariantCodeExists.into();
+        }
         Err(_) => return HttpResponse::InternalServerError().finish(),
     };
 
@@ -89,6 +118,125 @@ async fn get(path: Path<VariantsPath>, state: web::Data<AppState>) -> HttpRespon
     HttpResponse::Ok().json(model)
 }
 
+#[utoipa::path(
+    tag = "Variants",
+    summary = "Available to promise",
+    description = "Get what is on hand of a variant in each of the own stocks but consignment stocks at customers, how much of it confirmed sale orders have reserved and what is left to promise.",
+    responses(
+        (status = 200, description = "Returns the availability per stock", body = Vec<StockAvailabilityModel>),
+        (status = 404, description = err::MESSAGE_VARIANT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_VARIANT_NOT_FOUND,
+                "errorCode": err::CODE_VARIANT_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/variants/{id}/availability")]
+async fn availability(path: Path<VariantsPath>, state: web::Data<AppState>) -> HttpResponse {
+    let id = path.into_inner().id;
+
+    let variant = match state.variants.get(id).await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::VariantNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state
+        .stock_reservations
+        .availability(vec![variant.id], None);
+    let availability = match query.await {
+        Ok(availability) => availability,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let models: Vec<StockAvailabilityModel> = availability
+        .iter()
+        .map(StockAvailabilityModel::from)
+        .collect();
+    HttpResponse::Ok().json(models)
+}
+
+#[utoipa::path(
+    tag = "Variants",
+    summary = "List the lots of a variant",
+    description = "List the lots and serial numbers the variant was received in, the oldest first",
+    responses(
+        (status = 200, description = "Returns the lots", body = Vec<LotModel>),
+        (status = 404, description = err::MESSAGE_VARIANT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_VARIANT_NOT_FOUND,
+                "errorCode": err::CODE_VARIANT_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/variants/{id}/lots")]
+async fn lots(path: Path<VariantsPath>, state: web::Data<AppState>) -> HttpResponse {
+    let id = path.into_inner().id;
+
+    let variant = match state.variants.get(id).await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::VariantNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let lots = match state.lots.list(variant.id).await {
+        Ok(lots) => lots,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let models: Vec<LotModel> = lots.iter().map(LotModel::from).collect();
+    HttpResponse::Ok().json(models)
+}
+
+#[utoipa::path(
+    tag = "Variants",
+    summary = "Find a variant by code",
+    description = "Find the variant with the SKU or GTIN, e.g. as read by a barcode scanner",
+    responses(
+        (status = 200, description = "Returns the variant", body = VariantModel),
+        (status = 404, description = err::MESSAGE_VARIANT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_VARIANT_NOT_FOUND,
+                "errorCode": err::CODE_VARIANT_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[get("/api/variants/by-code/{code}")]
+async fn get_by_code(path: web::Path<String>, state: web::Data<AppState>) -> HttpResponse {
+    let code = path.into_inner().trim().to_string();
+
+    let query = state.variants.find_by_code(code);
+    let entity = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::VariantNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let model = VariantModel::from(&entity);
+    HttpResponse::Ok().json(model)
+}
+
 #[utoipa::path(
     tag = "Variants",
     summary = "Delete a variant",
@@ -131,3 +279,107 @@ async fn delete(path: Path<VariantsPath>, state: web::Data<AppState>) -> HttpRes
 
     HttpResponse::NoContent().finish()
 }
+
+#[utoipa::path(
+    tag = "Variants",
+    summary = "Generate the variants of a product",
+    description = "Create a variant for each combination of the values of the product's attributes which has none yet, e.g. S/Red, S/Blue, M/Red and so on. \
+        The variants are named after the template and get the default prices unless an override for their combination is given. \
+        Generating again after adding a value only creates the new combinations.",
+    responses(
+        (status = 200, description = "Returns the created variants", body = Vec<VariantModel>),
+        (status = 400, description = err::MESSAGE_INVALID_VARIANT_OVERRIDE, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INVALID_VARIANT_OVERRIDE,
+                "errorCode": err::CODE_INVALID_VARIANT_OVERRIDE,
+            })),
+        )),
+        (status = 404, description = err::MESSAGE_PRODUCT_NOT_FOUND, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_PRODUCT_NOT_FOUND,
+                "errorCode": err::CODE_PRODUCT_NOT_FOUND,
+            })),
+        )),
+        (status = 500, description = err::MESSAGE_INTERNAL_SERVER_ERROR, content(
+            (String = "application/json", example = json!({
+                "error": err::MESSAGE_INTERNAL_SERVER_ERROR,
+                "errorCode": err::CODE_INTERNAL_SERVER_ERROR,
+            })),
+        )),
+    )
+)]
+#[post("/api/products/{id}/variants/generate")]
+async fn generate(
+    path: web::Path<i32>,
+    payload: Json<GenerateVariantsInput>,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let payload = payload.into_inner();
+
+    let query = state.products.get(path.into_inner());
+    let product = match query.await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => return ApiErrors::ProductNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let query = state.product_attributes.list_for_product(product.id);
+    let attributes = match query.await {
+        Ok(attributes) => attributes,
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let combinations: Vec<(Vec<&entity::product_attribute_values::Model>, Vec<i32>)> =
+        combinations(&attributes)
+            .into_iter()
+            .map(|combination| {
+                let mut value_ids: Vec<i32> = combination.iter().map(|value| value.id).collect();
+                value_ids.sort_unstable();
+                (combination, value_ids)
+            })
+            .collect();
+
+    let mut overrides = Vec::with_capacity(payload.overrides.len());
+    for price_override in payload.overrides {
+        let mut value_ids = price_override.value_ids.clone();
+        value_ids.sort_unstable();
+        if !combinations.iter().any(|(_, ids)| *ids == value_ids) {
+            return ApiErrors::InvalidVariantOverride.into();
+        }
+        overrides.push((value_ids, price_override));
+    }
+
+    let template = payload
+        .name_template
+        .as_deref()
+        .unwrap_or(DEFAULT_VARIANT_NAME_TEMPLATE);
+    let variants = combinations
+        .iter()
+        .map(|(combination, value_ids)| {
+            let price_override = overrides
+                .iter()
+                .find(|(ids, _)| ids == value_ids)
+                .map(|(_, price_override)| price_override);
+            GeneratedVariant {
+                name: variant_name(template, &product.name, &attributes, combination),
+                sale_price: price_override
+                    .and_then(|price_override| price_override.sale_price)
+                    .unwrap_or(payload.sale_price),
+                purchase_price: price_override
+                    .and_then(|price_override| price_override.purchase_price)
+                    .unwrap_or(payload.purchase_price),
+                value_ids: combination.iter().map(|value| value.id).collect(),
+            }
+        })
+        .collect();
+
+    let query = state.variants.generate(product.id, variants);
+    let entities = match query.await {
+        Ok(entities) => entities,
+        Err(DbErr::RecordNotFound(_)) => return ApiErrors::ProductNotFound.into(),
+        Err(_) => return ApiErrors::InternalServerError.into(),
+    };
+
+    let models: Vec<VariantModel> = entities.iter().map(VariantModel::from).collect();
+    HttpResponse::Ok().json(models)
+}
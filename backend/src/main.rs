@@ -1,3 +1,4 @@
+pub mod documents;
 pub mod err;
 pub mod handlers;
 pub mod models;
@@ -15,7 +16,7 @@ use sea_orm::{ConnectOptions, Database, DatabaseConnection};
 use tracing_actix_web::TracingLogger;
 use utoipa::OpenApi;
 
-use crate::state::AppState;
+use crate::{documents::Letterhead, state::AppState, utils::tax_rules::is_country_code};
 
 #[derive(OpenApi)]
 #[openapi(paths(
@@ -27,7 +28,17 @@ use crate::state::AppState;
     handlers::contacts::get_supplier,
     handlers::contacts::list_customers,
     handlers::contacts::list_suppliers,
+    handlers::credit_notes::create_purchase,
+    handlers::credit_notes::create_sale,
+    handlers::credit_notes::list_purchase,
+    handlers::credit_notes::list_sale,
     handlers::info::get,
+    handlers::inventory_counts::approve,
+    handlers::inventory_counts::create,
+    handlers::inventory_counts::get,
+    handlers::inventory_counts::record,
+    handlers::invoice_postings::post_purchase,
+    handlers::invoice_postings::post_sale,
     handlers::ledger_accounts::create,
     handlers::ledger_accounts::delete,
     handlers::ledger_accounts::get,
@@ -36,6 +47,22 @@ use crate::state::AppState;
     handlers::ledger_transactions::delete,
     handlers::ledger_transactions::get,
     handlers::ledger_transactions::list,
+    handlers::lots::trace,
+    handlers::number_sequences::list,
+    handlers::number_sequences::update,
+    handlers::price_lists::create,
+    handlers::price_lists::create_item,
+    handlers::price_lists::delete,
+    handlers::price_lists::get,
+    handlers::price_lists::list,
+    handlers::price_lists::set_customers,
+    handlers::product_attributes::create,
+    handlers::product_attributes::delete,
+    handlers::product_attributes::list,
+    handlers::product_categories::create,
+    handlers::product_categories::delete,
+    handlers::product_categories::get,
+    handlers::product_categories::list,
     handlers::products::create,
     handlers::products::delete,
     handlers::products::get,
@@ -47,31 +74,47 @@ use crate::state::AppState;
     handlers::purchase_invoices::create,
     handlers::purchase_invoices::delete,
     handlers::purchase_invoices::get,
+    handlers::purchase_invoices::import,
     handlers::purchase_invoices::list,
     handlers::purchase_order_lines::create,
     handlers::purchase_order_lines::delete,
     handlers::purchase_order_lines::get,
     handlers::purchase_order_lines::list,
+    handlers::purchase_orders::confirm,
     handlers::purchase_orders::create,
     handlers::purchase_orders::delete,
     handlers::purchase_orders::get,
     handlers::purchase_orders::list,
+    handlers::purchase_orders::receive,
+    handlers::reorder_rules::create,
+    handlers::reorder_rules::delete,
+    handlers::reorder_rules::list,
+    handlers::reorder_rules::replenish,
+    handlers::reports::inventory_valuation,
+    handlers::reports::ustva,
+    handlers::reports::vat,
     handlers::sale_invoice_lines::create,
     handlers::sale_invoice_lines::delete,
     handlers::sale_invoice_lines::get,
     handlers::sale_invoice_lines::list,
     handlers::sale_invoices::create,
     handlers::sale_invoices::delete,
+    handlers::sale_invoices::einvoice,
     handlers::sale_invoices::get,
     handlers::sale_invoices::list,
+    handlers::sale_invoices::pdf,
     handlers::sale_order_lines::create,
     handlers::sale_order_lines::delete,
     handlers::sale_order_lines::get,
     handlers::sale_order_lines::list,
+    handlers::sale_orders::cancel,
+    handlers::sale_orders::confirm,
     handlers::sale_orders::create,
     handlers::sale_orders::delete,
+    handlers::sale_orders::deliver,
     handlers::sale_orders::get,
     handlers::sale_orders::list,
+    handlers::sale_orders::pdf,
     handlers::stock_moves::create,
     handlers::stock_moves::delete,
     handlers::stock_moves::get,
@@ -82,18 +125,39 @@ use crate::state::AppState;
     handlers::stocks::get,
     handlers::stocks::list_contract,
     handlers::stocks::list_own,
+    handlers::stocks::list_virtual,
+    handlers::stocks::quantities,
+    handlers::stocks::report_consumption,
+    handlers::supplier_prices::create,
+    handlers::supplier_prices::delete,
+    handlers::supplier_prices::list,
+    handlers::tax_rules::create,
+    handlers::tax_rules::delete,
+    handlers::tax_rules::determine,
+    handlers::tax_rules::list,
     handlers::taxes::create,
+    handlers::taxes::create_rate,
     handlers::taxes::delete,
     handlers::taxes::get,
     handlers::taxes::list,
+    handlers::taxes::list_components,
+    handlers::taxes::list_rates,
+    handlers::taxes::set_components,
+    handlers::units::create,
+    handlers::units::delete,
+    handlers::units::list,
     handlers::users::create,
     handlers::users::delete,
     handlers::users::get,
     handlers::users::list,
+    handlers::variants::availability,
     handlers::variants::create,
     handlers::variants::delete,
+    handlers::variants::generate,
     handlers::variants::get,
+    handlers::variants::get_by_code,
     handlers::variants::list,
+    handlers::variants::lots,
 ))]
 struct ApiDoc;
 
@@ -142,9 +206,20 @@ async fn main() -> std::io::Result<()> {
     dotenv().ok();
     tracing_subscriber::fmt().init();
 
+    // Tax regions are determined by comparing the contact's country with
+    // the company's.
+    let letterhead = Letterhead::from_env();
+    if !is_country_code(&letterhead.country) {
+        tracing::error!(
+            "COMPANY_COUNTRY must be an ISO 3166-1 alpha-2 code like DE, got {:?}",
+            letterhead.country
+        );
+        std::process::exit(1);
+    }
+
     let app_state = {
         let db = connect_database().await;
-        AppState::new(db)
+        AppState::new(db, letterhead)
     };
 
     tracing::info!("Start web server");
@@ -165,7 +240,17 @@ async fn main() -> std::io::Result<()> {
             .service(handlers::contacts::get_supplier)
             .service(handlers::contacts::list_customers)
             .service(handlers::contacts::list_suppliers)
+            .service(handlers::credit_notes::create_purchase)
+            .service(handlers::credit_notes::create_sale)
+            .service(handlers::credit_notes::list_purchase)
+            .service(handlers::credit_notes::list_sale)
             .service(handlers::info::get)
+            .service(handlers::inventory_counts::approve)
+            .service(handlers::inventory_counts::create)
+            .service(handlers::inventory_counts::get)
+            .service(handlers::inventory_counts::record)
+            .service(handlers::invoice_postings::post_purchase)
+            .service(handlers::invoice_postings::post_sale)
             .service(handlers::ledger_accounts::create)
             .service(handlers::ledger_accounts::delete)
             .service(handlers::ledger_accounts::get)
@@ -174,6 +259,22 @@ async fn main() -> std::io::Result<()> {
             .service(handlers::ledger_transactions::delete)
             .service(handlers::ledger_transactions::get)
             .service(handlers::ledger_transactions::list)
+            .service(handlers::lots::trace)
+            .service(handlers::number_sequences::list)
+            .service(handlers::number_sequences::update)
+            .service(handlers::price_lists::create)
+            .service(handlers::price_lists::create_item)
+            .service(handlers::price_lists::delete)
+            .service(handlers::price_lists::get)
+            .service(handlers::price_lists::list)
+            .service(handlers::price_lists::set_customers)
+            .service(handlers::product_attributes::create)
+            .service(handlers::product_attributes::delete)
+            .service(handlers::product_attributes::list)
+            .service(handlers::product_categories::create)
+            .service(handlers::product_categories::delete)
+            .service(handlers::product_categories::get)
+            .service(handlers::product_categories::list)
             .service(handlers::products::create)
             .service(handlers::products::delete)
             .service(handlers::products::get)
@@ -185,31 +286,47 @@ async fn main() -> std::io::Result<()> {
             .service(handlers::purchase_invoices::create)
             .service(handlers::purchase_invoices::delete)
             .service(handlers::purchase_invoices::get)
+            .service(handlers::purchase_invoices::import)
             .service(handlers::purchase_invoices::list)
             .service(handlers::purchase_order_lines::create)
             .service(handlers::purchase_order_lines::delete)
             .service(handlers::purchase_order_lines::get)
             .service(handlers::purchase_order_lines::list)
+            .service(handlers::purchase_orders::confirm)
             .service(handlers::purchase_orders::create)
             .service(handlers::purchase_orders::delete)
             .service(handlers::purchase_orders::get)
             .service(handlers::purchase_orders::list)
+            .service(handlers::purchase_orders::receive)
+            .service(handlers::reorder_rules::create)
+            .service(handlers::reorder_rules::delete)
+            .service(handlers::reorder_rules::list)
+            .service(handlers::reorder_rules::replenish)
+            .service(handlers::reports::inventory_valuation)
+            .service(handlers::reports::ustva)
+            .service(handlers::reports::vat)
             .service(handlers::sale_invoice_lines::create)
             .service(handlers::sale_invoice_lines::delete)
             .service(handlers::sale_invoice_lines::get)
             .service(handlers::sale_invoice_lines::list)
             .service(handlers::sale_invoices::create)
             .service(handlers::sale_invoices::delete)
+            .service(handlers::sale_invoices::einvoice)
             .service(handlers::sale_invoices::get)
             .service(handlers::sale_invoices::list)
+            .service(handlers::sale_invoices::pdf)
             .service(handlers::sale_order_lines::create)
             .service(handlers::sale_order_lines::delete)
             .service(handlers::sale_order_lines::get)
             .service(handlers::sale_order_lines::list)
+            .service(handlers::sale_orders::cancel)
+            .service(handlers::sale_orders::confirm)
             .service(handlers::sale_orders::create)
             .service(handlers::sale_orders::delete)
+            .service(handlers::sale_orders::deliver)
             .service(handlers::sale_orders::get)
             .service(handlers::sale_orders::list)
+            .service(handlers::sale_orders::pdf)
             .service(handlers::stock_moves::create)
             .service(handlers::stock_moves::delete)
             .service(handlers::stock_moves::get)
@@ -217,21 +334,42 @@ async fn main() -> std::io::Result<()> {
             .service(handlers::stocks::create_contact)
             .service(handlers::stocks::create_own)
             .service(handlers::stocks::delete)
+            .service(handlers::stocks::list_virtual)
             .service(handlers::stocks::get)
             .service(handlers::stocks::list_contract)
             .service(handlers::stocks::list_own)
+            .service(handlers::stocks::quantities)
+            .service(handlers::stocks::report_consumption)
+            .service(handlers::supplier_prices::create)
+            .service(handlers::supplier_prices::delete)
+            .service(handlers::supplier_prices::list)
+            .service(handlers::tax_rules::create)
+            .service(handlers::tax_rules::delete)
+            .service(handlers::tax_rules::determine)
+            .service(handlers::tax_rules::list)
             .service(handlers::taxes::create)
+            .service(handlers::taxes::create_rate)
             .service(handlers::taxes::delete)
             .service(handlers::taxes::get)
             .service(handlers::taxes::list)
+            .service(handlers::taxes::list_components)
+            .service(handlers::taxes::list_rates)
+            .service(handlers::taxes::set_components)
+            .service(handlers::units::create)
+            .service(handlers::units::delete)
+            .service(handlers::units::list)
             .service(handlers::users::create)
             .service(handlers::users::delete)
             .service(handlers::users::get)
             .service(handlers::users::list)
+            .service(handlers::variants::availability)
             .service(handlers::variants::create)
             .service(handlers::variants::delete)
+            .service(handlers::variants::generate)
+            .service(handlers::variants::get_by_code)
             .service(handlers::variants::get)
             .service(handlers::variants::list)
+            .service(handlers::variants::lots)
     })
     .bind(("0.0.0.0", 5000))?
     .run()
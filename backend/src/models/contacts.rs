@@ -20,6 +20,9 @@ pub struct ContactModel {
     pub postal_code: String,
     pub city: String,
     pub country: String,
+    #[serde(rename = "vatId")]
+    pub vat_id: String,
+    pub currency: String,
 }
 
 impl From<&entity::contacts::Model> for ContactModel {
@@ -36,6 +39,8 @@ impl From<&entity::contacts::Model> for ContactModel {
             postal_code: entity.postal_code.clone(),
             city: entity.city.clone(),
             country: entity.country.clone(),
+            vat_id: entity.vat_id.clone(),
+            currency: entity.currency.clone(),
         }
     }
 }
@@ -58,6 +63,13 @@ pub struct CreateContactInput {
     pub city: String,
     #[validate(length(max = 128))]
     pub country: String,
+    /// ISO 4217 code of the currency the contact is quoted in, EUR by default.
+    #[serde(default = "default_currency")]
+    pub currency: String,
+}
+
+fn default_currency() -> String {
+    "EUR".to_string()
 }
 
 #[derive(Deserialize, Validate)]
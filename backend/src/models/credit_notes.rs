@@ -0,0 +1,163 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+use super::LineLotInput;
+
+#[derive(Serialize, ToSchema)]
+pub struct CreditNoteLineModel {
+    pub id: i32,
+    #[serde(rename = "invoiceLineId")]
+    pub invoice_line_id: i32,
+    pub description: String,
+    pub quantity: f64,
+    pub price: i32,
+    pub tax: i32,
+    #[serde(rename = "taxId")]
+    pub tax_id: Option<i32>,
+    #[serde(rename = "variantId")]
+    pub variant_id: Option<i32>,
+    #[serde(rename = "transactionId")]
+    pub transaction_id: Option<i32>,
+    #[serde(rename = "taxTransactionId")]
+    pub tax_transaction_id: Option<i32>,
+    #[serde(rename = "moveId")]
+    pub move_id: Option<i32>,
+    /// The credited share of the invoice line's discounts in cents,
+    /// including its share of the invoice discount.
+    #[serde(rename = "discountAmount")]
+    pub discount_amount: i32,
+    #[serde(rename = "discountTransactionId")]
+    pub discount_transaction_id: Option<i32>,
+}
+
+impl From<&entity::sale_credit_note_lines::Model> for CreditNoteLineModel {
+    fn from(entity: &entity::sale_credit_note_lines::Model) -> Self {
+        Self {
+            id: entity.id,
+            invoice_line_id: entity.invoice_line_id,
+            description: entity.description.clone(),
+            quantity: entity.quantity,
+            price: entity.price,
+            tax: entity.tax,
+            tax_id: entity.tax_id,
+            variant_id: entity.variant_id,
+            transaction_id: entity.transaction_id,
+            tax_transaction_id: entity.tax_transaction_id,
+            move_id: entity.move_id,
+            discount_amount: entity.discount_amount,
+            discount_transaction_id: entity.discount_transaction_id,
+        }
+    }
+}
+
+impl From<&entity::purchase_credit_note_lines::Model> for CreditNoteLineModel {
+    fn from(entity: &entity::purchase_credit_note_lines::Model) -> Self {
+        Self {
+            id: entity.id,
+            invoice_line_id: entity.invoice_line_id,
+            description: entity.description.clone(),
+            quantity: entity.quantity,
+            price: entity.price,
+            tax: entity.tax,
+            tax_id: entity.tax_id,
+            variant_id: entity.variant_id,
+            transaction_id: entity.transaction_id,
+            tax_transaction_id: entity.tax_transaction_id,
+            move_id: entity.move_id,
+            discount_amount: entity.discount_amount,
+            discount_transaction_id: entity.discount_transaction_id,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct CreditNoteModel {
+    pub id: i32,
+    pub reference: String,
+    pub date: String,
+    #[serde(rename = "invoiceId")]
+    pub invoice_id: i32,
+    pub reason: String,
+    pub lines: Vec<CreditNoteLineModel>,
+    /// The credited amount in cents including tax.
+    #[serde(rename = "grossTotal")]
+    pub gross_total: i64,
+}
+
+impl CreditNoteModel {
+    pub fn sale(
+        entity: &entity::sale_credit_notes::Model,
+        lines: Vec<CreditNoteLineModel>,
+        gross_total: i64,
+    ) -> Self {
+        Self {
+            id: entity.id,
+            reference: entity.reference.clone(),
+            date: entity.date.to_string(),
+            invoice_id: entity.invoice_id,
+            reason: entity.reason.clone(),
+            lines,
+            gross_total,
+        }
+    }
+
+    pub fn purchase(
+        entity: &entity::purchase_credit_notes::Model,
+        lines: Vec<CreditNoteLineModel>,
+        gross_total: i64,
+    ) -> Self {
+        Self {
+            id: entity.id,
+            reference: entity.reference.clone(),
+            date: entity.date.to_string(),
+            invoice_id: entity.invoice_id,
+            reason: entity.reason.clone(),
+            lines,
+            gross_total,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct InvoiceCreditNotesModel {
+    #[serde(rename = "invoiceTotal")]
+    pub invoice_total: i64,
+    #[serde(rename = "creditedTotal")]
+    pub credited_total: i64,
+    /// What is left to pay of the invoice after the credit notes.
+    #[serde(rename = "openAmount")]
+    pub open_amount: i64,
+    #[serde(rename = "creditNotes")]
+    pub credit_notes: Vec<CreditNoteModel>,
+}
+
+#[derive(Serialize, Deserialize, Validate, ToSchema)]
+pub struct CreditNoteLineInput {
+    #[serde(rename = "invoiceLineId")]
+    #[validate(range(min = 1))]
+    pub invoice_line_id: i32,
+    #[validate(range(exclusive_min = 0.0))]
+    pub quantity: f64,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct CreateCreditNoteInput {
+    pub date: String,
+    #[serde(default)]
+    #[validate(length(max = 1000))]
+    pub reason: String,
+    /// Moves the goods of the credited lines back to where they came from.
+    /// Consumables and services aren't returned to stock.
+    #[serde(rename = "returnStock", default)]
+    pub return_stock: bool,
+    /// The lines and quantities to credit. Without lines everything that
+    /// is left of the invoice is credited.
+    #[validate(length(min = 1), nested)]
+    pub lines: Option<Vec<CreditNoteLineInput>>,
+    /// The lots and serial numbers the returned goods of lot or serial
+    /// tracked variants are moved in. Their `lineId` is the invoice line's.
+    #[serde(default)]
+    #[validate(length(max = 1000), nested)]
+    pub lots: Vec<LineLotInput>,
+}
@@ -0,0 +1,145 @@
+use entity::sea_orm_active_enums::InventoryCountStatus;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::utils::inventory_counts::counted;
+
+/// What a counter found of a line's variant, in the variant's unit.
+#[derive(Serialize, ToSchema)]
+pub struct InventoryCountEntryModel {
+    pub counter: String,
+    pub quantity: f64,
+}
+
+impl From<&entity::inventory_count_entries::Model> for InventoryCountEntryModel {
+    fn from(entity: &entity::inventory_count_entries::Model) -> Self {
+        Self {
+            counter: entity.counter.clone(),
+            quantity: entity.quantity,
+        }
+    }
+}
+
+/// A variant, or a lot of a tracked variant, of the counted location with
+/// what should be there and what the counters found, in the variant's unit.
+#[derive(Serialize, ToSchema)]
+pub struct InventoryCountLineModel {
+    pub id: i32,
+    #[serde(rename = "variantId")]
+    pub variant_id: i32,
+    /// The lot or serial number the line counts, `null` for untracked
+    /// variants and for what a tracked variant has outside of its lots.
+    #[serde(rename = "lotNumber")]
+    pub lot_number: Option<String>,
+    #[serde(rename = "expectedQuantity")]
+    pub expected_quantity: f64,
+    /// The sum of the counters' entries, or `null` while nobody has counted
+    /// the variant.
+    #[serde(rename = "countedQuantity")]
+    pub counted_quantity: Option<f64>,
+    pub entries: Vec<InventoryCountEntryModel>,
+    /// The move that adjusted the stock to the count once it was approved.
+    #[serde(rename = "moveId")]
+    pub move_id: Option<i32>,
+}
+
+impl InventoryCountLineModel {
+    pub fn new(
+        entity: &entity::inventory_count_lines::Model,
+        entries: &[entity::inventory_count_entries::Model],
+    ) -> Self {
+        Self {
+            id: entity.id,
+            variant_id: entity.variant_id,
+            lot_number: (!entity.lot_number.is_empty()).then(|| entity.lot_number.clone()),
+            expected_quantity: entity.expected_quantity,
+            counted_quantity: counted(entity.id, entries),
+            entries: entries
+                .iter()
+                .filter(|entry| entry.line_id == entity.id)
+                .map(InventoryCountEntryModel::from)
+                .collect(),
+            move_id: entity.move_id,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct InventoryCountModel {
+    pub id: i32,
+    #[serde(rename = "stockId")]
+    pub stock_id: i32,
+    /// `open` while counting, `approved` once the stock was adjusted.
+    pub status: String,
+    pub date: String,
+    pub lines: Vec<InventoryCountLineModel>,
+}
+
+impl InventoryCountModel {
+    pub fn new(
+        entity: &entity::inventory_counts::Model,
+        lines: &[entity::inventory_count_lines::Model],
+        entries: &[entity::inventory_count_entries::Model],
+    ) -> Self {
+        Self {
+            id: entity.id,
+            stock_id: entity.stock_id,
+            status: inventory_count_status_name(&entity.status).to_string(),
+            date: entity.date.to_string(),
+            lines: lines
+                .iter()
+                .map(|line| InventoryCountLineModel::new(line, entries))
+                .collect(),
+        }
+    }
+}
+
+pub fn inventory_count_status_name(status: &InventoryCountStatus) -> &'static str {
+    match status {
+        InventoryCountStatus::Open => "open",
+        InventoryCountStatus::Approved => "approved",
+    }
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct CreateInventoryCountInput {
+    /// The internal location to count. Locations below it are counted on
+    /// their own.
+    #[serde(rename = "stockId")]
+    #[validate(range(min = 1))]
+    pub stock_id: i32,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct RecordInventoryCountInput {
+    #[serde(rename = "variantId")]
+    #[validate(range(min = 1))]
+    pub variant_id: i32,
+    /// Who counted, e.g. a name or a scanner. A counter recording the same
+    /// variant again replaces their earlier count.
+    #[validate(length(min = 1))]
+    pub counter: String,
+    /// The lot or serial number that was counted. Tracked variants are
+    /// counted per lot and need it, untracked ones must leave it out.
+    #[serde(rename = "lotNumber")]
+    #[validate(length(min = 1))]
+    pub lot_number: Option<String>,
+    #[validate(range(min = 0.0))]
+    pub quantity: f64,
+    /// The unit of the quantity, which is converted into the variant's
+    /// unit. Defaults to the variant's unit.
+    #[serde(rename = "unitId")]
+    #[validate(range(min = 1))]
+    pub unit_id: Option<i32>,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct ApproveInventoryCountInput {
+    /// The account the value of inventory gains and losses is posted
+    /// against, with the inventory account of the product category on the
+    /// other side. Nothing is posted without it.
+    #[serde(rename = "accountId")]
+    #[validate(range(min = 1))]
+    pub account_id: Option<i32>,
+}
@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::repositories::LotMove;
+
+/// A lot or batch of a variant, or a serial number of a single piece.
+#[derive(Serialize, ToSchema)]
+pub struct LotModel {
+    pub id: i32,
+    #[serde(rename = "variantId")]
+    pub variant_id: i32,
+    pub number: String,
+    #[serde(rename = "expiryDate")]
+    pub expiry_date: Option<String>,
+}
+
+impl From<&entity::lots::Model> for LotModel {
+    fn from(entity: &entity::lots::Model) -> Self {
+        Self {
+            id: entity.id,
+            variant_id: entity.variant_id,
+            number: entity.number.clone(),
+            expiry_date: entity.expiry_date.map(|date| date.to_string()),
+        }
+    }
+}
+
+/// A move the lot was part of. Moves of purchase and sale orders name the
+/// order and its supplier or customer.
+#[derive(Serialize, ToSchema)]
+pub struct LotTraceMoveModel {
+    #[serde(rename = "moveId")]
+    pub move_id: i32,
+    /// The quantity of the lot, in the variant's unit.
+    pub quantity: f64,
+    #[serde(rename = "sourceStockId")]
+    pub source_stock_id: i32,
+    #[serde(rename = "targetStockId")]
+    pub target_stock_id: i32,
+    #[serde(rename = "purchaseOrderId")]
+    pub purchase_order_id: Option<i32>,
+    #[serde(rename = "saleOrderId")]
+    pub sale_order_id: Option<i32>,
+    #[serde(rename = "contactId")]
+    pub contact_id: Option<i32>,
+    #[serde(rename = "contactName")]
+    pub contact_name: Option<String>,
+}
+
+impl From<&LotMove> for LotTraceMoveModel {
+    fn from(lot_move: &LotMove) -> Self {
+        Self {
+            move_id: lot_move.move_id,
+            quantity: lot_move.quantity,
+            source_stock_id: lot_move.source_stock_id,
+            target_stock_id: lot_move.target_stock_id,
+            purchase_order_id: lot_move.purchase_order_id,
+            sale_order_id: lot_move.sale_order_id,
+            contact_id: lot_move.contact.as_ref().map(|contact| contact.id),
+            contact_name: lot_move
+                .contact
+                .as_ref()
+                .map(|contact| contact.name.clone()),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct LotTraceModel {
+    pub lot: LotModel,
+    pub moves: Vec<LotTraceMoveModel>,
+}
+
+/// The part of a move that belongs to one lot or serial number.
+#[derive(Serialize, Deserialize, Validate, ToSchema)]
+pub struct MoveLotInput {
+    #[validate(length(min = 1, max = 64))]
+    pub number: String,
+    /// In the variant's unit. Always 1 for serial numbers.
+    #[validate(range(exclusive_min = 0.0))]
+    pub quantity: f64,
+    /// `YYYY-MM-DD`. Only kept for lots that are received for the first
+    /// time.
+    #[serde(rename = "expiryDate")]
+    pub expiry_date: Option<String>,
+}
+
+/// The part of an order line's move that belongs to one lot or serial
+/// number.
+#[derive(Serialize, Deserialize, Validate, ToSchema)]
+pub struct LineLotInput {
+    #[serde(rename = "lineId")]
+    #[validate(range(min = 1))]
+    pub line_id: i32,
+    #[validate(length(min = 1, max = 64))]
+    pub number: String,
+    /// In the variant's unit. Always 1 for serial numbers.
+    #[validate(range(exclusive_min = 0.0))]
+    pub quantity: f64,
+    /// `YYYY-MM-DD`. Only kept for lots that are received for the first
+    /// time.
+    #[serde(rename = "expiryDate")]
+    pub expiry_date: Option<String>,
+}
+
+/// The lots and serial numbers the goods of an order's lines are moved in.
+/// Lines of variants that aren't tracked are left out.
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct OrderLotsInput {
+    #[serde(default)]
+    #[validate(length(max = 1000), nested)]
+    pub lots: Vec<LineLotInput>,
+}
@@ -1,37 +1,59 @@
 mod contacts;
+mod credit_notes;
+mod inventory_counts;
 mod ledger_accounts;
 mod ledger_transaction;
+mod lots;
+mod number_sequences;
 mod page;
+mod price_lists;
 mod products;
 mod purchase_invoice_lines;
 mod purchase_invoices;
 mod purchase_order_lines;
 mod purchase_orders;
+mod reorder_rules;
+mod reports;
 mod sale_invoice_lines;
 mod sale_invoices;
 mod sale_order_lines;
 mod sale_orders;
 mod stock_moves;
 mod stocks;
+mod supplier_prices;
+mod tax_rules;
 mod taxes;
+mod totals;
+mod units;
 mod users;
 mod variants;
 
 pub use contacts::*;
+pub use credit_notes::*;
+pub use inventory_counts::*;
 pub use ledger_accounts::*;
 pub use ledger_transaction::*;
+pub use lots::*;
+pub use number_sequences::*;
 pub use page::*;
+pub use price_lists::*;
 pub use products::*;
 pub use purchase_invoice_lines::*;
 pub use purchase_invoices::*;
 pub use purchase_order_lines::*;
 pub use purchase_orders::*;
+pub use reorder_rules::*;
+pub use reports::*;
 pub use sale_invoice_lines::*;
 pub use sale_invoices::*;
 pub use sale_order_lines::*;
 pub use sale_orders::*;
 pub use stock_moves::*;
 pub use stocks::*;
+pub use supplier_prices::*;
+pub use tax_rules::*;
 pub use taxes::*;
+pub use totals::*;
+pub use units::*;
 pub use users::*;
 pub use variants::*;
@@ -0,0 +1,56 @@
+use entity::sea_orm_active_enums::DocumentType;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Serialize, ToSchema)]
+pub struct NumberSequenceModel {
+    #[serde(rename = "documentType")]
+    pub document_type: String,
+    pub pattern: String,
+    pub gapless: bool,
+    pub period: String,
+    #[serde(rename = "nextNumber")]
+    pub next_number: i32,
+}
+
+impl From<&entity::number_sequences::Model> for NumberSequenceModel {
+    fn from(entity: &entity::number_sequences::Model) -> Self {
+        Self {
+            document_type: document_type_name(&entity.document_type).to_string(),
+            pattern: entity.pattern.clone(),
+            gapless: entity.gapless,
+            period: entity.period.clone(),
+            next_number: entity.next_number,
+        }
+    }
+}
+
+pub fn document_type_name(document_type: &DocumentType) -> &'static str {
+    match document_type {
+        DocumentType::SaleOrder => "sale_order",
+        DocumentType::SaleInvoice => "sale_invoice",
+        DocumentType::SaleCreditNote => "sale_credit_note",
+        DocumentType::PurchaseOrder => "purchase_order",
+        DocumentType::PurchaseInvoice => "purchase_invoice",
+        DocumentType::PurchaseCreditNote => "purchase_credit_note",
+    }
+}
+
+pub fn document_type_from_name(name: &str) -> Option<DocumentType> {
+    match name {
+        "sale_order" => Some(DocumentType::SaleOrder),
+        "sale_invoice" => Some(DocumentType::SaleInvoice),
+        "sale_credit_note" => Some(DocumentType::SaleCreditNote),
+        "purchase_order" => Some(DocumentType::PurchaseOrder),
+        "purchase_invoice" => Some(DocumentType::PurchaseInvoice),
+        "purchase_credit_note" => Some(DocumentType::PurchaseCreditNote),
+        _ => None,
+    }
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct UpdateNumberSequenceInput {
+    #[validate(length(min = 1, max = 64))]
+    pub pattern: String,
+}
@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Serialize, ToSchema)]
+pub struct PriceListModel {
+    pub id: i32,
+    pub name: String,
+    pub currency: String,
+    #[serde(rename = "validFrom")]
+    pub valid_from: Option<String>,
+    #[serde(rename = "validTo")]
+    pub valid_to: Option<String>,
+}
+
+impl From<&entity::price_lists::Model> for PriceListModel {
+    fn from(entity: &entity::price_lists::Model) -> Self {
+        Self {
+            id: entity.id,
+            name: entity.name.clone(),
+            currency: entity.currency.clone(),
+            valid_from: entity.valid_from.map(|date| date.to_string()),
+            valid_to: entity.valid_to.map(|date| date.to_string()),
+        }
+    }
+}
+
+/// The price of a variant from a minimum quantity on.
+#[derive(Serialize, ToSchema)]
+pub struct PriceListItemModel {
+    pub id: i32,
+    #[serde(rename = "priceListId")]
+    pub price_list_id: i32,
+    #[serde(rename = "variantId")]
+    pub variant_id: i32,
+    #[serde(rename = "minQuantity")]
+    pub min_quantity: f64,
+    pub price: i32,
+}
+
+impl From<&entity::price_list_items::Model> for PriceListItemModel {
+    fn from(entity: &entity::price_list_items::Model) -> Self {
+        Self {
+            id: entity.id,
+            price_list_id: entity.price_list_id,
+            variant_id: entity.variant_id,
+            min_quantity: entity.min_quantity,
+            price: entity.price,
+        }
+    }
+}
+
+/// A price list with its prices and the customers it is assigned to.
+#[derive(Serialize, ToSchema)]
+pub struct PriceListDetailModel {
+    #[serde(rename = "priceList")]
+    pub price_list: PriceListModel,
+    pub items: Vec<PriceListItemModel>,
+    #[serde(rename = "customerIds")]
+    pub customer_ids: Vec<i32>,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct CreatePriceListInput {
+    #[validate(length(min = 1, max = 128))]
+    pub name: String,
+    /// ISO 4217 code like EUR. The list only prices orders of customers
+    /// quoted in this currency.
+    pub currency: String,
+    /// First day the list applies, or null if it applies from the start.
+    #[serde(rename = "validFrom")]
+    pub valid_from: Option<String>,
+    /// Last day the list applies, or null if it applies until further notice.
+    #[serde(rename = "validTo")]
+    pub valid_to: Option<String>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct ListPriceListsInput {
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u64>,
+    #[validate(range(min = 0))]
+    pub offset: Option<u64>,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct CreatePriceListItemInput {
+    #[serde(rename = "variantId")]
+    #[validate(range(min = 1))]
+    pub variant_id: i32,
+    /// The quantity from which on the price applies, 0 for any quantity.
+    #[serde(rename = "minQuantity", default)]
+    #[validate(range(min = 0.0))]
+    pub min_quantity: f64,
+    #[validate(range(min = 0))]
+    pub price: i32,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct SetPriceListCustomersInput {
+    #[serde(rename = "customerIds")]
+    #[validate(length(max = 1000))]
+    pub customer_ids: Vec<i32>,
+}
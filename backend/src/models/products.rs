@@ -1,11 +1,26 @@
+use entity::sea_orm_active_enums::{CostingMethod, ProductType, Tracking};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use validator::Validate;
 
+use crate::utils::variants::Attribute;
+
+/// Tax category of products created without one.
+pub const DEFAULT_TAX_CATEGORY: &str = "standard";
+
 #[derive(Serialize)]
 pub struct ProductModel {
     pub id: i32,
     pub name: String,
+    #[serde(rename = "taxCategory")]
+    pub tax_category: String,
+    #[serde(rename = "categoryId")]
+    pub category_id: Option<i32>,
+    #[serde(rename = "productType")]
+    pub product_type: String,
+    #[serde(rename = "costingMethod")]
+    pub costing_method: String,
+    pub tracking: String,
 }
 
 impl From<&entity::products::Model> for ProductModel {
@@ -13,14 +28,219 @@ impl From<&entity::products::Model> for ProductModel {
         Self {
             id: entity.id,
             name: entity.name.clone(),
+            tax_category: entity.tax_category.clone(),
+            category_id: entity.category_id,
+            product_type: product_type_name(&entity.product_type).to_string(),
+            costing_method: costing_method_name(&entity.costing_method).to_string(),
+            tracking: tracking_name(&entity.tracking).to_string(),
         }
     }
 }
 
+pub fn product_type_name(product_type: &ProductType) -> &'static str {
+    match product_type {
+        ProductType::Stockable => "stockable",
+        ProductType::Consumable => "consumable",
+        ProductType::Service => "service",
+    }
+}
+
+pub fn product_type_from_name(name: &str) -> Option<ProductType> {
+    match name {
+        "stockable" => Some(ProductType::Stockable),
+        "consumable" => Some(ProductType::Consumable),
+        "service" => Some(ProductType::Service),
+        _ => None,
+    }
+}
+
+pub fn costing_method_name(costing_method: &CostingMethod) -> &'static str {
+    match costing_method {
+        CostingMethod::Fifo => "fifo",
+        CostingMethod::Average => "average",
+    }
+}
+
+pub fn costing_method_from_name(name: &str) -> Option<CostingMethod> {
+    match name {
+        "fifo" => Some(CostingMethod::Fifo),
+        "average" => Some(CostingMethod::Average),
+        _ => None,
+    }
+}
+
+pub fn tracking_name(tracking: &Tracking) -> &'static str {
+    match tracking {
+        Tracking::None => "none",
+        Tracking::Lot => "lot",
+        Tracking::Serial => "serial",
+    }
+}
+
+pub fn tracking_from_name(name: &str) -> Option<Tracking> {
+    match name {
+        "none" => Some(Tracking::None),
+        "lot" => Some(Tracking::Lot),
+        "serial" => Some(Tracking::Serial),
+        _ => None,
+    }
+}
+
 #[derive(Deserialize, Validate, ToSchema)]
 pub struct CreateProductInput {
     #[validate(length(min = 1, max = 32))]
     pub name: String,
+    /// Matched against the product category of the tax rules, e.g.
+    /// `reduced` for books or food. Defaults to `standard`.
+    #[serde(rename = "taxCategory")]
+    #[validate(length(min = 1, max = 32))]
+    pub tax_category: Option<String>,
+    /// The category whose ledger accounts and tax the product's invoice
+    /// lines are posted with.
+    #[serde(rename = "categoryId")]
+    #[validate(range(min = 1))]
+    pub category_id: Option<i32>,
+    /// `stockable`, `consumable` or `service`. Only stockable products are
+    /// moved between stocks. Defaults to `stockable`.
+    #[serde(rename = "productType")]
+    pub product_type: Option<String>,
+    /// `fifo` or `average`. Whether goods leaving the stock are valued at
+    /// the cost of the oldest receipts or at the average cost of the stock.
+    /// Defaults to `fifo`.
+    #[serde(rename = "costingMethod")]
+    pub costing_method: Option<String>,
+    /// `none`, `lot` or `serial`. Moves of lot tracked products name the
+    /// lots they move, moves of serial tracked products every single
+    /// piece. Defaults to `none`.
+    pub tracking: Option<String>,
+}
+
+/// An attribute like size or color along which the product's variants
+/// differ, with its values.
+#[derive(Serialize, ToSchema)]
+pub struct ProductAttributeModel {
+    pub id: i32,
+    #[serde(rename = "productId")]
+    pub product_id: i32,
+    pub name: String,
+    pub position: i32,
+    pub values: Vec<ProductAttributeValueModel>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ProductAttributeValueModel {
+    pub id: i32,
+    pub name: String,
+    pub position: i32,
+}
+
+impl From<&entity::product_attribute_values::Model> for ProductAttributeValueModel {
+    fn from(entity: &entity::product_attribute_values::Model) -> Self {
+        Self {
+            id: entity.id,
+            name: entity.name.clone(),
+            position: entity.position,
+        }
+    }
+}
+
+impl From<&Attribute> for ProductAttributeModel {
+    fn from((attribute, values): &Attribute) -> Self {
+        Self {
+            id: attribute.id,
+            product_id: attribute.product_id,
+            name: attribute.name.clone(),
+            position: attribute.position,
+            values: values
+                .iter()
+                .map(ProductAttributeValueModel::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct CreateProductAttributeInput {
+    /// Also the placeholder for the attribute's value in variant names,
+    /// e.g. `{Size}`.
+    #[validate(length(min = 1, max = 32))]
+    pub name: String,
+    /// The values in the order variants are generated in, e.g. S, M and L.
+    #[validate(length(min = 1, max = 50))]
+    pub values: Vec<String>,
+}
+
+/// A node of the product category tree. Accounts and tax left unset are
+/// taken from the nearest ancestor that sets them.
+#[derive(Serialize, ToSchema)]
+pub struct ProductCategoryModel {
+    pub id: i32,
+    pub name: String,
+    #[serde(rename = "parentId")]
+    pub parent_id: Option<i32>,
+    #[serde(rename = "incomeAccountId")]
+    pub income_account_id: Option<i32>,
+    #[serde(rename = "expenseAccountId")]
+    pub expense_account_id: Option<i32>,
+    #[serde(rename = "inventoryAccountId")]
+    pub inventory_account_id: Option<i32>,
+    #[serde(rename = "taxId")]
+    pub tax_id: Option<i32>,
+    #[serde(rename = "saleDiscountAccountId")]
+    pub sale_discount_account_id: Option<i32>,
+    #[serde(rename = "purchaseDiscountAccountId")]
+    pub purchase_discount_account_id: Option<i32>,
+}
+
+impl From<&entity::product_categories::Model> for ProductCategoryModel {
+    fn from(entity: &entity::product_categories::Model) -> Self {
+        Self {
+            id: entity.id,
+            name: entity.name.clone(),
+            parent_id: entity.parent_id,
+            income_account_id: entity.income_account_id,
+            expense_account_id: entity.expense_account_id,
+            inventory_account_id: entity.inventory_account_id,
+            tax_id: entity.tax_id,
+            sale_discount_account_id: entity.sale_discount_account_id,
+            purchase_discount_account_id: entity.purchase_discount_account_id,
+        }
+    }
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct CreateProductCategoryInput {
+    #[validate(length(min = 1, max = 64))]
+    pub name: String,
+    #[serde(rename = "parentId")]
+    #[validate(range(min = 1))]
+    pub parent_id: Option<i32>,
+    /// Credited with the revenue of sale invoice lines.
+    #[serde(rename = "incomeAccountId")]
+    #[validate(range(min = 1))]
+    pub income_account_id: Option<i32>,
+    /// Debited with the cost of purchase invoice lines.
+    #[serde(rename = "expenseAccountId")]
+    #[validate(range(min = 1))]
+    pub expense_account_id: Option<i32>,
+    #[serde(rename = "inventoryAccountId")]
+    #[validate(range(min = 1))]
+    pub inventory_account_id: Option<i32>,
+    /// Levied on invoice lines of the category's products that carry no
+    /// tax of their own.
+    #[serde(rename = "taxId")]
+    #[validate(range(min = 1))]
+    pub tax_id: Option<i32>,
+    /// Debited with the discounts of sale invoice lines, whose revenue is
+    /// then credited undiscounted.
+    #[serde(rename = "saleDiscountAccountId")]
+    #[validate(range(min = 1))]
+    pub sale_discount_account_id: Option<i32>,
+    /// Credited with the discounts of purchase invoice lines which aren't
+    /// taken into inventory, whose cost is then debited undiscounted.
+    #[serde(rename = "purchaseDiscountAccountId")]
+    #[validate(range(min = 1))]
+    pub purchase_discount_account_id: Option<i32>,
 }
 
 #[derive(Deserialize, Validate)]
@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use validator::Validate;
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct PurchaseInvoiceLineModel {
     pub id: i32,
     pub description: String,
@@ -14,8 +15,16 @@ pub struct PurchaseInvoiceLineModel {
     pub variant_id: Option<i32>,
     #[serde(rename = "transactionId")]
     pub transaction_id: Option<i32>,
+    #[serde(rename = "taxId")]
+    pub tax_id: Option<i32>,
     #[serde(rename = "taxTransactionId")]
     pub tax_transaction_id: Option<i32>,
+    #[serde(rename = "discountPercent")]
+    pub discount_percent: f64,
+    #[serde(rename = "discountAmount")]
+    pub discount_amount: i32,
+    #[serde(rename = "discountTransactionId")]
+    pub discount_transaction_id: Option<i32>,
 }
 
 impl From<&entity::purchase_invoice_lines::Model> for PurchaseInvoiceLineModel {
@@ -29,7 +38,11 @@ impl From<&entity::purchase_invoice_lines::Model> for PurchaseInvoiceLineModel {
             invoice_id: entity.invoice_id,
             variant_id: entity.variant_id,
             transaction_id: entity.transaction_id,
+            tax_id: entity.tax_id,
             tax_transaction_id: entity.tax_transaction_id,
+            discount_percent: entity.discount_percent,
+            discount_amount: entity.discount_amount,
+            discount_transaction_id: entity.discount_transaction_id,
         }
     }
 }
@@ -44,6 +57,12 @@ pub struct CreatePurchaseInvoiceLineInput {
     pub order_id: i32,
     #[serde(rename = "variantId")]
     pub variant_id: Option<i32>,
+    #[serde(rename = "discountPercent", default)]
+    #[validate(range(min = 0.0, max = 100.0))]
+    pub discount_percent: f64,
+    #[serde(rename = "discountAmount", default)]
+    #[validate(range(min = 0))]
+    pub discount_amount: i32,
 }
 
 #[derive(Deserialize, Validate)]
@@ -1,16 +1,29 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use validator::Validate;
 
-#[derive(Serialize)]
+use super::{DocumentTotalsModel, PurchaseInvoiceLineModel};
+
+#[derive(Serialize, ToSchema)]
 pub struct PurchaseInvoiceModel {
     pub id: i32,
     pub reference: String,
+    #[serde(rename = "supplierReference")]
+    pub supplier_reference: String,
     pub date: String,
     pub status: String,
     #[serde(rename = "orderId")]
-    pub order_id: i32,
+    pub order_id: Option<i32>,
     #[serde(rename = "supplierId")]
-    pub supplier_id: i32,
+    pub supplier_id: Option<i32>,
+    #[serde(rename = "needsReview")]
+    pub needs_review: bool,
+    #[serde(rename = "reviewNote")]
+    pub review_note: String,
+    #[serde(rename = "discountPercent")]
+    pub discount_percent: f64,
+    #[serde(rename = "discountAmount")]
+    pub discount_amount: i32,
 }
 
 impl From<&entity::purchase_invoices::Model> for PurchaseInvoiceModel {
@@ -23,22 +36,57 @@ impl From<&entity::purchase_invoices::Model> for PurchaseInvoiceModel {
         Self {
             id: entity.id,
             reference: entity.reference.clone(),
+            supplier_reference: entity.supplier_reference.clone(),
             date: entity.date.to_string(),
             status: status.to_string(),
             order_id: entity.order_id,
             supplier_id: entity.supplier_id,
+            needs_review: entity.needs_review,
+            review_note: entity.review_note.clone(),
+            discount_percent: entity.discount_percent,
+            discount_amount: entity.discount_amount,
         }
     }
 }
 
-#[derive(Deserialize, Validate)]
+/// A purchase invoice with its lines and what they add up to.
+#[derive(Serialize, ToSchema)]
+pub struct PurchaseInvoiceDetailModel {
+    pub invoice: PurchaseInvoiceModel,
+    pub lines: Vec<PurchaseInvoiceLineModel>,
+    pub totals: DocumentTotalsModel,
+}
+
+pub fn purchase_invoice_status(
+    status: &str,
+) -> Option<entity::sea_orm_active_enums::PurchaseInvoiceStatus> {
+    match status {
+        "draft" => Some(entity::sea_orm_active_enums::PurchaseInvoiceStatus::Draft),
+        "finished" => Some(entity::sea_orm_active_enums::PurchaseInvoiceStatus::Finished),
+        _ => None,
+    }
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
 pub struct CreatePurchaseInvoiceInput {
-    pub reference: String,
+    #[serde(rename = "supplierReference")]
+    #[validate(length(min = 1, max = 64))]
+    pub supplier_reference: String,
     pub date: String,
+    pub status: String,
     #[serde(rename = "orderId")]
+    #[validate(range(min = 1))]
     pub order_id: i32,
     #[serde(rename = "supplierId")]
+    #[validate(range(min = 1))]
     pub supplier_id: i32,
+    /// Taken off in percent first, then as an amount in cents.
+    #[serde(rename = "discountPercent", default)]
+    #[validate(range(min = 0.0, max = 100.0))]
+    pub discount_percent: f64,
+    #[serde(rename = "discountAmount", default)]
+    #[validate(range(min = 0))]
+    pub discount_amount: i32,
 }
 
 #[derive(Deserialize, Validate)]
@@ -48,3 +96,18 @@ pub struct ListPurchaseInvoiceInput {
     #[validate(range(min = 0))]
     pub offset: Option<u64>,
 }
+
+/// Something the import could not decide on its own. The code is either one
+/// of the import's own codes or the id of a violated EN 16931 rule.
+#[derive(Serialize)]
+pub struct ReviewIssueModel {
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(Serialize)]
+pub struct ImportedPurchaseInvoiceModel {
+    pub invoice: PurchaseInvoiceModel,
+    pub lines: Vec<PurchaseInvoiceLineModel>,
+    pub issues: Vec<ReviewIssueModel>,
+}
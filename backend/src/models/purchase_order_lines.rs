@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use validator::Validate;
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct PurchaseOrderLineModel {
     pub id: i32,
     pub description: String,
@@ -14,6 +15,15 @@ pub struct PurchaseOrderLineModel {
     pub variant_id: Option<i32>,
     #[serde(rename = "moveId")]
     pub move_id: Option<i32>,
+    #[serde(rename = "discountPercent")]
+    pub discount_percent: f64,
+    #[serde(rename = "discountAmount")]
+    pub discount_amount: i32,
+    #[serde(rename = "unitId")]
+    pub unit_id: Option<i32>,
+    /// The sale order line the line drop-ships.
+    #[serde(rename = "saleLineId")]
+    pub sale_line_id: Option<i32>,
 }
 
 impl From<&entity::purchase_order_lines::Model> for PurchaseOrderLineModel {
@@ -27,20 +37,42 @@ impl From<&entity::purchase_order_lines::Model> for PurchaseOrderLineModel {
             order_id: entity.order_id,
             variant_id: entity.variant_id,
             move_id: entity.move_id,
+            discount_percent: entity.discount_percent,
+            discount_amount: entity.discount_amount,
+            unit_id: entity.unit_id,
+            sale_line_id: entity.sale_line_id,
         }
     }
 }
 
-#[derive(Deserialize, Validate)]
+#[derive(Deserialize, Validate, ToSchema)]
 pub struct CreatePurchaseOrderLineInput {
+    /// Defaults to the supplier's description of the variant, or else the
+    /// variant's name.
+    #[serde(default)]
+    #[validate(length(max = 256))]
     pub description: String,
+    #[validate(range(min = 0.0))]
     pub quantity: f64,
-    pub price: i32,
+    /// The unit price in cents. Without one, lines with a variant are priced
+    /// from the supplier's catalog or else the variant's purchase price.
+    pub price: Option<i32>,
     pub tax: i32,
     #[serde(rename = "orderId")]
     pub order_id: i32,
     #[serde(rename = "variantId")]
     pub variant_id: Option<i32>,
+    #[serde(rename = "discountPercent", default)]
+    #[validate(range(min = 0.0, max = 100.0))]
+    pub discount_percent: f64,
+    #[serde(rename = "discountAmount", default)]
+    #[validate(range(min = 0))]
+    pub discount_amount: i32,
+    /// The unit of the quantity and price, e.g. a box of 12 for a variant
+    /// stocked in pieces. Defaults to the variant's unit.
+    #[serde(rename = "unitId")]
+    #[validate(range(min = 1))]
+    pub unit_id: Option<i32>,
 }
 
 #[derive(Deserialize, Validate)]
@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use validator::Validate;
 
-#[derive(Serialize)]
+use super::{DocumentTotalsModel, PurchaseOrderLineModel};
+
+#[derive(Serialize, ToSchema)]
 pub struct PurchaseOrderModel {
     pub id: i32,
     pub reference: String,
@@ -13,12 +16,17 @@ pub struct PurchaseOrderModel {
     pub source_stock_id: i32,
     #[serde(rename = "targetStockId")]
     pub target_stock_id: i32,
+    #[serde(rename = "discountPercent")]
+    pub discount_percent: f64,
+    #[serde(rename = "discountAmount")]
+    pub discount_amount: i32,
 }
 
 impl From<&entity::purchase_orders::Model> for PurchaseOrderModel {
     fn from(entity: &entity::purchase_orders::Model) -> Self {
         let status = match entity.status {
             entity::sea_orm_active_enums::PurchaseOrderStatus::Draft => "draft",
+            entity::sea_orm_active_enums::PurchaseOrderStatus::Order => "order",
             entity::sea_orm_active_enums::PurchaseOrderStatus::Finished => "finished",
         };
 
@@ -30,21 +38,51 @@ impl From<&entity::purchase_orders::Model> for PurchaseOrderModel {
             supplier_id: entity.supplier_id,
             source_stock_id: entity.source_stock_id,
             target_stock_id: entity.target_stock_id,
+            discount_percent: entity.discount_percent,
+            discount_amount: entity.discount_amount,
         }
     }
 }
 
-#[derive(Deserialize, Validate)]
+/// A purchase order with its lines and what they add up to.
+#[derive(Serialize, ToSchema)]
+pub struct PurchaseOrderDetailModel {
+    pub order: PurchaseOrderModel,
+    pub lines: Vec<PurchaseOrderLineModel>,
+    pub totals: DocumentTotalsModel,
+}
+
+pub fn purchase_order_status(
+    status: &str,
+) -> Option<entity::sea_orm_active_enums::PurchaseOrderStatus> {
+    match status {
+        "draft" => Some(entity::sea_orm_active_enums::PurchaseOrderStatus::Draft),
+        "order" => Some(entity::sea_orm_active_enums::PurchaseOrderStatus::Order),
+        "finished" => Some(entity::sea_orm_active_enums::PurchaseOrderStatus::Finished),
+        _ => None,
+    }
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
 pub struct CreatePurchaseOrderInput {
-    pub reference: String,
     pub date: String,
     pub status: String,
     #[serde(rename = "supplierId")]
+    #[validate(range(min = 1))]
     pub supplier_id: i32,
     #[serde(rename = "sourceStockId")]
+    #[validate(range(min = 1))]
     pub source_stock_id: i32,
     #[serde(rename = "targetStockId")]
+    #[validate(range(min = 1))]
     pub target_stock_id: i32,
+    /// Taken off in percent first, then as an amount in cents.
+    #[serde(rename = "discountPercent", default)]
+    #[validate(range(min = 0.0, max = 100.0))]
+    pub discount_percent: f64,
+    #[serde(rename = "discountAmount", default)]
+    #[validate(range(min = 0))]
+    pub discount_amount: i32,
 }
 
 #[derive(Deserialize, Validate)]
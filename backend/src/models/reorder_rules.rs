@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+use super::PurchaseOrderModel;
+
+/// Keeps a variant in a stock between a minimum and a maximum quantity, in
+/// the variant's unit.
+#[derive(Serialize, ToSchema)]
+pub struct ReorderRuleModel {
+    pub id: i32,
+    #[serde(rename = "variantId")]
+    pub variant_id: i32,
+    #[serde(rename = "stockId")]
+    pub stock_id: i32,
+    #[serde(rename = "minQuantity")]
+    pub min_quantity: f64,
+    #[serde(rename = "maxQuantity")]
+    pub max_quantity: f64,
+}
+
+impl From<&entity::reorder_rules::Model> for ReorderRuleModel {
+    fn from(entity: &entity::reorder_rules::Model) -> Self {
+        Self {
+            id: entity.id,
+            variant_id: entity.variant_id,
+            stock_id: entity.stock_id,
+            min_quantity: entity.min_quantity,
+            max_quantity: entity.max_quantity,
+        }
+    }
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct CreateReorderRuleInput {
+    #[serde(rename = "variantId")]
+    #[validate(range(min = 1))]
+    pub variant_id: i32,
+    /// The internal location to keep stocked, counting the locations below
+    /// it.
+    #[serde(rename = "stockId")]
+    #[validate(range(min = 1))]
+    pub stock_id: i32,
+    /// Below this quantity the variant is reordered.
+    #[serde(rename = "minQuantity")]
+    #[validate(range(min = 0.0))]
+    pub min_quantity: f64,
+    /// The quantity the variant is reordered up to.
+    #[serde(rename = "maxQuantity")]
+    #[validate(range(min = 0.0))]
+    pub max_quantity: f64,
+}
+
+/// A variant that fell below its rule's minimum, with the quantity needed
+/// to bring it back up to the maximum, in the variant's unit.
+#[derive(Serialize, ToSchema)]
+pub struct ReplenishmentShortfallModel {
+    #[serde(rename = "ruleId")]
+    pub rule_id: i32,
+    #[serde(rename = "variantId")]
+    pub variant_id: i32,
+    #[serde(rename = "stockId")]
+    pub stock_id: i32,
+    pub quantity: f64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ReplenishmentModel {
+    /// The proposed draft purchase orders, one per supplier and stock.
+    pub orders: Vec<PurchaseOrderModel>,
+    /// Shortfalls of variants no supplier sells, which are left to the
+    /// buyer.
+    #[serde(rename = "withoutSupplier")]
+    pub without_supplier: Vec<ReplenishmentShortfallModel>,
+}
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Deserialize, Validate)]
+pub struct VatReportInput {
+    pub from: String,
+    pub to: String,
+}
+
+/// The taxable base and tax of one tax code and region. Amounts are in
+/// cents.
+#[derive(Serialize, ToSchema)]
+pub struct VatReportLineModel {
+    #[serde(rename = "taxId")]
+    pub tax_id: Option<i32>,
+    pub label: String,
+    pub rate: f64,
+    /// The UNTDID 5305 category of the tax, unknown for lines without one.
+    pub category: Option<String>,
+    /// `domestic`, `eu` or `non_eu`, where the customer or supplier is seen
+    /// from the company's country.
+    pub region: String,
+    pub base: i64,
+    pub tax: i64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct VatReportModel {
+    pub from: String,
+    pub to: String,
+    pub sales: Vec<VatReportLineModel>,
+    pub purchases: Vec<VatReportLineModel>,
+    #[serde(rename = "outputTax")]
+    pub output_tax: i64,
+    #[serde(rename = "inputTax")]
+    pub input_tax: i64,
+    /// The tax to pay for the period, negative if it is refunded.
+    pub balance: i64,
+}
+
+/// One field of the German advance VAT return, with the value formatted
+/// like it is entered in ELSTER.
+#[derive(Serialize, ToSchema)]
+pub struct UstvaFieldModel {
+    pub field: String,
+    pub label: String,
+    pub value: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct UstvaModel {
+    pub from: String,
+    pub to: String,
+    pub fields: Vec<UstvaFieldModel>,
+}
+
+/// The quantity of a variant in the own stocks and its value in cents.
+#[derive(Serialize, ToSchema)]
+pub struct InventoryValuationLineModel {
+    #[serde(rename = "variantId")]
+    pub variant_id: i32,
+    pub name: String,
+    pub quantity: f64,
+    pub value: i64,
+    /// The value divided by the quantity, in cents.
+    #[serde(rename = "unitCost")]
+    pub unit_cost: f64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct InventoryValuationModel {
+    pub lines: Vec<InventoryValuationLineModel>,
+    pub total: i64,
+}
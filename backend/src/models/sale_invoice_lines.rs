@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use validator::Validate;
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct SaleInvoiceLineModel {
     pub id: i32,
     pub description: String,
@@ -16,6 +17,12 @@ pub struct SaleInvoiceLineModel {
     pub transaction_id: Option<i32>,
     #[serde(rename = "taxTransactionId")]
     pub tax_transaction_id: Option<i32>,
+    #[serde(rename = "discountPercent")]
+    pub discount_percent: f64,
+    #[serde(rename = "discountAmount")]
+    pub discount_amount: i32,
+    #[serde(rename = "discountTransactionId")]
+    pub discount_transaction_id: Option<i32>,
 }
 
 impl From<&entity::sale_invoice_lines::Model> for SaleInvoiceLineModel {
@@ -30,6 +37,9 @@ impl From<&entity::sale_invoice_lines::Model> for SaleInvoiceLineModel {
             variant_id: entity.variant_id,
             transaction_id: entity.transaction_id,
             tax_transaction_id: entity.tax_transaction_id,
+            discount_percent: entity.discount_percent,
+            discount_amount: entity.discount_amount,
+            discount_transaction_id: entity.discount_transaction_id,
         }
     }
 }
@@ -44,6 +54,12 @@ pub struct CreateSaleInvoiceLineInput {
     pub order_id: i32,
     #[serde(rename = "variantId")]
     pub variant_id: Option<i32>,
+    #[serde(rename = "discountPercent", default)]
+    #[validate(range(min = 0.0, max = 100.0))]
+    pub discount_percent: f64,
+    #[serde(rename = "discountAmount", default)]
+    #[validate(range(min = 0))]
+    pub discount_amount: i32,
 }
 
 #[derive(Deserialize, Validate)]
@@ -1,8 +1,11 @@
 use entity::sea_orm_active_enums::SaleInvoiceStatus;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use validator::Validate;
 
-#[derive(Serialize)]
+use super::{DocumentTotalsModel, SaleInvoiceLineModel};
+
+#[derive(Serialize, ToSchema)]
 pub struct SaleInvoiceModel {
     pub id: i32,
     pub reference: String,
@@ -12,6 +15,10 @@ pub struct SaleInvoiceModel {
     pub order_id: i32,
     #[serde(rename = "customerId")]
     pub customer_id: i32,
+    #[serde(rename = "discountPercent")]
+    pub discount_percent: f64,
+    #[serde(rename = "discountAmount")]
+    pub discount_amount: i32,
 }
 
 impl From<&entity::sale_invoices::Model> for SaleInvoiceModel {
@@ -30,18 +37,47 @@ impl From<&entity::sale_invoices::Model> for SaleInvoiceModel {
             status: status.to_string(),
             order_id: entity.order_id,
             customer_id: entity.customer_id,
+            discount_percent: entity.discount_percent,
+            discount_amount: entity.discount_amount,
         }
     }
 }
 
-#[derive(Deserialize, Validate)]
+/// A sale invoice with its lines and what they add up to.
+#[derive(Serialize, ToSchema)]
+pub struct SaleInvoiceDetailModel {
+    pub invoice: SaleInvoiceModel,
+    pub lines: Vec<SaleInvoiceLineModel>,
+    pub totals: DocumentTotalsModel,
+}
+
+pub fn sale_invoice_status(status: &str) -> Option<SaleInvoiceStatus> {
+    match status {
+        "draft" => Some(SaleInvoiceStatus::Draft),
+        "proforma" => Some(SaleInvoiceStatus::Proforma),
+        "invoice" => Some(SaleInvoiceStatus::Invoice),
+        "finished" => Some(SaleInvoiceStatus::Finished),
+        _ => None,
+    }
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
 pub struct CreateSaleInvoiceInput {
-    pub reference: String,
     pub date: String,
+    pub status: String,
     #[serde(rename = "orderId")]
+    #[validate(range(min = 1))]
     pub order_id: i32,
     #[serde(rename = "customerId")]
+    #[validate(range(min = 1))]
     pub customer_id: i32,
+    /// Taken off in percent first, then as an amount in cents.
+    #[serde(rename = "discountPercent", default)]
+    #[validate(range(min = 0.0, max = 100.0))]
+    pub discount_percent: f64,
+    #[serde(rename = "discountAmount", default)]
+    #[validate(range(min = 0))]
+    pub discount_amount: i32,
 }
 
 #[derive(Deserialize, Validate)]
@@ -51,3 +87,16 @@ pub struct ListSaleInvoiceInput {
     #[validate(range(min = 0))]
     pub offset: Option<u64>,
 }
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum EInvoiceFormat {
+    XRechnung,
+    Cii,
+    FacturX,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct ExportEInvoiceInput {
+    pub format: EInvoiceFormat,
+}
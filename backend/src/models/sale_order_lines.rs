@@ -1,7 +1,9 @@
+use entity::sea_orm_active_enums::SaleOrderLineRoute;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use validator::Validate;
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct SaleOrderLineModel {
     pub id: i32,
     pub description: String,
@@ -14,6 +16,15 @@ pub struct SaleOrderLineModel {
     pub variant_id: Option<i32>,
     #[serde(rename = "moveId")]
     pub move_id: Option<i32>,
+    #[serde(rename = "discountPercent")]
+    pub discount_percent: f64,
+    #[serde(rename = "discountAmount")]
+    pub discount_amount: i32,
+    #[serde(rename = "unitId")]
+    pub unit_id: Option<i32>,
+    pub route: String,
+    #[serde(rename = "taxId")]
+    pub tax_id: Option<i32>,
 }
 
 impl From<&entity::sale_order_lines::Model> for SaleOrderLineModel {
@@ -27,20 +38,73 @@ impl From<&entity::sale_order_lines::Model> for SaleOrderLineModel {
             order_id: entity.order_id,
             variant_id: entity.variant_id,
             move_id: entity.move_id,
+            discount_percent: entity.discount_percent,
+            discount_amount: entity.discount_amount,
+            unit_id: entity.unit_id,
+            route: sale_order_line_route_name(&entity.route).to_string(),
+            tax_id: entity.tax_id,
         }
     }
 }
 
-#[derive(Deserialize, Validate)]
+pub fn sale_order_line_route_name(route: &SaleOrderLineRoute) -> &'static str {
+    match route {
+        SaleOrderLineRoute::Stock => "stock",
+        SaleOrderLineRoute::DropShip => "drop_ship",
+    }
+}
+
+pub fn sale_order_line_route(route: &str) -> Option<SaleOrderLineRoute> {
+    match route {
+        "stock" => Some(SaleOrderLineRoute::Stock),
+        "drop_ship" => Some(SaleOrderLineRoute::DropShip),
+        _ => None,
+    }
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
 pub struct CreateSaleOrderLineInput {
+    /// Defaults to the variant's name.
+    #[serde(default)]
+    #[validate(length(max = 256))]
     pub description: String,
+    #[validate(range(min = 0.0))]
     pub quantity: f64,
-    pub price: i32,
-    pub tax: i32,
+    /// The unit price in cents. Without one, lines with a variant are priced
+    /// from the customer's price lists or else the variant's sale price.
+    pub price: Option<i32>,
+    /// The tax percentage. Without one or a `taxId`, lines with a variant
+    /// are taxed as the tax rules determine for the customer.
+    pub tax: Option<i32>,
+    /// A tax whose rate on the order date applies instead of `tax`, e.g. a
+    /// tax group or a tax with rate versions.
+    #[serde(rename = "taxId")]
+    #[validate(range(min = 1))]
+    pub tax_id: Option<i32>,
     #[serde(rename = "orderId")]
     pub order_id: i32,
     #[serde(rename = "variantId")]
     pub variant_id: Option<i32>,
+    #[serde(rename = "discountPercent", default)]
+    #[validate(range(min = 0.0, max = 100.0))]
+    pub discount_percent: f64,
+    #[serde(rename = "discountAmount", default)]
+    #[validate(range(min = 0))]
+    pub discount_amount: i32,
+    /// The unit of the quantity and price, e.g. a box of 12 for a variant
+    /// stocked in pieces. Defaults to the variant's unit.
+    #[serde(rename = "unitId")]
+    #[validate(range(min = 1))]
+    pub unit_id: Option<i32>,
+    /// `stock` to deliver the line from the order's source stock, or
+    /// `drop_ship` to have the variant's preferred supplier deliver it
+    /// straight to the customer once the order is confirmed.
+    #[serde(default = "default_route")]
+    pub route: String,
+}
+
+fn default_route() -> String {
+    "stock".to_string()
 }
 
 #[derive(Deserialize, Validate)]
@@ -1,7 +1,11 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use validator::Validate;
 
-#[derive(Serialize)]
+use super::{DocumentTotalsModel, PurchaseOrderModel, SaleOrderLineModel};
+use crate::utils::availability::Shortage;
+
+#[derive(Serialize, ToSchema)]
 pub struct SaleOrderModel {
     pub id: i32,
     pub reference: String,
@@ -13,6 +17,10 @@ pub struct SaleOrderModel {
     pub source_stock_id: i32,
     #[serde(rename = "targetStockId")]
     pub target_stock_id: i32,
+    #[serde(rename = "discountPercent")]
+    pub discount_percent: f64,
+    #[serde(rename = "discountAmount")]
+    pub discount_amount: i32,
 }
 
 impl From<&entity::sale_orders::Model> for SaleOrderModel {
@@ -22,6 +30,7 @@ impl From<&entity::sale_orders::Model> for SaleOrderModel {
             entity::sea_orm_active_enums::SaleOrderStatus::Quotation => "quotation",
             entity::sea_orm_active_enums::SaleOrderStatus::Order => "order",
             entity::sea_orm_active_enums::SaleOrderStatus::Finished => "finished",
+            entity::sea_orm_active_enums::SaleOrderStatus::Cancelled => "cancelled",
         };
 
         Self {
@@ -32,21 +41,101 @@ impl From<&entity::sale_orders::Model> for SaleOrderModel {
             customer_id: entity.customer_id,
             source_stock_id: entity.source_stock_id,
             target_stock_id: entity.target_stock_id,
+            discount_percent: entity.discount_percent,
+            discount_amount: entity.discount_amount,
         }
     }
 }
 
-#[derive(Deserialize, Validate)]
+/// A sale order with its lines, what they add up to and the purchase orders
+/// its drop-shipped lines are delivered by.
+#[derive(Serialize, ToSchema)]
+pub struct SaleOrderDetailModel {
+    pub order: SaleOrderModel,
+    pub lines: Vec<SaleOrderLineModel>,
+    pub totals: DocumentTotalsModel,
+    #[serde(rename = "dropShipments")]
+    pub drop_shipments: Vec<DropShipmentModel>,
+}
+
+/// The purchase order line a supplier delivers a sale order line by.
+#[derive(Serialize, ToSchema)]
+pub struct DropShipmentModel {
+    #[serde(rename = "lineId")]
+    pub line_id: i32,
+    #[serde(rename = "purchaseOrderLineId")]
+    pub purchase_order_line_id: i32,
+    #[serde(rename = "purchaseOrder")]
+    pub purchase_order: PurchaseOrderModel,
+}
+
+pub fn sale_order_status(status: &str) -> Option<entity::sea_orm_active_enums::SaleOrderStatus> {
+    match status {
+        "draft" => Some(entity::sea_orm_active_enums::SaleOrderStatus::Draft),
+        "quotation" => Some(entity::sea_orm_active_enums::SaleOrderStatus::Quotation),
+        "order" => Some(entity::sea_orm_active_enums::SaleOrderStatus::Order),
+        "finished" => Some(entity::sea_orm_active_enums::SaleOrderStatus::Finished),
+        _ => None,
+    }
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
 pub struct CreateSaleOrderInput {
-    pub reference: String,
     pub date: String,
     pub status: String,
     #[serde(rename = "customerId")]
+    #[validate(range(min = 1))]
     pub customer_id: i32,
     #[serde(rename = "sourceStockId")]
+    #[validate(range(min = 1))]
     pub source_stock_id: i32,
     #[serde(rename = "targetStockId")]
+    #[validate(range(min = 1))]
     pub target_stock_id: i32,
+    /// Taken off in percent first, then as an amount in cents.
+    #[serde(rename = "discountPercent", default)]
+    #[validate(range(min = 0.0, max = 100.0))]
+    pub discount_percent: f64,
+    #[serde(rename = "discountAmount", default)]
+    #[validate(range(min = 0))]
+    pub discount_amount: i32,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct ConfirmSaleOrderInput {
+    /// Confirms the order even if the stock doesn't cover it.
+    #[serde(rename = "allowOversell", default)]
+    pub allow_oversell: bool,
+}
+
+/// A variant the order asks for more of than is available, in the
+/// variant's unit.
+#[derive(Serialize, ToSchema)]
+pub struct StockShortageModel {
+    #[serde(rename = "variantId")]
+    pub variant_id: i32,
+    pub requested: f64,
+    pub available: f64,
+}
+
+impl From<&Shortage> for StockShortageModel {
+    fn from(shortage: &Shortage) -> Self {
+        Self {
+            variant_id: shortage.variant_id,
+            requested: shortage.requested,
+            available: shortage.available,
+        }
+    }
+}
+
+/// A confirmed sale order with the shortages it was confirmed with and the
+/// purchase orders placed for its drop-shipped lines.
+#[derive(Serialize, ToSchema)]
+pub struct SaleOrderConfirmationModel {
+    pub order: SaleOrderModel,
+    pub shortages: Vec<StockShortageModel>,
+    #[serde(rename = "purchaseOrders")]
+    pub purchase_orders: Vec<PurchaseOrderModel>,
 }
 
 #[derive(Deserialize, Validate)]
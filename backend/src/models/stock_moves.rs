@@ -1,9 +1,13 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use validator::Validate;
 
-#[derive(Serialize)]
+use super::MoveLotInput;
+
+#[derive(Serialize, ToSchema)]
 pub struct StockMoveModel {
     pub id: i32,
+    /// In the variant's unit.
     pub quantity: f64,
     #[serde(rename = "variantId")]
     pub variant_id: i32,
@@ -25,12 +29,30 @@ impl From<&entity::stock_moves::Model> for StockMoveModel {
     }
 }
 
-#[derive(Deserialize, Validate)]
+#[derive(Deserialize, Validate, ToSchema)]
 pub struct CreateStockMoveInput {
+    #[validate(range(exclusive_min = 0.0))]
     pub quantity: f64,
+    #[serde(rename = "variantId")]
+    #[validate(range(min = 1))]
     pub variant_id: i32,
+    #[serde(rename = "sourceStockId")]
+    #[validate(range(min = 1))]
     pub source_stock_id: i32,
+    #[serde(rename = "targetStockId")]
+    #[validate(range(min = 1))]
     pub target_stock_id: i32,
+    /// The unit of the quantity, which is converted into the variant's
+    /// unit. Defaults to the variant's unit.
+    #[serde(rename = "unitId")]
+    #[validate(range(min = 1))]
+    pub unit_id: Option<i32>,
+    /// The lots or serial numbers the goods are moved in. Required for
+    /// variants of tracked products, which are moved out of an own stock
+    /// only in lots it holds.
+    #[serde(default)]
+    #[validate(length(max = 1000), nested)]
+    pub lots: Vec<MoveLotInput>,
 }
 
 #[derive(Deserialize, Validate)]
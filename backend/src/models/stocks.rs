@@ -1,8 +1,12 @@
+use entity::sea_orm_active_enums::StockKind;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use validator::Validate;
 
-#[derive(Serialize)]
+use super::{MoveLotInput, PurchaseOrderModel, SaleOrderModel};
+use crate::repositories::{StockAvailability, VariantQuantity};
+
+#[derive(Serialize, ToSchema)]
 pub struct StockModel {
     pub id: i32,
     pub name: String,
@@ -10,6 +14,15 @@ pub struct StockModel {
     pub is_own: bool,
     #[serde(rename = "owner")]
     pub owner_id: Option<i32>,
+    /// `internal` for locations of the company, `supplier`, `customer`,
+    /// `inventory_loss` or `production` for the virtual locations goods
+    /// come from or go to.
+    pub kind: String,
+    #[serde(rename = "parentId")]
+    pub parent_id: Option<i32>,
+    /// Whether the stock holds goods on consignment: the company's at a
+    /// customer, or a supplier's at the company.
+    pub consignment: bool,
 }
 
 impl From<&entity::stocks::Model> for StockModel {
@@ -19,12 +32,137 @@ impl From<&entity::stocks::Model> for StockModel {
             name: entity.name.to_string(),
             is_own: entity.is_own,
             owner_id: entity.owner_id,
+            kind: stock_kind_name(&entity.kind).to_string(),
+            parent_id: entity.parent_id,
+            consignment: entity.consignment,
         }
     }
 }
 
+pub fn stock_kind_name(kind: &StockKind) -> &'static str {
+    match kind {
+        StockKind::Internal => "internal",
+        StockKind::Supplier => "supplier",
+        StockKind::Customer => "customer",
+        StockKind::InventoryLoss => "inventory_loss",
+        StockKind::Production => "production",
+    }
+}
+
+/// The kinds of stock a contact can have.
+pub fn contact_stock_kind_from_name(name: &str) -> Option<StockKind> {
+    match name {
+        "customer" => Some(StockKind::Customer),
+        "supplier" => Some(StockKind::Supplier),
+        _ => None,
+    }
+}
+
 #[derive(Deserialize, Validate, ToSchema)]
 pub struct CreateStockInput {
     #[validate(length(min = 1))]
     pub name: String,
+    /// The internal location the new one is part of, e.g. the zone of a
+    /// bin.
+    #[serde(rename = "parentId")]
+    #[validate(range(min = 1))]
+    pub parent_id: Option<i32>,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct CreateContactStockInput {
+    #[validate(length(min = 1))]
+    pub name: String,
+    /// `customer` or `supplier`, which the contact must be.
+    pub kind: String,
+    /// Whether the stock holds goods on consignment. At a customer they stay
+    /// the company's until the customer reports consuming them. From a
+    /// supplier they stay the supplier's until the company does.
+    #[serde(default)]
+    pub consignment: bool,
+}
+
+/// The goods consumed from a consignment stock.
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct ReportConsumptionInput {
+    /// `YYYY-MM-DD`
+    pub date: String,
+    /// The internal location goods consumed from a supplier's stock go to.
+    /// Goods a customer consumes go to the customer location.
+    #[serde(rename = "targetStockId")]
+    #[validate(range(min = 1))]
+    pub target_stock_id: Option<i32>,
+    #[validate(length(min = 1, max = 1000), nested)]
+    pub lines: Vec<ConsumptionLineInput>,
+}
+
+#[derive(Serialize, Deserialize, Validate, ToSchema)]
+pub struct ConsumptionLineInput {
+    #[serde(rename = "variantId")]
+    #[validate(range(min = 1))]
+    pub variant_id: i32,
+    #[validate(range(exclusive_min = 0.0))]
+    pub quantity: f64,
+    /// The unit of the quantity. Defaults to the variant's unit.
+    #[serde(rename = "unitId")]
+    #[validate(range(min = 1))]
+    pub unit_id: Option<i32>,
+    /// The lots or serial numbers consumed, required for variants of
+    /// tracked products.
+    #[serde(default)]
+    #[validate(length(max = 1000), nested)]
+    pub lots: Vec<MoveLotInput>,
+}
+
+/// The order the consumption is invoiced by: a sale order to the customer
+/// for goods consumed at the customer, or a purchase order from the supplier
+/// for goods the company consumed.
+#[derive(Serialize, ToSchema)]
+pub struct ConsumptionModel {
+    #[serde(rename = "saleOrder")]
+    pub sale_order: Option<SaleOrderModel>,
+    #[serde(rename = "purchaseOrder")]
+    pub purchase_order: Option<PurchaseOrderModel>,
+}
+
+/// What there is of a variant in a location and the locations below it, in
+/// the variant's unit.
+#[derive(Serialize, ToSchema)]
+pub struct StockQuantityModel {
+    #[serde(rename = "variantId")]
+    pub variant_id: i32,
+    pub quantity: f64,
+}
+
+impl From<&VariantQuantity> for StockQuantityModel {
+    fn from(quantity: &VariantQuantity) -> Self {
+        Self {
+            variant_id: quantity.variant_id,
+            quantity: quantity.quantity,
+        }
+    }
+}
+
+/// What there is of a variant in a stock and how much of it is promised to
+/// confirmed sale orders, in the variant's unit.
+#[derive(Serialize, ToSchema)]
+pub struct StockAvailabilityModel {
+    #[serde(rename = "stockId")]
+    pub stock_id: i32,
+    #[serde(rename = "onHand")]
+    pub on_hand: f64,
+    pub reserved: f64,
+    /// What is left to promise, negative if the stock is oversold.
+    pub available: f64,
+}
+
+impl From<&StockAvailability> for StockAvailabilityModel {
+    fn from(availability: &StockAvailability) -> Self {
+        Self {
+            stock_id: availability.stock_id,
+            on_hand: availability.on_hand,
+            reserved: availability.reserved,
+            available: availability.available(),
+        }
+    }
 }
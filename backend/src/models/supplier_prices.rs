@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// A supplier's catalog entry for a variant.
+#[derive(Serialize, ToSchema)]
+pub struct SupplierPriceModel {
+    pub id: i32,
+    #[serde(rename = "variantId")]
+    pub variant_id: i32,
+    #[serde(rename = "supplierId")]
+    pub supplier_id: i32,
+    #[serde(rename = "supplierSku")]
+    pub supplier_sku: String,
+    pub description: String,
+    pub price: i32,
+    #[serde(rename = "minQuantity")]
+    pub min_quantity: f64,
+    #[serde(rename = "packSize")]
+    pub pack_size: f64,
+    #[serde(rename = "leadTimeDays")]
+    pub lead_time_days: i32,
+    pub preferred: bool,
+}
+
+impl From<&entity::supplier_prices::Model> for SupplierPriceModel {
+    fn from(entity: &entity::supplier_prices::Model) -> Self {
+        Self {
+            id: entity.id,
+            variant_id: entity.variant_id,
+            supplier_id: entity.supplier_id,
+            supplier_sku: entity.supplier_sku.clone(),
+            description: entity.description.clone(),
+            price: entity.price,
+            min_quantity: entity.min_quantity,
+            pack_size: entity.pack_size,
+            lead_time_days: entity.lead_time_days,
+            preferred: entity.preferred,
+        }
+    }
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct CreateSupplierPriceInput {
+    #[serde(rename = "supplierId")]
+    #[validate(range(min = 1))]
+    pub supplier_id: i32,
+    /// The supplier's article number for the variant.
+    #[serde(rename = "supplierSku", default)]
+    #[validate(length(max = 64))]
+    pub supplier_sku: String,
+    /// The supplier's name for the variant, the variant's own if empty.
+    #[serde(default)]
+    #[validate(length(max = 256))]
+    pub description: String,
+    /// The unit price in cents.
+    #[validate(range(min = 0))]
+    pub price: i32,
+    #[serde(rename = "minQuantity", default)]
+    #[validate(range(min = 0.0))]
+    pub min_quantity: f64,
+    #[serde(rename = "packSize", default = "default_pack_size")]
+    #[validate(range(exclusive_min = 0.0))]
+    pub pack_size: f64,
+    #[serde(rename = "leadTimeDays", default)]
+    #[validate(range(min = 0))]
+    pub lead_time_days: i32,
+    #[serde(default)]
+    pub preferred: bool,
+}
+
+fn default_pack_size() -> f64 {
+    1.0
+}
@@ -0,0 +1,109 @@
+use entity::sea_orm_active_enums::{TaxDirection, TaxRegion};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+use super::TaxModel;
+
+#[derive(Serialize, ToSchema)]
+pub struct TaxRuleModel {
+    pub id: i32,
+    pub direction: String,
+    pub region: String,
+    #[serde(rename = "vatId")]
+    pub vat_id: Option<bool>,
+    #[serde(rename = "productTaxCategory")]
+    pub product_tax_category: Option<String>,
+    #[serde(rename = "taxId")]
+    pub tax_id: i32,
+}
+
+impl From<&entity::tax_rules::Model> for TaxRuleModel {
+    fn from(entity: &entity::tax_rules::Model) -> Self {
+        Self {
+            id: entity.id,
+            direction: tax_direction_name(&entity.direction).to_string(),
+            region: tax_region_name(&entity.region).to_string(),
+            vat_id: entity.vat_id,
+            product_tax_category: entity.product_tax_category.clone(),
+            tax_id: entity.tax_id,
+        }
+    }
+}
+
+pub fn tax_direction_name(direction: &TaxDirection) -> &'static str {
+    match direction {
+        TaxDirection::Sale => "sale",
+        TaxDirection::Purchase => "purchase",
+    }
+}
+
+pub fn tax_direction_from_name(name: &str) -> Option<TaxDirection> {
+    match name {
+        "sale" => Some(TaxDirection::Sale),
+        "purchase" => Some(TaxDirection::Purchase),
+        _ => None,
+    }
+}
+
+pub fn tax_region_name(region: &TaxRegion) -> &'static str {
+    match region {
+        TaxRegion::Domestic => "domestic",
+        TaxRegion::Eu => "eu",
+        TaxRegion::NonEu => "non_eu",
+    }
+}
+
+pub fn tax_region_from_name(name: &str) -> Option<TaxRegion> {
+    match name {
+        "domestic" => Some(TaxRegion::Domestic),
+        "eu" => Some(TaxRegion::Eu),
+        "non_eu" => Some(TaxRegion::NonEu),
+        _ => None,
+    }
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct CreateTaxRuleInput {
+    /// `sale` or `purchase`.
+    pub direction: String,
+    /// `domestic`, `eu` or `non_eu`, seen from the company's country.
+    pub region: String,
+    /// Whether the contact must have a VAT ID, or null if it doesn't matter.
+    #[serde(rename = "vatId")]
+    pub vat_id: Option<bool>,
+    /// The product tax category the rule is limited to, or null for all.
+    #[serde(rename = "productTaxCategory")]
+    #[validate(length(min = 1, max = 32))]
+    pub product_tax_category: Option<String>,
+    #[serde(rename = "taxId")]
+    pub tax_id: i32,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct ListTaxRulesInput {
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u64>,
+    #[validate(range(min = 0))]
+    pub offset: Option<u64>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct DetermineTaxInput {
+    pub direction: String,
+    #[serde(rename = "contactId")]
+    pub contact_id: i32,
+    #[serde(rename = "variantId")]
+    pub variant_id: Option<i32>,
+    pub date: Option<String>,
+}
+
+/// The tax a line for the contact and variant gets. The tax's note has to be
+/// printed on the invoice.
+#[derive(Serialize, ToSchema)]
+pub struct TaxDeterminationModel {
+    pub region: String,
+    #[serde(rename = "ruleId")]
+    pub rule_id: i32,
+    pub tax: TaxModel,
+}
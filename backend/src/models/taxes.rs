@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use validator::Validate;
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct TaxModel {
     pub id: i32,
     pub name: String,
@@ -11,6 +11,8 @@ pub struct TaxModel {
     pub rate: f64,
     #[serde(rename = "accountId")]
     pub account_id: i32,
+    pub category: String,
+    pub note: String,
 }
 
 impl From<&entity::taxes::Model> for TaxModel {
@@ -21,6 +23,8 @@ impl From<&entity::taxes::Model> for TaxModel {
             name_short: entity.name_short.clone(),
             rate: entity.rate,
             account_id: entity.account_id,
+            category: entity.category.clone(),
+            note: entity.note.clone(),
         }
     }
 }
@@ -36,6 +40,14 @@ pub struct CreateTaxInput {
     pub rate: f64,
     #[serde(rename = "account")]
     pub account_id: i32,
+    /// UNTDID 5305 code like `S` for standard rated or `AE` for reverse
+    /// charge. Defaults to `S`, or `Z` for a rate of zero.
+    pub category: Option<String>,
+    /// Printed on every invoice with a line of this tax, e.g. the legal
+    /// reason of an exemption.
+    #[serde(default)]
+    #[validate(length(max = 1000))]
+    pub note: String,
 }
 
 #[derive(Deserialize, Validate)]
@@ -45,3 +57,78 @@ pub struct ListTaxesInput {
     #[validate(range(min = 0))]
     pub offset: Option<u64>,
 }
+
+/// A rate of a tax limited to a period. Outside of all its versions a tax
+/// has the rate stored on the tax itself.
+#[derive(Serialize, ToSchema)]
+pub struct TaxRateModel {
+    pub id: i32,
+    #[serde(rename = "taxId")]
+    pub tax_id: i32,
+    pub rate: f64,
+    #[serde(rename = "validFrom")]
+    pub valid_from: String,
+    #[serde(rename = "validTo")]
+    pub valid_to: Option<String>,
+}
+
+impl From<&entity::tax_rates::Model> for TaxRateModel {
+    fn from(entity: &entity::tax_rates::Model) -> Self {
+        Self {
+            id: entity.id,
+            tax_id: entity.tax_id,
+            rate: entity.rate,
+            valid_from: entity.valid_from.to_string(),
+            valid_to: entity.valid_to.map(|date| date.to_string()),
+        }
+    }
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct CreateTaxRateInput {
+    #[validate(range(min = 0f64))]
+    pub rate: f64,
+    /// First day the rate applies, as YYYY-MM-DD.
+    #[serde(rename = "validFrom")]
+    pub valid_from: String,
+    /// Last day the rate applies, or null if it applies until further notice.
+    #[serde(rename = "validTo")]
+    pub valid_to: Option<String>,
+}
+
+/// A component of a tax group, levied in the order of the sequence.
+#[derive(Serialize, ToSchema)]
+pub struct TaxComponentModel {
+    #[serde(rename = "taxId")]
+    pub tax_id: i32,
+    pub sequence: i32,
+    pub compound: bool,
+}
+
+impl From<&entity::tax_components::Model> for TaxComponentModel {
+    fn from(entity: &entity::tax_components::Model) -> Self {
+        Self {
+            tax_id: entity.tax_id,
+            sequence: entity.sequence,
+            compound: entity.compound,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Validate, ToSchema)]
+pub struct TaxComponentInput {
+    #[serde(rename = "taxId")]
+    #[validate(range(min = 1))]
+    pub tax_id: i32,
+    /// Levies the tax on the net amount plus the components before.
+    #[serde(default)]
+    pub compound: bool,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct SetTaxComponentsInput {
+    /// The components in the order they are levied. An empty list turns
+    /// the group back into a plain tax.
+    #[validate(length(max = 10), nested)]
+    pub components: Vec<TaxComponentInput>,
+}
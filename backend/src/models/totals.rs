@@ -0,0 +1,83 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::utils::totals::DocumentTotals;
+
+/// Amounts of a document line in cents.
+#[derive(Serialize, ToSchema)]
+pub struct LineTotalsModel {
+    #[serde(rename = "lineId")]
+    pub line_id: i32,
+    /// Quantity times price, before any discount.
+    pub amount: i64,
+    pub discount: i64,
+    /// What is left after the line discount.
+    pub net: i64,
+    /// The line's share of the document discount.
+    #[serde(rename = "documentDiscount")]
+    pub document_discount: i64,
+    /// Rounded per line for display, so the lines can be a few cents off
+    /// from the tax total of the document.
+    pub tax: i64,
+    pub gross: i64,
+}
+
+/// Everything levied at one rate, with the tax rounded once.
+#[derive(Serialize, ToSchema)]
+pub struct RateTotalsModel {
+    pub rate: f64,
+    pub base: i64,
+    pub amount: i64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DocumentTotalsModel {
+    pub lines: Vec<LineTotalsModel>,
+    pub taxes: Vec<RateTotalsModel>,
+    /// The sum of the line nets, before the document discount.
+    #[serde(rename = "lineTotal")]
+    pub line_total: i64,
+    pub discount: i64,
+    #[serde(rename = "netTotal")]
+    pub net_total: i64,
+    #[serde(rename = "taxTotal")]
+    pub tax_total: i64,
+    #[serde(rename = "grossTotal")]
+    pub gross_total: i64,
+}
+
+impl DocumentTotalsModel {
+    /// Pairs the line totals with the ids of the lines they were computed
+    /// from, in the same order.
+    pub fn new(line_ids: impl IntoIterator<Item = i32>, totals: DocumentTotals) -> Self {
+        Self {
+            lines: line_ids
+                .into_iter()
+                .zip(totals.lines)
+                .map(|(line_id, line)| LineTotalsModel {
+                    line_id,
+                    amount: line.amount,
+                    discount: line.discount,
+                    net: line.net,
+                    document_discount: line.document_discount,
+                    tax: line.tax,
+                    gross: line.gross,
+                })
+                .collect(),
+            taxes: totals
+                .rates
+                .into_iter()
+                .map(|rate| RateTotalsModel {
+                    rate: rate.rate,
+                    base: rate.base,
+                    amount: rate.amount,
+                })
+                .collect(),
+            line_total: totals.line_total,
+            discount: totals.discount,
+            net_total: totals.net,
+            tax_total: totals.tax,
+            gross_total: totals.gross,
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Serialize, ToSchema)]
+pub struct UnitModel {
+    pub id: i32,
+    pub name: String,
+    pub symbol: String,
+    #[serde(rename = "baseUnitId")]
+    pub base_unit_id: Option<i32>,
+    pub factor: f64,
+}
+
+impl From<&entity::units::Model> for UnitModel {
+    fn from(entity: &entity::units::Model) -> Self {
+        Self {
+            id: entity.id,
+            name: entity.name.clone(),
+            symbol: entity.symbol.clone(),
+            base_unit_id: entity.base_unit_id,
+            factor: entity.factor,
+        }
+    }
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct CreateUnitInput {
+    #[validate(length(min = 1, max = 32))]
+    pub name: String,
+    #[validate(length(min = 1, max = 8))]
+    pub symbol: String,
+    /// The unit this one is a multiple of, e.g. piece for a box of 12.
+    /// Without one the unit is a base unit.
+    #[serde(rename = "baseUnitId")]
+    #[validate(range(min = 1))]
+    pub base_unit_id: Option<i32>,
+    /// How many base units one of this unit is, e.g. 12 for a box of 12.
+    /// Always 1 for base units.
+    #[serde(default = "default_factor")]
+    #[validate(range(exclusive_min = 0.0))]
+    pub factor: f64,
+}
+
+fn default_factor() -> f64 {
+    1.0
+}
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use validator::Validate;
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct VariantModel {
     pub id: i32,
     pub name: String,
@@ -12,6 +12,10 @@ pub struct VariantModel {
     pub purchase_price: i32,
     #[serde(rename = "productId")]
     pub product_id: i32,
+    pub sku: Option<String>,
+    pub gtin: Option<String>,
+    #[serde(rename = "unitId")]
+    pub unit_id: Option<i32>,
 }
 
 impl From<&entity::variants::Model> for VariantModel {
@@ -22,6 +26,9 @@ impl From<&entity::variants::Model> for VariantModel {
             sale_price: entity.sale_price,
             purchase_price: entity.purchase_price,
             product_id: entity.product_id,
+            sku: entity.sku.clone(),
+            gtin: entity.gtin.clone(),
+            unit_id: entity.unit_id,
         }
     }
 }
@@ -45,6 +52,53 @@ pub struct CreateVariantInput {
     #[serde(rename = "productId")]
     #[validate(range(min = 1))]
     pub product_id: i32,
+    /// The article number the variant is known by in the warehouse.
+    #[validate(length(min = 1, max = 64))]
+    pub sku: Option<String>,
+    /// The EAN or UPC on the variant's barcode, checked by its check digit.
+    pub gtin: Option<String>,
+    /// The unit the variant's stock is kept in, e.g. piece or kg.
+    #[serde(rename = "unitId")]
+    #[validate(range(min = 1))]
+    pub unit_id: Option<i32>,
+}
+
+/// Name template of generated variants without one.
+pub const DEFAULT_VARIANT_NAME_TEMPLATE: &str = "{product} {values}";
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct GenerateVariantsInput {
+    /// How variants are named: `{product}` is the product's name, `{values}`
+    /// the values separated by slashes and an attribute's name in braces
+    /// like `{Size}` its value. Defaults to `{product} {values}`.
+    #[serde(rename = "nameTemplate")]
+    #[validate(length(min = 1, max = 128))]
+    pub name_template: Option<String>,
+    #[serde(rename = "salePrice")]
+    #[validate(range(min = 0))]
+    pub sale_price: i32,
+    #[serde(rename = "purchasePrice")]
+    #[validate(range(min = 0))]
+    pub purchase_price: i32,
+    /// Prices of single combinations which differ from the default ones,
+    /// e.g. for an XXL size.
+    #[serde(default)]
+    #[validate(length(max = 1000), nested)]
+    pub overrides: Vec<VariantPriceOverrideInput>,
+}
+
+#[derive(Serialize, Deserialize, Validate, ToSchema)]
+pub struct VariantPriceOverrideInput {
+    /// One value of each attribute of the product.
+    #[serde(rename = "valueIds")]
+    #[validate(length(min = 1))]
+    pub value_ids: Vec<i32>,
+    #[serde(rename = "salePrice")]
+    #[validate(range(min = 0))]
+    pub sale_price: Option<i32>,
+    #[serde(rename = "purchasePrice")]
+    #[validate(range(min = 0))]
+    pub purchase_price: Option<i32>,
 }
 
 #[derive(Deserialize, Validate)]
@@ -1,33 +1,75 @@
+use entity::prelude::Contacts;
 use macros::make_repo;
-use sea_orm::DbErr;
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::Set,
+    ColumnTrait, DbErr, EntityTrait, QueryFilter,
+    sea_query::{Expr, Func},
+};
 
 use crate::models::Page;
 
+/// A customer or supplier to create. The account becomes the customer or
+/// supplier account of the contact.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContactDraft {
+    pub name: String,
+    pub account_id: i32,
+    pub street1: String,
+    pub street2: String,
+    pub postal_code: String,
+    pub city: String,
+    pub country: String,
+    pub currency: String,
+}
+
+impl ContactDraft {
+    fn into_active_model(self) -> entity::contacts::ActiveModel {
+        entity::contacts::ActiveModel {
+            name: Set(self.name),
+            street1: Set(self.street1),
+            street2: Set(self.street2),
+            postal_code: Set(self.postal_code),
+            city: Set(self.city),
+            country: Set(self.country),
+            vat_id: Set(String::new()),
+            currency: Set(self.currency),
+            ..Default::default()
+        }
+    }
+}
+
 make_repo!(Contact, (
     async fn create_customer(
         &self,
-        _name: String,
-        _account_id: i32,
-        _street1: String,
-        _street2: String,
-        _postal_code: String,
-        _city: String,
-        _county: String,
+        contact: ContactDraft,
     ) -> Result<entity::contacts::Model, DbErr> {
-        todo!()
+        let account_id = contact.account_id;
+        let entity = entity::contacts::ActiveModel {
+            is_customer: Set(true),
+            is_supplier: Set(false),
+            customer_account_id: Set(Some(account_id)),
+            supplier_account_id: Set(None),
+            ..contact.into_active_model()
+        };
+
+        entity.insert(&self.db).await
     }
 
     async fn create_supplier(
         &self,
-        _name: String,
-        _account_id: i32,
-        _street1: String,
-        _street2: String,
-        _postal_code: String,
-        _city: String,
-        _county: String,
+        contact: ContactDraft,
     ) -> Result<entity::contacts::Model, DbErr> {
-        todo!()
+        let account_id = contact.account_id;
+        let entity = entity::contacts::ActiveModel {
+            is_customer: Set(false),
+            is_supplier: Set(true),
+            customer_account_id: Set(None),
+            supplier_account_id: Set(Some(account_id)),
+            ..contact.into_active_model()
+        };
+
+        entity.insert(&self.db).await
     }
 
     async fn list_customers(
@@ -46,8 +88,33 @@ make_repo!(Contact, (
         todo!()
     }
 
-    async fn get(&self, _id: i32) -> Result<Option<entity::contacts::Model>, DbErr> {
-        todo!()
+    async fn get(&self, id: i32) -> Result<Option<entity::contacts::Model>, DbErr> {
+        Contacts::find_by_id(id).one(&self.db).await
+    }
+
+    async fn find_suppliers_by_vat_id(
+        &self,
+        vat_id: String,
+    ) -> Result<Vec<entity::contacts::Model>, DbErr> {
+        Contacts::find()
+            .filter(entity::contacts::Column::IsSupplier.eq(true))
+            .filter(entity::contacts::Column::VatId.eq(vat_id))
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_suppliers_by_name(
+        &self,
+        name: String,
+    ) -> Result<Vec<entity::contacts::Model>, DbErr> {
+        Contacts::find()
+            .filter(entity::contacts::Column::IsSupplier.eq(true))
+            .filter(
+                Expr::expr(Func::lower(Expr::col(entity::contacts::Column::Name)))
+                    .eq(name.to_lowercase()),
+            )
+            .all(&self.db)
+            .await
     }
 
     async fn delete_customer(&self, _id: i32) -> Result<bool, DbErr> {
@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use entity::prelude::LedgerTransactions;
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::{NotSet, Set},
+    ConnectionTrait, DbErr, EntityTrait,
+};
+
+use super::{LotDraft, assign_lots, value_move};
+
+/// Returned as `DbErr::Custom` when a credit note would credit more of an
+/// invoice line than is left of it.
+pub const ERR_CREDIT_EXCEEDS_INVOICE: &str = "the credit note exceeds the invoice";
+
+/// Quantities are stored as floating point numbers, so they are compared
+/// with a little tolerance.
+pub const QUANTITY_TOLERANCE: f64 = 1e-9;
+
+/// A line of a credit note as resolved from the credited invoice line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CreditNoteLineDraft {
+    pub invoice_line_id: i32,
+    pub description: String,
+    pub quantity: f64,
+    pub price: i32,
+    pub tax: i32,
+    pub tax_id: Option<i32>,
+    pub variant_id: Option<i32>,
+    /// The net and tax amount in cents, booked back on the accounts of the
+    /// invoice line's postings.
+    pub net: i32,
+    pub tax_amount: i32,
+    pub transaction_id: Option<i32>,
+    pub tax_transaction_id: Option<i32>,
+    /// The credited discount in cents. Lines whose discount was posted on
+    /// its own reverse the undiscounted amount on `transaction_id` and the
+    /// discount on `discount_transaction_id`.
+    pub discount: i32,
+    pub discount_transaction_id: Option<i32>,
+    /// One per component for lines with a tax group, whose tax isn't posted
+    /// through `tax_transaction_id`.
+    pub tax_postings: Vec<TaxPostingDraft>,
+    /// The source and target stock of the move returning the goods.
+    pub stock_return: Option<(i32, i32)>,
+    /// The lots the goods of tracked variants are returned in.
+    pub lots: Vec<LotDraft>,
+}
+
+/// The share of a tax group component in a credited line, booked back on
+/// the accounts of the invoice line's posting of that component.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaxPostingDraft {
+    pub tax_id: i32,
+    pub amount: i32,
+    pub transaction_id: Option<i32>,
+}
+
+/// Checks that the drafts together with the lines credited before stay
+/// within the quantities of the invoice lines. Both are given as pairs of
+/// invoice line id and quantity.
+pub(super) fn check_remaining(
+    invoiced: &[(i32, f64)],
+    credited: &[(i32, f64)],
+    drafts: &[CreditNoteLineDraft],
+) -> Result<(), DbErr> {
+    let mut remaining: HashMap<i32, f64> = invoiced.iter().copied().collect();
+    let credited = credited.iter().copied().chain(
+        drafts
+            .iter()
+            .map(|draft| (draft.invoice_line_id, draft.quantity)),
+    );
+    for (invoice_line_id, quantity) in credited {
+        let left = remaining
+            .get_mut(&invoice_line_id)
+            .ok_or(DbErr::Custom(ERR_CREDIT_EXCEEDS_INVOICE.to_string()))?;
+        *left -= quantity;
+        if *left < -QUANTITY_TOLERANCE {
+            return Err(DbErr::Custom(ERR_CREDIT_EXCEEDS_INVOICE.to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// Books the amount from the credit back to the debit account of the
+/// original transaction. Lines that were never posted aren't reversed.
+pub(super) async fn reverse_transaction<C: ConnectionTrait>(
+    db: &C,
+    transaction_id: Option<i32>,
+    date: NaiveDate,
+    amount: i32,
+) -> Result<Option<i32>, DbErr> {
+    let Some(transaction_id) = transaction_id else {
+        return Ok(None);
+    };
+
+    let original = LedgerTransactions::find_by_id(transaction_id)
+        .one(db)
+        .await?
+        .ok_or(DbErr::RecordNotFound("ledger transaction".to_string()))?;
+
+    let reversal = entity::ledger_transactions::ActiveModel {
+        id: NotSet,
+        date: Set(date),
+        amount: Set(amount),
+        debit_account_id: Set(original.credit_account_id),
+        credit_account_id: Set(original.debit_account_id),
+    }
+    .insert(db)
+    .await?;
+
+    Ok(Some(reversal.id))
+}
+
+/// Moves the goods of the line back in their lots, if they are returned at
+/// all, and values the move like any other.
+pub(super) async fn return_stock<C: ConnectionTrait>(
+    db: &C,
+    draft: &CreditNoteLineDraft,
+) -> Result<Option<i32>, DbErr> {
+    let (Some(variant_id), Some((source_stock_id, target_stock_id))) =
+        (draft.variant_id, draft.stock_return)
+    else {
+        return Ok(None);
+    };
+
+    let stock_move = entity::stock_moves::ActiveModel {
+        id: NotSet,
+        quantity: Set(draft.quantity),
+        variant_id: Set(variant_id),
+        source_stock_id: Set(source_stock_id),
+        target_stock_id: Set(target_stock_id),
+    }
+    .insert(db)
+    .await?;
+    assign_lots(db, &stock_move, &draft.lots).await?;
+    value_move(db, &stock_move, None).await?;
+
+    Ok(Some(stock_move.id))
+}
@@ -0,0 +1,210 @@
+use chrono::NaiveDate;
+use entity::{
+    prelude::{InventoryCountEntries, InventoryCountLines, InventoryCounts},
+    sea_orm_active_enums::InventoryCountStatus,
+};
+use macros::make_repo;
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::{NotSet, Set},
+    ColumnTrait, DbErr, EntityTrait, JoinType, QueryFilter, QueryOrder, QuerySelect, RelationTrait,
+    TransactionTrait,
+};
+
+use super::{LineMoveDraft, QUANTITY_TOLERANCE, balances, move_line, stock_lot_balances};
+
+/// A line of an inventory count whose stock is adjusted to what was
+/// counted. The line's quantity is the difference, in the variant's unit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdjustmentDraft {
+    /// Whether more was counted than expected, which moves the difference
+    /// from the inventory loss location into the counted one rather than
+    /// out of it.
+    pub found: bool,
+    pub line: LineMoveDraft,
+}
+
+make_repo!(InventoryCount, (
+    /// Opens a count of the location with a line for every variant that
+    /// should be there, expecting what the moves into and out of it add up
+    /// to. Tracked variants get a line for each of their lots.
+    async fn create(
+        &self,
+        stock_id: i32,
+        date: NaiveDate,
+    ) -> Result<entity::inventory_counts::Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let count = entity::inventory_counts::ActiveModel {
+            id: NotSet,
+            stock_id: Set(stock_id),
+            status: Set(InventoryCountStatus::Open),
+            date: Set(date),
+        }
+        .insert(&txn)
+        .await?;
+
+        // Tracked variants get a line per lot the location holds. Whatever
+        // isn't in any lot is expected on a line without a lot number.
+        let mut expected = stock_lot_balances(&txn, stock_id).await?;
+        for (variant_id, quantity) in balances(&txn, &[stock_id]).await? {
+            let in_lots: f64 = expected
+                .iter()
+                .filter(|((id, _), _)| *id == variant_id)
+                .map(|(_, quantity)| quantity)
+                .sum();
+            expected.insert((variant_id, String::new()), quantity - in_lots);
+        }
+
+        let lines: Vec<entity::inventory_count_lines::ActiveModel> = expected
+            .into_iter()
+            .filter(|(_, quantity)| quantity.abs() > QUANTITY_TOLERANCE)
+            .map(
+                |((variant_id, lot_number), quantity)| entity::inventory_count_lines::ActiveModel {
+                    id: NotSet,
+                    count_id: Set(count.id),
+                    variant_id: Set(variant_id),
+                    expected_quantity: Set(quantity),
+                    move_id: Set(None),
+                    lot_number: Set(lot_number),
+                },
+            )
+            .collect();
+        if !lines.is_empty() {
+            InventoryCountLines::insert_many(lines).exec(&txn).await?;
+        }
+
+        txn.commit().await?;
+        Ok(count)
+    }
+
+    async fn get(&self, id: i32) -> Result<Option<entity::inventory_counts::Model>, DbErr> {
+        InventoryCounts::find_by_id(id).one(&self.db).await
+    }
+
+    async fn list_lines(
+        &self,
+        count_id: i32,
+    ) -> Result<Vec<entity::inventory_count_lines::Model>, DbErr> {
+        InventoryCountLines::find()
+            .filter(entity::inventory_count_lines::Column::CountId.eq(count_id))
+            .order_by_asc(entity::inventory_count_lines::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    /// Lists what the counters found of the lines of the count.
+    async fn list_entries(
+        &self,
+        count_id: i32,
+    ) -> Result<Vec<entity::inventory_count_entries::Model>, DbErr> {
+        InventoryCountEntries::find()
+            .join(
+                JoinType::InnerJoin,
+                entity::inventory_count_entries::Relation::InventoryCountLines.def(),
+            )
+            .filter(entity::inventory_count_lines::Column::CountId.eq(count_id))
+            .order_by_asc(entity::inventory_count_entries::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    /// Records what a counter found of the variant, in the variant's unit,
+    /// replacing what the counter recorded of it before. Tracked variants
+    /// are recorded per lot, untracked ones with an empty lot number.
+    /// Variants or lots that weren't expected in the location get a line
+    /// expecting none.
+    async fn record(
+        &self,
+        count_id: i32,
+        variant_id: i32,
+        lot_number: String,
+        counter: String,
+        quantity: f64,
+    ) -> Result<entity::inventory_count_entries::Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let line = InventoryCountLines::find()
+            .filter(entity::inventory_count_lines::Column::CountId.eq(count_id))
+            .filter(entity::inventory_count_lines::Column::VariantId.eq(variant_id))
+            .filter(entity::inventory_count_lines::Column::LotNumber.eq(lot_number.as_str()))
+            .one(&txn)
+            .await?;
+        let line = match line {
+            Some(line) => line,
+            None => {
+                entity::inventory_count_lines::ActiveModel {
+                    id: NotSet,
+                    count_id: Set(count_id),
+                    variant_id: Set(variant_id),
+                    expected_quantity: Set(0.0),
+                    move_id: Set(None),
+                    lot_number: Set(lot_number),
+                }
+                .insert(&txn)
+                .await?
+            }
+        };
+
+        let entry = InventoryCountEntries::find()
+            .filter(entity::inventory_count_entries::Column::LineId.eq(line.id))
+            .filter(entity::inventory_count_entries::Column::Counter.eq(counter.as_str()))
+            .one(&txn)
+            .await?;
+        let entry = match entry {
+            Some(entry) => {
+                let mut entry: entity::inventory_count_entries::ActiveModel = entry.into();
+                entry.quantity = Set(quantity);
+                entry.update(&txn).await?
+            }
+            None => {
+                entity::inventory_count_entries::ActiveModel {
+                    id: NotSet,
+                    line_id: Set(line.id),
+                    counter: Set(counter),
+                    quantity: Set(quantity),
+                }
+                .insert(&txn)
+                .await?
+            }
+        };
+
+        txn.commit().await?;
+        Ok(entry)
+    }
+
+    /// Moves the differences between what was counted and what was
+    /// expected into or out of the counted location against the inventory
+    /// loss location, posts their value if the lines have accounts for it
+    /// and closes the count.
+    async fn approve(
+        &self,
+        count: entity::inventory_counts::Model,
+        date: NaiveDate,
+        loss_stock_id: i32,
+        adjustments: Vec<AdjustmentDraft>,
+    ) -> Result<entity::inventory_counts::Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        for adjustment in &adjustments {
+            let (source_stock_id, target_stock_id) = match adjustment.found {
+                true => (loss_stock_id, count.stock_id),
+                false => (count.stock_id, loss_stock_id),
+            };
+            let move_id =
+                move_line(&txn, date, source_stock_id, target_stock_id, &adjustment.line).await?;
+            InventoryCountLines::update_many()
+                .col_expr(entity::inventory_count_lines::Column::MoveId, move_id.into())
+                .filter(entity::inventory_count_lines::Column::Id.eq(adjustment.line.line_id))
+                .exec(&txn)
+                .await?;
+        }
+
+        let mut count: entity::inventory_counts::ActiveModel = count.into();
+        count.status = Set(InventoryCountStatus::Approved);
+        let count = count.update(&txn).await?;
+
+        txn.commit().await?;
+        Ok(count)
+    }
+));
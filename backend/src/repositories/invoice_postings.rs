@@ -0,0 +1,52 @@
+use chrono::NaiveDate;
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::{NotSet, Set},
+    ConnectionTrait, DbErr,
+};
+
+/// A ledger transaction booking the amount in cents from the credit to the
+/// debit account.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PostingDraft {
+    pub amount: i32,
+    pub debit_account_id: i32,
+    pub credit_account_id: i32,
+}
+
+/// The postings of an invoice line that hasn't been posted yet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinePostingDraft {
+    pub line_id: i32,
+    /// The tax the line is posted with, which is the category's default
+    /// for lines without one.
+    pub tax_id: Option<i32>,
+    /// The net amount after the line discount and the line's share of the
+    /// invoice discount, or the undiscounted amount if the discount is
+    /// posted on its own.
+    pub net: PostingDraft,
+    /// Both discounts of lines whose category has a discount account.
+    pub discount: Option<PostingDraft>,
+    /// The tax of lines with a plain tax.
+    pub tax: Option<PostingDraft>,
+    /// One per component for lines with a tax group, by component tax id.
+    pub tax_postings: Vec<(i32, PostingDraft)>,
+}
+
+pub(super) async fn insert_posting<C: ConnectionTrait>(
+    db: &C,
+    date: NaiveDate,
+    posting: &PostingDraft,
+) -> Result<i32, DbErr> {
+    let transaction = entity::ledger_transactions::ActiveModel {
+        id: NotSet,
+        date: Set(date),
+        amount: Set(posting.amount),
+        debit_account_id: Set(posting.debit_account_id),
+        credit_account_id: Set(posting.credit_account_id),
+    }
+    .insert(db)
+    .await?;
+
+    Ok(transaction.id)
+}
@@ -0,0 +1,271 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::NaiveDate;
+use entity::{
+    prelude::{
+        Contacts, Lots, Products, PurchaseOrderLines, PurchaseOrders, SaleOrderLines, SaleOrders,
+        StockMoveLots, StockMoves, Stocks, Variants,
+    },
+    sea_orm_active_enums::Tracking,
+};
+use macros::make_repo;
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::{NotSet, Set},
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, JoinType, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait,
+};
+
+use super::{QUANTITY_TOLERANCE, is_own_stock};
+
+/// Returned as `DbErr::Custom` when goods are taken out of the own stocks
+/// with a lot or serial number that was never received.
+pub const ERR_LOT_NOT_FOUND: &str = "the lot or serial number doesn't exist";
+/// Returned as `DbErr::Custom` when a lot is moved out of an own stock
+/// that doesn't hold enough of it.
+pub const ERR_LOT_NOT_IN_STOCK: &str = "the stock doesn't hold enough of the lot";
+/// Returned as `DbErr::Custom` when a serial number is received that is
+/// still in the own stocks.
+pub const ERR_SERIAL_IN_STOCK: &str = "the serial number is already in stock";
+
+/// The part of a move of tracked goods that belongs to one lot or serial
+/// number. The quantity is in the variant's unit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LotDraft {
+    pub number: String,
+    pub quantity: f64,
+    /// Only kept for lots that are received for the first time.
+    pub expiry_date: Option<NaiveDate>,
+}
+
+/// A move a lot was part of, with the order that moved it and the
+/// supplier or customer of the order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LotMove {
+    pub move_id: i32,
+    pub quantity: f64,
+    pub source_stock_id: i32,
+    pub target_stock_id: i32,
+    pub purchase_order_id: Option<i32>,
+    pub sale_order_id: Option<i32>,
+    pub contact: Option<entity::contacts::Model>,
+}
+
+/// Splits a move over its lots. Goods coming from outside the own stocks
+/// may bring new lots, which are created. Goods leaving an own stock must
+/// come from lots that stock holds. Each lot stays locked until the end of
+/// the transaction, so moves of the same lot at the same time can't both
+/// pass the check against its balance.
+pub(super) async fn assign_lots<C: ConnectionTrait>(
+    db: &C,
+    stock_move: &entity::stock_moves::Model,
+    lots: &[LotDraft],
+) -> Result<(), DbErr> {
+    if lots.is_empty() {
+        return Ok(());
+    }
+
+    let Some((_, Some(product))) = Variants::find_by_id(stock_move.variant_id)
+        .find_also_related(Products)
+        .one(db)
+        .await?
+    else {
+        return Err(DbErr::RecordNotFound("variant".to_string()));
+    };
+    let from_own = is_own_stock(db, stock_move.source_stock_id).await?;
+    let own_ids: Vec<i32> = Stocks::find()
+        .filter(entity::stocks::Column::IsOwn.eq(true))
+        .all(db)
+        .await?
+        .iter()
+        .map(|stock| stock.id)
+        .collect();
+
+    for draft in lots {
+        let found = Lots::find()
+            .filter(entity::lots::Column::VariantId.eq(stock_move.variant_id))
+            .filter(entity::lots::Column::Number.eq(draft.number.as_str()))
+            .lock_exclusive()
+            .one(db)
+            .await?;
+        let lot = match found {
+            Some(lot) => lot,
+            None if from_own => return Err(DbErr::Custom(ERR_LOT_NOT_FOUND.to_string())),
+            None => {
+                entity::lots::ActiveModel {
+                    id: NotSet,
+                    variant_id: Set(stock_move.variant_id),
+                    number: Set(draft.number.clone()),
+                    expiry_date: Set(draft.expiry_date),
+                }
+                .insert(db)
+                .await?
+            }
+        };
+
+        let balances = lot_balances(db, lot.id).await?;
+        if from_own {
+            let held = balances
+                .get(&stock_move.source_stock_id)
+                .copied()
+                .unwrap_or(0.0);
+            if held < draft.quantity - QUANTITY_TOLERANCE {
+                return Err(DbErr::Custom(ERR_LOT_NOT_IN_STOCK.to_string()));
+            }
+        } else if product.tracking == Tracking::Serial {
+            let in_stock: f64 = own_ids.iter().filter_map(|id| balances.get(id)).sum();
+            if in_stock > QUANTITY_TOLERANCE {
+                return Err(DbErr::Custom(ERR_SERIAL_IN_STOCK.to_string()));
+            }
+        }
+
+        entity::stock_move_lots::ActiveModel {
+            id: NotSet,
+            move_id: Set(stock_move.id),
+            lot_id: Set(lot.id),
+            quantity: Set(draft.quantity),
+        }
+        .insert(db)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Sums up the moves of a lot into the quantity each stock holds of it.
+async fn lot_balances<C: ConnectionTrait>(db: &C, lot_id: i32) -> Result<HashMap<i32, f64>, DbErr> {
+    let moves = StockMoveLots::find()
+        .filter(entity::stock_move_lots::Column::LotId.eq(lot_id))
+        .find_also_related(StockMoves)
+        .all(db)
+        .await?;
+
+    let mut balances = HashMap::new();
+    for (move_lot, stock_move) in moves {
+        let Some(stock_move) = stock_move else {
+            continue;
+        };
+        *balances.entry(stock_move.target_stock_id).or_insert(0.0) += move_lot.quantity;
+        *balances.entry(stock_move.source_stock_id).or_insert(0.0) -= move_lot.quantity;
+    }
+    Ok(balances)
+}
+
+/// Sums up the moves of lots into and out of the stock into the quantity it
+/// holds of each, by variant id and lot number. Lots the stock never held
+/// are left out.
+pub(super) async fn stock_lot_balances<C: ConnectionTrait>(
+    db: &C,
+    stock_id: i32,
+) -> Result<BTreeMap<(i32, String), f64>, DbErr> {
+    let mut totals: BTreeMap<(i32, String), f64> = BTreeMap::new();
+    for (column, sign) in [
+        (entity::stock_moves::Column::TargetStockId, 1.0),
+        (entity::stock_moves::Column::SourceStockId, -1.0),
+    ] {
+        let sums: Vec<(i32, String, f64)> = StockMoveLots::find()
+            .select_only()
+            .column(entity::lots::Column::VariantId)
+            .column(entity::lots::Column::Number)
+            .column_as(entity::stock_move_lots::Column::Quantity.sum(), "quantity")
+            .join(
+                JoinType::InnerJoin,
+                entity::stock_move_lots::Relation::Lots.def(),
+            )
+            .join(
+                JoinType::InnerJoin,
+                entity::stock_move_lots::Relation::StockMoves.def(),
+            )
+            .filter(column.eq(stock_id))
+            .group_by(entity::lots::Column::VariantId)
+            .group_by(entity::lots::Column::Number)
+            .into_tuple()
+            .all(db)
+            .await?;
+        for (variant_id, number, quantity) in sums {
+            *totals.entry((variant_id, number)).or_default() += sign * quantity;
+        }
+    }
+    Ok(totals)
+}
+
+make_repo!(Lot, (
+    async fn get(&self, id: i32) -> Result<Option<entity::lots::Model>, DbErr> {
+        Lots::find_by_id(id).one(&self.db).await
+    }
+
+    /// Lists the lots and serial numbers of a variant, the oldest first.
+    async fn list(&self, variant_id: i32) -> Result<Vec<entity::lots::Model>, DbErr> {
+        Lots::find()
+            .filter(entity::lots::Column::VariantId.eq(variant_id))
+            .order_by_asc(entity::lots::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    /// Follows a lot through every move it was part of, in the order they
+    /// were made, with the purchase or sale orders behind them.
+    async fn trace(&self, lot_id: i32) -> Result<Vec<LotMove>, DbErr> {
+        let moves: Vec<(entity::stock_move_lots::Model, entity::stock_moves::Model)> =
+            StockMoveLots::find()
+                .filter(entity::stock_move_lots::Column::LotId.eq(lot_id))
+                .find_also_related(StockMoves)
+                .order_by_asc(entity::stock_move_lots::Column::MoveId)
+                .all(&self.db)
+                .await?
+                .into_iter()
+                .filter_map(|(move_lot, stock_move)| stock_move.map(|m| (move_lot, m)))
+                .collect();
+        let move_ids: Vec<i32> = moves.iter().map(|(_, stock_move)| stock_move.id).collect();
+
+        let purchases: HashMap<i32, entity::purchase_orders::Model> = PurchaseOrderLines::find()
+            .filter(entity::purchase_order_lines::Column::MoveId.is_in(move_ids.clone()))
+            .find_also_related(PurchaseOrders)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .filter_map(|(line, order)| Some((line.move_id?, order?)))
+            .collect();
+        let sales: HashMap<i32, entity::sale_orders::Model> = SaleOrderLines::find()
+            .filter(entity::sale_order_lines::Column::MoveId.is_in(move_ids))
+            .find_also_related(SaleOrders)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .filter_map(|(line, order)| Some((line.move_id?, order?)))
+            .collect();
+
+        let contact_ids: Vec<i32> = purchases
+            .values()
+            .map(|order| order.supplier_id)
+            .chain(sales.values().map(|order| order.customer_id))
+            .collect();
+        let contacts: HashMap<i32, entity::contacts::Model> = Contacts::find()
+            .filter(entity::contacts::Column::Id.is_in(contact_ids))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|contact| (contact.id, contact))
+            .collect();
+
+        Ok(moves
+            .into_iter()
+            .map(|(move_lot, stock_move)| {
+                let purchase = purchases.get(&stock_move.id);
+                let sale = sales.get(&stock_move.id);
+                let contact_id = purchase
+                    .map(|order| order.supplier_id)
+                    .or(sale.map(|order| order.customer_id));
+                LotMove {
+                    move_id: stock_move.id,
+                    quantity: move_lot.quantity,
+                    source_stock_id: stock_move.source_stock_id,
+                    target_stock_id: stock_move.target_stock_id,
+                    purchase_order_id: purchase.map(|order| order.id),
+                    sale_order_id: sale.map(|order| order.id),
+                    contact: contact_id.and_then(|id| contacts.get(&id).cloned()),
+                }
+            })
+            .collect())
+    }
+));
@@ -1,19 +1,67 @@
 mod contacts;
+mod credit_notes;
+mod inventory_counts;
+mod invoice_postings;
 mod ledger_accounts;
 mod ledger_transactions;
+mod lots;
+mod number_sequences;
+mod price_lists;
+mod product_attributes;
+mod product_categories;
 mod products;
+mod purchase_credit_notes;
+mod purchase_invoice_lines;
+mod purchase_invoices;
+mod purchase_order_lines;
+mod purchase_orders;
+mod reorder_rules;
+mod sale_credit_notes;
+mod sale_invoice_lines;
+mod sale_invoices;
+mod sale_order_lines;
+mod sale_orders;
 mod stock_moves;
+mod stock_reservations;
+mod stock_valuation_layers;
 mod stocks;
+mod supplier_prices;
+mod tax_rules;
 mod taxes;
+mod units;
 mod users;
 mod variants;
 
 pub use contacts::*;
+pub use credit_notes::*;
+pub use inventory_counts::*;
+pub use invoice_postings::*;
 pub use ledger_accounts::*;
 pub use ledger_transactions::*;
+pub use lots::*;
+pub use number_sequences::*;
+pub use price_lists::*;
+pub use product_attributes::*;
+pub use product_categories::*;
 pub use products::*;
+pub use purchase_credit_notes::*;
+pub use purchase_invoice_lines::*;
+pub use purchase_invoices::*;
+pub use purchase_order_lines::*;
+pub use purchase_orders::*;
+pub use reorder_rules::*;
+pub use sale_credit_notes::*;
+pub use sale_invoice_lines::*;
+pub use sale_invoices::*;
+pub use sale_order_lines::*;
+pub use sale_orders::*;
 pub use stock_moves::*;
+pub use stock_reservations::*;
+pub use stock_valuation_layers::*;
 pub use stocks::*;
+pub use supplier_prices::*;
+pub use tax_rules::*;
 pub use taxes::*;
+pub use units::*;
 pub use users::*;
 pub use variants::*;
@@ -0,0 +1,108 @@
+use chrono::NaiveDate;
+use entity::{prelude::NumberSequences, sea_orm_active_enums::DocumentType};
+use macros::make_repo;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    DatabaseTransaction, DbErr, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder, QuerySelect,
+    TransactionTrait,
+};
+
+use crate::utils::number_pattern;
+
+/// Returned as `DbErr::Custom` when a document is dated into a period whose
+/// numbers have already been passed, e.g. a 2024 invoice after the first
+/// invoice of 2025.
+pub const ERR_PERIOD_CLOSED: &str = "the period of the number sequence is closed";
+
+make_repo!(NumberSequence, (
+    async fn list(&self) -> Result<Vec<entity::number_sequences::Model>, DbErr> {
+        NumberSequences::find()
+            .order_by_asc(entity::number_sequences::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    async fn update_pattern(
+        &self,
+        document_type: DocumentType,
+        pattern: String,
+    ) -> Result<Option<entity::number_sequences::Model>, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let sequence = NumberSequences::find()
+            .filter(entity::number_sequences::Column::DocumentType.eq(document_type))
+            .lock_exclusive()
+            .one(&txn)
+            .await?;
+        let Some(sequence) = sequence else {
+            return Ok(None);
+        };
+
+        let mut sequence = sequence.into_active_model();
+        sequence.pattern = Set(pattern);
+        let sequence = sequence.update(&txn).await?;
+
+        txn.commit().await?;
+        Ok(Some(sequence))
+    }
+));
+
+/// Allocates the next number of the document type's sequence.
+///
+/// Gapless sequences are counted up inside the given transaction, so the
+/// sequence row stays locked until the document is committed and a failed
+/// create gives its number back. The other sequences are counted up in a
+/// transaction of their own, which keeps the lock short but may leave gaps.
+pub async fn next_number(
+    db: &DatabaseConnection,
+    txn: &DatabaseTransaction,
+    document_type: DocumentType,
+    date: NaiveDate,
+) -> Result<String, DbErr> {
+    let sequence = NumberSequences::find()
+        .filter(entity::number_sequences::Column::DocumentType.eq(document_type.clone()))
+        .one(txn)
+        .await?
+        .ok_or(DbErr::RecordNotFound("number sequence".to_string()))?;
+
+    if sequence.gapless {
+        return allocate(txn, document_type, date).await;
+    }
+
+    let own = db.begin().await?;
+    let number = allocate(&own, document_type, date).await?;
+    own.commit().await?;
+    Ok(number)
+}
+
+async fn allocate<C: ConnectionTrait>(
+    db: &C,
+    document_type: DocumentType,
+    date: NaiveDate,
+) -> Result<String, DbErr> {
+    let sequence = NumberSequences::find()
+        .filter(entity::number_sequences::Column::DocumentType.eq(document_type))
+        .lock_exclusive()
+        .one(db)
+        .await?
+        .ok_or(DbErr::RecordNotFound("number sequence".to_string()))?;
+
+    let period = number_pattern::period(&sequence.pattern, date);
+    let number = match period.cmp(&sequence.period) {
+        std::cmp::Ordering::Equal => sequence.next_number,
+        // Periods of the same pattern have the same length and sort by date.
+        // A changed pattern starts over with the new kind of period.
+        std::cmp::Ordering::Less if period.len() == sequence.period.len() => {
+            return Err(DbErr::Custom(ERR_PERIOD_CLOSED.to_string()));
+        }
+        _ => 1,
+    };
+    let reference = number_pattern::format_number(&sequence.pattern, date, number);
+
+    let mut sequence = sequence.into_active_model();
+    sequence.period = Set(period);
+    sequence.next_number = Set(number + 1);
+    sequence.update(db).await?;
+
+    Ok(reference)
+}
@@ -0,0 +1,189 @@
+use chrono::NaiveDate;
+use entity::prelude::{PriceListCustomers, PriceListItems, PriceLists};
+use macros::make_repo;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DbErr, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+
+use crate::models::Page;
+
+/// Returned as `DbErr::Custom` when the list already has a price for the
+/// variant at the same minimum quantity.
+pub const ERR_PRICE_LIST_ITEM_EXISTS: &str = "the price list already has this quantity break";
+
+make_repo!(PriceList, (
+    async fn create(
+        &self,
+        name: String,
+        currency: String,
+        valid_from: Option<NaiveDate>,
+        valid_to: Option<NaiveDate>,
+    ) -> Result<entity::price_lists::Model, DbErr> {
+        let entity = entity::price_lists::ActiveModel {
+            name: Set(name),
+            currency: Set(currency),
+            valid_from: Set(valid_from),
+            valid_to: Set(valid_to),
+            ..Default::default()
+        };
+
+        entity.insert(&self.db).await
+    }
+
+    async fn list(
+        &self,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Page<entity::price_lists::Model>, DbErr> {
+        let total = PriceLists::find().count(&self.db).await?;
+
+        let data = PriceLists::find()
+            .order_by_asc(entity::price_lists::Column::Id)
+            .limit(limit)
+            .offset(offset)
+            .all(&self.db)
+            .await?;
+
+        Ok(Page {
+            limit,
+            offset,
+            total,
+            data,
+        })
+    }
+
+    async fn get(&self, id: i32) -> Result<Option<entity::price_lists::Model>, DbErr> {
+        PriceLists::find_by_id(id).one(&self.db).await
+    }
+
+    async fn delete(&self, id: i32) -> Result<bool, DbErr> {
+        let result = PriceLists::delete_by_id(id).exec(&self.db).await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn list_items(
+        &self,
+        price_list_id: i32,
+    ) -> Result<Vec<entity::price_list_items::Model>, DbErr> {
+        PriceListItems::find()
+            .filter(entity::price_list_items::Column::PriceListId.eq(price_list_id))
+            .order_by_asc(entity::price_list_items::Column::VariantId)
+            .order_by_asc(entity::price_list_items::Column::MinQuantity)
+            .all(&self.db)
+            .await
+    }
+
+    /// Adds the price of a variant from a minimum quantity on. The list row
+    /// is locked, so two requests can't add the same quantity break.
+    async fn create_item(
+        &self,
+        price_list_id: i32,
+        variant_id: i32,
+        min_quantity: f64,
+        price: i32,
+    ) -> Result<entity::price_list_items::Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        PriceLists::find_by_id(price_list_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(DbErr::RecordNotFound("price list".to_string()))?;
+
+        let existing = PriceListItems::find()
+            .filter(entity::price_list_items::Column::PriceListId.eq(price_list_id))
+            .filter(entity::price_list_items::Column::VariantId.eq(variant_id))
+            .filter(entity::price_list_items::Column::MinQuantity.eq(min_quantity))
+            .one(&txn)
+            .await?;
+        if existing.is_some() {
+            return Err(DbErr::Custom(ERR_PRICE_LIST_ITEM_EXISTS.to_string()));
+        }
+
+        let item = entity::price_list_items::ActiveModel {
+            price_list_id: Set(price_list_id),
+            variant_id: Set(variant_id),
+            min_quantity: Set(min_quantity),
+            price: Set(price),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        txn.commit().await?;
+        Ok(item)
+    }
+
+    async fn list_customers(&self, price_list_id: i32) -> Result<Vec<i32>, DbErr> {
+        let customers = PriceListCustomers::find()
+            .filter(entity::price_list_customers::Column::PriceListId.eq(price_list_id))
+            .order_by_asc(entity::price_list_customers::Column::ContactId)
+            .all(&self.db)
+            .await?;
+
+        Ok(customers.into_iter().map(|customer| customer.contact_id).collect())
+    }
+
+    /// Replaces the customers the list is assigned to.
+    async fn set_customers(
+        &self,
+        price_list_id: i32,
+        contact_ids: Vec<i32>,
+    ) -> Result<Vec<i32>, DbErr> {
+        let txn = self.db.begin().await?;
+
+        PriceLists::find_by_id(price_list_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(DbErr::RecordNotFound("price list".to_string()))?;
+
+        PriceListCustomers::delete_many()
+            .filter(entity::price_list_customers::Column::PriceListId.eq(price_list_id))
+            .exec(&txn)
+            .await?;
+
+        let mut created = Vec::with_capacity(contact_ids.len());
+        for contact_id in contact_ids {
+            let customer = entity::price_list_customers::ActiveModel {
+                price_list_id: Set(price_list_id),
+                contact_id: Set(contact_id),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?;
+            created.push(customer.contact_id);
+        }
+
+        txn.commit().await?;
+        Ok(created)
+    }
+
+    /// Lists the prices of the variant on all lists assigned to the
+    /// customer, each with its list, regardless of currency and validity.
+    async fn list_prices_for_customer(
+        &self,
+        contact_id: i32,
+        variant_id: i32,
+    ) -> Result<Vec<(entity::price_lists::Model, entity::price_list_items::Model)>, DbErr> {
+        let assigned = PriceListCustomers::find()
+            .filter(entity::price_list_customers::Column::ContactId.eq(contact_id))
+            .all(&self.db)
+            .await?;
+        let list_ids: Vec<i32> = assigned.iter().map(|customer| customer.price_list_id).collect();
+
+        let items = PriceListItems::find()
+            .find_also_related(PriceLists)
+            .filter(entity::price_list_items::Column::PriceListId.is_in(list_ids))
+            .filter(entity::price_list_items::Column::VariantId.eq(variant_id))
+            .order_by_asc(entity::price_list_items::Column::Id)
+            .all(&self.db)
+            .await?;
+
+        Ok(items
+            .into_iter()
+            .filter_map(|(item, list)| list.map(|list| (list, item)))
+            .collect())
+    }
+));
@@ -0,0 +1,108 @@
+use entity::prelude::{ProductAttributeValues, ProductAttributes, Products};
+use macros::make_repo;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DbErr, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+
+use crate::utils::variants::Attribute;
+
+/// Returned as `DbErr::Custom` when the product already has an attribute
+/// with the name.
+pub const ERR_PRODUCT_ATTRIBUTE_EXISTS: &str =
+    "the product already has an attribute with this name";
+
+make_repo!(ProductAttribute, (
+    /// Adds an attribute with its values after the product's other
+    /// attributes. The product row is locked, so two requests can't add
+    /// the same attribute.
+    async fn create(
+        &self,
+        product_id: i32,
+        name: String,
+        values: Vec<String>,
+    ) -> Result<Attribute, DbErr> {
+        let txn = self.db.begin().await?;
+
+        Products::find_by_id(product_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(DbErr::RecordNotFound("product".to_string()))?;
+
+        let existing = ProductAttributes::find()
+            .filter(entity::product_attributes::Column::ProductId.eq(product_id))
+            .filter(entity::product_attributes::Column::Name.eq(name.clone()))
+            .one(&txn)
+            .await?;
+        if existing.is_some() {
+            return Err(DbErr::Custom(ERR_PRODUCT_ATTRIBUTE_EXISTS.to_string()));
+        }
+
+        let count = ProductAttributes::find()
+            .filter(entity::product_attributes::Column::ProductId.eq(product_id))
+            .count(&txn)
+            .await?;
+
+        let attribute = entity::product_attributes::ActiveModel {
+            product_id: Set(product_id),
+            name: Set(name),
+            position: Set(count as i32 + 1),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        let mut created = Vec::with_capacity(values.len());
+        for (position, value) in values.into_iter().enumerate() {
+            let value = entity::product_attribute_values::ActiveModel {
+                attribute_id: Set(attribute.id),
+                name: Set(value),
+                position: Set(position as i32 + 1),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?;
+            created.push(value);
+        }
+
+        txn.commit().await?;
+        Ok((attribute, created))
+    }
+
+    /// Lists the attributes of the product with their values, both in
+    /// position order.
+    async fn list_for_product(&self, product_id: i32) -> Result<Vec<Attribute>, DbErr> {
+        let attributes = ProductAttributes::find()
+            .filter(entity::product_attributes::Column::ProductId.eq(product_id))
+            .order_by_asc(entity::product_attributes::Column::Position)
+            .order_by_asc(entity::product_attributes::Column::Id)
+            .all(&self.db)
+            .await?;
+        let attribute_ids: Vec<i32> = attributes.iter().map(|attribute| attribute.id).collect();
+
+        let values = ProductAttributeValues::find()
+            .filter(entity::product_attribute_values::Column::AttributeId.is_in(attribute_ids))
+            .order_by_asc(entity::product_attribute_values::Column::Position)
+            .order_by_asc(entity::product_attribute_values::Column::Id)
+            .all(&self.db)
+            .await?;
+
+        Ok(attributes
+            .into_iter()
+            .map(|attribute| {
+                let values = values
+                    .iter()
+                    .filter(|value| value.attribute_id == attribute.id)
+                    .cloned()
+                    .collect();
+                (attribute, values)
+            })
+            .collect())
+    }
+
+    async fn delete(&self, id: i32) -> Result<bool, DbErr> {
+        let result = ProductAttributes::delete_by_id(id).exec(&self.db).await?;
+        Ok(result.rows_affected > 0)
+    }
+));
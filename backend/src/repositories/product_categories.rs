@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use entity::prelude::{ProductCategories, Products, Variants};
+use macros::make_repo;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DbErr, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder,
+};
+
+use crate::utils::product_categories::{CategoryDefaults, category_defaults};
+
+/// Returned as `DbErr::Custom` when a category still has subcategories or
+/// products.
+pub const ERR_PRODUCT_CATEGORY_IN_USE: &str = "the product category is still in use";
+
+/// A product category to create. Unset accounts and tax are taken from the
+/// parent.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProductCategoryDraft {
+    pub name: String,
+    pub parent_id: Option<i32>,
+    pub income_account_id: Option<i32>,
+    pub expense_account_id: Option<i32>,
+    pub inventory_account_id: Option<i32>,
+    pub tax_id: Option<i32>,
+    pub sale_discount_account_id: Option<i32>,
+    pub purchase_discount_account_id: Option<i32>,
+}
+
+make_repo!(ProductCategory, (
+    async fn create(
+        &self,
+        category: ProductCategoryDraft,
+    ) -> Result<entity::product_categories::Model, DbErr> {
+        let entity = entity::product_categories::ActiveModel {
+            name: Set(category.name),
+            parent_id: Set(category.parent_id),
+            income_account_id: Set(category.income_account_id),
+            expense_account_id: Set(category.expense_account_id),
+            inventory_account_id: Set(category.inventory_account_id),
+            tax_id: Set(category.tax_id),
+            sale_discount_account_id: Set(category.sale_discount_account_id),
+            purchase_discount_account_id: Set(category.purchase_discount_account_id),
+            ..Default::default()
+        };
+
+        entity.insert(&self.db).await
+    }
+
+    async fn list(&self) -> Result<Vec<entity::product_categories::Model>, DbErr> {
+        ProductCategories::find()
+            .order_by_asc(entity::product_categories::Column::Name)
+            .all(&self.db)
+            .await
+    }
+
+    async fn get(&self, id: i32) -> Result<Option<entity::product_categories::Model>, DbErr> {
+        ProductCategories::find_by_id(id).one(&self.db).await
+    }
+
+    async fn delete(&self, id: i32) -> Result<bool, DbErr> {
+        let children = ProductCategories::find()
+            .filter(entity::product_categories::Column::ParentId.eq(id))
+            .count(&self.db)
+            .await?;
+        let products = Products::find()
+            .filter(entity::products::Column::CategoryId.eq(id))
+            .count(&self.db)
+            .await?;
+        if children > 0 || products > 0 {
+            return Err(DbErr::Custom(ERR_PRODUCT_CATEGORY_IN_USE.to_string()));
+        }
+
+        let result = ProductCategories::delete_by_id(id).exec(&self.db).await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    /// Resolves the category defaults of the variants' products by variant
+    /// id. Variants whose product has no category are left out.
+    async fn defaults_for_variants(
+        &self,
+        variant_ids: Vec<i32>,
+    ) -> Result<HashMap<i32, CategoryDefaults>, DbErr> {
+        let variants = Variants::find()
+            .filter(entity::variants::Column::Id.is_in(variant_ids))
+            .find_also_related(Products)
+            .all(&self.db)
+            .await?;
+        let categories = ProductCategories::find().all(&self.db).await?;
+
+        Ok(variants
+            .into_iter()
+            .filter_map(|(variant, product)| {
+                let category_id = product?.category_id?;
+                Some((variant.id, category_defaults(&categories, category_id)))
+            })
+            .collect())
+    }
+));
@@ -1,11 +1,33 @@
+use entity::{
+    prelude::Products,
+    sea_orm_active_enums::{CostingMethod, ProductType, Tracking},
+};
 use macros::make_repo;
-use sea_orm::DbErr;
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, DbErr, EntityTrait};
 
 use crate::models::Page;
 
 make_repo!(Product, (
-    async fn create(&self, _name: String) -> Result<entity::products::Model, DbErr> {
-        todo!();
+    async fn create(
+        &self,
+        name: String,
+        tax_category: String,
+        category_id: Option<i32>,
+        product_type: ProductType,
+        costing_method: CostingMethod,
+        tracking: Tracking,
+    ) -> Result<entity::products::Model, DbErr> {
+        let entity = entity::products::ActiveModel {
+            name: Set(name),
+            tax_category: Set(tax_category),
+            category_id: Set(category_id),
+            product_type: Set(product_type),
+            costing_method: Set(costing_method),
+            tracking: Set(tracking),
+            ..Default::default()
+        };
+
+        entity.insert(&self.db).await
     }
 
     async fn list(
@@ -16,8 +38,8 @@ make_repo!(Product, (
         todo!();
     }
 
-    async fn get(&self, _id: i32) -> Result<Option<entity::products::Model>, DbErr> {
-        todo!();
+    async fn get(&self, id: i32) -> Result<Option<entity::products::Model>, DbErr> {
+        Products::find_by_id(id).one(&self.db).await
     }
 
     async fn delete(&self, _id: i32) -> Result<bool, DbErr> {
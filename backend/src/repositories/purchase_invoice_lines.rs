@@ -0,0 +1,34 @@
+use entity::prelude::{PurchaseInvoiceLineTaxes, PurchaseInvoiceLines};
+use macros::make_repo;
+use sea_orm::{
+    ColumnTrait, DbErr, EntityTrait, JoinType, QueryFilter, QueryOrder, QuerySelect, RelationTrait,
+};
+
+make_repo!(PurchaseInvoiceLine, (
+    async fn list_for_invoice(
+        &self,
+        invoice_id: i32,
+    ) -> Result<Vec<entity::purchase_invoice_lines::Model>, DbErr> {
+        PurchaseInvoiceLines::find()
+            .filter(entity::purchase_invoice_lines::Column::InvoiceId.eq(invoice_id))
+            .order_by_asc(entity::purchase_invoice_lines::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    /// Lists the postings of the tax group components of the invoice's lines.
+    async fn list_taxes_for_invoice(
+        &self,
+        invoice_id: i32,
+    ) -> Result<Vec<entity::purchase_invoice_line_taxes::Model>, DbErr> {
+        PurchaseInvoiceLineTaxes::find()
+            .join(
+                JoinType::InnerJoin,
+                entity::purchase_invoice_line_taxes::Relation::PurchaseInvoiceLines.def(),
+            )
+            .filter(entity::purchase_invoice_lines::Column::InvoiceId.eq(invoice_id))
+            .order_by_asc(entity::purchase_invoice_line_taxes::Column::Id)
+            .all(&self.db)
+            .await
+    }
+));
@@ -0,0 +1,203 @@
+use chrono::NaiveDate;
+use entity::{
+    prelude::{PurchaseInvoiceLines, PurchaseInvoices},
+    sea_orm_active_enums::{DocumentType, PurchaseInvoiceStatus},
+};
+use macros::make_repo;
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::{NotSet, Set},
+    ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+
+use super::{LinePostingDraft, insert_posting, next_number};
+use crate::utils::totals::Discount;
+
+/// All lines of the invoice have been posted already, e.g. by a second
+/// posting at the same time.
+pub const ERR_PURCHASE_INVOICE_POSTED: &str = "the purchase invoice has been posted already";
+
+make_repo!(PurchaseInvoice, (
+    /// Creates the invoice with the next number of the purchase invoice
+    /// sequence, which stays locked until the invoice is committed.
+    async fn create(
+        &self,
+        supplier_reference: String,
+        date: NaiveDate,
+        status: PurchaseInvoiceStatus,
+        order_id: i32,
+        supplier_id: i32,
+        discount: Discount,
+    ) -> Result<entity::purchase_invoices::Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let reference = next_number(&self.db, &txn, DocumentType::PurchaseInvoice, date).await?;
+        let invoice = entity::purchase_invoices::ActiveModel {
+            id: NotSet,
+            reference: Set(reference),
+            date: Set(date),
+            status: Set(status),
+            order_id: Set(Some(order_id)),
+            supplier_id: Set(Some(supplier_id)),
+            needs_review: Set(false),
+            review_note: Set(String::new()),
+            supplier_reference: Set(supplier_reference),
+            discount_percent: Set(discount.percent),
+            discount_amount: Set(discount.amount as i32),
+        }
+        .insert(&txn)
+        .await?;
+
+        txn.commit().await?;
+        Ok(invoice)
+    }
+
+    async fn get(&self, id: i32) -> Result<Option<entity::purchase_invoices::Model>, DbErr> {
+        PurchaseInvoices::find_by_id(id).one(&self.db).await
+    }
+
+    /// Books the postings of the lines in one transaction, dated on the
+    /// invoice date, and finishes the invoice.
+    /// The invoice is locked while the lines which are still unposted are
+    /// selected, lines posted at the same time are skipped.
+    async fn post(
+        &self,
+        invoice: entity::purchase_invoices::Model,
+        lines: Vec<LinePostingDraft>,
+    ) -> Result<entity::purchase_invoices::Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let invoice = PurchaseInvoices::find_by_id(invoice.id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(DbErr::RecordNotFound("purchase invoice".to_string()))?;
+        let unposted: Vec<i32> = PurchaseInvoiceLines::find()
+            .select_only()
+            .column(entity::purchase_invoice_lines::Column::Id)
+            .filter(entity::purchase_invoice_lines::Column::InvoiceId.eq(invoice.id))
+            .filter(entity::purchase_invoice_lines::Column::TransactionId.is_null())
+            .into_tuple()
+            .all(&txn)
+            .await?;
+        let lines: Vec<LinePostingDraft> = lines
+            .into_iter()
+            .filter(|line| unposted.contains(&line.line_id))
+            .collect();
+        let posted = invoice.status == PurchaseInvoiceStatus::Finished;
+        if posted && lines.is_empty() {
+            return Err(DbErr::Custom(ERR_PURCHASE_INVOICE_POSTED.to_string()));
+        }
+
+        for line in lines {
+            let transaction_id = insert_posting(&txn, invoice.date, &line.net).await?;
+            let tax_transaction_id = match &line.tax {
+                Some(tax) => Some(insert_posting(&txn, invoice.date, tax).await?),
+                None => None,
+            };
+            let discount_transaction_id = match &line.discount {
+                Some(discount) => Some(insert_posting(&txn, invoice.date, discount).await?),
+                None => None,
+            };
+            entity::purchase_invoice_lines::ActiveModel {
+                id: Set(line.line_id),
+                tax_id: Set(line.tax_id),
+                transaction_id: Set(Some(transaction_id)),
+                tax_transaction_id: Set(tax_transaction_id),
+                discount_transaction_id: Set(discount_transaction_id),
+                ..Default::default()
+            }
+            .update(&txn)
+            .await?;
+
+            for (tax_id, posting) in &line.tax_postings {
+                let transaction_id = insert_posting(&txn, invoice.date, posting).await?;
+                entity::purchase_invoice_line_taxes::ActiveModel {
+                    id: NotSet,
+                    line_id: Set(line.line_id),
+                    tax_id: Set(*tax_id),
+                    amount: Set(posting.amount),
+                    transaction_id: Set(Some(transaction_id)),
+                }
+                .insert(&txn)
+                .await?;
+            }
+        }
+
+        let status = PurchaseInvoiceStatus::Finished;
+        let mut invoice: entity::purchase_invoices::ActiveModel = invoice.into();
+        invoice.status = Set(status);
+        let invoice = invoice.update(&txn).await?;
+
+        txn.commit().await?;
+        Ok(invoice)
+    }
+
+    /// Lists the finished invoices dated within the range together with
+    /// their lines. Drafts, like unreviewed imports, aren't booked yet.
+    async fn list_posted_with_lines(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<
+        Vec<(
+            entity::purchase_invoices::Model,
+            Vec<entity::purchase_invoice_lines::Model>,
+        )>,
+        DbErr,
+    > {
+        PurchaseInvoices::find()
+            .filter(entity::purchase_invoices::Column::Date.between(from, to))
+            .filter(entity::purchase_invoices::Column::Status.eq(PurchaseInvoiceStatus::Finished))
+            .order_by_asc(entity::purchase_invoices::Column::Id)
+            .find_with_related(PurchaseInvoiceLines)
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_by_supplier_reference(
+        &self,
+        supplier_id: i32,
+        supplier_reference: String,
+    ) -> Result<Option<entity::purchase_invoices::Model>, DbErr> {
+        PurchaseInvoices::find()
+            .filter(entity::purchase_invoices::Column::SupplierId.eq(supplier_id))
+            .filter(entity::purchase_invoices::Column::SupplierReference.eq(supplier_reference))
+            .one(&self.db)
+            .await
+    }
+
+    /// Inserts the invoice and its lines in one transaction. The number of
+    /// the invoice and the invoice id of the lines are set here.
+    async fn create_with_lines(
+        &self,
+        mut invoice: entity::purchase_invoices::ActiveModel,
+        lines: Vec<entity::purchase_invoice_lines::ActiveModel>,
+    ) -> Result<
+        (
+            entity::purchase_invoices::Model,
+            Vec<entity::purchase_invoice_lines::Model>,
+        ),
+        DbErr,
+    > {
+        let date = invoice
+            .date
+            .clone()
+            .take()
+            .ok_or(DbErr::AttrNotSet("date".to_string()))?;
+
+        let txn = self.db.begin().await?;
+
+        let reference = next_number(&self.db, &txn, DocumentType::PurchaseInvoice, date).await?;
+        invoice.reference = Set(reference);
+        let invoice = invoice.insert(&txn).await?;
+        let mut created = Vec::with_capacity(lines.len());
+        for mut line in lines {
+            line.invoice_id = Set(invoice.id);
+            created.push(line.insert(&txn).await?);
+        }
+
+        txn.commit().await?;
+        Ok((invoice, created))
+    }
+));
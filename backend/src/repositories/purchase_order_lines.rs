@@ -0,0 +1,78 @@
+use entity::prelude::{PurchaseOrderLines, PurchaseOrders};
+use macros::make_repo;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+};
+
+use crate::utils::totals::Discount;
+
+/// A line as it is added to a purchase order, with its price and
+/// description already filled in.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PurchaseOrderLineDraft {
+    pub description: String,
+    pub quantity: f64,
+    pub price: i32,
+    pub tax: i32,
+    /// The tax whose rate on the order date applies instead of `tax`.
+    pub tax_id: Option<i32>,
+    pub variant_id: Option<i32>,
+    pub discount: Discount,
+    /// The unit of the quantity and price.
+    pub unit_id: Option<i32>,
+}
+
+make_repo!(PurchaseOrderLine, (
+    async fn create(
+        &self,
+        order_id: i32,
+        line: PurchaseOrderLineDraft,
+    ) -> Result<entity::purchase_order_lines::Model, DbErr> {
+        let entity = entity::purchase_order_lines::ActiveModel {
+            description: Set(line.description),
+            quantity: Set(line.quantity),
+            price: Set(line.price),
+            tax: Set(line.tax),
+            tax_id: Set(line.tax_id),
+            order_id: Set(order_id),
+            variant_id: Set(line.variant_id),
+            discount_percent: Set(line.discount.percent),
+            discount_amount: Set(line.discount.amount as i32),
+            unit_id: Set(line.unit_id),
+            ..Default::default()
+        };
+
+        entity.insert(&self.db).await
+    }
+
+    async fn list_for_order(
+        &self,
+        order_id: i32,
+    ) -> Result<Vec<entity::purchase_order_lines::Model>, DbErr> {
+        PurchaseOrderLines::find()
+            .filter(entity::purchase_order_lines::Column::OrderId.eq(order_id))
+            .order_by_asc(entity::purchase_order_lines::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    /// Lists the lines that drop-ship the sale order lines, with their
+    /// orders.
+    async fn list_for_sale_lines(
+        &self,
+        sale_line_ids: Vec<i32>,
+    ) -> Result<Vec<(entity::purchase_order_lines::Model, entity::purchase_orders::Model)>, DbErr>
+    {
+        let lines = PurchaseOrderLines::find()
+            .find_also_related(PurchaseOrders)
+            .filter(entity::purchase_order_lines::Column::SaleLineId.is_in(sale_line_ids))
+            .order_by_asc(entity::purchase_order_lines::Column::Id)
+            .all(&self.db)
+            .await?;
+
+        Ok(lines
+            .into_iter()
+            .filter_map(|(line, order)| Some((line, order?)))
+            .collect())
+    }
+));
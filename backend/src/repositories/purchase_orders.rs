@@ -0,0 +1,339 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use entity::{
+    prelude::{PurchaseOrderLines, PurchaseOrders, SaleOrderLines},
+    sea_orm_active_enums::{DocumentType, PurchaseOrderStatus},
+};
+use macros::make_repo;
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::{NotSet, Set},
+    ColumnTrait, DatabaseConnection, DatabaseTransaction, DbErr, EntityTrait, PaginatorTrait,
+    QueryFilter, QuerySelect, TransactionTrait,
+};
+
+use super::{
+    LineMoveDraft, PurchaseOrderLineDraft, check_available, finish_if_delivered, move_line,
+    next_number,
+};
+use crate::utils::totals::Discount;
+
+/// The order or some of the lines to receive have been received already,
+/// e.g. by a second receipt at the same time.
+pub const ERR_PURCHASE_ORDER_RECEIVED: &str = "the purchase order has been received already";
+
+/// A draft order of the goods a supplier is to deliver into a stock, as the
+/// replenishment proposes it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PurchaseProposalDraft {
+    pub supplier_id: i32,
+    pub source_stock_id: i32,
+    pub target_stock_id: i32,
+    pub lines: Vec<PurchaseOrderLineDraft>,
+}
+
+/// A line of goods the company reports consuming from a supplier's
+/// consignment stock, with the move taking them out of it. The line id of
+/// the move is set once the line is inserted.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConsumedPurchaseLineDraft {
+    pub line: PurchaseOrderLineDraft,
+    pub stock_move: LineMoveDraft,
+}
+
+/// Inserts an order numbered from the purchase order sequence.
+pub(super) async fn insert_order(
+    db: &DatabaseConnection,
+    txn: &DatabaseTransaction,
+    date: NaiveDate,
+    status: PurchaseOrderStatus,
+    supplier_id: i32,
+    source_stock_id: i32,
+    target_stock_id: i32,
+) -> Result<entity::purchase_orders::Model, DbErr> {
+    let reference = next_number(db, txn, DocumentType::PurchaseOrder, date).await?;
+    entity::purchase_orders::ActiveModel {
+        id: NotSet,
+        reference: Set(reference),
+        date: Set(date),
+        status: Set(status),
+        supplier_id: Set(supplier_id),
+        source_stock_id: Set(source_stock_id),
+        target_stock_id: Set(target_stock_id),
+        discount_percent: Set(0.0),
+        discount_amount: Set(0),
+    }
+    .insert(txn)
+    .await
+}
+
+/// Inserts the lines of an order, each with the sale order line it
+/// drop-ships, if any.
+pub(super) async fn insert_lines(
+    txn: &DatabaseTransaction,
+    order_id: i32,
+    lines: Vec<(Option<i32>, PurchaseOrderLineDraft)>,
+) -> Result<(), DbErr> {
+    if lines.is_empty() {
+        return Ok(());
+    }
+
+    let models =
+        lines.into_iter().map(
+            |(sale_line_id, line)| entity::purchase_order_lines::ActiveModel {
+                id: NotSet,
+                description: Set(line.description),
+                quantity: Set(line.quantity),
+                price: Set(line.price),
+                tax: Set(line.tax),
+                tax_id: Set(line.tax_id),
+                order_id: Set(order_id),
+                variant_id: Set(line.variant_id),
+                move_id: Set(None),
+                discount_percent: Set(line.discount.percent),
+                discount_amount: Set(line.discount.amount as i32),
+                unit_id: Set(line.unit_id),
+                sale_line_id: Set(sale_line_id),
+            },
+        );
+    PurchaseOrderLines::insert_many(models).exec(txn).await?;
+    Ok(())
+}
+
+make_repo!(PurchaseOrder, (
+    /// Creates the order with the next number of the purchase order sequence.
+    async fn create(
+        &self,
+        date: NaiveDate,
+        status: PurchaseOrderStatus,
+        supplier_id: i32,
+        source_stock_id: i32,
+        target_stock_id: i32,
+        discount: Discount,
+    ) -> Result<entity::purchase_orders::Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let reference = next_number(&self.db, &txn, DocumentType::PurchaseOrder, date).await?;
+        let order = entity::purchase_orders::ActiveModel {
+            id: NotSet,
+            reference: Set(reference),
+            date: Set(date),
+            status: Set(status),
+            supplier_id: Set(supplier_id),
+            source_stock_id: Set(source_stock_id),
+            target_stock_id: Set(target_stock_id),
+            discount_percent: Set(discount.percent),
+            discount_amount: Set(discount.amount as i32),
+        }
+        .insert(&txn)
+        .await?;
+
+        txn.commit().await?;
+        Ok(order)
+    }
+
+    /// Creates a draft order with its lines for every proposal, numbered
+    /// from the purchase order sequence.
+    async fn propose(
+        &self,
+        date: NaiveDate,
+        proposals: Vec<PurchaseProposalDraft>,
+    ) -> Result<Vec<entity::purchase_orders::Model>, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let mut orders = Vec::with_capacity(proposals.len());
+        for proposal in proposals {
+            let order = insert_order(
+                &self.db,
+                &txn,
+                date,
+                PurchaseOrderStatus::Draft,
+                proposal.supplier_id,
+                proposal.source_stock_id,
+                proposal.target_stock_id,
+            )
+            .await?;
+            let lines = proposal.lines.into_iter().map(|line| (None, line)).collect();
+            insert_lines(&txn, order.id, lines).await?;
+            orders.push(order);
+        }
+
+        txn.commit().await?;
+        Ok(orders)
+    }
+
+    /// Turns a draft into an order placed with the supplier.
+    async fn confirm(
+        &self,
+        order: entity::purchase_orders::Model,
+    ) -> Result<entity::purchase_orders::Model, DbErr> {
+        let mut order: entity::purchase_orders::ActiveModel = order.into();
+        order.status = Set(PurchaseOrderStatus::Order);
+        order.update(&self.db).await
+    }
+
+    /// Receives the goods of the lines from the order's source stock into
+    /// its target stock and finishes the order. Sale orders whose lines it
+    /// drop-ships are finished once all their goods are delivered. The order
+    /// is locked while its lines are checked to be still open, so a second
+    /// receipt at the same time can't move the goods again.
+    async fn receive(
+        &self,
+        order: entity::purchase_orders::Model,
+        date: NaiveDate,
+        lines: Vec<LineMoveDraft>,
+    ) -> Result<entity::purchase_orders::Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let order = PurchaseOrders::find_by_id(order.id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(DbErr::RecordNotFound("purchase order".to_string()))?;
+        if order.status == PurchaseOrderStatus::Finished {
+            return Err(DbErr::Custom(ERR_PURCHASE_ORDER_RECEIVED.to_string()));
+        }
+        let line_ids: Vec<i32> = lines.iter().map(|line| line.line_id).collect();
+        let open = PurchaseOrderLines::find()
+            .filter(entity::purchase_order_lines::Column::OrderId.eq(order.id))
+            .filter(entity::purchase_order_lines::Column::Id.is_in(line_ids))
+            .filter(entity::purchase_order_lines::Column::MoveId.is_null())
+            .count(&txn)
+            .await?;
+        if open != lines.len() as u64 {
+            return Err(DbErr::Custom(ERR_PURCHASE_ORDER_RECEIVED.to_string()));
+        }
+
+        let drop_ships: HashMap<i32, i32> = PurchaseOrderLines::find()
+            .filter(entity::purchase_order_lines::Column::OrderId.eq(order.id))
+            .all(&txn)
+            .await?
+            .into_iter()
+            .filter_map(|line| Some((line.id, line.sale_line_id?)))
+            .collect();
+
+        let mut sale_line_ids = Vec::new();
+        for line in &lines {
+            let move_id =
+                move_line(&txn, date, order.source_stock_id, order.target_stock_id, line).await?;
+            PurchaseOrderLines::update_many()
+                .col_expr(entity::purchase_order_lines::Column::MoveId, move_id.into())
+                .filter(entity::purchase_order_lines::Column::Id.eq(line.line_id))
+                .filter(entity::purchase_order_lines::Column::MoveId.is_null())
+                .exec(&txn)
+                .await?;
+
+            // The goods of a drop-shipped sale order line are delivered by
+            // the same move.
+            if let Some(&sale_line_id) = drop_ships.get(&line.line_id) {
+                SaleOrderLines::update_many()
+                    .col_expr(entity::sale_order_lines::Column::MoveId, move_id.into())
+                    .filter(entity::sale_order_lines::Column::Id.eq(sale_line_id))
+                    .filter(entity::sale_order_lines::Column::MoveId.is_null())
+                    .exec(&txn)
+                    .await?;
+                sale_line_ids.push(sale_line_id);
+            }
+        }
+
+        let sale_order_ids: Vec<i32> = SaleOrderLines::find()
+            .select_only()
+            .column(entity::sale_order_lines::Column::OrderId)
+            .distinct()
+            .filter(entity::sale_order_lines::Column::Id.is_in(sale_line_ids))
+            .into_tuple()
+            .all(&txn)
+            .await?;
+        for sale_order_id in sale_order_ids {
+            finish_if_delivered(&txn, sale_order_id).await?;
+        }
+
+        let mut order: entity::purchase_orders::ActiveModel = order.into();
+        order.status = Set(PurchaseOrderStatus::Finished);
+        let order = order.update(&txn).await?;
+
+        txn.commit().await?;
+        Ok(order)
+    }
+
+    /// Records the goods consumed from a supplier's consignment stock as a
+    /// finished order, each line received by a move from that stock to the
+    /// target stock. The goods become the company's as they enter the own
+    /// stock, and the order is ready to be invoiced by the supplier. Fails if
+    /// the consignment stock doesn't hold the goods.
+    async fn consume(
+        &self,
+        date: NaiveDate,
+        supplier_id: i32,
+        source_stock_id: i32,
+        target_stock_id: i32,
+        lines: Vec<ConsumedPurchaseLineDraft>,
+    ) -> Result<entity::purchase_orders::Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let quantities: Vec<(i32, f64)> = lines
+            .iter()
+            .map(|draft| (draft.stock_move.variant_id, draft.stock_move.quantity))
+            .collect();
+        check_available(&txn, source_stock_id, &quantities).await?;
+
+        let order = insert_order(
+            &self.db,
+            &txn,
+            date,
+            PurchaseOrderStatus::Finished,
+            supplier_id,
+            source_stock_id,
+            target_stock_id,
+        )
+        .await?;
+
+        for draft in lines {
+            let line = entity::purchase_order_lines::ActiveModel {
+                id: NotSet,
+                description: Set(draft.line.description),
+                quantity: Set(draft.line.quantity),
+                price: Set(draft.line.price),
+                tax: Set(draft.line.tax),
+                tax_id: Set(draft.line.tax_id),
+                order_id: Set(order.id),
+                variant_id: Set(draft.line.variant_id),
+                move_id: Set(None),
+                discount_percent: Set(draft.line.discount.percent),
+                discount_amount: Set(draft.line.discount.amount as i32),
+                unit_id: Set(draft.line.unit_id),
+                sale_line_id: Set(None),
+            }
+            .insert(&txn)
+            .await?;
+
+            let stock_move = LineMoveDraft {
+                line_id: line.id,
+                ..draft.stock_move
+            };
+            let move_id =
+                move_line(&txn, date, source_stock_id, target_stock_id, &stock_move).await?;
+            let mut line: entity::purchase_order_lines::ActiveModel = line.into();
+            line.move_id = Set(Some(move_id));
+            line.update(&txn).await?;
+        }
+
+        txn.commit().await?;
+        Ok(order)
+    }
+
+    async fn get(&self, id: i32) -> Result<Option<entity::purchase_orders::Model>, DbErr> {
+        PurchaseOrders::find_by_id(id).one(&self.db).await
+    }
+
+    async fn find_by_reference(
+        &self,
+        reference: String,
+    ) -> Result<Vec<entity::purchase_orders::Model>, DbErr> {
+        PurchaseOrders::find()
+            .filter(entity::purchase_orders::Column::Reference.eq(reference))
+            .all(&self.db)
+            .await
+    }
+));
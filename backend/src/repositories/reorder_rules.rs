@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use entity::{
+    prelude::{PurchaseOrderLines, PurchaseOrders, ReorderRules, StockReservations, Units, Variants},
+    sea_orm_active_enums::PurchaseOrderStatus,
+};
+use macros::make_repo;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect,
+};
+
+use super::{balances, subtree_ids};
+use crate::utils::units::conversion_factor;
+
+/// Returned as `DbErr::Custom` when the variant already has a rule for the
+/// stock.
+pub const ERR_REORDER_RULE_EXISTS: &str = "the variant already has a reorder rule for the stock";
+
+/// What there is of a reorder rule's variant in the rule's stock and the
+/// locations below it, what confirmed sale orders have reserved of it and
+/// what open purchase orders bring in, all in the variant's unit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RuleForecast {
+    pub rule: entity::reorder_rules::Model,
+    pub on_hand: f64,
+    pub reserved: f64,
+    pub incoming: f64,
+}
+
+impl RuleForecast {
+    /// What the stock will hold once the open orders are through.
+    pub fn forecast(&self) -> f64 {
+        self.on_hand - self.reserved + self.incoming
+    }
+}
+
+make_repo!(ReorderRule, (
+    async fn create(
+        &self,
+        variant_id: i32,
+        stock_id: i32,
+        min_quantity: f64,
+        max_quantity: f64,
+    ) -> Result<entity::reorder_rules::Model, DbErr> {
+        let existing = ReorderRules::find()
+            .filter(entity::reorder_rules::Column::VariantId.eq(variant_id))
+            .filter(entity::reorder_rules::Column::StockId.eq(stock_id))
+            .one(&self.db)
+            .await?;
+        if existing.is_some() {
+            return Err(DbErr::Custom(ERR_REORDER_RULE_EXISTS.to_string()));
+        }
+
+        entity::reorder_rules::ActiveModel {
+            variant_id: Set(variant_id),
+            stock_id: Set(stock_id),
+            min_quantity: Set(min_quantity),
+            max_quantity: Set(max_quantity),
+            ..Default::default()
+        }
+        .insert(&self.db)
+        .await
+    }
+
+    async fn list(&self) -> Result<Vec<entity::reorder_rules::Model>, DbErr> {
+        ReorderRules::find()
+            .order_by_asc(entity::reorder_rules::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    async fn delete(&self, id: i32) -> Result<bool, DbErr> {
+        let result = ReorderRules::delete_by_id(id).exec(&self.db).await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    /// Looks at what every rule's stock holds of its variant and what the
+    /// open orders will change about it. Draft purchase orders count as
+    /// incoming, so proposals that haven't been confirmed yet aren't
+    /// proposed again.
+    async fn forecast(&self) -> Result<Vec<RuleForecast>, DbErr> {
+        let rules = ReorderRules::find()
+            .order_by_asc(entity::reorder_rules::Column::Id)
+            .all(&self.db)
+            .await?;
+        let variant_ids: Vec<i32> = rules.iter().map(|rule| rule.variant_id).collect();
+
+        let variant_units: HashMap<i32, Option<i32>> = Variants::find()
+            .filter(entity::variants::Column::Id.is_in(variant_ids.clone()))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|variant| (variant.id, variant.unit_id))
+            .collect();
+        let units: HashMap<i32, entity::units::Model> = Units::find()
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|unit| (unit.id, unit))
+            .collect();
+        // Lines are ordered in any unit converting into the variant's.
+        let factor = |variant_id: i32, unit_id: Option<i32>| {
+            let variant_unit = variant_units
+                .get(&variant_id)
+                .copied()
+                .flatten()
+                .and_then(|id| units.get(&id));
+            match (unit_id.and_then(|id| units.get(&id)), variant_unit) {
+                (Some(unit), Some(variant_unit)) => {
+                    conversion_factor(unit, variant_unit).unwrap_or(1.0)
+                }
+                _ => 1.0,
+            }
+        };
+
+        let open = PurchaseOrderLines::find()
+            .filter(entity::purchase_order_lines::Column::VariantId.is_in(variant_ids))
+            .filter(entity::purchase_order_lines::Column::MoveId.is_null())
+            .find_also_related(PurchaseOrders)
+            .filter(entity::purchase_orders::Column::Status.ne(PurchaseOrderStatus::Finished))
+            .all(&self.db)
+            .await?;
+
+        let mut forecasts = Vec::with_capacity(rules.len());
+        for rule in rules {
+            let stock_ids = subtree_ids(&self.db, rule.stock_id).await?;
+            let on_hand = balances(&self.db, &stock_ids)
+                .await?
+                .get(&rule.variant_id)
+                .copied()
+                .unwrap_or_default();
+
+            let reserved: Option<f64> = StockReservations::find()
+                .select_only()
+                .column_as(entity::stock_reservations::Column::Quantity.sum(), "quantity")
+                .filter(entity::stock_reservations::Column::VariantId.eq(rule.variant_id))
+                .filter(entity::stock_reservations::Column::StockId.is_in(stock_ids.clone()))
+                .into_tuple()
+                .one(&self.db)
+                .await?
+                .flatten();
+
+            let incoming = open
+                .iter()
+                .filter_map(|(line, order)| Some((line, order.as_ref()?)))
+                .filter(|(line, order)| {
+                    line.variant_id == Some(rule.variant_id)
+                        && stock_ids.contains(&order.target_stock_id)
+                })
+                .map(|(line, _)| line.quantity * factor(rule.variant_id, line.unit_id))
+                .sum();
+
+            forecasts.push(RuleForecast {
+                rule,
+                on_hand,
+                reserved: reserved.unwrap_or_default(),
+                incoming,
+            });
+        }
+        Ok(forecasts)
+    }
+));
@@ -0,0 +1,199 @@
+use chrono::NaiveDate;
+use entity::{
+    prelude::{SaleCreditNoteLines, SaleCreditNotes, SaleInvoiceLines, SaleInvoices},
+    sea_orm_active_enums::DocumentType,
+};
+use macros::make_repo;
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::{NotSet, Set},
+    ColumnTrait, DbErr, EntityTrait, JoinType, QueryFilter, QueryOrder, QuerySelect, RelationTrait,
+    TransactionTrait,
+};
+
+use super::{CreditNoteLineDraft, check_remaining, next_number, return_stock, reverse_transaction};
+
+make_repo!(SaleCreditNote, (
+    async fn list_for_invoice(
+        &self,
+        invoice_id: i32,
+    ) -> Result<Vec<entity::sale_credit_notes::Model>, DbErr> {
+        SaleCreditNotes::find()
+            .filter(entity::sale_credit_notes::Column::InvoiceId.eq(invoice_id))
+            .order_by_asc(entity::sale_credit_notes::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    async fn list_lines_for_invoice(
+        &self,
+        invoice_id: i32,
+    ) -> Result<Vec<entity::sale_credit_note_lines::Model>, DbErr> {
+        SaleCreditNoteLines::find()
+            .join(
+                JoinType::InnerJoin,
+                entity::sale_credit_note_lines::Relation::SaleCreditNotes.def(),
+            )
+            .filter(entity::sale_credit_notes::Column::InvoiceId.eq(invoice_id))
+            .order_by_asc(entity::sale_credit_note_lines::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    /// Lists the credit notes dated within the range together with their
+    /// lines and the credited invoice, whose tax rates they correct.
+    async fn list_with_lines(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<
+        Vec<(
+            entity::sale_credit_notes::Model,
+            Vec<entity::sale_credit_note_lines::Model>,
+            entity::sale_invoices::Model,
+        )>,
+        DbErr,
+    > {
+        let credit_notes = SaleCreditNotes::find()
+            .filter(entity::sale_credit_notes::Column::Date.between(from, to))
+            .order_by_asc(entity::sale_credit_notes::Column::Id)
+            .find_with_related(SaleCreditNoteLines)
+            .all(&self.db)
+            .await?;
+
+        let invoice_ids: Vec<i32> = credit_notes
+            .iter()
+            .map(|(credit_note, _)| credit_note.invoice_id)
+            .collect();
+        let invoices = SaleInvoices::find()
+            .filter(entity::sale_invoices::Column::Id.is_in(invoice_ids))
+            .all(&self.db)
+            .await?;
+
+        // Invoices with credit notes can't be deleted.
+        credit_notes
+            .into_iter()
+            .map(|(credit_note, lines)| {
+                let invoice = invoices
+                    .iter()
+                    .find(|invoice| invoice.id == credit_note.invoice_id)
+                    .cloned()
+                    .ok_or(DbErr::RecordNotFound("invoice".to_string()))?;
+                Ok((credit_note, lines, invoice))
+            })
+            .collect()
+    }
+
+    /// Creates the credit note with the next number of the sale credit note
+    /// sequence. The postings of the credited invoice lines are reversed and
+    /// returned goods are moved back in the same transaction.
+    async fn create(
+        &self,
+        invoice_id: i32,
+        date: NaiveDate,
+        reason: String,
+        lines: Vec<CreditNoteLineDraft>,
+    ) -> Result<
+        (
+            entity::sale_credit_notes::Model,
+            Vec<entity::sale_credit_note_lines::Model>,
+        ),
+        DbErr,
+    > {
+        let txn = self.db.begin().await?;
+
+        // Concurrent credit notes of the same invoice wait for each other,
+        // so together they can't credit more than was invoiced.
+        SaleInvoices::find_by_id(invoice_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(DbErr::RecordNotFound("sale invoice".to_string()))?;
+
+        let invoiced: Vec<(i32, f64)> = SaleInvoiceLines::find()
+            .filter(entity::sale_invoice_lines::Column::InvoiceId.eq(invoice_id))
+            .all(&txn)
+            .await?
+            .iter()
+            .map(|line| (line.id, line.quantity))
+            .collect();
+        let credited: Vec<(i32, f64)> = SaleCreditNoteLines::find()
+            .join(
+                JoinType::InnerJoin,
+                entity::sale_credit_note_lines::Relation::SaleCreditNotes.def(),
+            )
+            .filter(entity::sale_credit_notes::Column::InvoiceId.eq(invoice_id))
+            .all(&txn)
+            .await?
+            .iter()
+            .map(|line| (line.invoice_line_id, line.quantity))
+            .collect();
+        check_remaining(&invoiced, &credited, &lines)?;
+
+        let reference = next_number(&self.db, &txn, DocumentType::SaleCreditNote, date).await?;
+        let credit_note = entity::sale_credit_notes::ActiveModel {
+            id: NotSet,
+            reference: Set(reference),
+            date: Set(date),
+            invoice_id: Set(invoice_id),
+            reason: Set(reason),
+        }
+        .insert(&txn)
+        .await?;
+
+        let mut created = Vec::with_capacity(lines.len());
+        for line in lines {
+            let (revenue, discount_transaction_id) = match line.discount_transaction_id {
+                Some(_) => (
+                    line.net + line.discount,
+                    reverse_transaction(&txn, line.discount_transaction_id, date, line.discount)
+                        .await?,
+                ),
+                None => (line.net, None),
+            };
+            let transaction_id =
+                reverse_transaction(&txn, line.transaction_id, date, revenue).await?;
+            let tax_transaction_id =
+                reverse_transaction(&txn, line.tax_transaction_id, date, line.tax_amount).await?;
+            let move_id = return_stock(&txn, &line).await?;
+            let tax_postings = line.tax_postings;
+
+            let line = entity::sale_credit_note_lines::ActiveModel {
+                id: NotSet,
+                credit_note_id: Set(credit_note.id),
+                invoice_line_id: Set(line.invoice_line_id),
+                description: Set(line.description),
+                quantity: Set(line.quantity),
+                price: Set(line.price),
+                tax: Set(line.tax),
+                tax_id: Set(line.tax_id),
+                variant_id: Set(line.variant_id),
+                transaction_id: Set(transaction_id),
+                tax_transaction_id: Set(tax_transaction_id),
+                move_id: Set(move_id),
+                discount_amount: Set(line.discount),
+                discount_transaction_id: Set(discount_transaction_id),
+            }
+            .insert(&txn)
+            .await?;
+
+            for posting in tax_postings {
+                let transaction_id =
+                    reverse_transaction(&txn, posting.transaction_id, date, posting.amount).await?;
+                entity::sale_credit_note_line_taxes::ActiveModel {
+                    id: NotSet,
+                    line_id: Set(line.id),
+                    tax_id: Set(posting.tax_id),
+                    amount: Set(posting.amount),
+                    transaction_id: Set(transaction_id),
+                }
+                .insert(&txn)
+                .await?;
+            }
+            created.push(line);
+        }
+
+        txn.commit().await?;
+        Ok((credit_note, created))
+    }
+));
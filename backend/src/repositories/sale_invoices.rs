@@ -0,0 +1,162 @@
+use chrono::NaiveDate;
+use entity::{
+    prelude::{SaleInvoiceLines, SaleInvoices},
+    sea_orm_active_enums::{DocumentType, SaleInvoiceStatus},
+};
+use macros::make_repo;
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::{NotSet, Set},
+    ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+
+use super::{LinePostingDraft, insert_posting, next_number};
+use crate::utils::totals::Discount;
+
+/// All lines of the invoice have been posted already, e.g. by a second
+/// posting at the same time.
+pub const ERR_SALE_INVOICE_POSTED: &str = "the sale invoice has been posted already";
+
+make_repo!(SaleInvoice, (
+    /// Creates the invoice with the next number of the sale invoice
+    /// sequence, which stays locked until the invoice is committed.
+    async fn create(
+        &self,
+        date: NaiveDate,
+        status: SaleInvoiceStatus,
+        order_id: i32,
+        customer_id: i32,
+        discount: Discount,
+    ) -> Result<entity::sale_invoices::Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let reference = next_number(&self.db, &txn, DocumentType::SaleInvoice, date).await?;
+        let invoice = entity::sale_invoices::ActiveModel {
+            id: NotSet,
+            reference: Set(reference),
+            date: Set(date),
+            status: Set(status),
+            order_id: Set(order_id),
+            customer_id: Set(customer_id),
+            discount_percent: Set(discount.percent),
+            discount_amount: Set(discount.amount as i32),
+        }
+        .insert(&txn)
+        .await?;
+
+        txn.commit().await?;
+        Ok(invoice)
+    }
+
+    async fn get(&self, id: i32) -> Result<Option<entity::sale_invoices::Model>, DbErr> {
+        SaleInvoices::find_by_id(id).one(&self.db).await
+    }
+
+    /// Books the postings of the lines in one transaction, dated on the
+    /// invoice date. Drafts and proforma invoices become invoices.
+    /// The invoice is locked while the lines which are still unposted are
+    /// selected, lines posted at the same time are skipped.
+    async fn post(
+        &self,
+        invoice: entity::sale_invoices::Model,
+        lines: Vec<LinePostingDraft>,
+    ) -> Result<entity::sale_invoices::Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let invoice = SaleInvoices::find_by_id(invoice.id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(DbErr::RecordNotFound("sale invoice".to_string()))?;
+        let unposted: Vec<i32> = SaleInvoiceLines::find()
+            .select_only()
+            .column(entity::sale_invoice_lines::Column::Id)
+            .filter(entity::sale_invoice_lines::Column::InvoiceId.eq(invoice.id))
+            .filter(entity::sale_invoice_lines::Column::TransactionId.is_null())
+            .into_tuple()
+            .all(&txn)
+            .await?;
+        let lines: Vec<LinePostingDraft> = lines
+            .into_iter()
+            .filter(|line| unposted.contains(&line.line_id))
+            .collect();
+        let posted = matches!(
+            invoice.status,
+            SaleInvoiceStatus::Invoice | SaleInvoiceStatus::Finished
+        );
+        if posted && lines.is_empty() {
+            return Err(DbErr::Custom(ERR_SALE_INVOICE_POSTED.to_string()));
+        }
+
+        for line in lines {
+            let transaction_id = insert_posting(&txn, invoice.date, &line.net).await?;
+            let tax_transaction_id = match &line.tax {
+                Some(tax) => Some(insert_posting(&txn, invoice.date, tax).await?),
+                None => None,
+            };
+            let discount_transaction_id = match &line.discount {
+                Some(discount) => Some(insert_posting(&txn, invoice.date, discount).await?),
+                None => None,
+            };
+            entity::sale_invoice_lines::ActiveModel {
+                id: Set(line.line_id),
+                tax_id: Set(line.tax_id),
+                transaction_id: Set(Some(transaction_id)),
+                tax_transaction_id: Set(tax_transaction_id),
+                discount_transaction_id: Set(discount_transaction_id),
+                ..Default::default()
+            }
+            .update(&txn)
+            .await?;
+
+            for (tax_id, posting) in &line.tax_postings {
+                let transaction_id = insert_posting(&txn, invoice.date, posting).await?;
+                entity::sale_invoice_line_taxes::ActiveModel {
+                    id: NotSet,
+                    line_id: Set(line.line_id),
+                    tax_id: Set(*tax_id),
+                    amount: Set(posting.amount),
+                    transaction_id: Set(Some(transaction_id)),
+                }
+                .insert(&txn)
+                .await?;
+            }
+        }
+
+        let status = match &invoice.status {
+            SaleInvoiceStatus::Draft | SaleInvoiceStatus::Proforma => SaleInvoiceStatus::Invoice,
+            status => status.clone(),
+        };
+        let mut invoice: entity::sale_invoices::ActiveModel = invoice.into();
+        invoice.status = Set(status);
+        let invoice = invoice.update(&txn).await?;
+
+        txn.commit().await?;
+        Ok(invoice)
+    }
+
+    /// Lists the posted invoices dated within the range together with
+    /// their lines. Drafts and proforma invoices carry no tax liability.
+    async fn list_posted_with_lines(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<
+        Vec<(
+            entity::sale_invoices::Model,
+            Vec<entity::sale_invoice_lines::Model>,
+        )>,
+        DbErr,
+    > {
+        SaleInvoices::find()
+            .filter(entity::sale_invoices::Column::Date.between(from, to))
+            .filter(
+                entity::sale_invoices::Column::Status
+                    .is_in([SaleInvoiceStatus::Invoice, SaleInvoiceStatus::Finished]),
+            )
+            .order_by_asc(entity::sale_invoices::Column::Id)
+            .find_with_related(SaleInvoiceLines)
+            .all(&self.db)
+            .await
+    }
+));
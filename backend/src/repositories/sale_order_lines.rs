@@ -0,0 +1,59 @@
+use entity::{prelude::SaleOrderLines, sea_orm_active_enums::SaleOrderLineRoute};
+use macros::make_repo;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+};
+
+use crate::utils::totals::Discount;
+
+/// A line as it is added to a sale order, with its price already resolved.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SaleOrderLineDraft {
+    pub description: String,
+    pub quantity: f64,
+    pub price: i32,
+    pub tax: i32,
+    /// The tax whose rate on the order date applies instead of `tax`.
+    pub tax_id: Option<i32>,
+    pub variant_id: Option<i32>,
+    pub discount: Discount,
+    /// The unit of the quantity and price.
+    pub unit_id: Option<i32>,
+    pub route: SaleOrderLineRoute,
+}
+
+make_repo!(SaleOrderLine, (
+    async fn create(
+        &self,
+        order_id: i32,
+        line: SaleOrderLineDraft,
+    ) -> Result<entity::sale_order_lines::Model, DbErr> {
+        let entity = entity::sale_order_lines::ActiveModel {
+            description: Set(line.description),
+            quantity: Set(line.quantity),
+            price: Set(line.price),
+            tax: Set(line.tax),
+            tax_id: Set(line.tax_id),
+            order_id: Set(order_id),
+            variant_id: Set(line.variant_id),
+            discount_percent: Set(line.discount.percent),
+            discount_amount: Set(line.discount.amount as i32),
+            unit_id: Set(line.unit_id),
+            route: Set(line.route),
+            ..Default::default()
+        };
+
+        entity.insert(&self.db).await
+    }
+
+    async fn list_for_order(
+        &self,
+        order_id: i32,
+    ) -> Result<Vec<entity::sale_order_lines::Model>, DbErr> {
+        SaleOrderLines::find()
+            .filter(entity::sale_order_lines::Column::OrderId.eq(order_id))
+            .order_by_asc(entity::sale_order_lines::Column::Id)
+            .all(&self.db)
+            .await
+    }
+));
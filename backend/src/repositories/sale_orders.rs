@@ -0,0 +1,413 @@
+use chrono::NaiveDate;
+use entity::{
+    prelude::{PurchaseOrderLines, SaleOrderLines, SaleOrders, StockReservations, Variants},
+    sea_orm_active_enums::{DocumentType, ProductType, PurchaseOrderStatus, SaleOrderStatus},
+};
+use macros::make_repo;
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::{NotSet, Set},
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, JoinType, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, RelationTrait, TransactionTrait,
+};
+
+use super::{
+    LineMoveDraft, PurchaseOrderLineDraft, ReservationDraft, SaleOrderLineDraft, availability_in,
+    check_available, insert_lines, insert_order, move_line, next_number, release,
+};
+use crate::utils::{
+    availability::{Shortage, available_by_variant, shortages},
+    totals::Discount,
+};
+
+/// The order is no longer a draft or quotation, e.g. because it has been
+/// confirmed at the same time.
+pub const ERR_SALE_ORDER_NOT_DRAFT: &str = "the sale order is no longer a draft or quotation";
+
+/// The order has drop-ship purchase orders, which suppliers deliver on.
+pub const ERR_SALE_ORDER_DROP_SHIPPED: &str = "the sale order has drop-ship purchase orders";
+
+/// The order or some of the lines to deliver have been delivered already,
+/// e.g. by a second delivery at the same time.
+pub const ERR_SALE_ORDER_DELIVERED: &str = "the sale order has been delivered already";
+
+/// The order has been cancelled, e.g. while it was being delivered.
+pub const ERR_SALE_ORDER_CANCELLED: &str = "the sale order has been cancelled";
+
+/// The source stock can't cover the order and overselling isn't allowed.
+pub const ERR_INSUFFICIENT_STOCK: &str = "the source stock can't cover the order";
+
+/// A confirmed order with what it asks for beyond the available quantities
+/// and the purchase orders placed for its drop-shipped lines.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SaleOrderConfirmation {
+    pub order: entity::sale_orders::Model,
+    pub shortages: Vec<Shortage>,
+    pub purchase_orders: Vec<entity::purchase_orders::Model>,
+}
+
+/// A purchase order of the goods a supplier delivers straight from its stock
+/// to the customer's stock.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DropShipDraft {
+    pub supplier_id: i32,
+    pub source_stock_id: i32,
+    pub target_stock_id: i32,
+    pub lines: Vec<DropShipLineDraft>,
+}
+
+/// A purchase order line for the goods of a drop-shipped sale order line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DropShipLineDraft {
+    pub sale_line_id: i32,
+    pub line: PurchaseOrderLineDraft,
+}
+
+/// A line of goods a customer reports consuming from a consignment stock,
+/// with the move taking them out of it. The line id of the move is set once
+/// the line is inserted.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConsumedSaleLineDraft {
+    pub line: SaleOrderLineDraft,
+    pub stock_move: LineMoveDraft,
+}
+
+/// Finishes the sale order once the goods of all its lines with a stockable
+/// variant are delivered, whether from the own stock or drop-shipped.
+/// Returns whether the order is finished.
+pub(super) async fn finish_if_delivered<C: ConnectionTrait>(
+    db: &C,
+    order_id: i32,
+) -> Result<bool, DbErr> {
+    let pending = SaleOrderLines::find()
+        .join(
+            JoinType::InnerJoin,
+            entity::sale_order_lines::Relation::Variants.def(),
+        )
+        .join(
+            JoinType::InnerJoin,
+            entity::variants::Relation::Products.def(),
+        )
+        .filter(entity::sale_order_lines::Column::OrderId.eq(order_id))
+        .filter(entity::sale_order_lines::Column::MoveId.is_null())
+        .filter(entity::products::Column::ProductType.eq(ProductType::Stockable))
+        .count(db)
+        .await?;
+    if pending > 0 {
+        return Ok(false);
+    }
+
+    if let Some(order) = SaleOrders::find_by_id(order_id).one(db).await? {
+        let mut order: entity::sale_orders::ActiveModel = order.into();
+        order.status = Set(SaleOrderStatus::Finished);
+        order.update(db).await?;
+    }
+    Ok(true)
+}
+
+make_repo!(SaleOrder, (
+    /// Creates the order with the next number of the sale order sequence.
+    async fn create(
+        &self,
+        date: NaiveDate,
+        status: SaleOrderStatus,
+        customer_id: i32,
+        source_stock_id: i32,
+        target_stock_id: i32,
+        discount: Discount,
+    ) -> Result<entity::sale_orders::Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let reference = next_number(&self.db, &txn, DocumentType::SaleOrder, date).await?;
+        let order = entity::sale_orders::ActiveModel {
+            id: NotSet,
+            reference: Set(reference),
+            date: Set(date),
+            status: Set(status),
+            customer_id: Set(customer_id),
+            source_stock_id: Set(source_stock_id),
+            target_stock_id: Set(target_stock_id),
+            discount_percent: Set(discount.percent),
+            discount_amount: Set(discount.amount as i32),
+        }
+        .insert(&txn)
+        .await?;
+
+        txn.commit().await?;
+        Ok(order)
+    }
+
+    /// Confirms the order, reserves the quantities of its lines and places
+    /// the purchase orders of its drop-shipped lines. The order and the
+    /// reserved variants are locked while the quantities available in the
+    /// order's source stock are checked, so neither a second confirmation of
+    /// the order nor one of another order can promise the same goods.
+    async fn confirm(
+        &self,
+        order_id: i32,
+        date: NaiveDate,
+        reservations: Vec<ReservationDraft>,
+        drop_ships: Vec<DropShipDraft>,
+        allow_oversell: bool,
+    ) -> Result<SaleOrderConfirmation, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let order = SaleOrders::find_by_id(order_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(DbErr::RecordNotFound("sale order".to_string()))?;
+        if !matches!(
+            order.status,
+            SaleOrderStatus::Draft | SaleOrderStatus::Quotation
+        ) {
+            return Err(DbErr::Custom(ERR_SALE_ORDER_NOT_DRAFT.to_string()));
+        }
+
+        let mut variant_ids: Vec<i32> = reservations
+            .iter()
+            .map(|reservation| reservation.variant_id)
+            .collect();
+        variant_ids.sort_unstable();
+        variant_ids.dedup();
+        Variants::find()
+            .filter(entity::variants::Column::Id.is_in(variant_ids.clone()))
+            .order_by_asc(entity::variants::Column::Id)
+            .lock_exclusive()
+            .all(&txn)
+            .await?;
+
+        // Goods anywhere below the source stock, e.g. in the bins of a
+        // warehouse, can be delivered from it.
+        let availability = availability_in(&txn, &variant_ids, Some(order.source_stock_id)).await?;
+        let requested: Vec<(i32, f64)> = reservations
+            .iter()
+            .map(|reservation| (reservation.variant_id, reservation.quantity))
+            .collect();
+        let shortages = shortages(&requested, &available_by_variant(&availability));
+        if !shortages.is_empty() && !allow_oversell {
+            return Err(DbErr::Custom(ERR_INSUFFICIENT_STOCK.to_string()));
+        }
+
+        if !reservations.is_empty() {
+            let models = reservations
+                .into_iter()
+                .map(|reservation| entity::stock_reservations::ActiveModel {
+                    id: NotSet,
+                    line_id: Set(reservation.line_id),
+                    variant_id: Set(reservation.variant_id),
+                    stock_id: Set(reservation.stock_id),
+                    quantity: Set(reservation.quantity),
+                });
+            StockReservations::insert_many(models).exec(&txn).await?;
+        }
+
+        let mut purchase_orders = Vec::with_capacity(drop_ships.len());
+        for drop_ship in drop_ships {
+            let purchase_order = insert_order(
+                &self.db,
+                &txn,
+                date,
+                PurchaseOrderStatus::Order,
+                drop_ship.supplier_id,
+                drop_ship.source_stock_id,
+                drop_ship.target_stock_id,
+            )
+            .await?;
+            let lines = drop_ship
+                .lines
+                .into_iter()
+                .map(|line| (Some(line.sale_line_id), line.line))
+                .collect();
+            insert_lines(&txn, purchase_order.id, lines).await?;
+            purchase_orders.push(purchase_order);
+        }
+
+        let mut order: entity::sale_orders::ActiveModel = order.into();
+        order.status = Set(SaleOrderStatus::Order);
+        let order = order.update(&txn).await?;
+
+        txn.commit().await?;
+        Ok(SaleOrderConfirmation {
+            order,
+            shortages,
+            purchase_orders,
+        })
+    }
+
+    /// Cancels the order and releases what it has reserved. Orders whose
+    /// lines are drop-shipped can't be cancelled once their purchase orders
+    /// are placed.
+    async fn cancel(
+        &self,
+        order: entity::sale_orders::Model,
+    ) -> Result<entity::sale_orders::Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        // Confirming the order at the same time may place purchase orders for
+        // its lines.
+        let order = SaleOrders::find_by_id(order.id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(DbErr::RecordNotFound("sale order".to_string()))?;
+        let line_ids: Vec<i32> = SaleOrderLines::find()
+            .select_only()
+            .column(entity::sale_order_lines::Column::Id)
+            .filter(entity::sale_order_lines::Column::OrderId.eq(order.id))
+            .into_tuple()
+            .all(&txn)
+            .await?;
+        let drop_shipped = PurchaseOrderLines::find()
+            .filter(entity::purchase_order_lines::Column::SaleLineId.is_in(line_ids.clone()))
+            .count(&txn)
+            .await?;
+        if drop_shipped > 0 {
+            return Err(DbErr::Custom(ERR_SALE_ORDER_DROP_SHIPPED.to_string()));
+        }
+        release(&txn, line_ids).await?;
+
+        let mut order: entity::sale_orders::ActiveModel = order.into();
+        order.status = Set(SaleOrderStatus::Cancelled);
+        let order = order.update(&txn).await?;
+
+        txn.commit().await?;
+        Ok(order)
+    }
+
+    /// Delivers the goods of the lines from the order's source stock to its
+    /// target stock, posts their cost and releases their reservations. The
+    /// order is finished unless drop-shipped lines are still to arrive. The
+    /// order is locked while its lines are checked to be still open, so a
+    /// second delivery at the same time can't move the goods again.
+    async fn deliver(
+        &self,
+        order: entity::sale_orders::Model,
+        date: NaiveDate,
+        lines: Vec<LineMoveDraft>,
+    ) -> Result<entity::sale_orders::Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let order = SaleOrders::find_by_id(order.id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(DbErr::RecordNotFound("sale order".to_string()))?;
+        match order.status {
+            SaleOrderStatus::Finished => {
+                return Err(DbErr::Custom(ERR_SALE_ORDER_DELIVERED.to_string()));
+            }
+            SaleOrderStatus::Cancelled => {
+                return Err(DbErr::Custom(ERR_SALE_ORDER_CANCELLED.to_string()));
+            }
+            _ => {}
+        }
+        let line_ids: Vec<i32> = lines.iter().map(|line| line.line_id).collect();
+        let open = SaleOrderLines::find()
+            .filter(entity::sale_order_lines::Column::OrderId.eq(order.id))
+            .filter(entity::sale_order_lines::Column::Id.is_in(line_ids))
+            .filter(entity::sale_order_lines::Column::MoveId.is_null())
+            .count(&txn)
+            .await?;
+        if open != lines.len() as u64 {
+            return Err(DbErr::Custom(ERR_SALE_ORDER_DELIVERED.to_string()));
+        }
+
+        for line in &lines {
+            let move_id =
+                move_line(&txn, date, order.source_stock_id, order.target_stock_id, line).await?;
+            SaleOrderLines::update_many()
+                .col_expr(entity::sale_order_lines::Column::MoveId, move_id.into())
+                .filter(entity::sale_order_lines::Column::Id.eq(line.line_id))
+                .filter(entity::sale_order_lines::Column::MoveId.is_null())
+                .exec(&txn)
+                .await?;
+        }
+        release(&txn, lines.iter().map(|line| line.line_id).collect()).await?;
+
+        let order = match finish_if_delivered(&txn, order.id).await? {
+            true => entity::sale_orders::Model {
+                status: SaleOrderStatus::Finished,
+                ..order
+            },
+            false => order,
+        };
+
+        txn.commit().await?;
+        Ok(order)
+    }
+
+    /// Records the goods a customer reports consuming from a consignment
+    /// stock as a finished order, each line delivered by a move from that
+    /// stock to the target stock. The cost of the goods is posted as they
+    /// leave the own stock, and the order is ready to be invoiced. Fails if
+    /// the consignment stock doesn't hold the goods.
+    async fn consume(
+        &self,
+        date: NaiveDate,
+        customer_id: i32,
+        source_stock_id: i32,
+        target_stock_id: i32,
+        lines: Vec<ConsumedSaleLineDraft>,
+    ) -> Result<entity::sale_orders::Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let quantities: Vec<(i32, f64)> = lines
+            .iter()
+            .map(|draft| (draft.stock_move.variant_id, draft.stock_move.quantity))
+            .collect();
+        check_available(&txn, source_stock_id, &quantities).await?;
+
+        let reference = next_number(&self.db, &txn, DocumentType::SaleOrder, date).await?;
+        let order = entity::sale_orders::ActiveModel {
+            id: NotSet,
+            reference: Set(reference),
+            date: Set(date),
+            status: Set(SaleOrderStatus::Finished),
+            customer_id: Set(customer_id),
+            source_stock_id: Set(source_stock_id),
+            target_stock_id: Set(target_stock_id),
+            discount_percent: Set(0.0),
+            discount_amount: Set(0),
+        }
+        .insert(&txn)
+        .await?;
+
+        for draft in lines {
+            let line = entity::sale_order_lines::ActiveModel {
+                id: NotSet,
+                description: Set(draft.line.description),
+                quantity: Set(draft.line.quantity),
+                price: Set(draft.line.price),
+                tax: Set(draft.line.tax),
+                tax_id: Set(draft.line.tax_id),
+                order_id: Set(order.id),
+                variant_id: Set(draft.line.variant_id),
+                move_id: Set(None),
+                discount_percent: Set(draft.line.discount.percent),
+                discount_amount: Set(draft.line.discount.amount as i32),
+                unit_id: Set(draft.line.unit_id),
+                route: Set(draft.line.route),
+            }
+            .insert(&txn)
+            .await?;
+
+            let stock_move = LineMoveDraft {
+                line_id: line.id,
+                ..draft.stock_move
+            };
+            let move_id =
+                move_line(&txn, date, source_stock_id, target_stock_id, &stock_move).await?;
+            let mut line: entity::sale_order_lines::ActiveModel = line.into();
+            line.move_id = Set(Some(move_id));
+            line.update(&txn).await?;
+        }
+
+        txn.commit().await?;
+        Ok(order)
+    }
+
+    async fn get(&self, id: i32) -> Result<Option<entity::sale_orders::Model>, DbErr> {
+        SaleOrders::find_by_id(id).one(&self.db).await
+    }
+));
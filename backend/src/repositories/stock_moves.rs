@@ -1,11 +1,37 @@
 use macros::make_repo;
-use sea_orm::DbErr;
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, DbErr, TransactionTrait};
 
+use super::{LotDraft, assign_lots, value_move};
 use crate::models::Page;
 
 make_repo!(StockMove, (
-    async fn create(&self) -> Result<entity::stock_moves::Model, DbErr> {
-        todo!()
+    /// Records a move of the quantity, in the variant's unit, from one
+    /// stock to another, split over the lots of tracked variants. Goods
+    /// coming into the own stocks are valued at the variant's purchase
+    /// price.
+    async fn create(
+        &self,
+        quantity: f64,
+        variant_id: i32,
+        source_stock_id: i32,
+        target_stock_id: i32,
+        lots: Vec<LotDraft>,
+    ) -> Result<entity::stock_moves::Model, DbErr> {
+        let entity = entity::stock_moves::ActiveModel {
+            quantity: Set(quantity),
+            variant_id: Set(variant_id),
+            source_stock_id: Set(source_stock_id),
+            target_stock_id: Set(target_stock_id),
+            ..Default::default()
+        };
+
+        let txn = self.db.begin().await?;
+        let stock_move = entity.insert(&txn).await?;
+        assign_lots(&txn, &stock_move, &lots).await?;
+        value_move(&txn, &stock_move, None).await?;
+
+        txn.commit().await?;
+        Ok(stock_move)
     }
 
     async fn list(
@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+
+use entity::prelude::{StockMoves, StockReservations, Variants};
+use macros::make_repo;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, JoinType, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait,
+};
+
+use super::subtree_ids;
+use crate::utils::availability::{available_by_variant, shortages};
+
+/// The stock doesn't hold the quantities taken out of it.
+pub const ERR_EXCEEDS_STOCK: &str = "the stock doesn't hold the quantities taken out of it";
+
+/// The quantity of a variant in a stock and how much of it sale orders
+/// have reserved, both in the variant's unit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StockAvailability {
+    pub stock_id: i32,
+    pub variant_id: i32,
+    pub on_hand: f64,
+    pub reserved: f64,
+}
+
+impl StockAvailability {
+    /// What is left to promise, negative if more is reserved than there is.
+    pub fn available(&self) -> f64 {
+        self.on_hand - self.reserved
+    }
+}
+
+/// A reservation of a sale order line's quantity, in the variant's unit,
+/// in the stock the order is delivered from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReservationDraft {
+    pub line_id: i32,
+    pub variant_id: i32,
+    pub stock_id: i32,
+    pub quantity: f64,
+}
+
+/// Releases the reservations of the sale order lines.
+pub(super) async fn release<C: ConnectionTrait>(db: &C, line_ids: Vec<i32>) -> Result<(), DbErr> {
+    StockReservations::delete_many()
+        .filter(entity::stock_reservations::Column::LineId.is_in(line_ids))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// Sums up the moves of the variants into or out of the stocks, or the own
+/// stocks that can be sold from if none are given, per stock and variant.
+async fn move_sums<C: ConnectionTrait>(
+    db: &C,
+    incoming: bool,
+    variant_ids: &[i32],
+    stock_ids: Option<&[i32]>,
+) -> Result<Vec<(i32, i32, f64)>, DbErr> {
+    let (column, relation) = match incoming {
+        true => (
+            entity::stock_moves::Column::TargetStockId,
+            entity::stock_moves::Relation::Stocks1,
+        ),
+        false => (
+            entity::stock_moves::Column::SourceStockId,
+            entity::stock_moves::Relation::Stocks2,
+        ),
+    };
+
+    let query = StockMoves::find()
+        .select_only()
+        .column(column)
+        .column(entity::stock_moves::Column::VariantId)
+        .column_as(entity::stock_moves::Column::Quantity.sum(), "quantity")
+        .filter(entity::stock_moves::Column::VariantId.is_in(variant_ids.to_vec()))
+        .group_by(column)
+        .group_by(entity::stock_moves::Column::VariantId);
+    let query = match stock_ids {
+        Some(stock_ids) => query.filter(column.is_in(stock_ids.to_vec())),
+        // Consignment stocks at customers are valued as own, but the goods
+        // in them are the customer's to consume.
+        None => query
+            .join(JoinType::InnerJoin, relation.def())
+            .filter(entity::stocks::Column::IsOwn.eq(true))
+            .filter(entity::stocks::Column::Consignment.eq(false)),
+    };
+    query.into_tuple().all(db).await
+}
+
+/// Computes the availability on the connection, e.g. in a transaction that
+/// holds the locks of the variants.
+pub(super) async fn availability_in<C: ConnectionTrait>(
+    db: &C,
+    variant_ids: &[i32],
+    stock_id: Option<i32>,
+) -> Result<Vec<StockAvailability>, DbErr> {
+    let stock_ids = match stock_id {
+        Some(stock_id) => Some(subtree_ids(db, stock_id).await?),
+        None => None,
+    };
+
+    let mut totals: BTreeMap<(i32, i32), (f64, f64)> = BTreeMap::new();
+    for (stock_id, variant_id, quantity) in
+        move_sums(db, true, variant_ids, stock_ids.as_deref()).await?
+    {
+        totals.entry((stock_id, variant_id)).or_default().0 += quantity;
+    }
+    for (stock_id, variant_id, quantity) in
+        move_sums(db, false, variant_ids, stock_ids.as_deref()).await?
+    {
+        totals.entry((stock_id, variant_id)).or_default().0 -= quantity;
+    }
+
+    let query = StockReservations::find()
+        .select_only()
+        .column(entity::stock_reservations::Column::StockId)
+        .column(entity::stock_reservations::Column::VariantId)
+        .column_as(
+            entity::stock_reservations::Column::Quantity.sum(),
+            "quantity",
+        )
+        .filter(entity::stock_reservations::Column::VariantId.is_in(variant_ids.to_vec()))
+        .group_by(entity::stock_reservations::Column::StockId)
+        .group_by(entity::stock_reservations::Column::VariantId);
+    let query = match stock_ids {
+        Some(stock_ids) => {
+            query.filter(entity::stock_reservations::Column::StockId.is_in(stock_ids))
+        }
+        None => query
+            .join(
+                JoinType::InnerJoin,
+                entity::stock_reservations::Relation::Stocks.def(),
+            )
+            .filter(entity::stocks::Column::IsOwn.eq(true))
+            .filter(entity::stocks::Column::Consignment.eq(false)),
+    };
+    let reserved: Vec<(i32, i32, f64)> = query.into_tuple().all(db).await?;
+    for (stock_id, variant_id, quantity) in reserved {
+        totals.entry((stock_id, variant_id)).or_default().1 += quantity;
+    }
+
+    Ok(totals
+        .into_iter()
+        .map(
+            |((stock_id, variant_id), (on_hand, reserved))| StockAvailability {
+                stock_id,
+                variant_id,
+                on_hand,
+                reserved,
+            },
+        )
+        .collect())
+}
+
+/// Locks the variants and checks that the stock and the locations below it
+/// hold the quantities of them, in the variants' units, which are summed up
+/// per variant.
+pub(super) async fn check_available<C: ConnectionTrait>(
+    db: &C,
+    stock_id: i32,
+    quantities: &[(i32, f64)],
+) -> Result<(), DbErr> {
+    let mut variant_ids: Vec<i32> = quantities
+        .iter()
+        .map(|(variant_id, _)| *variant_id)
+        .collect();
+    variant_ids.sort_unstable();
+    variant_ids.dedup();
+    Variants::find()
+        .filter(entity::variants::Column::Id.is_in(variant_ids.clone()))
+        .order_by_asc(entity::variants::Column::Id)
+        .lock_exclusive()
+        .all(db)
+        .await?;
+
+    let availability = availability_in(db, &variant_ids, Some(stock_id)).await?;
+    match shortages(quantities, &available_by_variant(&availability)).is_empty() {
+        true => Ok(()),
+        false => Err(DbErr::Custom(ERR_EXCEEDS_STOCK.to_string())),
+    }
+}
+
+make_repo!(StockReservation, (
+    /// Sums up what is in the stock and each location below it, or each of
+    /// the own stocks but consignment stocks if none is given, and what is
+    /// reserved of it per variant.
+    async fn availability(
+        &self,
+        variant_ids: Vec<i32>,
+        stock_id: Option<i32>,
+    ) -> Result<Vec<StockAvailability>, DbErr> {
+        availability_in(&self.db, &variant_ids, stock_id).await
+    }
+));
@@ -0,0 +1,292 @@
+use chrono::NaiveDate;
+use entity::{
+    prelude::{Products, StockValuationLayers, Stocks, Variants},
+    sea_orm_active_enums::ProductType,
+};
+use macros::make_repo;
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::{NotSet, Set},
+    ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, JoinType, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait,
+};
+
+use super::{LotDraft, PostingDraft, QUANTITY_TOLERANCE, assign_lots, insert_posting};
+use crate::utils::valuation::{OpenLayer, consume, settle};
+
+/// A line of an order whose goods are moved into or out of the own stock.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineMoveDraft {
+    pub line_id: i32,
+    pub variant_id: i32,
+    /// The quantity in the variant's unit.
+    pub quantity: f64,
+    /// The cost of one unit in cents that goods coming into the own stock
+    /// are valued at. Goods leaving it are valued from the layers they came
+    /// in with.
+    pub unit_cost: Option<f64>,
+    /// The accounts the cost of goods leaving the own stock is posted to,
+    /// or the value of goods coming into it.
+    pub cost_accounts: Option<CostAccounts>,
+    /// The lots or serial numbers the goods are moved in, if the variant is
+    /// tracked.
+    pub lots: Vec<LotDraft>,
+}
+
+/// The cost of goods sold is debited to the expense account and credited
+/// to the inventory account. Goods coming into the stock, e.g. those found
+/// counting it, are posted the other way round.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CostAccounts {
+    pub expense_account_id: i32,
+    pub inventory_account_id: i32,
+}
+
+/// The quantity of a variant in the own stocks and its value in cents.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariantValuation {
+    pub variant_id: i32,
+    pub name: String,
+    pub quantity: f64,
+    pub value: i64,
+}
+
+/// Whether the stock belongs to the company, rather than to a supplier or
+/// customer.
+pub(super) async fn is_own_stock<C: ConnectionTrait>(db: &C, stock_id: i32) -> Result<bool, DbErr> {
+    Stocks::find_by_id(stock_id)
+        .one(db)
+        .await?
+        .map(|stock| stock.is_own)
+        .ok_or(DbErr::RecordNotFound("stock".to_string()))
+}
+
+/// Converts a value in cents to the width it is stored in, rather than
+/// truncating it.
+fn stored_cents(value: i64) -> Result<i32, DbErr> {
+    i32::try_from(value)
+        .map_err(|_| DbErr::Custom(format!("value of {value} cents is out of range")))
+}
+
+/// Locks and returns the layers of the variant whose remaining quantity is
+/// above the tolerance, if `in_stock`, or below its negative, oldest first.
+async fn remaining_layers<C: ConnectionTrait>(
+    db: &C,
+    variant_id: i32,
+    in_stock: bool,
+) -> Result<(Vec<entity::stock_valuation_layers::Model>, Vec<OpenLayer>), DbErr> {
+    let remaining = match in_stock {
+        true => entity::stock_valuation_layers::Column::RemainingQuantity.gt(QUANTITY_TOLERANCE),
+        false => entity::stock_valuation_layers::Column::RemainingQuantity.lt(-QUANTITY_TOLERANCE),
+    };
+    let models = StockValuationLayers::find()
+        .filter(entity::stock_valuation_layers::Column::VariantId.eq(variant_id))
+        .filter(remaining)
+        .order_by_asc(entity::stock_valuation_layers::Column::Id)
+        .lock_exclusive()
+        .all(db)
+        .await?;
+    let layers = models
+        .iter()
+        .map(|layer| OpenLayer {
+            id: layer.id,
+            quantity: layer.remaining_quantity,
+            value: layer.remaining_value as i64,
+        })
+        .collect();
+    Ok((models, layers))
+}
+
+/// Records a valuation layer for a move of a stockable variant into or out
+/// of the own stocks. Receipts are valued at the unit cost, or the
+/// variant's purchase price if there is none. Issues consume the open
+/// layers by the product's costing method. What an issue takes beyond the
+/// stock is valued at the latest cost and left open on its layer as a
+/// negative remainder, which the next receipts settle; the difference
+/// between that cost and theirs is booked with them. Moves between own
+/// stocks, or between stocks of others, don't change the value of the
+/// stock.
+pub(super) async fn value_move<C: ConnectionTrait>(
+    db: &C,
+    stock_move: &entity::stock_moves::Model,
+    unit_cost: Option<f64>,
+) -> Result<Option<entity::stock_valuation_layers::Model>, DbErr> {
+    let receipt = match (
+        is_own_stock(db, stock_move.source_stock_id).await?,
+        is_own_stock(db, stock_move.target_stock_id).await?,
+    ) {
+        (false, true) => true,
+        (true, false) => false,
+        _ => return Ok(None),
+    };
+
+    let Some((variant, Some(product))) = Variants::find_by_id(stock_move.variant_id)
+        .find_also_related(Products)
+        .one(db)
+        .await?
+    else {
+        return Err(DbErr::RecordNotFound("variant".to_string()));
+    };
+    if product.product_type != ProductType::Stockable {
+        return Ok(None);
+    }
+
+    let purchase_price = variant.purchase_price as f64;
+    let (quantity, value, remaining_quantity, remaining_value) = if receipt {
+        let value = (stock_move.quantity * unit_cost.unwrap_or(purchase_price)).round() as i64;
+        let (models, shortfalls) = remaining_layers(db, variant.id, false).await?;
+        let (settlements, settled_value) = settle(&shortfalls, stock_move.quantity, value);
+        for (settlement, layer) in settlements.iter().zip(models) {
+            let remaining_quantity = layer.remaining_quantity + settlement.quantity;
+            let remaining_value = layer.remaining_value + stored_cents(settlement.value)?;
+            let mut layer: entity::stock_valuation_layers::ActiveModel = layer.into();
+            layer.remaining_quantity = Set(remaining_quantity);
+            layer.remaining_value = Set(remaining_value);
+            layer.update(db).await?;
+        }
+
+        let settled_quantity: f64 = settlements.iter().map(|s| s.quantity).sum();
+        let issued_value: i64 = settlements.iter().map(|s| s.value).sum();
+        (
+            stock_move.quantity,
+            value - settled_value + issued_value,
+            stock_move.quantity - settled_quantity,
+            value - settled_value,
+        )
+    } else {
+        let (models, layers) = remaining_layers(db, variant.id, true).await?;
+        // Goods issued beyond the stock are valued at the latest cost.
+        let fallback = models
+            .last()
+            .map(|layer| layer.value as f64 / layer.quantity)
+            .unwrap_or(purchase_price);
+
+        let (consumptions, value) = consume(
+            &layers,
+            stock_move.quantity,
+            &product.costing_method,
+            fallback,
+        );
+        for (consumption, layer) in consumptions.iter().zip(models) {
+            let remaining_quantity = layer.remaining_quantity - consumption.quantity;
+            let remaining_value = layer.remaining_value - stored_cents(consumption.value)?;
+            let mut layer: entity::stock_valuation_layers::ActiveModel = layer.into();
+            layer.remaining_quantity = Set(remaining_quantity);
+            layer.remaining_value = Set(remaining_value);
+            layer.update(db).await?;
+        }
+
+        let consumed_quantity: f64 = consumptions.iter().map(|c| c.quantity).sum();
+        let consumed_value: i64 = consumptions.iter().map(|c| c.value).sum();
+        let short = stock_move.quantity - consumed_quantity;
+        match short > QUANTITY_TOLERANCE {
+            true => (-stock_move.quantity, -value, -short, consumed_value - value),
+            false => (-stock_move.quantity, -value, 0.0, 0),
+        }
+    };
+
+    entity::stock_valuation_layers::ActiveModel {
+        id: NotSet,
+        move_id: Set(stock_move.id),
+        variant_id: Set(variant.id),
+        quantity: Set(quantity),
+        value: Set(stored_cents(value)?),
+        remaining_quantity: Set(remaining_quantity),
+        remaining_value: Set(stored_cents(remaining_value)?),
+        transaction_id: Set(None),
+    }
+    .insert(db)
+    .await
+    .map(Some)
+}
+
+/// Moves the goods of an order or count line in their lots and values the
+/// move. The value of goods leaving or coming into the own stock is posted
+/// if the line has accounts for it. Returns the id of the move.
+pub(super) async fn move_line<C: ConnectionTrait>(
+    db: &C,
+    date: NaiveDate,
+    source_stock_id: i32,
+    target_stock_id: i32,
+    line: &LineMoveDraft,
+) -> Result<i32, DbErr> {
+    let stock_move = entity::stock_moves::ActiveModel {
+        id: NotSet,
+        quantity: Set(line.quantity),
+        variant_id: Set(line.variant_id),
+        source_stock_id: Set(source_stock_id),
+        target_stock_id: Set(target_stock_id),
+    }
+    .insert(db)
+    .await?;
+    assign_lots(db, &stock_move, &line.lots).await?;
+
+    let layer = value_move(db, &stock_move, line.unit_cost).await?;
+    if let (Some(layer), Some(accounts)) = (layer, line.cost_accounts)
+        && layer.value != 0
+    {
+        let posting = match layer.value < 0 {
+            true => PostingDraft {
+                amount: -layer.value,
+                debit_account_id: accounts.expense_account_id,
+                credit_account_id: accounts.inventory_account_id,
+            },
+            false => PostingDraft {
+                amount: layer.value,
+                debit_account_id: accounts.inventory_account_id,
+                credit_account_id: accounts.expense_account_id,
+            },
+        };
+        let transaction_id = insert_posting(db, date, &posting).await?;
+        let mut layer: entity::stock_valuation_layers::ActiveModel = layer.into();
+        layer.transaction_id = Set(Some(transaction_id));
+        layer.update(db).await?;
+    }
+
+    Ok(stock_move.id)
+}
+
+make_repo!(StockValuationLayer, (
+    /// Sums up what is left of the receipts per variant, less the
+    /// shortfalls of issues beyond the stock, which is the quantity in the
+    /// own stocks and its value.
+    async fn summary(&self) -> Result<Vec<VariantValuation>, DbErr> {
+        let rows: Vec<(i32, String, f64, i64)> = StockValuationLayers::find()
+            .select_only()
+            .column(entity::stock_valuation_layers::Column::VariantId)
+            .column(entity::variants::Column::Name)
+            .column_as(
+                entity::stock_valuation_layers::Column::RemainingQuantity.sum(),
+                "quantity",
+            )
+            .column_as(
+                entity::stock_valuation_layers::Column::RemainingValue.sum(),
+                "value",
+            )
+            .join(
+                JoinType::InnerJoin,
+                entity::stock_valuation_layers::Relation::Variants.def(),
+            )
+            .filter(
+                Condition::any()
+                    .add(entity::stock_valuation_layers::Column::RemainingQuantity.gt(QUANTITY_TOLERANCE))
+                    .add(entity::stock_valuation_layers::Column::RemainingQuantity.lt(-QUANTITY_TOLERANCE)),
+            )
+            .group_by(entity::stock_valuation_layers::Column::VariantId)
+            .group_by(entity::variants::Column::Name)
+            .order_by_asc(entity::stock_valuation_layers::Column::VariantId)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(variant_id, name, quantity, value)| VariantValuation {
+                variant_id,
+                name,
+                quantity,
+                value,
+            })
+            .collect())
+    }
+));
@@ -1,18 +1,105 @@
+use std::collections::BTreeMap;
+
+use entity::{
+    prelude::{StockMoves, Stocks},
+    sea_orm_active_enums::StockKind,
+};
 use macros::make_repo;
-use sea_orm::DbErr;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DbErr, EntityTrait,
+    QueryFilter, QueryOrder, QuerySelect,
+};
+
+use crate::{models::Page, utils::locations::subtree};
+
+/// The quantity of a variant in a location and the locations below it, in
+/// the variant's unit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariantQuantity {
+    pub variant_id: i32,
+    pub quantity: f64,
+}
 
-use crate::models::Page;
+/// Returns the location and every location below it.
+pub(super) async fn subtree_ids<C: ConnectionTrait>(
+    db: &C,
+    stock_id: i32,
+) -> Result<Vec<i32>, DbErr> {
+    let stocks = Stocks::find().all(db).await?;
+    Ok(subtree(&stocks, stock_id))
+}
+
+/// Sums up the moves into and out of the locations per variant. Moves
+/// between the locations cancel out.
+pub(super) async fn balances<C: ConnectionTrait>(
+    db: &C,
+    stock_ids: &[i32],
+) -> Result<BTreeMap<i32, f64>, DbErr> {
+    let mut totals: BTreeMap<i32, f64> = BTreeMap::new();
+    for (column, sign) in [
+        (entity::stock_moves::Column::TargetStockId, 1.0),
+        (entity::stock_moves::Column::SourceStockId, -1.0),
+    ] {
+        let sums: Vec<(i32, f64)> = StockMoves::find()
+            .select_only()
+            .column(entity::stock_moves::Column::VariantId)
+            .column_as(entity::stock_moves::Column::Quantity.sum(), "quantity")
+            .filter(column.is_in(stock_ids.to_vec()))
+            .group_by(entity::stock_moves::Column::VariantId)
+            .into_tuple()
+            .all(db)
+            .await?;
+        for (variant_id, quantity) in sums {
+            *totals.entry(variant_id).or_default() += sign * quantity;
+        }
+    }
+    Ok(totals)
+}
 
 make_repo!(Stock, (
-    async fn create_own(&self, _name: String) -> Result<entity::stocks::Model, DbErr> {
-        todo!();
+    /// Creates an internal location of the company, optionally below
+    /// another, e.g. a bin in a zone of a warehouse.
+    async fn create_own(
+        &self,
+        name: String,
+        parent_id: Option<i32>,
+    ) -> Result<entity::stocks::Model, DbErr> {
+        let entity = entity::stocks::ActiveModel {
+            name: Set(name),
+            is_own: Set(true),
+            owner_id: Set(None),
+            kind: Set(StockKind::Internal),
+            parent_id: Set(parent_id),
+            consignment: Set(false),
+            ..Default::default()
+        };
+
+        entity.insert(&self.db).await
     }
+
+    /// Creates a stock of a customer or supplier. Goods on consignment at a
+    /// customer stay the company's until the customer reports consuming
+    /// them, so the stock counts as own. Goods a supplier keeps on
+    /// consignment at the company stay the supplier's, like those in any
+    /// other stock of a contact.
     async fn create_for_contact(
         &self,
-        _name: String,
-        _contact_id: i32,
+        name: String,
+        contact_id: i32,
+        kind: StockKind,
+        consignment: bool,
     ) -> Result<entity::stocks::Model, DbErr> {
-        todo!();
+        let entity = entity::stocks::ActiveModel {
+            name: Set(name),
+            is_own: Set(consignment && kind == StockKind::Customer),
+            owner_id: Set(Some(contact_id)),
+            kind: Set(kind),
+            parent_id: Set(None),
+            consignment: Set(consignment),
+            ..Default::default()
+        };
+
+        entity.insert(&self.db).await
     }
 
     async fn list_own(
@@ -32,8 +119,48 @@ make_repo!(Stock, (
         todo!();
     }
 
-    async fn get(&self, _id: i32) -> Result<Option<entity::stocks::Model>, DbErr> {
-        todo!();
+    /// Lists the virtual locations goods come from or go to when they
+    /// enter or leave the company without a stock of a contact.
+    async fn list_virtual(&self) -> Result<Vec<entity::stocks::Model>, DbErr> {
+        Stocks::find()
+            .filter(entity::stocks::Column::Kind.ne(StockKind::Internal))
+            .filter(entity::stocks::Column::OwnerId.is_null())
+            .order_by_asc(entity::stocks::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    /// Finds the first stock of the contact, e.g. the one drop-shipped goods
+    /// are delivered to or from. Consignment stocks are left out.
+    async fn find_for_contact(
+        &self,
+        contact_id: i32,
+    ) -> Result<Option<entity::stocks::Model>, DbErr> {
+        Stocks::find()
+            .filter(entity::stocks::Column::OwnerId.eq(contact_id))
+            .filter(entity::stocks::Column::Consignment.eq(false))
+            .order_by_asc(entity::stocks::Column::Id)
+            .one(&self.db)
+            .await
+    }
+
+    async fn get(&self, id: i32) -> Result<Option<entity::stocks::Model>, DbErr> {
+        Stocks::find_by_id(id).one(&self.db).await
+    }
+
+    /// Sums up what is in the location and the locations below it per
+    /// variant. Moves between locations of the subtree cancel out.
+    async fn quantities(&self, stock_id: i32) -> Result<Vec<VariantQuantity>, DbErr> {
+        let ids = subtree_ids(&self.db, stock_id).await?;
+        let totals = balances(&self.db, &ids).await?;
+
+        Ok(totals
+            .into_iter()
+            .map(|(variant_id, quantity)| VariantQuantity {
+                variant_id,
+                quantity,
+            })
+            .collect())
     }
 
     async fn delete(&self, _id: i32) -> Result<bool, DbErr> {
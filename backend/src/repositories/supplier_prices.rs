@@ -0,0 +1,101 @@
+use entity::prelude::SupplierPrices;
+use macros::make_repo;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    TransactionTrait, sea_query::Expr,
+};
+
+/// Returned as `DbErr::Custom` when the supplier already has an entry for
+/// the variant.
+pub const ERR_SUPPLIER_PRICE_EXISTS: &str = "the supplier already has a price for the variant";
+
+/// A supplier's catalog entry for a variant as it is added.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SupplierPriceDraft {
+    pub supplier_sku: String,
+    pub description: String,
+    pub price: i32,
+    pub min_quantity: f64,
+    pub pack_size: f64,
+    pub lead_time_days: i32,
+    pub preferred: bool,
+}
+
+make_repo!(SupplierPrice, (
+    /// Adds the entry. A preferred entry takes the preference from the other
+    /// suppliers of the variant.
+    async fn create(
+        &self,
+        variant_id: i32,
+        supplier_id: i32,
+        entry: SupplierPriceDraft,
+    ) -> Result<entity::supplier_prices::Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let existing = SupplierPrices::find()
+            .filter(entity::supplier_prices::Column::VariantId.eq(variant_id))
+            .filter(entity::supplier_prices::Column::SupplierId.eq(supplier_id))
+            .one(&txn)
+            .await?;
+        if existing.is_some() {
+            return Err(DbErr::Custom(ERR_SUPPLIER_PRICE_EXISTS.to_string()));
+        }
+
+        if entry.preferred {
+            SupplierPrices::update_many()
+                .col_expr(entity::supplier_prices::Column::Preferred, Expr::value(false))
+                .filter(entity::supplier_prices::Column::VariantId.eq(variant_id))
+                .exec(&txn)
+                .await?;
+        }
+
+        let created = entity::supplier_prices::ActiveModel {
+            variant_id: Set(variant_id),
+            supplier_id: Set(supplier_id),
+            supplier_sku: Set(entry.supplier_sku),
+            description: Set(entry.description),
+            price: Set(entry.price),
+            min_quantity: Set(entry.min_quantity),
+            pack_size: Set(entry.pack_size),
+            lead_time_days: Set(entry.lead_time_days),
+            preferred: Set(entry.preferred),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        txn.commit().await?;
+        Ok(created)
+    }
+
+    /// Lists the suppliers' entries for the variant, the preferred one first.
+    async fn list_for_variant(
+        &self,
+        variant_id: i32,
+    ) -> Result<Vec<entity::supplier_prices::Model>, DbErr> {
+        SupplierPrices::find()
+            .filter(entity::supplier_prices::Column::VariantId.eq(variant_id))
+            .order_by_desc(entity::supplier_prices::Column::Preferred)
+            .order_by_asc(entity::supplier_prices::Column::Price)
+            .order_by_asc(entity::supplier_prices::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    async fn get_for_supplier(
+        &self,
+        variant_id: i32,
+        supplier_id: i32,
+    ) -> Result<Option<entity::supplier_prices::Model>, DbErr> {
+        SupplierPrices::find()
+            .filter(entity::supplier_prices::Column::VariantId.eq(variant_id))
+            .filter(entity::supplier_prices::Column::SupplierId.eq(supplier_id))
+            .one(&self.db)
+            .await
+    }
+
+    async fn delete(&self, id: i32) -> Result<bool, DbErr> {
+        let result = SupplierPrices::delete_by_id(id).exec(&self.db).await?;
+        Ok(result.rows_affected > 0)
+    }
+));
@@ -0,0 +1,70 @@
+use entity::{
+    prelude::TaxRules,
+    sea_orm_active_enums::{TaxDirection, TaxRegion},
+};
+use macros::make_repo;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DbErr, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect,
+};
+
+use crate::models::Page;
+
+make_repo!(TaxRule, (
+    async fn create(
+        &self,
+        direction: TaxDirection,
+        region: TaxRegion,
+        vat_id: Option<bool>,
+        product_tax_category: Option<String>,
+        tax_id: i32,
+    ) -> Result<entity::tax_rules::Model, DbErr> {
+        let entity = entity::tax_rules::ActiveModel {
+            direction: Set(direction),
+            region: Set(region),
+            vat_id: Set(vat_id),
+            product_tax_category: Set(product_tax_category),
+            tax_id: Set(tax_id),
+            ..Default::default()
+        };
+
+        entity.insert(&self.db).await
+    }
+
+    async fn list(&self, limit: u64, offset: u64) -> Result<Page<entity::tax_rules::Model>, DbErr> {
+        let total = TaxRules::find().count(&self.db).await?;
+
+        let data = TaxRules::find()
+            .order_by_asc(entity::tax_rules::Column::Id)
+            .limit(limit)
+            .offset(offset)
+            .all(&self.db)
+            .await?;
+
+        Ok(Page {
+            limit,
+            offset,
+            total,
+            data,
+        })
+    }
+
+    /// Lists the rules the determination chooses from for a line.
+    async fn list_for_region(
+        &self,
+        direction: TaxDirection,
+        region: TaxRegion,
+    ) -> Result<Vec<entity::tax_rules::Model>, DbErr> {
+        TaxRules::find()
+            .filter(entity::tax_rules::Column::Direction.eq(direction))
+            .filter(entity::tax_rules::Column::Region.eq(region))
+            .order_by_asc(entity::tax_rules::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    async fn delete(&self, id: i32) -> Result<bool, DbErr> {
+        let result = TaxRules::delete_by_id(id).exec(&self.db).await?;
+        Ok(result.rows_affected > 0)
+    }
+));
@@ -1,25 +1,280 @@
+use chrono::NaiveDate;
+use entity::prelude::{TaxComponents, TaxRates, Taxes};
 use macros::make_repo;
-use sea_orm::DbErr;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, DbErr, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, TransactionTrait,
+};
 
 use crate::models::Page;
 
+/// Returned as `DbErr::Custom` when a new rate version overlaps the validity
+/// of an existing one of the same tax.
+pub const ERR_TAX_RATE_OVERLAP: &str = "the rate version overlaps an existing one";
+
+/// Returned as `DbErr::Custom` when a tax group would contain another group
+/// or become a component itself.
+pub const ERR_NESTED_TAX_GROUP: &str = "tax groups can't be nested";
+
+/// A levy of a line's tax with the rate that applied on the document date.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaxComponent {
+    pub tax: entity::taxes::Model,
+    /// Levied on the net amount plus the tax of the components before.
+    pub compound: bool,
+}
+
+/// A tax as the lines of a document apply it. A plain tax is its own only
+/// component, a tax group levies its components in sequence.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResolvedTax {
+    pub tax: entity::taxes::Model,
+    pub components: Vec<TaxComponent>,
+}
+
+impl ResolvedTax {
+    pub fn plain(tax: entity::taxes::Model) -> Self {
+        let components = vec![TaxComponent {
+            tax: tax.clone(),
+            compound: false,
+        }];
+        Self { tax, components }
+    }
+}
+
 make_repo!(Tax, (
     async fn create(
         &self,
-        _name: String,
-        _name_short: String,
-        _rate: f64,
-        _account_id: i32,
+        name: String,
+        name_short: String,
+        rate: f64,
+        account_id: i32,
+        category: String,
+        note: String,
     ) -> Result<entity::taxes::Model, DbErr> {
-        todo!();
+        let entity = entity::taxes::ActiveModel {
+            name: Set(name),
+            name_short: Set(name_short),
+            rate: Set(rate),
+            account_id: Set(account_id),
+            category: Set(category),
+            note: Set(note),
+            ..Default::default()
+        };
+
+        entity.insert(&self.db).await
     }
 
     async fn list(&self, _limit: u64, _offset: u64) -> Result<Page<entity::taxes::Model>, DbErr> {
         todo!();
     }
 
-    async fn get(&self, _id: i32) -> Result<Option<entity::taxes::Model>, DbErr> {
-        todo!();
+    async fn get(&self, id: i32) -> Result<Option<entity::taxes::Model>, DbErr> {
+        Taxes::find_by_id(id).one(&self.db).await
+    }
+
+    /// Gets the tax with the rate that applied on the date. Documents resolve
+    /// their taxes by their own date, so a later rate change doesn't alter
+    /// old invoices.
+    async fn get_at(
+        &self,
+        id: i32,
+        date: NaiveDate,
+    ) -> Result<Option<entity::taxes::Model>, DbErr> {
+        let Some(mut tax) = Taxes::find_by_id(id).one(&self.db).await? else {
+            return Ok(None);
+        };
+
+        let version = TaxRates::find()
+            .filter(entity::tax_rates::Column::TaxId.eq(id))
+            .filter(entity::tax_rates::Column::ValidFrom.lte(date))
+            .filter(
+                Condition::any()
+                    .add(entity::tax_rates::Column::ValidTo.is_null())
+                    .add(entity::tax_rates::Column::ValidTo.gte(date)),
+            )
+            .one(&self.db)
+            .await?;
+        if let Some(version) = version {
+            tax.rate = version.rate;
+        }
+
+        Ok(Some(tax))
+    }
+
+    /// Gets the tax with its components as they applied on the date.
+    async fn resolve_at(&self, id: i32, date: NaiveDate) -> Result<Option<ResolvedTax>, DbErr> {
+        let Some(tax) = self.get_at(id, date).await? else {
+            return Ok(None);
+        };
+
+        let members = self.list_components(id).await?;
+        if members.is_empty() {
+            return Ok(Some(ResolvedTax::plain(tax)));
+        }
+
+        let mut components = Vec::with_capacity(members.len());
+        for member in members {
+            // Components can't be deleted while they belong to a group.
+            let component = self
+                .get_at(member.tax_id, date)
+                .await?
+                .ok_or(DbErr::RecordNotFound("tax".to_string()))?;
+            components.push(TaxComponent {
+                tax: component,
+                compound: member.compound,
+            });
+        }
+
+        Ok(Some(ResolvedTax { tax, components }))
+    }
+
+    async fn list_components(
+        &self,
+        group_id: i32,
+    ) -> Result<Vec<entity::tax_components::Model>, DbErr> {
+        TaxComponents::find()
+            .filter(entity::tax_components::Column::GroupId.eq(group_id))
+            .order_by_asc(entity::tax_components::Column::Sequence)
+            .all(&self.db)
+            .await
+    }
+
+    /// Replaces the components of the group, given as tax id and compound
+    /// flag in the order they are levied. An empty list turns the group back
+    /// into a plain tax.
+    async fn set_components(
+        &self,
+        group_id: i32,
+        components: Vec<(i32, bool)>,
+    ) -> Result<Vec<entity::tax_components::Model>, DbErr> {
+        let txn = self.db.begin().await?;
+
+        Taxes::find_by_id(group_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(DbErr::RecordNotFound("tax".to_string()))?;
+
+        let is_component = TaxComponents::find()
+            .filter(entity::tax_components::Column::TaxId.eq(group_id))
+            .one(&txn)
+            .await?;
+        if is_component.is_some() && !components.is_empty() {
+            return Err(DbErr::Custom(ERR_NESTED_TAX_GROUP.to_string()));
+        }
+
+        for (tax_id, _) in &components {
+            Taxes::find_by_id(*tax_id)
+                .one(&txn)
+                .await?
+                .ok_or(DbErr::RecordNotFound("tax".to_string()))?;
+
+            let is_group = TaxComponents::find()
+                .filter(entity::tax_components::Column::GroupId.eq(*tax_id))
+                .one(&txn)
+                .await?;
+            if is_group.is_some() {
+                return Err(DbErr::Custom(ERR_NESTED_TAX_GROUP.to_string()));
+            }
+        }
+
+        TaxComponents::delete_many()
+            .filter(entity::tax_components::Column::GroupId.eq(group_id))
+            .exec(&txn)
+            .await?;
+
+        let mut created = Vec::with_capacity(components.len());
+        for (sequence, (tax_id, compound)) in components.into_iter().enumerate() {
+            let component = entity::tax_components::ActiveModel {
+                group_id: Set(group_id),
+                tax_id: Set(tax_id),
+                sequence: Set(sequence as i32 + 1),
+                compound: Set(compound),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?;
+            created.push(component);
+        }
+
+        txn.commit().await?;
+        Ok(created)
+    }
+
+    async fn list_rates(&self, tax_id: i32) -> Result<Vec<entity::tax_rates::Model>, DbErr> {
+        TaxRates::find()
+            .filter(entity::tax_rates::Column::TaxId.eq(tax_id))
+            .order_by_asc(entity::tax_rates::Column::ValidFrom)
+            .all(&self.db)
+            .await
+    }
+
+    /// Adds a rate version. An open-ended version that starts earlier is
+    /// ended the day before the new one starts. The tax row is locked, so
+    /// concurrent versions can't slip past the overlap check.
+    async fn create_rate(
+        &self,
+        tax_id: i32,
+        rate: f64,
+        valid_from: NaiveDate,
+        valid_to: Option<NaiveDate>,
+    ) -> Result<entity::tax_rates::Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        Taxes::find_by_id(tax_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(DbErr::RecordNotFound("tax".to_string()))?;
+
+        let predecessor = TaxRates::find()
+            .filter(entity::tax_rates::Column::TaxId.eq(tax_id))
+            .filter(entity::tax_rates::Column::ValidTo.is_null())
+            .filter(entity::tax_rates::Column::ValidFrom.lt(valid_from))
+            .one(&txn)
+            .await?;
+        if let Some(predecessor) = predecessor {
+            let mut predecessor: entity::tax_rates::ActiveModel = predecessor.into();
+            predecessor.valid_to = Set(valid_from.pred_opt());
+            predecessor.update(&txn).await?;
+        }
+
+        let mut overlap = TaxRates::find()
+            .filter(entity::tax_rates::Column::TaxId.eq(tax_id))
+            .filter(
+                Condition::any()
+                    .add(entity::tax_rates::Column::ValidTo.is_null())
+                    .add(entity::tax_rates::Column::ValidTo.gte(valid_from)),
+            );
+        if let Some(valid_to) = valid_to {
+            overlap = overlap.filter(entity::tax_rates::Column::ValidFrom.lte(valid_to));
+        }
+        if overlap.one(&txn).await?.is_some() {
+            return Err(DbErr::Custom(ERR_TAX_RATE_OVERLAP.to_string()));
+        }
+
+        let version = entity::tax_rates::ActiveModel {
+            tax_id: Set(tax_id),
+            rate: Set(rate),
+            valid_from: Set(valid_from),
+            valid_to: Set(valid_to),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        txn.commit().await?;
+        Ok(version)
+    }
+
+    /// Finds all taxes with the given rate. The rate is a fraction and is
+    /// compared with a small tolerance, because it is stored as `f64`.
+    async fn find_by_rate(&self, rate: f64) -> Result<Vec<entity::taxes::Model>, DbErr> {
+        Taxes::find()
+            .filter(entity::taxes::Column::Rate.between(rate - 0.00001, rate + 0.00001))
+            .all(&self.db)
+            .await
     }
 
     async fn delete(&self, _id: i32) -> Result<bool, DbErr> {
@@ -0,0 +1,52 @@
+use entity::prelude::Units;
+use macros::make_repo;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+};
+
+/// Returned as `DbErr::Custom` when a unit with the name already exists.
+pub const ERR_UNIT_EXISTS: &str = "a unit with this name already exists";
+
+make_repo!(Unit, (
+    async fn create(
+        &self,
+        name: String,
+        symbol: String,
+        base_unit_id: Option<i32>,
+        factor: f64,
+    ) -> Result<entity::units::Model, DbErr> {
+        let existing = Units::find()
+            .filter(entity::units::Column::Name.eq(name.clone()))
+            .one(&self.db)
+            .await?;
+        if existing.is_some() {
+            return Err(DbErr::Custom(ERR_UNIT_EXISTS.to_string()));
+        }
+
+        let entity = entity::units::ActiveModel {
+            name: Set(name),
+            symbol: Set(symbol),
+            base_unit_id: Set(base_unit_id),
+            factor: Set(factor),
+            ..Default::default()
+        };
+
+        entity.insert(&self.db).await
+    }
+
+    async fn list(&self) -> Result<Vec<entity::units::Model>, DbErr> {
+        Units::find()
+            .order_by_asc(entity::units::Column::Name)
+            .all(&self.db)
+            .await
+    }
+
+    async fn get(&self, id: i32) -> Result<Option<entity::units::Model>, DbErr> {
+        Units::find_by_id(id).one(&self.db).await
+    }
+
+    async fn delete(&self, id: i32) -> Result<bool, DbErr> {
+        let result = Units::delete_by_id(id).exec(&self.db).await?;
+        Ok(result.rows_affected > 0)
+    }
+));
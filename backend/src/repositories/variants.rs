@@ -1,17 +1,78 @@
+use std::collections::{HashMap, HashSet};
+
+use entity::{
+    prelude::{Products, VariantAttributeValues, Variants},
+    sea_orm_active_enums::{ProductType, Tracking},
+};
 use macros::make_repo;
-use sea_orm::DbErr;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, DbErr, EntityTrait, JoinType,
+    QueryFilter, QuerySelect, RelationTrait, TransactionTrait,
+};
 
 use crate::models::Page;
 
+/// Returned as `DbErr::Custom` when another variant already has the SKU or
+/// GTIN as either of its codes.
+pub const ERR_VARIANT_CODE_EXISTS: &str = "another variant already has this code";
+
+/// A variant as it is created.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariantDraft {
+    pub name: String,
+    pub sale_price: i32,
+    pub purchase_price: i32,
+    pub sku: Option<String>,
+    pub gtin: Option<String>,
+    /// The unit its stock is kept in.
+    pub unit_id: Option<i32>,
+}
+
+/// A variant generated from a combination of attribute values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeneratedVariant {
+    pub name: String,
+    pub sale_price: i32,
+    pub purchase_price: i32,
+    /// One value of each attribute of the product.
+    pub value_ids: Vec<i32>,
+}
+
 make_repo!(Variant, (
+    /// Creates a variant. Its SKU and GTIN must not be either code of
+    /// another variant, so scanning a code finds exactly one variant.
     async fn create(
         &self,
-        _product_id: i32,
-        _name: String,
-        _sale_price: i32,
-        _purchase_price: i32,
+        product_id: i32,
+        variant: VariantDraft,
     ) -> Result<entity::variants::Model, DbErr> {
-        todo!();
+        let codes: Vec<&String> = [&variant.sku, &variant.gtin].into_iter().flatten().collect();
+        if !codes.is_empty() {
+            let existing = Variants::find()
+                .filter(
+                    Condition::any()
+                        .add(entity::variants::Column::Sku.is_in(codes.clone()))
+                        .add(entity::variants::Column::Gtin.is_in(codes)),
+                )
+                .one(&self.db)
+                .await?;
+            if existing.is_some() {
+                return Err(DbErr::Custom(ERR_VARIANT_CODE_EXISTS.to_string()));
+            }
+        }
+
+        let entity = entity::variants::ActiveModel {
+            name: Set(variant.name),
+            sale_price: Set(variant.sale_price),
+            purchase_price: Set(variant.purchase_price),
+            product_id: Set(product_id),
+            sku: Set(variant.sku),
+            gtin: Set(variant.gtin),
+            unit_id: Set(variant.unit_id),
+            ..Default::default()
+        };
+
+        entity.insert(&self.db).await
     }
 
     async fn list(
@@ -23,11 +84,123 @@ make_repo!(Variant, (
         todo!();
     }
 
-    async fn get(&self, _id: i32) -> Result<Option<entity::variants::Model>, DbErr> {
-        todo!();
+    async fn get(&self, id: i32) -> Result<Option<entity::variants::Model>, DbErr> {
+        Variants::find_by_id(id).one(&self.db).await
+    }
+
+    /// Finds the variant with the SKU or GTIN.
+    async fn find_by_code(&self, code: String) -> Result<Option<entity::variants::Model>, DbErr> {
+        Variants::find()
+            .filter(
+                Condition::any()
+                    .add(entity::variants::Column::Sku.eq(code.clone()))
+                    .add(entity::variants::Column::Gtin.eq(code)),
+            )
+            .one(&self.db)
+            .await
     }
 
     async fn delete(&self, _id: i32) -> Result<bool, DbErr> {
         todo!();
     }
+
+    /// Picks the variants of stockable products out of the given ones.
+    /// Only those are kept in stock; consumables and services are not.
+    async fn stockable_ids(&self, ids: Vec<i32>) -> Result<Vec<i32>, DbErr> {
+        Variants::find()
+            .select_only()
+            .column(entity::variants::Column::Id)
+            .join(JoinType::InnerJoin, entity::variants::Relation::Products.def())
+            .filter(entity::variants::Column::Id.is_in(ids))
+            .filter(entity::products::Column::ProductType.eq(ProductType::Stockable))
+            .into_tuple()
+            .all(&self.db)
+            .await
+    }
+
+    /// Returns how the products of the variants are tracked, by variant id.
+    async fn tracking(&self, ids: Vec<i32>) -> Result<HashMap<i32, Tracking>, DbErr> {
+        let rows: Vec<(i32, Tracking)> = Variants::find()
+            .select_only()
+            .column(entity::variants::Column::Id)
+            .column(entity::products::Column::Tracking)
+            .join(JoinType::InnerJoin, entity::variants::Relation::Products.def())
+            .filter(entity::variants::Column::Id.is_in(ids))
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        Ok(rows.into_iter().collect())
+    }
+
+    /// Creates the variants whose combination of values the product doesn't
+    /// have a variant for yet and returns them. The product row is locked,
+    /// so generating twice at once can't create a combination twice.
+    async fn generate(
+        &self,
+        product_id: i32,
+        variants: Vec<GeneratedVariant>,
+    ) -> Result<Vec<entity::variants::Model>, DbErr> {
+        let txn = self.db.begin().await?;
+
+        Products::find_by_id(product_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(DbErr::RecordNotFound("product".to_string()))?;
+
+        let links = VariantAttributeValues::find()
+            .join(
+                JoinType::InnerJoin,
+                entity::variant_attribute_values::Relation::Variants.def(),
+            )
+            .filter(entity::variants::Column::ProductId.eq(product_id))
+            .all(&txn)
+            .await?;
+
+        let mut existing: HashMap<i32, Vec<i32>> = HashMap::new();
+        for link in links {
+            existing.entry(link.variant_id).or_default().push(link.value_id);
+        }
+        let mut taken: HashSet<Vec<i32>> = existing
+            .into_values()
+            .map(|mut value_ids| {
+                value_ids.sort_unstable();
+                value_ids
+            })
+            .collect();
+
+        let mut created = Vec::new();
+        for variant in variants {
+            let mut combination = variant.value_ids.clone();
+            combination.sort_unstable();
+            if !taken.insert(combination) {
+                continue;
+            }
+
+            let entity = entity::variants::ActiveModel {
+                name: Set(variant.name),
+                sale_price: Set(variant.sale_price),
+                purchase_price: Set(variant.purchase_price),
+                product_id: Set(product_id),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?;
+
+            for value_id in variant.value_ids {
+                entity::variant_attribute_values::ActiveModel {
+                    variant_id: Set(entity.id),
+                    value_id: Set(value_id),
+                    ..Default::default()
+                }
+                .insert(&txn)
+                .await?;
+            }
+            created.push(entity);
+        }
+
+        txn.commit().await?;
+        Ok(created)
+    }
 ));
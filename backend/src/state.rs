@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use sea_orm::DatabaseConnection;
 
-use crate::repositories::*;
+use crate::{documents::Letterhead, repositories::*};
 
 #[cfg(test)]
 use crate::repositories::{
@@ -11,27 +11,73 @@ use crate::repositories::{
 
 #[derive(Clone)]
 pub struct AppState {
+    pub letterhead: Arc<Letterhead>,
     pub contacts: Arc<dyn ContactRepo>,
     pub ledger_accounts: Arc<dyn LedgerAccountRepo>,
     pub ledger_transactions: Arc<dyn LedgerTransactionRepo>,
+    pub number_sequences: Arc<dyn NumberSequenceRepo>,
+    pub price_lists: Arc<dyn PriceListRepo>,
+    pub product_attributes: Arc<dyn ProductAttributeRepo>,
+    pub stock_valuation_layers: Arc<dyn StockValuationLayerRepo>,
+    pub stock_reservations: Arc<dyn StockReservationRepo>,
+    pub lots: Arc<dyn LotRepo>,
+    pub inventory_counts: Arc<dyn InventoryCountRepo>,
+    pub reorder_rules: Arc<dyn ReorderRuleRepo>,
+    pub product_categories: Arc<dyn ProductCategoryRepo>,
     pub products: Arc<dyn ProductRepo>,
+    pub purchase_credit_notes: Arc<dyn PurchaseCreditNoteRepo>,
+    pub purchase_invoice_lines: Arc<dyn PurchaseInvoiceLineRepo>,
+    pub purchase_invoices: Arc<dyn PurchaseInvoiceRepo>,
+    pub purchase_order_lines: Arc<dyn PurchaseOrderLineRepo>,
+    pub purchase_orders: Arc<dyn PurchaseOrderRepo>,
+    pub sale_credit_notes: Arc<dyn SaleCreditNoteRepo>,
+    pub sale_invoice_lines: Arc<dyn SaleInvoiceLineRepo>,
+    pub sale_invoices: Arc<dyn SaleInvoiceRepo>,
+    pub sale_order_lines: Arc<dyn SaleOrderLineRepo>,
+    pub sale_orders: Arc<dyn SaleOrderRepo>,
     pub stocks: Arc<dyn StockRepo>,
     pub stock_moves: Arc<dyn StockMoveRepo>,
+    pub supplier_prices: Arc<dyn SupplierPriceRepo>,
+    pub tax_rules: Arc<dyn TaxRuleRepo>,
     pub taxes: Arc<dyn TaxRepo>,
+    pub units: Arc<dyn UnitRepo>,
     pub users: Arc<dyn UserRepo>,
     pub variants: Arc<dyn VariantRepo>,
 }
 
 impl AppState {
-    pub fn new(db: DatabaseConnection) -> Self {
+    pub fn new(db: DatabaseConnection, letterhead: Letterhead) -> Self {
         Self {
+            letterhead: Arc::new(letterhead),
             contacts: Arc::new(ContactRepoImpl::new(db.clone())),
             ledger_accounts: Arc::new(LedgerAccountRepoImpl::new(db.clone())),
             ledger_transactions: Arc::new(LedgerTransactionRepoImpl::new(db.clone())),
+            number_sequences: Arc::new(NumberSequenceRepoImpl::new(db.clone())),
+            price_lists: Arc::new(PriceListRepoImpl::new(db.clone())),
+            product_attributes: Arc::new(ProductAttributeRepoImpl::new(db.clone())),
+            stock_valuation_layers: Arc::new(StockValuationLayerRepoImpl::new(db.clone())),
+            stock_reservations: Arc::new(StockReservationRepoImpl::new(db.clone())),
+            lots: Arc::new(LotRepoImpl::new(db.clone())),
+            inventory_counts: Arc::new(InventoryCountRepoImpl::new(db.clone())),
+            reorder_rules: Arc::new(ReorderRuleRepoImpl::new(db.clone())),
+            product_categories: Arc::new(ProductCategoryRepoImpl::new(db.clone())),
             products: Arc::new(ProductRepoImpl::new(db.clone())),
+            purchase_credit_notes: Arc::new(PurchaseCreditNoteRepoImpl::new(db.clone())),
+            purchase_invoice_lines: Arc::new(PurchaseInvoiceLineRepoImpl::new(db.clone())),
+            purchase_invoices: Arc::new(PurchaseInvoiceRepoImpl::new(db.clone())),
+            purchase_order_lines: Arc::new(PurchaseOrderLineRepoImpl::new(db.clone())),
+            purchase_orders: Arc::new(PurchaseOrderRepoImpl::new(db.clone())),
+            sale_credit_notes: Arc::new(SaleCreditNoteRepoImpl::new(db.clone())),
+            sale_invoice_lines: Arc::new(SaleInvoiceLineRepoImpl::new(db.clone())),
+            sale_invoices: Arc::new(SaleInvoiceRepoImpl::new(db.clone())),
+            sale_order_lines: Arc::new(SaleOrderLineRepoImpl::new(db.clone())),
+            sale_orders: Arc::new(SaleOrderRepoImpl::new(db.clone())),
             stocks: Arc::new(StockRepoImpl::new(db.clone())),
             stock_moves: Arc::new(StockMoveRepoImpl::new(db.clone())),
+            supplier_prices: Arc::new(SupplierPriceRepoImpl::new(db.clone())),
+            tax_rules: Arc::new(TaxRuleRepoImpl::new(db.clone())),
             taxes: Arc::new(TaxRepoImpl::new(db.clone())),
+            units: Arc::new(UnitRepoImpl::new(db.clone())),
             users: Arc::new(UserRepoImpl::new(db.clone())),
             variants: Arc::new(VariantRepoImpl::new(db.clone())),
         }
@@ -40,13 +86,36 @@ impl AppState {
 
 #[cfg(test)]
 pub struct MockAppState {
+    pub letterhead: Letterhead,
     pub contacts: MockContactRepo,
     pub ledger_accounts: MockLedgerAccountRepo,
     pub ledger_transactions: MockLedgerTransactionRepo,
+    pub number_sequences: MockNumberSequenceRepo,
+    pub price_lists: MockPriceListRepo,
+    pub product_attributes: MockProductAttributeRepo,
+    pub stock_valuation_layers: MockStockValuationLayerRepo,
+    pub stock_reservations: MockStockReservationRepo,
+    pub lots: MockLotRepo,
+    pub inventory_counts: MockInventoryCountRepo,
+    pub reorder_rules: MockReorderRuleRepo,
+    pub product_categories: MockProductCategoryRepo,
     pub products: MockProductRepo,
+    pub purchase_credit_notes: MockPurchaseCreditNoteRepo,
+    pub purchase_invoice_lines: MockPurchaseInvoiceLineRepo,
+    pub purchase_invoices: MockPurchaseInvoiceRepo,
+    pub purchase_order_lines: MockPurchaseOrderLineRepo,
+    pub purchase_orders: MockPurchaseOrderRepo,
+    pub sale_credit_notes: MockSaleCreditNoteRepo,
+    pub sale_invoice_lines: MockSaleInvoiceLineRepo,
+    pub sale_invoices: MockSaleInvoiceRepo,
+    pub sale_order_lines: MockSaleOrderLineRepo,
+    pub sale_orders: MockSaleOrderRepo,
     pub stocks: MockStockRepo,
     pub stock_moves: MockStockMoveRepo,
+    pub supplier_prices: MockSupplierPriceRepo,
+    pub tax_rules: MockTaxRuleRepo,
     pub taxes: MockTaxRepo,
+    pub units: MockUnitRepo,
     pub users: MockUserRepo,
     pub variants: MockVariantRepo,
 }
@@ -55,13 +124,36 @@ pub struct MockAppState {
 impl From<MockAppState> for AppState {
     fn from(value: MockAppState) -> Self {
         AppState {
+            letterhead: Arc::new(value.letterhead),
             contacts: Arc::new(value.contacts),
             ledger_accounts: Arc::new(value.ledger_accounts),
             ledger_transactions: Arc::new(value.ledger_transactions),
+            number_sequences: Arc::new(value.number_sequences),
+            price_lists: Arc::new(value.price_lists),
+            product_attributes: Arc::new(value.product_attributes),
+            stock_valuation_layers: Arc::new(value.stock_valuation_layers),
+            stock_reservations: Arc::new(value.stock_reservations),
+            lots: Arc::new(value.lots),
+            inventory_counts: Arc::new(value.inventory_counts),
+            reorder_rules: Arc::new(value.reorder_rules),
+            product_categories: Arc::new(value.product_categories),
             products: Arc::new(value.products),
+            purchase_credit_notes: Arc::new(value.purchase_credit_notes),
+            purchase_invoice_lines: Arc::new(value.purchase_invoice_lines),
+            purchase_invoices: Arc::new(value.purchase_invoices),
+            purchase_order_lines: Arc::new(value.purchase_order_lines),
+            purchase_orders: Arc::new(value.purchase_orders),
+            sale_credit_notes: Arc::new(value.sale_credit_notes),
+            sale_invoice_lines: Arc::new(value.sale_invoice_lines),
+            sale_invoices: Arc::new(value.sale_invoices),
+            sale_order_lines: Arc::new(value.sale_order_lines),
+            sale_orders: Arc::new(value.sale_orders),
             stocks: Arc::new(value.stocks),
             stock_moves: Arc::new(value.stock_moves),
+            supplier_prices: Arc::new(value.supplier_prices),
+            tax_rules: Arc::new(value.tax_rules),
             taxes: Arc::new(value.taxes),
+            units: Arc::new(value.units),
             users: Arc::new(value.users),
             variants: Arc::new(value.variants),
         }
@@ -72,13 +164,36 @@ impl From<MockAppState> for AppState {
 impl MockAppState {
     pub fn new() -> Self {
         Self {
+            letterhead: Letterhead::default(),
             contacts: MockContactRepo::new(),
             ledger_accounts: MockLedgerAccountRepo::new(),
             ledger_transactions: MockLedgerTransactionRepo::new(),
+            number_sequences: MockNumberSequenceRepo::new(),
+            price_lists: MockPriceListRepo::new(),
+            product_attributes: MockProductAttributeRepo::new(),
+            stock_valuation_layers: MockStockValuationLayerRepo::new(),
+            stock_reservations: MockStockReservationRepo::new(),
+            lots: MockLotRepo::new(),
+            inventory_counts: MockInventoryCountRepo::new(),
+            reorder_rules: MockReorderRuleRepo::new(),
+            product_categories: MockProductCategoryRepo::new(),
             products: MockProductRepo::new(),
+            purchase_credit_notes: MockPurchaseCreditNoteRepo::new(),
+            purchase_invoice_lines: MockPurchaseInvoiceLineRepo::new(),
+            purchase_invoices: MockPurchaseInvoiceRepo::new(),
+            purchase_order_lines: MockPurchaseOrderLineRepo::new(),
+            purchase_orders: MockPurchaseOrderRepo::new(),
+            sale_credit_notes: MockSaleCreditNoteRepo::new(),
+            sale_invoice_lines: MockSaleInvoiceLineRepo::new(),
+            sale_invoices: MockSaleInvoiceRepo::new(),
+            sale_order_lines: MockSaleOrderLineRepo::new(),
+            sale_orders: MockSaleOrderRepo::new(),
             stocks: MockStockRepo::new(),
             stock_moves: MockStockMoveRepo::new(),
+            supplier_prices: MockSupplierPriceRepo::new(),
+            tax_rules: MockTaxRuleRepo::new(),
             taxes: MockTaxRepo::new(),
+            units: MockUnitRepo::new(),
             users: MockUserRepo::new(),
             variants: MockVariantRepo::new(),
         }
@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use crate::{
+    repositories::StockAvailability,
+    utils::availability::{Shortage, available_by_variant, shortages},
+};
+
+#[test]
+fn test_covered() {
+    let available = HashMap::from([(8, 5.0)]);
+    assert!(shortages(&[(8, 2.0), (8, 3.0)], &available).is_empty());
+}
+
+#[test]
+fn test_lines_add_up() {
+    let available = HashMap::from([(8, 4.0), (9, 10.0)]);
+    assert_eq!(
+        shortages(&[(8, 2.0), (9, 1.0), (8, 3.0)], &available),
+        vec![Shortage {
+            variant_id: 8,
+            requested: 5.0,
+            available: 4.0,
+        }]
+    );
+}
+
+#[test]
+fn test_not_in_stock() {
+    assert_eq!(
+        shortages(&[(8, 1.0)], &HashMap::new()),
+        vec![Shortage {
+            variant_id: 8,
+            requested: 1.0,
+            available: 0.0,
+        }]
+    );
+}
+
+#[test]
+fn test_oversold() {
+    let available = HashMap::from([(8, -2.0)]);
+    assert_eq!(shortages(&[(8, 1.0)], &available)[0].available, -2.0);
+}
+
+#[test]
+fn test_available_below_stock() {
+    // The warehouse itself holds 2 mugs and one of its bins 4, of which 1
+    // is reserved.
+    let availability = [
+        StockAvailability {
+            stock_id: 2,
+            variant_id: 8,
+            on_hand: 2.0,
+            reserved: 0.0,
+        },
+        StockAvailability {
+            stock_id: 6,
+            variant_id: 8,
+            on_hand: 4.0,
+            reserved: 1.0,
+        },
+    ];
+    let available = available_by_variant(&availability);
+    assert_eq!(available, HashMap::from([(8, 5.0)]));
+    assert!(shortages(&[(8, 2.0), (8, 3.0)], &available).is_empty());
+}
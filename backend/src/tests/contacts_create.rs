@@ -5,9 +5,23 @@ use serde_json::json;
 
 use crate::{
     handlers,
+    repositories::ContactDraft,
     state::{AppState, MockAppState},
 };
 
+fn draft() -> ContactDraft {
+    ContactDraft {
+        name: "Example Inc.".to_string(),
+        account_id: 5,
+        street1: "street1".to_string(),
+        street2: "street2".to_string(),
+        postal_code: "postalCode".to_string(),
+        city: "city".to_string(),
+        country: "country".to_string(),
+        currency: "EUR".to_string(),
+    }
+}
+
 #[actix_web::test]
 async fn test_customer_success() {
     let mut state = MockAppState::new();
@@ -27,32 +41,24 @@ async fn test_customer_success() {
         .contacts
         .expect_create_customer()
         .once()
-        .with(
-            predicate::eq("Example Inc.".to_string()),
-            predicate::eq(5),
-            predicate::eq("street1".to_string()),
-            predicate::eq("street2".to_string()),
-            predicate::eq("postalCode".to_string()),
-            predicate::eq("city".to_string()),
-            predicate::eq("country".to_string()),
-        )
-        .returning(
-            |name, account_id, street1, street2, postal_code, city, country| {
-                Ok(entity::contacts::Model {
-                    id: 1,
-                    name,
-                    is_customer: true,
-                    is_supplier: false,
-                    street1,
-                    street2,
-                    postal_code,
-                    city,
-                    country,
-                    customer_account_id: Some(account_id),
-                    supplier_account_id: None,
-                })
-            },
-        );
+        .with(predicate::eq(draft()))
+        .returning(|contact| {
+            Ok(entity::contacts::Model {
+                id: 1,
+                name: contact.name,
+                is_customer: true,
+                is_supplier: false,
+                street1: contact.street1,
+                street2: contact.street2,
+                postal_code: contact.postal_code,
+                city: contact.city,
+                country: contact.country,
+                customer_account_id: Some(contact.account_id),
+                supplier_account_id: None,
+                vat_id: "".to_string(),
+                currency: contact.currency,
+            })
+        });
 
     let app = test::init_service(
         App::new()
@@ -89,6 +95,7 @@ async fn test_customer_success() {
     assert_eq!(payload["postalCode"], "postalCode".to_string());
     assert_eq!(payload["city"], "city".to_string());
     assert_eq!(payload["country"], "country".to_string());
+    assert_eq!(payload["currency"], "EUR".to_string());
 }
 
 #[actix_web::test]
@@ -110,32 +117,24 @@ async fn test_supplier_success() {
         .contacts
         .expect_create_supplier()
         .once()
-        .with(
-            predicate::eq("Example Inc.".to_string()),
-            predicate::eq(5),
-            predicate::eq("street1".to_string()),
-            predicate::eq("street2".to_string()),
-            predicate::eq("postalCode".to_string()),
-            predicate::eq("city".to_string()),
-            predicate::eq("country".to_string()),
-        )
-        .returning(
-            |name, account_id, street1, street2, postal_code, city, country| {
-                Ok(entity::contacts::Model {
-                    id: 1,
-                    name,
-                    is_customer: false,
-                    is_supplier: true,
-                    street1,
-                    street2,
-                    postal_code,
-                    city,
-                    country,
-                    customer_account_id: None,
-                    supplier_account_id: Some(account_id),
-                })
-            },
-        );
+        .with(predicate::eq(draft()))
+        .returning(|contact| {
+            Ok(entity::contacts::Model {
+                id: 1,
+                name: contact.name,
+                is_customer: false,
+                is_supplier: true,
+                street1: contact.street1,
+                street2: contact.street2,
+                postal_code: contact.postal_code,
+                city: contact.city,
+                country: contact.country,
+                customer_account_id: None,
+                supplier_account_id: Some(contact.account_id),
+                vat_id: "".to_string(),
+                currency: contact.currency,
+            })
+        });
 
     let app = test::init_service(
         App::new()
@@ -172,6 +171,7 @@ async fn test_supplier_success() {
     assert_eq!(payload["postalCode"], "postalCode".to_string());
     assert_eq!(payload["city"], "city".to_string());
     assert_eq!(payload["country"], "country".to_string());
+    assert_eq!(payload["currency"], "EUR".to_string());
 }
 
 #[actix_web::test]
@@ -204,6 +204,40 @@ async fn test_customer_invalid_name() {
     assert_eq!(res.status().as_u16(), 400u16);
 }
 
+#[actix_web::test]
+async fn test_customer_invalid_currency() {
+    let mut state = MockAppState::new();
+    state.ledger_accounts.expect_get().never();
+    state.contacts.expect_create_customer().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::contacts::create_customer),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/customers")
+        .set_json(json!({
+            "name": "Example Inc.",
+            "accountId": 5,
+            "street1": "",
+            "street2": "",
+            "postalCode": "",
+            "city": "",
+            "country": "",
+            "currency": "Euro",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVALID_CURRENCY");
+}
+
 #[actix_web::test]
 async fn test_supplier_invalid_name() {
     let mut state = MockAppState::new();
@@ -421,16 +455,8 @@ async fn test_connection_error2() {
         .contacts
         .expect_create_customer()
         .once()
-        .with(
-            predicate::eq("Example Inc.".to_string()),
-            predicate::eq(5),
-            predicate::eq("street1".to_string()),
-            predicate::eq("street2".to_string()),
-            predicate::eq("postalCode".to_string()),
-            predicate::eq("city".to_string()),
-            predicate::eq("country".to_string()),
-        )
-        .returning(|_, _, _, _, _, _, _| {
+        .with(predicate::eq(draft()))
+        .returning(|_| {
             let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
             Err(err)
         });
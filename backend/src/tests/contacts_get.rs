@@ -28,6 +28,8 @@ async fn test_customer_success() {
                 country: "country".to_string(),
                 customer_account_id: Some(5),
                 supplier_account_id: None,
+                vat_id: "".to_string(),
+                currency: "EUR".to_string(),
             }))
         });
 
@@ -80,6 +82,8 @@ async fn test_supplier_success() {
                 country: "country".to_string(),
                 customer_account_id: None,
                 supplier_account_id: Some(5),
+                vat_id: "".to_string(),
+                currency: "EUR".to_string(),
             }))
         });
 
@@ -157,6 +161,8 @@ async fn test_customer_not_found2() {
                 country: "country".to_string(),
                 customer_account_id: None,
                 supplier_account_id: Some(5),
+                vat_id: "".to_string(),
+                currency: "EUR".to_string(),
             }))
         });
 
@@ -221,6 +227,8 @@ async fn test_supplier_not_found2() {
                 country: "country".to_string(),
                 customer_account_id: Some(5),
                 supplier_account_id: None,
+                vat_id: "".to_string(),
+                currency: "EUR".to_string(),
             }))
         });
 
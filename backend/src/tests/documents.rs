@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use entity::sea_orm_active_enums::SaleInvoiceStatus;
+
+use crate::{
+    documents::{Letterhead, PrintableDocument},
+    tests::fixtures::{sale_invoice, sale_invoice_line},
+};
+
+fn line(id: i32, description: &str, price: i32) -> entity::sale_invoice_lines::Model {
+    entity::sale_invoice_lines::Model {
+        description: description.to_string(),
+        price,
+        ..sale_invoice_line(id, 5)
+    }
+}
+
+#[test]
+fn test_sale_invoice_discounts() {
+    let invoice = entity::sale_invoices::Model {
+        discount_percent: 10.0,
+        ..sale_invoice(5, SaleInvoiceStatus::Invoice)
+    };
+    let lines = vec![
+        entity::sale_invoice_lines::Model {
+            discount_percent: 10.0,
+            ..line(1, "Chair", 10000)
+        },
+        entity::sale_invoice_lines::Model {
+            discount_amount: 1000,
+            ..line(2, "Table", 4000)
+        },
+    ];
+    let customer = entity::contacts::Model {
+        id: 4,
+        name: "Müller GmbH".to_string(),
+        is_customer: true,
+        is_supplier: false,
+        street1: "Hauptstraße 1".to_string(),
+        street2: "".to_string(),
+        postal_code: "10115".to_string(),
+        city: "Berlin".to_string(),
+        country: "DE".to_string(),
+        customer_account_id: Some(1),
+        supplier_account_id: None,
+        vat_id: "".to_string(),
+        currency: "EUR".to_string(),
+    };
+
+    let document = PrintableDocument::sale_invoice(
+        &invoice,
+        &lines,
+        &customer,
+        &HashMap::new(),
+        &Letterhead::default(),
+    );
+    assert_eq!(document.lines[0].amount, 10000);
+    assert_eq!(document.lines[0].net, 9000);
+    assert_eq!(document.line_total, 12000);
+    assert_eq!(document.discount, 1200);
+    assert_eq!(document.net_total, 10800);
+    assert_eq!(document.taxes[0].base, 10800);
+    assert_eq!(document.tax_total, 2052);
+    assert_eq!(document.gross_total, 12852);
+
+    let text = document.render_text().unwrap();
+    assert!(text.contains("less discount 10%"));
+    assert!(text.contains("less discount 10.00"));
+    assert!(text.contains("Subtotal"));
+    assert!(text.contains("Discount 10%"));
+    assert!(text.contains("-12.00"));
+    assert!(text.contains("108.00"));
+}
@@ -0,0 +1,245 @@
+//! Entities shared by the handler tests. Tests override the fields they
+//! depend on with struct update syntax.
+
+use entity::sea_orm_active_enums::{
+    PurchaseInvoiceStatus, PurchaseOrderStatus, SaleInvoiceStatus, SaleOrderLineRoute,
+    SaleOrderStatus, StockKind,
+};
+use sea_orm::prelude::Date;
+
+/// A stoneware mug stocked in pieces, the unit with id 1.
+pub fn variant(id: i32) -> entity::variants::Model {
+    entity::variants::Model {
+        id,
+        name: "Stoneware mug".to_string(),
+        sale_price: 1200,
+        purchase_price: 600,
+        product_id: 5,
+        sku: None,
+        gtin: None,
+        unit_id: Some(1),
+    }
+}
+
+/// A unit, which is a base unit without `base_unit_id`.
+pub fn unit(id: i32, base_unit_id: Option<i32>, factor: f64) -> entity::units::Model {
+    entity::units::Model {
+        id,
+        name: format!("Unit {id}"),
+        symbol: "u".to_string(),
+        base_unit_id,
+        factor,
+    }
+}
+
+/// A stock of the kind. Only internal stocks belong to the company.
+pub fn stock(id: i32, kind: StockKind) -> entity::stocks::Model {
+    entity::stocks::Model {
+        id,
+        name: format!("Stock {id}"),
+        is_own: kind == StockKind::Internal,
+        owner_id: None,
+        kind,
+        parent_id: None,
+        consignment: false,
+    }
+}
+
+/// An oak chair sold by the piece, without a unit.
+pub fn oak_chair(id: i32) -> entity::variants::Model {
+    entity::variants::Model {
+        id,
+        name: "Chair, oak".to_string(),
+        sale_price: 4999,
+        purchase_price: 2500,
+        product_id: 1,
+        sku: None,
+        gtin: None,
+        unit_id: None,
+    }
+}
+
+/// A customer in Germany with the customer account 1.
+pub fn customer(id: i32) -> entity::contacts::Model {
+    entity::contacts::Model {
+        id,
+        name: "Huber KG".to_string(),
+        is_customer: true,
+        is_supplier: false,
+        street1: "".to_string(),
+        street2: "".to_string(),
+        postal_code: "".to_string(),
+        city: "".to_string(),
+        country: "DE".to_string(),
+        customer_account_id: Some(1),
+        supplier_account_id: None,
+        vat_id: "".to_string(),
+        currency: "EUR".to_string(),
+    }
+}
+
+/// A supplier in Germany with the supplier account 2.
+pub fn supplier(id: i32) -> entity::contacts::Model {
+    entity::contacts::Model {
+        id,
+        name: "Holzwerk GmbH".to_string(),
+        is_customer: false,
+        is_supplier: true,
+        customer_account_id: None,
+        supplier_account_id: Some(2),
+        ..customer(id)
+    }
+}
+
+/// The standard sales tax of 19 % posted to the account 1.
+pub fn tax(id: i32) -> entity::taxes::Model {
+    entity::taxes::Model {
+        id,
+        name: "Sales tax".to_string(),
+        name_short: "19%".to_string(),
+        rate: 0.19,
+        account_id: 1,
+        category: "S".to_string(),
+        note: "".to_string(),
+    }
+}
+
+/// A sale order of customer 4, delivered from stock 2 to stock 3.
+pub fn sale_order(id: i32, status: SaleOrderStatus) -> entity::sale_orders::Model {
+    entity::sale_orders::Model {
+        id,
+        reference: "SO-2025-00001".to_string(),
+        date: Date::from_ymd_opt(2025, 7, 1).unwrap(),
+        status,
+        customer_id: 4,
+        source_stock_id: 2,
+        target_stock_id: 3,
+        discount_percent: 0.0,
+        discount_amount: 0,
+    }
+}
+
+/// A mug on a sale order, taken from stock.
+pub fn sale_order_line(id: i32, order_id: i32) -> entity::sale_order_lines::Model {
+    entity::sale_order_lines::Model {
+        id,
+        description: "Mug".to_string(),
+        quantity: 1.0,
+        price: 1200,
+        tax: 19,
+        tax_id: None,
+        order_id,
+        variant_id: None,
+        move_id: None,
+        discount_percent: 0.0,
+        discount_amount: 0,
+        unit_id: None,
+        route: SaleOrderLineRoute::Stock,
+    }
+}
+
+/// A purchase order from supplier 4, received from stock 1 into stock 2.
+pub fn purchase_order(id: i32, status: PurchaseOrderStatus) -> entity::purchase_orders::Model {
+    entity::purchase_orders::Model {
+        id,
+        reference: "PO-2025-00001".to_string(),
+        date: Date::from_ymd_opt(2025, 7, 1).unwrap(),
+        status,
+        supplier_id: 4,
+        source_stock_id: 1,
+        target_stock_id: 2,
+        discount_percent: 0.0,
+        discount_amount: 0,
+    }
+}
+
+/// A mug on a purchase order.
+pub fn purchase_order_line(id: i32, order_id: i32) -> entity::purchase_order_lines::Model {
+    entity::purchase_order_lines::Model {
+        id,
+        description: "Mug".to_string(),
+        quantity: 1.0,
+        price: 600,
+        tax: 19,
+        order_id,
+        variant_id: None,
+        move_id: None,
+        discount_percent: 0.0,
+        discount_amount: 0,
+        unit_id: None,
+        sale_line_id: None,
+        tax_id: None,
+    }
+}
+
+/// An invoice of sale order 3 to customer 4.
+pub fn sale_invoice(id: i32, status: SaleInvoiceStatus) -> entity::sale_invoices::Model {
+    entity::sale_invoices::Model {
+        id,
+        reference: "INV-2025-00001".to_string(),
+        date: Date::from_ymd_opt(2025, 7, 1).unwrap(),
+        status,
+        order_id: 3,
+        customer_id: 4,
+        discount_percent: 0.0,
+        discount_amount: 0,
+    }
+}
+
+/// A mug on a sale invoice, not posted yet.
+pub fn sale_invoice_line(id: i32, invoice_id: i32) -> entity::sale_invoice_lines::Model {
+    entity::sale_invoice_lines::Model {
+        id,
+        description: "Mug".to_string(),
+        quantity: 1.0,
+        price: 1200,
+        tax: 19,
+        invoice_id,
+        variant_id: None,
+        transaction_id: None,
+        tax_id: None,
+        tax_transaction_id: None,
+        discount_percent: 0.0,
+        discount_amount: 0,
+        discount_transaction_id: None,
+    }
+}
+
+/// An invoice of supplier 4 for purchase order 3.
+pub fn purchase_invoice(
+    id: i32,
+    status: PurchaseInvoiceStatus,
+) -> entity::purchase_invoices::Model {
+    entity::purchase_invoices::Model {
+        id,
+        reference: "PI-2025-00001".to_string(),
+        date: Date::from_ymd_opt(2025, 7, 1).unwrap(),
+        status,
+        order_id: Some(3),
+        supplier_id: Some(4),
+        needs_review: false,
+        review_note: "".to_string(),
+        supplier_reference: "R-4711".to_string(),
+        discount_percent: 0.0,
+        discount_amount: 0,
+    }
+}
+
+/// A mug on a purchase invoice, not posted yet.
+pub fn purchase_invoice_line(id: i32, invoice_id: i32) -> entity::purchase_invoice_lines::Model {
+    entity::purchase_invoice_lines::Model {
+        id,
+        description: "Mug".to_string(),
+        quantity: 1.0,
+        price: 600,
+        tax: 19,
+        invoice_id,
+        variant_id: None,
+        transaction_id: None,
+        tax_id: None,
+        tax_transaction_id: None,
+        discount_percent: 0.0,
+        discount_amount: 0,
+        discount_transaction_id: None,
+    }
+}
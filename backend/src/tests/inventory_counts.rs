@@ -0,0 +1,113 @@
+use crate::utils::inventory_counts::{CountDifference, counted, differences};
+
+fn line(id: i32, variant_id: i32, expected_quantity: f64) -> entity::inventory_count_lines::Model {
+    entity::inventory_count_lines::Model {
+        id,
+        count_id: 1,
+        variant_id,
+        expected_quantity,
+        move_id: None,
+        lot_number: String::new(),
+    }
+}
+
+fn entry(line_id: i32, counter: &str, quantity: f64) -> entity::inventory_count_entries::Model {
+    entity::inventory_count_entries::Model {
+        id: 0,
+        line_id,
+        counter: counter.to_string(),
+        quantity,
+    }
+}
+
+#[test]
+fn test_counted() {
+    let entries = vec![
+        entry(1, "Anna", 4.0),
+        entry(1, "Ben", 2.5),
+        entry(2, "Anna", 0.0),
+    ];
+
+    assert_eq!(counted(1, &entries), Some(6.5));
+    assert_eq!(counted(2, &entries), Some(0.0));
+    assert_eq!(counted(3, &entries), None);
+}
+
+#[test]
+fn test_differences() {
+    // 10 mugs expected, 7 counted; 2 plates expected, 5 counted; 4 cups
+    // expected and counted; nobody counted the 3 bowls; 1 unexpected jug.
+    let lines = vec![
+        line(1, 8, 10.0),
+        line(2, 9, 2.0),
+        line(3, 10, 4.0),
+        line(4, 11, 3.0),
+        line(5, 12, 0.0),
+    ];
+    let entries = vec![
+        entry(1, "Anna", 5.0),
+        entry(1, "Ben", 2.0),
+        entry(2, "Anna", 5.0),
+        entry(3, "Ben", 4.0),
+        entry(5, "Ben", 1.0),
+    ];
+
+    assert_eq!(
+        differences(&lines, &entries),
+        vec![
+            CountDifference {
+                line_id: 1,
+                variant_id: 8,
+                lot_number: String::new(),
+                difference: -3.0,
+            },
+            CountDifference {
+                line_id: 2,
+                variant_id: 9,
+                lot_number: String::new(),
+                difference: 3.0,
+            },
+            CountDifference {
+                line_id: 5,
+                variant_id: 12,
+                lot_number: String::new(),
+                difference: 1.0,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_differences_per_lot() {
+    // Lot B-17 of the mugs lacks 2, lot B-18 has one more than expected.
+    let lots = [("B-17", 5.0), ("B-18", 3.0)];
+    let lines: Vec<_> = lots
+        .iter()
+        .enumerate()
+        .map(
+            |(index, (number, expected))| entity::inventory_count_lines::Model {
+                lot_number: number.to_string(),
+                ..line(index as i32 + 1, 8, *expected)
+            },
+        )
+        .collect();
+    let entries = vec![entry(1, "Anna", 3.0), entry(2, "Anna", 4.0)];
+
+    assert_eq!(
+        differences(&lines, &entries),
+        vec![
+            CountDifference {
+                line_id: 1,
+                variant_id: 8,
+                lot_number: "B-17".to_string(),
+                difference: -2.0,
+            },
+            CountDifference {
+                line_id: 2,
+                variant_id: 8,
+                lot_number: "B-18".to_string(),
+                difference: 1.0,
+            },
+        ]
+    );
+}
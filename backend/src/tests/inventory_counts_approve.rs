@@ -0,0 +1,439 @@
+use std::collections::HashMap;
+
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{InventoryCountStatus, StockKind};
+use mockall::predicate;
+use sea_orm::{DbErr, prelude::Date};
+use serde_json::json;
+
+use crate::{
+    handlers,
+    repositories::{AdjustmentDraft, CostAccounts, ERR_LOT_NOT_IN_STOCK, LineMoveDraft, LotDraft},
+    state::{AppState, MockAppState},
+    utils::product_categories::CategoryDefaults,
+};
+
+fn count(status: InventoryCountStatus) -> entity::inventory_counts::Model {
+    entity::inventory_counts::Model {
+        id: 1,
+        stock_id: 2,
+        status,
+        date: Date::from_ymd_opt(2025, 7, 1).unwrap(),
+    }
+}
+
+fn loss_location() -> entity::stocks::Model {
+    entity::stocks::Model {
+        id: 7,
+        name: "Inventory loss".to_string(),
+        is_own: false,
+        owner_id: None,
+        kind: StockKind::InventoryLoss,
+        parent_id: None,
+        consignment: false,
+    }
+}
+
+fn line(id: i32, variant_id: i32, expected_quantity: f64) -> entity::inventory_count_lines::Model {
+    entity::inventory_count_lines::Model {
+        id,
+        count_id: 1,
+        variant_id,
+        expected_quantity,
+        move_id: None,
+        lot_number: String::new(),
+    }
+}
+
+fn entry(line_id: i32, counter: &str, quantity: f64) -> entity::inventory_count_entries::Model {
+    entity::inventory_count_entries::Model {
+        id: line_id * 10,
+        line_id,
+        counter: counter.to_string(),
+        quantity,
+    }
+}
+
+/// 10 mugs expected and 7 counted, 2 plates expected and 5 counted, and
+/// nobody counted the 4 cups.
+fn mock_count(state: &mut MockAppState) {
+    state
+        .inventory_counts
+        .expect_get()
+        .once()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(Some(count(InventoryCountStatus::Open))));
+    state
+        .stocks
+        .expect_list_virtual()
+        .once()
+        .returning(|| Ok(vec![loss_location()]));
+    state
+        .inventory_counts
+        .expect_list_lines()
+        .returning(|_| Ok(vec![line(1, 8, 10.0), line(2, 9, 2.0), line(3, 10, 4.0)]));
+    state.inventory_counts.expect_list_entries().returning(|_| {
+        Ok(vec![
+            entry(1, "Anna", 4.0),
+            entry(1, "Ben", 3.0),
+            entry(2, "Anna", 5.0),
+        ])
+    });
+}
+
+fn adjustments(cost_accounts: [Option<CostAccounts>; 2]) -> Vec<AdjustmentDraft> {
+    vec![
+        AdjustmentDraft {
+            found: false,
+            line: LineMoveDraft {
+                line_id: 1,
+                variant_id: 8,
+                quantity: 3.0,
+                unit_cost: None,
+                cost_accounts: cost_accounts[0],
+                lots: vec![],
+            },
+        },
+        AdjustmentDraft {
+            found: true,
+            line: LineMoveDraft {
+                line_id: 2,
+                variant_id: 9,
+                quantity: 3.0,
+                unit_cost: None,
+                cost_accounts: cost_accounts[1],
+                lots: vec![],
+            },
+        },
+    ]
+}
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    mock_count(&mut state);
+    state.ledger_accounts.expect_get().never();
+    state
+        .inventory_counts
+        .expect_approve()
+        .once()
+        .with(
+            predicate::always(),
+            predicate::always(),
+            predicate::eq(7),
+            predicate::eq(adjustments([None, None])),
+        )
+        .returning(|count, _, _, _| {
+            Ok(entity::inventory_counts::Model {
+                status: InventoryCountStatus::Approved,
+                ..count
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::inventory_counts::approve),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/inventory-counts/1/approve")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["status"], "approved");
+    assert_eq!(body["lines"][0]["countedQuantity"], 7.0);
+}
+
+/// Lot B-17 of the mugs lacks 2 and lot B-18 has one more than expected.
+fn mock_lot_count(state: &mut MockAppState) {
+    state
+        .inventory_counts
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(count(InventoryCountStatus::Open))));
+    state
+        .stocks
+        .expect_list_virtual()
+        .once()
+        .returning(|| Ok(vec![loss_location()]));
+    state.inventory_counts.expect_list_lines().returning(|_| {
+        Ok(vec![
+            entity::inventory_count_lines::Model {
+                lot_number: "B-17".to_string(),
+                ..line(1, 8, 5.0)
+            },
+            entity::inventory_count_lines::Model {
+                lot_number: "B-18".to_string(),
+                ..line(2, 8, 3.0)
+            },
+        ])
+    });
+    state
+        .inventory_counts
+        .expect_list_entries()
+        .returning(|_| Ok(vec![entry(1, "Anna", 3.0), entry(2, "Anna", 4.0)]));
+}
+
+#[actix_web::test]
+async fn test_lots() {
+    let mut state = MockAppState::new();
+    mock_lot_count(&mut state);
+    let lot = |number: &str, quantity| LotDraft {
+        number: number.to_string(),
+        quantity,
+        expiry_date: None,
+    };
+    let adjustments = vec![
+        AdjustmentDraft {
+            found: false,
+            line: LineMoveDraft {
+                line_id: 1,
+                variant_id: 8,
+                quantity: 2.0,
+                unit_cost: None,
+                cost_accounts: None,
+                lots: vec![lot("B-17", 2.0)],
+            },
+        },
+        AdjustmentDraft {
+            found: true,
+            line: LineMoveDraft {
+                line_id: 2,
+                variant_id: 8,
+                quantity: 1.0,
+                unit_cost: None,
+                cost_accounts: None,
+                lots: vec![lot("B-18", 1.0)],
+            },
+        },
+    ];
+    state
+        .inventory_counts
+        .expect_approve()
+        .once()
+        .with(
+            predicate::always(),
+            predicate::always(),
+            predicate::eq(7),
+            predicate::eq(adjustments),
+        )
+        .returning(|count, _, _, _| {
+            Ok(entity::inventory_counts::Model {
+                status: InventoryCountStatus::Approved,
+                ..count
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::inventory_counts::approve),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/inventory-counts/1/approve")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["lines"][0]["lotNumber"], "B-17");
+    assert_eq!(body["lines"][1]["countedQuantity"], 4.0);
+}
+
+#[actix_web::test]
+async fn test_lot_not_in_stock() {
+    let mut state = MockAppState::new();
+    mock_lot_count(&mut state);
+    state
+        .inventory_counts
+        .expect_approve()
+        .once()
+        .returning(|_, _, _, _| Err(DbErr::Custom(ERR_LOT_NOT_IN_STOCK.to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::inventory_counts::approve),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/inventory-counts/1/approve")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "LOT_NOT_IN_STOCK");
+}
+
+#[actix_web::test]
+async fn test_with_postings() {
+    let mut state = MockAppState::new();
+    mock_count(&mut state);
+    state
+        .ledger_accounts
+        .expect_get()
+        .once()
+        .with(predicate::eq(5900))
+        .returning(|id| {
+            Ok(Some(entity::ledger_accounts::Model {
+                id,
+                account_type: "expense".to_string(),
+                name: "Inventory differences".to_string(),
+            }))
+        });
+    state
+        .product_categories
+        .expect_defaults_for_variants()
+        .once()
+        .with(predicate::eq(vec![8, 9]))
+        .returning(|ids| {
+            Ok(ids
+                .into_iter()
+                .map(|id| {
+                    let defaults = CategoryDefaults {
+                        inventory_account_id: Some(1400),
+                        ..Default::default()
+                    };
+                    (id, defaults)
+                })
+                .collect::<HashMap<_, _>>())
+        });
+    let accounts = Some(CostAccounts {
+        expense_account_id: 5900,
+        inventory_account_id: 1400,
+    });
+    state
+        .inventory_counts
+        .expect_approve()
+        .once()
+        .with(
+            predicate::always(),
+            predicate::always(),
+            predicate::eq(7),
+            predicate::eq(adjustments([accounts, accounts])),
+        )
+        .returning(|count, _, _, _| {
+            Ok(entity::inventory_counts::Model {
+                status: InventoryCountStatus::Approved,
+                ..count
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::inventory_counts::approve),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/inventory-counts/1/approve")
+        .set_json(json!({ "accountId": 5900 }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+}
+
+#[actix_web::test]
+async fn test_inventory_account_missing() {
+    let mut state = MockAppState::new();
+    mock_count(&mut state);
+    state.ledger_accounts.expect_get().once().returning(|id| {
+        Ok(Some(entity::ledger_accounts::Model {
+            id,
+            account_type: "expense".to_string(),
+            name: "Inventory differences".to_string(),
+        }))
+    });
+    state
+        .product_categories
+        .expect_defaults_for_variants()
+        .once()
+        .returning(|_| Ok(HashMap::new()));
+    state.inventory_counts.expect_approve().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::inventory_counts::approve),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/inventory-counts/1/approve")
+        .set_json(json!({ "accountId": 5900 }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "POSTING_ACCOUNT_MISSING");
+}
+
+#[actix_web::test]
+async fn test_already_approved() {
+    let mut state = MockAppState::new();
+    state
+        .inventory_counts
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(count(InventoryCountStatus::Approved))));
+    state.inventory_counts.expect_approve().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::inventory_counts::approve),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/inventory-counts/1/approve")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVENTORY_COUNT_APPROVED");
+}
+
+#[actix_web::test]
+async fn test_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .inventory_counts
+        .expect_get()
+        .once()
+        .returning(|_| Ok(None));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::inventory_counts::approve),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/inventory-counts/1/approve")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVENTORY_COUNT_NOT_FOUND");
+}
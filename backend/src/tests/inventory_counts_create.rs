@@ -0,0 +1,132 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{InventoryCountStatus, StockKind};
+use mockall::predicate;
+use sea_orm::prelude::Date;
+use serde_json::json;
+
+use crate::{
+    handlers,
+    state::{AppState, MockAppState},
+    tests::fixtures::stock,
+};
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .stocks
+        .expect_get()
+        .once()
+        .with(predicate::eq(2))
+        .returning(|id| Ok(Some(stock(id, StockKind::Internal))));
+    state
+        .inventory_counts
+        .expect_create()
+        .once()
+        .with(predicate::eq(2), predicate::always())
+        .returning(|stock_id, date| {
+            Ok(entity::inventory_counts::Model {
+                id: 1,
+                stock_id,
+                status: InventoryCountStatus::Open,
+                date,
+            })
+        });
+    state
+        .inventory_counts
+        .expect_list_lines()
+        .once()
+        .with(predicate::eq(1))
+        .returning(|count_id| {
+            Ok(vec![entity::inventory_count_lines::Model {
+                id: 3,
+                count_id,
+                variant_id: 8,
+                expected_quantity: 12.0,
+                move_id: None,
+                lot_number: String::new(),
+            }])
+        });
+    state
+        .inventory_counts
+        .expect_list_entries()
+        .once()
+        .returning(|_| Ok(vec![]));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::inventory_counts::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/inventory-counts")
+        .set_json(json!({ "stockId": 2 }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["stockId"], 2);
+    assert_eq!(body["status"], "open");
+    assert_eq!(body["lines"][0]["variantId"], 8);
+    assert_eq!(body["lines"][0]["expectedQuantity"], 12.0);
+    assert_eq!(body["lines"][0]["countedQuantity"], serde_json::Value::Null);
+    assert!(Date::parse_from_str(body["date"].as_str().unwrap(), "%Y-%m-%d").is_ok());
+}
+
+#[actix_web::test]
+async fn test_virtual_location() {
+    let mut state = MockAppState::new();
+    state
+        .stocks
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(stock(id, StockKind::Customer))));
+    state.inventory_counts.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::inventory_counts::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/inventory-counts")
+        .set_json(json!({ "stockId": 4 }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVALID_COUNT_STOCK");
+}
+
+#[actix_web::test]
+async fn test_stock_not_found() {
+    let mut state = MockAppState::new();
+    state.stocks.expect_get().once().returning(|_| Ok(None));
+    state.inventory_counts.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::inventory_counts::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/inventory-counts")
+        .set_json(json!({ "stockId": 9 }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "STOCK_NOT_FOUND");
+}
@@ -0,0 +1,360 @@
+use std::collections::HashMap;
+
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{InventoryCountStatus, Tracking};
+use mockall::predicate;
+use sea_orm::prelude::Date;
+use serde_json::json;
+
+use crate::{
+    handlers,
+    state::{AppState, MockAppState},
+    tests::fixtures::{unit, variant},
+};
+
+fn count(status: InventoryCountStatus) -> entity::inventory_counts::Model {
+    entity::inventory_counts::Model {
+        id: 1,
+        stock_id: 2,
+        status,
+        date: Date::from_ymd_opt(2025, 7, 1).unwrap(),
+    }
+}
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .inventory_counts
+        .expect_get()
+        .once()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(Some(count(InventoryCountStatus::Open))));
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .variants
+        .expect_stockable_ids()
+        .once()
+        .with(predicate::eq(vec![8]))
+        .returning(Ok);
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .returning(|_| Ok(HashMap::new()));
+    state.units.expect_get().times(2).returning(|id| match id {
+        2 => Ok(Some(unit(2, Some(1), 12.0))),
+        _ => Ok(Some(unit(id, None, 1.0))),
+    });
+    // 2 boxes of 12 are 24 pieces.
+    state
+        .inventory_counts
+        .expect_record()
+        .once()
+        .with(
+            predicate::eq(1),
+            predicate::eq(8),
+            predicate::eq(String::new()),
+            predicate::eq("Anna".to_string()),
+            predicate::eq(24.0),
+        )
+        .returning(|_, _, _, counter, quantity| {
+            Ok(entity::inventory_count_entries::Model {
+                id: 6,
+                line_id: 3,
+                counter,
+                quantity,
+            })
+        });
+    state
+        .inventory_counts
+        .expect_list_lines()
+        .once()
+        .returning(|count_id| {
+            Ok(vec![entity::inventory_count_lines::Model {
+                id: 3,
+                count_id,
+                variant_id: 8,
+                expected_quantity: 30.0,
+                move_id: None,
+                lot_number: String::new(),
+            }])
+        });
+    state
+        .inventory_counts
+        .expect_list_entries()
+        .once()
+        .returning(|_| {
+            Ok(vec![
+                entity::inventory_count_entries::Model {
+                    id: 5,
+                    line_id: 3,
+                    counter: "Ben".to_string(),
+                    quantity: 5.0,
+                },
+                entity::inventory_count_entries::Model {
+                    id: 6,
+                    line_id: 3,
+                    counter: "Anna".to_string(),
+                    quantity: 24.0,
+                },
+            ])
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::inventory_counts::record),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/inventory-counts/1/entries")
+        .set_json(json!({
+            "variantId": 8,
+            "counter": "Anna",
+            "quantity": 2.0,
+            "unitId": 2,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["lines"][0]["expectedQuantity"], 30.0);
+    assert_eq!(body["lines"][0]["countedQuantity"], 29.0);
+    assert_eq!(body["lines"][0]["entries"][1]["counter"], "Anna");
+    assert_eq!(body["lines"][0]["entries"][1]["quantity"], 24.0);
+}
+
+#[actix_web::test]
+async fn test_count_approved() {
+    let mut state = MockAppState::new();
+    state
+        .inventory_counts
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(count(InventoryCountStatus::Approved))));
+    state.inventory_counts.expect_record().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::inventory_counts::record),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/inventory-counts/1/entries")
+        .set_json(json!({ "variantId": 8, "counter": "Anna", "quantity": 3.0 }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVENTORY_COUNT_APPROVED");
+}
+
+#[actix_web::test]
+async fn test_variant_not_stockable() {
+    let mut state = MockAppState::new();
+    state
+        .inventory_counts
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(count(InventoryCountStatus::Open))));
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .variants
+        .expect_stockable_ids()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state.inventory_counts.expect_record().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::inventory_counts::record),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/inventory-counts/1/entries")
+        .set_json(json!({ "variantId": 8, "counter": "Anna", "quantity": 3.0 }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "VARIANT_NOT_STOCKABLE");
+}
+
+#[actix_web::test]
+async fn test_negative_quantity() {
+    let mut state = MockAppState::new();
+    state.inventory_counts.expect_get().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::inventory_counts::record),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/inventory-counts/1/entries")
+        .set_json(json!({ "variantId": 8, "counter": "Anna", "quantity": -1.0 }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+}
+
+#[actix_web::test]
+async fn test_lot() {
+    let mut state = MockAppState::new();
+    state
+        .inventory_counts
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(count(InventoryCountStatus::Open))));
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state.variants.expect_stockable_ids().once().returning(Ok);
+    state
+        .units
+        .expect_get()
+        .returning(|id| Ok(Some(unit(id, None, 1.0))));
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .returning(|ids| Ok(ids.into_iter().map(|id| (id, Tracking::Lot)).collect()));
+    state
+        .inventory_counts
+        .expect_record()
+        .once()
+        .with(
+            predicate::eq(1),
+            predicate::eq(8),
+            predicate::eq("B-17".to_string()),
+            predicate::eq("Anna".to_string()),
+            predicate::eq(3.0),
+        )
+        .returning(|_, _, _, counter, quantity| {
+            Ok(entity::inventory_count_entries::Model {
+                id: 6,
+                line_id: 3,
+                counter,
+                quantity,
+            })
+        });
+    state
+        .inventory_counts
+        .expect_list_lines()
+        .once()
+        .returning(|count_id| {
+            Ok(vec![entity::inventory_count_lines::Model {
+                id: 3,
+                count_id,
+                variant_id: 8,
+                expected_quantity: 5.0,
+                move_id: None,
+                lot_number: "B-17".to_string(),
+            }])
+        });
+    state
+        .inventory_counts
+        .expect_list_entries()
+        .once()
+        .returning(|_| {
+            Ok(vec![entity::inventory_count_entries::Model {
+                id: 6,
+                line_id: 3,
+                counter: "Anna".to_string(),
+                quantity: 3.0,
+            }])
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::inventory_counts::record),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/inventory-counts/1/entries")
+        .set_json(json!({
+            "variantId": 8,
+            "counter": "Anna",
+            "lotNumber": " B-17 ",
+            "quantity": 3.0,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["lines"][0]["lotNumber"], "B-17");
+    assert_eq!(body["lines"][0]["countedQuantity"], 3.0);
+}
+
+#[actix_web::test]
+async fn test_lot_missing() {
+    let mut state = MockAppState::new();
+    state
+        .inventory_counts
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(count(InventoryCountStatus::Open))));
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state.variants.expect_stockable_ids().once().returning(Ok);
+    state
+        .units
+        .expect_get()
+        .returning(|id| Ok(Some(unit(id, None, 1.0))));
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .returning(|ids| Ok(ids.into_iter().map(|id| (id, Tracking::Serial)).collect()));
+    state.inventory_counts.expect_record().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::inventory_counts::record),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/inventory-counts/1/entries")
+        .set_json(json!({ "variantId": 8, "counter": "Anna", "quantity": 1.0 }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "LOTS_MISSING");
+}
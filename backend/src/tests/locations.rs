@@ -0,0 +1,28 @@
+use entity::sea_orm_active_enums::StockKind;
+
+use crate::{tests::fixtures::stock, utils::locations::subtree};
+
+#[test]
+fn test_subtree() {
+    // Warehouse 1 with zones 2 and 3, zone 2 with bins 4 and 5, and a
+    // second warehouse 6.
+    let stocks: Vec<_> = [
+        (1, None),
+        (2, Some(1)),
+        (3, Some(1)),
+        (4, Some(2)),
+        (5, Some(2)),
+        (6, None),
+    ]
+    .into_iter()
+    .map(|(id, parent_id)| entity::stocks::Model {
+        parent_id,
+        ..stock(id, StockKind::Internal)
+    })
+    .collect();
+
+    assert_eq!(subtree(&stocks, 1), vec![1, 2, 3, 4, 5]);
+    assert_eq!(subtree(&stocks, 2), vec![2, 4, 5]);
+    assert_eq!(subtree(&stocks, 5), vec![5]);
+    assert_eq!(subtree(&stocks, 6), vec![6]);
+}
@@ -0,0 +1,56 @@
+use entity::sea_orm_active_enums::Tracking;
+
+use crate::{
+    repositories::LotDraft,
+    utils::lots::{LotProblem, check_lots},
+};
+
+fn lot(number: &str, quantity: f64) -> LotDraft {
+    LotDraft {
+        number: number.to_string(),
+        quantity,
+        expiry_date: None,
+    }
+}
+
+#[test]
+fn test_untracked() {
+    assert_eq!(check_lots(&Tracking::None, 3.0, &[]), None);
+    assert_eq!(
+        check_lots(&Tracking::None, 3.0, &[lot("L-1", 3.0)]),
+        Some(LotProblem::NotTracked)
+    );
+}
+
+#[test]
+fn test_lots() {
+    let lots = [lot("L-1", 2.0), lot("L-2", 1.5)];
+    assert_eq!(check_lots(&Tracking::Lot, 3.5, &lots), None);
+    assert_eq!(
+        check_lots(&Tracking::Lot, 4.0, &lots),
+        Some(LotProblem::QuantityMismatch)
+    );
+    assert_eq!(
+        check_lots(&Tracking::Lot, 3.5, &[]),
+        Some(LotProblem::Missing)
+    );
+}
+
+#[test]
+fn test_duplicate() {
+    let lots = [lot("L-1", 2.0), lot("L-1", 1.0)];
+    assert_eq!(
+        check_lots(&Tracking::Lot, 3.0, &lots),
+        Some(LotProblem::Duplicate)
+    );
+}
+
+#[test]
+fn test_serials() {
+    let serials = [lot("SN-1", 1.0), lot("SN-2", 1.0)];
+    assert_eq!(check_lots(&Tracking::Serial, 2.0, &serials), None);
+    assert_eq!(
+        check_lots(&Tracking::Serial, 2.0, &[lot("SN-1", 2.0)]),
+        Some(LotProblem::SerialQuantity)
+    );
+}
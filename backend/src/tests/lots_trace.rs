@@ -0,0 +1,116 @@
+use actix_web::{App, test, web};
+use mockall::predicate;
+
+use crate::{
+    handlers,
+    repositories::LotMove,
+    state::{AppState, MockAppState},
+    tests::fixtures::{customer, supplier},
+};
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .lots
+        .expect_get()
+        .once()
+        .with(predicate::eq(3))
+        .returning(|id| {
+            Ok(Some(entity::lots::Model {
+                id,
+                variant_id: 8,
+                number: "B-100".to_string(),
+                expiry_date: None,
+            }))
+        });
+    // Received from the supplier, moved to the shop and sold from there.
+    state
+        .lots
+        .expect_trace()
+        .once()
+        .with(predicate::eq(3))
+        .returning(|_| {
+            Ok(vec![
+                LotMove {
+                    move_id: 10,
+                    quantity: 20.0,
+                    source_stock_id: 1,
+                    target_stock_id: 2,
+                    purchase_order_id: Some(4),
+                    sale_order_id: None,
+                    contact: Some(entity::contacts::Model {
+                        name: "Pharma Wholesale".to_string(),
+                        ..supplier(6)
+                    }),
+                },
+                LotMove {
+                    move_id: 11,
+                    quantity: 5.0,
+                    source_stock_id: 2,
+                    target_stock_id: 3,
+                    purchase_order_id: None,
+                    sale_order_id: None,
+                    contact: None,
+                },
+                LotMove {
+                    move_id: 12,
+                    quantity: 2.0,
+                    source_stock_id: 3,
+                    target_stock_id: 4,
+                    purchase_order_id: None,
+                    sale_order_id: Some(9),
+                    contact: Some(entity::contacts::Model {
+                        name: "Corner Pharmacy".to_string(),
+                        ..customer(7)
+                    }),
+                },
+            ])
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::lots::trace),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/lots/3/trace")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["lot"]["number"], "B-100");
+    assert_eq!(body["moves"][0]["purchaseOrderId"], 4);
+    assert_eq!(body["moves"][0]["contactName"], "Pharma Wholesale");
+    assert_eq!(body["moves"][1]["contactId"], serde_json::Value::Null);
+    assert_eq!(body["moves"][2]["saleOrderId"], 9);
+    assert_eq!(body["moves"][2]["contactId"], 7);
+}
+
+#[actix_web::test]
+async fn test_not_found() {
+    let mut state = MockAppState::new();
+    state.lots.expect_get().once().returning(|_| Ok(None));
+    state.lots.expect_trace().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::lots::trace),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/lots/3/trace")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "LOT_NOT_FOUND");
+}
@@ -1,7 +1,14 @@
+mod availability;
 mod contacts_create;
 mod contacts_delete;
 mod contacts_get;
 mod contacts_list;
+mod documents;
+mod fixtures;
+mod inventory_counts;
+mod inventory_counts_approve;
+mod inventory_counts_create;
+mod inventory_counts_record;
 mod ledger_accounts_create;
 mod ledger_accounts_delete;
 mod ledger_accounts_get;
@@ -10,42 +17,75 @@ mod ledger_transactions_create;
 mod ledger_transactions_delete;
 mod ledger_transactions_get;
 mod ledger_transactions_list;
+mod locations;
+mod lots;
+mod lots_trace;
+mod number_pattern;
+mod number_sequences_list;
+mod number_sequences_update;
+mod price_lists;
+mod price_lists_create;
+mod price_lists_customers_set;
+mod price_lists_items_create;
+mod product_attributes_create;
+mod product_categories;
+mod product_categories_create;
 mod products_create;
 mod products_delete;
 mod products_get;
 mod products_list;
 mod purchase_invoice_create;
+mod purchase_invoice_credit_notes_create;
 mod purchase_invoice_delete;
 mod purchase_invoice_get;
+mod purchase_invoice_import;
 mod purchase_invoice_lines_create;
 mod purchase_invoice_lines_delete;
 mod purchase_invoice_lines_get;
 mod purchase_invoice_lines_list;
 mod purchase_invoice_list;
+mod purchase_invoice_post;
 mod purchase_order_lines_create;
 mod purchase_order_lines_delete;
 mod purchase_order_lines_get;
 mod purchase_order_lines_list;
+mod purchase_orders_confirm;
 mod purchase_orders_create;
 mod purchase_orders_delete;
 mod purchase_orders_get;
 mod purchase_orders_list;
+mod purchase_orders_receive;
+mod reorder_rules_create;
+mod reorder_rules_replenish;
+mod replenishment;
+mod reports_inventory_valuation;
+mod reports_vat;
+mod reports_vat_ustva;
 mod sale_invoice_create;
+mod sale_invoice_credit_notes_create;
+mod sale_invoice_credit_notes_list;
 mod sale_invoice_delete;
+mod sale_invoice_einvoice;
 mod sale_invoice_get;
 mod sale_invoice_lines_create;
 mod sale_invoice_lines_delete;
 mod sale_invoice_lines_get;
 mod sale_invoice_lines_list;
 mod sale_invoice_list;
+mod sale_invoice_pdf;
+mod sale_invoice_post;
 mod sale_order_lines_create;
 mod sale_order_lines_delete;
 mod sale_order_lines_get;
 mod sale_order_lines_list;
+mod sale_orders_cancel;
+mod sale_orders_confirm;
 mod sale_orders_create;
 mod sale_orders_delete;
+mod sale_orders_deliver;
 mod sale_orders_get;
 mod sale_orders_list;
+mod sale_orders_pdf;
 mod stock_moves_create;
 mod stock_moves_delete;
 mod stock_moves_get;
@@ -54,15 +94,32 @@ mod stocks_create;
 mod stocks_delete;
 mod stocks_get;
 mod stocks_list;
+mod stocks_list_virtual;
+mod stocks_quantities;
+mod stocks_report_consumption;
+mod supplier_prices_create;
+mod tax_groups;
+mod tax_rules;
+mod tax_rules_create;
+mod tax_rules_determine;
+mod taxes_components_set;
 mod taxes_create;
 mod taxes_delete;
 mod taxes_get;
 mod taxes_list;
+mod taxes_rates_create;
+mod totals;
+mod units_create;
 mod users_create;
 mod users_delete;
 mod users_get;
 mod users_list;
+mod valuation;
+mod variants_availability;
 mod variants_create;
 mod variants_delete;
+mod variants_generate;
 mod variants_get;
+mod variants_get_by_code;
 mod variants_list;
+mod variants_lots;
@@ -0,0 +1,39 @@
+use chrono::NaiveDate;
+
+use crate::utils::number_pattern::{format_number, period, valid_pattern};
+
+fn date(year: i32, month: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, 15).unwrap()
+}
+
+#[test]
+fn test_format_number() {
+    assert_eq!(
+        format_number("INV-{YYYY}-{00000}", date(2025, 7), 42),
+        "INV-2025-00042"
+    );
+    assert_eq!(
+        format_number("RE{YY}{MM}-{000}", date(2025, 3), 7),
+        "RE2503-007"
+    );
+    assert_eq!(format_number("{00}", date(2025, 3), 123), "123");
+}
+
+#[test]
+fn test_period() {
+    assert_eq!(period("INV-{YYYY}-{00000}", date(2025, 7)), "2025");
+    assert_eq!(period("RE{YY}{MM}-{000}", date(2025, 3)), "2025-03");
+    assert_eq!(period("SO-{MM}-{000}", date(2025, 3)), "2025-03");
+    assert_eq!(period("SO-{00000}", date(2025, 3)), "");
+}
+
+#[test]
+fn test_valid_pattern() {
+    assert!(valid_pattern("INV-{YYYY}-{00000}"));
+    assert!(valid_pattern("{0}"));
+    assert!(!valid_pattern("INV-{YYYY}"));
+    assert!(!valid_pattern("{00}-{00}"));
+    assert!(!valid_pattern("INV-{DD}-{000}"));
+    assert!(!valid_pattern("INV-{000"));
+    assert!(!valid_pattern("INV-000}"));
+}
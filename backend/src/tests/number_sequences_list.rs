@@ -0,0 +1,92 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::DocumentType;
+use sea_orm::{DbErr, RuntimeErr};
+use serde_json::json;
+
+use crate::{
+    handlers,
+    state::{AppState, MockAppState},
+};
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state.number_sequences.expect_list().once().returning(|| {
+        Ok(vec![
+            entity::number_sequences::Model {
+                id: 1,
+                document_type: DocumentType::SaleOrder,
+                pattern: "SO-{YYYY}-{00000}".to_string(),
+                gapless: false,
+                period: "2025".to_string(),
+                next_number: 43,
+            },
+            entity::number_sequences::Model {
+                id: 2,
+                document_type: DocumentType::SaleInvoice,
+                pattern: "INV-{YYYY}-{00000}".to_string(),
+                gapless: true,
+                period: "".to_string(),
+                next_number: 1,
+            },
+        ])
+    });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::number_sequences::list),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/number-sequences")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(
+        body,
+        json!([
+            {
+                "documentType": "sale_order",
+                "pattern": "SO-{YYYY}-{00000}",
+                "gapless": false,
+                "period": "2025",
+                "nextNumber": 43,
+            },
+            {
+                "documentType": "sale_invoice",
+                "pattern": "INV-{YYYY}-{00000}",
+                "gapless": true,
+                "period": "",
+                "nextNumber": 1,
+            },
+        ])
+    );
+}
+
+#[actix_web::test]
+async fn test_connection_error() {
+    let mut state = MockAppState::new();
+    state.number_sequences.expect_list().once().returning(|| {
+        let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+        Err(err)
+    });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::number_sequences::list),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/number-sequences")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
+}
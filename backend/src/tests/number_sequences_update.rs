@@ -0,0 +1,137 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::DocumentType;
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr};
+use serde_json::json;
+
+use crate::{
+    handlers,
+    state::{AppState, MockAppState},
+};
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .number_sequences
+        .expect_update_pattern()
+        .once()
+        .with(
+            predicate::eq(DocumentType::SaleInvoice),
+            predicate::eq("RE{YY}{MM}-{0000}".to_string()),
+        )
+        .returning(|document_type, pattern| {
+            Ok(Some(entity::number_sequences::Model {
+                id: 2,
+                document_type,
+                pattern,
+                gapless: true,
+                period: "2025".to_string(),
+                next_number: 8,
+            }))
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::number_sequences::update),
+    )
+    .await;
+
+    let req = test::TestRequest::put()
+        .uri("/api/number-sequences/sale_invoice")
+        .set_json(json!({
+            "pattern": "RE{YY}{MM}-{0000}",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["documentType"], "sale_invoice");
+    assert_eq!(body["pattern"], "RE{YY}{MM}-{0000}");
+}
+
+#[actix_web::test]
+async fn test_invalid_pattern() {
+    let mut state = MockAppState::new();
+    state.number_sequences.expect_update_pattern().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::number_sequences::update),
+    )
+    .await;
+
+    for pattern in [
+        "INV-{YYYY}",
+        "INV-{000}-{000}",
+        "INV-{DD}-{000}",
+        "INV-{000",
+    ] {
+        let req = test::TestRequest::put()
+            .uri("/api/number-sequences/sale_invoice")
+            .set_json(json!({
+                "pattern": pattern,
+            }))
+            .to_request();
+
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status().as_u16(), 400u16, "{pattern}");
+    }
+}
+
+#[actix_web::test]
+async fn test_not_found() {
+    let mut state = MockAppState::new();
+    state.number_sequences.expect_update_pattern().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::number_sequences::update),
+    )
+    .await;
+
+    let req = test::TestRequest::put()
+        .uri("/api/number-sequences/delivery_note")
+        .set_json(json!({
+            "pattern": "DN-{00000}",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+}
+
+#[actix_web::test]
+async fn test_connection_error() {
+    let mut state = MockAppState::new();
+    state
+        .number_sequences
+        .expect_update_pattern()
+        .once()
+        .returning(|_, _| {
+            let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+            Err(err)
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::number_sequences::update),
+    )
+    .await;
+
+    let req = test::TestRequest::put()
+        .uri("/api/number-sequences/sale_order")
+        .set_json(json!({
+            "pattern": "SO-{00000}",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
+}
@@ -0,0 +1,105 @@
+use sea_orm::prelude::Date;
+
+use crate::utils::{currency::normalize_currency, price_lists::select_price};
+
+fn list(
+    id: i32,
+    currency: &str,
+    valid_from: Option<Date>,
+    valid_to: Option<Date>,
+) -> entity::price_lists::Model {
+    entity::price_lists::Model {
+        id,
+        name: format!("List {id}"),
+        currency: currency.to_string(),
+        valid_from,
+        valid_to,
+    }
+}
+
+fn item(
+    list: &entity::price_lists::Model,
+    min_quantity: f64,
+    price: i32,
+) -> (entity::price_lists::Model, entity::price_list_items::Model) {
+    let item = entity::price_list_items::Model {
+        id: list.id * 10 + min_quantity as i32,
+        price_list_id: list.id,
+        variant_id: 7,
+        min_quantity,
+        price,
+    };
+    (list.clone(), item)
+}
+
+fn date(month: u32, day: u32) -> Date {
+    Date::from_ymd_opt(2026, month, day).unwrap()
+}
+
+#[test]
+fn test_normalize_currency() {
+    assert_eq!(normalize_currency("EUR"), Some("EUR".to_string()));
+    assert_eq!(normalize_currency(" chf "), Some("CHF".to_string()));
+    assert_eq!(normalize_currency("EURO"), None);
+    assert_eq!(normalize_currency("E1R"), None);
+    assert_eq!(normalize_currency(""), None);
+}
+
+#[test]
+fn test_select_price_quantity_breaks() {
+    let yearly = list(1, "EUR", None, None);
+    let items = vec![
+        item(&yearly, 0.0, 1000),
+        item(&yearly, 10.0, 900),
+        item(&yearly, 100.0, 800),
+    ];
+    let select = |quantity| select_price(&items, "EUR", date(3, 1), quantity);
+
+    assert_eq!(select(1.0), Some(1000));
+    assert_eq!(select(9.5), Some(1000));
+    assert_eq!(select(10.0), Some(900));
+    assert_eq!(select(250.0), Some(800));
+}
+
+#[test]
+fn test_select_price_validity() {
+    let yearly = list(1, "EUR", Some(date(1, 1)), Some(date(12, 31)));
+    let summer = list(2, "EUR", Some(date(6, 1)), Some(date(8, 31)));
+    let items = vec![item(&yearly, 0.0, 1000), item(&summer, 0.0, 850)];
+    let select = |date| select_price(&items, "EUR", date, 1.0);
+
+    assert_eq!(select(date(5, 31)), Some(1000));
+    // The summer list starts later, so it beats the yearly one it overlaps.
+    assert_eq!(select(date(6, 1)), Some(850));
+    assert_eq!(select(date(8, 31)), Some(850));
+    assert_eq!(select(date(9, 1)), Some(1000));
+    assert_eq!(select(Date::from_ymd_opt(2027, 1, 1).unwrap()), None);
+}
+
+#[test]
+fn test_select_price_later_list_without_break() {
+    // The later list applies even where the earlier one has a better break,
+    // but only for quantities its own items cover.
+    let yearly = list(1, "EUR", Some(date(1, 1)), None);
+    let promotion = list(2, "EUR", Some(date(3, 1)), None);
+    let items = vec![
+        item(&yearly, 0.0, 1000),
+        item(&yearly, 50.0, 700),
+        item(&promotion, 5.0, 900),
+    ];
+    let select = |quantity| select_price(&items, "EUR", date(4, 1), quantity);
+
+    assert_eq!(select(1.0), Some(1000));
+    assert_eq!(select(50.0), Some(900));
+}
+
+#[test]
+fn test_select_price_currency() {
+    let euro = list(1, "EUR", None, None);
+    let franc = list(2, "CHF", None, None);
+    let items = vec![item(&euro, 0.0, 1000), item(&franc, 0.0, 1100)];
+
+    assert_eq!(select_price(&items, "EUR", date(1, 1), 1.0), Some(1000));
+    assert_eq!(select_price(&items, "CHF", date(1, 1), 1.0), Some(1100));
+    assert_eq!(select_price(&items, "USD", date(1, 1), 1.0), None);
+}
@@ -0,0 +1,145 @@
+use actix_web::{App, test, web};
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr, prelude::Date};
+use serde_json::json;
+
+use crate::{
+    handlers,
+    state::{AppState, MockAppState},
+};
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .price_lists
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq("Wholesale 2026".to_string()),
+            predicate::eq("CHF".to_string()),
+            predicate::eq(Some(Date::from_ymd_opt(2026, 1, 1).unwrap())),
+            predicate::eq(None),
+        )
+        .returning(|name, currency, valid_from, valid_to| {
+            Ok(entity::price_lists::Model {
+                id: 1,
+                name,
+                currency,
+                valid_from,
+                valid_to,
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::price_lists::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/price-lists")
+        .set_json(json!({
+            "name": "Wholesale 2026",
+            "currency": "chf",
+            "validFrom": "2026-01-01",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["id"], 1);
+    assert_eq!(body["currency"], "CHF");
+    assert_eq!(body["validFrom"], "2026-01-01");
+    assert_eq!(body["validTo"], serde_json::Value::Null);
+}
+
+#[actix_web::test]
+async fn test_invalid_currency() {
+    let mut state = MockAppState::new();
+    state.price_lists.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::price_lists::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/price-lists")
+        .set_json(json!({
+            "name": "Wholesale 2026",
+            "currency": "€",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVALID_CURRENCY");
+}
+
+#[actix_web::test]
+async fn test_invalid_date_range() {
+    let mut state = MockAppState::new();
+    state.price_lists.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::price_lists::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/price-lists")
+        .set_json(json!({
+            "name": "Summer",
+            "currency": "EUR",
+            "validFrom": "2026-08-31",
+            "validTo": "2026-06-01",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVALID_DATE_RANGE");
+}
+
+#[actix_web::test]
+async fn test_connection_error() {
+    let mut state = MockAppState::new();
+    state
+        .price_lists
+        .expect_create()
+        .once()
+        .returning(|_, _, _, _| {
+            let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+            Err(err)
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::price_lists::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/price-lists")
+        .set_json(json!({
+            "name": "Wholesale 2026",
+            "currency": "EUR",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
+}
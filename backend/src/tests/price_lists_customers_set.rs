@@ -0,0 +1,102 @@
+use actix_web::{App, test, web};
+use mockall::predicate;
+use sea_orm::DbErr;
+use serde_json::json;
+
+use crate::{
+    handlers,
+    state::{AppState, MockAppState},
+    tests::fixtures::{customer, supplier},
+};
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .contacts
+        .expect_get()
+        .times(2)
+        .returning(|id| Ok(Some(customer(id))));
+    state
+        .price_lists
+        .expect_set_customers()
+        .once()
+        .with(predicate::eq(3), predicate::eq(vec![4, 9]))
+        .returning(|_, customer_ids| Ok(customer_ids));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::price_lists::set_customers),
+    )
+    .await;
+
+    let req = test::TestRequest::put()
+        .uri("/api/price-lists/3/customers")
+        .set_json(json!({ "customerIds": [9, 4, 9] }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body, json!([4, 9]));
+}
+
+#[actix_web::test]
+async fn test_supplier() {
+    let mut state = MockAppState::new();
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(supplier(id))));
+    state.price_lists.expect_set_customers().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::price_lists::set_customers),
+    )
+    .await;
+
+    let req = test::TestRequest::put()
+        .uri("/api/price-lists/3/customers")
+        .set_json(json!({ "customerIds": [4] }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "CONTACT_NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn test_price_list_not_found() {
+    let mut state = MockAppState::new();
+    state.contacts.expect_get().never();
+    state
+        .price_lists
+        .expect_set_customers()
+        .once()
+        .returning(|_, _| Err(DbErr::RecordNotFound("price list".to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::price_lists::set_customers),
+    )
+    .await;
+
+    let req = test::TestRequest::put()
+        .uri("/api/price-lists/3/customers")
+        .set_json(json!({ "customerIds": [] }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "PRICE_LIST_NOT_FOUND");
+}
@@ -0,0 +1,163 @@
+use actix_web::{App, test, web};
+use mockall::predicate;
+use sea_orm::DbErr;
+use serde_json::json;
+
+use crate::{
+    handlers,
+    repositories::ERR_PRICE_LIST_ITEM_EXISTS,
+    state::{AppState, MockAppState},
+    tests::fixtures::variant,
+};
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .variants
+        .expect_get()
+        .once()
+        .with(predicate::eq(7))
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .price_lists
+        .expect_create_item()
+        .once()
+        .with(
+            predicate::eq(3),
+            predicate::eq(7),
+            predicate::eq(10.0),
+            predicate::eq(4500),
+        )
+        .returning(|price_list_id, variant_id, min_quantity, price| {
+            Ok(entity::price_list_items::Model {
+                id: 1,
+                price_list_id,
+                variant_id,
+                min_quantity,
+                price,
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::price_lists::create_item),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/price-lists/3/items")
+        .set_json(json!({
+            "variantId": 7,
+            "minQuantity": 10.0,
+            "price": 4500,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["priceListId"], 3);
+    assert_eq!(body["variantId"], 7);
+    assert_eq!(body["minQuantity"], 10.0);
+    assert_eq!(body["price"], 4500);
+}
+
+#[actix_web::test]
+async fn test_variant_not_found() {
+    let mut state = MockAppState::new();
+    state.variants.expect_get().once().returning(|_| Ok(None));
+    state.price_lists.expect_create_item().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::price_lists::create_item),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/price-lists/3/items")
+        .set_json(json!({ "variantId": 7, "price": 4500 }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "VARIANT_NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn test_price_list_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .price_lists
+        .expect_create_item()
+        .once()
+        .returning(|_, _, _, _| Err(DbErr::RecordNotFound("price list".to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::price_lists::create_item),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/price-lists/3/items")
+        .set_json(json!({ "variantId": 7, "price": 4500 }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "PRICE_LIST_NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn test_exists() {
+    let mut state = MockAppState::new();
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .price_lists
+        .expect_create_item()
+        .once()
+        .with(
+            predicate::eq(3),
+            predicate::eq(7),
+            predicate::eq(0.0),
+            predicate::eq(4500),
+        )
+        .returning(|_, _, _, _| Err(DbErr::Custom(ERR_PRICE_LIST_ITEM_EXISTS.to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::price_lists::create_item),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/price-lists/3/items")
+        .set_json(json!({ "variantId": 7, "price": 4500 }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "PRICE_LIST_ITEM_EXISTS");
+}
@@ -0,0 +1,157 @@
+use actix_web::{App, test, web};
+use mockall::predicate;
+use sea_orm::DbErr;
+use serde_json::json;
+
+use crate::{
+    handlers,
+    repositories::ERR_PRODUCT_ATTRIBUTE_EXISTS,
+    state::{AppState, MockAppState},
+    utils::variants::Attribute,
+};
+
+fn created(product_id: i32, name: String, values: Vec<String>) -> Attribute {
+    let attribute = entity::product_attributes::Model {
+        id: 1,
+        product_id,
+        name,
+        position: 1,
+    };
+    let values = values
+        .into_iter()
+        .enumerate()
+        .map(|(index, name)| entity::product_attribute_values::Model {
+            id: index as i32 + 10,
+            attribute_id: 1,
+            name,
+            position: index as i32 + 1,
+        })
+        .collect();
+    (attribute, values)
+}
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .product_attributes
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq(5),
+            predicate::eq("Size".to_string()),
+            predicate::eq(vec!["S".to_string(), "M".to_string(), "L".to_string()]),
+        )
+        .returning(|product_id, name, values| Ok(created(product_id, name, values)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::product_attributes::create),
+    )
+    .await;
+
+    // Blank and repeated values are dropped.
+    let req = test::TestRequest::post()
+        .uri("/api/products/5/attributes")
+        .set_json(json!({
+            "name": " Size ",
+            "values": ["S", " M", "", "L", "M"],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["productId"], 5);
+    assert_eq!(body["name"], "Size");
+    assert_eq!(body["values"][1]["name"], "M");
+    assert_eq!(body["values"][1]["position"], 2);
+}
+
+#[actix_web::test]
+async fn test_missing_values() {
+    let mut state = MockAppState::new();
+    state.product_attributes.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::product_attributes::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/products/5/attributes")
+        .set_json(json!({
+            "name": "Size",
+            "values": [],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+}
+
+#[actix_web::test]
+async fn test_product_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .product_attributes
+        .expect_create()
+        .once()
+        .returning(|_, _, _| Err(DbErr::RecordNotFound("product".to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::product_attributes::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/products/5/attributes")
+        .set_json(json!({
+            "name": "Size",
+            "values": ["S"],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "PRODUCT_NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn test_exists() {
+    let mut state = MockAppState::new();
+    state
+        .product_attributes
+        .expect_create()
+        .once()
+        .returning(|_, _, _| Err(DbErr::Custom(ERR_PRODUCT_ATTRIBUTE_EXISTS.to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::product_attributes::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/products/5/attributes")
+        .set_json(json!({
+            "name": "Size",
+            "values": ["S"],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "PRODUCT_ATTRIBUTE_EXISTS");
+}
@@ -0,0 +1,71 @@
+use crate::utils::product_categories::{CategoryDefaults, category_defaults};
+
+fn category(
+    id: i32,
+    parent_id: Option<i32>,
+    income_account_id: Option<i32>,
+    tax_id: Option<i32>,
+) -> entity::product_categories::Model {
+    entity::product_categories::Model {
+        id,
+        name: format!("Category {id}"),
+        parent_id,
+        income_account_id,
+        expense_account_id: None,
+        inventory_account_id: None,
+        tax_id,
+        sale_discount_account_id: None,
+        purchase_discount_account_id: None,
+    }
+}
+
+#[test]
+fn test_own_defaults() {
+    let categories = vec![category(1, None, Some(8400), Some(7))];
+    assert_eq!(
+        category_defaults(&categories, 1),
+        CategoryDefaults {
+            income_account_id: Some(8400),
+            tax_id: Some(7),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_nearest_ancestor_wins() {
+    let categories = vec![
+        category(1, None, Some(8400), Some(7)),
+        category(2, Some(1), Some(8300), None),
+        category(3, Some(2), None, None),
+    ];
+    assert_eq!(
+        category_defaults(&categories, 3),
+        CategoryDefaults {
+            income_account_id: Some(8300),
+            tax_id: Some(7),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_unknown_category() {
+    let categories = vec![category(1, None, Some(8400), None)];
+    assert_eq!(
+        category_defaults(&categories, 2),
+        CategoryDefaults::default()
+    );
+}
+
+#[test]
+fn test_cycle_ends() {
+    let categories = vec![
+        category(1, Some(2), None, None),
+        category(2, Some(1), None, None),
+    ];
+    assert_eq!(
+        category_defaults(&categories, 1),
+        CategoryDefaults::default()
+    );
+}
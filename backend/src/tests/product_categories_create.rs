@@ -0,0 +1,221 @@
+use actix_web::{App, test, web};
+use mockall::predicate;
+use sea_orm::DbErr;
+use serde_json::json;
+
+use crate::{
+    handlers,
+    repositories::{ERR_PRODUCT_CATEGORY_IN_USE, ProductCategoryDraft},
+    state::{AppState, MockAppState},
+};
+
+fn category(id: i32, name: &str, parent_id: Option<i32>) -> entity::product_categories::Model {
+    entity::product_categories::Model {
+        id,
+        name: name.to_string(),
+        parent_id,
+        income_account_id: None,
+        expense_account_id: None,
+        inventory_account_id: None,
+        tax_id: None,
+        sale_discount_account_id: None,
+        purchase_discount_account_id: None,
+    }
+}
+
+fn account(id: i32) -> entity::ledger_accounts::Model {
+    entity::ledger_accounts::Model {
+        id,
+        account_type: "income".to_string(),
+        name: "Revenue".to_string(),
+    }
+}
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .product_categories
+        .expect_get()
+        .once()
+        .with(predicate::eq(1))
+        .returning(|id| Ok(Some(category(id, "Kitchen", None))));
+    state
+        .ledger_accounts
+        .expect_get()
+        .once()
+        .with(predicate::eq(8400))
+        .returning(|id| Ok(Some(account(id))));
+    state
+        .product_categories
+        .expect_create()
+        .once()
+        .with(predicate::eq(ProductCategoryDraft {
+            name: "Mugs".to_string(),
+            parent_id: Some(1),
+            income_account_id: Some(8400),
+            expense_account_id: None,
+            inventory_account_id: None,
+            tax_id: None,
+            sale_discount_account_id: None,
+            purchase_discount_account_id: None,
+        }))
+        .returning(|draft| {
+            Ok(entity::product_categories::Model {
+                income_account_id: draft.income_account_id,
+                ..category(2, &draft.name, draft.parent_id)
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::product_categories::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/product-categories")
+        .set_json(json!({
+            "name": " Mugs ",
+            "parentId": 1,
+            "incomeAccountId": 8400,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["name"], "Mugs");
+    assert_eq!(body["parentId"], 1);
+    assert_eq!(body["incomeAccountId"], 8400);
+    assert_eq!(body["expenseAccountId"], serde_json::Value::Null);
+}
+
+#[actix_web::test]
+async fn test_parent_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .product_categories
+        .expect_get()
+        .once()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(None));
+    state.product_categories.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::product_categories::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/product-categories")
+        .set_json(json!({
+            "name": "Mugs",
+            "parentId": 1,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "PRODUCT_CATEGORY_NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn test_account_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .ledger_accounts
+        .expect_get()
+        .once()
+        .with(predicate::eq(5400))
+        .returning(|_| Ok(None));
+    state.product_categories.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::product_categories::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/product-categories")
+        .set_json(json!({
+            "name": "Mugs",
+            "expenseAccountId": 5400,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "ACCOUNT_NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn test_tax_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .taxes
+        .expect_get()
+        .once()
+        .with(predicate::eq(7))
+        .returning(|_| Ok(None));
+    state.product_categories.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::product_categories::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/product-categories")
+        .set_json(json!({
+            "name": "Mugs",
+            "taxId": 7,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "TAX_NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn test_delete_in_use() {
+    let mut state = MockAppState::new();
+    state
+        .product_categories
+        .expect_delete()
+        .once()
+        .with(predicate::eq(1))
+        .returning(|_| Err(DbErr::Custom(ERR_PRODUCT_CATEGORY_IN_USE.to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::product_categories::delete),
+    )
+    .await;
+
+    let req = test::TestRequest::delete()
+        .uri("/api/product-categories/1")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "PRODUCT_CATEGORY_IN_USE");
+}
@@ -1,4 +1,5 @@
 use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{CostingMethod, ProductType, Tracking};
 use mockall::predicate;
 use sea_orm::{DbErr, RuntimeErr};
 use serde_json::json;
@@ -15,8 +16,27 @@ async fn test_success() {
         .products
         .expect_create()
         .once()
-        .with(predicate::eq("Mug".to_string()))
-        .returning(|name| Ok(entity::products::Model { id: 1, name }));
+        .with(
+            predicate::eq("Mug".to_string()),
+            predicate::eq("standard".to_string()),
+            predicate::eq(None),
+            predicate::eq(ProductType::Stockable),
+            predicate::eq(CostingMethod::Fifo),
+            predicate::eq(Tracking::None),
+        )
+        .returning(
+            |name, tax_category, category_id, product_type, costing_method, tracking| {
+                Ok(entity::products::Model {
+                    id: 1,
+                    name,
+                    tax_category,
+                    category_id,
+                    product_type,
+                    costing_method,
+                    tracking,
+                })
+            },
+        );
 
     let app = test::init_service(
         App::new()
@@ -66,8 +86,15 @@ async fn test_connection_error() {
         .products
         .expect_create()
         .once()
-        .with(predicate::eq("Mug".to_string()))
-        .returning(|_| {
+        .with(
+            predicate::eq("Mug".to_string()),
+            predicate::eq("standard".to_string()),
+            predicate::eq(None),
+            predicate::eq(ProductType::Stockable),
+            predicate::eq(CostingMethod::Fifo),
+            predicate::eq(Tracking::None),
+        )
+        .returning(|_, _, _, _, _, _| {
             let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
             Err(err)
         });
@@ -89,3 +116,339 @@ async fn test_connection_error() {
     let res = test::call_service(&app, req).await;
     assert_eq!(res.status().as_u16(), 500u16);
 }
+
+#[actix_web::test]
+async fn test_with_category() {
+    let mut state = MockAppState::new();
+    state
+        .product_categories
+        .expect_get()
+        .once()
+        .with(predicate::eq(3))
+        .returning(|id| {
+            Ok(Some(entity::product_categories::Model {
+                id,
+                name: "Kitchen".to_string(),
+                parent_id: None,
+                income_account_id: Some(8400),
+                expense_account_id: None,
+                inventory_account_id: None,
+                tax_id: None,
+                sale_discount_account_id: None,
+                purchase_discount_account_id: None,
+            }))
+        });
+    state
+        .products
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq("Mug".to_string()),
+            predicate::eq("standard".to_string()),
+            predicate::eq(Some(3)),
+            predicate::eq(ProductType::Stockable),
+            predicate::eq(CostingMethod::Fifo),
+            predicate::eq(Tracking::None),
+        )
+        .returning(
+            |name, tax_category, category_id, product_type, costing_method, tracking| {
+                Ok(entity::products::Model {
+                    id: 1,
+                    name,
+                    tax_category,
+                    category_id,
+                    product_type,
+                    costing_method,
+                    tracking,
+                })
+            },
+        );
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::products::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/products")
+        .set_json(json!({
+            "name": "Mug",
+            "categoryId": 3,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["categoryId"], 3);
+}
+
+#[actix_web::test]
+async fn test_category_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .product_categories
+        .expect_get()
+        .once()
+        .with(predicate::eq(3))
+        .returning(|_| Ok(None));
+    state.products.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::products::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/products")
+        .set_json(json!({
+            "name": "Mug",
+            "categoryId": 3,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "PRODUCT_CATEGORY_NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn test_service() {
+    let mut state = MockAppState::new();
+    state
+        .products
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq("Consulting".to_string()),
+            predicate::eq("standard".to_string()),
+            predicate::eq(None),
+            predicate::eq(ProductType::Service),
+            predicate::eq(CostingMethod::Fifo),
+            predicate::eq(Tracking::None),
+        )
+        .returning(
+            |name, tax_category, category_id, product_type, costing_method, tracking| {
+                Ok(entity::products::Model {
+                    id: 1,
+                    name,
+                    tax_category,
+                    category_id,
+                    product_type,
+                    costing_method,
+                    tracking,
+                })
+            },
+        );
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::products::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/products")
+        .set_json(json!({
+            "name": "Consulting",
+            "productType": "service",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["productType"], "service");
+}
+
+#[actix_web::test]
+async fn test_invalid_product_type() {
+    let mut state = MockAppState::new();
+    state.products.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::products::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/products")
+        .set_json(json!({
+            "name": "Consulting",
+            "productType": "digital",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVALID_PRODUCT_TYPE");
+}
+
+#[actix_web::test]
+async fn test_average_cost() {
+    let mut state = MockAppState::new();
+    state
+        .products
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq("Flour".to_string()),
+            predicate::eq("standard".to_string()),
+            predicate::eq(None),
+            predicate::eq(ProductType::Stockable),
+            predicate::eq(CostingMethod::Average),
+            predicate::eq(Tracking::None),
+        )
+        .returning(
+            |name, tax_category, category_id, product_type, costing_method, tracking| {
+                Ok(entity::products::Model {
+                    id: 1,
+                    name,
+                    tax_category,
+                    category_id,
+                    product_type,
+                    costing_method,
+                    tracking,
+                })
+            },
+        );
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::products::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/products")
+        .set_json(json!({
+            "name": "Flour",
+            "costingMethod": "average",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["costingMethod"], "average");
+}
+
+#[actix_web::test]
+async fn test_invalid_costing_method() {
+    let mut state = MockAppState::new();
+    state.products.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::products::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/products")
+        .set_json(json!({
+            "name": "Flour",
+            "costingMethod": "lifo",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVALID_COSTING_METHOD");
+}
+
+#[actix_web::test]
+async fn test_serial_tracking() {
+    let mut state = MockAppState::new();
+    state
+        .products
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq("Scanner".to_string()),
+            predicate::eq("standard".to_string()),
+            predicate::eq(None),
+            predicate::eq(ProductType::Stockable),
+            predicate::eq(CostingMethod::Fifo),
+            predicate::eq(Tracking::Serial),
+        )
+        .returning(
+            |name, tax_category, category_id, product_type, costing_method, tracking| {
+                Ok(entity::products::Model {
+                    id: 1,
+                    name,
+                    tax_category,
+                    category_id,
+                    product_type,
+                    costing_method,
+                    tracking,
+                })
+            },
+        );
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::products::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/products")
+        .set_json(json!({
+            "name": "Scanner",
+            "tracking": "serial",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["tracking"], "serial");
+}
+
+#[actix_web::test]
+async fn test_invalid_tracking() {
+    let mut state = MockAppState::new();
+    state.products.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::products::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/products")
+        .set_json(json!({
+            "name": "Scanner",
+            "tracking": "batch",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVALID_TRACKING");
+}
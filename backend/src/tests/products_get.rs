@@ -1,4 +1,5 @@
 use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{CostingMethod, ProductType, Tracking};
 use mockall::predicate;
 use sea_orm::{DbErr, RuntimeErr};
 
@@ -19,6 +20,11 @@ async fn test_success() {
             Ok(Some(entity::products::Model {
                 id,
                 name: "Dummy product".to_string(),
+                tax_category: "standard".to_string(),
+                category_id: None,
+                product_type: ProductType::Stockable,
+                costing_method: CostingMethod::Fifo,
+                tracking: Tracking::None,
             }))
         });
 
@@ -1,39 +1,274 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::PurchaseInvoiceStatus;
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr, prelude::Date};
+use serde_json::json;
+
+use crate::{
+    handlers,
+    repositories::ERR_PERIOD_CLOSED,
+    state::{AppState, MockAppState},
+    utils::totals::Discount,
+};
+
 #[actix_web::test]
 async fn test_success() {
-    todo!();
+    let mut state = MockAppState::new();
+    state
+        .purchase_invoices
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq("R-1001".to_string()),
+            predicate::eq(Date::from_ymd_opt(2025, 7, 1).unwrap()),
+            predicate::eq(PurchaseInvoiceStatus::Draft),
+            predicate::eq(12),
+            predicate::eq(7),
+            predicate::eq(Discount::default()),
+        )
+        .returning(
+            |supplier_reference, date, status, order_id, supplier_id, discount| {
+                Ok(entity::purchase_invoices::Model {
+                    id: 1,
+                    reference: "PI-2025-00005".to_string(),
+                    date,
+                    status,
+                    order_id: Some(order_id),
+                    supplier_id: Some(supplier_id),
+                    needs_review: false,
+                    review_note: "".to_string(),
+                    supplier_reference,
+                    discount_percent: discount.percent,
+                    discount_amount: discount.amount as i32,
+                })
+            },
+        );
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_invoices::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices")
+        .set_json(json!({
+            "supplierReference": "R-1001",
+            "date": "2025-07-01",
+            "status": "draft",
+            "orderId": 12,
+            "supplierId": 7,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["reference"], "PI-2025-00005");
+    assert_eq!(body["supplierReference"], "R-1001");
 }
 
 #[actix_web::test]
-async fn test_customer_invalid_reference() {
-    todo!();
+async fn test_invalid_date() {
+    let mut state = MockAppState::new();
+    state.purchase_invoices.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_invoices::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices")
+        .set_json(json!({
+            "supplierReference": "R-1001",
+            "date": "01.07.2025",
+            "status": "draft",
+            "orderId": 12,
+            "supplierId": 7,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
 }
 
 #[actix_web::test]
-async fn test_supplier_reference_unique() {
-    todo!();
+async fn test_invalid_status() {
+    let mut state = MockAppState::new();
+    state.purchase_invoices.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_invoices::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices")
+        .set_json(json!({
+            "supplierReference": "R-1001",
+            "date": "2025-07-01",
+            "status": "paid",
+            "orderId": 12,
+            "supplierId": 7,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
 }
 
 #[actix_web::test]
-async fn test_customer_invalid_date() {
-    todo!();
+async fn test_invalid_supplier_reference() {
+    let mut state = MockAppState::new();
+    state.purchase_invoices.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_invoices::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices")
+        .set_json(json!({
+            "supplierReference": "",
+            "date": "2025-07-01",
+            "status": "draft",
+            "orderId": 12,
+            "supplierId": 7,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
 }
 
 #[actix_web::test]
-async fn test_supplier_invalid_status() {
-    todo!();
+async fn test_invalid_order_id() {
+    let mut state = MockAppState::new();
+    state.purchase_invoices.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_invoices::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices")
+        .set_json(json!({
+            "supplierReference": "R-1001",
+            "date": "2025-07-01",
+            "status": "draft",
+            "orderId": 0,
+            "supplierId": 7,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
 }
 
 #[actix_web::test]
-async fn test_supplier_invalid_order_id() {
-    todo!();
+async fn test_invalid_supplier_id() {
+    let mut state = MockAppState::new();
+    state.purchase_invoices.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_invoices::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices")
+        .set_json(json!({
+            "supplierReference": "R-1001",
+            "date": "2025-07-01",
+            "status": "draft",
+            "orderId": 12,
+            "supplierId": 0,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
 }
 
 #[actix_web::test]
-async fn test_supplier_invalid_supplier_id() {
-    todo!();
+async fn test_period_closed() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_invoices
+        .expect_create()
+        .once()
+        .returning(|_, _, _, _, _, _| {
+            let err = DbErr::Custom(ERR_PERIOD_CLOSED.to_string());
+            Err(err)
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_invoices::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices")
+        .set_json(json!({
+            "supplierReference": "R-1001",
+            "date": "2025-07-01",
+            "status": "draft",
+            "orderId": 12,
+            "supplierId": 7,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
 }
 
 #[actix_web::test]
 async fn test_connection_error() {
-    todo!();
+    let mut state = MockAppState::new();
+    state
+        .purchase_invoices
+        .expect_create()
+        .once()
+        .returning(|_, _, _, _, _, _| {
+            let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+            Err(err)
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_invoices::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices")
+        .set_json(json!({
+            "supplierReference": "R-1001",
+            "date": "2025-07-01",
+            "status": "draft",
+            "orderId": 12,
+            "supplierId": 7,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
 }
@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{PurchaseInvoiceStatus, PurchaseOrderStatus};
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr, prelude::Date};
+use serde_json::json;
+
+use crate::{
+    handlers,
+    repositories::CreditNoteLineDraft,
+    state::{AppState, MockAppState},
+    tests::fixtures::{purchase_invoice, purchase_invoice_line, purchase_order},
+};
+
+fn invoice_lines(invoice_id: i32) -> Vec<entity::purchase_invoice_lines::Model> {
+    vec![entity::purchase_invoice_lines::Model {
+        description: "Beech boards".to_string(),
+        quantity: 10.0,
+        price: 1250,
+        variant_id: Some(8),
+        transaction_id: Some(11),
+        tax_transaction_id: Some(12),
+        ..purchase_invoice_line(1, invoice_id)
+    }]
+}
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_invoices
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|id| Ok(Some(purchase_invoice(id, PurchaseInvoiceStatus::Finished))));
+    state
+        .purchase_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|invoice_id| Ok(invoice_lines(invoice_id)));
+    state
+        .purchase_invoice_lines
+        .expect_list_taxes_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state
+        .purchase_credit_notes
+        .expect_list_lines_for_invoice()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|_| Ok(vec![]));
+    state
+        .purchase_orders
+        .expect_get()
+        .once()
+        .with(predicate::eq(3))
+        .returning(|id| {
+            Ok(Some(entity::purchase_orders::Model {
+                date: Date::from_ymd_opt(2025, 6, 20).unwrap(),
+                source_stock_id: 6,
+                target_stock_id: 1,
+                ..purchase_order(id, PurchaseOrderStatus::Finished)
+            }))
+        });
+    state
+        .variants
+        .expect_stockable_ids()
+        .once()
+        .with(predicate::eq(vec![8]))
+        .returning(Ok);
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .returning(|_| Ok(HashMap::new()));
+    state
+        .purchase_credit_notes
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq(5),
+            predicate::eq(Date::from_ymd_opt(2025, 7, 10).unwrap()),
+            predicate::eq("Wrong thickness".to_string()),
+            predicate::eq(vec![CreditNoteLineDraft {
+                invoice_line_id: 1,
+                description: "Beech boards".to_string(),
+                quantity: 4.0,
+                price: 1250,
+                tax: 19,
+                tax_id: None,
+                variant_id: Some(8),
+                net: 5000,
+                tax_amount: 950,
+                transaction_id: Some(11),
+                tax_transaction_id: Some(12),
+                tax_postings: vec![],
+                discount: 0,
+                discount_transaction_id: None,
+                stock_return: Some((1, 6)),
+                lots: Vec::new(),
+            }]),
+        )
+        .returning(|invoice_id, date, reason, lines| {
+            let credit_note = entity::purchase_credit_notes::Model {
+                id: 1,
+                reference: "PCN-2025-00001".to_string(),
+                date,
+                invoice_id,
+                reason,
+            };
+            let lines = lines
+                .into_iter()
+                .map(|line| entity::purchase_credit_note_lines::Model {
+                    id: 1,
+                    credit_note_id: 1,
+                    invoice_line_id: line.invoice_line_id,
+                    description: line.description,
+                    quantity: line.quantity,
+                    price: line.price,
+                    tax: line.tax,
+                    tax_id: line.tax_id,
+                    variant_id: line.variant_id,
+                    transaction_id: Some(21),
+                    tax_transaction_id: Some(22),
+                    move_id: Some(30),
+                    discount_amount: 0,
+                    discount_transaction_id: None,
+                })
+                .collect();
+            Ok((credit_note, lines))
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::create_purchase),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices/5/credit-notes")
+        .set_json(json!({
+            "date": "2025-07-10",
+            "reason": "Wrong thickness",
+            "returnStock": true,
+            "lines": [{ "invoiceLineId": 1, "quantity": 4.0 }],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["reference"], "PCN-2025-00001");
+    assert_eq!(body["grossTotal"], 5950);
+    assert_eq!(body["lines"][0]["moveId"], 30);
+}
+
+#[actix_web::test]
+async fn test_return_without_order() {
+    let mut state = MockAppState::new();
+    state.purchase_invoices.expect_get().once().returning(|id| {
+        Ok(Some(entity::purchase_invoices::Model {
+            order_id: None,
+            ..purchase_invoice(id, PurchaseInvoiceStatus::Finished)
+        }))
+    });
+    state
+        .purchase_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|invoice_id| Ok(invoice_lines(invoice_id)));
+    state
+        .purchase_invoice_lines
+        .expect_list_taxes_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state
+        .purchase_credit_notes
+        .expect_list_lines_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state.purchase_credit_notes.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::create_purchase),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices/5/credit-notes")
+        .set_json(json!({ "date": "2025-07-10", "returnStock": true }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "STOCK_RETURN_WITHOUT_ORDER");
+}
+
+#[actix_web::test]
+async fn test_not_posted() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_invoices
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(purchase_invoice(id, PurchaseInvoiceStatus::Draft))));
+    state.purchase_credit_notes.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::create_purchase),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices/5/credit-notes")
+        .set_json(json!({ "date": "2025-07-10" }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+}
+
+#[actix_web::test]
+async fn test_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_invoices
+        .expect_get()
+        .once()
+        .returning(|_| Ok(None));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::create_purchase),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices/5/credit-notes")
+        .set_json(json!({ "date": "2025-07-10" }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+}
+
+#[actix_web::test]
+async fn test_connection_error() {
+    let mut state = MockAppState::new();
+    state.purchase_invoices.expect_get().once().returning(|_| {
+        let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+        Err(err)
+    });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::create_purchase),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices/5/credit-notes")
+        .set_json(json!({ "date": "2025-07-10" }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
+}
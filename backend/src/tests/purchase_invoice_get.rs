@@ -1,14 +1,214 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::PurchaseInvoiceStatus;
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr, prelude::Date};
+
+use crate::{
+    handlers,
+    repositories::{ResolvedTax, TaxComponent},
+    state::{AppState, MockAppState},
+    tests::fixtures,
+};
+
+fn invoice(id: i32) -> entity::purchase_invoices::Model {
+    entity::purchase_invoices::Model {
+        reference: "PI-2020-00001".to_string(),
+        date: Date::from_ymd_opt(2020, 8, 1).unwrap(),
+        ..fixtures::purchase_invoice(id, PurchaseInvoiceStatus::Finished)
+    }
+}
+
+fn line(
+    id: i32,
+    invoice_id: i32,
+    price: i32,
+    tax_id: Option<i32>,
+) -> entity::purchase_invoice_lines::Model {
+    entity::purchase_invoice_lines::Model {
+        description: "Chair".to_string(),
+        price,
+        tax_id,
+        ..fixtures::purchase_invoice_line(id, invoice_id)
+    }
+}
+
+fn tax(id: i32, name_short: &str, rate: f64) -> entity::taxes::Model {
+    entity::taxes::Model {
+        name: name_short.to_string(),
+        name_short: name_short.to_string(),
+        rate,
+        ..fixtures::tax(id)
+    }
+}
+
 #[actix_web::test]
 async fn test_success() {
-    todo!();
+    let mut state = MockAppState::new();
+    state
+        .purchase_invoices
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|id| Ok(Some(invoice(id))));
+    state
+        .purchase_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|invoice_id| {
+            Ok(vec![
+                line(1, invoice_id, 10000, Some(7)),
+                line(2, invoice_id, 1500, None),
+            ])
+        });
+    state
+        .taxes
+        .expect_resolve_at()
+        .once()
+        // The rate of the invoice date, not the 19% stored on the line.
+        .with(
+            predicate::eq(7),
+            predicate::eq(Date::from_ymd_opt(2020, 8, 1).unwrap()),
+        )
+        .returning(|id, _| Ok(Some(ResolvedTax::plain(tax(id, "16%", 0.16)))));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_invoices::get),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/purchase-invoices/5")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["invoice"]["reference"], "PI-2020-00001");
+    assert_eq!(body["lines"][1]["price"], 1500);
+    assert_eq!(body["totals"]["lines"][0]["tax"], 1600);
+    assert_eq!(body["totals"]["lines"][1]["lineId"], 2);
+    assert_eq!(body["totals"]["lines"][1]["tax"], 285);
+    assert_eq!(body["totals"]["taxes"][0]["rate"], 0.16);
+    assert_eq!(body["totals"]["taxes"][1]["rate"], 0.19);
+    assert_eq!(body["totals"]["netTotal"], 11500);
+    assert_eq!(body["totals"]["taxTotal"], 1885);
+    assert_eq!(body["totals"]["grossTotal"], 13385);
+}
+
+#[actix_web::test]
+async fn test_tax_group() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_invoices
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(invoice(id))));
+    state
+        .purchase_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|invoice_id| Ok(vec![line(1, invoice_id, 10000, Some(9))]));
+    state.taxes.expect_resolve_at().once().returning(|id, _| {
+        let mut resolved = ResolvedTax::plain(tax(id, "GST+PST", 0.0));
+        resolved.components = vec![
+            TaxComponent {
+                tax: tax(21, "GST", 0.05),
+                compound: false,
+            },
+            TaxComponent {
+                tax: tax(22, "PST", 0.07),
+                compound: true,
+            },
+        ];
+        Ok(Some(resolved))
+    });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_invoices::get),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/purchase-invoices/5")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    // The compound PST is levied on the net amount plus the GST.
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["totals"]["lines"][0]["tax"], 1235);
+    assert_eq!(body["totals"]["taxes"][0]["base"], 10000);
+    assert_eq!(body["totals"]["taxes"][0]["amount"], 500);
+    assert_eq!(body["totals"]["taxes"][1]["base"], 10500);
+    assert_eq!(body["totals"]["taxes"][1]["amount"], 735);
+    assert_eq!(body["totals"]["grossTotal"], 11235);
 }
 
 #[actix_web::test]
 async fn test_not_found() {
-    todo!();
+    let mut state = MockAppState::new();
+    state
+        .purchase_invoices
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|_| Ok(None));
+    state
+        .purchase_invoice_lines
+        .expect_list_for_invoice()
+        .never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_invoices::get),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/purchase-invoices/5")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
 }
 
 #[actix_web::test]
 async fn test_connection_error() {
-    todo!();
+    let mut state = MockAppState::new();
+    state
+        .purchase_invoices
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(invoice(id))));
+    state
+        .purchase_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|invoice_id| Ok(vec![line(1, invoice_id, 10000, Some(7))]));
+    state.taxes.expect_resolve_at().once().returning(|_, _| {
+        let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+        Err(err)
+    });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_invoices::get),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/purchase-invoices/5")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
 }
@@ -0,0 +1,745 @@
+use std::collections::HashMap;
+
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{PurchaseOrderStatus, SaleInvoiceStatus};
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr, prelude::Date};
+
+use crate::{
+    documents::{Letterhead, PrintableDocument},
+    handlers,
+    state::{AppState, MockAppState},
+    tests::fixtures,
+};
+
+const UBL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ubl:Invoice xmlns:ubl="urn:oasis:names:specification:ubl:schema:xsd:Invoice-2"
+    xmlns:cac="urn:oasis:names:specification:ubl:schema:xsd:CommonAggregateComponents-2"
+    xmlns:cbc="urn:oasis:names:specification:ubl:schema:xsd:CommonBasicComponents-2">
+  <cbc:ID>R-1001</cbc:ID>
+  <cbc:IssueDate>2025-07-03</cbc:IssueDate>
+  <cbc:InvoiceTypeCode>380</cbc:InvoiceTypeCode>
+  <cbc:DocumentCurrencyCode>EUR</cbc:DocumentCurrencyCode>
+  <cac:OrderReference><cbc:ID>PO-2025-00012</cbc:ID></cac:OrderReference>
+  <cac:AccountingSupplierParty><cac:Party>
+    <cac:PostalAddress>
+      <cbc:StreetName>Industriestraße 3</cbc:StreetName>
+      <cbc:CityName>München</cbc:CityName>
+      <cbc:PostalZone>80331</cbc:PostalZone>
+      <cac:Country><cbc:IdentificationCode>DE</cbc:IdentificationCode></cac:Country>
+    </cac:PostalAddress>
+    <cac:PartyTaxScheme>
+      <cbc:CompanyID>DE 987 654 321</cbc:CompanyID>
+      <cac:TaxScheme><cbc:ID>VAT</cbc:ID></cac:TaxScheme>
+    </cac:PartyTaxScheme>
+    <cac:PartyLegalEntity><cbc:RegistrationName>Holz AG</cbc:RegistrationName></cac:PartyLegalEntity>
+  </cac:Party></cac:AccountingSupplierParty>
+  <cac:AccountingCustomerParty><cac:Party>
+    <cac:PostalAddress>
+      <cac:Country><cbc:IdentificationCode>DE</cbc:IdentificationCode></cac:Country>
+    </cac:PostalAddress>
+    <cac:PartyLegalEntity><cbc:RegistrationName>Liro GmbH</cbc:RegistrationName></cac:PartyLegalEntity>
+  </cac:Party></cac:AccountingCustomerParty>
+  <cac:TaxTotal>
+    <cbc:TaxAmount currencyID="EUR">25.15</cbc:TaxAmount>
+    <cac:TaxSubtotal>
+      <cbc:TaxableAmount currencyID="EUR">125.00</cbc:TaxableAmount>
+      <cbc:TaxAmount currencyID="EUR">23.75</cbc:TaxAmount>
+      <cac:TaxCategory><cbc:ID>S</cbc:ID><cbc:Percent>19</cbc:Percent><cac:TaxScheme><cbc:ID>VAT</cbc:ID></cac:TaxScheme></cac:TaxCategory>
+    </cac:TaxSubtotal>
+    <cac:TaxSubtotal>
+      <cbc:TaxableAmount currencyID="EUR">20.00</cbc:TaxableAmount>
+      <cbc:TaxAmount currencyID="EUR">1.40</cbc:TaxAmount>
+      <cac:TaxCategory><cbc:ID>S</cbc:ID><cbc:Percent>7</cbc:Percent><cac:TaxScheme><cbc:ID>VAT</cbc:ID></cac:TaxScheme></cac:TaxCategory>
+    </cac:TaxSubtotal>
+  </cac:TaxTotal>
+  <cac:LegalMonetaryTotal>
+    <cbc:LineExtensionAmount currencyID="EUR">145.00</cbc:LineExtensionAmount>
+    <cbc:TaxExclusiveAmount currencyID="EUR">145.00</cbc:TaxExclusiveAmount>
+    <cbc:TaxInclusiveAmount currencyID="EUR">170.15</cbc:TaxInclusiveAmount>
+    <cbc:PayableAmount currencyID="EUR">170.15</cbc:PayableAmount>
+  </cac:LegalMonetaryTotal>
+  <cac:InvoiceLine>
+    <cbc:ID>1</cbc:ID>
+    <cbc:InvoicedQuantity unitCode="C62">10</cbc:InvoicedQuantity>
+    <cbc:LineExtensionAmount currencyID="EUR">125.00</cbc:LineExtensionAmount>
+    <cac:Item>
+      <cbc:Name>Oak board</cbc:Name>
+      <cac:ClassifiedTaxCategory><cbc:ID>S</cbc:ID><cbc:Percent>19</cbc:Percent><cac:TaxScheme><cbc:ID>VAT</cbc:ID></cac:TaxScheme></cac:ClassifiedTaxCategory>
+    </cac:Item>
+    <cac:Price><cbc:PriceAmount currencyID="EUR">12.50</cbc:PriceAmount></cac:Price>
+  </cac:InvoiceLine>
+  <cac:InvoiceLine>
+    <cbc:ID>2</cbc:ID>
+    <cbc:InvoicedQuantity unitCode="C62">2</cbc:InvoicedQuantity>
+    <cbc:LineExtensionAmount currencyID="EUR">20.00</cbc:LineExtensionAmount>
+    <cac:Item>
+      <cbc:Name>Assembly manual</cbc:Name>
+      <cac:ClassifiedTaxCategory><cbc:ID>S</cbc:ID><cbc:Percent>7</cbc:Percent><cac:TaxScheme><cbc:ID>VAT</cbc:ID></cac:TaxScheme></cac:ClassifiedTaxCategory>
+    </cac:Item>
+    <cac:Price><cbc:PriceAmount currencyID="EUR">10.00</cbc:PriceAmount></cac:Price>
+  </cac:InvoiceLine>
+</ubl:Invoice>"#;
+
+const CII: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rsm:CrossIndustryInvoice xmlns:rsm="urn:un:unece:uncefact:data:standard:CrossIndustryInvoice:100"
+    xmlns:ram="urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:100"
+    xmlns:udt="urn:un:unece:uncefact:data:standard:UnqualifiedDataType:100">
+  <rsm:ExchangedDocumentContext>
+    <ram:GuidelineSpecifiedDocumentContextParameter><ram:ID>urn:cen.eu:en16931:2017</ram:ID></ram:GuidelineSpecifiedDocumentContextParameter>
+  </rsm:ExchangedDocumentContext>
+  <rsm:ExchangedDocument>
+    <ram:ID>2025-0815</ram:ID>
+    <ram:TypeCode>380</ram:TypeCode>
+    <ram:IssueDateTime><udt:DateTimeString format="102">20250710</udt:DateTimeString></ram:IssueDateTime>
+  </rsm:ExchangedDocument>
+  <rsm:SupplyChainTradeTransaction>
+    <ram:IncludedSupplyChainTradeLineItem>
+      <ram:AssociatedDocumentLineDocument><ram:LineID>1</ram:LineID></ram:AssociatedDocumentLineDocument>
+      <ram:SpecifiedTradeProduct><ram:Name>Steel screws</ram:Name></ram:SpecifiedTradeProduct>
+      <ram:SpecifiedLineTradeAgreement>
+        <ram:NetPriceProductTradePrice><ram:ChargeAmount>0.25</ram:ChargeAmount></ram:NetPriceProductTradePrice>
+      </ram:SpecifiedLineTradeAgreement>
+      <ram:SpecifiedLineTradeDelivery><ram:BilledQuantity unitCode="C62">400</ram:BilledQuantity></ram:SpecifiedLineTradeDelivery>
+      <ram:SpecifiedLineTradeSettlement>
+        <ram:ApplicableTradeTax><ram:TypeCode>VAT</ram:TypeCode><ram:CategoryCode>S</ram:CategoryCode><ram:RateApplicablePercent>19</ram:RateApplicablePercent></ram:ApplicableTradeTax>
+        <ram:SpecifiedTradeSettlementLineMonetarySummation><ram:LineTotalAmount>100.00</ram:LineTotalAmount></ram:SpecifiedTradeSettlementLineMonetarySummation>
+      </ram:SpecifiedLineTradeSettlement>
+    </ram:IncludedSupplyChainTradeLineItem>
+    <ram:ApplicableHeaderTradeAgreement>
+      <ram:SellerTradeParty>
+        <ram:Name>Schrauben KG</ram:Name>
+        <ram:PostalTradeAddress><ram:CountryID>DE</ram:CountryID></ram:PostalTradeAddress>
+        <ram:SpecifiedTaxRegistration><ram:ID schemeID="VA">DE111222333</ram:ID></ram:SpecifiedTaxRegistration>
+      </ram:SellerTradeParty>
+      <ram:BuyerTradeParty>
+        <ram:Name>Liro GmbH</ram:Name>
+        <ram:PostalTradeAddress><ram:CountryID>DE</ram:CountryID></ram:PostalTradeAddress>
+      </ram:BuyerTradeParty>
+      <ram:BuyerOrderReferencedDocument><ram:IssuerAssignedID>PO-2025-00014</ram:IssuerAssignedID></ram:BuyerOrderReferencedDocument>
+    </ram:ApplicableHeaderTradeAgreement>
+    <ram:ApplicableHeaderTradeDelivery/>
+    <ram:ApplicableHeaderTradeSettlement>
+      <ram:InvoiceCurrencyCode>EUR</ram:InvoiceCurrencyCode>
+      <ram:ApplicableTradeTax>
+        <ram:CalculatedAmount>19.00</ram:CalculatedAmount>
+        <ram:TypeCode>VAT</ram:TypeCode>
+        <ram:BasisAmount>100.00</ram:BasisAmount>
+        <ram:CategoryCode>S</ram:CategoryCode>
+        <ram:RateApplicablePercent>19</ram:RateApplicablePercent>
+      </ram:ApplicableTradeTax>
+      <ram:SpecifiedTradeSettlementHeaderMonetarySummation>
+        <ram:LineTotalAmount>100.00</ram:LineTotalAmount>
+        <ram:TaxBasisTotalAmount>100.00</ram:TaxBasisTotalAmount>
+        <ram:TaxTotalAmount currencyID="EUR">19.00</ram:TaxTotalAmount>
+        <ram:GrandTotalAmount>119.00</ram:GrandTotalAmount>
+        <ram:DuePayableAmount>119.00</ram:DuePayableAmount>
+      </ram:SpecifiedTradeSettlementHeaderMonetarySummation>
+    </ram:ApplicableHeaderTradeSettlement>
+  </rsm:SupplyChainTradeTransaction>
+</rsm:CrossIndustryInvoice>"#;
+
+fn supplier(id: i32, name: &str, vat_id: &str) -> entity::contacts::Model {
+    entity::contacts::Model {
+        name: name.to_string(),
+        vat_id: vat_id.to_string(),
+        ..fixtures::supplier(id)
+    }
+}
+
+fn order(id: i32, reference: &str, supplier_id: i32) -> entity::purchase_orders::Model {
+    entity::purchase_orders::Model {
+        reference: reference.to_string(),
+        date: Date::from_ymd_opt(2025, 6, 20).unwrap(),
+        supplier_id,
+        ..fixtures::purchase_order(id, PurchaseOrderStatus::Draft)
+    }
+}
+
+fn tax(id: i32, rate: f64) -> entity::taxes::Model {
+    entity::taxes::Model {
+        name: format!("Input tax {}%", rate * 100.0),
+        name_short: format!("{}%", rate * 100.0),
+        rate,
+        account_id: 3,
+        ..fixtures::tax(id)
+    }
+}
+
+/// Lets the mocked repository assign ids the way the database would.
+fn expect_create(state: &mut MockAppState) {
+    state
+        .purchase_invoices
+        .expect_create_with_lines()
+        .once()
+        .returning(|invoice, lines| {
+            let invoice = entity::purchase_invoices::Model {
+                id: 9,
+                reference: "PI-2025-00001".to_string(),
+                date: invoice.date.unwrap(),
+                status: invoice.status.unwrap(),
+                order_id: invoice.order_id.unwrap(),
+                supplier_id: invoice.supplier_id.unwrap(),
+                needs_review: invoice.needs_review.unwrap(),
+                review_note: invoice.review_note.unwrap(),
+                supplier_reference: invoice.supplier_reference.unwrap(),
+                discount_percent: invoice.discount_percent.unwrap(),
+                discount_amount: invoice.discount_amount.unwrap(),
+            };
+            let lines = lines
+                .into_iter()
+                .enumerate()
+                .map(|(index, line)| entity::purchase_invoice_lines::Model {
+                    id: index as i32 + 1,
+                    description: line.description.unwrap(),
+                    quantity: line.quantity.unwrap(),
+                    price: line.price.unwrap(),
+                    tax: line.tax.unwrap(),
+                    invoice_id: invoice.id,
+                    variant_id: line.variant_id.unwrap(),
+                    transaction_id: line.transaction_id.unwrap(),
+                    tax_id: line.tax_id.unwrap(),
+                    tax_transaction_id: line.tax_transaction_id.unwrap(),
+                    discount_percent: line.discount_percent.unwrap(),
+                    discount_amount: line.discount_amount.unwrap(),
+                    discount_transaction_id: line.discount_transaction_id.unwrap(),
+                })
+                .collect();
+            Ok((invoice, lines))
+        });
+}
+
+#[actix_web::test]
+async fn test_ubl_success() {
+    let mut state = MockAppState::new();
+    state
+        .contacts
+        .expect_find_suppliers_by_vat_id()
+        .once()
+        .with(predicate::eq("DE987654321".to_string()))
+        .returning(|vat_id| Ok(vec![supplier(7, "Holz AG", &vat_id)]));
+    state.contacts.expect_find_suppliers_by_name().never();
+    state
+        .purchase_invoices
+        .expect_find_by_supplier_reference()
+        .once()
+        .with(predicate::eq(7), predicate::eq("R-1001".to_string()))
+        .returning(|_, _| Ok(None));
+    state
+        .purchase_orders
+        .expect_find_by_reference()
+        .once()
+        .with(predicate::eq("PO-2025-00012".to_string()))
+        .returning(|reference| Ok(vec![order(12, &reference, 7), order(13, &reference, 8)]));
+    state
+        .taxes
+        .expect_find_by_rate()
+        .once()
+        .with(predicate::eq(0.19))
+        .returning(|rate| Ok(vec![tax(1, rate)]));
+    state
+        .taxes
+        .expect_find_by_rate()
+        .once()
+        .with(predicate::eq(0.07))
+        .returning(|rate| Ok(vec![tax(2, rate)]));
+    expect_create(&mut state);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_invoices::import),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices/import")
+        .insert_header(("Content-Type", "application/xml"))
+        .set_payload(UBL)
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "invoice": {
+                "id": 9,
+                "reference": "PI-2025-00001",
+                "supplierReference": "R-1001",
+                "date": "2025-07-03",
+                "status": "draft",
+                "orderId": 12,
+                "supplierId": 7,
+                "needsReview": false,
+                "reviewNote": "",
+                "discountPercent": 0.0,
+                "discountAmount": 0,
+            },
+            "lines": [
+                {
+                    "id": 1,
+                    "description": "Oak board",
+                    "quantity": 10.0,
+                    "price": 1250,
+                    "tax": 19,
+                    "taxId": 1,
+                    "invoiceId": 9,
+                    "variantId": null,
+                    "transactionId": null,
+                    "taxTransactionId": null,
+                    "discountPercent": 0.0,
+                    "discountAmount": 0,
+                    "discountTransactionId": null,
+                },
+                {
+                    "id": 2,
+                    "description": "Assembly manual",
+                    "quantity": 2.0,
+                    "price": 1000,
+                    "tax": 7,
+                    "taxId": 2,
+                    "invoiceId": 9,
+                    "variantId": null,
+                    "transactionId": null,
+                    "taxTransactionId": null,
+                    "discountPercent": 0.0,
+                    "discountAmount": 0,
+                    "discountTransactionId": null,
+                },
+            ],
+            "issues": [],
+        })
+    );
+}
+
+#[actix_web::test]
+async fn test_cii_supplier_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .contacts
+        .expect_find_suppliers_by_vat_id()
+        .once()
+        .with(predicate::eq("DE111222333".to_string()))
+        .returning(|_| Ok(vec![]));
+    state
+        .contacts
+        .expect_find_suppliers_by_name()
+        .once()
+        .with(predicate::eq("Schrauben KG".to_string()))
+        .returning(|_| Ok(vec![]));
+    state
+        .purchase_invoices
+        .expect_find_by_supplier_reference()
+        .never();
+    state
+        .purchase_orders
+        .expect_find_by_reference()
+        .once()
+        .with(predicate::eq("PO-2025-00014".to_string()))
+        .returning(|reference| Ok(vec![order(14, &reference, 8)]));
+    state
+        .taxes
+        .expect_find_by_rate()
+        .once()
+        .with(predicate::eq(0.19))
+        .returning(|rate| Ok(vec![tax(1, rate)]));
+    expect_create(&mut state);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_invoices::import),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices/import")
+        .set_payload(CII)
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["invoice"]["date"], "2025-07-10");
+    assert_eq!(body["invoice"]["supplierId"], serde_json::Value::Null);
+    assert_eq!(body["invoice"]["orderId"], 14);
+    assert_eq!(body["invoice"]["needsReview"], true);
+    assert_eq!(
+        body["invoice"]["reviewNote"],
+        "SUPPLIER_NOT_FOUND: No supplier matches \"Schrauben KG\""
+    );
+    assert_eq!(body["lines"][0]["price"], 25);
+    assert_eq!(body["lines"][0]["taxId"], 1);
+    assert_eq!(body["issues"][0]["code"], "SUPPLIER_NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn test_factur_x_pdf() {
+    let mut state = MockAppState::new();
+    state
+        .contacts
+        .expect_find_suppliers_by_vat_id()
+        .once()
+        .returning(|vat_id| Ok(vec![supplier(8, "Schrauben KG", &vat_id)]));
+    state
+        .purchase_invoices
+        .expect_find_by_supplier_reference()
+        .once()
+        .with(predicate::eq(8), predicate::eq("2025-0815".to_string()))
+        .returning(|_, _| Ok(None));
+    state
+        .purchase_orders
+        .expect_find_by_reference()
+        .once()
+        .returning(|reference| Ok(vec![order(14, &reference, 8)]));
+    state
+        .taxes
+        .expect_find_by_rate()
+        .once()
+        .returning(|rate| Ok(vec![tax(1, rate)]));
+    expect_create(&mut state);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_invoices::import),
+    )
+    .await;
+
+    // Any rendered document will do as carrier for the embedded XML.
+    let invoice = entity::sale_invoices::Model {
+        id: 1,
+        reference: "2025-0815".to_string(),
+        date: Date::from_ymd_opt(2025, 7, 10).unwrap(),
+        status: SaleInvoiceStatus::Invoice,
+        order_id: 1,
+        customer_id: 1,
+        discount_percent: 0.0,
+        discount_amount: 0,
+    };
+    let pdf = PrintableDocument::sale_invoice(
+        &invoice,
+        &[],
+        &supplier(1, "Liro GmbH", ""),
+        &HashMap::new(),
+        &Letterhead::default(),
+    )
+    .render_factur_x(CII.as_bytes())
+    .unwrap();
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices/import")
+        .insert_header(("Content-Type", "application/pdf"))
+        .set_payload(pdf)
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["invoice"]["supplierId"], 8);
+    assert_eq!(body["invoice"]["orderId"], 14);
+    assert_eq!(body["invoice"]["needsReview"], false);
+}
+
+#[actix_web::test]
+async fn test_tax_ambiguous() {
+    let mut state = MockAppState::new();
+    state
+        .contacts
+        .expect_find_suppliers_by_vat_id()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state
+        .contacts
+        .expect_find_suppliers_by_name()
+        .once()
+        .with(predicate::eq("Holz AG".to_string()))
+        .returning(|name| Ok(vec![supplier(7, &name, "")]));
+    state
+        .purchase_invoices
+        .expect_find_by_supplier_reference()
+        .once()
+        .returning(|_, _| Ok(None));
+    state
+        .purchase_orders
+        .expect_find_by_reference()
+        .once()
+        .returning(|reference| Ok(vec![order(12, &reference, 7)]));
+    state
+        .taxes
+        .expect_find_by_rate()
+        .once()
+        .with(predicate::eq(0.19))
+        .returning(|rate| Ok(vec![tax(1, rate), tax(4, rate)]));
+    state
+        .taxes
+        .expect_find_by_rate()
+        .once()
+        .with(predicate::eq(0.07))
+        .returning(|rate| Ok(vec![tax(2, rate)]));
+    expect_create(&mut state);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_invoices::import),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices/import")
+        .set_payload(UBL)
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["invoice"]["supplierId"], 7);
+    assert_eq!(body["invoice"]["needsReview"], true);
+    assert_eq!(body["lines"][0]["taxId"], serde_json::Value::Null);
+    assert_eq!(body["lines"][1]["taxId"], 2);
+    assert_eq!(body["issues"].as_array().unwrap().len(), 1);
+    assert_eq!(body["issues"][0]["code"], "TAX_AMBIGUOUS");
+}
+
+#[actix_web::test]
+async fn test_already_imported() {
+    let mut state = MockAppState::new();
+    state
+        .contacts
+        .expect_find_suppliers_by_vat_id()
+        .once()
+        .returning(|vat_id| Ok(vec![supplier(7, "Holz AG", &vat_id)]));
+    state
+        .purchase_invoices
+        .expect_find_by_supplier_reference()
+        .once()
+        .with(predicate::eq(7), predicate::eq("R-1001".to_string()))
+        .returning(|supplier_id, supplier_reference| {
+            Ok(Some(entity::purchase_invoices::Model {
+                id: 3,
+                reference: "PI-2025-00001".to_string(),
+                date: Date::from_ymd_opt(2025, 7, 3).unwrap(),
+                status: entity::sea_orm_active_enums::PurchaseInvoiceStatus::Draft,
+                order_id: Some(12),
+                supplier_id: Some(supplier_id),
+                needs_review: false,
+                review_note: "".to_string(),
+                supplier_reference,
+                discount_percent: 0.0,
+                discount_amount: 0,
+            }))
+        });
+    state.purchase_invoices.expect_create_with_lines().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_invoices::import),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices/import")
+        .set_payload(UBL)
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+}
+
+#[actix_web::test]
+async fn test_unreadable() {
+    let mut state = MockAppState::new();
+    state.contacts.expect_find_suppliers_by_vat_id().never();
+    state.purchase_invoices.expect_create_with_lines().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_invoices::import),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices/import")
+        .set_payload("<Order><ID>PO-1</ID></Order>")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+}
+
+#[actix_web::test]
+async fn test_connection_error() {
+    let mut state = MockAppState::new();
+    state
+        .contacts
+        .expect_find_suppliers_by_vat_id()
+        .once()
+        .returning(|_| {
+            let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+            Err(err)
+        });
+    state.purchase_invoices.expect_create_with_lines().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_invoices::import),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices/import")
+        .set_payload(UBL)
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
+}
+
+/// Mocks the lookups of importing the UBL invoice from a known supplier.
+fn mock_ubl_lookups(state: &mut MockAppState) {
+    state
+        .contacts
+        .expect_find_suppliers_by_vat_id()
+        .once()
+        .returning(|vat_id| Ok(vec![supplier(7, "Holz AG", &vat_id)]));
+    state
+        .purchase_invoices
+        .expect_find_by_supplier_reference()
+        .once()
+        .returning(|_, _| Ok(None));
+    state
+        .purchase_orders
+        .expect_find_by_reference()
+        .once()
+        .returning(|reference| Ok(vec![order(12, &reference, 7)]));
+    state
+        .taxes
+        .expect_find_by_rate()
+        .times(2)
+        .returning(|rate| Ok(vec![tax(1, rate)]));
+    expect_create(state);
+}
+
+#[actix_web::test]
+async fn test_base_quantity() {
+    let mut state = MockAppState::new();
+    mock_ubl_lookups(&mut state);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_invoices::import),
+    )
+    .await;
+
+    // The oak boards are priced per pack of 10.
+    let xml = UBL.replace(
+        r#"<cbc:PriceAmount currencyID="EUR">12.50</cbc:PriceAmount>"#,
+        r#"<cbc:PriceAmount currencyID="EUR">125.00</cbc:PriceAmount><cbc:BaseQuantity unitCode="C62">10</cbc:BaseQuantity>"#,
+    );
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices/import")
+        .set_payload(xml)
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["lines"][0]["price"], 1250);
+    assert_eq!(body["issues"], serde_json::json!([]));
+}
+
+#[actix_web::test]
+async fn test_cii_basis_quantity() {
+    let mut state = MockAppState::new();
+    state
+        .contacts
+        .expect_find_suppliers_by_vat_id()
+        .once()
+        .returning(|vat_id| Ok(vec![supplier(8, "Schrauben KG", &vat_id)]));
+    state
+        .purchase_invoices
+        .expect_find_by_supplier_reference()
+        .once()
+        .returning(|_, _| Ok(None));
+    state
+        .purchase_orders
+        .expect_find_by_reference()
+        .once()
+        .returning(|reference| Ok(vec![order(14, &reference, 8)]));
+    state
+        .taxes
+        .expect_find_by_rate()
+        .once()
+        .returning(|rate| Ok(vec![tax(1, rate)]));
+    expect_create(&mut state);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_invoices::import),
+    )
+    .await;
+
+    // The screws are priced per hundred.
+    let xml = CII.replace(
+        "<ram:ChargeAmount>0.25</ram:ChargeAmount>",
+        r#"<ram:ChargeAmount>25.00</ram:ChargeAmount><ram:BasisQuantity unitCode="C62">100</ram:BasisQuantity>"#,
+    );
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices/import")
+        .set_payload(xml)
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["lines"][0]["price"], 25);
+    assert_eq!(body["issues"], serde_json::json!([]));
+}
+
+#[actix_web::test]
+async fn test_line_net_mismatch() {
+    let mut state = MockAppState::new();
+    mock_ubl_lookups(&mut state);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_invoices::import),
+    )
+    .await;
+
+    let xml = UBL.replace(
+        r#"<cbc:PriceAmount currencyID="EUR">12.50</cbc:PriceAmount>"#,
+        r#"<cbc:PriceAmount currencyID="EUR">12.00</cbc:PriceAmount>"#,
+    );
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices/import")
+        .set_payload(xml)
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["invoice"]["needsReview"], true);
+    assert_eq!(body["issues"].as_array().unwrap().len(), 1);
+    assert_eq!(body["issues"][0]["code"], "LINE_NET_MISMATCH");
+    assert_eq!(
+        body["issues"][0]["message"],
+        "Line 1: quantity times price less the discount is 120.00, the invoice states 125.00"
+    );
+}
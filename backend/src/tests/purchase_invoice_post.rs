@@ -0,0 +1,354 @@
+use std::collections::HashMap;
+
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::PurchaseInvoiceStatus;
+use mockall::predicate;
+
+use crate::{
+    handlers,
+    repositories::{LinePostingDraft, PostingDraft, ResolvedTax, TaxComponent},
+    state::{AppState, MockAppState},
+    tests::fixtures,
+    utils::product_categories::CategoryDefaults,
+};
+
+fn invoice(supplier_id: Option<i32>) -> entity::purchase_invoices::Model {
+    entity::purchase_invoices::Model {
+        order_id: None,
+        supplier_id,
+        discount_percent: 20.0,
+        ..fixtures::purchase_invoice(1, PurchaseInvoiceStatus::Draft)
+    }
+}
+
+fn invoice_lines() -> Vec<entity::purchase_invoice_lines::Model> {
+    vec![entity::purchase_invoice_lines::Model {
+        description: "Beech boards".to_string(),
+        price: 10000,
+        tax: 0,
+        variant_id: Some(8),
+        tax_id: Some(20),
+        ..fixtures::purchase_invoice_line(1, 1)
+    }]
+}
+
+fn supplier() -> entity::contacts::Model {
+    entity::contacts::Model {
+        name: "Holz Maier".to_string(),
+        country: "CA".to_string(),
+        supplier_account_id: Some(1600),
+        currency: "CAD".to_string(),
+        ..fixtures::supplier(4)
+    }
+}
+
+fn tax(id: i32, rate: f64, account_id: i32) -> entity::taxes::Model {
+    entity::taxes::Model {
+        name: "Tax".to_string(),
+        name_short: "Tax".to_string(),
+        rate,
+        account_id,
+        ..fixtures::tax(id)
+    }
+}
+
+fn posting(amount: i32, debit_account_id: i32) -> PostingDraft {
+    PostingDraft {
+        amount,
+        debit_account_id,
+        credit_account_id: 1600,
+    }
+}
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_invoices
+        .expect_get()
+        .once()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(Some(invoice(Some(4)))));
+    state
+        .purchase_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|_| Ok(invoice_lines()));
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .with(predicate::eq(4))
+        .returning(|_| Ok(Some(supplier())));
+    state
+        .product_categories
+        .expect_defaults_for_variants()
+        .once()
+        .returning(|_| {
+            Ok(HashMap::from([(
+                8,
+                CategoryDefaults {
+                    expense_account_id: Some(5400),
+                    ..Default::default()
+                },
+            )]))
+        });
+    state
+        .variants
+        .expect_stockable_ids()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state.taxes.expect_resolve_at().once().returning(|id, _| {
+        let mut resolved = ResolvedTax::plain(tax(id, 0.0, 0));
+        resolved.components = vec![
+            TaxComponent {
+                tax: tax(21, 0.05, 1571),
+                compound: false,
+            },
+            TaxComponent {
+                tax: tax(22, 0.07, 1572),
+                compound: true,
+            },
+        ];
+        Ok(Some(resolved))
+    });
+    // The invoice discount of 20% leaves 8000 of the line. The compound
+    // component is levied on that plus the first component's 400.
+    state
+        .purchase_invoices
+        .expect_post()
+        .once()
+        .with(
+            predicate::always(),
+            predicate::eq(vec![LinePostingDraft {
+                line_id: 1,
+                tax_id: Some(20),
+                net: posting(8000, 5400),
+                discount: None,
+                tax: None,
+                tax_postings: vec![(21, posting(400, 1571)), (22, posting(588, 1572))],
+            }]),
+        )
+        .returning(|invoice, _| {
+            Ok(entity::purchase_invoices::Model {
+                status: PurchaseInvoiceStatus::Finished,
+                ..invoice
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::invoice_postings::post_purchase),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices/1/post")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["status"], "finished");
+}
+
+#[actix_web::test]
+async fn test_stockable_to_inventory() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_invoices
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(invoice(Some(4)))));
+    state
+        .purchase_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|_| Ok(invoice_lines()));
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(supplier())));
+    state
+        .product_categories
+        .expect_defaults_for_variants()
+        .once()
+        .returning(|_| {
+            Ok(HashMap::from([(
+                8,
+                CategoryDefaults {
+                    expense_account_id: Some(5400),
+                    inventory_account_id: Some(1400),
+                    ..Default::default()
+                },
+            )]))
+        });
+    state
+        .variants
+        .expect_stockable_ids()
+        .once()
+        .with(predicate::eq(vec![8]))
+        .returning(Ok);
+    state
+        .taxes
+        .expect_resolve_at()
+        .once()
+        .returning(|id, _| Ok(Some(ResolvedTax::plain(tax(id, 0.19, 1576)))));
+    // The boards go into stock and are expensed when they are sold.
+    state
+        .purchase_invoices
+        .expect_post()
+        .once()
+        .with(
+            predicate::always(),
+            predicate::eq(vec![LinePostingDraft {
+                line_id: 1,
+                tax_id: Some(20),
+                net: posting(8000, 1400),
+                discount: None,
+                tax: Some(posting(1520, 1576)),
+                tax_postings: vec![],
+            }]),
+        )
+        .returning(|invoice, _| Ok(invoice));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::invoice_postings::post_purchase),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices/1/post")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+}
+
+#[actix_web::test]
+async fn test_drop_shipped_to_expense() {
+    let mut state = MockAppState::new();
+    state.purchase_invoices.expect_get().once().returning(|_| {
+        Ok(Some(entity::purchase_invoices::Model {
+            order_id: Some(3),
+            ..invoice(Some(4))
+        }))
+    });
+    state
+        .purchase_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|_| Ok(invoice_lines()));
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(supplier())));
+    state
+        .purchase_order_lines
+        .expect_list_for_order()
+        .once()
+        .with(predicate::eq(3))
+        .returning(|_| {
+            Ok(vec![entity::purchase_order_lines::Model {
+                description: "Beech boards".to_string(),
+                price: 10000,
+                tax: 0,
+                variant_id: Some(8),
+                move_id: Some(11),
+                sale_line_id: Some(6),
+                ..fixtures::purchase_order_line(1, 3)
+            }])
+        });
+    state
+        .product_categories
+        .expect_defaults_for_variants()
+        .once()
+        .returning(|_| {
+            Ok(HashMap::from([(
+                8,
+                CategoryDefaults {
+                    expense_account_id: Some(5400),
+                    inventory_account_id: Some(1400),
+                    ..Default::default()
+                },
+            )]))
+        });
+    state.variants.expect_stockable_ids().once().returning(Ok);
+    state
+        .taxes
+        .expect_resolve_at()
+        .once()
+        .returning(|id, _| Ok(Some(ResolvedTax::plain(tax(id, 0.19, 1576)))));
+    // The boards went straight to the customer, so they are expensed
+    // although they are stockable.
+    state
+        .purchase_invoices
+        .expect_post()
+        .once()
+        .with(
+            predicate::always(),
+            predicate::eq(vec![LinePostingDraft {
+                line_id: 1,
+                tax_id: Some(20),
+                net: posting(8000, 5400),
+                discount: None,
+                tax: Some(posting(1520, 1576)),
+                tax_postings: vec![],
+            }]),
+        )
+        .returning(|invoice, _| Ok(invoice));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::invoice_postings::post_purchase),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices/1/post")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+}
+
+#[actix_web::test]
+async fn test_without_supplier() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_invoices
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(invoice(None))));
+    state
+        .purchase_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|_| Ok(invoice_lines()));
+    state.contacts.expect_get().never();
+    state.purchase_invoices.expect_post().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::invoice_postings::post_purchase),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-invoices/1/post")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "CONTACT_ACCOUNT_MISSING");
+}
@@ -1,6 +1,359 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::PurchaseOrderStatus;
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr, prelude::Date};
+use serde_json::json;
+
+use crate::{
+    handlers,
+    repositories::PurchaseOrderLineDraft,
+    state::{AppState, MockAppState},
+    tests::fixtures::{oak_chair, purchase_order},
+    utils::totals::Discount,
+};
+
+fn order(id: i32) -> entity::purchase_orders::Model {
+    entity::purchase_orders::Model {
+        reference: "PO-2026-00001".to_string(),
+        date: Date::from_ymd_opt(2026, 7, 1).unwrap(),
+        supplier_id: 3,
+        ..purchase_order(id, PurchaseOrderStatus::Draft)
+    }
+}
+
+fn entry(
+    variant_id: i32,
+    supplier_id: i32,
+    supplier_sku: &str,
+    description: &str,
+) -> entity::supplier_prices::Model {
+    entity::supplier_prices::Model {
+        id: 1,
+        variant_id,
+        supplier_id,
+        supplier_sku: supplier_sku.to_string(),
+        description: description.to_string(),
+        price: 2250,
+        min_quantity: 10.0,
+        pack_size: 5.0,
+        lead_time_days: 14,
+        preferred: true,
+    }
+}
+
+fn chair(description: &str, price: i32) -> PurchaseOrderLineDraft {
+    PurchaseOrderLineDraft {
+        description: description.to_string(),
+        quantity: 20.0,
+        price,
+        tax: 19,
+        tax_id: None,
+        variant_id: Some(7),
+        discount: Discount::default(),
+        unit_id: None,
+    }
+}
+
+fn created(order_id: i32, line: PurchaseOrderLineDraft) -> entity::purchase_order_lines::Model {
+    entity::purchase_order_lines::Model {
+        id: 1,
+        description: line.description,
+        quantity: line.quantity,
+        price: line.price,
+        tax: line.tax,
+        order_id,
+        variant_id: line.variant_id,
+        move_id: None,
+        discount_percent: line.discount.percent,
+        discount_amount: line.discount.amount as i32,
+        unit_id: line.unit_id,
+        sale_line_id: None,
+        tax_id: line.tax_id,
+    }
+}
+
 #[actix_web::test]
 async fn test_success() {
-    todo!();
+    let mut state = MockAppState::new();
+    state
+        .purchase_orders
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|id| Ok(Some(order(id))));
+    state.variants.expect_get().never();
+    state.supplier_prices.expect_get_for_supplier().never();
+    state
+        .purchase_order_lines
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq(5),
+            predicate::eq(PurchaseOrderLineDraft {
+                description: "Freight".to_string(),
+                quantity: 1.0,
+                price: 8000,
+                tax: 19,
+                tax_id: None,
+                variant_id: None,
+                discount: Discount::default(),
+                unit_id: None,
+            }),
+        )
+        .returning(|order_id, line| Ok(created(order_id, line)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_order_lines::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-order-lines")
+        .set_json(json!({
+            "description": "Freight",
+            "quantity": 1.0,
+            "price": 8000,
+            "tax": 19,
+            "orderId": 5,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["id"], 1);
+    assert_eq!(body["orderId"], 5);
+    assert_eq!(body["price"], 8000);
+}
+
+#[actix_web::test]
+async fn test_filled_from_supplier_price() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_orders
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(order(id))));
+    state
+        .variants
+        .expect_get()
+        .once()
+        .with(predicate::eq(7))
+        .returning(|id| Ok(Some(oak_chair(id))));
+    state
+        .supplier_prices
+        .expect_get_for_supplier()
+        .once()
+        .with(predicate::eq(7), predicate::eq(3))
+        .returning(|variant_id, supplier_id| {
+            Ok(Some(entry(variant_id, supplier_id, "CH-100", "Oak chair")))
+        });
+    state
+        .purchase_order_lines
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq(5),
+            predicate::eq(chair("CH-100 Oak chair", 2250)),
+        )
+        .returning(|order_id, line| Ok(created(order_id, line)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_order_lines::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-order-lines")
+        .set_json(json!({
+            "quantity": 20.0,
+            "tax": 19,
+            "orderId": 5,
+            "variantId": 7,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["description"], "CH-100 Oak chair");
+    assert_eq!(body["price"], 2250);
+}
+
+#[actix_web::test]
+async fn test_supplier_price_without_description() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_orders
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(order(id))));
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(oak_chair(id))));
+    state
+        .supplier_prices
+        .expect_get_for_supplier()
+        .once()
+        .returning(|variant_id, supplier_id| {
+            Ok(Some(entry(variant_id, supplier_id, "CH-100", "")))
+        });
+    // The given price wins over the catalog's.
+    state
+        .purchase_order_lines
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq(5),
+            predicate::eq(chair("CH-100 Chair, oak", 2100)),
+        )
+        .returning(|order_id, line| Ok(created(order_id, line)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_order_lines::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-order-lines")
+        .set_json(json!({
+            "quantity": 20.0,
+            "price": 2100,
+            "tax": 19,
+            "orderId": 5,
+            "variantId": 7,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+}
+
+#[actix_web::test]
+async fn test_price_from_variant() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_orders
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(order(id))));
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(oak_chair(id))));
+    state
+        .supplier_prices
+        .expect_get_for_supplier()
+        .once()
+        .returning(|_, _| Ok(None));
+    state
+        .purchase_order_lines
+        .expect_create()
+        .once()
+        .with(predicate::eq(5), predicate::eq(chair("Chair, oak", 2500)))
+        .returning(|order_id, line| Ok(created(order_id, line)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_order_lines::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-order-lines")
+        .set_json(json!({
+            "quantity": 20.0,
+            "tax": 19,
+            "orderId": 5,
+            "variantId": 7,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["price"], 2500);
+}
+
+#[actix_web::test]
+async fn test_missing_price() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_orders
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(order(id))));
+    state.purchase_order_lines.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_order_lines::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-order-lines")
+        .set_json(json!({
+            "description": "Freight",
+            "quantity": 1.0,
+            "tax": 19,
+            "orderId": 5,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "MISSING_PRICE");
+}
+
+#[actix_web::test]
+async fn test_order_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(None));
+    state.purchase_order_lines.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_order_lines::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-order-lines")
+        .set_json(json!({
+            "quantity": 1.0,
+            "tax": 19,
+            "orderId": 5,
+            "variantId": 7,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "PURCHASE_ORDER_NOT_FOUND");
 }
 
 #[actix_web::test]
@@ -40,5 +393,31 @@ async fn test_supplier_invalid_move_id() {
 
 #[actix_web::test]
 async fn test_connection_error() {
-    todo!();
+    let mut state = MockAppState::new();
+    state.purchase_orders.expect_get().once().returning(|_| {
+        let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+        Err(err)
+    });
+    state.purchase_order_lines.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_order_lines::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-order-lines")
+        .set_json(json!({
+            "description": "Freight",
+            "quantity": 1.0,
+            "price": 8000,
+            "tax": 19,
+            "orderId": 5,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
 }
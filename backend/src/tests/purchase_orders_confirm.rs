@@ -0,0 +1,110 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::PurchaseOrderStatus;
+use sea_orm::prelude::Date;
+
+use crate::{
+    handlers,
+    state::{AppState, MockAppState},
+    tests::fixtures::purchase_order,
+};
+
+fn order(status: PurchaseOrderStatus) -> entity::purchase_orders::Model {
+    entity::purchase_orders::Model {
+        reference: "PO-2026-00001".to_string(),
+        date: Date::from_ymd_opt(2026, 11, 6).unwrap(),
+        ..purchase_order(5, status)
+    }
+}
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(order(PurchaseOrderStatus::Draft))));
+    state
+        .purchase_orders
+        .expect_confirm()
+        .once()
+        .returning(|order| {
+            Ok(entity::purchase_orders::Model {
+                status: PurchaseOrderStatus::Order,
+                ..order
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_orders::confirm),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-orders/5/confirm")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["id"], 5);
+    assert_eq!(body["status"], "order");
+}
+
+#[actix_web::test]
+async fn test_already_confirmed() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(order(PurchaseOrderStatus::Order))));
+    state.purchase_orders.expect_confirm().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_orders::confirm),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-orders/5/confirm")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "PURCHASE_ORDER_ALREADY_CONFIRMED");
+}
+
+#[actix_web::test]
+async fn test_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(None));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_orders::confirm),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-orders/5/confirm")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "PURCHASE_ORDER_NOT_FOUND");
+}
@@ -1,44 +1,271 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::PurchaseOrderStatus;
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr, prelude::Date};
+use serde_json::json;
+
+use crate::{
+    handlers,
+    repositories::ERR_PERIOD_CLOSED,
+    state::{AppState, MockAppState},
+    utils::totals::Discount,
+};
+
 #[actix_web::test]
 async fn test_success() {
-    todo!();
-}
+    let mut state = MockAppState::new();
+    state
+        .purchase_orders
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq(Date::from_ymd_opt(2025, 7, 1).unwrap()),
+            predicate::eq(PurchaseOrderStatus::Draft),
+            predicate::eq(7),
+            predicate::eq(3),
+            predicate::eq(1),
+            predicate::eq(Discount::default()),
+        )
+        .returning(
+            |date, status, supplier_id, source_stock_id, target_stock_id, discount| {
+                Ok(entity::purchase_orders::Model {
+                    id: 1,
+                    reference: "PO-2025-00013".to_string(),
+                    date,
+                    status,
+                    supplier_id,
+                    source_stock_id,
+                    target_stock_id,
+                    discount_percent: discount.percent,
+                    discount_amount: discount.amount as i32,
+                })
+            },
+        );
 
-#[actix_web::test]
-async fn test_customer_invalid_reference() {
-    todo!();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_orders::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-orders")
+        .set_json(json!({
+            "date": "2025-07-01",
+            "status": "draft",
+            "supplierId": 7,
+            "sourceStockId": 3,
+            "targetStockId": 1,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["reference"], "PO-2025-00013");
 }
 
 #[actix_web::test]
-async fn test_supplier_reference_unique() {
-    todo!();
+async fn test_invalid_date() {
+    let mut state = MockAppState::new();
+    state.purchase_orders.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_orders::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-orders")
+        .set_json(json!({
+            "date": "01.07.2025",
+            "status": "draft",
+            "supplierId": 7,
+            "sourceStockId": 3,
+            "targetStockId": 1,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
 }
 
 #[actix_web::test]
-async fn test_customer_invalid_date() {
-    todo!();
+async fn test_invalid_status() {
+    let mut state = MockAppState::new();
+    state.purchase_orders.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_orders::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-orders")
+        .set_json(json!({
+            "date": "2025-07-01",
+            "status": "paid",
+            "supplierId": 7,
+            "sourceStockId": 3,
+            "targetStockId": 1,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
 }
 
 #[actix_web::test]
-async fn test_supplier_invalid_status() {
-    todo!();
+async fn test_invalid_supplier_id() {
+    let mut state = MockAppState::new();
+    state.purchase_orders.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_orders::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-orders")
+        .set_json(json!({
+            "date": "2025-07-01",
+            "status": "draft",
+            "supplierId": 0,
+            "sourceStockId": 3,
+            "targetStockId": 1,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
 }
 
 #[actix_web::test]
-async fn test_supplier_invalid_supplier_id() {
-    todo!();
+async fn test_invalid_source_stock_id() {
+    let mut state = MockAppState::new();
+    state.purchase_orders.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_orders::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-orders")
+        .set_json(json!({
+            "date": "2025-07-01",
+            "status": "draft",
+            "supplierId": 7,
+            "sourceStockId": 0,
+            "targetStockId": 1,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
 }
 
 #[actix_web::test]
-async fn test_supplier_invalid_source_stock_id() {
-    todo!();
+async fn test_invalid_target_stock_id() {
+    let mut state = MockAppState::new();
+    state.purchase_orders.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_orders::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-orders")
+        .set_json(json!({
+            "date": "2025-07-01",
+            "status": "draft",
+            "supplierId": 7,
+            "sourceStockId": 3,
+            "targetStockId": 0,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
 }
 
 #[actix_web::test]
-async fn test_supplier_invalid_target_stock_id() {
-    todo!();
+async fn test_period_closed() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_orders
+        .expect_create()
+        .once()
+        .returning(|_, _, _, _, _, _| {
+            let err = DbErr::Custom(ERR_PERIOD_CLOSED.to_string());
+            Err(err)
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_orders::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-orders")
+        .set_json(json!({
+            "date": "2025-07-01",
+            "status": "draft",
+            "supplierId": 7,
+            "sourceStockId": 3,
+            "targetStockId": 1,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
 }
 
 #[actix_web::test]
 async fn test_connection_error() {
-    todo!();
+    let mut state = MockAppState::new();
+    state
+        .purchase_orders
+        .expect_create()
+        .once()
+        .returning(|_, _, _, _, _, _| {
+            let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+            Err(err)
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_orders::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-orders")
+        .set_json(json!({
+            "date": "2025-07-01",
+            "status": "draft",
+            "supplierId": 7,
+            "sourceStockId": 3,
+            "targetStockId": 1,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
 }
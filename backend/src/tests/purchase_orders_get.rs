@@ -1,14 +1,145 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::PurchaseOrderStatus;
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr, prelude::Date};
+
+use crate::{
+    handlers,
+    state::{AppState, MockAppState},
+    tests::fixtures,
+};
+
+fn line(
+    id: i32,
+    order_id: i32,
+    description: &str,
+    price: i32,
+    tax: i32,
+) -> entity::purchase_order_lines::Model {
+    entity::purchase_order_lines::Model {
+        description: description.to_string(),
+        price,
+        tax,
+        ..fixtures::purchase_order_line(id, order_id)
+    }
+}
+
 #[actix_web::test]
 async fn test_success() {
-    todo!();
+    let mut state = MockAppState::new();
+    state
+        .purchase_orders
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|id| {
+            Ok(Some(entity::purchase_orders::Model {
+                id,
+                reference: "PO-2025-00001".to_string(),
+                date: Date::from_ymd_opt(2025, 7, 1).unwrap(),
+                status: PurchaseOrderStatus::Draft,
+                supplier_id: 4,
+                source_stock_id: 1,
+                target_stock_id: 2,
+                discount_percent: 0.0,
+                discount_amount: 0,
+            }))
+        });
+    state
+        .purchase_order_lines
+        .expect_list_for_order()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|order_id| {
+            Ok(vec![
+                line(1, order_id, "Chair", 1050, 19),
+                line(2, order_id, "Cushion", 1050, 19),
+                line(3, order_id, "Manual", 1999, 7),
+            ])
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_orders::get),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/purchase-orders/5")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["order"]["reference"], "PO-2025-00001");
+    assert_eq!(body["lines"].as_array().unwrap().len(), 3);
+    assert_eq!(body["totals"]["lines"][0]["lineId"], 1);
+    assert_eq!(body["totals"]["lines"][0]["net"], 1050);
+    assert_eq!(body["totals"]["lines"][0]["tax"], 200);
+    assert_eq!(body["totals"]["lines"][0]["gross"], 1250);
+    // Rounded once per rate, 399 instead of the 400 of the two lines.
+    assert_eq!(body["totals"]["taxes"][0]["rate"], 0.19);
+    assert_eq!(body["totals"]["taxes"][0]["base"], 2100);
+    assert_eq!(body["totals"]["taxes"][0]["amount"], 399);
+    assert_eq!(body["totals"]["taxes"][1]["rate"], 0.07);
+    assert_eq!(body["totals"]["taxes"][1]["amount"], 140);
+    assert_eq!(body["totals"]["netTotal"], 4099);
+    assert_eq!(body["totals"]["taxTotal"], 539);
+    assert_eq!(body["totals"]["grossTotal"], 4638);
 }
 
 #[actix_web::test]
 async fn test_not_found() {
-    todo!();
+    let mut state = MockAppState::new();
+    state
+        .purchase_orders
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|_| Ok(None));
+    state.purchase_order_lines.expect_list_for_order().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_orders::get),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/purchase-orders/5")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
 }
 
 #[actix_web::test]
 async fn test_connection_error() {
-    todo!();
+    let mut state = MockAppState::new();
+    state
+        .purchase_orders
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|_| {
+            let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+            Err(err)
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_orders::get),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/purchase-orders/5")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
 }
@@ -0,0 +1,436 @@
+use std::collections::HashMap;
+
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{PurchaseOrderStatus, Tracking};
+use mockall::predicate;
+use sea_orm::DbErr;
+use serde_json::json;
+
+use crate::{
+    handlers,
+    repositories::{ERR_PURCHASE_ORDER_RECEIVED, ERR_SERIAL_IN_STOCK, LineMoveDraft, LotDraft},
+    state::{AppState, MockAppState},
+    tests::fixtures::{purchase_order, purchase_order_line, unit, variant},
+};
+
+fn order(status: PurchaseOrderStatus) -> entity::purchase_orders::Model {
+    entity::purchase_orders::Model {
+        discount_percent: 10.0,
+        ..purchase_order(5, status)
+    }
+}
+
+fn line(
+    id: i32,
+    quantity: f64,
+    price: i32,
+    variant_id: Option<i32>,
+    unit_id: Option<i32>,
+) -> entity::purchase_order_lines::Model {
+    entity::purchase_order_lines::Model {
+        quantity,
+        price,
+        variant_id,
+        unit_id,
+        ..purchase_order_line(id, 5)
+    }
+}
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_orders
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|_| Ok(Some(order(PurchaseOrderStatus::Draft))));
+    state
+        .purchase_order_lines
+        .expect_list_for_order()
+        .once()
+        .returning(|_| {
+            Ok(vec![
+                line(1, 4.0, 1000, Some(8), None),
+                line(2, 1.0, 2000, Some(9), None),
+                line(3, 1.0, 1000, None, None),
+                line(4, 1.0, 6000, Some(10), Some(2)),
+            ])
+        });
+    state
+        .variants
+        .expect_stockable_ids()
+        .once()
+        .with(predicate::eq(vec![8, 9, 10]))
+        .returning(|_| Ok(vec![8, 10]));
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .returning(|_| Ok(HashMap::new()));
+    state
+        .variants
+        .expect_get()
+        .times(2)
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .units
+        .expect_get()
+        .with(predicate::eq(2))
+        .returning(|id| Ok(Some(unit(id, Some(1), 12.0))));
+    state
+        .units
+        .expect_get()
+        .with(predicate::eq(1))
+        .returning(|id| Ok(Some(unit(id, None, 1.0))));
+    // The order discount of 1300 is spread as 400, 200, 100 and 600 over
+    // the lines. The box of 12 comes in as 12 pieces.
+    state
+        .purchase_orders
+        .expect_receive()
+        .once()
+        .with(
+            predicate::always(),
+            predicate::always(),
+            predicate::eq(vec![
+                LineMoveDraft {
+                    line_id: 1,
+                    variant_id: 8,
+                    quantity: 4.0,
+                    unit_cost: Some(900.0),
+                    cost_accounts: None,
+                    lots: Vec::new(),
+                },
+                LineMoveDraft {
+                    line_id: 4,
+                    variant_id: 10,
+                    quantity: 12.0,
+                    unit_cost: Some(450.0),
+                    cost_accounts: None,
+                    lots: Vec::new(),
+                },
+            ]),
+        )
+        .returning(|order, _, _| {
+            Ok(entity::purchase_orders::Model {
+                status: PurchaseOrderStatus::Finished,
+                ..order
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_orders::receive),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-orders/5/receive")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["status"], "finished");
+}
+
+#[actix_web::test]
+async fn test_already_received() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(order(PurchaseOrderStatus::Finished))));
+    state.purchase_order_lines.expect_list_for_order().never();
+    state.purchase_orders.expect_receive().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_orders::receive),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-orders/5/receive")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "PURCHASE_ORDER_ALREADY_RECEIVED");
+}
+
+#[actix_web::test]
+async fn test_received_meanwhile() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(order(PurchaseOrderStatus::Draft))));
+    state
+        .purchase_order_lines
+        .expect_list_for_order()
+        .once()
+        .returning(|_| Ok(vec![line(1, 4.0, 1000, Some(8), None)]));
+    state.variants.expect_stockable_ids().once().returning(Ok);
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .returning(|_| Ok(HashMap::new()));
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .units
+        .expect_get()
+        .returning(|id| Ok(Some(unit(id, None, 1.0))));
+    state
+        .purchase_orders
+        .expect_receive()
+        .once()
+        .returning(|_, _, _| Err(DbErr::Custom(ERR_PURCHASE_ORDER_RECEIVED.to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_orders::receive),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-orders/5/receive")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "PURCHASE_ORDER_ALREADY_RECEIVED");
+}
+
+#[actix_web::test]
+async fn test_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(None));
+    state.purchase_orders.expect_receive().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_orders::receive),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-orders/5/receive")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "PURCHASE_ORDER_NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn test_serials() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(order(PurchaseOrderStatus::Draft))));
+    state
+        .purchase_order_lines
+        .expect_list_for_order()
+        .once()
+        .returning(|_| Ok(vec![line(1, 2.0, 1000, Some(8), None)]));
+    state.variants.expect_stockable_ids().once().returning(Ok);
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .units
+        .expect_get()
+        .returning(|id| Ok(Some(unit(id, None, 1.0))));
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .with(predicate::eq(vec![8]))
+        .returning(|ids| Ok(ids.into_iter().map(|id| (id, Tracking::Serial)).collect()));
+    state
+        .purchase_orders
+        .expect_receive()
+        .once()
+        .with(
+            predicate::always(),
+            predicate::always(),
+            predicate::eq(vec![LineMoveDraft {
+                line_id: 1,
+                variant_id: 8,
+                quantity: 2.0,
+                unit_cost: Some(900.0),
+                cost_accounts: None,
+                lots: vec![
+                    LotDraft {
+                        number: "SN-1".to_string(),
+                        quantity: 1.0,
+                        expiry_date: None,
+                    },
+                    LotDraft {
+                        number: "SN-2".to_string(),
+                        quantity: 1.0,
+                        expiry_date: None,
+                    },
+                ],
+            }]),
+        )
+        .returning(|order, _, _| {
+            Ok(entity::purchase_orders::Model {
+                status: PurchaseOrderStatus::Finished,
+                ..order
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_orders::receive),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-orders/5/receive")
+        .set_json(json!({
+            "lots": [
+                { "lineId": 1, "number": "SN-1", "quantity": 1.0 },
+                { "lineId": 1, "number": "SN-2", "quantity": 1.0 },
+            ],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+}
+
+#[actix_web::test]
+async fn test_serial_in_stock() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(order(PurchaseOrderStatus::Draft))));
+    state
+        .purchase_order_lines
+        .expect_list_for_order()
+        .once()
+        .returning(|_| Ok(vec![line(1, 1.0, 1000, Some(8), None)]));
+    state.variants.expect_stockable_ids().once().returning(Ok);
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .units
+        .expect_get()
+        .returning(|id| Ok(Some(unit(id, None, 1.0))));
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .returning(|ids| Ok(ids.into_iter().map(|id| (id, Tracking::Serial)).collect()));
+    state
+        .purchase_orders
+        .expect_receive()
+        .once()
+        .returning(|_, _, _| Err(DbErr::Custom(ERR_SERIAL_IN_STOCK.to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_orders::receive),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-orders/5/receive")
+        .set_json(json!({
+            "lots": [{ "lineId": 1, "number": "SN-1", "quantity": 1.0 }],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "SERIAL_IN_STOCK");
+}
+
+#[actix_web::test]
+async fn test_lot_line_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .purchase_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(order(PurchaseOrderStatus::Draft))));
+    state
+        .purchase_order_lines
+        .expect_list_for_order()
+        .once()
+        .returning(|_| Ok(vec![line(1, 2.0, 1000, Some(8), None)]));
+    state.variants.expect_stockable_ids().once().returning(Ok);
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .units
+        .expect_get()
+        .returning(|id| Ok(Some(unit(id, None, 1.0))));
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .returning(|ids| Ok(ids.into_iter().map(|id| (id, Tracking::Lot)).collect()));
+    state.purchase_orders.expect_receive().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::purchase_orders::receive),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/purchase-orders/5/receive")
+        .set_json(json!({
+            "lots": [{ "lineId": 2, "number": "B-100", "quantity": 2.0 }],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "LOT_LINE_NOT_FOUND");
+}
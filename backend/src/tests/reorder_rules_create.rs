@@ -0,0 +1,181 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::StockKind;
+use mockall::predicate;
+use sea_orm::DbErr;
+use serde_json::json;
+
+use crate::{
+    handlers,
+    repositories::ERR_REORDER_RULE_EXISTS,
+    state::{AppState, MockAppState},
+    tests::fixtures::{stock, variant},
+};
+
+fn state_with_lookups(kind: StockKind) -> MockAppState {
+    let mut state = MockAppState::new();
+    state
+        .variants
+        .expect_get()
+        .returning(|id| Ok(Some(variant(id))));
+    state.variants.expect_stockable_ids().returning(Ok);
+    state
+        .stocks
+        .expect_get()
+        .returning(move |id| Ok(Some(stock(id, kind.clone()))));
+    state
+}
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = state_with_lookups(StockKind::Internal);
+    state
+        .reorder_rules
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq(8),
+            predicate::eq(2),
+            predicate::eq(5.0),
+            predicate::eq(20.0),
+        )
+        .returning(|variant_id, stock_id, min_quantity, max_quantity| {
+            Ok(entity::reorder_rules::Model {
+                id: 1,
+                variant_id,
+                stock_id,
+                min_quantity,
+                max_quantity,
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::reorder_rules::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/reorder-rules")
+        .set_json(json!({ "variantId": 8, "stockId": 2, "minQuantity": 5.0, "maxQuantity": 20.0 }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["id"], 1);
+    assert_eq!(body["variantId"], 8);
+    assert_eq!(body["stockId"], 2);
+    assert_eq!(body["minQuantity"], 5.0);
+    assert_eq!(body["maxQuantity"], 20.0);
+}
+
+#[actix_web::test]
+async fn test_max_below_min() {
+    let mut state = state_with_lookups(StockKind::Internal);
+    state.reorder_rules.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::reorder_rules::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/reorder-rules")
+        .set_json(json!({ "variantId": 8, "stockId": 2, "minQuantity": 20.0, "maxQuantity": 5.0 }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVALID_REORDER_QUANTITIES");
+}
+
+#[actix_web::test]
+async fn test_virtual_location() {
+    let mut state = state_with_lookups(StockKind::Supplier);
+    state.reorder_rules.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::reorder_rules::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/reorder-rules")
+        .set_json(json!({ "variantId": 8, "stockId": 4, "minQuantity": 5.0, "maxQuantity": 20.0 }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVALID_REORDER_STOCK");
+}
+
+#[actix_web::test]
+async fn test_variant_not_stockable() {
+    let mut state = MockAppState::new();
+    state
+        .variants
+        .expect_get()
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .variants
+        .expect_stockable_ids()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state.reorder_rules.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::reorder_rules::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/reorder-rules")
+        .set_json(json!({ "variantId": 8, "stockId": 2, "minQuantity": 5.0, "maxQuantity": 20.0 }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "VARIANT_NOT_STOCKABLE");
+}
+
+#[actix_web::test]
+async fn test_rule_exists() {
+    let mut state = state_with_lookups(StockKind::Internal);
+    state
+        .reorder_rules
+        .expect_create()
+        .once()
+        .returning(|_, _, _, _| Err(DbErr::Custom(ERR_REORDER_RULE_EXISTS.to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::reorder_rules::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/reorder-rules")
+        .set_json(json!({ "variantId": 8, "stockId": 2, "minQuantity": 5.0, "maxQuantity": 20.0 }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "REORDER_RULE_EXISTS");
+}
@@ -0,0 +1,329 @@
+use std::collections::HashMap;
+
+use actix_web::{App, test, web};
+use chrono::Utc;
+use entity::sea_orm_active_enums::{
+    CostingMethod, ProductType, PurchaseOrderStatus, StockKind, TaxDirection, TaxRegion, Tracking,
+};
+use mockall::predicate;
+use sea_orm::prelude::Date;
+
+use crate::{
+    documents::Letterhead,
+    handlers,
+    repositories::{PurchaseOrderLineDraft, PurchaseProposalDraft, RuleForecast},
+    state::{AppState, MockAppState},
+    tests::fixtures::{purchase_order, stock, supplier, tax, variant},
+    utils::{product_categories::CategoryDefaults, totals::Discount},
+};
+
+fn forecast(id: i32, variant_id: i32, on_hand: f64, reserved: f64, incoming: f64) -> RuleForecast {
+    RuleForecast {
+        rule: entity::reorder_rules::Model {
+            id,
+            variant_id,
+            stock_id: 2,
+            min_quantity: 5.0,
+            max_quantity: 20.0,
+        },
+        on_hand,
+        reserved,
+        incoming,
+    }
+}
+
+fn supplier_price(
+    variant_id: i32,
+    supplier_id: i32,
+    pack_size: f64,
+) -> entity::supplier_prices::Model {
+    entity::supplier_prices::Model {
+        id: variant_id * 10,
+        variant_id,
+        supplier_id,
+        supplier_sku: format!("S-{variant_id}"),
+        description: "Stoneware mug".to_string(),
+        price: 450,
+        min_quantity: 0.0,
+        pack_size,
+        lead_time_days: 3,
+        preferred: true,
+    }
+}
+
+fn order(id: i32, proposal: &PurchaseProposalDraft) -> entity::purchase_orders::Model {
+    entity::purchase_orders::Model {
+        reference: format!("PO-{id}"),
+        date: Date::from_ymd_opt(2026, 11, 6).unwrap(),
+        supplier_id: proposal.supplier_id,
+        source_stock_id: proposal.source_stock_id,
+        target_stock_id: proposal.target_stock_id,
+        ..purchase_order(id, PurchaseOrderStatus::Draft)
+    }
+}
+
+/// Mocks the tax lookups of the contact as supplier 30, whose category
+/// taxes variant 8 with tax 7 unless one of the rules matches.
+fn mock_taxes(
+    state: &mut MockAppState,
+    contact: entity::contacts::Model,
+    rules: Vec<entity::tax_rules::Model>,
+) {
+    state.letterhead = Letterhead {
+        country: "DE".to_string(),
+        ..Letterhead::default()
+    };
+    state
+        .contacts
+        .expect_get()
+        .with(predicate::eq(30))
+        .returning(move |_| Ok(Some(contact.clone())));
+    state.products.expect_get().returning(|id| {
+        Ok(Some(entity::products::Model {
+            id,
+            name: "Stoneware mug".to_string(),
+            tax_category: "standard".to_string(),
+            category_id: Some(2),
+            product_type: ProductType::Stockable,
+            costing_method: CostingMethod::Fifo,
+            tracking: Tracking::None,
+        }))
+    });
+    state
+        .tax_rules
+        .expect_list_for_region()
+        .once()
+        .with(predicate::eq(TaxDirection::Purchase), predicate::always())
+        .returning(move |_, _| Ok(rules.clone()));
+    state
+        .product_categories
+        .expect_defaults_for_variants()
+        .once()
+        .returning(|_| {
+            Ok(HashMap::from([(
+                8,
+                CategoryDefaults {
+                    tax_id: Some(7),
+                    ..Default::default()
+                },
+            )]))
+        });
+    state
+        .taxes
+        .expect_get_at()
+        .with(predicate::always(), predicate::eq(Utc::now().date_naive()))
+        .returning(|id, _| match id {
+            7 => Ok(Some(tax(id))),
+            _ => Ok(Some(entity::taxes::Model {
+                name: "Intra-EU acquisition".to_string(),
+                name_short: "0%".to_string(),
+                rate: 0.0,
+                category: "K".to_string(),
+                ..tax(id)
+            })),
+        });
+}
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state.reorder_rules.expect_forecast().once().returning(|| {
+        Ok(vec![
+            // 10 on hand, 8 reserved and 0 incoming is 2, below the minimum.
+            forecast(1, 8, 10.0, 8.0, 0.0),
+            // Covered by an open order.
+            forecast(2, 9, 0.0, 0.0, 20.0),
+            // Nobody sells it.
+            forecast(3, 10, 0.0, 0.0, 0.0),
+        ])
+    });
+    state
+        .stocks
+        .expect_list_virtual()
+        .once()
+        .returning(|| Ok(vec![stock(90, StockKind::Supplier)]));
+    mock_taxes(&mut state, supplier(30), vec![]);
+    state
+        .supplier_prices
+        .expect_list_for_variant()
+        .returning(|variant_id| match variant_id {
+            8 => Ok(vec![supplier_price(8, 30, 6.0)]),
+            _ => Ok(vec![]),
+        });
+    state
+        .variants
+        .expect_get()
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .purchase_orders
+        .expect_propose()
+        .once()
+        .withf(|_, proposals| {
+            proposals
+                == &[PurchaseProposalDraft {
+                    supplier_id: 30,
+                    source_stock_id: 90,
+                    target_stock_id: 2,
+                    // 18 short of the maximum, in packs of 6.
+                    lines: vec![PurchaseOrderLineDraft {
+                        description: "S-8 Stoneware mug".to_string(),
+                        quantity: 18.0,
+                        price: 450,
+                        tax: 19,
+                        tax_id: Some(7),
+                        variant_id: Some(8),
+                        discount: Discount::default(),
+                        unit_id: Some(1),
+                    }],
+                }]
+        })
+        .returning(|_, proposals| Ok(vec![order(4, &proposals[0])]));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::reorder_rules::replenish),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/reorder-rules/replenish")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["orders"].as_array().unwrap().len(), 1);
+    assert_eq!(body["orders"][0]["supplierId"], 30);
+    assert_eq!(body["orders"][0]["status"], "draft");
+    assert_eq!(body["withoutSupplier"].as_array().unwrap().len(), 1);
+    assert_eq!(body["withoutSupplier"][0]["variantId"], 10);
+    assert_eq!(body["withoutSupplier"][0]["quantity"], 20.0);
+}
+
+#[actix_web::test]
+async fn test_purchase_rule() {
+    let mut state = MockAppState::new();
+    state
+        .reorder_rules
+        .expect_forecast()
+        .once()
+        .returning(|| Ok(vec![forecast(1, 8, 0.0, 0.0, 0.0)]));
+    state
+        .stocks
+        .expect_list_virtual()
+        .once()
+        .returning(|| Ok(vec![stock(90, StockKind::Supplier)]));
+    // Purchases from EU suppliers with a VAT ID are acquisitions, whatever
+    // the category's tax.
+    mock_taxes(
+        &mut state,
+        entity::contacts::Model {
+            country: "AT".to_string(),
+            vat_id: "ATU12345678".to_string(),
+            ..supplier(30)
+        },
+        vec![entity::tax_rules::Model {
+            id: 1,
+            direction: TaxDirection::Purchase,
+            region: TaxRegion::Eu,
+            vat_id: Some(true),
+            product_tax_category: None,
+            tax_id: 4,
+        }],
+    );
+    state
+        .supplier_prices
+        .expect_list_for_variant()
+        .returning(|variant_id| Ok(vec![supplier_price(variant_id, 30, 1.0)]));
+    state
+        .variants
+        .expect_get()
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .purchase_orders
+        .expect_propose()
+        .once()
+        .withf(|_, proposals| {
+            let line = &proposals[0].lines[0];
+            line.tax == 0 && line.tax_id == Some(4)
+        })
+        .returning(|_, proposals| Ok(vec![order(4, &proposals[0])]));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::reorder_rules::replenish),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/reorder-rules/replenish")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+}
+
+#[actix_web::test]
+async fn test_nothing_short() {
+    let mut state = MockAppState::new();
+    state
+        .reorder_rules
+        .expect_forecast()
+        .once()
+        .returning(|| Ok(vec![forecast(1, 8, 12.0, 0.0, 0.0)]));
+    state.purchase_orders.expect_propose().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::reorder_rules::replenish),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/reorder-rules/replenish")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["orders"], serde_json::json!([]));
+    assert_eq!(body["withoutSupplier"], serde_json::json!([]));
+}
+
+#[actix_web::test]
+async fn test_supplier_location_missing() {
+    let mut state = MockAppState::new();
+    state
+        .reorder_rules
+        .expect_forecast()
+        .once()
+        .returning(|| Ok(vec![forecast(1, 8, 0.0, 0.0, 0.0)]));
+    state
+        .stocks
+        .expect_list_virtual()
+        .once()
+        .returning(|| Ok(vec![]));
+    state.purchase_orders.expect_propose().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::reorder_rules::replenish),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/reorder-rules/replenish")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "SUPPLIER_LOCATION_MISSING");
+}
@@ -0,0 +1,27 @@
+use crate::utils::replenishment::{order_quantity, shortfall};
+
+#[test]
+fn test_shortfall_below_minimum() {
+    assert_eq!(shortfall(3.0, 5.0, 20.0), Some(17.0));
+    assert_eq!(shortfall(-2.0, 0.0, 10.0), Some(12.0));
+}
+
+#[test]
+fn test_no_shortfall_at_minimum() {
+    assert_eq!(shortfall(5.0, 5.0, 20.0), None);
+    assert_eq!(shortfall(5.0 - 1e-12, 5.0, 20.0), None);
+    assert_eq!(shortfall(30.0, 5.0, 20.0), None);
+}
+
+#[test]
+fn test_order_quantity_minimum() {
+    assert_eq!(order_quantity(3.0, 10.0, 0.0), 10.0);
+    assert_eq!(order_quantity(12.5, 10.0, 0.0), 12.5);
+}
+
+#[test]
+fn test_order_quantity_packs() {
+    assert_eq!(order_quantity(13.0, 0.0, 6.0), 18.0);
+    assert_eq!(order_quantity(12.0, 0.0, 6.0), 12.0);
+    assert_eq!(order_quantity(3.0, 10.0, 6.0), 12.0);
+}
@@ -0,0 +1,52 @@
+use actix_web::{App, test, web};
+
+use crate::{
+    handlers,
+    repositories::VariantValuation,
+    state::{AppState, MockAppState},
+};
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .stock_valuation_layers
+        .expect_summary()
+        .once()
+        .returning(|| {
+            Ok(vec![
+                VariantValuation {
+                    variant_id: 8,
+                    name: "Mug".to_string(),
+                    quantity: 4.0,
+                    value: 3600,
+                },
+                VariantValuation {
+                    variant_id: 10,
+                    name: "Plate".to_string(),
+                    quantity: 12.0,
+                    value: 5400,
+                },
+            ])
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::reports::inventory_valuation),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/reports/inventory-valuation")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["lines"][0]["variantId"], 8);
+    assert_eq!(body["lines"][0]["unitCost"], 900.0);
+    assert_eq!(body["lines"][1]["value"], 5400);
+    assert_eq!(body["total"], 9000);
+}
@@ -0,0 +1,287 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{PurchaseInvoiceStatus, SaleInvoiceStatus};
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr, prelude::Date};
+
+use crate::{
+    documents::Letterhead,
+    handlers,
+    repositories::ResolvedTax,
+    state::{AppState, MockAppState},
+};
+
+fn sale_invoice_line(
+    id: i32,
+    quantity: f64,
+    price: i32,
+    tax: i32,
+    tax_id: Option<i32>,
+) -> entity::sale_invoice_lines::Model {
+    entity::sale_invoice_lines::Model {
+        id,
+        description: "".to_string(),
+        quantity,
+        price,
+        tax,
+        invoice_id: 1,
+        variant_id: None,
+        transaction_id: None,
+        tax_id,
+        tax_transaction_id: None,
+        discount_percent: 0.0,
+        discount_amount: 0,
+        discount_transaction_id: None,
+    }
+}
+
+pub fn sale_invoice(date: Date) -> entity::sale_invoices::Model {
+    entity::sale_invoices::Model {
+        id: 1,
+        reference: "INV-2025-00001".to_string(),
+        date,
+        status: SaleInvoiceStatus::Invoice,
+        order_id: 3,
+        customer_id: 4,
+        discount_percent: 0.0,
+        discount_amount: 0,
+    }
+}
+
+pub fn contact(id: i32, country: &str) -> entity::contacts::Model {
+    entity::contacts::Model {
+        id,
+        name: "Huber KG".to_string(),
+        is_customer: true,
+        is_supplier: true,
+        street1: "".to_string(),
+        street2: "".to_string(),
+        postal_code: "".to_string(),
+        city: "".to_string(),
+        country: country.to_string(),
+        customer_account_id: Some(1),
+        supplier_account_id: Some(2),
+        vat_id: "".to_string(),
+        currency: "EUR".to_string(),
+    }
+}
+
+/// Mocks a period with one sale invoice, one of its chairs credited and
+/// one purchase invoice.
+pub fn mock_period(state: &mut MockAppState) {
+    state.letterhead = Letterhead {
+        country: "DE".to_string(),
+        ..Letterhead::default()
+    };
+    let from = Date::from_ymd_opt(2025, 7, 1).unwrap();
+    let to = Date::from_ymd_opt(2025, 7, 31).unwrap();
+
+    state
+        .sale_invoices
+        .expect_list_posted_with_lines()
+        .once()
+        .with(predicate::eq(from), predicate::eq(to))
+        .returning(|from, _| {
+            let lines = vec![
+                sale_invoice_line(1, 10.0, 4999, 19, Some(7)),
+                sale_invoice_line(2, 1.0, 1500, 7, None),
+            ];
+            Ok(vec![(sale_invoice(from), lines)])
+        });
+    state
+        .sale_credit_notes
+        .expect_list_with_lines()
+        .once()
+        .with(predicate::eq(from), predicate::eq(to))
+        .returning(|from, _| {
+            let credit_note = entity::sale_credit_notes::Model {
+                id: 1,
+                reference: "CN-2025-00001".to_string(),
+                date: from,
+                invoice_id: 1,
+                reason: "".to_string(),
+            };
+            let line = entity::sale_credit_note_lines::Model {
+                id: 1,
+                credit_note_id: 1,
+                invoice_line_id: 1,
+                description: "".to_string(),
+                quantity: 1.0,
+                price: 4999,
+                tax: 19,
+                tax_id: Some(7),
+                variant_id: None,
+                transaction_id: None,
+                tax_transaction_id: None,
+                move_id: None,
+                discount_amount: 0,
+                discount_transaction_id: None,
+            };
+            Ok(vec![(credit_note, vec![line], sale_invoice(from))])
+        });
+    state
+        .purchase_invoices
+        .expect_list_posted_with_lines()
+        .once()
+        .with(predicate::eq(from), predicate::eq(to))
+        .returning(|from, _| {
+            let invoice = entity::purchase_invoices::Model {
+                id: 1,
+                reference: "PI-2025-00001".to_string(),
+                date: from,
+                status: PurchaseInvoiceStatus::Finished,
+                order_id: None,
+                supplier_id: Some(5),
+                needs_review: false,
+                review_note: "".to_string(),
+                supplier_reference: "R-1".to_string(),
+                discount_percent: 0.0,
+                discount_amount: 0,
+            };
+            let line = entity::purchase_invoice_lines::Model {
+                id: 1,
+                description: "".to_string(),
+                quantity: 1.0,
+                price: 100000,
+                tax: 19,
+                invoice_id: 1,
+                variant_id: None,
+                transaction_id: None,
+                tax_id: Some(7),
+                tax_transaction_id: None,
+                discount_percent: 0.0,
+                discount_amount: 0,
+                discount_transaction_id: None,
+            };
+            Ok(vec![(invoice, vec![line])])
+        });
+    state
+        .purchase_credit_notes
+        .expect_list_with_lines()
+        .once()
+        .returning(|_, _| Ok(vec![]));
+    state
+        .contacts
+        .expect_get()
+        .times(2)
+        .returning(|id| Ok(Some(contact(id, "DE"))));
+    state
+        .taxes
+        .expect_resolve_at()
+        .once()
+        .with(predicate::eq(7), predicate::eq(from))
+        .returning(|id, _| {
+            Ok(Some(ResolvedTax::plain(entity::taxes::Model {
+                id,
+                name: "Sales tax".to_string(),
+                name_short: "19%".to_string(),
+                rate: 0.19,
+                account_id: 1,
+                category: "S".to_string(),
+                note: "".to_string(),
+            })))
+        });
+}
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    mock_period(&mut state);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::reports::vat),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/reports/vat?from=2025-07-01&to=2025-07-31")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["sales"][0]["taxId"], 7);
+    assert_eq!(body["sales"][0]["category"], "S");
+    assert_eq!(body["sales"][0]["region"], "domestic");
+    assert_eq!(body["sales"][0]["base"], 44991);
+    assert_eq!(body["sales"][0]["tax"], 8548);
+    assert_eq!(body["sales"][1]["label"], "7%");
+    assert_eq!(body["sales"][1]["base"], 1500);
+    assert_eq!(body["sales"][1]["tax"], 105);
+    assert_eq!(body["purchases"][0]["tax"], 19000);
+    assert_eq!(body["outputTax"], 8653);
+    assert_eq!(body["inputTax"], 19000);
+    assert_eq!(body["balance"], -10347);
+}
+
+#[actix_web::test]
+async fn test_invalid_date() {
+    let mut state = MockAppState::new();
+    state.sale_invoices.expect_list_posted_with_lines().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::reports::vat),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/reports/vat?from=2025-07-01&to=July")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+}
+
+#[actix_web::test]
+async fn test_invalid_date_range() {
+    let mut state = MockAppState::new();
+    state.sale_invoices.expect_list_posted_with_lines().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::reports::vat),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/reports/vat?from=2025-07-31&to=2025-07-01")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVALID_DATE_RANGE");
+}
+
+#[actix_web::test]
+async fn test_connection_error() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_list_posted_with_lines()
+        .once()
+        .returning(|_, _| {
+            let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+            Err(err)
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::reports::vat),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/reports/vat?from=2025-07-01&to=2025-07-31")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
+}
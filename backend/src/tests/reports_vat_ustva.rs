@@ -0,0 +1,300 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::PurchaseInvoiceStatus;
+
+use crate::{
+    documents::Letterhead,
+    handlers,
+    repositories::ResolvedTax,
+    state::{AppState, MockAppState},
+    tests::reports_vat::{contact, mock_period, sale_invoice},
+};
+
+/// Mocks a period with a tax-free sale of 1,000.00 to a customer in the
+/// country and a tax-free purchase of 500.00 from a supplier in the other
+/// one, taxed by taxes of the given categories.
+fn mock_tax_free(
+    state: &mut MockAppState,
+    customer_country: &'static str,
+    sale_category: &'static str,
+    supplier_country: &'static str,
+    purchase_category: &'static str,
+) {
+    state.letterhead = Letterhead {
+        country: "DE".to_string(),
+        ..Letterhead::default()
+    };
+    state
+        .sale_invoices
+        .expect_list_posted_with_lines()
+        .once()
+        .returning(|from, _| {
+            let line = entity::sale_invoice_lines::Model {
+                id: 1,
+                description: "".to_string(),
+                quantity: 10.0,
+                price: 10000,
+                tax: 0,
+                invoice_id: 1,
+                variant_id: None,
+                transaction_id: None,
+                tax_id: Some(8),
+                tax_transaction_id: None,
+                discount_percent: 0.0,
+                discount_amount: 0,
+                discount_transaction_id: None,
+            };
+            Ok(vec![(sale_invoice(from), vec![line])])
+        });
+    state
+        .sale_credit_notes
+        .expect_list_with_lines()
+        .once()
+        .returning(|_, _| Ok(vec![]));
+    state
+        .purchase_invoices
+        .expect_list_posted_with_lines()
+        .once()
+        .returning(|from, _| {
+            let invoice = entity::purchase_invoices::Model {
+                id: 1,
+                reference: "PI-2025-00001".to_string(),
+                date: from,
+                status: PurchaseInvoiceStatus::Finished,
+                order_id: None,
+                supplier_id: Some(5),
+                needs_review: false,
+                review_note: "".to_string(),
+                supplier_reference: "R-1".to_string(),
+                discount_percent: 0.0,
+                discount_amount: 0,
+            };
+            let line = entity::purchase_invoice_lines::Model {
+                id: 1,
+                description: "".to_string(),
+                quantity: 1.0,
+                price: 50000,
+                tax: 0,
+                invoice_id: 1,
+                variant_id: None,
+                transaction_id: None,
+                tax_id: Some(9),
+                tax_transaction_id: None,
+                discount_percent: 0.0,
+                discount_amount: 0,
+                discount_transaction_id: None,
+            };
+            Ok(vec![(invoice, vec![line])])
+        });
+    state
+        .purchase_credit_notes
+        .expect_list_with_lines()
+        .once()
+        .returning(|_, _| Ok(vec![]));
+    state
+        .contacts
+        .expect_get()
+        .times(2)
+        .returning(move |id| match id {
+            4 => Ok(Some(contact(id, customer_country))),
+            _ => Ok(Some(contact(id, supplier_country))),
+        });
+    state
+        .taxes
+        .expect_resolve_at()
+        .times(2)
+        .returning(move |id, _| {
+            let category = if id == 8 {
+                sale_category
+            } else {
+                purchase_category
+            };
+            Ok(Some(ResolvedTax::plain(entity::taxes::Model {
+                id,
+                name: "Tax-free".to_string(),
+                name_short: "0%".to_string(),
+                rate: 0.0,
+                account_id: 1,
+                category: category.to_string(),
+                note: "".to_string(),
+            })))
+        });
+}
+
+async fn ustva_fields(state: MockAppState) -> Vec<(String, String)> {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::reports::ustva),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/reports/vat/ustva?from=2025-07-01&to=2025-07-31")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    body["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|field| {
+            (
+                field["field"].as_str().unwrap().to_string(),
+                field["value"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect()
+}
+
+fn field(field: &str, value: &str) -> (String, String) {
+    (field.to_string(), value.to_string())
+}
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    mock_period(&mut state);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::reports::ustva),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/reports/vat/ustva?from=2025-07-01&to=2025-07-31")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    let fields: Vec<(String, String)> = body["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|field| {
+            (
+                field["field"].as_str().unwrap().to_string(),
+                field["value"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        fields,
+        vec![
+            ("81".to_string(), "449".to_string()),
+            ("86".to_string(), "15".to_string()),
+            ("66".to_string(), "190,00".to_string()),
+            ("83".to_string(), "-103,47".to_string()),
+        ]
+    );
+}
+
+#[actix_web::test]
+async fn test_invalid_date_range() {
+    let state = MockAppState::new();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::reports::ustva),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/reports/vat/ustva?from=2025-08-01&to=2025-07-31")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+}
+
+#[actix_web::test]
+async fn test_intra_eu_supply() {
+    let mut state = MockAppState::new();
+    mock_tax_free(&mut state, "AT", "K", "DE", "E");
+
+    let fields = ustva_fields(state).await;
+    assert_eq!(
+        fields,
+        vec![
+            field("41", "1000"),
+            field("81", "0"),
+            field("86", "0"),
+            field("66", "0,00"),
+            field("83", "0,00"),
+        ]
+    );
+}
+
+#[actix_web::test]
+async fn test_export() {
+    let mut state = MockAppState::new();
+    mock_tax_free(&mut state, "US", "G", "DE", "E");
+
+    let fields = ustva_fields(state).await;
+    assert_eq!(fields[0], field("43", "1000"));
+    assert!(!fields.iter().any(|(field, _)| field == "41"));
+}
+
+#[actix_web::test]
+async fn test_domestic_exemption_left_out() {
+    let mut state = MockAppState::new();
+    mock_tax_free(&mut state, "DE", "E", "DE", "E");
+
+    let fields = ustva_fields(state).await;
+    assert_eq!(
+        fields
+            .iter()
+            .map(|(field, _)| field.as_str())
+            .collect::<Vec<_>>(),
+        vec!["81", "86", "66", "83"]
+    );
+}
+
+#[actix_web::test]
+async fn test_reverse_charge_sale() {
+    let mut state = MockAppState::new();
+    mock_tax_free(&mut state, "DE", "AE", "DE", "E");
+
+    let fields = ustva_fields(state).await;
+    assert!(fields.contains(&field("60", "1000")));
+    assert!(!fields.iter().any(|(field, _)| field == "41"));
+}
+
+#[actix_web::test]
+async fn test_reverse_charge_purchase_from_eu() {
+    let mut state = MockAppState::new();
+    mock_tax_free(&mut state, "DE", "E", "AT", "AE");
+
+    let fields = ustva_fields(state).await;
+    assert_eq!(
+        fields,
+        vec![
+            field("81", "0"),
+            field("86", "0"),
+            field("46", "500"),
+            field("47", "95,00"),
+            field("66", "0,00"),
+            field("67", "95,00"),
+            field("83", "0,00"),
+        ]
+    );
+}
+
+#[actix_web::test]
+async fn test_reverse_charge_purchase_from_elsewhere() {
+    let mut state = MockAppState::new();
+    mock_tax_free(&mut state, "DE", "E", "CH", "AE");
+
+    let fields = ustva_fields(state).await;
+    assert!(fields.contains(&field("84", "500")));
+    assert!(fields.contains(&field("85", "95,00")));
+    assert!(fields.contains(&field("67", "95,00")));
+    assert!(!fields.iter().any(|(field, _)| field == "46"));
+}
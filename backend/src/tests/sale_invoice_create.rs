@@ -1,39 +1,233 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::SaleInvoiceStatus;
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr, prelude::Date};
+use serde_json::json;
+
+use crate::{
+    handlers,
+    repositories::ERR_PERIOD_CLOSED,
+    state::{AppState, MockAppState},
+    utils::totals::Discount,
+};
+
 #[actix_web::test]
 async fn test_success() {
-    todo!();
-}
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq(Date::from_ymd_opt(2025, 7, 1).unwrap()),
+            predicate::eq(SaleInvoiceStatus::Draft),
+            predicate::eq(3),
+            predicate::eq(4),
+            predicate::eq(Discount::default()),
+        )
+        .returning(|date, status, order_id, customer_id, discount| {
+            Ok(entity::sale_invoices::Model {
+                id: 1,
+                reference: "INV-2025-00007".to_string(),
+                date,
+                status,
+                order_id,
+                customer_id,
+                discount_percent: discount.percent,
+                discount_amount: discount.amount as i32,
+            })
+        });
 
-#[actix_web::test]
-async fn test_customer_invalid_reference() {
-    todo!();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_invoices::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices")
+        .set_json(json!({
+            "date": "2025-07-01",
+            "status": "draft",
+            "orderId": 3,
+            "customerId": 4,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["reference"], "INV-2025-00007");
 }
 
 #[actix_web::test]
-async fn test_supplier_reference_unique() {
-    todo!();
+async fn test_invalid_date() {
+    let mut state = MockAppState::new();
+    state.sale_invoices.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_invoices::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices")
+        .set_json(json!({
+            "date": "01.07.2025",
+            "status": "draft",
+            "orderId": 3,
+            "customerId": 4,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
 }
 
 #[actix_web::test]
-async fn test_customer_invalid_date() {
-    todo!();
+async fn test_invalid_status() {
+    let mut state = MockAppState::new();
+    state.sale_invoices.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_invoices::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices")
+        .set_json(json!({
+            "date": "2025-07-01",
+            "status": "paid",
+            "orderId": 3,
+            "customerId": 4,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
 }
 
 #[actix_web::test]
-async fn test_supplier_invalid_status() {
-    todo!();
+async fn test_invalid_order_id() {
+    let mut state = MockAppState::new();
+    state.sale_invoices.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_invoices::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices")
+        .set_json(json!({
+            "date": "2025-07-01",
+            "status": "draft",
+            "orderId": 0,
+            "customerId": 4,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
 }
 
 #[actix_web::test]
-async fn test_supplier_invalid_order_id() {
-    todo!();
+async fn test_invalid_customer_id() {
+    let mut state = MockAppState::new();
+    state.sale_invoices.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_invoices::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices")
+        .set_json(json!({
+            "date": "2025-07-01",
+            "status": "draft",
+            "orderId": 3,
+            "customerId": 0,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
 }
 
 #[actix_web::test]
-async fn test_supplier_invalid_customer_id() {
-    todo!();
+async fn test_period_closed() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_create()
+        .once()
+        .returning(|_, _, _, _, _| {
+            let err = DbErr::Custom(ERR_PERIOD_CLOSED.to_string());
+            Err(err)
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_invoices::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices")
+        .set_json(json!({
+            "date": "2025-07-01",
+            "status": "draft",
+            "orderId": 3,
+            "customerId": 4,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
 }
 
 #[actix_web::test]
 async fn test_connection_error() {
-    todo!();
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_create()
+        .once()
+        .returning(|_, _, _, _, _| {
+            let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+            Err(err)
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_invoices::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices")
+        .set_json(json!({
+            "date": "2025-07-01",
+            "status": "draft",
+            "orderId": 3,
+            "customerId": 4,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
 }
@@ -0,0 +1,1119 @@
+use std::collections::HashMap;
+
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{SaleInvoiceStatus, SaleOrderStatus, Tracking};
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr, prelude::Date};
+use serde_json::json;
+
+use crate::{
+    handlers,
+    repositories::{
+        CreditNoteLineDraft, ERR_CREDIT_EXCEEDS_INVOICE, ERR_PERIOD_CLOSED, LotDraft, ResolvedTax,
+        TaxComponent, TaxPostingDraft,
+    },
+    state::{AppState, MockAppState},
+    tests::fixtures::{sale_invoice, sale_invoice_line, tax},
+};
+
+fn invoice_lines(invoice_id: i32) -> Vec<entity::sale_invoice_lines::Model> {
+    vec![
+        entity::sale_invoice_lines::Model {
+            description: "Chair".to_string(),
+            quantity: 2.0,
+            price: 4999,
+            variant_id: Some(8),
+            transaction_id: Some(11),
+            tax_id: Some(7),
+            tax_transaction_id: Some(12),
+            ..sale_invoice_line(1, invoice_id)
+        },
+        entity::sale_invoice_lines::Model {
+            description: "Delivery".to_string(),
+            price: 1500,
+            transaction_id: Some(13),
+            ..sale_invoice_line(2, invoice_id)
+        },
+    ]
+}
+
+fn credited_line(invoice_line_id: i32, quantity: f64) -> entity::sale_credit_note_lines::Model {
+    entity::sale_credit_note_lines::Model {
+        id: 20 + invoice_line_id,
+        credit_note_id: 2,
+        invoice_line_id,
+        description: "".to_string(),
+        quantity,
+        price: 0,
+        tax: 19,
+        tax_id: None,
+        variant_id: None,
+        transaction_id: None,
+        tax_transaction_id: None,
+        move_id: None,
+        discount_amount: 0,
+        discount_transaction_id: None,
+    }
+}
+
+fn chair_draft(quantity: f64, net: i32, tax_amount: i32) -> CreditNoteLineDraft {
+    CreditNoteLineDraft {
+        invoice_line_id: 1,
+        description: "Chair".to_string(),
+        quantity,
+        price: 4999,
+        tax: 19,
+        tax_id: Some(7),
+        variant_id: Some(8),
+        net,
+        tax_amount,
+        transaction_id: Some(11),
+        tax_transaction_id: Some(12),
+        tax_postings: vec![],
+        discount: 0,
+        discount_transaction_id: None,
+        stock_return: None,
+        lots: Vec::new(),
+    }
+}
+
+fn created(
+    invoice_id: i32,
+    date: Date,
+    reason: String,
+    lines: Vec<CreditNoteLineDraft>,
+) -> (
+    entity::sale_credit_notes::Model,
+    Vec<entity::sale_credit_note_lines::Model>,
+) {
+    let credit_note = entity::sale_credit_notes::Model {
+        id: 1,
+        reference: "CN-2025-00001".to_string(),
+        date,
+        invoice_id,
+        reason,
+    };
+    let lines = lines
+        .into_iter()
+        .enumerate()
+        .map(|(index, line)| entity::sale_credit_note_lines::Model {
+            id: index as i32 + 1,
+            credit_note_id: 1,
+            invoice_line_id: line.invoice_line_id,
+            description: line.description,
+            quantity: line.quantity,
+            price: line.price,
+            tax: line.tax,
+            tax_id: line.tax_id,
+            variant_id: line.variant_id,
+            transaction_id: line.transaction_id.map(|id| id + 100),
+            tax_transaction_id: line.tax_transaction_id.map(|id| id + 100),
+            move_id: line.stock_return.map(|_| 30),
+            discount_amount: line.discount,
+            discount_transaction_id: line.discount_transaction_id.map(|id| id + 100),
+        })
+        .collect();
+    (credit_note, lines)
+}
+
+#[actix_web::test]
+async fn test_success_selected_lines() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|id| Ok(Some(sale_invoice(id, SaleInvoiceStatus::Invoice))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|invoice_id| Ok(invoice_lines(invoice_id)));
+    state
+        .sale_invoice_lines
+        .expect_list_taxes_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state
+        .sale_credit_notes
+        .expect_list_lines_for_invoice()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|_| Ok(vec![]));
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .with(predicate::eq(3))
+        .returning(|id| {
+            Ok(Some(entity::sale_orders::Model {
+                id,
+                reference: "SO-2025-00001".to_string(),
+                date: Date::from_ymd_opt(2025, 6, 20).unwrap(),
+                status: SaleOrderStatus::Finished,
+                customer_id: 4,
+                source_stock_id: 1,
+                target_stock_id: 2,
+                discount_percent: 0.0,
+                discount_amount: 0,
+            }))
+        });
+    state
+        .variants
+        .expect_stockable_ids()
+        .once()
+        .with(predicate::eq(vec![8]))
+        .returning(Ok);
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .returning(|_| Ok(HashMap::new()));
+    state
+        .taxes
+        .expect_resolve_at()
+        .once()
+        // Credited at the rate of the invoice date.
+        .with(
+            predicate::eq(7),
+            predicate::eq(Date::from_ymd_opt(2025, 7, 1).unwrap()),
+        )
+        .returning(|id, _| Ok(Some(ResolvedTax::plain(tax(id)))));
+    state
+        .sale_credit_notes
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq(5),
+            predicate::eq(Date::from_ymd_opt(2025, 7, 10).unwrap()),
+            predicate::eq("Damaged in transit".to_string()),
+            predicate::eq(vec![CreditNoteLineDraft {
+                stock_return: Some((2, 1)),
+                ..chair_draft(1.0, 4999, 950)
+            }]),
+        )
+        .returning(|invoice_id, date, reason, lines| Ok(created(invoice_id, date, reason, lines)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::create_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/5/credit-notes")
+        .set_json(json!({
+            "date": "2025-07-10",
+            "reason": "Damaged in transit",
+            "returnStock": true,
+            "lines": [{ "invoiceLineId": 1, "quantity": 1.0 }],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["reference"], "CN-2025-00001");
+    assert_eq!(body["invoiceId"], 5);
+    assert_eq!(body["grossTotal"], 5949);
+    assert_eq!(body["lines"][0]["transactionId"], 111);
+    assert_eq!(body["lines"][0]["moveId"], 30);
+}
+
+#[actix_web::test]
+async fn test_returned_in_serials() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(sale_invoice(id, SaleInvoiceStatus::Invoice))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|invoice_id| Ok(invoice_lines(invoice_id)));
+    state
+        .sale_invoice_lines
+        .expect_list_taxes_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state
+        .sale_credit_notes
+        .expect_list_lines_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state.sale_orders.expect_get().once().returning(|id| {
+        Ok(Some(entity::sale_orders::Model {
+            id,
+            reference: "SO-2025-00001".to_string(),
+            date: Date::from_ymd_opt(2025, 6, 20).unwrap(),
+            status: SaleOrderStatus::Finished,
+            customer_id: 4,
+            source_stock_id: 1,
+            target_stock_id: 2,
+            discount_percent: 0.0,
+            discount_amount: 0,
+        }))
+    });
+    state.variants.expect_stockable_ids().once().returning(Ok);
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .with(predicate::eq(vec![8]))
+        .returning(|ids| Ok(ids.into_iter().map(|id| (id, Tracking::Serial)).collect()));
+    state
+        .taxes
+        .expect_resolve_at()
+        .once()
+        .returning(|id, _| Ok(Some(ResolvedTax::plain(tax(id)))));
+    // The chair comes back with the serial number it was delivered in.
+    state
+        .sale_credit_notes
+        .expect_create()
+        .once()
+        .with(
+            predicate::always(),
+            predicate::always(),
+            predicate::always(),
+            predicate::eq(vec![CreditNoteLineDraft {
+                stock_return: Some((2, 1)),
+                lots: vec![LotDraft {
+                    number: "SN-7".to_string(),
+                    quantity: 1.0,
+                    expiry_date: None,
+                }],
+                ..chair_draft(1.0, 4999, 950)
+            }]),
+        )
+        .returning(|invoice_id, date, reason, lines| Ok(created(invoice_id, date, reason, lines)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::create_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/5/credit-notes")
+        .set_json(json!({
+            "date": "2025-07-10",
+            "returnStock": true,
+            "lines": [{ "invoiceLineId": 1, "quantity": 1.0 }],
+            "lots": [{ "lineId": 1, "number": "SN-7", "quantity": 1.0 }],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+}
+
+#[actix_web::test]
+async fn test_lots_missing() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(sale_invoice(id, SaleInvoiceStatus::Invoice))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|invoice_id| Ok(invoice_lines(invoice_id)));
+    state
+        .sale_invoice_lines
+        .expect_list_taxes_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state
+        .sale_credit_notes
+        .expect_list_lines_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state.sale_orders.expect_get().once().returning(|id| {
+        Ok(Some(entity::sale_orders::Model {
+            id,
+            reference: "SO-2025-00001".to_string(),
+            date: Date::from_ymd_opt(2025, 6, 20).unwrap(),
+            status: SaleOrderStatus::Finished,
+            customer_id: 4,
+            source_stock_id: 1,
+            target_stock_id: 2,
+            discount_percent: 0.0,
+            discount_amount: 0,
+        }))
+    });
+    state.variants.expect_stockable_ids().once().returning(Ok);
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .with(predicate::eq(vec![8]))
+        .returning(|ids| Ok(ids.into_iter().map(|id| (id, Tracking::Lot)).collect()));
+    state
+        .taxes
+        .expect_resolve_at()
+        .once()
+        .returning(|id, _| Ok(Some(ResolvedTax::plain(tax(id)))));
+    state.sale_credit_notes.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::create_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/5/credit-notes")
+        .set_json(json!({
+            "date": "2025-07-10",
+            "returnStock": true,
+            "lines": [{ "invoiceLineId": 1, "quantity": 1.0 }],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "LOTS_MISSING");
+}
+
+#[actix_web::test]
+async fn test_service_not_returned() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|id| Ok(Some(sale_invoice(id, SaleInvoiceStatus::Invoice))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|invoice_id| Ok(invoice_lines(invoice_id)));
+    state
+        .sale_invoice_lines
+        .expect_list_taxes_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state
+        .sale_credit_notes
+        .expect_list_lines_for_invoice()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|_| Ok(vec![]));
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .with(predicate::eq(3))
+        .returning(|id| {
+            Ok(Some(entity::sale_orders::Model {
+                id,
+                reference: "SO-2025-00001".to_string(),
+                date: Date::from_ymd_opt(2025, 6, 20).unwrap(),
+                status: SaleOrderStatus::Finished,
+                customer_id: 4,
+                source_stock_id: 1,
+                target_stock_id: 2,
+                discount_percent: 0.0,
+                discount_amount: 0,
+            }))
+        });
+    state
+        .variants
+        .expect_stockable_ids()
+        .once()
+        .with(predicate::eq(vec![8]))
+        // The chair is booked as a service, e.g. an assembly fee.
+        .returning(|_| Ok(vec![]));
+    state
+        .taxes
+        .expect_resolve_at()
+        .once()
+        // Credited at the rate of the invoice date.
+        .with(
+            predicate::eq(7),
+            predicate::eq(Date::from_ymd_opt(2025, 7, 1).unwrap()),
+        )
+        .returning(|id, _| Ok(Some(ResolvedTax::plain(tax(id)))));
+    state
+        .sale_credit_notes
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq(5),
+            predicate::eq(Date::from_ymd_opt(2025, 7, 10).unwrap()),
+            predicate::eq("Damaged in transit".to_string()),
+            predicate::eq(vec![chair_draft(1.0, 4999, 950)]),
+        )
+        .returning(|invoice_id, date, reason, lines| Ok(created(invoice_id, date, reason, lines)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::create_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/5/credit-notes")
+        .set_json(json!({
+            "date": "2025-07-10",
+            "reason": "Damaged in transit",
+            "returnStock": true,
+            "lines": [{ "invoiceLineId": 1, "quantity": 1.0 }],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+}
+
+#[actix_web::test]
+async fn test_success_discounts() {
+    let mut state = MockAppState::new();
+    state.sale_invoices.expect_get().once().returning(|id| {
+        Ok(Some(entity::sale_invoices::Model {
+            discount_amount: 1000,
+            ..sale_invoice(id, SaleInvoiceStatus::Invoice)
+        }))
+    });
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|invoice_id| {
+            let mut lines = invoice_lines(invoice_id);
+            lines[0].discount_percent = 10.0;
+            lines[0].discount_transaction_id = Some(14);
+            Ok(lines)
+        });
+    state
+        .sale_invoice_lines
+        .expect_list_taxes_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state
+        .sale_credit_notes
+        .expect_list_lines_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state
+        .taxes
+        .expect_resolve_at()
+        .once()
+        .returning(|id, _| Ok(Some(ResolvedTax::plain(tax(id)))));
+    // The chair is 89.98 after its discount and 81.41 after its share of
+    // the invoice discount, half of which is credited.
+    state
+        .sale_credit_notes
+        .expect_create()
+        .once()
+        .with(
+            predicate::always(),
+            predicate::always(),
+            predicate::always(),
+            predicate::eq(vec![CreditNoteLineDraft {
+                discount: 928,
+                discount_transaction_id: Some(14),
+                ..chair_draft(1.0, 4071, 773)
+            }]),
+        )
+        .returning(|invoice_id, date, reason, lines| Ok(created(invoice_id, date, reason, lines)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::create_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/5/credit-notes")
+        .set_json(json!({
+            "date": "2025-07-10",
+            "lines": [{ "invoiceLineId": 1, "quantity": 1.0 }],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["grossTotal"], 4844);
+    assert_eq!(body["lines"][0]["discountAmount"], 928);
+    assert_eq!(body["lines"][0]["discountTransactionId"], 114);
+}
+
+#[actix_web::test]
+async fn test_success_remaining() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(sale_invoice(id, SaleInvoiceStatus::Finished))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|invoice_id| Ok(invoice_lines(invoice_id)));
+    state
+        .sale_invoice_lines
+        .expect_list_taxes_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state
+        .sale_credit_notes
+        .expect_list_lines_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![credited_line(1, 1.0)]));
+    state.sale_orders.expect_get().never();
+    state
+        .taxes
+        .expect_resolve_at()
+        .once()
+        .returning(|id, _| Ok(Some(ResolvedTax::plain(tax(id)))));
+    state
+        .sale_credit_notes
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq(5),
+            predicate::eq(Date::from_ymd_opt(2025, 7, 10).unwrap()),
+            predicate::eq("".to_string()),
+            predicate::eq(vec![
+                chair_draft(1.0, 4999, 950),
+                CreditNoteLineDraft {
+                    invoice_line_id: 2,
+                    description: "Delivery".to_string(),
+                    quantity: 1.0,
+                    price: 1500,
+                    tax: 19,
+                    tax_id: None,
+                    variant_id: None,
+                    net: 1500,
+                    tax_amount: 285,
+                    transaction_id: Some(13),
+                    tax_transaction_id: None,
+                    tax_postings: vec![],
+                    discount: 0,
+                    discount_transaction_id: None,
+                    stock_return: None,
+                    lots: Vec::new(),
+                },
+            ]),
+        )
+        .returning(|invoice_id, date, reason, lines| Ok(created(invoice_id, date, reason, lines)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::create_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/5/credit-notes")
+        .set_json(json!({ "date": "2025-07-10" }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["grossTotal"], 7734);
+}
+
+#[actix_web::test]
+async fn test_success_tax_group() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(sale_invoice(id, SaleInvoiceStatus::Invoice))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|invoice_id| Ok(invoice_lines(invoice_id)));
+    state
+        .sale_invoice_lines
+        .expect_list_taxes_for_invoice()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|_| {
+            Ok(vec![
+                entity::sale_invoice_line_taxes::Model {
+                    id: 1,
+                    line_id: 1,
+                    tax_id: 21,
+                    amount: 500,
+                    transaction_id: Some(31),
+                },
+                entity::sale_invoice_line_taxes::Model {
+                    id: 2,
+                    line_id: 1,
+                    tax_id: 22,
+                    amount: 735,
+                    transaction_id: Some(32),
+                },
+            ])
+        });
+    state
+        .sale_credit_notes
+        .expect_list_lines_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state.taxes.expect_resolve_at().once().returning(|id, _| {
+        let mut resolved = ResolvedTax::plain(tax(id));
+        resolved.components = vec![
+            TaxComponent {
+                tax: entity::taxes::Model {
+                    id: 21,
+                    rate: 0.05,
+                    ..tax(21)
+                },
+                compound: false,
+            },
+            TaxComponent {
+                tax: entity::taxes::Model {
+                    id: 22,
+                    rate: 0.07,
+                    ..tax(22)
+                },
+                compound: true,
+            },
+        ];
+        Ok(Some(resolved))
+    });
+    state
+        .sale_credit_notes
+        .expect_create()
+        .once()
+        .with(
+            predicate::always(),
+            predicate::always(),
+            predicate::always(),
+            // The components are reversed one by one, the compound one on
+            // the net amount plus the first component.
+            predicate::eq(vec![CreditNoteLineDraft {
+                tax_postings: vec![
+                    TaxPostingDraft {
+                        tax_id: 21,
+                        amount: 250,
+                        transaction_id: Some(31),
+                    },
+                    TaxPostingDraft {
+                        tax_id: 22,
+                        amount: 367,
+                        transaction_id: Some(32),
+                    },
+                ],
+                ..chair_draft(1.0, 4999, 617)
+            }]),
+        )
+        .returning(|invoice_id, date, reason, lines| Ok(created(invoice_id, date, reason, lines)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::create_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/5/credit-notes")
+        .set_json(json!({
+            "date": "2025-07-10",
+            "lines": [{ "invoiceLineId": 1, "quantity": 1.0 }],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["grossTotal"], 5616);
+}
+
+#[actix_web::test]
+async fn test_exceeds_invoice() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(sale_invoice(id, SaleInvoiceStatus::Invoice))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|invoice_id| Ok(invoice_lines(invoice_id)));
+    state
+        .sale_invoice_lines
+        .expect_list_taxes_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state
+        .sale_credit_notes
+        .expect_list_lines_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![credited_line(1, 1.5)]));
+    state
+        .taxes
+        .expect_resolve_at()
+        .returning(|id, _| Ok(Some(ResolvedTax::plain(tax(id)))));
+    state.sale_credit_notes.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::create_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/5/credit-notes")
+        .set_json(json!({
+            "date": "2025-07-10",
+            "lines": [{ "invoiceLineId": 1, "quantity": 1.0 }],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "CREDIT_NOTE_EXCEEDS_INVOICE");
+}
+
+#[actix_web::test]
+async fn test_fully_credited() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(sale_invoice(id, SaleInvoiceStatus::Invoice))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|invoice_id| Ok(invoice_lines(invoice_id)));
+    state
+        .sale_invoice_lines
+        .expect_list_taxes_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state
+        .sale_credit_notes
+        .expect_list_lines_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![credited_line(1, 2.0), credited_line(2, 1.0)]));
+    state
+        .taxes
+        .expect_resolve_at()
+        .returning(|id, _| Ok(Some(ResolvedTax::plain(tax(id)))));
+    state.sale_credit_notes.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::create_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/5/credit-notes")
+        .set_json(json!({ "date": "2025-07-10" }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVOICE_FULLY_CREDITED");
+}
+
+#[actix_web::test]
+async fn test_line_not_on_invoice() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(sale_invoice(id, SaleInvoiceStatus::Invoice))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|invoice_id| Ok(invoice_lines(invoice_id)));
+    state
+        .sale_invoice_lines
+        .expect_list_taxes_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state
+        .sale_credit_notes
+        .expect_list_lines_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state
+        .taxes
+        .expect_resolve_at()
+        .returning(|id, _| Ok(Some(ResolvedTax::plain(tax(id)))));
+    state.sale_credit_notes.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::create_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/5/credit-notes")
+        .set_json(json!({
+            "date": "2025-07-10",
+            "lines": [{ "invoiceLineId": 99, "quantity": 1.0 }],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+}
+
+#[actix_web::test]
+async fn test_invalid_quantity() {
+    let mut state = MockAppState::new();
+    state.sale_invoices.expect_get().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::create_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/5/credit-notes")
+        .set_json(json!({
+            "date": "2025-07-10",
+            "lines": [{ "invoiceLineId": 1, "quantity": 0.0 }],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+}
+
+#[actix_web::test]
+async fn test_invalid_date() {
+    let mut state = MockAppState::new();
+    state.sale_invoices.expect_get().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::create_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/5/credit-notes")
+        .set_json(json!({ "date": "10.07.2025" }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+}
+
+#[actix_web::test]
+async fn test_not_posted() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(sale_invoice(id, SaleInvoiceStatus::Proforma))));
+    state.sale_credit_notes.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::create_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/5/credit-notes")
+        .set_json(json!({ "date": "2025-07-10" }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+}
+
+#[actix_web::test]
+async fn test_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|_| Ok(None));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::create_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/5/credit-notes")
+        .set_json(json!({ "date": "2025-07-10" }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+}
+
+#[actix_web::test]
+async fn test_concurrently_credited() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(sale_invoice(id, SaleInvoiceStatus::Invoice))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|invoice_id| Ok(invoice_lines(invoice_id)));
+    state
+        .sale_invoice_lines
+        .expect_list_taxes_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state
+        .sale_credit_notes
+        .expect_list_lines_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state
+        .taxes
+        .expect_resolve_at()
+        .returning(|id, _| Ok(Some(ResolvedTax::plain(tax(id)))));
+    state
+        .sale_credit_notes
+        .expect_create()
+        .once()
+        .returning(|_, _, _, _| Err(DbErr::Custom(ERR_CREDIT_EXCEEDS_INVOICE.to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::create_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/5/credit-notes")
+        .set_json(json!({ "date": "2025-07-10" }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+}
+
+#[actix_web::test]
+async fn test_period_closed() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(sale_invoice(id, SaleInvoiceStatus::Invoice))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|invoice_id| Ok(invoice_lines(invoice_id)));
+    state
+        .sale_invoice_lines
+        .expect_list_taxes_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state
+        .sale_credit_notes
+        .expect_list_lines_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state
+        .taxes
+        .expect_resolve_at()
+        .returning(|id, _| Ok(Some(ResolvedTax::plain(tax(id)))));
+    state
+        .sale_credit_notes
+        .expect_create()
+        .once()
+        .returning(|_, _, _, _| Err(DbErr::Custom(ERR_PERIOD_CLOSED.to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::create_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/5/credit-notes")
+        .set_json(json!({ "date": "2024-12-31" }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "NUMBER_SEQUENCE_PERIOD_CLOSED");
+}
+
+#[actix_web::test]
+async fn test_connection_error() {
+    let mut state = MockAppState::new();
+    state.sale_invoices.expect_get().once().returning(|_| {
+        let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+        Err(err)
+    });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::create_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/5/credit-notes")
+        .set_json(json!({ "date": "2025-07-10" }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
+}
@@ -0,0 +1,188 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::SaleInvoiceStatus;
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr, prelude::Date};
+
+use crate::{
+    handlers,
+    repositories::ResolvedTax,
+    state::{AppState, MockAppState},
+};
+
+fn credit_note(id: i32, invoice_id: i32) -> entity::sale_credit_notes::Model {
+    entity::sale_credit_notes::Model {
+        id,
+        reference: format!("CN-2025-{id:05}"),
+        date: Date::from_ymd_opt(2025, 7, 10).unwrap(),
+        invoice_id,
+        reason: "".to_string(),
+    }
+}
+
+fn credited_line(
+    id: i32,
+    credit_note_id: i32,
+    quantity: f64,
+) -> entity::sale_credit_note_lines::Model {
+    entity::sale_credit_note_lines::Model {
+        id,
+        credit_note_id,
+        invoice_line_id: 1,
+        description: "Chair".to_string(),
+        quantity,
+        price: 4999,
+        tax: 19,
+        tax_id: Some(7),
+        variant_id: None,
+        transaction_id: None,
+        tax_transaction_id: None,
+        move_id: None,
+        discount_amount: 0,
+        discount_transaction_id: None,
+    }
+}
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|id| {
+            Ok(Some(entity::sale_invoices::Model {
+                id,
+                reference: "INV-2025-00001".to_string(),
+                date: Date::from_ymd_opt(2025, 7, 1).unwrap(),
+                status: SaleInvoiceStatus::Invoice,
+                order_id: 3,
+                customer_id: 4,
+                discount_percent: 0.0,
+                discount_amount: 0,
+            }))
+        });
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|invoice_id| {
+            Ok(vec![entity::sale_invoice_lines::Model {
+                id: 1,
+                description: "Chair".to_string(),
+                quantity: 3.0,
+                price: 4999,
+                tax: 19,
+                invoice_id,
+                variant_id: None,
+                transaction_id: None,
+                tax_id: Some(7),
+                tax_transaction_id: None,
+                discount_percent: 0.0,
+                discount_amount: 0,
+                discount_transaction_id: None,
+            }])
+        });
+    state
+        .sale_credit_notes
+        .expect_list_for_invoice()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|invoice_id| Ok(vec![credit_note(1, invoice_id), credit_note(2, invoice_id)]));
+    state
+        .sale_credit_notes
+        .expect_list_lines_for_invoice()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|_| Ok(vec![credited_line(1, 1, 1.0), credited_line(2, 2, 1.0)]));
+    state
+        .taxes
+        .expect_resolve_at()
+        .once()
+        // Credited at the rate of the invoice date.
+        .with(
+            predicate::eq(7),
+            predicate::eq(Date::from_ymd_opt(2025, 7, 1).unwrap()),
+        )
+        .returning(|id, _| {
+            Ok(Some(ResolvedTax::plain(entity::taxes::Model {
+                id,
+                name: "Sales tax".to_string(),
+                name_short: "19%".to_string(),
+                rate: 0.19,
+                account_id: 1,
+                category: "S".to_string(),
+                note: "".to_string(),
+            })))
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::list_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-invoices/5/credit-notes")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["invoiceTotal"], 17846);
+    assert_eq!(body["creditedTotal"], 11898);
+    assert_eq!(body["openAmount"], 5948);
+    assert_eq!(body["creditNotes"].as_array().unwrap().len(), 2);
+    assert_eq!(body["creditNotes"][1]["reference"], "CN-2025-00002");
+    assert_eq!(body["creditNotes"][1]["lines"][0]["id"], 2);
+}
+
+#[actix_web::test]
+async fn test_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|_| Ok(None));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::list_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-invoices/5/credit-notes")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+}
+
+#[actix_web::test]
+async fn test_connection_error() {
+    let mut state = MockAppState::new();
+    state.sale_invoices.expect_get().once().returning(|_| {
+        let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+        Err(err)
+    });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::credit_notes::list_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-invoices/5/credit-notes")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
+}
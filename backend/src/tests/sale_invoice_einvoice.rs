@@ -0,0 +1,623 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{SaleInvoiceStatus, SaleOrderStatus};
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr, prelude::Date};
+
+use crate::{
+    documents::{
+        Letterhead,
+        einvoice::{EInvoice, Syntax},
+    },
+    handlers,
+    repositories::{ResolvedTax, TaxComponent},
+    state::{AppState, MockAppState},
+    tests::fixtures::sale_invoice,
+};
+
+fn letterhead() -> Letterhead {
+    Letterhead {
+        name: "Liro GmbH".to_string(),
+        street1: "Werkstraße 5".to_string(),
+        postal_code: "20095".to_string(),
+        city: "Hamburg".to_string(),
+        country: "DE".to_string(),
+        vat_id: "DE123456789".to_string(),
+        email: "billing@liro.example".to_string(),
+        phone: "+49 40 123456".to_string(),
+        iban: "DE02120300000000202051".to_string(),
+        bic: "BYLADEM1001".to_string(),
+        ..Letterhead::default()
+    }
+}
+
+/// Expects the invoice with one line, its customer and its order to be loaded.
+fn expect_invoice(state: &mut MockAppState, status: SaleInvoiceStatus) {
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(move |id| Ok(Some(sale_invoice(id, status.clone()))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|invoice_id| {
+            Ok(vec![entity::sale_invoice_lines::Model {
+                id: 1,
+                description: "Chair".to_string(),
+                quantity: 2.0,
+                price: 4999,
+                tax: 19,
+                invoice_id,
+                variant_id: Some(1),
+                transaction_id: None,
+                tax_id: None,
+                tax_transaction_id: None,
+                discount_percent: 0.0,
+                discount_amount: 0,
+                discount_transaction_id: None,
+            }])
+        });
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .with(predicate::eq(4))
+        .returning(|id| {
+            Ok(Some(entity::contacts::Model {
+                id,
+                name: "Müller GmbH".to_string(),
+                is_customer: true,
+                is_supplier: false,
+                street1: "Hauptstraße 1".to_string(),
+                street2: "".to_string(),
+                postal_code: "10115".to_string(),
+                city: "Berlin".to_string(),
+                country: "DE".to_string(),
+                customer_account_id: Some(1),
+                supplier_account_id: None,
+                vat_id: "".to_string(),
+                currency: "EUR".to_string(),
+            }))
+        });
+}
+
+fn expect_order(state: &mut MockAppState) {
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .with(predicate::eq(3))
+        .returning(|id| {
+            Ok(Some(entity::sale_orders::Model {
+                id,
+                reference: "SO-2025-00001".to_string(),
+                date: Date::from_ymd_opt(2025, 6, 20).unwrap(),
+                status: SaleOrderStatus::Order,
+                customer_id: 4,
+                source_stock_id: 1,
+                target_stock_id: 2,
+                discount_percent: 0.0,
+                discount_amount: 0,
+            }))
+        });
+}
+
+#[actix_web::test]
+async fn test_xrechnung_success() {
+    let mut state = MockAppState::new();
+    state.letterhead = letterhead();
+    expect_invoice(&mut state, SaleInvoiceStatus::Invoice);
+    expect_order(&mut state);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_invoices::einvoice),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-invoices/5/einvoice?format=xrechnung")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+    assert_eq!(
+        res.headers().get("Content-Type").unwrap(),
+        "application/xml"
+    );
+
+    let body = test::read_body(res).await;
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert!(body.contains("urn:xeinkauf.de:kosit:xrechnung_3.0"));
+    assert!(body.contains("<cbc:BuyerReference>SO-2025-00001</cbc:BuyerReference>"));
+    assert!(body.contains("<cbc:TaxAmount currencyID=\"EUR\">19.00</cbc:TaxAmount>"));
+    assert!(body.contains("<cbc:PayableAmount currencyID=\"EUR\">118.98</cbc:PayableAmount>"));
+}
+
+#[actix_web::test]
+async fn test_cii_success() {
+    let mut state = MockAppState::new();
+    state.letterhead = letterhead();
+    expect_invoice(&mut state, SaleInvoiceStatus::Finished);
+    expect_order(&mut state);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_invoices::einvoice),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-invoices/5/einvoice?format=cii")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body = test::read_body(res).await;
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert!(body.contains("<ram:ID>urn:cen.eu:en16931:2017</ram:ID>"));
+    assert!(body.contains("<udt:DateTimeString format=\"102\">20250701</udt:DateTimeString>"));
+    assert!(body.contains("<ram:RateApplicablePercent>19</ram:RateApplicablePercent>"));
+    assert!(body.contains("<ram:GrandTotalAmount>118.98</ram:GrandTotalAmount>"));
+}
+
+#[actix_web::test]
+async fn test_factur_x_success() {
+    let mut state = MockAppState::new();
+    state.letterhead = letterhead();
+    expect_invoice(&mut state, SaleInvoiceStatus::Invoice);
+    expect_order(&mut state);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_invoices::einvoice),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-invoices/5/einvoice?format=facturx")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+    assert_eq!(
+        res.headers().get("Content-Type").unwrap(),
+        "application/pdf"
+    );
+
+    let body = test::read_body(res).await;
+    let doc = printpdf::lopdf::Document::load_mem(&body).unwrap();
+    let catalog = doc.catalog().unwrap();
+    assert!(catalog.has(b"AF"));
+    assert!(catalog.has(b"Names"));
+}
+
+#[actix_web::test]
+async fn test_not_posted() {
+    let mut state = MockAppState::new();
+    state.letterhead = letterhead();
+    expect_invoice(&mut state, SaleInvoiceStatus::Draft);
+    state.sale_orders.expect_get().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_invoices::einvoice),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-invoices/5/einvoice?format=xrechnung")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+}
+
+#[actix_web::test]
+async fn test_validation_failed() {
+    let mut state = MockAppState::new();
+    expect_invoice(&mut state, SaleInvoiceStatus::Invoice);
+    expect_order(&mut state);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_invoices::einvoice),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-invoices/5/einvoice?format=xrechnung")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 422u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    let rules: Vec<&str> = body["violations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|violation| violation["rule"].as_str().unwrap())
+        .collect();
+    assert!(rules.contains(&"BR-06"));
+    assert!(rules.contains(&"BR-S-02"));
+    assert!(rules.contains(&"BR-DE-1"));
+}
+
+#[actix_web::test]
+async fn test_invalid_format() {
+    let mut state = MockAppState::new();
+    state.sale_invoices.expect_get().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_invoices::einvoice),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-invoices/5/einvoice?format=edifact")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+}
+
+#[actix_web::test]
+async fn test_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|_| Ok(None));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_invoices::einvoice),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-invoices/5/einvoice?format=cii")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+}
+
+#[actix_web::test]
+async fn test_connection_error() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|_| {
+            let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+            Err(err)
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_invoices::einvoice),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-invoices/5/einvoice?format=cii")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
+}
+
+#[actix_web::test]
+async fn test_reverse_charge() {
+    let mut state = MockAppState::new();
+    state.letterhead = letterhead();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(sale_invoice(id, SaleInvoiceStatus::Invoice))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|invoice_id| {
+            Ok(vec![entity::sale_invoice_lines::Model {
+                id: 1,
+                description: "Chair".to_string(),
+                quantity: 2.0,
+                price: 4999,
+                tax: 0,
+                invoice_id,
+                variant_id: Some(1),
+                transaction_id: None,
+                tax_id: Some(9),
+                tax_transaction_id: None,
+                discount_percent: 0.0,
+                discount_amount: 0,
+                discount_transaction_id: None,
+            }])
+        });
+    state.contacts.expect_get().once().returning(|id| {
+        Ok(Some(entity::contacts::Model {
+            id,
+            name: "Huber KG".to_string(),
+            is_customer: true,
+            is_supplier: false,
+            street1: "Ringstraße 3".to_string(),
+            street2: "".to_string(),
+            postal_code: "1010".to_string(),
+            city: "Wien".to_string(),
+            country: "AT".to_string(),
+            customer_account_id: Some(1),
+            supplier_account_id: None,
+            vat_id: "ATU12345678".to_string(),
+            currency: "EUR".to_string(),
+        }))
+    });
+    state
+        .taxes
+        .expect_resolve_at()
+        .once()
+        .with(predicate::eq(9), predicate::always())
+        .returning(|id, _| {
+            Ok(Some(ResolvedTax::plain(entity::taxes::Model {
+                id,
+                name: "Reverse charge".to_string(),
+                name_short: "RC".to_string(),
+                rate: 0.0,
+                account_id: 1,
+                category: "AE".to_string(),
+                note: "Steuerschuldnerschaft des Leistungsempfängers".to_string(),
+            })))
+        });
+    expect_order(&mut state);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_invoices::einvoice),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-invoices/5/einvoice?format=xrechnung")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body = test::read_body(res).await;
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert!(body.contains("<cbc:ID>AE</cbc:ID>"));
+    assert!(body.contains(
+        "<cbc:TaxExemptionReason>Steuerschuldnerschaft des Leistungsempfängers</cbc:TaxExemptionReason>"
+    ));
+    assert!(body.contains("<cbc:CompanyID>ATU12345678</cbc:CompanyID>"));
+    assert!(body.contains("<cbc:PayableAmount currencyID=\"EUR\">99.98</cbc:PayableAmount>"));
+}
+
+#[actix_web::test]
+async fn test_tax_group() {
+    let mut state = MockAppState::new();
+    state.letterhead = letterhead();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(sale_invoice(id, SaleInvoiceStatus::Invoice))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|invoice_id| {
+            Ok(vec![entity::sale_invoice_lines::Model {
+                id: 1,
+                description: "Chair".to_string(),
+                quantity: 2.0,
+                price: 4999,
+                tax: 12,
+                invoice_id,
+                variant_id: Some(1),
+                transaction_id: None,
+                tax_id: Some(9),
+                tax_transaction_id: None,
+                discount_percent: 0.0,
+                discount_amount: 0,
+                discount_transaction_id: None,
+            }])
+        });
+    state.contacts.expect_get().once().returning(|id| {
+        Ok(Some(entity::contacts::Model {
+            id,
+            name: "Tremblay Inc.".to_string(),
+            is_customer: true,
+            is_supplier: false,
+            street1: "1 Rue Principale".to_string(),
+            street2: "".to_string(),
+            postal_code: "H2X 1Y4".to_string(),
+            city: "Montréal".to_string(),
+            country: "CA".to_string(),
+            customer_account_id: Some(1),
+            supplier_account_id: None,
+            vat_id: "".to_string(),
+            currency: "EUR".to_string(),
+        }))
+    });
+    state.taxes.expect_resolve_at().once().returning(|id, _| {
+        let component = |id: i32, name_short: &str, rate: f64| entity::taxes::Model {
+            id,
+            name: name_short.to_string(),
+            name_short: name_short.to_string(),
+            rate,
+            account_id: 1,
+            category: "S".to_string(),
+            note: "".to_string(),
+        };
+        let mut resolved = ResolvedTax::plain(component(id, "GST+PST", 0.0));
+        resolved.components = vec![
+            TaxComponent {
+                tax: component(21, "GST", 0.05),
+                compound: false,
+            },
+            TaxComponent {
+                tax: component(22, "PST", 0.07),
+                compound: true,
+            },
+        ];
+        Ok(Some(resolved))
+    });
+    state.sale_orders.expect_get().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_invoices::einvoice),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-invoices/5/einvoice?format=xrechnung")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 422u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "EINVOICE_TAX_GROUP");
+}
+
+#[actix_web::test]
+async fn test_discounts() {
+    let mut state = MockAppState::new();
+    state.letterhead = letterhead();
+    state.sale_invoices.expect_get().times(2).returning(|id| {
+        Ok(Some(entity::sale_invoices::Model {
+            discount_percent: 10.0,
+            ..sale_invoice(id, SaleInvoiceStatus::Invoice)
+        }))
+    });
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .times(2)
+        .returning(|invoice_id| {
+            Ok(vec![entity::sale_invoice_lines::Model {
+                id: 1,
+                description: "Chair".to_string(),
+                quantity: 2.0,
+                price: 4999,
+                tax: 19,
+                invoice_id,
+                variant_id: Some(1),
+                transaction_id: None,
+                tax_id: None,
+                tax_transaction_id: None,
+                discount_percent: 0.0,
+                discount_amount: 998,
+                discount_transaction_id: None,
+            }])
+        });
+    state.contacts.expect_get().times(2).returning(|id| {
+        Ok(Some(entity::contacts::Model {
+            id,
+            name: "Müller GmbH".to_string(),
+            is_customer: true,
+            is_supplier: false,
+            street1: "Hauptstraße 1".to_string(),
+            street2: "".to_string(),
+            postal_code: "10115".to_string(),
+            city: "Berlin".to_string(),
+            country: "DE".to_string(),
+            customer_account_id: Some(1),
+            supplier_account_id: None,
+            vat_id: "".to_string(),
+            currency: "EUR".to_string(),
+        }))
+    });
+    state.sale_orders.expect_get().times(2).returning(|id| {
+        Ok(Some(entity::sale_orders::Model {
+            id,
+            reference: "SO-2025-00001".to_string(),
+            date: Date::from_ymd_opt(2025, 6, 20).unwrap(),
+            status: SaleOrderStatus::Order,
+            customer_id: 4,
+            source_stock_id: 1,
+            target_stock_id: 2,
+            discount_percent: 0.0,
+            discount_amount: 0,
+        }))
+    });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_invoices::einvoice),
+    )
+    .await;
+
+    // The line discount is an allowance of the line, the invoice discount
+    // one of the document that reduces the taxable amount.
+    let req = test::TestRequest::get()
+        .uri("/api/sale-invoices/5/einvoice?format=xrechnung")
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body = test::read_body(res).await;
+    let xml = String::from_utf8(body.to_vec()).unwrap();
+    assert!(
+        xml.contains("<cbc:LineExtensionAmount currencyID=\"EUR\">90.00</cbc:LineExtensionAmount>")
+    );
+    assert!(xml.contains("<cbc:Amount currencyID=\"EUR\">9.98</cbc:Amount>"));
+    assert!(xml.contains("<cbc:TaxableAmount currencyID=\"EUR\">81.00</cbc:TaxableAmount>"));
+    assert!(
+        xml.contains("<cbc:TaxExclusiveAmount currencyID=\"EUR\">81.00</cbc:TaxExclusiveAmount>")
+    );
+    assert!(
+        xml.contains(
+            "<cbc:AllowanceTotalAmount currencyID=\"EUR\">9.00</cbc:AllowanceTotalAmount>"
+        )
+    );
+    assert!(xml.contains("<cbc:PayableAmount currencyID=\"EUR\">96.39</cbc:PayableAmount>"));
+
+    let parsed = EInvoice::parse(&body).unwrap();
+    assert_eq!(parsed.lines[0].allowance, 998);
+    assert_eq!(parsed.allowance_total, 900);
+    assert!(parsed.validate(Syntax::XRechnung).is_empty());
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-invoices/5/einvoice?format=cii")
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body = test::read_body(res).await;
+    let xml = String::from_utf8(body.to_vec()).unwrap();
+    assert!(xml.contains("<ram:AllowanceTotalAmount>9.00</ram:AllowanceTotalAmount>"));
+    assert!(xml.contains("<ram:TaxBasisTotalAmount>81.00</ram:TaxBasisTotalAmount>"));
+    assert!(xml.contains("<ram:GrandTotalAmount>96.39</ram:GrandTotalAmount>"));
+
+    let parsed = EInvoice::parse(&body).unwrap();
+    assert_eq!(parsed.lines[0].allowance, 998);
+    assert_eq!(parsed.allowances[0].amount, 900);
+    assert!(parsed.validate(Syntax::FacturX).is_empty());
+}
@@ -0,0 +1,197 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::SaleInvoiceStatus;
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr, prelude::Date};
+
+use crate::{
+    handlers,
+    repositories::ResolvedTax,
+    state::{AppState, MockAppState},
+    tests::fixtures::{customer, sale_invoice},
+};
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|id| Ok(Some(sale_invoice(id, SaleInvoiceStatus::Invoice))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|invoice_id| {
+            Ok(vec![
+                entity::sale_invoice_lines::Model {
+                    id: 1,
+                    description: "Chair".to_string(),
+                    quantity: 2.0,
+                    price: 4999,
+                    tax: 19,
+                    invoice_id,
+                    variant_id: Some(1),
+                    transaction_id: None,
+                    tax_id: Some(7),
+                    tax_transaction_id: None,
+                    discount_percent: 0.0,
+                    discount_amount: 0,
+                    discount_transaction_id: None,
+                },
+                entity::sale_invoice_lines::Model {
+                    id: 2,
+                    description: "Book".to_string(),
+                    quantity: 1.0,
+                    price: 1500,
+                    tax: 7,
+                    invoice_id,
+                    variant_id: None,
+                    transaction_id: None,
+                    tax_id: None,
+                    tax_transaction_id: None,
+                    discount_percent: 0.0,
+                    discount_amount: 0,
+                    discount_transaction_id: None,
+                },
+            ])
+        });
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .with(predicate::eq(4))
+        .returning(|id| Ok(Some(customer(id))));
+    state
+        .taxes
+        .expect_resolve_at()
+        .once()
+        .with(
+            predicate::eq(7),
+            predicate::eq(Date::from_ymd_opt(2025, 7, 1).unwrap()),
+        )
+        .returning(|id, _| {
+            Ok(Some(ResolvedTax::plain(entity::taxes::Model {
+                id,
+                name: "Sales tax".to_string(),
+                name_short: "19%".to_string(),
+                rate: 0.19,
+                account_id: 1,
+                category: "S".to_string(),
+                note: "".to_string(),
+            })))
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_invoices::pdf),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-invoices/5/pdf")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+    assert_eq!(
+        res.headers().get("Content-Type").unwrap(),
+        "application/pdf"
+    );
+
+    let body = test::read_body(res).await;
+    assert!(body.starts_with(b"%PDF"));
+}
+
+#[actix_web::test]
+async fn test_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|_| Ok(None));
+    state.sale_invoice_lines.expect_list_for_invoice().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_invoices::pdf),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-invoices/5/pdf")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+}
+
+#[actix_web::test]
+async fn test_customer_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|id| Ok(Some(sale_invoice(id, SaleInvoiceStatus::Invoice))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .with(predicate::eq(4))
+        .returning(|_| Ok(None));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_invoices::pdf),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-invoices/5/pdf")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+}
+
+#[actix_web::test]
+async fn test_connection_error() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|_| {
+            let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+            Err(err)
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_invoices::pdf),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-invoices/5/pdf")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
+}
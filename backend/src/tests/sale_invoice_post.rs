@@ -0,0 +1,664 @@
+use std::collections::HashMap;
+
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{
+    CostingMethod, ProductType, SaleInvoiceStatus, TaxDirection, TaxRegion, Tracking,
+};
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr, prelude::Date};
+
+use crate::{
+    documents::Letterhead,
+    handlers,
+    repositories::{ERR_SALE_INVOICE_POSTED, LinePostingDraft, PostingDraft, ResolvedTax},
+    state::{AppState, MockAppState},
+    tests::fixtures::{self, sale_invoice, sale_invoice_line},
+    utils::product_categories::CategoryDefaults,
+};
+
+fn line(
+    id: i32,
+    quantity: f64,
+    price: i32,
+    variant_id: Option<i32>,
+    tax_id: Option<i32>,
+) -> entity::sale_invoice_lines::Model {
+    entity::sale_invoice_lines::Model {
+        quantity,
+        price,
+        tax: if tax_id.is_some() { 19 } else { 0 },
+        variant_id,
+        tax_id,
+        ..sale_invoice_line(id, 1)
+    }
+}
+
+fn customer(customer_account_id: Option<i32>) -> entity::contacts::Model {
+    entity::contacts::Model {
+        customer_account_id,
+        ..fixtures::customer(4)
+    }
+}
+
+fn tax(id: i32) -> entity::taxes::Model {
+    entity::taxes::Model {
+        account_id: 3806,
+        ..fixtures::tax(id)
+    }
+}
+
+fn income(income_account_id: i32, tax_id: Option<i32>) -> CategoryDefaults {
+    CategoryDefaults {
+        income_account_id: Some(income_account_id),
+        tax_id,
+        ..Default::default()
+    }
+}
+
+fn posting(amount: i32, credit_account_id: i32) -> PostingDraft {
+    PostingDraft {
+        amount,
+        debit_account_id: 1400,
+        credit_account_id,
+    }
+}
+
+/// Mocks the tax rules for the one line without a tax. The mug is a
+/// product of the standard tax category.
+fn mock_tax_rules(state: &mut MockAppState, rules: Vec<entity::tax_rules::Model>) {
+    state.letterhead = Letterhead {
+        country: "DE".to_string(),
+        ..Letterhead::default()
+    };
+    state.variants.expect_get().once().returning(|id| {
+        Ok(Some(entity::variants::Model {
+            id,
+            name: "Mug".to_string(),
+            sale_price: 1000,
+            purchase_price: 500,
+            product_id: 5,
+            sku: None,
+            gtin: None,
+            unit_id: None,
+        }))
+    });
+    state.products.expect_get().once().returning(|id| {
+        Ok(Some(entity::products::Model {
+            id,
+            name: "Mug".to_string(),
+            tax_category: "standard".to_string(),
+            category_id: Some(2),
+            product_type: ProductType::Consumable,
+            costing_method: CostingMethod::Fifo,
+            tracking: Tracking::None,
+        }))
+    });
+    state
+        .tax_rules
+        .expect_list_for_region()
+        .once()
+        .with(
+            predicate::eq(TaxDirection::Sale),
+            predicate::eq(TaxRegion::Domestic),
+        )
+        .returning(move |_, _| Ok(rules.clone()));
+}
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(Some(sale_invoice(1, SaleInvoiceStatus::Draft))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .with(predicate::eq(1))
+        .returning(|_| {
+            Ok(vec![
+                line(1, 2.0, 4999, Some(8), Some(7)),
+                line(2, 1.0, 1500, Some(9), None),
+            ])
+        });
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .with(predicate::eq(4))
+        .returning(|_| Ok(Some(customer(Some(1400)))));
+    state
+        .product_categories
+        .expect_defaults_for_variants()
+        .once()
+        .with(predicate::eq(vec![8, 9]))
+        .returning(|_| {
+            // The mug's category sets no tax, the second line's passes on
+            // the one of its parent.
+            Ok(HashMap::from([
+                (8, income(8400, None)),
+                (9, income(8401, Some(7))),
+            ]))
+        });
+    mock_tax_rules(&mut state, vec![]);
+    state
+        .taxes
+        .expect_resolve_at()
+        .once()
+        .with(
+            predicate::eq(7),
+            predicate::eq(Date::from_ymd_opt(2025, 7, 1).unwrap()),
+        )
+        .returning(|id, _| Ok(Some(ResolvedTax::plain(tax(id)))));
+    state
+        .sale_invoices
+        .expect_post()
+        .once()
+        .with(
+            predicate::eq(sale_invoice(1, SaleInvoiceStatus::Draft)),
+            predicate::eq(vec![
+                LinePostingDraft {
+                    line_id: 1,
+                    tax_id: Some(7),
+                    net: posting(9998, 8400),
+                    discount: None,
+                    tax: Some(posting(1900, 3806)),
+                    tax_postings: vec![],
+                },
+                LinePostingDraft {
+                    line_id: 2,
+                    tax_id: Some(7),
+                    net: posting(1500, 8401),
+                    discount: None,
+                    tax: Some(posting(285, 3806)),
+                    tax_postings: vec![],
+                },
+            ]),
+        )
+        .returning(|invoice, _| {
+            Ok(entity::sale_invoices::Model {
+                status: SaleInvoiceStatus::Invoice,
+                ..invoice
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::invoice_postings::post_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/1/post")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["status"], "invoice");
+}
+
+#[actix_web::test]
+async fn test_skips_posted_lines() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(sale_invoice(1, SaleInvoiceStatus::Invoice))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|_| {
+            Ok(vec![
+                entity::sale_invoice_lines::Model {
+                    transaction_id: Some(11),
+                    ..line(1, 1.0, 1000, Some(8), None)
+                },
+                line(2, 1.0, 500, Some(8), None),
+            ])
+        });
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(customer(Some(1400)))));
+    state
+        .product_categories
+        .expect_defaults_for_variants()
+        .once()
+        .with(predicate::eq(vec![8]))
+        .returning(|_| Ok(HashMap::from([(8, income(8400, None))])));
+    mock_tax_rules(&mut state, vec![]);
+    state.taxes.expect_resolve_at().never();
+    state
+        .sale_invoices
+        .expect_post()
+        .once()
+        .with(
+            predicate::always(),
+            predicate::eq(vec![LinePostingDraft {
+                line_id: 2,
+                tax_id: None,
+                net: posting(500, 8400),
+                discount: None,
+                tax: None,
+                tax_postings: vec![],
+            }]),
+        )
+        .returning(|invoice, _| Ok(invoice));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::invoice_postings::post_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/1/post")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+}
+
+#[actix_web::test]
+async fn test_discount_account() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(sale_invoice(1, SaleInvoiceStatus::Draft))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|_| {
+            Ok(vec![entity::sale_invoice_lines::Model {
+                discount_percent: 10.0,
+                ..line(1, 2.0, 5000, Some(8), Some(7))
+            }])
+        });
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(customer(Some(1400)))));
+    state
+        .product_categories
+        .expect_defaults_for_variants()
+        .once()
+        .returning(|_| {
+            Ok(HashMap::from([(
+                8,
+                CategoryDefaults {
+                    sale_discount_account_id: Some(8730),
+                    ..income(8400, None)
+                },
+            )]))
+        });
+    state
+        .taxes
+        .expect_resolve_at()
+        .once()
+        .returning(|id, _| Ok(Some(ResolvedTax::plain(tax(id)))));
+    // The revenue is credited undiscounted, the discount of 1000 is debited
+    // to the discount account. The tax is levied on the discounted net.
+    state
+        .sale_invoices
+        .expect_post()
+        .once()
+        .with(
+            predicate::always(),
+            predicate::eq(vec![LinePostingDraft {
+                line_id: 1,
+                tax_id: Some(7),
+                net: posting(10000, 8400),
+                discount: Some(PostingDraft {
+                    amount: 1000,
+                    debit_account_id: 8730,
+                    credit_account_id: 1400,
+                }),
+                tax: Some(posting(1710, 3806)),
+                tax_postings: vec![],
+            }]),
+        )
+        .returning(|invoice, _| Ok(invoice));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::invoice_postings::post_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/1/post")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+}
+
+#[actix_web::test]
+async fn test_already_posted() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(sale_invoice(1, SaleInvoiceStatus::Finished))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|_| {
+            Ok(vec![entity::sale_invoice_lines::Model {
+                transaction_id: Some(11),
+                ..line(1, 1.0, 1000, Some(8), None)
+            }])
+        });
+    state.contacts.expect_get().never();
+    state.sale_invoices.expect_post().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::invoice_postings::post_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/1/post")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVOICE_ALREADY_POSTED");
+}
+
+#[actix_web::test]
+async fn test_posting_account_missing() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(sale_invoice(1, SaleInvoiceStatus::Draft))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![line(1, 1.0, 1000, Some(8), None)]));
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(customer(Some(1400)))));
+    // The product has no category.
+    state
+        .product_categories
+        .expect_defaults_for_variants()
+        .once()
+        .returning(|_| Ok(HashMap::new()));
+    mock_tax_rules(&mut state, vec![]);
+    state.sale_invoices.expect_post().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::invoice_postings::post_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/1/post")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "POSTING_ACCOUNT_MISSING");
+}
+
+#[actix_web::test]
+async fn test_contact_account_missing() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(sale_invoice(1, SaleInvoiceStatus::Draft))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![line(1, 1.0, 1000, Some(8), None)]));
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(customer(None))));
+    state.sale_invoices.expect_post().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::invoice_postings::post_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/1/post")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "CONTACT_ACCOUNT_MISSING");
+}
+
+#[actix_web::test]
+async fn test_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|_| Ok(None));
+    state.sale_invoices.expect_post().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::invoice_postings::post_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/1/post")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+}
+
+#[actix_web::test]
+async fn test_connection_error() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(sale_invoice(1, SaleInvoiceStatus::Draft))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![line(1, 1.0, 1000, Some(8), None)]));
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(customer(Some(1400)))));
+    state
+        .product_categories
+        .expect_defaults_for_variants()
+        .once()
+        .returning(|_| Ok(HashMap::from([(8, income(8400, None))])));
+    mock_tax_rules(&mut state, vec![]);
+    state.sale_invoices.expect_post().once().returning(|_, _| {
+        let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+        Err(err)
+    });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::invoice_postings::post_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/1/post")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
+}
+
+#[actix_web::test]
+async fn test_posted_meanwhile() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(sale_invoice(1, SaleInvoiceStatus::Draft))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![line(1, 1.0, 1000, Some(8), None)]));
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(customer(Some(1400)))));
+    state
+        .product_categories
+        .expect_defaults_for_variants()
+        .once()
+        .returning(|_| Ok(HashMap::from([(8, income(8400, None))])));
+    mock_tax_rules(&mut state, vec![]);
+    state
+        .sale_invoices
+        .expect_post()
+        .once()
+        .returning(|_, _| Err(DbErr::Custom(ERR_SALE_INVOICE_POSTED.to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::invoice_postings::post_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/1/post")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVOICE_ALREADY_POSTED");
+}
+
+#[actix_web::test]
+async fn test_tax_rule() {
+    let mut state = MockAppState::new();
+    state
+        .sale_invoices
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(sale_invoice(1, SaleInvoiceStatus::Draft))));
+    state
+        .sale_invoice_lines
+        .expect_list_for_invoice()
+        .once()
+        .returning(|_| Ok(vec![line(1, 1.0, 1000, Some(8), None)]));
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(customer(Some(1400)))));
+    state
+        .product_categories
+        .expect_defaults_for_variants()
+        .once()
+        .returning(|_| Ok(HashMap::from([(8, income(8400, Some(7)))])));
+    // The rule for standard products comes before the category's tax.
+    mock_tax_rules(
+        &mut state,
+        vec![entity::tax_rules::Model {
+            id: 1,
+            direction: TaxDirection::Sale,
+            region: TaxRegion::Domestic,
+            vat_id: None,
+            product_tax_category: Some("standard".to_string()),
+            tax_id: 9,
+        }],
+    );
+    state
+        .taxes
+        .expect_resolve_at()
+        .once()
+        .with(
+            predicate::eq(9),
+            predicate::eq(Date::from_ymd_opt(2025, 7, 1).unwrap()),
+        )
+        .returning(|id, _| Ok(Some(ResolvedTax::plain(tax(id)))));
+    state
+        .sale_invoices
+        .expect_post()
+        .once()
+        .with(
+            predicate::always(),
+            predicate::eq(vec![LinePostingDraft {
+                line_id: 1,
+                tax_id: Some(9),
+                net: posting(1000, 8400),
+                discount: None,
+                tax: Some(posting(190, 3806)),
+                tax_postings: vec![],
+            }]),
+        )
+        .returning(|invoice, _| Ok(invoice));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::invoice_postings::post_sale),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-invoices/1/post")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+}
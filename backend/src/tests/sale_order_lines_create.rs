@@ -1,6 +1,829 @@
+use std::collections::HashMap;
+
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{
+    CostingMethod, ProductType, SaleOrderLineRoute, SaleOrderStatus, TaxDirection, TaxRegion,
+    Tracking,
+};
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr, prelude::Date};
+use serde_json::json;
+
+use crate::{
+    documents::Letterhead,
+    handlers,
+    repositories::SaleOrderLineDraft,
+    state::{AppState, MockAppState},
+    tests::fixtures::{customer, oak_chair, sale_order},
+    utils::totals::Discount,
+};
+
+fn order(id: i32) -> entity::sale_orders::Model {
+    entity::sale_orders::Model {
+        reference: "SO-2026-00001".to_string(),
+        date: Date::from_ymd_opt(2026, 7, 1).unwrap(),
+        source_stock_id: 1,
+        target_stock_id: 2,
+        ..sale_order(id, SaleOrderStatus::Draft)
+    }
+}
+
+/// A customer in Switzerland, who pays in francs.
+fn swiss_customer(id: i32) -> entity::contacts::Model {
+    entity::contacts::Model {
+        country: "CH".to_string(),
+        currency: "CHF".to_string(),
+        ..customer(id)
+    }
+}
+
+fn price(
+    list_id: i32,
+    currency: &str,
+    min_quantity: f64,
+    price: i32,
+) -> (entity::price_lists::Model, entity::price_list_items::Model) {
+    let list = entity::price_lists::Model {
+        id: list_id,
+        name: "Wholesale".to_string(),
+        currency: currency.to_string(),
+        valid_from: Some(Date::from_ymd_opt(2026, 1, 1).unwrap()),
+        valid_to: None,
+    };
+    let item = entity::price_list_items::Model {
+        id: list_id * 10,
+        price_list_id: list_id,
+        variant_id: 7,
+        min_quantity,
+        price,
+    };
+    (list, item)
+}
+
+fn chair(quantity: f64, price: i32) -> SaleOrderLineDraft {
+    SaleOrderLineDraft {
+        description: "Chair, oak".to_string(),
+        quantity,
+        price,
+        tax: 19,
+        tax_id: None,
+        variant_id: Some(7),
+        discount: Discount::default(),
+        unit_id: None,
+        route: SaleOrderLineRoute::Stock,
+    }
+}
+
+fn created(order_id: i32, line: SaleOrderLineDraft) -> entity::sale_order_lines::Model {
+    entity::sale_order_lines::Model {
+        id: 1,
+        description: line.description,
+        quantity: line.quantity,
+        price: line.price,
+        tax: line.tax,
+        tax_id: line.tax_id,
+        order_id,
+        variant_id: line.variant_id,
+        move_id: None,
+        discount_percent: line.discount.percent,
+        discount_amount: line.discount.amount as i32,
+        unit_id: line.unit_id,
+        route: line.route,
+    }
+}
+
 #[actix_web::test]
 async fn test_success() {
-    todo!();
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|id| Ok(Some(order(id))));
+    state.variants.expect_get().never();
+    state.price_lists.expect_list_prices_for_customer().never();
+    state
+        .sale_order_lines
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq(5),
+            predicate::eq(SaleOrderLineDraft {
+                description: "Delivery".to_string(),
+                quantity: 1.0,
+                price: 1500,
+                tax: 19,
+                tax_id: None,
+                variant_id: None,
+                discount: Discount::new(10.0, 0),
+                unit_id: None,
+                route: SaleOrderLineRoute::Stock,
+            }),
+        )
+        .returning(|order_id, line| Ok(created(order_id, line)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_order_lines::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-order-lines")
+        .set_json(json!({
+            "description": "Delivery",
+            "quantity": 1.0,
+            "price": 1500,
+            "tax": 19,
+            "orderId": 5,
+            "discountPercent": 10.0,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["id"], 1);
+    assert_eq!(body["orderId"], 5);
+    assert_eq!(body["price"], 1500);
+    assert_eq!(body["discountPercent"], 10.0);
+}
+
+#[actix_web::test]
+async fn test_price_from_price_list() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(order(id))));
+    state
+        .variants
+        .expect_get()
+        .once()
+        .with(predicate::eq(7))
+        .returning(|id| Ok(Some(oak_chair(id))));
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .with(predicate::eq(4))
+        .returning(|id| Ok(Some(swiss_customer(id))));
+    state
+        .price_lists
+        .expect_list_prices_for_customer()
+        .once()
+        .with(predicate::eq(4), predicate::eq(7))
+        .returning(|_, _| {
+            Ok(vec![
+                price(1, "EUR", 0.0, 4200),
+                price(2, "CHF", 0.0, 5200),
+                price(2, "CHF", 10.0, 4900),
+            ])
+        });
+    // The customer is quoted in francs and orders 12 chairs.
+    state
+        .sale_order_lines
+        .expect_create()
+        .once()
+        .with(predicate::eq(5), predicate::eq(chair(12.0, 4900)))
+        .returning(|order_id, line| Ok(created(order_id, line)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_order_lines::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-order-lines")
+        .set_json(json!({
+            "quantity": 12.0,
+            "tax": 19,
+            "orderId": 5,
+            "variantId": 7,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["description"], "Chair, oak");
+    assert_eq!(body["price"], 4900);
+    assert_eq!(body["variantId"], 7);
+}
+
+#[actix_web::test]
+async fn test_price_from_variant() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(order(id))));
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(oak_chair(id))));
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(swiss_customer(id))));
+    state
+        .price_lists
+        .expect_list_prices_for_customer()
+        .once()
+        .returning(|_, _| Ok(vec![price(1, "EUR", 0.0, 4200)]));
+    state
+        .sale_order_lines
+        .expect_create()
+        .once()
+        .with(predicate::eq(5), predicate::eq(chair(2.0, 4999)))
+        .returning(|order_id, line| Ok(created(order_id, line)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_order_lines::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-order-lines")
+        .set_json(json!({
+            "quantity": 2.0,
+            "tax": 19,
+            "orderId": 5,
+            "variantId": 7,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["price"], 4999);
+}
+
+#[actix_web::test]
+async fn test_price_in_line_unit() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(order(id))));
+    state.variants.expect_get().once().returning(|id| {
+        Ok(Some(entity::variants::Model {
+            unit_id: Some(1),
+            ..oak_chair(id)
+        }))
+    });
+    state.units.expect_get().times(2).returning(|id| {
+        Ok(Some(entity::units::Model {
+            id,
+            name: format!("Unit {id}"),
+            symbol: "u".to_string(),
+            base_unit_id: (id == 2).then_some(1),
+            factor: if id == 2 { 12.0 } else { 1.0 },
+        }))
+    });
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(swiss_customer(id))));
+    // One box reaches the break for 10 pieces.
+    state
+        .price_lists
+        .expect_list_prices_for_customer()
+        .once()
+        .returning(|_, _| {
+            Ok(vec![
+                price(2, "CHF", 0.0, 5200),
+                price(2, "CHF", 10.0, 4900),
+            ])
+        });
+    state
+        .sale_order_lines
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq(5),
+            predicate::eq(SaleOrderLineDraft {
+                unit_id: Some(2),
+                ..chair(1.0, 58800)
+            }),
+        )
+        .returning(|order_id, line| Ok(created(order_id, line)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_order_lines::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-order-lines")
+        .set_json(json!({
+            "quantity": 1.0,
+            "tax": 19,
+            "orderId": 5,
+            "variantId": 7,
+            "unitId": 2,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["price"], 58800);
+    assert_eq!(body["unitId"], 2);
+}
+
+#[actix_web::test]
+async fn test_unit_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(order(id))));
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(oak_chair(id))));
+    state.units.expect_get().once().returning(|_| Ok(None));
+    state.sale_order_lines.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_order_lines::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-order-lines")
+        .set_json(json!({
+            "quantity": 1.0,
+            "tax": 19,
+            "orderId": 5,
+            "variantId": 7,
+            "unitId": 2,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "UNIT_NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn test_missing_price() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(order(id))));
+    state.sale_order_lines.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_order_lines::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-order-lines")
+        .set_json(json!({
+            "description": "Delivery",
+            "quantity": 1.0,
+            "tax": 19,
+            "orderId": 5,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "MISSING_PRICE");
+}
+
+/// Mocks the determination of the chair's tax for the Swiss customer.
+fn mock_tax_rules(state: &mut MockAppState, rules: Vec<entity::tax_rules::Model>) {
+    state.letterhead = Letterhead {
+        country: "DE".to_string(),
+        ..Letterhead::default()
+    };
+    state.products.expect_get().once().returning(|id| {
+        Ok(Some(entity::products::Model {
+            id,
+            name: "Chair".to_string(),
+            tax_category: "standard".to_string(),
+            category_id: Some(2),
+            product_type: ProductType::Stockable,
+            costing_method: CostingMethod::Fifo,
+            tracking: Tracking::None,
+        }))
+    });
+    state
+        .product_categories
+        .expect_defaults_for_variants()
+        .once()
+        .returning(|_| Ok(HashMap::new()));
+    state
+        .tax_rules
+        .expect_list_for_region()
+        .once()
+        .with(
+            predicate::eq(TaxDirection::Sale),
+            predicate::eq(TaxRegion::NonEu),
+        )
+        .returning(move |_, _| Ok(rules.clone()));
+}
+
+#[actix_web::test]
+async fn test_tax_from_rules() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(order(id))));
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(oak_chair(id))));
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(swiss_customer(id))));
+    mock_tax_rules(
+        &mut state,
+        vec![entity::tax_rules::Model {
+            id: 1,
+            direction: TaxDirection::Sale,
+            region: TaxRegion::NonEu,
+            vat_id: None,
+            product_tax_category: None,
+            tax_id: 12,
+        }],
+    );
+    state
+        .taxes
+        .expect_get_at()
+        .once()
+        .with(
+            predicate::eq(12),
+            predicate::eq(Date::from_ymd_opt(2026, 7, 1).unwrap()),
+        )
+        .returning(|id, _| {
+            Ok(Some(entity::taxes::Model {
+                id,
+                name: "Export".to_string(),
+                name_short: "0%".to_string(),
+                rate: 0.0,
+                account_id: 1,
+                category: "G".to_string(),
+                note: "Tax-free export".to_string(),
+            }))
+        });
+    state.price_lists.expect_list_prices_for_customer().never();
+    state
+        .sale_order_lines
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq(5),
+            predicate::eq(SaleOrderLineDraft {
+                tax: 0,
+                tax_id: Some(12),
+                ..chair(1.0, 4500)
+            }),
+        )
+        .returning(|order_id, line| Ok(created(order_id, line)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_order_lines::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-order-lines")
+        .set_json(json!({
+            "quantity": 1.0,
+            "price": 4500,
+            "orderId": 5,
+            "variantId": 7,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["tax"], 0);
+    assert_eq!(body["taxId"], 12);
+}
+
+#[actix_web::test]
+async fn test_no_matching_tax_rule() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(order(id))));
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(oak_chair(id))));
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(swiss_customer(id))));
+    mock_tax_rules(&mut state, vec![]);
+    state.sale_order_lines.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_order_lines::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-order-lines")
+        .set_json(json!({
+            "quantity": 1.0,
+            "price": 4500,
+            "orderId": 5,
+            "variantId": 7,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 422u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "NO_MATCHING_TAX_RULE");
+}
+
+#[actix_web::test]
+async fn test_missing_tax() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(order(id))));
+    state.sale_order_lines.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_order_lines::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-order-lines")
+        .set_json(json!({
+            "description": "Delivery",
+            "quantity": 1.0,
+            "price": 1500,
+            "orderId": 5,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "MISSING_TAX");
+}
+
+#[actix_web::test]
+async fn test_tax_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(order(id))));
+    state
+        .taxes
+        .expect_get()
+        .once()
+        .with(predicate::eq(9))
+        .returning(|_| Ok(None));
+    state.sale_order_lines.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_order_lines::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-order-lines")
+        .set_json(json!({
+            "description": "Delivery",
+            "quantity": 1.0,
+            "price": 1500,
+            "tax": 19,
+            "taxId": 9,
+            "orderId": 5,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "TAX_NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn test_drop_ship() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(order(id))));
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(oak_chair(id))));
+    state
+        .variants
+        .expect_stockable_ids()
+        .once()
+        .with(predicate::eq(vec![7]))
+        .returning(Ok);
+    state
+        .sale_order_lines
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq(5),
+            predicate::eq(SaleOrderLineDraft {
+                route: SaleOrderLineRoute::DropShip,
+                ..chair(1.0, 4999)
+            }),
+        )
+        .returning(|order_id, line| Ok(created(order_id, line)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_order_lines::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-order-lines")
+        .set_json(json!({
+            "quantity": 1.0,
+            "price": 4999,
+            "tax": 19,
+            "orderId": 5,
+            "variantId": 7,
+            "route": "drop_ship",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["route"], "drop_ship");
+}
+
+#[actix_web::test]
+async fn test_drop_ship_without_variant() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(order(id))));
+    state.sale_order_lines.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_order_lines::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-order-lines")
+        .set_json(json!({
+            "description": "Delivery",
+            "quantity": 1.0,
+            "price": 1500,
+            "tax": 19,
+            "orderId": 5,
+            "route": "drop_ship",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "DROP_SHIP_VARIANT_MISSING");
+}
+
+#[actix_web::test]
+async fn test_invalid_route() {
+    let mut state = MockAppState::new();
+    state.sale_orders.expect_get().never();
+    state.sale_order_lines.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_order_lines::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-order-lines")
+        .set_json(json!({
+            "quantity": 1.0,
+            "price": 1500,
+            "tax": 19,
+            "orderId": 5,
+            "route": "courier",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVALID_ROUTE");
+}
+
+#[actix_web::test]
+async fn test_order_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(None));
+    state.sale_order_lines.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_order_lines::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-order-lines")
+        .set_json(json!({
+            "quantity": 1.0,
+            "tax": 19,
+            "orderId": 5,
+            "variantId": 7,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "SALE_ORDER_NOT_FOUND");
 }
 
 #[actix_web::test]
@@ -40,5 +863,31 @@ async fn test_supplier_invalid_move_id() {
 
 #[actix_web::test]
 async fn test_connection_error() {
-    todo!();
+    let mut state = MockAppState::new();
+    state.sale_orders.expect_get().once().returning(|_| {
+        let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+        Err(err)
+    });
+    state.sale_order_lines.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_order_lines::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-order-lines")
+        .set_json(json!({
+            "description": "Delivery",
+            "quantity": 1.0,
+            "price": 1500,
+            "tax": 19,
+            "orderId": 5,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
 }
@@ -0,0 +1,110 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::SaleOrderStatus;
+use mockall::predicate;
+use sea_orm::DbErr;
+
+use crate::{
+    handlers,
+    repositories::ERR_SALE_ORDER_DROP_SHIPPED,
+    state::{AppState, MockAppState},
+    tests::fixtures::sale_order,
+};
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|_| Ok(Some(sale_order(5, SaleOrderStatus::Order))));
+    state
+        .sale_orders
+        .expect_cancel()
+        .once()
+        .with(predicate::eq(sale_order(5, SaleOrderStatus::Order)))
+        .returning(|order| {
+            Ok(entity::sale_orders::Model {
+                status: SaleOrderStatus::Cancelled,
+                ..order
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::cancel),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-orders/5/cancel")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["status"], "cancelled");
+}
+
+#[actix_web::test]
+async fn test_already_delivered() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(sale_order(5, SaleOrderStatus::Finished))));
+    state.sale_orders.expect_cancel().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::cancel),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-orders/5/cancel")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "SALE_ORDER_ALREADY_DELIVERED");
+}
+
+#[actix_web::test]
+async fn test_drop_shipped() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(sale_order(5, SaleOrderStatus::Order))));
+    state
+        .sale_orders
+        .expect_cancel()
+        .once()
+        .returning(|_| Err(DbErr::Custom(ERR_SALE_ORDER_DROP_SHIPPED.to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::cancel),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-orders/5/cancel")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "SALE_ORDER_DROP_SHIPPED");
+}
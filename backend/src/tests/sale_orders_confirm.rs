@@ -0,0 +1,467 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{
+    PurchaseOrderStatus, SaleOrderLineRoute, SaleOrderStatus, StockKind,
+};
+use mockall::predicate;
+use sea_orm::DbErr;
+
+use crate::{
+    handlers,
+    repositories::{
+        DropShipDraft, DropShipLineDraft, ERR_INSUFFICIENT_STOCK, ERR_SALE_ORDER_NOT_DRAFT,
+        PurchaseOrderLineDraft, ReservationDraft, SaleOrderConfirmation,
+    },
+    state::{AppState, MockAppState},
+    tests::fixtures::{sale_order, sale_order_line, stock, variant},
+    utils::{availability::Shortage, totals::Discount},
+};
+
+fn line(id: i32, quantity: f64, variant_id: Option<i32>) -> entity::sale_order_lines::Model {
+    entity::sale_order_lines::Model {
+        quantity,
+        variant_id,
+        ..sale_order_line(id, 5)
+    }
+}
+
+/// Two lines of the same mug and one without a variant.
+fn mock_lines(state: &mut MockAppState) {
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|_| Ok(Some(sale_order(5, SaleOrderStatus::Quotation))));
+    state
+        .sale_order_lines
+        .expect_list_for_order()
+        .once()
+        .returning(|_| {
+            Ok(vec![
+                line(1, 2.0, Some(8)),
+                line(2, 3.0, Some(8)),
+                line(3, 1.0, None),
+            ])
+        });
+    state.variants.expect_stockable_ids().once().returning(Ok);
+    state
+        .variants
+        .expect_get()
+        .times(2)
+        .returning(|id| Ok(Some(variant(id))));
+}
+
+fn confirmed(shortages: Vec<Shortage>) -> SaleOrderConfirmation {
+    SaleOrderConfirmation {
+        order: sale_order(5, SaleOrderStatus::Order),
+        shortages,
+        purchase_orders: vec![],
+    }
+}
+
+fn reservations() -> Vec<ReservationDraft> {
+    vec![
+        ReservationDraft {
+            line_id: 1,
+            variant_id: 8,
+            stock_id: 2,
+            quantity: 2.0,
+        },
+        ReservationDraft {
+            line_id: 2,
+            variant_id: 8,
+            stock_id: 2,
+            quantity: 3.0,
+        },
+    ]
+}
+
+#[actix_web::test]
+async fn test_insufficient_stock() {
+    let mut state = MockAppState::new();
+    mock_lines(&mut state);
+    state
+        .sale_orders
+        .expect_confirm()
+        .once()
+        .with(
+            predicate::eq(5),
+            predicate::always(),
+            predicate::eq(reservations()),
+            predicate::eq(vec![]),
+            predicate::eq(false),
+        )
+        .returning(|_, _, _, _, _| Err(DbErr::Custom(ERR_INSUFFICIENT_STOCK.to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::confirm),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-orders/5/confirm")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INSUFFICIENT_STOCK");
+}
+
+#[actix_web::test]
+async fn test_allow_oversell() {
+    let mut state = MockAppState::new();
+    mock_lines(&mut state);
+    state
+        .sale_orders
+        .expect_confirm()
+        .once()
+        .with(
+            predicate::eq(5),
+            predicate::always(),
+            predicate::eq(reservations()),
+            predicate::eq(vec![]),
+            predicate::eq(true),
+        )
+        .returning(|_, _, _, _, _| {
+            Ok(confirmed(vec![Shortage {
+                variant_id: 8,
+                requested: 5.0,
+                available: 4.0,
+            }]))
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::confirm),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-orders/5/confirm?allowOversell=true")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["order"]["status"], "order");
+    assert_eq!(body["shortages"][0]["variantId"], 8);
+    assert_eq!(body["shortages"][0]["requested"], 5.0);
+    assert_eq!(body["shortages"][0]["available"], 4.0);
+}
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(sale_order(5, SaleOrderStatus::Draft))));
+    state
+        .sale_order_lines
+        .expect_list_for_order()
+        .once()
+        .returning(|_| Ok(vec![line(1, 2.0, Some(8)), line(2, 3.0, Some(8))]));
+    state.variants.expect_stockable_ids().once().returning(Ok);
+    state
+        .variants
+        .expect_get()
+        .times(2)
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .sale_orders
+        .expect_confirm()
+        .once()
+        .with(
+            predicate::eq(5),
+            predicate::always(),
+            predicate::eq(reservations()),
+            predicate::eq(vec![]),
+            predicate::eq(false),
+        )
+        .returning(|_, _, _, _, _| Ok(confirmed(vec![])));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::confirm),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-orders/5/confirm")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["order"]["status"], "order");
+    assert_eq!(body["shortages"], serde_json::json!([]));
+}
+
+#[actix_web::test]
+async fn test_already_confirmed() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(sale_order(5, SaleOrderStatus::Order))));
+    state.sale_order_lines.expect_list_for_order().never();
+    state.sale_orders.expect_confirm().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::confirm),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-orders/5/confirm")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "SALE_ORDER_ALREADY_CONFIRMED");
+}
+
+#[actix_web::test]
+async fn test_confirmed_meanwhile() {
+    let mut state = MockAppState::new();
+    mock_lines(&mut state);
+    // Another confirmation got the lock on the order first.
+    state
+        .sale_orders
+        .expect_confirm()
+        .once()
+        .returning(|_, _, _, _, _| Err(DbErr::Custom(ERR_SALE_ORDER_NOT_DRAFT.to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::confirm),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-orders/5/confirm")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "SALE_ORDER_ALREADY_CONFIRMED");
+}
+
+fn supplier_price(variant_id: i32) -> entity::supplier_prices::Model {
+    entity::supplier_prices::Model {
+        id: 1,
+        variant_id,
+        supplier_id: 30,
+        supplier_sku: "S-8".to_string(),
+        description: "Stoneware mug".to_string(),
+        price: 450,
+        min_quantity: 0.0,
+        pack_size: 0.0,
+        lead_time_days: 3,
+        preferred: true,
+    }
+}
+
+/// A draft with a single drop-shipped line of 2 mugs.
+fn mock_drop_ship(state: &mut MockAppState) {
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(sale_order(5, SaleOrderStatus::Draft))));
+    state
+        .sale_order_lines
+        .expect_list_for_order()
+        .once()
+        .returning(|_| {
+            Ok(vec![entity::sale_order_lines::Model {
+                route: SaleOrderLineRoute::DropShip,
+                ..line(1, 2.0, Some(8))
+            }])
+        });
+    state.variants.expect_stockable_ids().returning(Ok);
+    state
+        .variants
+        .expect_get()
+        .returning(|id| Ok(Some(variant(id))));
+}
+
+#[actix_web::test]
+async fn test_drop_ship() {
+    let mut state = MockAppState::new();
+    mock_drop_ship(&mut state);
+    state
+        .stocks
+        .expect_find_for_contact()
+        .times(2)
+        .returning(|contact_id| match contact_id {
+            4 => Ok(Some(entity::stocks::Model {
+                owner_id: Some(4),
+                ..stock(40, StockKind::Customer)
+            })),
+            _ => Ok(None),
+        });
+    state
+        .stocks
+        .expect_list_virtual()
+        .once()
+        .returning(|| Ok(vec![stock(90, StockKind::Supplier)]));
+    state
+        .supplier_prices
+        .expect_list_for_variant()
+        .once()
+        .with(predicate::eq(8))
+        .returning(|variant_id| Ok(vec![supplier_price(variant_id)]));
+    state
+        .sale_orders
+        .expect_confirm()
+        .once()
+        .with(
+            predicate::eq(5),
+            predicate::always(),
+            predicate::eq(vec![]),
+            predicate::eq(vec![DropShipDraft {
+                supplier_id: 30,
+                source_stock_id: 90,
+                target_stock_id: 40,
+                lines: vec![DropShipLineDraft {
+                    sale_line_id: 1,
+                    line: PurchaseOrderLineDraft {
+                        description: "S-8 Stoneware mug".to_string(),
+                        quantity: 2.0,
+                        price: 450,
+                        tax: 19,
+                        tax_id: None,
+                        variant_id: Some(8),
+                        discount: Discount::default(),
+                        unit_id: None,
+                    },
+                }],
+            }]),
+            predicate::eq(false),
+        )
+        .returning(|_, date, _, drop_ships, _| {
+            let purchase_order = entity::purchase_orders::Model {
+                id: 7,
+                reference: "PO-2025-00001".to_string(),
+                date,
+                status: PurchaseOrderStatus::Order,
+                supplier_id: drop_ships[0].supplier_id,
+                source_stock_id: drop_ships[0].source_stock_id,
+                target_stock_id: drop_ships[0].target_stock_id,
+                discount_percent: 0.0,
+                discount_amount: 0,
+            };
+            Ok(SaleOrderConfirmation {
+                purchase_orders: vec![purchase_order],
+                ..confirmed(vec![])
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::confirm),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-orders/5/confirm")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["order"]["status"], "order");
+    assert_eq!(body["shortages"], serde_json::json!([]));
+    assert_eq!(body["purchaseOrders"][0]["id"], 7);
+    assert_eq!(body["purchaseOrders"][0]["status"], "order");
+    assert_eq!(body["purchaseOrders"][0]["targetStockId"], 40);
+}
+
+#[actix_web::test]
+async fn test_drop_ship_customer_stock_missing() {
+    let mut state = MockAppState::new();
+    mock_drop_ship(&mut state);
+    state
+        .stocks
+        .expect_find_for_contact()
+        .once()
+        .with(predicate::eq(4))
+        .returning(|_| Ok(None));
+    state.sale_orders.expect_confirm().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::confirm),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-orders/5/confirm")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "CUSTOMER_STOCK_MISSING");
+}
+
+#[actix_web::test]
+async fn test_drop_ship_supplier_missing() {
+    let mut state = MockAppState::new();
+    mock_drop_ship(&mut state);
+    state
+        .stocks
+        .expect_find_for_contact()
+        .once()
+        .returning(|contact_id| {
+            Ok(Some(entity::stocks::Model {
+                owner_id: Some(contact_id),
+                ..stock(40, StockKind::Customer)
+            }))
+        });
+    state
+        .supplier_prices
+        .expect_list_for_variant()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state.sale_orders.expect_confirm().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::confirm),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-orders/5/confirm")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "DROP_SHIP_SUPPLIER_MISSING");
+}
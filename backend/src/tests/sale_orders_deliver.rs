@@ -0,0 +1,419 @@
+use std::collections::HashMap;
+
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{SaleOrderLineRoute, SaleOrderStatus, Tracking};
+use mockall::predicate;
+use sea_orm::DbErr;
+use serde_json::json;
+
+use crate::{
+    handlers,
+    repositories::{CostAccounts, ERR_LOT_NOT_IN_STOCK, ERR_SALE_ORDER_DELIVERED, LineMoveDraft},
+    state::{AppState, MockAppState},
+    tests::fixtures::{sale_order, sale_order_line, variant},
+    utils::product_categories::CategoryDefaults,
+};
+
+fn lines() -> Vec<entity::sale_order_lines::Model> {
+    vec![
+        entity::sale_order_lines::Model {
+            quantity: 2.0,
+            variant_id: Some(8),
+            ..sale_order_line(1, 5)
+        },
+        entity::sale_order_lines::Model {
+            description: "Mug, delivered before".to_string(),
+            variant_id: Some(8),
+            move_id: Some(11),
+            ..sale_order_line(2, 5)
+        },
+    ]
+}
+
+fn defaults(inventory_account_id: Option<i32>) -> HashMap<i32, CategoryDefaults> {
+    HashMap::from([(
+        8,
+        CategoryDefaults {
+            expense_account_id: Some(5400),
+            inventory_account_id,
+            ..Default::default()
+        },
+    )])
+}
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|_| Ok(Some(sale_order(5, SaleOrderStatus::Order))));
+    state
+        .sale_order_lines
+        .expect_list_for_order()
+        .once()
+        .returning(|_| Ok(lines()));
+    state
+        .variants
+        .expect_stockable_ids()
+        .once()
+        .with(predicate::eq(vec![8]))
+        .returning(Ok);
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .returning(|_| Ok(HashMap::new()));
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .product_categories
+        .expect_defaults_for_variants()
+        .once()
+        .with(predicate::eq(vec![8]))
+        .returning(|_| Ok(defaults(Some(1400))));
+    state
+        .sale_orders
+        .expect_deliver()
+        .once()
+        .with(
+            predicate::always(),
+            predicate::always(),
+            predicate::eq(vec![LineMoveDraft {
+                line_id: 1,
+                variant_id: 8,
+                quantity: 2.0,
+                unit_cost: None,
+                cost_accounts: Some(CostAccounts {
+                    expense_account_id: 5400,
+                    inventory_account_id: 1400,
+                }),
+                lots: Vec::new(),
+            }]),
+        )
+        .returning(|order, _, _| {
+            Ok(entity::sale_orders::Model {
+                status: SaleOrderStatus::Finished,
+                ..order
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::deliver),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-orders/5/deliver")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["status"], "finished");
+}
+
+#[actix_web::test]
+async fn test_inventory_account_missing() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(sale_order(5, SaleOrderStatus::Order))));
+    state
+        .sale_order_lines
+        .expect_list_for_order()
+        .once()
+        .returning(|_| Ok(lines()));
+    state.variants.expect_stockable_ids().once().returning(Ok);
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .returning(|_| Ok(HashMap::new()));
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .product_categories
+        .expect_defaults_for_variants()
+        .once()
+        .returning(|_| Ok(defaults(None)));
+    state.sale_orders.expect_deliver().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::deliver),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-orders/5/deliver")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "POSTING_ACCOUNT_MISSING");
+}
+
+#[actix_web::test]
+async fn test_already_delivered() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(sale_order(5, SaleOrderStatus::Finished))));
+    state.sale_order_lines.expect_list_for_order().never();
+    state.sale_orders.expect_deliver().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::deliver),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-orders/5/deliver")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "SALE_ORDER_ALREADY_DELIVERED");
+}
+
+#[actix_web::test]
+async fn test_delivered_meanwhile() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(sale_order(5, SaleOrderStatus::Order))));
+    state
+        .sale_order_lines
+        .expect_list_for_order()
+        .once()
+        .returning(|_| Ok(lines()));
+    state.variants.expect_stockable_ids().once().returning(Ok);
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .returning(|_| Ok(HashMap::new()));
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .product_categories
+        .expect_defaults_for_variants()
+        .once()
+        .returning(|_| Ok(defaults(Some(1400))));
+    state
+        .sale_orders
+        .expect_deliver()
+        .once()
+        .returning(|_, _, _| Err(DbErr::Custom(ERR_SALE_ORDER_DELIVERED.to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::deliver),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-orders/5/deliver")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "SALE_ORDER_ALREADY_DELIVERED");
+}
+
+#[actix_web::test]
+async fn test_cancelled() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(sale_order(5, SaleOrderStatus::Cancelled))));
+    state.sale_orders.expect_deliver().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::deliver),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-orders/5/deliver")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "SALE_ORDER_CANCELLED");
+}
+
+#[actix_web::test]
+async fn test_lots_missing() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(sale_order(5, SaleOrderStatus::Order))));
+    state
+        .sale_order_lines
+        .expect_list_for_order()
+        .once()
+        .returning(|_| Ok(lines()));
+    state.variants.expect_stockable_ids().once().returning(Ok);
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .returning(|ids| Ok(ids.into_iter().map(|id| (id, Tracking::Lot)).collect()));
+    state.sale_orders.expect_deliver().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::deliver),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-orders/5/deliver")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "LOTS_MISSING");
+}
+
+#[actix_web::test]
+async fn test_lot_not_in_stock() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(sale_order(5, SaleOrderStatus::Order))));
+    state
+        .sale_order_lines
+        .expect_list_for_order()
+        .once()
+        .returning(|_| Ok(lines()));
+    state.variants.expect_stockable_ids().once().returning(Ok);
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .returning(|ids| Ok(ids.into_iter().map(|id| (id, Tracking::Lot)).collect()));
+    state
+        .product_categories
+        .expect_defaults_for_variants()
+        .once()
+        .returning(|_| Ok(defaults(Some(1400))));
+    state
+        .sale_orders
+        .expect_deliver()
+        .once()
+        .withf(|_, _, lines| lines[0].lots.len() == 1 && lines[0].lots[0].number == "B-100")
+        .returning(|_, _, _| Err(DbErr::Custom(ERR_LOT_NOT_IN_STOCK.to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::deliver),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-orders/5/deliver")
+        .set_json(json!({
+            "lots": [{ "lineId": 1, "number": "B-100", "quantity": 2.0 }],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "LOT_NOT_IN_STOCK");
+}
+
+#[actix_web::test]
+async fn test_drop_ship_not_confirmed() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .returning(|_| Ok(Some(sale_order(5, SaleOrderStatus::Draft))));
+    state
+        .sale_order_lines
+        .expect_list_for_order()
+        .once()
+        .returning(|_| {
+            let mut lines = lines();
+            lines[0].route = SaleOrderLineRoute::DropShip;
+            Ok(lines)
+        });
+    state.sale_orders.expect_deliver().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::deliver),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/sale-orders/5/deliver")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "SALE_ORDER_NOT_CONFIRMED");
+}
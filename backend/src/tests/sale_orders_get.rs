@@ -1,14 +1,297 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{PurchaseOrderStatus, SaleOrderLineRoute, SaleOrderStatus};
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr, prelude::Date};
+
+use crate::{
+    handlers,
+    repositories::ResolvedTax,
+    state::{AppState, MockAppState},
+    tests::fixtures,
+};
+
+fn line(
+    id: i32,
+    order_id: i32,
+    description: &str,
+    price: i32,
+    tax: i32,
+) -> entity::sale_order_lines::Model {
+    entity::sale_order_lines::Model {
+        description: description.to_string(),
+        price,
+        tax,
+        ..fixtures::sale_order_line(id, order_id)
+    }
+}
+
 #[actix_web::test]
 async fn test_success() {
-    todo!();
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|id| {
+            Ok(Some(entity::sale_orders::Model {
+                id,
+                reference: "SO-2025-00001".to_string(),
+                date: Date::from_ymd_opt(2025, 7, 1).unwrap(),
+                status: SaleOrderStatus::Order,
+                customer_id: 4,
+                source_stock_id: 1,
+                target_stock_id: 2,
+                discount_percent: 0.0,
+                discount_amount: 0,
+            }))
+        });
+    state
+        .sale_order_lines
+        .expect_list_for_order()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|order_id| {
+            Ok(vec![
+                line(1, order_id, "Chair", 1050, 19),
+                line(2, order_id, "Cushion", 1050, 19),
+                line(3, order_id, "Manual", 1999, 7),
+            ])
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::get),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-orders/5")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["order"]["reference"], "SO-2025-00001");
+    assert_eq!(body["lines"].as_array().unwrap().len(), 3);
+    assert_eq!(body["totals"]["lines"][0]["lineId"], 1);
+    assert_eq!(body["totals"]["lines"][0]["net"], 1050);
+    assert_eq!(body["totals"]["lines"][0]["tax"], 200);
+    assert_eq!(body["totals"]["lines"][0]["gross"], 1250);
+    // Rounded once per rate, 399 instead of the 400 of the two lines.
+    assert_eq!(body["totals"]["taxes"][0]["rate"], 0.19);
+    assert_eq!(body["totals"]["taxes"][0]["base"], 2100);
+    assert_eq!(body["totals"]["taxes"][0]["amount"], 399);
+    assert_eq!(body["totals"]["taxes"][1]["rate"], 0.07);
+    assert_eq!(body["totals"]["taxes"][1]["amount"], 140);
+    assert_eq!(body["totals"]["netTotal"], 4099);
+    assert_eq!(body["totals"]["taxTotal"], 539);
+    assert_eq!(body["totals"]["grossTotal"], 4638);
+}
+
+#[actix_web::test]
+async fn test_drop_shipments() {
+    let mut state = MockAppState::new();
+    state.sale_orders.expect_get().once().returning(|id| {
+        Ok(Some(entity::sale_orders::Model {
+            id,
+            reference: "SO-2025-00001".to_string(),
+            date: Date::from_ymd_opt(2025, 7, 1).unwrap(),
+            status: SaleOrderStatus::Order,
+            customer_id: 4,
+            source_stock_id: 1,
+            target_stock_id: 2,
+            discount_percent: 0.0,
+            discount_amount: 0,
+        }))
+    });
+    state
+        .sale_order_lines
+        .expect_list_for_order()
+        .once()
+        .returning(|order_id| {
+            Ok(vec![
+                line(1, order_id, "Chair", 1050, 19),
+                entity::sale_order_lines::Model {
+                    route: SaleOrderLineRoute::DropShip,
+                    ..line(2, order_id, "Sofa", 89900, 19)
+                },
+            ])
+        });
+    state
+        .purchase_order_lines
+        .expect_list_for_sale_lines()
+        .once()
+        .with(predicate::eq(vec![2]))
+        .returning(|_| {
+            Ok(vec![(
+                entity::purchase_order_lines::Model {
+                    description: "Sofa".to_string(),
+                    price: 60000,
+                    variant_id: Some(3),
+                    sale_line_id: Some(2),
+                    ..fixtures::purchase_order_line(12, 7)
+                },
+                entity::purchase_orders::Model {
+                    id: 7,
+                    reference: "PO-2025-00003".to_string(),
+                    date: Date::from_ymd_opt(2025, 7, 1).unwrap(),
+                    status: PurchaseOrderStatus::Order,
+                    supplier_id: 30,
+                    source_stock_id: 90,
+                    target_stock_id: 40,
+                    discount_percent: 0.0,
+                    discount_amount: 0,
+                },
+            )])
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::get),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-orders/5")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["lines"][1]["route"], "drop_ship");
+    assert_eq!(body["dropShipments"].as_array().unwrap().len(), 1);
+    assert_eq!(body["dropShipments"][0]["lineId"], 2);
+    assert_eq!(body["dropShipments"][0]["purchaseOrderLineId"], 12);
+    assert_eq!(
+        body["dropShipments"][0]["purchaseOrder"]["reference"],
+        "PO-2025-00003"
+    );
+    assert_eq!(body["dropShipments"][0]["purchaseOrder"]["status"], "order");
+}
+
+#[actix_web::test]
+async fn test_tax_rate_on_order_date() {
+    let mut state = MockAppState::new();
+    state.sale_orders.expect_get().once().returning(|id| {
+        Ok(Some(entity::sale_orders::Model {
+            id,
+            reference: "SO-2020-00001".to_string(),
+            date: Date::from_ymd_opt(2020, 7, 1).unwrap(),
+            status: SaleOrderStatus::Order,
+            customer_id: 4,
+            source_stock_id: 1,
+            target_stock_id: 2,
+            discount_percent: 0.0,
+            discount_amount: 0,
+        }))
+    });
+    state
+        .sale_order_lines
+        .expect_list_for_order()
+        .once()
+        .returning(|order_id| {
+            Ok(vec![entity::sale_order_lines::Model {
+                tax_id: Some(3),
+                ..line(1, order_id, "Chair", 1000, 19)
+            }])
+        });
+    // The rate was cut to 16% in the second half of 2020.
+    state
+        .taxes
+        .expect_resolve_at()
+        .once()
+        .with(
+            predicate::eq(3),
+            predicate::eq(Date::from_ymd_opt(2020, 7, 1).unwrap()),
+        )
+        .returning(|id, _| {
+            Ok(Some(ResolvedTax::plain(entity::taxes::Model {
+                id,
+                name: "Sales tax".to_string(),
+                name_short: "16%".to_string(),
+                rate: 0.16,
+                account_id: 1,
+                category: "S".to_string(),
+                note: "".to_string(),
+            })))
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::get),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-orders/5")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["lines"][0]["taxId"], 3);
+    assert_eq!(body["totals"]["taxes"][0]["rate"], 0.16);
+    assert_eq!(body["totals"]["taxTotal"], 160);
+    assert_eq!(body["totals"]["grossTotal"], 1160);
 }
 
 #[actix_web::test]
 async fn test_not_found() {
-    todo!();
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|_| Ok(None));
+    state.sale_order_lines.expect_list_for_order().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::get),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-orders/5")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
 }
 
 #[actix_web::test]
 async fn test_connection_error() {
-    todo!();
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|_| {
+            let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+            Err(err)
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::get),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-orders/5")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
 }
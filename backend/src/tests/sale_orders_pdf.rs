@@ -0,0 +1,151 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{SaleOrderLineRoute, SaleOrderStatus};
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr, prelude::Date};
+
+use crate::{
+    handlers,
+    state::{AppState, MockAppState},
+};
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|id| {
+            Ok(Some(entity::sale_orders::Model {
+                id,
+                reference: "SO-2025-00001".to_string(),
+                date: Date::from_ymd_opt(2025, 7, 1).unwrap(),
+                status: SaleOrderStatus::Quotation,
+                customer_id: 4,
+                source_stock_id: 1,
+                target_stock_id: 2,
+                discount_percent: 0.0,
+                discount_amount: 0,
+            }))
+        });
+    state
+        .sale_order_lines
+        .expect_list_for_order()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|order_id| {
+            Ok(vec![entity::sale_order_lines::Model {
+                id: 1,
+                description: "Chair".to_string(),
+                quantity: 2.0,
+                price: 4999,
+                tax: 19,
+                tax_id: None,
+                order_id,
+                variant_id: Some(1),
+                move_id: None,
+                discount_percent: 0.0,
+                discount_amount: 0,
+                unit_id: None,
+                route: SaleOrderLineRoute::Stock,
+            }])
+        });
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .with(predicate::eq(4))
+        .returning(|id| {
+            Ok(Some(entity::contacts::Model {
+                id,
+                name: "Customer".to_string(),
+                is_customer: true,
+                is_supplier: false,
+                street1: "Main Street 1".to_string(),
+                street2: "".to_string(),
+                postal_code: "12345".to_string(),
+                city: "Springfield".to_string(),
+                country: "US".to_string(),
+                customer_account_id: Some(1),
+                supplier_account_id: None,
+                vat_id: "".to_string(),
+                currency: "EUR".to_string(),
+            }))
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::pdf),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-orders/5/pdf")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+    assert_eq!(
+        res.headers().get("Content-Type").unwrap(),
+        "application/pdf"
+    );
+
+    let body = test::read_body(res).await;
+    assert!(body.starts_with(b"%PDF"));
+}
+
+#[actix_web::test]
+async fn test_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|_| Ok(None));
+    state.sale_order_lines.expect_list_for_order().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::pdf),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-orders/5/pdf")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+}
+
+#[actix_web::test]
+async fn test_connection_error() {
+    let mut state = MockAppState::new();
+    state
+        .sale_orders
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|_| {
+            let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+            Err(err)
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::sale_orders::pdf),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/sale-orders/5/pdf")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
+}
@@ -1,6 +1,203 @@
+use std::collections::HashMap;
+
+use actix_web::{App, test, web};
+use chrono::NaiveDate;
+use entity::sea_orm_active_enums::Tracking;
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr};
+use serde_json::json;
+
+use crate::{
+    handlers,
+    repositories::{ERR_LOT_NOT_IN_STOCK, LotDraft},
+    state::{AppState, MockAppState},
+    tests::fixtures::{unit, variant},
+};
+
 #[actix_web::test]
 async fn test_success() {
-    todo!();
+    let mut state = MockAppState::new();
+    state
+        .variants
+        .expect_get()
+        .once()
+        .with(predicate::eq(7))
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .variants
+        .expect_stockable_ids()
+        .once()
+        .with(predicate::eq(vec![7]))
+        .returning(Ok);
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .returning(|_| Ok(HashMap::new()));
+    state
+        .stock_moves
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq(3.0),
+            predicate::eq(7),
+            predicate::eq(1),
+            predicate::eq(2),
+            predicate::eq(vec![]),
+        )
+        .returning(
+            |quantity, variant_id, source_stock_id, target_stock_id, _| {
+                Ok(entity::stock_moves::Model {
+                    id: 1,
+                    quantity,
+                    variant_id,
+                    source_stock_id,
+                    target_stock_id,
+                })
+            },
+        );
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stock_moves::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/stock-moves")
+        .set_json(json!({
+            "quantity": 3.0,
+            "variantId": 7,
+            "sourceStockId": 1,
+            "targetStockId": 2,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["quantity"], 3.0);
+    assert_eq!(body["variantId"], 7);
+}
+
+#[actix_web::test]
+async fn test_converted_into_variant_unit() {
+    let mut state = MockAppState::new();
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .variants
+        .expect_stockable_ids()
+        .once()
+        .with(predicate::eq(vec![7]))
+        .returning(Ok);
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .returning(|_| Ok(HashMap::new()));
+    state.units.expect_get().times(2).returning(|id| match id {
+        2 => Ok(Some(unit(2, Some(1), 12.0))),
+        _ => Ok(Some(unit(id, None, 1.0))),
+    });
+    // 3 boxes of 12 are 36 pieces.
+    state
+        .stock_moves
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq(36.0),
+            predicate::eq(7),
+            predicate::eq(1),
+            predicate::eq(2),
+            predicate::eq(vec![]),
+        )
+        .returning(
+            |quantity, variant_id, source_stock_id, target_stock_id, _| {
+                Ok(entity::stock_moves::Model {
+                    id: 1,
+                    quantity,
+                    variant_id,
+                    source_stock_id,
+                    target_stock_id,
+                })
+            },
+        );
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stock_moves::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/stock-moves")
+        .set_json(json!({
+            "quantity": 3.0,
+            "variantId": 7,
+            "sourceStockId": 1,
+            "targetStockId": 2,
+            "unitId": 2,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["quantity"], 36.0);
+}
+
+#[actix_web::test]
+async fn test_incompatible_unit() {
+    let mut state = MockAppState::new();
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .variants
+        .expect_stockable_ids()
+        .once()
+        .with(predicate::eq(vec![7]))
+        .returning(Ok);
+    state
+        .units
+        .expect_get()
+        .times(2)
+        .returning(|id| Ok(Some(unit(id, None, 1.0))));
+    state.stock_moves.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stock_moves::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/stock-moves")
+        .set_json(json!({
+            "quantity": 3.0,
+            "variantId": 7,
+            "sourceStockId": 1,
+            "targetStockId": 2,
+            "unitId": 4,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INCOMPATIBLE_UNIT");
 }
 
 #[actix_web::test]
@@ -25,5 +222,269 @@ async fn test_invalid_target_stock_id() {
 
 #[actix_web::test]
 async fn test_connection_error() {
-    todo!();
+    let mut state = MockAppState::new();
+    state.variants.expect_get().once().returning(|_| {
+        let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+        Err(err)
+    });
+    state.stock_moves.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stock_moves::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/stock-moves")
+        .set_json(json!({
+            "quantity": 3.0,
+            "variantId": 7,
+            "sourceStockId": 1,
+            "targetStockId": 2,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
+}
+
+#[actix_web::test]
+async fn test_not_stockable() {
+    let mut state = MockAppState::new();
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .variants
+        .expect_stockable_ids()
+        .once()
+        .returning(|_| Ok(vec![]));
+    state.stock_moves.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stock_moves::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/stock-moves")
+        .set_json(json!({
+            "quantity": 3.0,
+            "variantId": 7,
+            "sourceStockId": 1,
+            "targetStockId": 2,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "VARIANT_NOT_STOCKABLE");
+}
+
+#[actix_web::test]
+async fn test_lots() {
+    let mut state = MockAppState::new();
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state.variants.expect_stockable_ids().once().returning(Ok);
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .with(predicate::eq(vec![7]))
+        .returning(|ids| Ok(ids.into_iter().map(|id| (id, Tracking::Lot)).collect()));
+    state
+        .stock_moves
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq(3.0),
+            predicate::eq(7),
+            predicate::eq(1),
+            predicate::eq(2),
+            predicate::eq(vec![
+                LotDraft {
+                    number: "L-1".to_string(),
+                    quantity: 2.0,
+                    expiry_date: NaiveDate::from_ymd_opt(2027, 3, 31),
+                },
+                LotDraft {
+                    number: "L-2".to_string(),
+                    quantity: 1.0,
+                    expiry_date: None,
+                },
+            ]),
+        )
+        .returning(
+            |quantity, variant_id, source_stock_id, target_stock_id, _| {
+                Ok(entity::stock_moves::Model {
+                    id: 1,
+                    quantity,
+                    variant_id,
+                    source_stock_id,
+                    target_stock_id,
+                })
+            },
+        );
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stock_moves::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/stock-moves")
+        .set_json(json!({
+            "quantity": 3.0,
+            "variantId": 7,
+            "sourceStockId": 1,
+            "targetStockId": 2,
+            "lots": [
+                { "number": " L-1 ", "quantity": 2.0, "expiryDate": "2027-03-31" },
+                { "number": "L-2", "quantity": 1.0 },
+            ],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+}
+
+#[actix_web::test]
+async fn test_lots_missing() {
+    let mut state = MockAppState::new();
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state.variants.expect_stockable_ids().once().returning(Ok);
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .returning(|ids| Ok(ids.into_iter().map(|id| (id, Tracking::Serial)).collect()));
+    state.stock_moves.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stock_moves::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/stock-moves")
+        .set_json(json!({
+            "quantity": 1.0,
+            "variantId": 7,
+            "sourceStockId": 1,
+            "targetStockId": 2,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "LOTS_MISSING");
+}
+
+#[actix_web::test]
+async fn test_invalid_expiry_date() {
+    let mut state = MockAppState::new();
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state.variants.expect_stockable_ids().once().returning(Ok);
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .returning(|ids| Ok(ids.into_iter().map(|id| (id, Tracking::Lot)).collect()));
+    state.stock_moves.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stock_moves::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/stock-moves")
+        .set_json(json!({
+            "quantity": 3.0,
+            "variantId": 7,
+            "sourceStockId": 1,
+            "targetStockId": 2,
+            "lots": [{ "number": "L-1", "quantity": 3.0, "expiryDate": "31.03.2027" }],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVALID_DATE");
+}
+
+#[actix_web::test]
+async fn test_lot_not_in_stock() {
+    let mut state = MockAppState::new();
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state.variants.expect_stockable_ids().once().returning(Ok);
+    state
+        .variants
+        .expect_tracking()
+        .once()
+        .returning(|ids| Ok(ids.into_iter().map(|id| (id, Tracking::Lot)).collect()));
+    state
+        .stock_moves
+        .expect_create()
+        .once()
+        .returning(|_, _, _, _, _| Err(DbErr::Custom(ERR_LOT_NOT_IN_STOCK.to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stock_moves::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/stock-moves")
+        .set_json(json!({
+            "quantity": 3.0,
+            "variantId": 7,
+            "sourceStockId": 1,
+            "targetStockId": 2,
+            "lots": [{ "number": "L-1", "quantity": 3.0 }],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "LOT_NOT_IN_STOCK");
 }
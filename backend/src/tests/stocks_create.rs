@@ -1,4 +1,5 @@
 use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::StockKind;
 use mockall::predicate;
 use sea_orm::{DbErr, RuntimeErr};
 use serde_json::json;
@@ -6,6 +7,7 @@ use serde_json::json;
 use crate::{
     handlers,
     state::{AppState, MockAppState},
+    tests::fixtures::{customer, stock, supplier},
 };
 
 #[actix_web::test]
@@ -15,13 +17,16 @@ async fn test_own_success() {
         .stocks
         .expect_create_own()
         .once()
-        .with(predicate::eq("Warenhaus".to_string()))
-        .returning(|name| {
+        .with(predicate::eq("Warenhaus".to_string()), predicate::eq(None))
+        .returning(|name, parent_id| {
             Ok(entity::stocks::Model {
                 id: 1,
                 name,
                 is_own: true,
                 owner_id: None,
+                kind: StockKind::Internal,
+                parent_id,
+                consignment: false,
             })
         });
 
@@ -53,7 +58,7 @@ async fn test_other_success() {
             Ok(Some(entity::contacts::Model {
                 id: id,
                 name: "".to_string(),
-                is_customer: false,
+                is_customer: true,
                 is_supplier: false,
                 street1: "".to_string(),
                 street2: "".to_string(),
@@ -62,19 +67,29 @@ async fn test_other_success() {
                 country: "".to_string(),
                 customer_account_id: None,
                 supplier_account_id: None,
+                vat_id: "".to_string(),
+                currency: "EUR".to_string(),
             }))
         });
     state
         .stocks
         .expect_create_for_contact()
         .once()
-        .with(predicate::eq("Warenhaus".to_string()), predicate::eq(5))
-        .returning(|name, owner_id| {
+        .with(
+            predicate::eq("Warenhaus".to_string()),
+            predicate::eq(5),
+            predicate::eq(StockKind::Customer),
+            predicate::eq(false),
+        )
+        .returning(|name, owner_id, kind, consignment| {
             Ok(entity::stocks::Model {
                 id: 1,
                 name,
                 is_own: false,
                 owner_id: Some(owner_id),
+                kind,
+                parent_id: None,
+                consignment,
             })
         });
 
@@ -87,7 +102,7 @@ async fn test_other_success() {
 
     let req = test::TestRequest::post()
         .uri("/api/contacts/5/stocks")
-        .set_json(json!({"name": "Warenhaus"}))
+        .set_json(json!({"name": "Warenhaus", "kind": "customer"}))
         .to_request();
 
     let res = test::call_service(&app, req).await;
@@ -129,13 +144,13 @@ async fn test_invalid_owner() {
     let app = test::init_service(
         App::new()
             .app_data(web::Data::new(AppState::from(state)))
-            .service(handlers::stocks::create_own),
+            .service(handlers::stocks::create_contact),
     )
     .await;
 
     let req = test::TestRequest::post()
         .uri("/api/contacts/5/stocks")
-        .set_json(json!({"name": "Warenhaus"}))
+        .set_json(json!({"name": "Warenhaus", "kind": "customer"}))
         .to_request();
 
     let res = test::call_service(&app, req).await;
@@ -149,8 +164,8 @@ async fn test_connection_error() {
         .stocks
         .expect_create_own()
         .once()
-        .with(predicate::eq("Warenhaus".to_string()))
-        .returning(|_| {
+        .with(predicate::eq("Warenhaus".to_string()), predicate::eq(None))
+        .returning(|_, _| {
             let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
             Err(err)
         });
@@ -170,3 +185,309 @@ async fn test_connection_error() {
     let res = test::call_service(&app, req).await;
     assert_eq!(res.status().as_u16(), 500u16);
 }
+
+#[actix_web::test]
+async fn test_with_parent() {
+    let mut state = MockAppState::new();
+    state
+        .stocks
+        .expect_get()
+        .once()
+        .with(predicate::eq(3))
+        .returning(|id| Ok(Some(stock(id, StockKind::Internal))));
+    state
+        .stocks
+        .expect_create_own()
+        .once()
+        .with(
+            predicate::eq("Bin A-01".to_string()),
+            predicate::eq(Some(3)),
+        )
+        .returning(|name, parent_id| {
+            Ok(entity::stocks::Model {
+                name,
+                parent_id,
+                ..stock(4, StockKind::Internal)
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stocks::create_own),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/stocks")
+        .set_json(json!({"name": "Bin A-01", "parentId": 3}))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["parentId"], 3);
+    assert_eq!(body["kind"], "internal");
+}
+
+#[actix_web::test]
+async fn test_parent_not_internal() {
+    let mut state = MockAppState::new();
+    state
+        .stocks
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(stock(id, StockKind::InventoryLoss))));
+    state.stocks.expect_create_own().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stocks::create_own),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/stocks")
+        .set_json(json!({"name": "Bin A-01", "parentId": 3}))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVALID_PARENT_STOCK");
+}
+
+#[actix_web::test]
+async fn test_parent_not_found() {
+    let mut state = MockAppState::new();
+    state.stocks.expect_get().once().returning(|_| Ok(None));
+    state.stocks.expect_create_own().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stocks::create_own),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/stocks")
+        .set_json(json!({"name": "Bin A-01", "parentId": 3}))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "STOCK_NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn test_customer_consignment() {
+    let mut state = MockAppState::new();
+    state.contacts.expect_get().once().returning(|id| {
+        Ok(Some(entity::contacts::Model {
+            is_supplier: true,
+            ..customer(id)
+        }))
+    });
+    state
+        .stocks
+        .expect_create_for_contact()
+        .once()
+        .with(
+            predicate::eq("Consignment".to_string()),
+            predicate::eq(5),
+            predicate::eq(StockKind::Customer),
+            predicate::eq(true),
+        )
+        .returning(|name, owner_id, kind, consignment| {
+            Ok(entity::stocks::Model {
+                name,
+                is_own: true,
+                owner_id: Some(owner_id),
+                consignment,
+                ..stock(6, kind)
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stocks::create_contact),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/contacts/5/stocks")
+        .set_json(json!({"name": "Consignment", "kind": "customer", "consignment": true}))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["kind"], "customer");
+    assert_eq!(body["own"], true);
+    assert_eq!(body["owner"], 5);
+    assert_eq!(body["consignment"], true);
+}
+
+#[actix_web::test]
+async fn test_supplier_consignment() {
+    let mut state = MockAppState::new();
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(supplier(id))));
+    state
+        .stocks
+        .expect_create_for_contact()
+        .once()
+        .with(
+            predicate::eq("Consignment".to_string()),
+            predicate::eq(5),
+            predicate::eq(StockKind::Supplier),
+            predicate::eq(true),
+        )
+        .returning(|name, owner_id, kind, consignment| {
+            Ok(entity::stocks::Model {
+                name,
+                owner_id: Some(owner_id),
+                consignment,
+                ..stock(6, kind)
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stocks::create_contact),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/contacts/5/stocks")
+        .set_json(json!({"name": "Consignment", "kind": "supplier", "consignment": true}))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["kind"], "supplier");
+    assert_eq!(body["own"], false);
+    assert_eq!(body["consignment"], true);
+}
+
+#[actix_web::test]
+async fn test_contact_not_found() {
+    let mut state = MockAppState::new();
+    state.contacts.expect_get().once().returning(|_| Ok(None));
+    state.stocks.expect_create_for_contact().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stocks::create_contact),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/contacts/5/stocks")
+        .set_json(json!({"name": "Consignment", "kind": "customer", "consignment": true}))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "CONTACT_NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn test_invalid_kind() {
+    let mut state = MockAppState::new();
+    state.contacts.expect_get().never();
+    state.stocks.expect_create_for_contact().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stocks::create_contact),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/contacts/5/stocks")
+        .set_json(json!({"name": "Warenhaus", "kind": "internal"}))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVALID_CONTACT_STOCK_KIND");
+}
+
+#[actix_web::test]
+async fn test_contact_not_customer() {
+    let mut state = MockAppState::new();
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(supplier(id))));
+    state.stocks.expect_create_for_contact().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stocks::create_contact),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/contacts/5/stocks")
+        .set_json(json!({"name": "Warenhaus", "kind": "customer"}))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "CONTACT_NOT_CUSTOMER");
+}
+
+#[actix_web::test]
+async fn test_contact_not_supplier() {
+    let mut state = MockAppState::new();
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(customer(id))));
+    state.stocks.expect_create_for_contact().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stocks::create_contact),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/contacts/5/stocks")
+        .set_json(json!({"name": "Warenhaus", "kind": "supplier"}))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "CONTACT_NOT_SUPPLIER");
+}
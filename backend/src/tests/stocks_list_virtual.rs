@@ -0,0 +1,53 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::StockKind;
+
+use crate::{
+    handlers,
+    state::{AppState, MockAppState},
+};
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state.stocks.expect_list_virtual().once().returning(|| {
+        Ok(vec![
+            entity::stocks::Model {
+                id: 7,
+                name: "Suppliers".to_string(),
+                is_own: false,
+                owner_id: None,
+                kind: StockKind::Supplier,
+                parent_id: None,
+                consignment: false,
+            },
+            entity::stocks::Model {
+                id: 9,
+                name: "Inventory loss".to_string(),
+                is_own: false,
+                owner_id: None,
+                kind: StockKind::InventoryLoss,
+                parent_id: None,
+                consignment: false,
+            },
+        ])
+    });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stocks::list_virtual),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/stocks/virtual")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body[0]["kind"], "supplier");
+    assert_eq!(body[1]["kind"], "inventory_loss");
+    assert_eq!(body[1]["own"], false);
+}
@@ -0,0 +1,90 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::StockKind;
+use mockall::predicate;
+
+use crate::{
+    handlers,
+    repositories::VariantQuantity,
+    state::{AppState, MockAppState},
+};
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .stocks
+        .expect_get()
+        .once()
+        .with(predicate::eq(1))
+        .returning(|id| {
+            Ok(Some(entity::stocks::Model {
+                id,
+                name: "Main warehouse".to_string(),
+                is_own: true,
+                owner_id: None,
+                kind: StockKind::Internal,
+                parent_id: None,
+                consignment: false,
+            }))
+        });
+    state
+        .stocks
+        .expect_quantities()
+        .once()
+        .with(predicate::eq(1))
+        .returning(|_| {
+            Ok(vec![
+                VariantQuantity {
+                    variant_id: 8,
+                    quantity: 12.0,
+                },
+                VariantQuantity {
+                    variant_id: 9,
+                    quantity: 0.5,
+                },
+            ])
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stocks::quantities),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/stocks/1/quantities")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body[0]["variantId"], 8);
+    assert_eq!(body[0]["quantity"], 12.0);
+    assert_eq!(body[1]["quantity"], 0.5);
+}
+
+#[actix_web::test]
+async fn test_not_found() {
+    let mut state = MockAppState::new();
+    state.stocks.expect_get().once().returning(|_| Ok(None));
+    state.stocks.expect_quantities().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stocks::quantities),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/stocks/1/quantities")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "STOCK_NOT_FOUND");
+}
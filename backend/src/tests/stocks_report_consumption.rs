@@ -0,0 +1,565 @@
+use std::collections::HashMap;
+
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{
+    CostingMethod, ProductType, PurchaseOrderStatus, SaleOrderLineRoute, SaleOrderStatus,
+    StockKind, TaxDirection, TaxRegion, Tracking,
+};
+use mockall::predicate;
+use sea_orm::{DbErr, prelude::Date};
+use serde_json::json;
+
+use crate::{
+    documents::Letterhead,
+    handlers,
+    repositories::{
+        ConsumedPurchaseLineDraft, ConsumedSaleLineDraft, CostAccounts, ERR_EXCEEDS_STOCK,
+        LineMoveDraft, PurchaseOrderLineDraft, SaleOrderLineDraft,
+    },
+    state::{AppState, MockAppState},
+    tests::fixtures::{self, variant},
+    utils::{product_categories::CategoryDefaults, totals::Discount},
+};
+
+/// A stock of the kind. Consignment stocks at customers stay own stock.
+fn stock(
+    id: i32,
+    kind: StockKind,
+    owner_id: Option<i32>,
+    consignment: bool,
+) -> entity::stocks::Model {
+    entity::stocks::Model {
+        is_own: kind == StockKind::Internal || (consignment && kind == StockKind::Customer),
+        owner_id,
+        consignment,
+        ..fixtures::stock(id, kind)
+    }
+}
+
+/// A customer and supplier without a country or accounts.
+fn contact(id: i32) -> entity::contacts::Model {
+    entity::contacts::Model {
+        name: "Keramik Nord".to_string(),
+        is_supplier: true,
+        country: "".to_string(),
+        customer_account_id: None,
+        ..fixtures::customer(id)
+    }
+}
+
+fn supplier_price(supplier_id: i32, price: i32) -> entity::supplier_prices::Model {
+    entity::supplier_prices::Model {
+        id: supplier_id,
+        variant_id: 8,
+        supplier_id,
+        supplier_sku: format!("S-{supplier_id}"),
+        description: "Stoneware mug".to_string(),
+        price,
+        min_quantity: 0.0,
+        pack_size: 1.0,
+        lead_time_days: 3,
+        preferred: false,
+    }
+}
+
+/// Tax 3 is the standard rate, tax 4 the intra-EU supply.
+fn tax(id: i32) -> entity::taxes::Model {
+    let standard = fixtures::tax(id);
+    match id {
+        3 => entity::taxes::Model {
+            account_id: 1776,
+            ..standard
+        },
+        _ => entity::taxes::Model {
+            name: "Intra-EU supply".to_string(),
+            name_short: "0%".to_string(),
+            rate: 0.0,
+            account_id: 1776,
+            category: "K".to_string(),
+            ..standard
+        },
+    }
+}
+
+fn date() -> Date {
+    Date::from_ymd_opt(2026, 11, 8).unwrap()
+}
+
+/// Mocks the lookups of a consumption of variant 8, whose category taxes it
+/// with tax 3 unless one of the rules matches.
+fn mock_lines(state: &mut MockAppState, rules: Vec<entity::tax_rules::Model>) {
+    state.letterhead = Letterhead {
+        country: "DE".to_string(),
+        ..Letterhead::default()
+    };
+    state.variants.expect_stockable_ids().returning(Ok);
+    state
+        .variants
+        .expect_tracking()
+        .returning(|_| Ok(HashMap::new()));
+    state
+        .variants
+        .expect_get()
+        .returning(|id| Ok(Some(variant(id))));
+    state.products.expect_get().returning(|id| {
+        Ok(Some(entity::products::Model {
+            id,
+            name: "Stoneware mug".to_string(),
+            tax_category: "standard".to_string(),
+            category_id: Some(2),
+            product_type: ProductType::Stockable,
+            costing_method: CostingMethod::Fifo,
+            tracking: Tracking::None,
+        }))
+    });
+    state
+        .tax_rules
+        .expect_list_for_region()
+        .once()
+        .returning(move |_, _| Ok(rules.clone()));
+    state
+        .product_categories
+        .expect_defaults_for_variants()
+        .returning(|_| {
+            Ok(HashMap::from([(
+                8,
+                CategoryDefaults {
+                    expense_account_id: Some(5400),
+                    inventory_account_id: Some(3980),
+                    tax_id: Some(3),
+                    ..Default::default()
+                },
+            )]))
+        });
+    state
+        .taxes
+        .expect_get_at()
+        .with(predicate::always(), predicate::eq(date()))
+        .returning(|id, _| Ok(Some(tax(id))));
+}
+
+#[actix_web::test]
+async fn test_customer_success() {
+    let mut state = MockAppState::new();
+    state
+        .stocks
+        .expect_get()
+        .with(predicate::eq(7))
+        .returning(|id| Ok(Some(stock(id, StockKind::Customer, Some(5), true))));
+    state
+        .contacts
+        .expect_get()
+        .with(predicate::eq(5))
+        .returning(|id| Ok(Some(contact(id))));
+    state.stocks.expect_list_virtual().once().returning(|| {
+        Ok(vec![
+            stock(1, StockKind::Supplier, None, false),
+            stock(2, StockKind::Customer, None, false),
+        ])
+    });
+    mock_lines(&mut state, vec![]);
+    state
+        .price_lists
+        .expect_list_prices_for_customer()
+        .returning(|_, _| Ok(vec![]));
+    state
+        .sale_orders
+        .expect_consume()
+        .once()
+        .withf(
+            |date_, customer_id, source_stock_id, target_stock_id, lines| {
+                *date_ == date()
+                    && *customer_id == 5
+                    && *source_stock_id == 7
+                    && *target_stock_id == 2
+                    && lines
+                        == &[ConsumedSaleLineDraft {
+                            line: SaleOrderLineDraft {
+                                description: "Stoneware mug".to_string(),
+                                quantity: 3.0,
+                                price: 1200,
+                                tax: 19,
+                                tax_id: Some(3),
+                                variant_id: Some(8),
+                                discount: Discount::default(),
+                                unit_id: Some(1),
+                                route: SaleOrderLineRoute::Stock,
+                            },
+                            stock_move: LineMoveDraft {
+                                line_id: 0,
+                                variant_id: 8,
+                                quantity: 3.0,
+                                unit_cost: None,
+                                cost_accounts: Some(CostAccounts {
+                                    expense_account_id: 5400,
+                                    inventory_account_id: 3980,
+                                }),
+                                lots: vec![],
+                            },
+                        }]
+            },
+        )
+        .returning(|date, customer_id, source_stock_id, target_stock_id, _| {
+            Ok(entity::sale_orders::Model {
+                id: 12,
+                reference: "SO-12".to_string(),
+                date,
+                status: SaleOrderStatus::Finished,
+                customer_id,
+                source_stock_id,
+                target_stock_id,
+                discount_percent: 0.0,
+                discount_amount: 0,
+            })
+        });
+    state.purchase_orders.expect_consume().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stocks::report_consumption),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/stocks/7/consumption")
+        .set_json(json!({
+            "date": "2026-11-08",
+            "lines": [{"variantId": 8, "quantity": 3.0}],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["saleOrder"]["id"], 12);
+    assert_eq!(body["saleOrder"]["status"], "finished");
+    assert!(body["purchaseOrder"].is_null());
+}
+
+#[actix_web::test]
+async fn test_supplier_success() {
+    let mut state = MockAppState::new();
+    state.stocks.expect_get().returning(|id| match id {
+        7 => Ok(Some(stock(id, StockKind::Supplier, Some(30), true))),
+        _ => Ok(Some(stock(id, StockKind::Internal, None, false))),
+    });
+    state
+        .contacts
+        .expect_get()
+        .with(predicate::eq(30))
+        .returning(|id| Ok(Some(contact(id))));
+    mock_lines(&mut state, vec![]);
+    state
+        .supplier_prices
+        .expect_list_for_variant()
+        .with(predicate::eq(8))
+        .returning(|_| Ok(vec![supplier_price(31, 400), supplier_price(30, 450)]));
+    state
+        .purchase_orders
+        .expect_consume()
+        .once()
+        .withf(
+            |date_, supplier_id, source_stock_id, target_stock_id, lines| {
+                *date_ == date()
+                    && *supplier_id == 30
+                    && *source_stock_id == 7
+                    && *target_stock_id == 3
+                    && lines
+                        == &[ConsumedPurchaseLineDraft {
+                            line: PurchaseOrderLineDraft {
+                                description: "S-30 Stoneware mug".to_string(),
+                                quantity: 3.0,
+                                price: 450,
+                                tax: 19,
+                                tax_id: Some(3),
+                                variant_id: Some(8),
+                                discount: Discount::default(),
+                                unit_id: Some(1),
+                            },
+                            // The goods come in at the supplier's price.
+                            stock_move: LineMoveDraft {
+                                line_id: 0,
+                                variant_id: 8,
+                                quantity: 3.0,
+                                unit_cost: Some(450.0),
+                                cost_accounts: None,
+                                lots: vec![],
+                            },
+                        }]
+            },
+        )
+        .returning(|date, supplier_id, source_stock_id, target_stock_id, _| {
+            Ok(entity::purchase_orders::Model {
+                id: 14,
+                reference: "PO-14".to_string(),
+                date,
+                status: PurchaseOrderStatus::Finished,
+                supplier_id,
+                source_stock_id,
+                target_stock_id,
+                discount_percent: 0.0,
+                discount_amount: 0,
+            })
+        });
+    state.sale_orders.expect_consume().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stocks::report_consumption),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/stocks/7/consumption")
+        .set_json(json!({
+            "date": "2026-11-08",
+            "targetStockId": 3,
+            "lines": [{"variantId": 8, "quantity": 3.0}],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["purchaseOrder"]["id"], 14);
+    assert_eq!(body["purchaseOrder"]["status"], "finished");
+    assert!(body["saleOrder"].is_null());
+}
+
+#[actix_web::test]
+async fn test_not_consignment() {
+    let mut state = MockAppState::new();
+    state
+        .stocks
+        .expect_get()
+        .returning(|id| Ok(Some(stock(id, StockKind::Customer, Some(5), false))));
+    state.sale_orders.expect_consume().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stocks::report_consumption),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/stocks/7/consumption")
+        .set_json(json!({
+            "date": "2026-11-08",
+            "lines": [{"variantId": 8, "quantity": 3.0}],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "STOCK_NOT_CONSIGNMENT");
+}
+
+#[actix_web::test]
+async fn test_supplier_without_target() {
+    let mut state = MockAppState::new();
+    state
+        .stocks
+        .expect_get()
+        .returning(|id| Ok(Some(stock(id, StockKind::Supplier, Some(30), true))));
+    state
+        .contacts
+        .expect_get()
+        .returning(|id| Ok(Some(contact(id))));
+    state.purchase_orders.expect_consume().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stocks::report_consumption),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/stocks/7/consumption")
+        .set_json(json!({
+            "date": "2026-11-08",
+            "lines": [{"variantId": 8, "quantity": 3.0}],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVALID_CONSUMPTION_TARGET");
+}
+
+#[actix_web::test]
+async fn test_customer_location_missing() {
+    let mut state = MockAppState::new();
+    state
+        .stocks
+        .expect_get()
+        .returning(|id| Ok(Some(stock(id, StockKind::Customer, Some(5), true))));
+    state
+        .contacts
+        .expect_get()
+        .returning(|id| Ok(Some(contact(id))));
+    state
+        .stocks
+        .expect_list_virtual()
+        .once()
+        .returning(|| Ok(vec![stock(1, StockKind::Supplier, None, false)]));
+    state.sale_orders.expect_consume().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stocks::report_consumption),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/stocks/7/consumption")
+        .set_json(json!({
+            "date": "2026-11-08",
+            "lines": [{"variantId": 8, "quantity": 3.0}],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "CUSTOMER_LOCATION_MISSING");
+}
+
+#[actix_web::test]
+async fn test_exceeds_stock() {
+    let mut state = MockAppState::new();
+    state
+        .stocks
+        .expect_get()
+        .returning(|id| Ok(Some(stock(id, StockKind::Customer, Some(5), true))));
+    state
+        .contacts
+        .expect_get()
+        .returning(|id| Ok(Some(contact(id))));
+    state
+        .stocks
+        .expect_list_virtual()
+        .once()
+        .returning(|| Ok(vec![stock(2, StockKind::Customer, None, false)]));
+    mock_lines(&mut state, vec![]);
+    state
+        .price_lists
+        .expect_list_prices_for_customer()
+        .returning(|_, _| Ok(vec![]));
+    // Two lines of the same variant add up to more than is on consignment.
+    state
+        .sale_orders
+        .expect_consume()
+        .once()
+        .withf(|_, _, _, _, lines| lines.len() == 2)
+        .returning(|_, _, _, _, _| Err(DbErr::Custom(ERR_EXCEEDS_STOCK.to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stocks::report_consumption),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/stocks/7/consumption")
+        .set_json(json!({
+            "date": "2026-11-08",
+            "lines": [
+                {"variantId": 8, "quantity": 3.0},
+                {"variantId": 8, "quantity": 3.0},
+            ],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "CONSUMPTION_EXCEEDS_STOCK");
+}
+
+#[actix_web::test]
+async fn test_customer_tax_rule() {
+    let mut state = MockAppState::new();
+    state
+        .stocks
+        .expect_get()
+        .returning(|id| Ok(Some(stock(id, StockKind::Customer, Some(5), true))));
+    state.contacts.expect_get().returning(|id| {
+        Ok(Some(entity::contacts::Model {
+            country: "AT".to_string(),
+            vat_id: "ATU12345678".to_string(),
+            ..contact(id)
+        }))
+    });
+    state
+        .stocks
+        .expect_list_virtual()
+        .once()
+        .returning(|| Ok(vec![stock(2, StockKind::Customer, None, false)]));
+    // Supplies to EU customers with a VAT ID are tax-free, whatever the
+    // category's default tax.
+    mock_lines(
+        &mut state,
+        vec![entity::tax_rules::Model {
+            id: 1,
+            direction: TaxDirection::Sale,
+            region: TaxRegion::Eu,
+            vat_id: Some(true),
+            product_tax_category: None,
+            tax_id: 4,
+        }],
+    );
+    state
+        .price_lists
+        .expect_list_prices_for_customer()
+        .returning(|_, _| Ok(vec![]));
+    state
+        .sale_orders
+        .expect_consume()
+        .once()
+        .withf(|_, _, _, _, lines| lines[0].line.tax == 0)
+        .returning(|date, customer_id, source_stock_id, target_stock_id, _| {
+            Ok(entity::sale_orders::Model {
+                id: 12,
+                reference: "SO-12".to_string(),
+                date,
+                status: SaleOrderStatus::Finished,
+                customer_id,
+                source_stock_id,
+                target_stock_id,
+                discount_percent: 0.0,
+                discount_amount: 0,
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::stocks::report_consumption),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/stocks/7/consumption")
+        .set_json(json!({
+            "date": "2026-11-08",
+            "lines": [{"variantId": 8, "quantity": 3.0}],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+}
@@ -0,0 +1,279 @@
+use actix_web::{App, test, web};
+use mockall::predicate;
+use sea_orm::DbErr;
+use serde_json::json;
+
+use crate::{
+    handlers,
+    repositories::{ERR_SUPPLIER_PRICE_EXISTS, SupplierPriceDraft},
+    state::{AppState, MockAppState},
+    tests::fixtures::{customer, supplier, variant},
+};
+
+fn created(
+    variant_id: i32,
+    supplier_id: i32,
+    entry: SupplierPriceDraft,
+) -> entity::supplier_prices::Model {
+    entity::supplier_prices::Model {
+        id: 1,
+        variant_id,
+        supplier_id,
+        supplier_sku: entry.supplier_sku,
+        description: entry.description,
+        price: entry.price,
+        min_quantity: entry.min_quantity,
+        pack_size: entry.pack_size,
+        lead_time_days: entry.lead_time_days,
+        preferred: entry.preferred,
+    }
+}
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .variants
+        .expect_get()
+        .once()
+        .with(predicate::eq(7))
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .with(predicate::eq(3))
+        .returning(|id| Ok(Some(supplier(id))));
+    state
+        .supplier_prices
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq(7),
+            predicate::eq(3),
+            predicate::eq(SupplierPriceDraft {
+                supplier_sku: "CH-100".to_string(),
+                description: "Oak chair".to_string(),
+                price: 2250,
+                min_quantity: 10.0,
+                pack_size: 5.0,
+                lead_time_days: 14,
+                preferred: true,
+            }),
+        )
+        .returning(|variant_id, supplier_id, entry| Ok(created(variant_id, supplier_id, entry)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::supplier_prices::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/variants/7/supplier-prices")
+        .set_json(json!({
+            "supplierId": 3,
+            "supplierSku": " CH-100 ",
+            "description": "Oak chair",
+            "price": 2250,
+            "minQuantity": 10.0,
+            "packSize": 5.0,
+            "leadTimeDays": 14,
+            "preferred": true,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["variantId"], 7);
+    assert_eq!(body["supplierId"], 3);
+    assert_eq!(body["supplierSku"], "CH-100");
+    assert_eq!(body["packSize"], 5.0);
+    assert_eq!(body["leadTimeDays"], 14);
+    assert_eq!(body["preferred"], true);
+}
+
+#[actix_web::test]
+async fn test_defaults() {
+    let mut state = MockAppState::new();
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(supplier(id))));
+    state
+        .supplier_prices
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq(7),
+            predicate::eq(3),
+            predicate::eq(SupplierPriceDraft {
+                supplier_sku: "".to_string(),
+                description: "".to_string(),
+                price: 2400,
+                min_quantity: 0.0,
+                pack_size: 1.0,
+                lead_time_days: 0,
+                preferred: false,
+            }),
+        )
+        .returning(|variant_id, supplier_id, entry| Ok(created(variant_id, supplier_id, entry)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::supplier_prices::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/variants/7/supplier-prices")
+        .set_json(json!({
+            "supplierId": 3,
+            "price": 2400,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+}
+
+#[actix_web::test]
+async fn test_invalid_pack_size() {
+    let mut state = MockAppState::new();
+    state.supplier_prices.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::supplier_prices::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/variants/7/supplier-prices")
+        .set_json(json!({
+            "supplierId": 3,
+            "price": 2400,
+            "packSize": 0.0,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+}
+
+#[actix_web::test]
+async fn test_variant_not_found() {
+    let mut state = MockAppState::new();
+    state.variants.expect_get().once().returning(|_| Ok(None));
+    state.supplier_prices.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::supplier_prices::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/variants/7/supplier-prices")
+        .set_json(json!({
+            "supplierId": 3,
+            "price": 2400,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "VARIANT_NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn test_not_a_supplier() {
+    let mut state = MockAppState::new();
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(customer(id))));
+    state.supplier_prices.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::supplier_prices::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/variants/7/supplier-prices")
+        .set_json(json!({
+            "supplierId": 3,
+            "price": 2400,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "CONTACT_NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn test_exists() {
+    let mut state = MockAppState::new();
+    state
+        .variants
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(variant(id))));
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(supplier(id))));
+    state
+        .supplier_prices
+        .expect_create()
+        .once()
+        .returning(|_, _, _| Err(DbErr::Custom(ERR_SUPPLIER_PRICE_EXISTS.to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::supplier_prices::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/variants/7/supplier-prices")
+        .set_json(json!({
+            "supplierId": 3,
+            "price": 2400,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "SUPPLIER_PRICE_EXISTS");
+}
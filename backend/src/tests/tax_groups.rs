@@ -0,0 +1,30 @@
+use crate::utils::{tax_groups::component_bases, totals::tax_total};
+
+#[test]
+fn test_component_bases() {
+    assert_eq!(component_bases(10000, &[(0.19, false)]), vec![10000]);
+    assert_eq!(
+        component_bases(10000, &[(0.05, false), (0.07, false)]),
+        vec![10000, 10000]
+    );
+    assert_eq!(
+        component_bases(10000, &[(0.05, false), (0.07, true)]),
+        vec![10000, 10500]
+    );
+    assert_eq!(
+        component_bases(10000, &[(0.1, false), (0.1, true), (0.1, true)]),
+        vec![10000, 11000, 12100]
+    );
+    assert_eq!(
+        component_bases(4999, &[(0.05, false), (0.07, true)]),
+        vec![4999, 5249]
+    );
+}
+
+#[test]
+fn test_tax_total() {
+    assert_eq!(tax_total([(10000, 0.05), (10500, 0.07)]), 1235);
+    // Rounded once per rate, not per line.
+    assert_eq!(tax_total([(3, 0.19), (3, 0.19)]), 1);
+    assert_eq!(tax_total([(3, 0.19), (3, 0.07)]), 1);
+}
@@ -0,0 +1,78 @@
+use entity::sea_orm_active_enums::{TaxDirection, TaxRegion};
+
+use crate::utils::tax_rules::{is_country_code, select_tax_rule, tax_region};
+
+fn rule(
+    id: i32,
+    region: TaxRegion,
+    vat_id: Option<bool>,
+    product_tax_category: Option<&str>,
+) -> entity::tax_rules::Model {
+    entity::tax_rules::Model {
+        id,
+        direction: TaxDirection::Sale,
+        region,
+        vat_id,
+        product_tax_category: product_tax_category.map(str::to_string),
+        tax_id: id * 10,
+    }
+}
+
+#[test]
+fn test_tax_region() {
+    assert_eq!(tax_region("DE", "DE"), TaxRegion::Domestic);
+    assert_eq!(tax_region("DE", " de "), TaxRegion::Domestic);
+    assert_eq!(tax_region("DE", ""), TaxRegion::Domestic);
+    assert_eq!(tax_region("DE", "AT"), TaxRegion::Eu);
+    assert_eq!(tax_region("DE", "CH"), TaxRegion::NonEu);
+    assert_eq!(tax_region("AT", "DE"), TaxRegion::Eu);
+}
+
+#[test]
+fn test_is_country_code() {
+    assert!(is_country_code("DE"));
+    assert!(is_country_code("AT"));
+    assert!(!is_country_code(""));
+    assert!(!is_country_code("de"));
+    assert!(!is_country_code("DEU"));
+    assert!(!is_country_code("Germany"));
+}
+
+#[test]
+fn test_select_tax_rule() {
+    let rules = vec![
+        rule(1, TaxRegion::Domestic, None, None),
+        rule(2, TaxRegion::Domestic, None, Some("reduced")),
+        rule(3, TaxRegion::Eu, None, None),
+        rule(4, TaxRegion::Eu, Some(true), None),
+        rule(5, TaxRegion::NonEu, None, None),
+    ];
+    let select = |region, has_vat_id, category| {
+        select_tax_rule(&rules, &region, has_vat_id, category).map(|rule| rule.id)
+    };
+
+    assert_eq!(select(TaxRegion::Domestic, false, None), Some(1));
+    assert_eq!(select(TaxRegion::Domestic, true, Some("standard")), Some(1));
+    assert_eq!(select(TaxRegion::Domestic, false, Some("reduced")), Some(2));
+    // Consumers in other member states are taxed like domestic ones, only
+    // businesses with a VAT ID get the reverse charge rule.
+    assert_eq!(select(TaxRegion::Eu, false, None), Some(3));
+    assert_eq!(select(TaxRegion::Eu, true, None), Some(4));
+    assert_eq!(select(TaxRegion::NonEu, true, Some("reduced")), Some(5));
+}
+
+#[test]
+fn test_select_tax_rule_specificity() {
+    let rules = vec![
+        rule(1, TaxRegion::Eu, Some(true), None),
+        rule(2, TaxRegion::Eu, None, Some("reduced")),
+        rule(3, TaxRegion::Eu, None, Some("reduced")),
+        rule(4, TaxRegion::Eu, Some(false), None),
+    ];
+
+    let selected = select_tax_rule(&rules, &TaxRegion::Eu, true, Some("reduced"));
+    assert_eq!(selected.map(|rule| rule.id), Some(2));
+
+    let selected = select_tax_rule(&rules, &TaxRegion::Domestic, true, None);
+    assert_eq!(selected, None);
+}
@@ -0,0 +1,131 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{TaxDirection, TaxRegion};
+use mockall::predicate;
+use serde_json::json;
+
+use crate::{
+    handlers,
+    state::{AppState, MockAppState},
+};
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .taxes
+        .expect_get()
+        .once()
+        .with(predicate::eq(9))
+        .returning(|id| {
+            Ok(Some(entity::taxes::Model {
+                id,
+                name: "Export".to_string(),
+                name_short: "0%".to_string(),
+                rate: 0.0,
+                account_id: 1,
+                category: "G".to_string(),
+                note: "Steuerfreie Ausfuhrlieferung".to_string(),
+            }))
+        });
+    state
+        .tax_rules
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq(TaxDirection::Sale),
+            predicate::eq(TaxRegion::NonEu),
+            predicate::eq(None),
+            predicate::eq(Some("standard".to_string())),
+            predicate::eq(9),
+        )
+        .returning(|direction, region, vat_id, product_tax_category, tax_id| {
+            Ok(entity::tax_rules::Model {
+                id: 1,
+                direction,
+                region,
+                vat_id,
+                product_tax_category,
+                tax_id,
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::tax_rules::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/tax-rules")
+        .set_json(json!({
+            "direction": "sale",
+            "region": "non_eu",
+            "productTaxCategory": "standard",
+            "taxId": 9,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["region"], "non_eu");
+    assert_eq!(body["vatId"], serde_json::Value::Null);
+}
+
+#[actix_web::test]
+async fn test_invalid_region() {
+    let mut state = MockAppState::new();
+    state.taxes.expect_get().never();
+    state.tax_rules.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::tax_rules::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/tax-rules")
+        .set_json(json!({
+            "direction": "sale",
+            "region": "abroad",
+            "taxId": 9,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVALID_TAX_REGION");
+}
+
+#[actix_web::test]
+async fn test_tax_not_found() {
+    let mut state = MockAppState::new();
+    state.taxes.expect_get().once().returning(|_| Ok(None));
+    state.tax_rules.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::tax_rules::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/tax-rules")
+        .set_json(json!({
+            "direction": "purchase",
+            "region": "eu",
+            "vatId": true,
+            "taxId": 9,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+}
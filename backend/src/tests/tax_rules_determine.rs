@@ -0,0 +1,265 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{CostingMethod, ProductType, TaxDirection, TaxRegion, Tracking};
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr, prelude::Date};
+
+use crate::{
+    documents::Letterhead,
+    handlers,
+    state::{AppState, MockAppState},
+    tests::fixtures,
+};
+
+fn customer(id: i32, country: &str, vat_id: &str) -> entity::contacts::Model {
+    entity::contacts::Model {
+        country: country.to_string(),
+        vat_id: vat_id.to_string(),
+        ..fixtures::customer(id)
+    }
+}
+
+fn rule(id: i32, vat_id: Option<bool>, tax_id: i32) -> entity::tax_rules::Model {
+    entity::tax_rules::Model {
+        id,
+        direction: TaxDirection::Sale,
+        region: TaxRegion::Eu,
+        vat_id,
+        product_tax_category: None,
+        tax_id,
+    }
+}
+
+fn state_in_germany() -> MockAppState {
+    let mut state = MockAppState::new();
+    state.letterhead = Letterhead {
+        country: "DE".to_string(),
+        ..Letterhead::default()
+    };
+    state
+}
+
+#[actix_web::test]
+async fn test_reverse_charge() {
+    let mut state = state_in_germany();
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .with(predicate::eq(4))
+        .returning(|id| Ok(Some(customer(id, "AT", "ATU12345678"))));
+    state
+        .variants
+        .expect_get()
+        .once()
+        .with(predicate::eq(2))
+        .returning(|id| {
+            Ok(Some(entity::variants::Model {
+                id,
+                name: "Oak".to_string(),
+                sale_price: 4999,
+                purchase_price: 2000,
+                product_id: 1,
+                sku: None,
+                gtin: None,
+                unit_id: None,
+            }))
+        });
+    state
+        .products
+        .expect_get()
+        .once()
+        .with(predicate::eq(1))
+        .returning(|id| {
+            Ok(Some(entity::products::Model {
+                id,
+                name: "Chair".to_string(),
+                tax_category: "standard".to_string(),
+                category_id: None,
+                product_type: ProductType::Stockable,
+                costing_method: CostingMethod::Fifo,
+                tracking: Tracking::None,
+            }))
+        });
+    state
+        .tax_rules
+        .expect_list_for_region()
+        .once()
+        .with(
+            predicate::eq(TaxDirection::Sale),
+            predicate::eq(TaxRegion::Eu),
+        )
+        .returning(|_, _| Ok(vec![rule(1, None, 7), rule(2, Some(true), 9)]));
+    state
+        .taxes
+        .expect_get_at()
+        .once()
+        .with(
+            predicate::eq(9),
+            predicate::eq(Date::from_ymd_opt(2025, 7, 1).unwrap()),
+        )
+        .returning(|id, _| {
+            Ok(Some(entity::taxes::Model {
+                id,
+                name: "Reverse charge".to_string(),
+                name_short: "RC".to_string(),
+                rate: 0.0,
+                account_id: 1,
+                category: "AE".to_string(),
+                note: "Steuerschuldnerschaft des Leistungsempfängers".to_string(),
+            }))
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::tax_rules::determine),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/tax-rules/determine?direction=sale&contactId=4&variantId=2&date=2025-07-01")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["region"], "eu");
+    assert_eq!(body["ruleId"], 2);
+    assert_eq!(body["tax"]["id"], 9);
+    assert_eq!(body["tax"]["category"], "AE");
+    assert_eq!(
+        body["tax"]["note"],
+        "Steuerschuldnerschaft des Leistungsempfängers"
+    );
+}
+
+#[actix_web::test]
+async fn test_no_matching_rule() {
+    let mut state = state_in_germany();
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(customer(id, "CH", ""))));
+    state
+        .tax_rules
+        .expect_list_for_region()
+        .once()
+        .with(
+            predicate::eq(TaxDirection::Sale),
+            predicate::eq(TaxRegion::NonEu),
+        )
+        .returning(|_, _| Ok(vec![]));
+    state.taxes.expect_get_at().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::tax_rules::determine),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/tax-rules/determine?direction=sale&contactId=4")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 422u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "NO_MATCHING_TAX_RULE");
+}
+
+#[actix_web::test]
+async fn test_invalid_direction() {
+    let mut state = state_in_germany();
+    state.contacts.expect_get().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::tax_rules::determine),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/tax-rules/determine?direction=refund&contactId=4")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+}
+
+#[actix_web::test]
+async fn test_invalid_date() {
+    let mut state = state_in_germany();
+    state.contacts.expect_get().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::tax_rules::determine),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/tax-rules/determine?direction=sale&contactId=4&date=01.07.2025")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+}
+
+#[actix_web::test]
+async fn test_contact_not_found() {
+    let mut state = state_in_germany();
+    state.contacts.expect_get().once().returning(|_| Ok(None));
+    state.tax_rules.expect_list_for_region().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::tax_rules::determine),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/tax-rules/determine?direction=purchase&contactId=4")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+}
+
+#[actix_web::test]
+async fn test_connection_error() {
+    let mut state = state_in_germany();
+    state
+        .contacts
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(customer(id, "DE", ""))));
+    state
+        .tax_rules
+        .expect_list_for_region()
+        .once()
+        .returning(|_, _| {
+            let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+            Err(err)
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::tax_rules::determine),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/tax-rules/determine?direction=sale&contactId=4")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
+}
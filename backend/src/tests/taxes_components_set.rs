@@ -0,0 +1,189 @@
+use actix_web::{App, test, web};
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr};
+use serde_json::json;
+
+use crate::{
+    handlers,
+    repositories::ERR_NESTED_TAX_GROUP,
+    state::{AppState, MockAppState},
+};
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .taxes
+        .expect_set_components()
+        .once()
+        .with(predicate::eq(5), predicate::eq(vec![(2, false), (3, true)]))
+        .returning(|group_id, components| {
+            Ok(components
+                .into_iter()
+                .enumerate()
+                .map(
+                    |(index, (tax_id, compound))| entity::tax_components::Model {
+                        id: index as i32 + 1,
+                        group_id,
+                        tax_id,
+                        sequence: index as i32 + 1,
+                        compound,
+                    },
+                )
+                .collect())
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::taxes::set_components),
+    )
+    .await;
+
+    let req = test::TestRequest::put()
+        .uri("/api/taxes/5/components")
+        .set_json(json!({
+            "components": [
+                { "taxId": 2 },
+                { "taxId": 3, "compound": true },
+            ],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body[0]["taxId"], 2);
+    assert_eq!(body[0]["compound"], false);
+    assert_eq!(body[1]["taxId"], 3);
+    assert_eq!(body[1]["sequence"], 2);
+    assert_eq!(body[1]["compound"], true);
+}
+
+#[actix_web::test]
+async fn test_group_itself() {
+    let mut state = MockAppState::new();
+    state.taxes.expect_set_components().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::taxes::set_components),
+    )
+    .await;
+
+    let req = test::TestRequest::put()
+        .uri("/api/taxes/5/components")
+        .set_json(json!({ "components": [{ "taxId": 2 }, { "taxId": 5 }] }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVALID_TAX_COMPONENTS");
+}
+
+#[actix_web::test]
+async fn test_duplicate_component() {
+    let mut state = MockAppState::new();
+    state.taxes.expect_set_components().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::taxes::set_components),
+    )
+    .await;
+
+    let req = test::TestRequest::put()
+        .uri("/api/taxes/5/components")
+        .set_json(json!({ "components": [{ "taxId": 2 }, { "taxId": 2, "compound": true }] }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+}
+
+#[actix_web::test]
+async fn test_nested_group() {
+    let mut state = MockAppState::new();
+    state
+        .taxes
+        .expect_set_components()
+        .once()
+        .returning(|_, _| Err(DbErr::Custom(ERR_NESTED_TAX_GROUP.to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::taxes::set_components),
+    )
+    .await;
+
+    let req = test::TestRequest::put()
+        .uri("/api/taxes/5/components")
+        .set_json(json!({ "components": [{ "taxId": 6 }] }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "NESTED_TAX_GROUP");
+}
+
+#[actix_web::test]
+async fn test_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .taxes
+        .expect_set_components()
+        .once()
+        .returning(|_, _| Err(DbErr::RecordNotFound("tax".to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::taxes::set_components),
+    )
+    .await;
+
+    let req = test::TestRequest::put()
+        .uri("/api/taxes/5/components")
+        .set_json(json!({ "components": [] }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+}
+
+#[actix_web::test]
+async fn test_connection_error() {
+    let mut state = MockAppState::new();
+    state
+        .taxes
+        .expect_set_components()
+        .once()
+        .returning(|_, _| {
+            Err(DbErr::Conn(RuntimeErr::Internal(
+                "mocked connection error".to_string(),
+            )))
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::taxes::set_components),
+    )
+    .await;
+
+    let req = test::TestRequest::put()
+        .uri("/api/taxes/5/components")
+        .set_json(json!({ "components": [{ "taxId": 2 }] }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
+}
@@ -32,14 +32,18 @@ async fn test_success() {
             predicate::eq("19%".to_string()),
             predicate::eq(0.19),
             predicate::eq(5),
+            predicate::eq("S".to_string()),
+            predicate::eq("".to_string()),
         )
-        .returning(|name, name_short, rate, account_id| {
+        .returning(|name, name_short, rate, account_id, category, note| {
             Ok(entity::taxes::Model {
                 id: 1,
                 name,
                 name_short,
                 rate,
                 account_id,
+                category,
+                note,
             })
         });
 
@@ -201,8 +205,10 @@ async fn test_connection_error() {
             predicate::eq("19%".to_string()),
             predicate::eq(0.19),
             predicate::eq(5),
+            predicate::eq("S".to_string()),
+            predicate::eq("".to_string()),
         )
-        .returning(|_, _, _, _| {
+        .returning(|_, _, _, _, _, _| {
             let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
             Err(err)
         });
@@ -22,6 +22,8 @@ async fn test_success() {
                 name_short: "19%".to_string(),
                 rate: 0.19,
                 account_id: 1,
+                category: "S".to_string(),
+                note: "".to_string(),
             }))
         });
 
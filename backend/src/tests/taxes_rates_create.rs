@@ -0,0 +1,152 @@
+use actix_web::{App, test, web};
+use mockall::predicate;
+use sea_orm::{DbErr, prelude::Date};
+use serde_json::json;
+
+use crate::{
+    handlers,
+    repositories::ERR_TAX_RATE_OVERLAP,
+    state::{AppState, MockAppState},
+    tests::fixtures::tax,
+};
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .taxes
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|id| Ok(Some(tax(id))));
+    state
+        .taxes
+        .expect_create_rate()
+        .once()
+        .with(
+            predicate::eq(5),
+            predicate::eq(0.16),
+            predicate::eq(Date::from_ymd_opt(2020, 7, 1).unwrap()),
+            predicate::eq(Some(Date::from_ymd_opt(2020, 12, 31).unwrap())),
+        )
+        .returning(|tax_id, rate, valid_from, valid_to| {
+            Ok(entity::tax_rates::Model {
+                id: 1,
+                tax_id,
+                rate,
+                valid_from,
+                valid_to,
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::taxes::create_rate),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/taxes/5/rates")
+        .set_json(json!({
+            "rate": 0.16,
+            "validFrom": "2020-07-01",
+            "validTo": "2020-12-31",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["taxId"], 5);
+    assert_eq!(body["validFrom"], "2020-07-01");
+    assert_eq!(body["validTo"], "2020-12-31");
+}
+
+#[actix_web::test]
+async fn test_overlap() {
+    let mut state = MockAppState::new();
+    state
+        .taxes
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(tax(id))));
+    state
+        .taxes
+        .expect_create_rate()
+        .once()
+        .returning(|_, _, _, _| Err(DbErr::Custom(ERR_TAX_RATE_OVERLAP.to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::taxes::create_rate),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/taxes/5/rates")
+        .set_json(json!({
+            "rate": 0.16,
+            "validFrom": "2020-07-01",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "TAX_RATE_OVERLAP");
+}
+
+#[actix_web::test]
+async fn test_invalid_date_range() {
+    let mut state = MockAppState::new();
+    state.taxes.expect_get().never();
+    state.taxes.expect_create_rate().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::taxes::create_rate),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/taxes/5/rates")
+        .set_json(json!({
+            "rate": 0.16,
+            "validFrom": "2020-12-31",
+            "validTo": "2020-07-01",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+}
+
+#[actix_web::test]
+async fn test_tax_not_found() {
+    let mut state = MockAppState::new();
+    state.taxes.expect_get().once().returning(|_| Ok(None));
+    state.taxes.expect_create_rate().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::taxes::create_rate),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/taxes/5/rates")
+        .set_json(json!({
+            "rate": 0.16,
+            "validFrom": "2020-07-01",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+}
@@ -0,0 +1,164 @@
+use crate::utils::totals::{
+    Discount, DocumentTotals, LineTotals, RateTotals, TotalsLine, allocate, taxable_nets,
+};
+
+fn line(quantity: f64, price: i32, components: Vec<(f64, bool)>) -> TotalsLine {
+    TotalsLine {
+        quantity,
+        price,
+        discount: Discount::default(),
+        components,
+    }
+}
+
+fn undiscounted(net: i64, tax: i64) -> LineTotals {
+    LineTotals {
+        amount: net,
+        discount: 0,
+        net,
+        document_discount: 0,
+        tax,
+        gross: net + tax,
+    }
+}
+
+#[test]
+fn test_document_totals() {
+    let totals = DocumentTotals::compute(
+        vec![
+            line(1.0, 1050, vec![(0.19, false)]),
+            line(1.0, 1050, vec![(0.19, false)]),
+            line(2.5, 1000, vec![(0.05, false), (0.07, true)]),
+        ],
+        Discount::default(),
+    );
+
+    assert_eq!(
+        totals.lines,
+        vec![
+            undiscounted(1050, 200),
+            undiscounted(1050, 200),
+            undiscounted(2500, 309),
+        ]
+    );
+    assert_eq!(
+        totals.rates,
+        vec![
+            RateTotals {
+                rate: 0.19,
+                base: 2100,
+                amount: 399,
+            },
+            RateTotals {
+                rate: 0.05,
+                base: 2500,
+                amount: 125,
+            },
+            RateTotals {
+                rate: 0.07,
+                base: 2625,
+                amount: 184,
+            },
+        ]
+    );
+    assert_eq!(totals.line_total, 4600);
+    assert_eq!(totals.discount, 0);
+    assert_eq!(totals.net, 4600);
+    assert_eq!(totals.tax, 708);
+    assert_eq!(totals.gross, 5308);
+}
+
+#[test]
+fn test_document_totals_discounts() {
+    let totals = DocumentTotals::compute(
+        vec![
+            TotalsLine {
+                discount: Discount::new(10.0, 0),
+                ..line(2.0, 5000, vec![(0.19, false)])
+            },
+            TotalsLine {
+                discount: Discount::new(0.0, 1000),
+                ..line(1.0, 4000, vec![(0.07, false)])
+            },
+        ],
+        Discount::new(10.0, 0),
+    );
+
+    assert_eq!(
+        totals.lines,
+        vec![
+            LineTotals {
+                amount: 10000,
+                discount: 1000,
+                net: 9000,
+                document_discount: 900,
+                tax: 1539,
+                gross: 9639,
+            },
+            LineTotals {
+                amount: 4000,
+                discount: 1000,
+                net: 3000,
+                document_discount: 300,
+                tax: 189,
+                gross: 2889,
+            },
+        ]
+    );
+    assert_eq!(
+        totals.rates,
+        vec![
+            RateTotals {
+                rate: 0.19,
+                base: 8100,
+                amount: 1539,
+            },
+            RateTotals {
+                rate: 0.07,
+                base: 2700,
+                amount: 189,
+            },
+        ]
+    );
+    assert_eq!(totals.line_total, 12000);
+    assert_eq!(totals.discount, 1200);
+    assert_eq!(totals.net, 10800);
+    assert_eq!(totals.tax, 1728);
+    assert_eq!(totals.gross, 12528);
+}
+
+#[test]
+fn test_document_totals_empty() {
+    let totals = DocumentTotals::compute(vec![], Discount::new(10.0, 500));
+    assert!(totals.lines.is_empty());
+    assert!(totals.rates.is_empty());
+    assert_eq!(totals.discount, 0);
+    assert_eq!(totals.gross, 0);
+}
+
+#[test]
+fn test_discount_of() {
+    assert_eq!(Discount::new(10.0, 500).of(10000), 1500);
+    assert_eq!(Discount::new(10.0, 500).apply(10000), 8500);
+    assert_eq!(Discount::new(0.0, 20000).of(10000), 10000);
+    assert_eq!(Discount::default().of(10000), 0);
+}
+
+#[test]
+fn test_allocate() {
+    assert_eq!(allocate(&[100, 100, 100], 100), vec![34, 33, 33]);
+    assert_eq!(allocate(&[1000, 3000], 100), vec![25, 75]);
+    assert_eq!(allocate(&[0, 0], 50), vec![0, 0]);
+}
+
+#[test]
+fn test_taxable_nets() {
+    assert_eq!(
+        taxable_nets(&[9000, 3000], Discount::new(10.0, 0)),
+        vec![8100, 2700]
+    );
+    assert_eq!(
+        taxable_nets(&[333, 667], Discount::new(0.0, 100)),
+        vec![300, 600]
+    );
+}
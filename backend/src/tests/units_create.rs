@@ -0,0 +1,174 @@
+use actix_web::{App, test, web};
+use mockall::predicate;
+use sea_orm::DbErr;
+use serde_json::json;
+
+use crate::{
+    handlers,
+    repositories::ERR_UNIT_EXISTS,
+    state::{AppState, MockAppState},
+};
+
+fn unit(id: i32, name: &str, base_unit_id: Option<i32>, factor: f64) -> entity::units::Model {
+    entity::units::Model {
+        id,
+        name: name.to_string(),
+        symbol: "pc".to_string(),
+        base_unit_id,
+        factor,
+    }
+}
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .units
+        .expect_get()
+        .once()
+        .with(predicate::eq(1))
+        .returning(|id| Ok(Some(unit(id, "Piece", None, 1.0))));
+    state
+        .units
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq("Box of 12".to_string()),
+            predicate::eq("box".to_string()),
+            predicate::eq(Some(1)),
+            predicate::eq(12.0),
+        )
+        .returning(|name, symbol, base_unit_id, factor| {
+            Ok(entity::units::Model {
+                symbol,
+                ..unit(2, &name, base_unit_id, factor)
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::units::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/units")
+        .set_json(json!({
+            "name": "Box of 12",
+            "symbol": "box",
+            "baseUnitId": 1,
+            "factor": 12.0,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["id"], 2);
+    assert_eq!(body["baseUnitId"], 1);
+    assert_eq!(body["factor"], 12.0);
+}
+
+#[actix_web::test]
+async fn test_base_unit() {
+    let mut state = MockAppState::new();
+    state.units.expect_get().never();
+    // A base unit is always its own factor.
+    state
+        .units
+        .expect_create()
+        .once()
+        .with(
+            predicate::eq("Kilogram".to_string()),
+            predicate::eq("kg".to_string()),
+            predicate::eq(None),
+            predicate::eq(1.0),
+        )
+        .returning(|name, _, base_unit_id, factor| Ok(unit(1, &name, base_unit_id, factor)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::units::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/units")
+        .set_json(json!({
+            "name": "Kilogram",
+            "symbol": "kg",
+            "factor": 5.0,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+}
+
+#[actix_web::test]
+async fn test_invalid_base_unit() {
+    let mut state = MockAppState::new();
+    state
+        .units
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(unit(id, "Box of 12", Some(1), 12.0))));
+    state.units.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::units::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/units")
+        .set_json(json!({
+            "name": "Pallet",
+            "symbol": "pal",
+            "baseUnitId": 2,
+            "factor": 40.0,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVALID_BASE_UNIT");
+}
+
+#[actix_web::test]
+async fn test_exists() {
+    let mut state = MockAppState::new();
+    state
+        .units
+        .expect_create()
+        .once()
+        .returning(|_, _, _, _| Err(DbErr::Custom(ERR_UNIT_EXISTS.to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::units::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/units")
+        .set_json(json!({
+            "name": "Piece",
+            "symbol": "pc",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "UNIT_EXISTS");
+}
@@ -0,0 +1,148 @@
+use entity::sea_orm_active_enums::CostingMethod;
+
+use crate::utils::valuation::{Consumption, OpenLayer, consume, settle};
+
+fn layers() -> Vec<OpenLayer> {
+    vec![
+        OpenLayer {
+            id: 1,
+            quantity: 10.0,
+            value: 1000,
+        },
+        OpenLayer {
+            id: 2,
+            quantity: 10.0,
+            value: 1300,
+        },
+    ]
+}
+
+#[test]
+fn test_fifo() {
+    let (consumptions, value) = consume(&layers(), 15.0, &CostingMethod::Fifo, 0.0);
+    assert_eq!(
+        consumptions,
+        vec![
+            Consumption {
+                layer_id: 1,
+                quantity: 10.0,
+                value: 1000,
+            },
+            Consumption {
+                layer_id: 2,
+                quantity: 5.0,
+                value: 650,
+            },
+        ]
+    );
+    assert_eq!(value, 1650);
+}
+
+#[test]
+fn test_average() {
+    let (consumptions, value) = consume(&layers(), 5.0, &CostingMethod::Average, 0.0);
+    assert_eq!(
+        consumptions,
+        vec![
+            Consumption {
+                layer_id: 1,
+                quantity: 2.5,
+                value: 250,
+            },
+            Consumption {
+                layer_id: 2,
+                quantity: 2.5,
+                value: 325,
+            },
+        ]
+    );
+    assert_eq!(value, 575);
+}
+
+#[test]
+fn test_average_rounds_once() {
+    let layers = vec![
+        OpenLayer {
+            id: 1,
+            quantity: 3.0,
+            value: 100,
+        },
+        OpenLayer {
+            id: 2,
+            quantity: 3.0,
+            value: 101,
+        },
+    ];
+    // A third of 201 is 67, which the layers share as 33 and 34.
+    let (consumptions, value) = consume(&layers, 2.0, &CostingMethod::Average, 0.0);
+    assert_eq!(value, 67);
+    assert_eq!(consumptions.iter().map(|c| c.value).sum::<i64>(), 67);
+}
+
+#[test]
+fn test_beyond_stock() {
+    let (consumptions, value) = consume(&layers(), 25.0, &CostingMethod::Average, 130.0);
+    assert_eq!(consumptions.len(), 2);
+    assert_eq!(value, 2300 + 650);
+}
+
+#[test]
+fn test_empty_stock() {
+    let (consumptions, value) = consume(&[], 2.0, &CostingMethod::Fifo, 1000.0);
+    assert!(consumptions.is_empty());
+    assert_eq!(value, 2000);
+}
+
+fn shortfalls() -> Vec<OpenLayer> {
+    vec![
+        OpenLayer {
+            id: 3,
+            quantity: -4.0,
+            value: -400,
+        },
+        OpenLayer {
+            id: 4,
+            quantity: -2.0,
+            value: -260,
+        },
+    ]
+}
+
+#[test]
+fn test_settle() {
+    let (settlements, value) = settle(&shortfalls(), 5.0, 600);
+    assert_eq!(
+        settlements,
+        vec![
+            Consumption {
+                layer_id: 3,
+                quantity: 4.0,
+                value: 400,
+            },
+            Consumption {
+                layer_id: 4,
+                quantity: 1.0,
+                value: 130,
+            },
+        ]
+    );
+    assert_eq!(value, 600);
+}
+
+#[test]
+fn test_settle_beyond_shortfalls() {
+    let (settlements, value) = settle(&shortfalls(), 10.0, 1200);
+    assert_eq!(
+        settlements.iter().map(|s| s.value).sum::<i64>(),
+        660,
+        "both shortfalls are settled at the cost they were issued at"
+    );
+    assert_eq!(value, 720);
+}
+
+#[test]
+fn test_settle_nothing_short() {
+    let (settlements, value) = settle(&[], 5.0, 600);
+    assert!(settlements.is_empty());
+    assert_eq!(value, 0);
+}
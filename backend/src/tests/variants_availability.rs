@@ -0,0 +1,90 @@
+use actix_web::{App, test, web};
+use mockall::predicate;
+
+use crate::{
+    handlers,
+    repositories::StockAvailability,
+    state::{AppState, MockAppState},
+};
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .variants
+        .expect_get()
+        .once()
+        .with(predicate::eq(8))
+        .returning(|id| {
+            Ok(Some(entity::variants::Model {
+                id,
+                name: "Mug".to_string(),
+                sale_price: 1200,
+                purchase_price: 1000,
+                product_id: 5,
+                sku: None,
+                gtin: None,
+                unit_id: None,
+            }))
+        });
+    state
+        .stock_reservations
+        .expect_availability()
+        .once()
+        .with(predicate::eq(vec![8]), predicate::eq(None))
+        .returning(|_, _| {
+            Ok(vec![
+                StockAvailability {
+                    stock_id: 1,
+                    variant_id: 8,
+                    on_hand: 10.0,
+                    reserved: 4.0,
+                },
+                StockAvailability {
+                    stock_id: 2,
+                    variant_id: 8,
+                    on_hand: 1.0,
+                    reserved: 3.0,
+                },
+            ])
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::variants::availability),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/variants/8/availability")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body[0]["available"], 6.0);
+    assert_eq!(body[1]["available"], -2.0);
+}
+
+#[actix_web::test]
+async fn test_not_found() {
+    let mut state = MockAppState::new();
+    state.variants.expect_get().once().returning(|_| Ok(None));
+    state.stock_reservations.expect_availability().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::variants::availability),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/variants/8/availability")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+}
@@ -1,13 +1,39 @@
 use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{CostingMethod, ProductType, Tracking};
 use mockall::predicate;
 use sea_orm::{DbErr, RuntimeErr};
 use serde_json::json;
 
 use crate::{
     handlers,
+    repositories::{ERR_VARIANT_CODE_EXISTS, VariantDraft},
     state::{AppState, MockAppState},
 };
 
+fn red_mug() -> VariantDraft {
+    VariantDraft {
+        name: "Red Mug".to_string(),
+        sale_price: 1200,
+        purchase_price: 1000,
+        sku: None,
+        gtin: None,
+        unit_id: None,
+    }
+}
+
+fn created(product_id: i32, variant: VariantDraft) -> entity::variants::Model {
+    entity::variants::Model {
+        id: 15,
+        name: variant.name,
+        sale_price: variant.sale_price,
+        purchase_price: variant.purchase_price,
+        product_id,
+        sku: variant.sku,
+        gtin: variant.gtin,
+        unit_id: variant.unit_id,
+    }
+}
+
 #[actix_web::test]
 async fn test_success() {
     let mut state = MockAppState::new();
@@ -20,6 +46,67 @@ async fn test_success() {
             Ok(Some(entity::products::Model {
                 id,
                 name: "Mug".to_string(),
+                tax_category: "standard".to_string(),
+                category_id: None,
+                product_type: ProductType::Stockable,
+                costing_method: CostingMethod::Fifo,
+                tracking: Tracking::None,
+            }))
+        });
+    state
+        .variants
+        .expect_create()
+        .once()
+        .with(predicate::eq(5), predicate::eq(red_mug()))
+        .returning(|product_id, variant| Ok(created(product_id, variant)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::variants::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/variants")
+        .set_json(json!({
+            "name": "Red Mug",
+            "salePrice": 1200,
+            "purchasePrice": 1000,
+            "productId": 5,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+}
+
+#[actix_web::test]
+async fn test_codes_and_unit() {
+    let mut state = MockAppState::new();
+    state.products.expect_get().once().returning(|id| {
+        Ok(Some(entity::products::Model {
+            id,
+            name: "Mug".to_string(),
+            tax_category: "standard".to_string(),
+            category_id: None,
+            product_type: ProductType::Stockable,
+            costing_method: CostingMethod::Fifo,
+            tracking: Tracking::None,
+        }))
+    });
+    state
+        .units
+        .expect_get()
+        .once()
+        .with(predicate::eq(1))
+        .returning(|id| {
+            Ok(Some(entity::units::Model {
+                id,
+                name: "Piece".to_string(),
+                symbol: "pc".to_string(),
+                base_unit_id: None,
+                factor: 1.0,
             }))
         });
     state
@@ -28,19 +115,14 @@ async fn test_success() {
         .once()
         .with(
             predicate::eq(5),
-            predicate::eq("Red Mug".to_string()),
-            predicate::eq(1200),
-            predicate::eq(1000),
+            predicate::eq(VariantDraft {
+                sku: Some("MUG-RED".to_string()),
+                gtin: Some("4006381333931".to_string()),
+                unit_id: Some(1),
+                ..red_mug()
+            }),
         )
-        .returning(|product_id, name, sale_price, purchase_price| {
-            Ok(entity::variants::Model {
-                id: 15,
-                name,
-                sale_price,
-                purchase_price,
-                product_id,
-            })
-        });
+        .returning(|product_id, variant| Ok(created(product_id, variant)));
 
     let app = test::init_service(
         App::new()
@@ -56,11 +138,95 @@ async fn test_success() {
             "salePrice": 1200,
             "purchasePrice": 1000,
             "productId": 5,
+            "sku": "MUG-RED ",
+            "gtin": "400638 133393-1",
+            "unitId": 1,
         }))
         .to_request();
 
     let res = test::call_service(&app, req).await;
     assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["sku"], "MUG-RED");
+    assert_eq!(body["gtin"], "4006381333931");
+    assert_eq!(body["unitId"], 1);
+}
+
+#[actix_web::test]
+async fn test_invalid_gtin() {
+    let mut state = MockAppState::new();
+    state.products.expect_get().never();
+    state.variants.expect_create().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::variants::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/variants")
+        .set_json(json!({
+            "name": "Red Mug",
+            "salePrice": 1200,
+            "purchasePrice": 1000,
+            "productId": 5,
+            "gtin": "4006381333932",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVALID_GTIN");
+}
+
+#[actix_web::test]
+async fn test_code_exists() {
+    let mut state = MockAppState::new();
+    state.products.expect_get().once().returning(|id| {
+        Ok(Some(entity::products::Model {
+            id,
+            name: "Mug".to_string(),
+            tax_category: "standard".to_string(),
+            category_id: None,
+            product_type: ProductType::Stockable,
+            costing_method: CostingMethod::Fifo,
+            tracking: Tracking::None,
+        }))
+    });
+    state
+        .variants
+        .expect_create()
+        .once()
+        .returning(|_, _| Err(DbErr::Custom(ERR_VARIANT_CODE_EXISTS.to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::variants::create),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/variants")
+        .set_json(json!({
+            "name": "Red Mug",
+            "salePrice": 1200,
+            "purchasePrice": 1000,
+            "productId": 5,
+            "sku": "MUG-RED",
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 409u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "VARIANT_CODE_EXISTS");
 }
 
 #[actix_web::test]
@@ -161,19 +327,19 @@ async fn test_connection_error1() {
             Ok(Some(entity::products::Model {
                 id,
                 name: "Mug".to_string(),
+                tax_category: "standard".to_string(),
+                category_id: None,
+                product_type: ProductType::Stockable,
+                costing_method: CostingMethod::Fifo,
+                tracking: Tracking::None,
             }))
         });
     state
         .variants
         .expect_create()
         .once()
-        .with(
-            predicate::eq(5),
-            predicate::eq("Red Mug".to_string()),
-            predicate::eq(1200),
-            predicate::eq(1000),
-        )
-        .returning(|_, _, _, _| {
+        .with(predicate::eq(5), predicate::eq(red_mug()))
+        .returning(|_, _| {
             let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
             Err(err)
         });
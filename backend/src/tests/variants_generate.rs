@@ -0,0 +1,290 @@
+use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{CostingMethod, ProductType, Tracking};
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr};
+use serde_json::json;
+
+use crate::{
+    handlers,
+    repositories::GeneratedVariant,
+    state::{AppState, MockAppState},
+    utils::variants::Attribute,
+};
+
+fn product(id: i32) -> entity::products::Model {
+    entity::products::Model {
+        id,
+        name: "T-Shirt".to_string(),
+        tax_category: "standard".to_string(),
+        category_id: None,
+        product_type: ProductType::Stockable,
+        costing_method: CostingMethod::Fifo,
+        tracking: Tracking::None,
+    }
+}
+
+fn attribute(id: i32, name: &str, values: &[(i32, &str)]) -> Attribute {
+    let attribute = entity::product_attributes::Model {
+        id,
+        product_id: 5,
+        name: name.to_string(),
+        position: id,
+    };
+    let values = values
+        .iter()
+        .enumerate()
+        .map(
+            |(index, (value_id, value))| entity::product_attribute_values::Model {
+                id: *value_id,
+                attribute_id: id,
+                name: value.to_string(),
+                position: index as i32 + 1,
+            },
+        )
+        .collect();
+    (attribute, values)
+}
+
+fn attributes() -> Vec<Attribute> {
+    vec![
+        attribute(1, "Size", &[(11, "M"), (12, "XXL")]),
+        attribute(2, "Color", &[(21, "Red"), (22, "Blue")]),
+    ]
+}
+
+fn generated(name: &str, sale_price: i32, value_ids: [i32; 2]) -> GeneratedVariant {
+    GeneratedVariant {
+        name: name.to_string(),
+        sale_price,
+        purchase_price: 800,
+        value_ids: value_ids.to_vec(),
+    }
+}
+
+fn created(product_id: i32, variants: Vec<GeneratedVariant>) -> Vec<entity::variants::Model> {
+    variants
+        .into_iter()
+        .enumerate()
+        .map(|(index, variant)| entity::variants::Model {
+            id: index as i32 + 1,
+            name: variant.name,
+            sale_price: variant.sale_price,
+            purchase_price: variant.purchase_price,
+            product_id,
+            sku: None,
+            gtin: None,
+            unit_id: None,
+        })
+        .collect()
+}
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .products
+        .expect_get()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|id| Ok(Some(product(id))));
+    state
+        .product_attributes
+        .expect_list_for_product()
+        .once()
+        .with(predicate::eq(5))
+        .returning(|_| Ok(attributes()));
+    state
+        .variants
+        .expect_generate()
+        .once()
+        .with(
+            predicate::eq(5),
+            predicate::eq(vec![
+                generated("T-Shirt M / Red", 1900, [11, 21]),
+                generated("T-Shirt M / Blue", 1900, [11, 22]),
+                generated("T-Shirt XXL / Red", 2200, [12, 21]),
+                generated("T-Shirt XXL / Blue", 2200, [12, 22]),
+            ]),
+        )
+        .returning(|product_id, variants| Ok(created(product_id, variants)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::variants::generate),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/products/5/variants/generate")
+        .set_json(json!({
+            "salePrice": 1900,
+            "purchasePrice": 800,
+            "overrides": [
+                { "valueIds": [21, 12], "salePrice": 2200 },
+                { "valueIds": [12, 22], "salePrice": 2200 },
+            ],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body.as_array().unwrap().len(), 4);
+    assert_eq!(body[2]["name"], "T-Shirt XXL / Red");
+    assert_eq!(body[2]["salePrice"], 2200);
+    assert_eq!(body[2]["productId"], 5);
+}
+
+#[actix_web::test]
+async fn test_name_template() {
+    let mut state = MockAppState::new();
+    state
+        .products
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(product(id))));
+    state
+        .product_attributes
+        .expect_list_for_product()
+        .once()
+        .returning(|_| Ok(attributes()));
+    state
+        .variants
+        .expect_generate()
+        .once()
+        .with(
+            predicate::eq(5),
+            predicate::eq(vec![
+                generated("T-Shirt Red, size M", 1900, [11, 21]),
+                generated("T-Shirt Blue, size M", 1900, [11, 22]),
+                generated("T-Shirt Red, size XXL", 1900, [12, 21]),
+                generated("T-Shirt Blue, size XXL", 1900, [12, 22]),
+            ]),
+        )
+        .returning(|product_id, variants| Ok(created(product_id, variants)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::variants::generate),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/products/5/variants/generate")
+        .set_json(json!({
+            "nameTemplate": "{product} {Color}, size {Size}",
+            "salePrice": 1900,
+            "purchasePrice": 800,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+}
+
+#[actix_web::test]
+async fn test_invalid_override() {
+    let mut state = MockAppState::new();
+    state
+        .products
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(product(id))));
+    state
+        .product_attributes
+        .expect_list_for_product()
+        .once()
+        .returning(|_| Ok(attributes()));
+    state.variants.expect_generate().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::variants::generate),
+    )
+    .await;
+
+    // Both values are sizes.
+    let req = test::TestRequest::post()
+        .uri("/api/products/5/variants/generate")
+        .set_json(json!({
+            "salePrice": 1900,
+            "purchasePrice": 800,
+            "overrides": [{ "valueIds": [11, 12], "salePrice": 2200 }],
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 400u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "INVALID_VARIANT_OVERRIDE");
+}
+
+#[actix_web::test]
+async fn test_product_not_found() {
+    let mut state = MockAppState::new();
+    state.products.expect_get().once().returning(|_| Ok(None));
+    state.variants.expect_generate().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::variants::generate),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/products/5/variants/generate")
+        .set_json(json!({
+            "salePrice": 1900,
+            "purchasePrice": 800,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "PRODUCT_NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn test_connection_error() {
+    let mut state = MockAppState::new();
+    state
+        .products
+        .expect_get()
+        .once()
+        .returning(|id| Ok(Some(product(id))));
+    state
+        .product_attributes
+        .expect_list_for_product()
+        .once()
+        .returning(|_| Ok(attributes()));
+    state.variants.expect_generate().once().returning(|_, _| {
+        let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+        Err(err)
+    });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::variants::generate),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/products/5/variants/generate")
+        .set_json(json!({
+            "salePrice": 1900,
+            "purchasePrice": 800,
+        }))
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
+}
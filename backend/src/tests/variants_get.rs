@@ -22,6 +22,9 @@ async fn test_success() {
                 sale_price: 1200,
                 purchase_price: 1000,
                 product_id: 16,
+                sku: None,
+                gtin: None,
+                unit_id: None,
             }))
         });
 
@@ -0,0 +1,98 @@
+use actix_web::{App, test, web};
+use mockall::predicate;
+use sea_orm::{DbErr, RuntimeErr};
+
+use crate::{
+    handlers,
+    state::{AppState, MockAppState},
+};
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .variants
+        .expect_find_by_code()
+        .once()
+        .with(predicate::eq("4006381333931".to_string()))
+        .returning(|code| {
+            Ok(Some(entity::variants::Model {
+                id: 15,
+                name: "Red Mug".to_string(),
+                sale_price: 1200,
+                purchase_price: 1000,
+                product_id: 5,
+                sku: Some("MUG-RED".to_string()),
+                gtin: Some(code),
+                unit_id: None,
+            }))
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::variants::get_by_code),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/variants/by-code/4006381333931")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["id"], 15);
+    assert_eq!(body["sku"], "MUG-RED");
+}
+
+#[actix_web::test]
+async fn test_not_found() {
+    let mut state = MockAppState::new();
+    state
+        .variants
+        .expect_find_by_code()
+        .once()
+        .returning(|_| Ok(None));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::variants::get_by_code),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/variants/by-code/MUG-BLUE")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "VARIANT_NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn test_connection_error() {
+    let mut state = MockAppState::new();
+    state.variants.expect_find_by_code().once().returning(|_| {
+        let err = DbErr::Conn(RuntimeErr::Internal("mocked connection error".to_string()));
+        Err(err)
+    });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::variants::get_by_code),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/variants/by-code/MUG-RED")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 500u16);
+}
@@ -1,4 +1,5 @@
 use actix_web::{App, test, web};
+use entity::sea_orm_active_enums::{CostingMethod, ProductType, Tracking};
 use mockall::predicate;
 use sea_orm::{DbErr, RuntimeErr};
 
@@ -20,6 +21,11 @@ async fn test_success() {
             Ok(Some(entity::products::Model {
                 id,
                 name: "Product".to_string(),
+                tax_category: "standard".to_string(),
+                category_id: None,
+                product_type: ProductType::Stockable,
+                costing_method: CostingMethod::Fifo,
+                tracking: Tracking::None,
             }))
         });
     state
@@ -35,6 +41,9 @@ async fn test_success() {
                     sale_price: 1337,
                     purchase_price: 1337,
                     product_id,
+                    sku: None,
+                    gtin: None,
+                    unit_id: None,
                 },
                 entity::variants::Model {
                     id: 102,
@@ -42,6 +51,9 @@ async fn test_success() {
                     sale_price: 1337,
                     purchase_price: 1337,
                     product_id,
+                    sku: None,
+                    gtin: None,
+                    unit_id: None,
                 },
                 entity::variants::Model {
                     id: 103,
@@ -49,6 +61,9 @@ async fn test_success() {
                     sale_price: 1337,
                     purchase_price: 1337,
                     product_id,
+                    sku: None,
+                    gtin: None,
+                    unit_id: None,
                 },
             ];
 
@@ -113,6 +128,11 @@ async fn test_connection_error() {
             Ok(Some(entity::products::Model {
                 id,
                 name: "Product".to_string(),
+                tax_category: "standard".to_string(),
+                category_id: None,
+                product_type: ProductType::Stockable,
+                costing_method: CostingMethod::Fifo,
+                tracking: Tracking::None,
             }))
         });
     state
@@ -0,0 +1,94 @@
+use actix_web::{App, test, web};
+use mockall::predicate;
+use sea_orm::prelude::Date;
+
+use crate::{
+    handlers,
+    state::{AppState, MockAppState},
+};
+
+#[actix_web::test]
+async fn test_success() {
+    let mut state = MockAppState::new();
+    state
+        .variants
+        .expect_get()
+        .once()
+        .with(predicate::eq(8))
+        .returning(|id| {
+            Ok(Some(entity::variants::Model {
+                id,
+                name: "Aspirin".to_string(),
+                sale_price: 500,
+                purchase_price: 300,
+                product_id: 5,
+                sku: None,
+                gtin: None,
+                unit_id: None,
+            }))
+        });
+    state
+        .lots
+        .expect_list()
+        .once()
+        .with(predicate::eq(8))
+        .returning(|variant_id| {
+            Ok(vec![
+                entity::lots::Model {
+                    id: 1,
+                    variant_id,
+                    number: "B-100".to_string(),
+                    expiry_date: Date::from_ymd_opt(2027, 3, 31),
+                },
+                entity::lots::Model {
+                    id: 2,
+                    variant_id,
+                    number: "B-101".to_string(),
+                    expiry_date: None,
+                },
+            ])
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::variants::lots),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/variants/8/lots")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body[0]["number"], "B-100");
+    assert_eq!(body[0]["expiryDate"], "2027-03-31");
+    assert_eq!(body[1]["expiryDate"], serde_json::Value::Null);
+}
+
+#[actix_web::test]
+async fn test_not_found() {
+    let mut state = MockAppState::new();
+    state.variants.expect_get().once().returning(|_| Ok(None));
+    state.lots.expect_list().never();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from(state)))
+            .service(handlers::variants::lots),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/variants/8/lots")
+        .to_request();
+
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 404u16);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errorCode"], "VARIANT_NOT_FOUND");
+}
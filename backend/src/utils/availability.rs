@@ -0,0 +1,47 @@
+use std::collections::HashMap;
+
+use crate::repositories::{QUANTITY_TOLERANCE, StockAvailability};
+
+/// A variant an order asks for more of than is available.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shortage {
+    pub variant_id: i32,
+    pub requested: f64,
+    pub available: f64,
+}
+
+/// Compares the quantities requested per variant, summed up over the lines
+/// that ask for the same variant, with what is available of it. Variants
+/// that aren't available at all count as none available. The shortages are
+/// in the order the variants are first requested in.
+pub fn shortages(requested: &[(i32, f64)], available: &HashMap<i32, f64>) -> Vec<Shortage> {
+    let mut totals: Vec<(i32, f64)> = Vec::new();
+    for (variant_id, quantity) in requested {
+        match totals.iter_mut().find(|(id, _)| id == variant_id) {
+            Some((_, total)) => *total += quantity,
+            None => totals.push((*variant_id, *quantity)),
+        }
+    }
+
+    totals
+        .into_iter()
+        .filter_map(|(variant_id, requested)| {
+            let available = available.get(&variant_id).copied().unwrap_or(0.0);
+            (requested > available + QUANTITY_TOLERANCE).then_some(Shortage {
+                variant_id,
+                requested,
+                available,
+            })
+        })
+        .collect()
+}
+
+/// Adds up what is available of each variant over the stocks, e.g. the
+/// bins of the warehouse an order is delivered from.
+pub fn available_by_variant(availability: &[StockAvailability]) -> HashMap<i32, f64> {
+    let mut available: HashMap<i32, f64> = HashMap::new();
+    for availability in availability {
+        *available.entry(availability.variant_id).or_default() += availability.available();
+    }
+    available
+}
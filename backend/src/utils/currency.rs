@@ -0,0 +1,10 @@
+/// Brings a currency code into the stored form, e.g. ` eur` becomes `EUR`.
+/// Returns `None` unless it is three letters like an ISO 4217 code.
+pub fn normalize_currency(code: &str) -> Option<String> {
+    let code = code.trim().to_ascii_uppercase();
+    if code.len() == 3 && code.chars().all(|c| c.is_ascii_uppercase()) {
+        Some(code)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,26 @@
+/// Brings a GTIN (EAN, UPC) into the stored form with spaces and dashes
+/// removed. Returns `None` unless it has 8, 12, 13 or 14 digits and a
+/// matching check digit.
+pub fn normalize_gtin(gtin: &str) -> Option<String> {
+    let gtin: String = gtin.chars().filter(|c| *c != ' ' && *c != '-').collect();
+    if ![8, 12, 13, 14].contains(&gtin.len()) || !gtin.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let digits: Vec<u32> = gtin.chars().filter_map(|c| c.to_digit(10)).collect();
+    let (check, payload) = digits.split_last()?;
+    // Weighted 3 and 1 alternately from the right, so all lengths share
+    // the same rule.
+    let sum: u32 = payload
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(index, digit)| if index % 2 == 0 { digit * 3 } else { *digit })
+        .sum();
+
+    if (10 - sum % 10) % 10 == *check {
+        Some(gtin)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,43 @@
+use crate::repositories::QUANTITY_TOLERANCE;
+
+/// How much more of a line's variant was counted than expected, negative if
+/// less was found.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CountDifference {
+    pub line_id: i32,
+    pub variant_id: i32,
+    /// The lot the difference belongs to, empty for untracked variants.
+    pub lot_number: String,
+    pub difference: f64,
+}
+
+/// Adds up what the counters found of the line's variant. Each counter
+/// counts a part of the location, so their counts add up. Returns `None` if
+/// nobody has counted the variant yet.
+pub fn counted(line_id: i32, entries: &[entity::inventory_count_entries::Model]) -> Option<f64> {
+    entries
+        .iter()
+        .filter(|entry| entry.line_id == line_id)
+        .map(|entry| entry.quantity)
+        .reduce(|total, quantity| total + quantity)
+}
+
+/// Compares what was counted of each line with what was expected. Lines
+/// nobody has counted and lines that match are left out.
+pub fn differences(
+    lines: &[entity::inventory_count_lines::Model],
+    entries: &[entity::inventory_count_entries::Model],
+) -> Vec<CountDifference> {
+    lines
+        .iter()
+        .filter_map(|line| {
+            let difference = counted(line.id, entries)? - line.expected_quantity;
+            (difference.abs() > QUANTITY_TOLERANCE).then_some(CountDifference {
+                line_id: line.id,
+                variant_id: line.variant_id,
+                lot_number: line.lot_number.clone(),
+                difference,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,17 @@
+/// Collects the location and every location below it, the location first.
+pub fn subtree(stocks: &[entity::stocks::Model], stock_id: i32) -> Vec<i32> {
+    let mut ids = vec![stock_id];
+    // A location can only be placed below one that exists already, so the
+    // tree has no cycles and the walk ends.
+    let mut next = 0;
+    while let Some(&parent_id) = ids.get(next) {
+        ids.extend(
+            stocks
+                .iter()
+                .filter(|stock| stock.parent_id == Some(parent_id))
+                .map(|stock| stock.id),
+        );
+        next += 1;
+    }
+    ids
+}
@@ -0,0 +1,48 @@
+use std::collections::HashSet;
+
+use entity::sea_orm_active_enums::Tracking;
+
+use crate::repositories::{LotDraft, QUANTITY_TOLERANCE};
+
+/// Why the lots of a move don't fit the tracking of its variant.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LotProblem {
+    /// Lots were given for a variant that isn't tracked.
+    NotTracked,
+    /// A tracked variant was moved without naming its lots.
+    Missing,
+    /// The same number was given twice.
+    Duplicate,
+    /// A serial number was moved with a quantity other than one.
+    SerialQuantity,
+    /// The quantities of the lots don't add up to the move's.
+    QuantityMismatch,
+}
+
+/// Checks the lots of a move of the quantity against the tracking of the
+/// variant. Untracked variants are moved without lots, tracked ones must be
+/// split over lots adding up to the whole quantity, and every serial number
+/// stands for a single piece.
+pub fn check_lots(tracking: &Tracking, quantity: f64, lots: &[LotDraft]) -> Option<LotProblem> {
+    match (tracking, lots.is_empty()) {
+        (Tracking::None, true) => return None,
+        (Tracking::None, false) => return Some(LotProblem::NotTracked),
+        (_, true) => return Some(LotProblem::Missing),
+        (_, false) => {}
+    }
+
+    let mut numbers = HashSet::new();
+    if !lots.iter().all(|lot| numbers.insert(lot.number.as_str())) {
+        return Some(LotProblem::Duplicate);
+    }
+    if *tracking == Tracking::Serial
+        && lots
+            .iter()
+            .any(|lot| (lot.quantity - 1.0).abs() > QUANTITY_TOLERANCE)
+    {
+        return Some(LotProblem::SerialQuantity);
+    }
+
+    let total: f64 = lots.iter().map(|lot| lot.quantity).sum();
+    ((total - quantity).abs() > QUANTITY_TOLERANCE).then_some(LotProblem::QuantityMismatch)
+}
@@ -1,2 +1,19 @@
+pub mod availability;
+pub mod currency;
+pub mod gtin;
+pub mod inventory_counts;
+pub mod locations;
+pub mod lots;
 pub mod name;
+pub mod number_pattern;
 pub mod password;
+pub mod price_lists;
+pub mod product_categories;
+pub mod replenishment;
+pub mod tax_groups;
+pub mod tax_rules;
+pub mod totals;
+pub mod units;
+pub mod valuation;
+pub mod variants;
+pub mod vat_id;
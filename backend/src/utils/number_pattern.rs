@@ -0,0 +1,87 @@
+use chrono::{Datelike, NaiveDate};
+
+/// One part of a number pattern like `INV-{YYYY}-{00000}`.
+enum Token<'a> {
+    Text(&'a str),
+    Year,
+    ShortYear,
+    Month,
+    /// The running number, padded with zeros to the given width.
+    Counter(usize),
+}
+
+fn tokens(pattern: &str) -> Option<Vec<Token<'_>>> {
+    let mut tokens = Vec::new();
+    let mut rest = pattern;
+    while let Some(start) = rest.find('{') {
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+        }
+        let end = start + rest[start..].find('}')?;
+        let token = match &rest[start + 1..end] {
+            "YYYY" => Token::Year,
+            "YY" => Token::ShortYear,
+            "MM" => Token::Month,
+            zeros if !zeros.is_empty() && zeros.chars().all(|c| c == '0') => {
+                Token::Counter(zeros.len())
+            }
+            _ => return None,
+        };
+        tokens.push(token);
+        rest = &rest[end + 1..];
+    }
+    if rest.contains('}') {
+        return None;
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest));
+    }
+    Some(tokens)
+}
+
+/// Checks that the pattern only uses known placeholders and contains exactly
+/// one counter, e.g. `{00000}`.
+pub fn valid_pattern(pattern: &str) -> bool {
+    match tokens(pattern) {
+        Some(tokens) => {
+            let counters = tokens
+                .iter()
+                .filter(|token| matches!(token, Token::Counter(_)))
+                .count();
+            counters == 1 && pattern.len() <= 64
+        }
+        None => false,
+    }
+}
+
+/// Returns the period the counter belongs to, e.g. `2025` for a pattern with
+/// `{YYYY}` and `2025-07` for one with `{MM}`. The counter starts over
+/// whenever the period changes. Patterns without date have a single period.
+pub fn period(pattern: &str, date: NaiveDate) -> String {
+    let tokens = tokens(pattern).unwrap_or_default();
+    let year = tokens
+        .iter()
+        .any(|token| matches!(token, Token::Year | Token::ShortYear));
+    let month = tokens.iter().any(|token| matches!(token, Token::Month));
+
+    match (year, month) {
+        (_, true) => format!("{:04}-{:02}", date.year(), date.month()),
+        (true, false) => format!("{:04}", date.year()),
+        (false, false) => String::new(),
+    }
+}
+
+/// Renders the document number for the date and running number.
+pub fn format_number(pattern: &str, date: NaiveDate, number: i32) -> String {
+    let mut result = String::new();
+    for token in tokens(pattern).unwrap_or_default() {
+        match token {
+            Token::Text(text) => result.push_str(text),
+            Token::Year => result.push_str(&format!("{:04}", date.year())),
+            Token::ShortYear => result.push_str(&format!("{:02}", date.year() % 100)),
+            Token::Month => result.push_str(&format!("{:02}", date.month())),
+            Token::Counter(width) => result.push_str(&format!("{number:0width$}")),
+        }
+    }
+    result
+}
@@ -0,0 +1,30 @@
+use chrono::NaiveDate;
+
+/// Picks the price of a line from the items of the customer's price lists.
+/// A list applies if it is in the customer's currency and valid on the
+/// document date, an item if the line's quantity reaches its minimum.
+/// Of the lists with a price for the quantity, the one starting last wins,
+/// so a list for a season beats the yearly one it overlaps; within it the
+/// highest quantity break does.
+pub fn select_price(
+    items: &[(entity::price_lists::Model, entity::price_list_items::Model)],
+    currency: &str,
+    date: NaiveDate,
+    quantity: f64,
+) -> Option<i32> {
+    items
+        .iter()
+        .filter(|(list, item)| {
+            list.currency == currency
+                && list.valid_from.is_none_or(|from| from <= date)
+                && list.valid_to.is_none_or(|to| to >= date)
+                && item.min_quantity <= quantity
+        })
+        .max_by(|(a, a_item), (b, b_item)| {
+            a.valid_from
+                .cmp(&b.valid_from)
+                .then(a.id.cmp(&b.id))
+                .then(a_item.min_quantity.total_cmp(&b_item.min_quantity))
+        })
+        .map(|(_, item)| item.price)
+}
@@ -0,0 +1,41 @@
+/// The ledger accounts and tax a category passes on to its products.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CategoryDefaults {
+    pub income_account_id: Option<i32>,
+    pub expense_account_id: Option<i32>,
+    pub inventory_account_id: Option<i32>,
+    pub tax_id: Option<i32>,
+    pub sale_discount_account_id: Option<i32>,
+    pub purchase_discount_account_id: Option<i32>,
+}
+
+/// Collects the defaults of a category. Whatever the category leaves unset
+/// is taken from its nearest ancestor that sets it.
+pub fn category_defaults(
+    categories: &[entity::product_categories::Model],
+    category_id: i32,
+) -> CategoryDefaults {
+    let mut defaults = CategoryDefaults::default();
+    let mut next = Some(category_id);
+    // A category can only get a parent that exists already, so the chain
+    // ends after at most one step per category.
+    for _ in 0..categories.len() {
+        let Some(category) = next.and_then(|id| categories.iter().find(|c| c.id == id)) else {
+            break;
+        };
+        defaults.income_account_id = defaults.income_account_id.or(category.income_account_id);
+        defaults.expense_account_id = defaults.expense_account_id.or(category.expense_account_id);
+        defaults.inventory_account_id = defaults
+            .inventory_account_id
+            .or(category.inventory_account_id);
+        defaults.tax_id = defaults.tax_id.or(category.tax_id);
+        defaults.sale_discount_account_id = defaults
+            .sale_discount_account_id
+            .or(category.sale_discount_account_id);
+        defaults.purchase_discount_account_id = defaults
+            .purchase_discount_account_id
+            .or(category.purchase_discount_account_id);
+        next = category.parent_id;
+    }
+    defaults
+}
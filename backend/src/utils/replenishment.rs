@@ -0,0 +1,18 @@
+use crate::repositories::QUANTITY_TOLERANCE;
+
+/// How much of a variant to order to bring what is expected in a stock back
+/// up to the rule's maximum. Nothing is ordered until it falls below the
+/// minimum.
+pub fn shortfall(forecast: f64, min_quantity: f64, max_quantity: f64) -> Option<f64> {
+    (forecast < min_quantity - QUANTITY_TOLERANCE).then_some(max_quantity - forecast)
+}
+
+/// Rounds the quantity up to what the supplier sells: at least the minimum
+/// order quantity, in whole packs.
+pub fn order_quantity(quantity: f64, min_quantity: f64, pack_size: f64) -> f64 {
+    let quantity = quantity.max(min_quantity);
+    if pack_size <= QUANTITY_TOLERANCE {
+        return quantity;
+    }
+    (quantity / pack_size - QUANTITY_TOLERANCE).ceil() * pack_size
+}
@@ -0,0 +1,19 @@
+/// Splits a line's net amount into the bases its tax components are levied
+/// on. The components are given as rate and compound flag in the order they
+/// are levied; a compound component is levied on the net amount plus the tax
+/// of the components before it. Tax is only rounded per document, so the
+/// bases grow by the unrounded rates.
+pub fn component_bases(net: i64, components: &[(f64, bool)]) -> Vec<i64> {
+    let mut rate_before = 0.0;
+    components
+        .iter()
+        .map(|(rate, compound)| {
+            let factor = match compound {
+                true => 1.0 + rate_before,
+                false => 1.0,
+            };
+            rate_before += factor * rate;
+            (net as f64 * factor).round() as i64
+        })
+        .collect()
+}
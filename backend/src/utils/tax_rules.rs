@@ -0,0 +1,52 @@
+use entity::sea_orm_active_enums::TaxRegion;
+
+/// ISO 3166-1 alpha-2 codes of the EU member states.
+const EU_COUNTRIES: [&str; 27] = [
+    "AT", "BE", "BG", "CY", "CZ", "DE", "DK", "EE", "ES", "FI", "FR", "GR", "HR", "HU", "IE", "IT",
+    "LT", "LU", "LV", "MT", "NL", "PL", "PT", "RO", "SE", "SI", "SK",
+];
+
+/// Where a contact is seen from the company. Contacts without a country are
+/// treated as domestic, like addresses written without one.
+pub fn tax_region(company_country: &str, contact_country: &str) -> TaxRegion {
+    let contact_country = contact_country.trim().to_ascii_uppercase();
+    if contact_country.is_empty() || contact_country == company_country.trim().to_ascii_uppercase()
+    {
+        TaxRegion::Domestic
+    } else if EU_COUNTRIES.contains(&contact_country.as_str()) {
+        TaxRegion::Eu
+    } else {
+        TaxRegion::NonEu
+    }
+}
+
+/// Whether the code is written as an ISO 3166-1 alpha-2 country code, e.g.
+/// `DE`. Regions are told apart by comparing such codes.
+pub fn is_country_code(code: &str) -> bool {
+    code.len() == 2 && code.bytes().all(|byte| byte.is_ascii_uppercase())
+}
+
+/// Picks the rule for a line. A rule matches if its region is the contact's
+/// and its VAT ID and product category conditions are either unset or met.
+/// The most specific match wins, a product category weighing more than the
+/// VAT ID; among equally specific rules the oldest one is taken.
+pub fn select_tax_rule<'a>(
+    rules: &'a [entity::tax_rules::Model],
+    region: &TaxRegion,
+    has_vat_id: bool,
+    product_tax_category: Option<&str>,
+) -> Option<&'a entity::tax_rules::Model> {
+    rules
+        .iter()
+        .filter(|rule| rule.region == *region)
+        .filter(|rule| rule.vat_id.is_none_or(|vat_id| vat_id == has_vat_id))
+        .filter(|rule| match &rule.product_tax_category {
+            Some(category) => product_tax_category == Some(category.as_str()),
+            None => true,
+        })
+        .min_by_key(|rule| {
+            let specificity =
+                2 * rule.product_tax_category.is_some() as u8 + rule.vat_id.is_some() as u8;
+            (std::cmp::Reverse(specificity), rule.id)
+        })
+}
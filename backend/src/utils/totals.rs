@@ -0,0 +1,217 @@
+use crate::{repositories::ResolvedTax, utils::tax_groups::component_bases};
+
+/// Net amount of a line in cents.
+pub fn line_net(quantity: f64, price: i32) -> i64 {
+    (quantity * price as f64).round() as i64
+}
+
+/// A discount given as a percentage and an absolute amount in cents. The
+/// percentage is taken off first and the amount from what is left.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Discount {
+    pub percent: f64,
+    pub amount: i64,
+}
+
+impl Discount {
+    pub fn new(percent: f64, amount: i32) -> Self {
+        Self {
+            percent,
+            amount: amount as i64,
+        }
+    }
+
+    /// The part of the amount that is taken off, which is never more than
+    /// the amount itself.
+    pub fn of(&self, amount: i64) -> i64 {
+        let off = (amount as f64 * self.percent / 100.0).round() as i64 + self.amount;
+        off.clamp(0, amount.max(0))
+    }
+
+    /// What is left of the amount after the discount.
+    pub fn apply(&self, amount: i64) -> i64 {
+        amount - self.of(amount)
+    }
+}
+
+/// Spreads an amount over lines in proportion to their nets. The remainder
+/// of the rounding goes to the lines with the largest fractions, so the
+/// shares always add up to the amount.
+pub fn allocate(nets: &[i64], amount: i64) -> Vec<i64> {
+    let total: i64 = nets.iter().sum();
+    if total == 0 {
+        return vec![0; nets.len()];
+    }
+
+    let exact: Vec<f64> = nets
+        .iter()
+        .map(|net| amount as f64 * *net as f64 / total as f64)
+        .collect();
+    let mut shares: Vec<i64> = exact.iter().map(|share| share.floor() as i64).collect();
+    let mut order: Vec<usize> = (0..nets.len()).collect();
+    order.sort_by(|a, b| {
+        let fraction = |i: usize| exact[i] - shares[i] as f64;
+        fraction(*b).total_cmp(&fraction(*a))
+    });
+    let left = amount - shares.iter().sum::<i64>();
+    for i in order.into_iter().take(left.max(0) as usize) {
+        shares[i] += 1;
+    }
+    shares
+}
+
+/// The taxable amounts of lines given as their nets after the line
+/// discount, with the document discount spread over them.
+pub fn taxable_nets(nets: &[i64], discount: Discount) -> Vec<i64> {
+    let total = nets.iter().sum();
+    allocate(nets, discount.of(total))
+        .into_iter()
+        .zip(nets)
+        .map(|(share, net)| net - share)
+        .collect()
+}
+
+/// The tax components of a line as rate and compound flag, in the order they
+/// are levied. Lines without a known tax fall back to the percentage stored
+/// on the line, like on the printed documents.
+pub fn line_components(tax: i32, resolved: Option<&ResolvedTax>) -> Vec<(f64, bool)> {
+    match resolved {
+        Some(resolved) => resolved
+            .components
+            .iter()
+            .map(|component| (component.tax.rate, component.compound))
+            .collect(),
+        None => vec![(tax as f64 / 100.0, false)],
+    }
+}
+
+/// A line to total, with its tax components as rate and compound flag.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TotalsLine {
+    pub quantity: f64,
+    pub price: i32,
+    pub discount: Discount,
+    pub components: Vec<(f64, bool)>,
+}
+
+/// The amounts of a line in cents. The net is what is left after the line
+/// discount, and the tax is levied on the net less the line's share of the
+/// document discount.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineTotals {
+    pub amount: i64,
+    pub discount: i64,
+    pub net: i64,
+    pub document_discount: i64,
+    pub tax: i64,
+    pub gross: i64,
+}
+
+/// The bases and tax of everything levied at one rate.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RateTotals {
+    pub rate: f64,
+    pub base: i64,
+    pub amount: i64,
+}
+
+/// The totals of a document, with the lines in the order they were given and
+/// the rates in the order they first appear.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DocumentTotals {
+    pub lines: Vec<LineTotals>,
+    pub rates: Vec<RateTotals>,
+    /// The sum of the line nets, before the document discount.
+    pub line_total: i64,
+    pub discount: i64,
+    pub net: i64,
+    pub tax: i64,
+    pub gross: i64,
+}
+
+impl DocumentTotals {
+    /// Computes the totals of the lines and the document discount. The tax
+    /// of a line is rounded per component for display, while the document
+    /// tax is rounded once per rate. The line taxes can therefore add up to
+    /// a few cents more or less than the document tax, which is the amount
+    /// that is owed.
+    pub fn compute(lines: impl IntoIterator<Item = TotalsLine>, discount: Discount) -> Self {
+        let lines: Vec<TotalsLine> = lines.into_iter().collect();
+        let amounts: Vec<i64> = lines
+            .iter()
+            .map(|line| line_net(line.quantity, line.price))
+            .collect();
+        let nets: Vec<i64> = lines
+            .iter()
+            .zip(&amounts)
+            .map(|(line, amount)| line.discount.apply(*amount))
+            .collect();
+        let line_total: i64 = nets.iter().sum();
+        let document_discount = discount.of(line_total);
+
+        let mut line_totals = Vec::with_capacity(lines.len());
+        let mut bases = Vec::new();
+        let shares = allocate(&nets, document_discount);
+        for (((line, amount), net), share) in lines.iter().zip(amounts).zip(&nets).zip(shares) {
+            let taxable = net - share;
+            let mut tax = 0;
+            for ((rate, _), base) in line
+                .components
+                .iter()
+                .zip(component_bases(taxable, &line.components))
+            {
+                tax += (base as f64 * rate).round() as i64;
+                bases.push((base, *rate));
+            }
+            line_totals.push(LineTotals {
+                amount,
+                discount: amount - net,
+                net: *net,
+                document_discount: share,
+                tax,
+                gross: taxable + tax,
+            });
+        }
+
+        let rates = rate_totals(bases);
+        let net = line_total - document_discount;
+        let tax = rates.iter().map(|rate| rate.amount).sum();
+        Self {
+            lines: line_totals,
+            rates,
+            line_total,
+            discount: document_discount,
+            net,
+            tax,
+            gross: net + tax,
+        }
+    }
+}
+
+/// Groups bases given as amount and rate by rate. Like on the printed
+/// documents the tax is computed once per rate and not per line, so rounding
+/// differences of the lines don't add up. Lines with a tax group contribute
+/// one base per component.
+pub fn rate_totals(bases: impl IntoIterator<Item = (i64, f64)>) -> Vec<RateTotals> {
+    let mut rates: Vec<RateTotals> = Vec::new();
+    for (base, rate) in bases {
+        match rates.iter_mut().find(|totals| totals.rate == rate) {
+            Some(totals) => totals.base += base,
+            None => rates.push(RateTotals {
+                rate,
+                base,
+                amount: 0,
+            }),
+        }
+    }
+
+    for totals in rates.iter_mut() {
+        totals.amount = (totals.base as f64 * totals.rate).round() as i64;
+    }
+    rates
+}
+
+/// Sums the tax of bases given as amount and rate, rounded once per rate.
+pub fn tax_total(bases: impl IntoIterator<Item = (i64, f64)>) -> i64 {
+    rate_totals(bases).iter().map(|totals| totals.amount).sum()
+}
@@ -0,0 +1,10 @@
+/// Returns by how much a quantity in one unit is multiplied to get it in
+/// the other, e.g. 12 from boxes of 12 to pieces. Units only convert if
+/// they have the same base unit; `None` means they don't.
+pub fn conversion_factor(from: &entity::units::Model, to: &entity::units::Model) -> Option<f64> {
+    let base = |unit: &entity::units::Model| unit.base_unit_id.unwrap_or(unit.id);
+    if base(from) != base(to) {
+        return None;
+    }
+    Some(from.factor / to.factor)
+}
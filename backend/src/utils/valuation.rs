@@ -0,0 +1,132 @@
+use entity::sea_orm_active_enums::CostingMethod;
+
+use crate::{repositories::QUANTITY_TOLERANCE, utils::totals::allocate};
+
+/// What is left of a receipt in stock. The value is in cents.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OpenLayer {
+    pub id: i32,
+    pub quantity: f64,
+    pub value: i64,
+}
+
+/// The part of an open layer an issue takes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Consumption {
+    pub layer_id: i32,
+    pub quantity: f64,
+    pub value: i64,
+}
+
+/// Values an issue of the quantity from the open layers, given oldest
+/// first. FIFO takes from the oldest layers first, the average method the
+/// same share of every layer, which values the issue at the average cost of
+/// the stock. Whatever the layers can't cover is valued at the fallback
+/// cost per unit. Returns what is taken from each layer and the value of
+/// the whole issue.
+pub fn consume(
+    layers: &[OpenLayer],
+    quantity: f64,
+    method: &CostingMethod,
+    fallback_unit_cost: f64,
+) -> (Vec<Consumption>, i64) {
+    let in_stock: f64 = layers.iter().map(|layer| layer.quantity).sum();
+    let consumptions = if quantity >= in_stock - QUANTITY_TOLERANCE {
+        layers.iter().map(consume_all).collect()
+    } else {
+        match method {
+            CostingMethod::Fifo => fifo(layers, quantity),
+            CostingMethod::Average => average(layers, quantity / in_stock),
+        }
+    };
+
+    let short = (quantity - in_stock).max(0.0);
+    let value = consumptions.iter().map(|c| c.value).sum::<i64>()
+        + (short * fallback_unit_cost).round() as i64;
+    (consumptions, value)
+}
+
+fn consume_all(layer: &OpenLayer) -> Consumption {
+    Consumption {
+        layer_id: layer.id,
+        quantity: layer.quantity,
+        value: layer.value,
+    }
+}
+
+fn fifo(layers: &[OpenLayer], quantity: f64) -> Vec<Consumption> {
+    let mut left = quantity;
+    let mut consumptions = Vec::new();
+    for layer in layers {
+        if left <= QUANTITY_TOLERANCE {
+            break;
+        }
+        if left >= layer.quantity - QUANTITY_TOLERANCE {
+            consumptions.push(consume_all(layer));
+            left -= layer.quantity;
+            continue;
+        }
+        let value = (layer.value as f64 * left / layer.quantity).round() as i64;
+        consumptions.push(Consumption {
+            layer_id: layer.id,
+            quantity: left,
+            value,
+        });
+        left = 0.0;
+    }
+    consumptions
+}
+
+fn average(layers: &[OpenLayer], share: f64) -> Vec<Consumption> {
+    // The value is rounded once for the whole issue and then spread over
+    // the layers, so the stock keeps its average cost to the cent.
+    let values: Vec<i64> = layers.iter().map(|layer| layer.value).collect();
+    let total = (values.iter().sum::<i64>() as f64 * share).round() as i64;
+    layers
+        .iter()
+        .zip(allocate(&values, total))
+        .map(|(layer, value)| Consumption {
+            layer_id: layer.id,
+            quantity: layer.quantity * share,
+            value,
+        })
+        .collect()
+}
+
+/// Settles the shortfalls of earlier issues beyond the stock, given oldest
+/// first as layers with a negative quantity and value, with a receipt of
+/// the quantity and value. Each shortfall is settled at the cost it was
+/// issued at. Returns what is settled of each shortfall and the part of the
+/// receipt's value that goes into it.
+pub fn settle(shortfalls: &[OpenLayer], quantity: f64, value: i64) -> (Vec<Consumption>, i64) {
+    let mut left = quantity;
+    let mut settlements = Vec::new();
+    for shortfall in shortfalls {
+        if left <= QUANTITY_TOLERANCE {
+            break;
+        }
+        let settled = if left >= -shortfall.quantity - QUANTITY_TOLERANCE {
+            Consumption {
+                layer_id: shortfall.id,
+                quantity: -shortfall.quantity,
+                value: -shortfall.value,
+            }
+        } else {
+            Consumption {
+                layer_id: shortfall.id,
+                quantity: left,
+                value: (shortfall.value as f64 * left / shortfall.quantity).round() as i64,
+            }
+        };
+        left -= settled.quantity;
+        settlements.push(settled);
+    }
+
+    let settled_quantity = quantity - left.max(0.0);
+    let settled_value = if left <= QUANTITY_TOLERANCE {
+        value
+    } else {
+        (value as f64 * settled_quantity / quantity).round() as i64
+    };
+    (settlements, settled_value)
+}
@@ -0,0 +1,55 @@
+/// An attribute of a product with its values, both in position order.
+pub type Attribute = (
+    entity::product_attributes::Model,
+    Vec<entity::product_attribute_values::Model>,
+);
+
+/// Combines each value of every attribute with each value of the others,
+/// in the order of the attributes and their values. A product without
+/// attributes or with an attribute without values has no combinations.
+pub fn combinations(
+    attributes: &[Attribute],
+) -> Vec<Vec<&entity::product_attribute_values::Model>> {
+    if attributes.is_empty() {
+        return Vec::new();
+    }
+
+    let mut combinations = vec![Vec::new()];
+    for (_, values) in attributes {
+        combinations = combinations
+            .into_iter()
+            .flat_map(|combination| {
+                values.iter().map(move |value| {
+                    let mut combination = combination.clone();
+                    combination.push(value);
+                    combination
+                })
+            })
+            .collect();
+    }
+    combinations
+}
+
+/// Names a variant after a template. `{product}` is replaced with the name
+/// of the product, `{values}` with the names of the combination's values
+/// separated by slashes, and an attribute's name in braces like `{Size}`
+/// with its value.
+pub fn variant_name(
+    template: &str,
+    product: &str,
+    attributes: &[Attribute],
+    combination: &[&entity::product_attribute_values::Model],
+) -> String {
+    let values: Vec<&str> = combination
+        .iter()
+        .map(|value| value.name.as_str())
+        .collect();
+
+    let mut name = template
+        .replace("{product}", product)
+        .replace("{values}", &values.join(" / "));
+    for ((attribute, _), value) in attributes.iter().zip(&values) {
+        name = name.replace(&format!("{{{}}}", attribute.name), value);
+    }
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,9 @@
+/// Brings a VAT identification number into the stored form: upper case with
+/// spaces, dots and dashes removed, e.g. `de 123.456.789` becomes `DE123456789`.
+pub fn normalize_vat_id(vat_id: &str) -> String {
+    vat_id
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}